@@ -0,0 +1,249 @@
+use crate::reference_tables;
+
+/// The scores used to align two sequences, see [`global`] and [`local`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scoring {
+    /// The score for two identical residues
+    pub matched: isize,
+    /// The score for two different residues
+    pub mismatched: isize,
+    /// The score for a residue aligned to a gap
+    pub gap: isize,
+}
+
+impl Scoring {
+    /// Scoring which maximises the number of identical residues, with free mismatches and gaps
+    pub const IDENTITY: Self = Self::new(1, 0, 0);
+
+    /// Create a new scoring scheme
+    pub const fn new(matched: isize, mismatched: isize, gap: isize) -> Self {
+        Scoring {
+            matched,
+            mismatched,
+            gap,
+        }
+    }
+}
+
+impl Default for Scoring {
+    /// Match 2, mismatch -1, and gap -2
+    fn default() -> Self {
+        Self::new(2, -1, -2)
+    }
+}
+
+/// The alignment of two sequences, see [`global`] and [`local`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alignment {
+    /// The score of the alignment
+    pub score: isize,
+    /// The aligned positions as indices into the first and second sequence, with `None` for a
+    /// gap. For local alignments only the aligned region is included.
+    pub pairs: Vec<(Option<usize>, Option<usize>)>,
+    /// The number of aligned positions with identical residues
+    pub identical: usize,
+}
+
+impl Alignment {
+    /// Get the positions where a residue of the first sequence is aligned to a residue of the
+    /// second sequence, as indices into both sequences
+    pub fn aligned(&self) -> impl DoubleEndedIterator<Item = (usize, usize)> + '_ {
+        self.pairs.iter().filter_map(|pair| match pair {
+            (Some(a), Some(b)) => Some((*a, *b)),
+            _ => None,
+        })
+    }
+
+    /// Get the fraction of positions of the alignment (including gaps) with identical residues
+    #[allow(clippy::cast_precision_loss)]
+    pub fn identity(&self) -> f64 {
+        if self.pairs.is_empty() {
+            0.0
+        } else {
+            self.identical as f64 / self.pairs.len() as f64
+        }
+    }
+}
+
+/// Get the one letter code for the given residue name, for the standard amino acids and
+/// nucleotides (`ALA` gives `A`, `DG` gives `G`). Names of a single letter are their own code.
+pub fn one_letter_code(name: impl AsRef<str>) -> Option<char> {
+    let name = name.as_ref().trim();
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Some(c.to_ascii_uppercase()),
+        _ => reference_tables::one_letter_code(&name.to_ascii_uppercase()),
+    }
+}
+
+/// Globally align the two sequences (Needleman–Wunsch), aligning them over their full length.
+/// The sequences can be given as residue names (`MET`), one letter codes (`M`), or a mix of
+/// both, names which are not standard residues are only identical to the same name.
+/// ```rust
+/// use pdbtbx::alignment::{self, Scoring};
+/// let a = ["MET", "GLN", "ILE", "PHE", "VAL"];
+/// let b: Vec<String> = "MQFV".chars().map(String::from).collect();
+/// let alignment = alignment::global(&a, &b, Scoring::default());
+/// assert_eq!(alignment.identical, 4);
+/// assert_eq!(alignment.pairs[2], (Some(2), None));
+/// ```
+pub fn global(a: &[impl AsRef<str>], b: &[impl AsRef<str>], scoring: Scoring) -> Alignment {
+    align(a, b, scoring, false)
+}
+
+/// Locally align the two sequences (Smith–Waterman), finding the highest scoring region of
+/// both sequences. See [`global`] for the residue names.
+/// ```rust
+/// use pdbtbx::alignment::{self, Scoring};
+/// let a = ["GLY", "GLY", "MET", "GLN", "ILE", "GLY"];
+/// let b = ["TRP", "MET", "GLN", "ILE", "TRP"];
+/// let alignment = alignment::local(&a, &b, Scoring::default());
+/// assert_eq!(alignment.aligned().collect::<Vec<_>>(), vec![(2, 1), (3, 2), (4, 3)]);
+/// ```
+pub fn local(a: &[impl AsRef<str>], b: &[impl AsRef<str>], scoring: Scoring) -> Alignment {
+    align(a, b, scoring, true)
+}
+
+/// The cell a cell in the dynamic programming matrix was reached from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    /// The start of the alignment
+    Stop,
+    /// Both residues aligned
+    Diagonal,
+    /// The residue of the first sequence aligned to a gap
+    Up,
+    /// The residue of the second sequence aligned to a gap
+    Left,
+}
+
+/// Align the two sequences, globally or locally
+#[allow(clippy::cast_possible_wrap)]
+fn align(a: &[impl AsRef<str>], b: &[impl AsRef<str>], scoring: Scoring, local: bool) -> Alignment {
+    let normalise = |name: &str| {
+        one_letter_code(name).map_or_else(|| name.trim().to_ascii_uppercase(), String::from)
+    };
+    let a: Vec<String> = a.iter().map(|name| normalise(name.as_ref())).collect();
+    let b: Vec<String> = b.iter().map(|name| normalise(name.as_ref())).collect();
+    let width = b.len() + 1;
+    let mut scores = vec![0; (a.len() + 1) * width];
+    let mut steps = vec![Step::Stop; (a.len() + 1) * width];
+    if !local {
+        for i in 1..=a.len() {
+            scores[i * width] = i as isize * scoring.gap;
+            steps[i * width] = Step::Up;
+        }
+        for j in 1..width {
+            scores[j] = j as isize * scoring.gap;
+            steps[j] = Step::Left;
+        }
+    }
+
+    let mut best = (0, 0, 0);
+    for i in 1..=a.len() {
+        for j in 1..width {
+            let substitution = if a[i - 1] == b[j - 1] {
+                scoring.matched
+            } else {
+                scoring.mismatched
+            };
+            let mut cell = (
+                scores[(i - 1) * width + j - 1] + substitution,
+                Step::Diagonal,
+            );
+            let up = scores[(i - 1) * width + j] + scoring.gap;
+            if up > cell.0 {
+                cell = (up, Step::Up);
+            }
+            let left = scores[i * width + j - 1] + scoring.gap;
+            if left > cell.0 {
+                cell = (left, Step::Left);
+            }
+            if local && cell.0 <= 0 {
+                cell = (0, Step::Stop);
+            }
+            scores[i * width + j] = cell.0;
+            steps[i * width + j] = cell.1;
+            if cell.0 > best.0 {
+                best = (cell.0, i, j);
+            }
+        }
+    }
+
+    let (mut i, mut j) = if local {
+        (best.1, best.2)
+    } else {
+        (a.len(), b.len())
+    };
+    let score = scores[i * width + j];
+    let mut pairs = Vec::new();
+    let mut identical = 0;
+    loop {
+        match steps[i * width + j] {
+            Step::Stop => break,
+            Step::Diagonal => {
+                i -= 1;
+                j -= 1;
+                if a[i] == b[j] {
+                    identical += 1;
+                }
+                pairs.push((Some(i), Some(j)));
+            }
+            Step::Up => {
+                i -= 1;
+                pairs.push((Some(i), None));
+            }
+            Step::Left => {
+                j -= 1;
+                pairs.push((None, Some(j)));
+            }
+        }
+    }
+    pairs.reverse();
+    Alignment {
+        score,
+        pairs,
+        identical,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes() {
+        assert_eq!(one_letter_code("ALA"), Some('A'));
+        assert_eq!(one_letter_code("a"), Some('A'));
+        assert_eq!(one_letter_code("DT"), Some('T'));
+        assert_eq!(one_letter_code("HOH"), None);
+    }
+
+    #[test]
+    fn global_gaps() {
+        let alignment = global(&["A", "C", "D"], &["ALA", "ASP"], Scoring::default());
+        assert_eq!(
+            alignment.pairs,
+            vec![(Some(0), Some(0)), (Some(1), None), (Some(2), Some(1))]
+        );
+        assert_eq!(alignment.score, 2);
+        assert!((alignment.identity() - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty() {
+        let none: &[&str] = &[];
+        let alignment = global(&["A", "C"], none, Scoring::default());
+        assert_eq!(alignment.pairs, vec![(Some(0), None), (Some(1), None)]);
+        assert_eq!(alignment.score, -4);
+        let alignment = local(&["A"], &["C"], Scoring::default());
+        assert!(alignment.pairs.is_empty());
+        assert!(alignment.identity().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn unknown_names() {
+        let alignment = global(&["MSE", "HOH"], &["MSE", "WAT"], Scoring::IDENTITY);
+        assert_eq!(alignment.identical, 1);
+    }
+}
@@ -0,0 +1,305 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::geometry::grid_cell;
+use crate::{Atom, Chain, Conformer, Model, Residue, PDB};
+
+/// The backbone atoms every amino acid is expected to have
+pub const AMINO_ACID_BACKBONE: [&str; 4] = ["N", "CA", "C", "O"];
+
+/// The tolerance on the occupancies, so rounding in the files is not reported as an anomaly
+const OCCUPANCY_TOLERANCE: f64 = 0.01;
+
+/// Quality metrics of a structure, see [`statistics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics<'a> {
+    /// The counts for every Model, in the order of the Models
+    pub models: Vec<ModelStatistics>,
+    /// The B factors of all atoms of the first Model, `None` if there are no atoms
+    pub b_factors: Option<BFactorStatistics>,
+    /// The atoms of the first Model with anomalous occupancies
+    pub occupancy_anomalies: Vec<OccupancyAnomaly<'a>>,
+    /// The amino acids of the first Model which miss any backbone atoms
+    pub missing_backbone: Vec<MissingBackbone<'a>>,
+    /// The pairs of atoms of the first Model which are closer than the sum of their covalent
+    /// radii, while not being bonded, sorted from the largest to the smallest overlap
+    pub clashes: Vec<CovalentClash<'a>>,
+}
+
+impl Statistics<'_> {
+    /// Check if no anomalies are found: no anomalous occupancies, no missing backbone atoms, and
+    /// no clashes.
+    pub fn is_clean(&self) -> bool {
+        self.occupancy_anomalies.is_empty()
+            && self.missing_backbone.is_empty()
+            && self.clashes.is_empty()
+    }
+}
+
+/// The number of atoms, residues, and chains in a Model, see [`Statistics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelStatistics {
+    /// The serial number of the Model
+    pub serial_number: usize,
+    /// The number of Atoms, counting all conformers
+    pub atom_count: usize,
+    /// The number of Residues
+    pub residue_count: usize,
+    /// The number of Chains
+    pub chain_count: usize,
+}
+
+/// The distribution of the B factors of a set of atoms in Aͦ², see [`Statistics`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BFactorStatistics {
+    /// The mean B factor
+    pub mean: f64,
+    /// The lowest B factor
+    pub min: f64,
+    /// The highest B factor
+    pub max: f64,
+}
+
+/// The kind of anomaly found in the occupancy of an atom, see [`OccupancyAnomaly`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OccupancyAnomalyKind {
+    /// The occupancy is zero, so the atom was not observed
+    Zero,
+    /// The occupancy is outside the range 0.0 to 1.0
+    OutOfRange,
+    /// The occupancies of the alternative positions of the atom add up to more than 1.0
+    OverOccupied,
+}
+
+/// An atom with an anomalous occupancy, see [`Statistics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccupancyAnomaly<'a> {
+    /// The identifier of the Chain
+    pub chain: &'a str,
+    /// The Residue containing the atom
+    pub residue: &'a Residue,
+    /// The atom, for over occupied atoms the first alternative position
+    pub atom: &'a Atom,
+    /// The occupancy, for over occupied atoms the sum over all alternative positions
+    pub occupancy: f64,
+    /// The kind of anomaly
+    pub kind: OccupancyAnomalyKind,
+}
+
+/// An amino acid missing backbone atoms, see [`Statistics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingBackbone<'a> {
+    /// The identifier of the Chain
+    pub chain: &'a str,
+    /// The Residue
+    pub residue: &'a Residue,
+    /// The names of the missing backbone atoms, see [`AMINO_ACID_BACKBONE`]
+    pub missing: Vec<&'static str>,
+}
+
+/// Two atoms which are closer than the sum of their covalent radii, while not being bonded, see
+/// [`Statistics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CovalentClash<'a> {
+    /// The first atom
+    pub atom_a: &'a Atom,
+    /// The second atom
+    pub atom_b: &'a Atom,
+    /// The distance between the atoms in Aͦ
+    pub distance: f64,
+    /// The sum of the covalent radii of the atoms in Aͦ
+    pub covalent_distance: f64,
+}
+
+impl CovalentClash<'_> {
+    /// The overlap of the covalent spheres of the atoms in Aͦ
+    pub fn overlap(&self) -> f64 {
+        self.covalent_distance - self.distance
+    }
+}
+
+/// Calculate the quality metrics of the given PDB. The atom, residue, and chain counts are given
+/// for every Model, all other metrics are determined on the first Model only:
+/// * The mean, lowest, and highest B factor.
+/// * The atoms with an occupancy of zero or outside the range 0.0 to 1.0, and the atoms of which
+///   the occupancies of the alternative positions add up to more than 1.0.
+/// * The amino acids which miss any of the backbone atoms (N, CA, C, and O) in their first
+///   conformer.
+/// * The atoms closer than the sum of their covalent radii, see [`crate::Element::atomic_radius`],
+///   using only the first conformer of every residue. Atoms in the same residue or in
+///   consecutive residues of a chain, and atoms bonded by CONECT, LINK, or SSBOND records are
+///   not counted, neither are atoms without an element.
+///
+/// For a report with `PDBError`s see [`crate::validate_pdb`], for clashes based on the van der
+/// Waals radii see [`crate::geometry::clashes`].
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let statistics = analysis::statistics(&pdb);
+/// assert_eq!(statistics.models[0].chain_count, 1);
+/// println!("Mean B factor {:.2}", statistics.b_factors.unwrap().mean);
+/// ```
+pub fn statistics(pdb: &PDB) -> Statistics<'_> {
+    let chains: Vec<&Chain> = pdb.models().take(1).flat_map(Model::chains).collect();
+    Statistics {
+        models: pdb
+            .models()
+            .map(|model| ModelStatistics {
+                serial_number: model.serial_number(),
+                atom_count: model.atom_count(),
+                residue_count: model.residue_count(),
+                chain_count: model.chain_count(),
+            })
+            .collect(),
+        b_factors: b_factor_statistics(chains.iter().flat_map(|chain| chain.atoms())),
+        occupancy_anomalies: occupancy_anomalies(&chains),
+        missing_backbone: missing_backbone(&chains),
+        clashes: covalent_clashes(pdb, &chains),
+    }
+}
+
+/// Get the mean, lowest, and highest B factor of the given atoms
+fn b_factor_statistics<'a>(atoms: impl Iterator<Item = &'a Atom>) -> Option<BFactorStatistics> {
+    let mut count = 0;
+    let mut result = BFactorStatistics {
+        mean: 0.0,
+        min: f64::INFINITY,
+        max: f64::NEG_INFINITY,
+    };
+    for b_factor in atoms.map(Atom::b_factor) {
+        count += 1;
+        result.mean += b_factor;
+        result.min = result.min.min(b_factor);
+        result.max = result.max.max(b_factor);
+    }
+    #[allow(clippy::cast_precision_loss)]
+    (count > 0).then(|| BFactorStatistics {
+        mean: result.mean / count as f64,
+        ..result
+    })
+}
+
+/// Find the atoms with anomalous occupancies in the given chains
+fn occupancy_anomalies<'a>(chains: &[&'a Chain]) -> Vec<OccupancyAnomaly<'a>> {
+    let mut anomalies = Vec::new();
+    for chain in chains {
+        for residue in chain.residues() {
+            let anomaly =
+                |atom: &'a Atom, occupancy: f64, kind: OccupancyAnomalyKind| OccupancyAnomaly {
+                    chain: chain.id(),
+                    residue,
+                    atom,
+                    occupancy,
+                    kind,
+                };
+            // The atoms by name, with the sum of the occupancies of their alternative positions
+            let mut positions: Vec<(&'a Atom, f64)> = Vec::new();
+            for atom in residue.conformers().flat_map(Conformer::atoms) {
+                let occupancy = atom.occupancy();
+                if occupancy == 0.0 {
+                    anomalies.push(anomaly(atom, occupancy, OccupancyAnomalyKind::Zero));
+                } else if !(0.0..=1.0).contains(&occupancy) {
+                    anomalies.push(anomaly(atom, occupancy, OccupancyAnomalyKind::OutOfRange));
+                }
+                match positions.iter_mut().find(|(a, _)| a.name() == atom.name()) {
+                    Some((_, total)) => *total += occupancy,
+                    None => positions.push((atom, occupancy)),
+                }
+            }
+            if residue.conformer_count() > 1 {
+                anomalies.extend(
+                    positions
+                        .into_iter()
+                        .filter(|(_, total)| *total > 1.0 + OCCUPANCY_TOLERANCE)
+                        .map(|(atom, total)| {
+                            anomaly(atom, total, OccupancyAnomalyKind::OverOccupied)
+                        }),
+                );
+            }
+        }
+    }
+    anomalies
+}
+
+/// Find the amino acids in the given chains which miss backbone atoms in their first conformer
+fn missing_backbone<'a>(chains: &[&'a Chain]) -> Vec<MissingBackbone<'a>> {
+    chains
+        .iter()
+        .flat_map(|chain| chain.residues().map(move |residue| (chain.id(), residue)))
+        .filter_map(|(chain, residue)| {
+            let conformer = residue.conformer(0).filter(|c| c.is_amino_acid())?;
+            let missing: Vec<&'static str> = AMINO_ACID_BACKBONE
+                .iter()
+                .copied()
+                .filter(|name| !conformer.atoms().any(|atom| atom.name() == *name))
+                .collect();
+            (!missing.is_empty()).then_some(MissingBackbone {
+                chain,
+                residue,
+                missing,
+            })
+        })
+        .collect()
+}
+
+/// Find the atoms in the given chains closer than the sum of their covalent radii, see
+/// [`statistics`] for the pairs which are excluded
+fn covalent_clashes<'a>(pdb: &PDB, chains: &[&'a Chain]) -> Vec<CovalentClash<'a>> {
+    // Every atom with its covalent radius and the index of its chain and residue
+    let atoms: Vec<(&'a Atom, f64, (usize, usize))> = chains
+        .iter()
+        .enumerate()
+        .flat_map(|(chain_index, chain)| {
+            chain
+                .residues()
+                .enumerate()
+                .filter_map(|(index, residue)| Some((index, residue.conformer(0)?)))
+                .flat_map(move |(index, conformer)| {
+                    conformer.atoms().filter_map(move |atom| {
+                        let radius = atom.element()?.atomic_radius().covalent_single;
+                        Some((atom, radius, (chain_index, index)))
+                    })
+                })
+        })
+        .collect();
+    let bonds: FxHashSet<(usize, usize)> = pdb
+        .bond_counters()
+        .flat_map(|(a, b, _)| [(a, b), (b, a)])
+        .collect();
+    let max_radius = atoms.iter().map(|(_, r, _)| *r).fold(0.0, f64::max);
+    let cell = grid_cell(2.0 * max_radius);
+
+    // Place all atoms in a grid to quickly find the neighbours of each atom
+    let mut grid: FxHashMap<(i64, i64, i64), Vec<usize>> = FxHashMap::default();
+    for (index, (atom, _, _)) in atoms.iter().enumerate() {
+        grid.entry(cell(atom.pos())).or_default().push(index);
+    }
+
+    let mut clashes = Vec::new();
+    for (i, (a, radius_a, (chain_a, residue_a))) in atoms.iter().enumerate() {
+        let (x, y, z) = cell(a.pos());
+        let neighbours = (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter_map(|(dx, dy, dz)| grid.get(&(x + dx, y + dy, z + dz)))
+            .flatten()
+            .filter(|j| **j > i);
+        for (b, radius_b, (chain_b, residue_b)) in neighbours.map(|j| &atoms[*j]) {
+            if chain_a == chain_b && residue_a.abs_diff(*residue_b) <= 1
+                || bonds.contains(&(a.counter(), b.counter()))
+            {
+                continue;
+            }
+            let distance = a.distance(b);
+            if distance < radius_a + radius_b {
+                clashes.push(CovalentClash {
+                    atom_a: a,
+                    atom_b: b,
+                    distance,
+                    covalent_distance: radius_a + radius_b,
+                });
+            }
+        }
+    }
+    clashes.sort_by(|a, b| b.overlap().total_cmp(&a.overlap()));
+    clashes
+}
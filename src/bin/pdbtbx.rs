@@ -0,0 +1,93 @@
+//! A small command line companion to pdbtbx, to convert, validate, and summarise structure files.
+//! Build it with `cargo install pdbtbx --features cli`.
+
+use pdbtbx::*;
+use std::process::ExitCode;
+
+/// The usage message shown on invalid invocations
+const USAGE: &str = "Usage:
+    pdbtbx convert <input> <output> [--level strict|medium|loose]
+    pdbtbx validate <input> [--level strict|medium|loose]
+    pdbtbx summary <input> [--level strict|medium|loose]
+
+The formats (pdb, cif, optionally gzipped) are determined based on the file extensions.";
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let mut level = StrictnessLevel::Medium;
+    if let Some(index) = args.iter().position(|a| a == "--level") {
+        match args.get(index + 1).map(|l| l.parse()) {
+            Some(Ok(l)) => level = l,
+            Some(Err(e)) => return fail(&e),
+            None => return fail("Missing value for --level"),
+        }
+        args.drain(index..=index + 1);
+    }
+    let mut options = ReadOptions::new();
+    options.set_level(level);
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["convert", input, output] => match convert(input, output, &options) {
+            Ok(errors) => report(&errors, ExitCode::SUCCESS),
+            Err(errors) => report(&errors, ExitCode::FAILURE),
+        },
+        ["validate", input] => match options.read(input) {
+            Ok((_, errors)) => {
+                let code = report(&errors, ExitCode::SUCCESS);
+                println!("{input}: valid with {} warning(s)", errors.len());
+                code
+            }
+            Err(errors) => report(&errors, ExitCode::FAILURE),
+        },
+        ["summary", input] => match options.read(input) {
+            Ok((pdb, errors)) => {
+                summary(&pdb);
+                report(&errors, ExitCode::SUCCESS)
+            }
+            Err(errors) => report(&errors, ExitCode::FAILURE),
+        },
+        _ => fail(USAGE),
+    }
+}
+
+/// Print the given message to stderr and give a failing exit code
+fn fail(message: &str) -> ExitCode {
+    eprintln!("{message}");
+    ExitCode::FAILURE
+}
+
+/// Print all errors to stderr and return the given exit code
+fn report(errors: &[PDBError], code: ExitCode) -> ExitCode {
+    for error in errors {
+        eprintln!("{error}");
+    }
+    code
+}
+
+/// Print a summary of the given structure to stdout
+fn summary(pdb: &PDB) {
+    println!("Identifier: {}", pdb.identifier.as_deref().unwrap_or("-"));
+    println!("Models:     {}", pdb.model_count());
+    println!("Chains:     {}", pdb.chain_count());
+    println!("Residues:   {}", pdb.residue_count());
+    println!("Atoms:      {}", pdb.atom_count());
+    if let Some(unit_cell) = &pdb.unit_cell {
+        let (a, b, c) = unit_cell.size();
+        println!("Unit cell:  {a:.3} {b:.3} {c:.3}");
+    }
+    if let Some(symmetry) = &pdb.symmetry {
+        println!("Symmetry:   {}", symmetry.herman_mauguin_symbol());
+    }
+    for chain in pdb.chains() {
+        println!(
+            "Chain {:2}    {} residues",
+            chain.id(),
+            chain.residue_count()
+        );
+    }
+}
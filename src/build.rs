@@ -0,0 +1,614 @@
+use crate::geometry::{cross, dihedral, dot};
+use crate::{reference_tables, Atom, Chain, Conformer, Model, Residue, PDB};
+
+/// The conformation of the backbone of a peptide built with [`peptide`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PeptideConformation {
+    /// An extended chain as in a beta strand, with phi of -120 and psi of 130 degrees. A fully
+    /// extended chain (phi and psi of 180 degrees) can be built with [`Self::Angles`], but that
+    /// strains the side chains.
+    Extended,
+    /// A right handed alpha helix, with phi of -57 and psi of -47 degrees
+    AlphaHelix,
+    /// The given phi and psi angles in degrees for every residue
+    Angles(f64, f64),
+}
+
+impl PeptideConformation {
+    /// Get the phi and psi angles in degrees of this conformation
+    pub fn angles(self) -> (f64, f64) {
+        match self {
+            PeptideConformation::Extended => (-120.0, 130.0),
+            PeptideConformation::AlphaHelix => (-57.0, -47.0),
+            PeptideConformation::Angles(phi, psi) => (phi, psi),
+        }
+    }
+}
+
+/// The ideal lengths of the backbone bonds in Aͦ, after Engh and Huber (1991)
+const N_CA: f64 = 1.458;
+/// See [`N_CA`]
+const CA_C: f64 = 1.525;
+/// See [`N_CA`]
+const C_N: f64 = 1.329;
+/// See [`N_CA`]
+const C_O: f64 = 1.231;
+/// The ideal backbone bond angles in degrees, after Engh and Huber (1991)
+const N_CA_C: f64 = 111.2;
+/// See [`N_CA_C`]
+const CA_C_N: f64 = 116.2;
+/// See [`N_CA_C`]
+const C_N_CA: f64 = 121.7;
+/// See [`N_CA_C`]
+const CA_C_O: f64 = 120.5;
+/// See [`N_CA_C`]
+const N_C_O: f64 = 122.7;
+/// The ideal length of the bond between the carbonyl and methyl carbons of an acetyl group in Aͦ
+const C_CH3: f64 = 1.52;
+/// The ideal length of bonds from nitrogen to hydrogen in Aͦ
+const N_H: f64 = 1.01;
+
+/// The internal coordinates of the side chain atoms of the standard amino acids: the name of the
+/// atom, the names of the three atoms it is placed relative to, the bond length in Aͦ, the bond
+/// angle in degrees, and the torsion angle in degrees. The side chain torsions are set to the
+/// most common rotamers.
+#[allow(clippy::type_complexity)]
+const SIDE_CHAINS: &[(&str, &[(&str, [&str; 3], f64, f64, f64)])] = &[
+    ("GLY", &[]),
+    ("ALA", &[("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5)]),
+    (
+        "SER",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("OG", ["N", "CA", "CB"], 1.417, 110.8, 60.0),
+        ],
+    ),
+    (
+        "CYS",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("SG", ["N", "CA", "CB"], 1.808, 113.8, -60.0),
+        ],
+    ),
+    (
+        "VAL",
+        &[
+            ("CB", ["C", "N", "CA"], 1.54, 110.5, -122.5),
+            ("CG1", ["N", "CA", "CB"], 1.527, 110.7, 180.0),
+            ("CG2", ["N", "CA", "CB"], 1.527, 110.4, -60.0),
+        ],
+    ),
+    (
+        "THR",
+        &[
+            ("CB", ["C", "N", "CA"], 1.54, 110.5, -122.5),
+            ("OG1", ["N", "CA", "CB"], 1.433, 109.2, 60.0),
+            ("CG2", ["N", "CA", "CB"], 1.521, 111.1, -60.0),
+        ],
+    ),
+    (
+        "ILE",
+        &[
+            ("CB", ["C", "N", "CA"], 1.54, 110.5, -122.5),
+            ("CG1", ["N", "CA", "CB"], 1.527, 110.7, -60.0),
+            ("CG2", ["N", "CA", "CB"], 1.527, 110.4, 180.0),
+            ("CD1", ["CA", "CB", "CG1"], 1.52, 113.97, 180.0),
+        ],
+    ),
+    (
+        "LEU",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.53, 116.1, -60.0),
+            ("CD1", ["CA", "CB", "CG"], 1.524, 110.5, 180.0),
+            ("CD2", ["CA", "CB", "CG"], 1.525, 110.5, -60.0),
+        ],
+    ),
+    (
+        "MET",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.52, 114.0, -60.0),
+            ("SD", ["CA", "CB", "CG"], 1.81, 112.7, 180.0),
+            ("CE", ["CB", "CG", "SD"], 1.79, 100.8, -70.0),
+        ],
+    ),
+    (
+        "LYS",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.52, 113.8, -60.0),
+            ("CD", ["CA", "CB", "CG"], 1.52, 111.8, 180.0),
+            ("CE", ["CB", "CG", "CD"], 1.52, 111.7, 180.0),
+            ("NZ", ["CG", "CD", "CE"], 1.49, 111.9, 180.0),
+        ],
+    ),
+    (
+        "ARG",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.52, 113.8, -60.0),
+            ("CD", ["CA", "CB", "CG"], 1.52, 111.8, 180.0),
+            ("NE", ["CB", "CG", "CD"], 1.46, 111.7, 180.0),
+            ("CZ", ["CG", "CD", "NE"], 1.33, 124.8, 180.0),
+            ("NH1", ["CD", "NE", "CZ"], 1.33, 120.0, 0.0),
+            ("NH2", ["CD", "NE", "CZ"], 1.33, 120.0, 180.0),
+        ],
+    ),
+    (
+        "ASP",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.52, 113.1, -60.0),
+            ("OD1", ["CA", "CB", "CG"], 1.25, 119.2, -30.0),
+            ("OD2", ["CA", "CB", "CG"], 1.25, 118.2, 150.0),
+        ],
+    ),
+    (
+        "ASN",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.52, 112.6, -60.0),
+            ("OD1", ["CA", "CB", "CG"], 1.23, 120.8, -30.0),
+            ("ND2", ["CA", "CB", "CG"], 1.33, 116.4, 150.0),
+        ],
+    ),
+    (
+        "GLU",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.52, 113.8, -60.0),
+            ("CD", ["CA", "CB", "CG"], 1.52, 113.3, 180.0),
+            ("OE1", ["CB", "CG", "CD"], 1.25, 119.0, 0.0),
+            ("OE2", ["CB", "CG", "CD"], 1.25, 118.0, 180.0),
+        ],
+    ),
+    (
+        "GLN",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.52, 113.8, -60.0),
+            ("CD", ["CA", "CB", "CG"], 1.52, 112.6, 180.0),
+            ("OE1", ["CB", "CG", "CD"], 1.23, 120.9, 0.0),
+            ("NE2", ["CB", "CG", "CD"], 1.33, 116.5, 180.0),
+        ],
+    ),
+    (
+        "HIS",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.50, 113.7, -60.0),
+            ("ND1", ["CA", "CB", "CG"], 1.38, 122.7, -90.0),
+            ("CD2", ["CA", "CB", "CG"], 1.36, 131.0, 90.0),
+            ("CE1", ["CB", "CG", "ND1"], 1.32, 108.5, 180.0),
+            ("NE2", ["CB", "CG", "CD2"], 1.37, 107.0, 180.0),
+        ],
+    ),
+    (
+        "PHE",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.50, 113.85, -60.0),
+            ("CD1", ["CA", "CB", "CG"], 1.39, 120.0, 90.0),
+            ("CD2", ["CA", "CB", "CG"], 1.39, 120.0, -90.0),
+            ("CE1", ["CB", "CG", "CD1"], 1.39, 120.0, 180.0),
+            ("CE2", ["CB", "CG", "CD2"], 1.39, 120.0, 180.0),
+            ("CZ", ["CG", "CD1", "CE1"], 1.39, 120.0, 0.0),
+        ],
+    ),
+    (
+        "TYR",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.51, 113.8, -60.0),
+            ("CD1", ["CA", "CB", "CG"], 1.39, 120.0, 90.0),
+            ("CD2", ["CA", "CB", "CG"], 1.39, 120.0, -90.0),
+            ("CE1", ["CB", "CG", "CD1"], 1.39, 120.0, 180.0),
+            ("CE2", ["CB", "CG", "CD2"], 1.39, 120.0, 180.0),
+            ("CZ", ["CG", "CD1", "CE1"], 1.39, 120.0, 0.0),
+            ("OH", ["CD1", "CE1", "CZ"], 1.36, 120.0, 180.0),
+        ],
+    ),
+    (
+        "TRP",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 110.5, -122.5),
+            ("CG", ["N", "CA", "CB"], 1.50, 114.1, -60.0),
+            ("CD1", ["CA", "CB", "CG"], 1.37, 127.1, 90.0),
+            ("CD2", ["CA", "CB", "CG"], 1.43, 126.6, -90.0),
+            ("NE1", ["CB", "CG", "CD1"], 1.38, 110.2, 180.0),
+            ("CE2", ["CB", "CG", "CD2"], 1.40, 107.2, 180.0),
+            ("CE3", ["CB", "CG", "CD2"], 1.40, 133.9, 0.0),
+            ("CZ2", ["CG", "CD2", "CE2"], 1.40, 122.4, 180.0),
+            ("CZ3", ["CG", "CD2", "CE3"], 1.39, 118.7, 180.0),
+            ("CH2", ["CD2", "CE2", "CZ2"], 1.37, 117.5, 0.0),
+        ],
+    ),
+    (
+        "PRO",
+        &[
+            ("CB", ["C", "N", "CA"], 1.53, 103.0, -120.0),
+            ("CG", ["N", "CA", "CB"], 1.50, 104.5, 30.0),
+            ("CD", ["CA", "CB", "CG"], 1.51, 105.5, -35.0),
+        ],
+    ),
+];
+
+/// Subtract two vectors
+fn subtract(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Scale a vector to unit length
+fn normalize(a: [f64; 3]) -> [f64; 3] {
+    let length = dot(a, a).sqrt();
+    [a[0] / length, a[1] / length, a[2] / length]
+}
+
+/// Place an atom relative to three other atoms, with the given bond length to `c`, bond angle
+/// `b`-`c`-new, and torsion angle `a`-`b`-`c`-new in degrees (the natural extension reference
+/// frame method)
+fn place(a: [f64; 3], b: [f64; 3], c: [f64; 3], bond: f64, angle: f64, torsion: f64) -> [f64; 3] {
+    let (angle, torsion) = (angle.to_radians(), torsion.to_radians());
+    let bc = normalize(subtract(c, b));
+    let n = normalize(cross(subtract(b, a), bc));
+    let m = cross(n, bc);
+    let d = [
+        -bond * angle.cos(),
+        bond * angle.sin() * torsion.cos(),
+        bond * angle.sin() * torsion.sin(),
+    ];
+    [0, 1, 2].map(|i| c[i] + d[0] * bc[i] + d[1] * m[i] + d[2] * n[i])
+}
+
+/// Build a peptide from the given sequence of one letter codes, with ideal bond lengths and
+/// angles, trans peptide bonds, and the backbone torsions of the given conformation. The side
+/// chains are placed in their most common rotamer, no hydrogens are added. The peptide is a
+/// single chain `A` numbered from 1, with an OXT atom on the C terminal residue. Whitespace in
+/// the sequence is ignored. This gives a starting point for modelling, the side chains are not
+/// checked for clashes.
+///
+/// ## Errors
+/// Fails if the sequence is empty or contains a character which is not the one letter code of
+/// one of the 20 standard amino acids.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// use pdbtbx::build::{peptide, PeptideConformation};
+/// let pdb = peptide("ACDEFGHIKLMNPQRSTVWY", PeptideConformation::AlphaHelix).unwrap();
+/// assert_eq!(pdb.residue_count(), 20);
+/// assert_eq!(pdb.chain(0).unwrap().sequence()[0], "ALA");
+/// ```
+pub fn peptide(sequence: &str, conformation: PeptideConformation) -> Result<PDB, String> {
+    let residues = sequence
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|code| {
+            SIDE_CHAINS
+                .iter()
+                .find(|(name, _)| reference_tables::one_letter_code(name) == Some(code))
+                .ok_or_else(|| format!("'{code}' is not a standard amino acid"))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if residues.is_empty() {
+        return Err("The sequence is empty".to_string());
+    }
+    let (phi, psi) = conformation.angles();
+
+    // The first backbone is placed in the xy plane with N at the origin
+    let mut n = [0.0, 0.0, 0.0];
+    let mut ca = [N_CA, 0.0, 0.0];
+    let mut c = [
+        N_CA - CA_C * N_CA_C.to_radians().cos(),
+        CA_C * N_CA_C.to_radians().sin(),
+        0.0,
+    ];
+    let mut model = Model::new(0);
+    let mut serial = 1;
+    for (index, (name, side_chain)) in residues.iter().enumerate() {
+        if index > 0 {
+            let next_n = place(n, ca, c, C_N, CA_C_N, psi);
+            let next_ca = place(ca, c, next_n, N_CA, C_N_CA, 180.0);
+            let next_c = place(c, next_n, next_ca, CA_C, N_CA_C, phi);
+            (n, ca, c) = (next_n, next_ca, next_c);
+        }
+        let mut atoms = vec![
+            ("N", n),
+            ("CA", ca),
+            ("C", c),
+            ("O", place(n, ca, c, C_O, CA_C_O, psi + 180.0)),
+        ];
+        for (atom, references, bond, angle, torsion) in side_chain.iter() {
+            let position = |reference: &str| {
+                atoms
+                    .iter()
+                    .find(|(name, _)| *name == reference)
+                    .map(|(_, position)| *position)
+                    .expect("Side chain references are placed before")
+            };
+            let [a, b, c] = references.map(position);
+            atoms.push((atom, place(a, b, c, *bond, *angle, *torsion)));
+        }
+        if index == residues.len() - 1 {
+            atoms.push(("OXT", place(n, ca, c, C_O, CA_C_O, psi)));
+        }
+
+        #[allow(clippy::cast_possible_wrap)]
+        let residue_serial = index as isize + 1;
+        for (atom, [x, y, z]) in atoms {
+            model.add_atom(
+                Atom::new(false, serial, atom, x, y, z, 1.0, 0.0, &atom[..1], 0)
+                    .expect("Template atoms are valid"),
+                "A",
+                (residue_serial, None),
+                (*name, None),
+            );
+            serial += 1;
+        }
+    }
+    let mut pdb = PDB::new();
+    pdb.add_model(model);
+    Ok(pdb)
+}
+
+/// The caps added to the termini of a protein chain by [`cap_termini`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TerminalCaps {
+    /// Neutral caps: an acetyl (ACE) residue bonded to the N terminus and an N-methylamide (NME)
+    /// residue bonded to the C terminus
+    #[default]
+    Neutral,
+    /// Charged termini: an ammonium group (H1, H2, and H3, or H2 and H3 on proline) on the N
+    /// terminus and a carboxylate group (OXT) on the C terminus
+    Charged,
+}
+
+/// Find the atom with the given name in the first conformer of the residue
+fn find_atom<'a>(residue: &'a Residue, name: &str) -> Option<&'a Atom> {
+    residue
+        .conformer(0)?
+        .atoms()
+        .find(|atom| atom.name() == name)
+}
+
+/// Get the position of an atom as an array
+fn position(atom: &Atom) -> [f64; 3] {
+    let (x, y, z) = atom.pos();
+    [x, y, z]
+}
+
+/// Get the N, CA, and C atoms of the first conformer of the residue
+fn backbone(residue: &Residue) -> Option<[&Atom; 3]> {
+    Some([
+        find_atom(residue, "N")?,
+        find_atom(residue, "CA")?,
+        find_atom(residue, "C")?,
+    ])
+}
+
+/// Create a new residue with a single conformer holding the given atoms
+fn cap_residue(
+    name: &str,
+    serial_number: isize,
+    atoms: Vec<(&str, [f64; 3])>,
+    serial: &mut usize,
+) -> Residue {
+    let mut conformer = Conformer::new(name, None, None).expect("Cap names are valid");
+    for (atom, [x, y, z]) in atoms {
+        conformer.add_atom(
+            Atom::new(false, *serial, atom, x, y, z, 1.0, 0.0, &atom[..1], 0)
+                .expect("Cap atoms are valid"),
+        );
+        *serial += 1;
+    }
+    Residue::new(serial_number, None, Some(conformer)).expect("Caps have no insertion code")
+}
+
+/// Add the given atoms to all conformers of the residue
+fn add_atoms(residue: &mut Residue, atoms: &[(&str, [f64; 3])], serial: &mut usize) {
+    for conformer in residue.conformers_mut() {
+        for &(atom, [x, y, z]) in atoms {
+            let hetero = conformer.atoms().any(Atom::hetero);
+            conformer.add_atom(
+                Atom::new(hetero, *serial, atom, x, y, z, 1.0, 0.0, &atom[..1], 0)
+                    .expect("Terminal atoms are valid"),
+            );
+            *serial += 1;
+        }
+    }
+}
+
+/// Cap the termini of the protein in the given chain with ideal geometry, as is commonly needed
+/// before simulations. The first and last residues with N, CA, and C atoms are taken as the
+/// termini, the caps are placed relative to their first conformer.
+/// * [`TerminalCaps::Neutral`] adds an ACE residue before the N terminus and an NME residue
+///   after the C terminus, numbered one before and one after the terminal residues. Other
+///   residues in the chain are renumbered if they have these numbers. Hydrogens on the terminal
+///   nitrogen are replaced by a single amide hydrogen and the OXT atom is removed. No hydrogens
+///   are added to the caps.
+/// * [`TerminalCaps::Charged`] replaces the hydrogens on the terminal nitrogen by an ammonium
+///   group and adds an OXT atom if it is missing.
+///
+/// The caps are written as standard (ATOM) residues, so they are part of the sequence of the
+/// chain. Termini which are already capped with ACE or NME are left as is. The new atoms get serial
+/// numbers following the highest one in the chain, use [`PDB::renumber`] to make these unique
+/// over the whole structure. The caps are bonded to the termini with ideal bond lengths, so bonds
+/// inferred from the geometry (see [`crate::chemistry::infer_bonds`]) include them.
+///
+/// ## Errors
+/// Fails if the chain has no residues with N, CA, and C atoms, or if the C terminal residue has
+/// no O atom. The chain is not changed.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// use pdbtbx::build::{peptide, PeptideConformation, TerminalCaps};
+/// let mut pdb = peptide("GAG", PeptideConformation::Extended).unwrap();
+/// let chain = pdb.chain_mut(0).unwrap();
+/// chain.cap_termini(TerminalCaps::Neutral).unwrap();
+/// assert_eq!(chain.sequence(), vec!["ACE", "GLY", "ALA", "GLY", "NME"]);
+/// assert_eq!(chain.residue(0).unwrap().serial_number(), 0);
+/// ```
+pub fn cap_termini(chain: &mut Chain, caps: TerminalCaps) -> Result<(), String> {
+    let residues: Vec<&Residue> = chain.residues().collect();
+    let first = residues
+        .iter()
+        .position(|residue| backbone(residue).is_some())
+        .ok_or_else(|| format!("Chain {} has no amino acid residues", chain.id()))?;
+    let last = residues
+        .iter()
+        .rposition(|residue| backbone(residue).is_some())
+        .expect("There is a first amino acid residue");
+    let n_capped = first > 0 && residues[first - 1].name() == Some("ACE");
+    let c_capped = residues.get(last + 1).and_then(|residue| residue.name()) == Some("NME");
+    let mut serial = chain.atoms().map(Atom::serial_number).max().unwrap_or(0) + 1;
+
+    // The atoms added to the N terminal residue and the atoms of the ACE residue
+    let (mut n_terminal, mut acetyl) = (Vec::new(), Vec::new());
+    if !n_capped {
+        let residue = residues[first];
+        let atoms = backbone(residue).expect("The terminal residues have a backbone");
+        let [n, ca, c] = atoms.map(position);
+        let proline = residue.name() == Some("PRO");
+        let hydrogens = ["H", "H1", "H2", "H3"]
+            .iter()
+            .any(|name| find_atom(residue, name).is_some());
+        match caps {
+            TerminalCaps::Neutral => {
+                let carbon = place(c, ca, n, C_N, C_N_CA, -60.0);
+                acetyl = vec![
+                    ("C", carbon),
+                    ("O", place(ca, n, carbon, C_O, N_C_O, 0.0)),
+                    ("CH3", place(ca, n, carbon, C_CH3, CA_C_N, 180.0)),
+                ];
+                if hydrogens && !proline {
+                    let angle = (360.0 - C_N_CA) / 2.0;
+                    n_terminal.push(("H", place(c, ca, n, N_H, angle, 120.0)));
+                }
+            }
+            TerminalCaps::Charged => {
+                let torsions = if proline {
+                    // The hydrogens are staggered with the CD atom bonded to the nitrogen
+                    let cd = find_atom(residue, "CD")
+                        .map_or(60.0, |cd| dihedral(atoms[2], atoms[1], atoms[0], cd));
+                    vec![("H2", cd + 120.0), ("H3", cd - 120.0)]
+                } else {
+                    vec![("H1", 180.0), ("H2", 60.0), ("H3", -60.0)]
+                };
+                for (name, torsion) in torsions {
+                    n_terminal.push((name, place(c, ca, n, N_H, 109.5, torsion)));
+                }
+            }
+        }
+    }
+
+    // The atoms added to the C terminal residue and the atoms of the NME residue
+    let (mut c_terminal, mut methylamide) = (Vec::new(), Vec::new());
+    if !c_capped {
+        let residue = residues[last];
+        let atoms = backbone(residue).expect("The terminal residues have a backbone");
+        let oxygen = find_atom(residue, "O").ok_or_else(|| {
+            format!(
+                "Residue {}{} in chain {} has no O atom",
+                residue.serial_number(),
+                residue.insertion_code().unwrap_or_default(),
+                chain.id()
+            )
+        })?;
+        let psi = dihedral(atoms[0], atoms[1], atoms[2], oxygen) - 180.0;
+        let [n, ca, c] = atoms.map(position);
+        match caps {
+            TerminalCaps::Neutral => {
+                let nitrogen = place(n, ca, c, C_N, CA_C_N, psi);
+                methylamide = vec![
+                    ("N", nitrogen),
+                    ("C", place(ca, c, nitrogen, N_CA, C_N_CA, 180.0)),
+                ];
+            }
+            TerminalCaps::Charged => {
+                if find_atom(residue, "OXT").is_none() {
+                    c_terminal.push(("OXT", place(n, ca, c, C_O, CA_C_O, psi)));
+                }
+            }
+        }
+    }
+
+    let mut last = last;
+    if !n_capped {
+        let residue = chain.residue_mut(first).expect("The index is valid");
+        residue.remove_atoms_by(|atom| ["H", "H1", "H2", "H3"].contains(&atom.name()));
+        add_atoms(residue, &n_terminal, &mut serial);
+    }
+    if !c_capped {
+        let residue = chain.residue_mut(last).expect("The index is valid");
+        if caps == TerminalCaps::Neutral {
+            residue.remove_atoms_by(|atom| ["OXT", "HXT"].contains(&atom.name()));
+        }
+        add_atoms(residue, &c_terminal, &mut serial);
+    }
+    if !acetyl.is_empty() {
+        let number = chain
+            .residue(first)
+            .expect("The index is valid")
+            .serial_number()
+            - 1;
+        if chain
+            .residues()
+            .any(|residue| residue.id() == (number, None))
+        {
+            for residue in chain.residues_mut().take(first) {
+                residue.set_serial_number(residue.serial_number() - 1);
+            }
+        }
+        chain.insert_residue(first, cap_residue("ACE", number, acetyl, &mut serial));
+        last += 1;
+    }
+    if !methylamide.is_empty() {
+        let number = chain
+            .residue(last)
+            .expect("The index is valid")
+            .serial_number()
+            + 1;
+        if chain
+            .residues()
+            .any(|residue| residue.id() == (number, None))
+        {
+            for residue in chain.residues_mut().skip(last + 1) {
+                residue.set_serial_number(residue.serial_number() + 1);
+            }
+        }
+        chain.insert_residue(
+            last + 1,
+            cap_residue("NME", number, methylamide, &mut serial),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry;
+
+    #[test]
+    fn placement() {
+        let (a, b, c) = ([1.0, 1.0, 0.0], [0.0, 0.0, 0.0], [1.5, 0.0, 0.0]);
+        let d = place(a, b, c, 1.3, 110.0, -60.0);
+        let atom =
+            |p: [f64; 3]| Atom::new(false, 0, "C", p[0], p[1], p[2], 1.0, 0.0, "C", 0).unwrap();
+        let (a, b, c, d) = (atom(a), atom(b), atom(c), atom(d));
+        assert!((c.distance(&d) - 1.3).abs() < 1e-9);
+        assert!((geometry::angle(&b, &c, &d) - 110.0).abs() < 1e-9);
+        assert!((geometry::dihedral(&a, &b, &c, &d) + 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn templates() {
+        for (name, side_chain) in SIDE_CHAINS {
+            let template = reference_tables::amino_acid_atoms(name).unwrap();
+            assert_eq!(template.len(), side_chain.len() + 4, "{name}");
+            assert!(side_chain.iter().all(|atom| template.contains(&atom.0)));
+        }
+        assert!(peptide("", PeptideConformation::Extended).is_err());
+        assert!(peptide("AXA", PeptideConformation::Extended).is_err());
+    }
+}
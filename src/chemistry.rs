@@ -0,0 +1,885 @@
+use std::collections::HashSet;
+
+use crate::{Atom, Element, PDBError};
+
+/// The order of a covalent bond between two atoms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BondOrder {
+    /// A single bond
+    Single,
+    /// A double bond
+    Double,
+    /// A triple bond
+    Triple,
+}
+
+impl BondOrder {
+    /// The number of electron pairs shared in this bond
+    pub const fn value(self) -> usize {
+        match self {
+            BondOrder::Single => 1,
+            BondOrder::Double => 2,
+            BondOrder::Triple => 3,
+        }
+    }
+
+    /// The symbol for this bond in SMILES, single bonds are implicit
+    const fn smiles(self) -> &'static str {
+        match self {
+            BondOrder::Single => "",
+            BondOrder::Double => "=",
+            BondOrder::Triple => "#",
+        }
+    }
+}
+
+/// The tolerance in Aͦ added to the sum of the covalent radii to count two atoms as bonded
+const BOND_TOLERANCE: f64 = 0.45;
+
+/// The minimal distance in Aͦ between bonded atoms, closer atoms are taken to be overlapping
+const MIN_BOND_DISTANCE: f64 = 0.4;
+
+/// The maximal valence of an atom with the given element and charge, `None` if the valence is
+/// not restricted (like for metals)
+fn max_valence(element: Element, charge: isize) -> Option<usize> {
+    let valence = match element {
+        Element::H | Element::F | Element::Cl | Element::Br | Element::I => 1,
+        Element::O => 2 + charge,
+        Element::B => 3,
+        Element::N => 3 + charge.max(0),
+        Element::C | Element::Si => 4,
+        Element::P => 5,
+        Element::S | Element::Se => 6,
+        _ => return None,
+    };
+    Some(usize::try_from(valence).unwrap_or(0))
+}
+
+/// Infer the covalent bonds between the given atoms from their distances, with bond orders
+/// perceived from the bond lengths. Two atoms are bonded if their distance is at most the sum of
+/// their single bond covalent radii plus 0.45 Aͦ. A bond is taken to be a double (or triple) bond
+/// if its length is closer to the sum of the double (or triple) bond covalent radii than to the
+/// sum of the single (or double) bond radii. Afterwards the least certain multiple bonds are
+/// downgraded until no atom exceeds its maximal valence. Atoms without an element are ignored.
+/// Returns the bonds as indices into the given atoms, with the lowest index first.
+///
+/// This perception is a heuristic, the result is only as good as the geometry of the structure.
+/// Aromatic rings are given as one of their Kekulé structures.
+pub fn infer_bonds(atoms: &[&Atom]) -> Vec<(usize, usize, BondOrder)> {
+    // The bonds with how far their length is below the threshold of their order
+    let mut bonds = Vec::new();
+    for (i, a) in atoms.iter().enumerate() {
+        let element_a = match a.element() {
+            Some(element) => element,
+            None => continue,
+        };
+        for (j, b) in atoms.iter().enumerate().skip(i + 1) {
+            let element_b = match b.element() {
+                Some(element) => element,
+                None => continue,
+            };
+            if *element_a == Element::H && *element_b == Element::H {
+                continue;
+            }
+            let (radius_a, radius_b) = (element_a.atomic_radius(), element_b.atomic_radius());
+            let distance = a.distance(b);
+            let single = radius_a.covalent_single + radius_b.covalent_single;
+            if distance < MIN_BOND_DISTANCE || distance > single + BOND_TOLERANCE {
+                continue;
+            }
+            let double = radius_a
+                .covalent_double
+                .zip(radius_b.covalent_double)
+                .map(|(a, b)| a + b);
+            let triple = radius_a
+                .covalent_triple
+                .zip(radius_b.covalent_triple)
+                .map(|(a, b)| a + b);
+            let mut bond = (i, j, BondOrder::Single, f64::INFINITY);
+            if let Some(double) = double {
+                let threshold = (single + double) / 2.0;
+                if distance < threshold {
+                    bond = (i, j, BondOrder::Double, threshold - distance);
+                    let linear = |e: &Element| matches!(e, Element::C | Element::N);
+                    if let Some(triple) = triple.filter(|_| linear(element_a) && linear(element_b))
+                    {
+                        let threshold = (double + triple) / 2.0;
+                        if distance < threshold {
+                            bond = (i, j, BondOrder::Triple, threshold - distance);
+                        }
+                    }
+                }
+            }
+            bonds.push(bond);
+        }
+    }
+    // Atoms of these elements have at most one multiple bond, so pick the multiple bonds like a
+    // Kekulé structure: the bonds on the atoms with the fewest options first, the most certain
+    // bonds first if there is a choice
+    let restricted: Vec<bool> = atoms
+        .iter()
+        .map(|a| {
+            matches!(
+                a.element(),
+                Some(Element::B | Element::C | Element::N | Element::O)
+            )
+        })
+        .collect();
+    let mut candidates: Vec<usize> = (0..bonds.len())
+        .filter(|index| bonds[*index].2 != BondOrder::Single)
+        .collect();
+    let mut accepted = vec![false; bonds.len()];
+    while !candidates.is_empty() {
+        let mut options = vec![0; atoms.len()];
+        for index in &candidates {
+            options[bonds[*index].0] += 1;
+            options[bonds[*index].1] += 1;
+        }
+        let freedom = |atom: usize| {
+            if restricted[atom] {
+                options[atom]
+            } else {
+                usize::MAX
+            }
+        };
+        let best = candidates
+            .iter()
+            .copied()
+            .min_by(|a, b| {
+                let (a, b) = (bonds[*a], bonds[*b]);
+                freedom(a.0)
+                    .min(freedom(a.1))
+                    .cmp(&freedom(b.0).min(freedom(b.1)))
+                    .then(b.3.total_cmp(&a.3))
+            })
+            .expect("There are candidates left");
+        accepted[best] = true;
+        let (a, b, _, _) = bonds[best];
+        candidates.retain(|index| {
+            let (c, d, _, _) = bonds[*index];
+            *index != best
+                && !(restricted[a] && (a == c || a == d))
+                && !(restricted[b] && (b == c || b == d))
+        });
+    }
+    for (bond, accepted) in bonds.iter_mut().zip(accepted) {
+        if !accepted {
+            bond.2 = BondOrder::Single;
+        }
+    }
+
+    // Downgrade the least certain multiple bonds on atoms exceeding their valence
+    let limits: Vec<Option<usize>> = atoms
+        .iter()
+        .map(|a| a.element().and_then(|e| max_valence(*e, a.charge())))
+        .collect();
+    let mut order: Vec<usize> = (0..bonds.len()).collect();
+    order.sort_by(|a, b| bonds[*a].3.total_cmp(&bonds[*b].3));
+    loop {
+        let mut valence = vec![0; atoms.len()];
+        for (a, b, bond_order, _) in &bonds {
+            valence[*a] += bond_order.value();
+            valence[*b] += bond_order.value();
+        }
+        let exceeds = |index: usize| limits[index].map_or(false, |max| valence[index] > max);
+        let index = match order.iter().copied().find(|index| {
+            let (a, b, bond_order, _) = bonds[*index];
+            bond_order != BondOrder::Single && (exceeds(a) || exceeds(b))
+        }) {
+            Some(index) => index,
+            None => break,
+        };
+        bonds[index].2 = match bonds[index].2 {
+            BondOrder::Triple => BondOrder::Double,
+            _ => BondOrder::Single,
+        };
+    }
+    bonds
+        .into_iter()
+        .map(|(a, b, order, _)| (a, b, order))
+        .collect()
+}
+
+/// Find the rings in the molecule given by the bonds (as indices into the atoms, see
+/// [`infer_bonds`]), as a smallest set of smallest rings: for every independent cycle the
+/// smallest ring is given. Every ring is given as the indices of its atoms in order around the
+/// ring, the rings are sorted from small to large.
+pub fn rings(atom_count: usize, bonds: &[(usize, usize, BondOrder)]) -> Vec<Vec<usize>> {
+    let mut neighbours = vec![Vec::new(); atom_count];
+    for (index, (a, b, _)) in bonds.iter().enumerate() {
+        neighbours[*a].push((*b, index));
+        neighbours[*b].push((*a, index));
+    }
+    // The smallest ring through every bond, as the shortest path between its atoms without it
+    let mut candidates: Vec<Vec<usize>> = Vec::new();
+    for (removed, (start, end, _)) in bonds.iter().enumerate() {
+        let mut previous = vec![None; atom_count];
+        previous[*start] = Some(*start);
+        let mut queue = std::collections::VecDeque::from([*start]);
+        while let Some(atom) = queue.pop_front() {
+            if atom == *end {
+                break;
+            }
+            for (neighbour, bond) in &neighbours[atom] {
+                if *bond != removed && previous[*neighbour].is_none() {
+                    previous[*neighbour] = Some(atom);
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+        if previous[*end].is_none() {
+            continue;
+        }
+        let mut ring = vec![*end];
+        while let Some(atom) = previous[ring[ring.len() - 1]].filter(|a| *a != *start) {
+            ring.push(atom);
+        }
+        ring.push(*start);
+        candidates.push(ring);
+    }
+    candidates.sort_by_key(Vec::len);
+
+    // Keep the rings whose bonds are independent of the smaller rings (Gaussian elimination over
+    // GF(2) on the sets of bonds)
+    let bond_index = |a: usize, b: usize| {
+        neighbours[a]
+            .iter()
+            .find(|(n, _)| *n == b)
+            .map(|(_, bond)| *bond)
+    };
+    let mut basis: Vec<(usize, Vec<bool>)> = Vec::new();
+    let mut found: Vec<Vec<usize>> = Vec::new();
+    for ring in candidates {
+        let mut vector = vec![false; bonds.len()];
+        for (index, atom) in ring.iter().enumerate() {
+            if let Some(bond) = bond_index(*atom, ring[(index + 1) % ring.len()]) {
+                vector[bond] = true;
+            }
+        }
+        for (pivot, row) in &basis {
+            if vector[*pivot] {
+                vector.iter_mut().zip(row).for_each(|(v, r)| *v ^= *r);
+            }
+        }
+        if let Some(pivot) = vector.iter().position(|v| *v) {
+            basis.push((pivot, vector));
+            found.push(ring);
+        }
+    }
+    found
+}
+
+/// The maximal absolute endocyclic torsion angle in degrees for a ring to be planar
+const AROMATIC_MAX_TORSION: f64 = 15.0;
+
+/// The minimal shortening in Aͦ of a bond compared to the sum of the single bond covalent radii
+/// to count as conjugated
+const CONJUGATED_MIN_SHORTENING: f64 = 0.05;
+
+/// Find the aromatic rings in the molecule given by the atoms and bonds (as indices into the
+/// atoms, see [`infer_bonds`]). A ring (see [`rings`]) is aromatic if it is planar, with all
+/// endocyclic torsion angles within 15 degrees, and if it can follow Hückel's rule with 4n + 2
+/// π electrons. The π electrons are counted with valence rules instead of from the bond orders,
+/// as these are unreliable for heteroaromatic rings in experimental structures: carbons give
+/// one electron, or none with an exocyclic double bond to oxygen or sulfur (like the carbonyl
+/// carbons in uracil), oxygen and sulfur give their lone pair, and nitrogens give one electron
+/// with two bonds or their lone pair with three bonds. When there are no hydrogens in the
+/// molecule, a nitrogen with two bonds can give either, as it could be protonated (like in
+/// histidine). Every carbon needs a conjugated bond in the ring, at least 0.05 Aͦ shorter than a
+/// single bond, so rings with saturated carbons or other elements are not aromatic.
+/// Every ring is given as the indices of its atoms in order around the ring.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let phenylalanine = pdb.residues().find(|r| r.name() == Some("PHE")).unwrap();
+/// let atoms: Vec<&Atom> = phenylalanine.atoms().collect();
+/// let bonds = chemistry::infer_bonds(&atoms);
+/// let aromatic = chemistry::aromatic_rings(&atoms, &bonds);
+/// assert_eq!(aromatic.len(), 1);
+/// assert_eq!(aromatic[0].len(), 6);
+/// ```
+pub fn aromatic_rings(atoms: &[&Atom], bonds: &[(usize, usize, BondOrder)]) -> Vec<Vec<usize>> {
+    let hydrogens = atoms.iter().any(|a| a.element() == Some(&Element::H));
+    let mut neighbours = vec![Vec::new(); atoms.len()];
+    for (a, b, order) in bonds {
+        neighbours[*a].push((*b, *order));
+        neighbours[*b].push((*a, *order));
+    }
+    // The minimal and maximal number of π electrons the atom can give to a ring
+    let electrons = |ring: &[usize], atom: usize| -> Option<(usize, usize)> {
+        let bonded = &neighbours[atom];
+        let carbonyl = bonded.iter().any(|(other, order)| {
+            *order != BondOrder::Single
+                && !ring.contains(other)
+                && matches!(atoms[*other].element(), Some(Element::O | Element::S))
+        });
+        let conjugated = bonded.iter().any(|(other, _)| {
+            let radius = |a: &Atom| {
+                a.element()
+                    .map_or(0.0, |e| e.atomic_radius().covalent_single)
+            };
+            ring.contains(other)
+                && atoms[atom].distance(atoms[*other])
+                    <= radius(atoms[atom]) + radius(atoms[*other]) - CONJUGATED_MIN_SHORTENING
+        });
+        let charge = atoms[atom].charge();
+        match (atoms[atom].element()?, bonded.len()) {
+            (Element::C, _) if !conjugated => None,
+            (Element::C, 2 | 3) if carbonyl => Some((0, 0)),
+            (Element::C, 2 | 3) => Some((1, 1)),
+            (Element::N, 2) if charge > 0 => Some((1, 1)),
+            (Element::N, 2) if hydrogens => Some((1, 1)),
+            (Element::N, 2) => Some((1, 2)),
+            (Element::N, 3) if charge > 0 => Some((1, 1)),
+            (Element::N, 3) => Some((2, 2)),
+            (Element::O | Element::S, 2) => Some((2, 2)),
+            _ => None,
+        }
+    };
+    rings(atoms.len(), bonds)
+        .into_iter()
+        .filter(|ring| {
+            let planar = (0..ring.len()).all(|index| {
+                let atom = |offset: usize| atoms[ring[(index + offset) % ring.len()]];
+                crate::geometry::dihedral(atom(0), atom(1), atom(2), atom(3)).abs()
+                    <= AROMATIC_MAX_TORSION
+            });
+            let range = ring
+                .iter()
+                .map(|atom| electrons(ring, *atom))
+                .try_fold((0, 0), |acc, e| e.map(|e| (acc.0 + e.0, acc.1 + e.1)));
+            planar && range.map_or(false, |(min, max)| (min..=max).any(|pi| pi % 4 == 2))
+        })
+        .collect()
+}
+
+/// Get the molecular formula of the given atoms in Hill order: carbon first, hydrogen second,
+/// and all other elements alphabetically (all elements alphabetically if there is no carbon).
+/// The elements are separated by spaces and counts of one are left out, as in the FORMUL
+/// records of PDB files, eg `C8 H15 N O6`. Only the atoms present are counted, so hydrogens
+/// are missing for most experimental structures. Atoms without an element are ignored.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let alanine = pdb.residues().find(|r| r.name() == Some("ALA")).unwrap();
+/// let atoms: Vec<&Atom> = alanine.atoms().filter(|a| a.element() != Some(&Element::H)).collect();
+/// assert_eq!(chemistry::formula(&atoms), "C3 N O");
+/// ```
+pub fn formula(atoms: &[&Atom]) -> String {
+    let mut counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    for element in atoms.iter().filter_map(|atom| atom.element()) {
+        *counts.entry(element.symbol()).or_default() += 1;
+    }
+    let first: &[&str] = if counts.contains_key("C") {
+        &["C", "H"]
+    } else {
+        &[]
+    };
+    let order: Vec<&str> = first
+        .iter()
+        .copied()
+        .filter(|symbol| counts.contains_key(symbol))
+        .chain(
+            counts
+                .keys()
+                .copied()
+                .filter(|symbol| !first.contains(symbol)),
+        )
+        .collect();
+    order
+        .iter()
+        .map(|symbol| {
+            // The symbols are stored in uppercase, but formulas are case sensitive
+            let name = format!("{}{}", &symbol[..1], symbol[1..].to_lowercase());
+            match counts[symbol] {
+                1 => name,
+                n => format!("{name}{n}"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Get the molecular weight of the given atoms in Dalton, as the sum of the standard atomic
+/// weights of their elements (see [`Element::weight`]). Only the atoms present are counted, so
+/// hydrogens are missing for most experimental structures. Atoms without an element or known
+/// weight are ignored.
+pub fn molecular_weight<'a>(atoms: impl IntoIterator<Item = &'a Atom>) -> f64 {
+    atoms
+        .into_iter()
+        .filter_map(|atom| atom.element().and_then(Element::weight))
+        .sum()
+}
+
+/// The elements which can be written without brackets in SMILES, with their normal valences
+fn organic_subset(element: Element) -> Option<&'static [usize]> {
+    match element {
+        Element::B => Some(&[3]),
+        Element::C => Some(&[4]),
+        Element::N => Some(&[3, 5]),
+        Element::O => Some(&[2]),
+        Element::P => Some(&[3, 5]),
+        Element::S => Some(&[2, 4, 6]),
+        Element::F | Element::Cl | Element::Br | Element::I => Some(&[1]),
+        _ => None,
+    }
+}
+
+/// The state while writing a SMILES string
+struct SmilesWriter<'a> {
+    /// The atoms, hydrogens are only used to count the hydrogens on bracket atoms
+    atoms: &'a [&'a Atom],
+    /// The neighbours of each atom, without hydrogens
+    neighbours: Vec<Vec<(usize, BondOrder)>>,
+    /// The number of bonded hydrogens of each atom
+    hydrogens: Vec<usize>,
+    /// The children of each atom in the depth first spanning tree
+    children: Vec<Vec<(usize, BondOrder)>>,
+    /// The bonds closing rings, as (first written atom, last written atom, order)
+    rings: Vec<(usize, usize, BondOrder)>,
+    /// The ring closure number currently used for each ring bond
+    ring_numbers: Vec<Option<usize>>,
+    /// The atoms visited while building the spanning tree
+    visited: Vec<bool>,
+}
+
+impl SmilesWriter<'_> {
+    /// Build the spanning tree from the given atom, and find the ring closures
+    fn visit(&mut self, atom: usize, parent: Option<usize>, seen: &mut HashSet<(usize, usize)>) {
+        self.visited[atom] = true;
+        for (neighbour, order) in self.neighbours[atom].clone() {
+            if Some(neighbour) == parent {
+                continue;
+            }
+            if self.visited[neighbour] {
+                if seen.insert((atom.min(neighbour), atom.max(neighbour))) {
+                    self.rings.push((neighbour, atom, order));
+                }
+            } else {
+                seen.insert((atom.min(neighbour), atom.max(neighbour)));
+                self.children[atom].push((neighbour, order));
+                self.visit(neighbour, Some(atom), seen);
+            }
+        }
+    }
+
+    /// The symbol of the given atom
+    fn symbol(&self, index: usize) -> String {
+        let atom = self.atoms[index];
+        let element = match atom.element() {
+            Some(element) => *element,
+            None => return "*".to_string(),
+        };
+        let valence: usize = self.neighbours[index]
+            .iter()
+            .map(|(_, order)| order.value())
+            .sum::<usize>()
+            + self.hydrogens[index];
+        let organic = organic_subset(element).map_or(false, |valences| {
+            atom.charge() == 0 && valences.iter().any(|v| *v >= valence)
+        });
+        // The symbols are stored in uppercase, but SMILES is case sensitive
+        let symbol = element.symbol();
+        let symbol = format!("{}{}", &symbol[..1], symbol[1..].to_lowercase());
+        if organic {
+            return symbol;
+        }
+        let hydrogens = match self.hydrogens[index] {
+            0 => String::new(),
+            1 => "H".to_string(),
+            n => format!("H{n}"),
+        };
+        let charge = match atom.charge() {
+            0 => String::new(),
+            1 => "+".to_string(),
+            -1 => "-".to_string(),
+            n if n > 0 => format!("+{n}"),
+            n => format!("{n}"),
+        };
+        format!("[{symbol}{hydrogens}{charge}]")
+    }
+
+    /// Write the given atom and its subtree
+    fn write(&mut self, atom: usize, output: &mut String) {
+        output.push_str(&self.symbol(atom));
+        // Open the new rings first, so a ring number closed at this atom is not reused directly
+        for ring in 0..self.rings.len() {
+            let (first, _, order) = self.rings[ring];
+            if first == atom {
+                let number = (1..)
+                    .find(|n| !self.ring_numbers.contains(&Some(*n)))
+                    .expect("There is always a free ring number");
+                self.ring_numbers[ring] = Some(number);
+                output.push_str(order.smiles());
+                output.push_str(&ring_number(number));
+            }
+        }
+        for ring in 0..self.rings.len() {
+            if self.rings[ring].1 == atom {
+                if let Some(number) = self.ring_numbers[ring].take() {
+                    output.push_str(&ring_number(number));
+                }
+            }
+        }
+        let children = self.children[atom].clone();
+        for (index, (child, order)) in children.iter().enumerate() {
+            let branch = index + 1 < children.len();
+            if branch {
+                output.push('(');
+            }
+            output.push_str(order.smiles());
+            self.write(*child, output);
+            if branch {
+                output.push(')');
+            }
+        }
+    }
+}
+
+/// Format a ring closure number, numbers above 9 are prefixed by `%`
+fn ring_number(number: usize) -> String {
+    if number < 10 {
+        number.to_string()
+    } else {
+        format!("%{number}")
+    }
+}
+
+/// Write the given atoms and bonds (as indices into the atoms, see [`infer_bonds`]) as a SMILES
+/// string. Hydrogens are written implicitly, except on atoms that need brackets (like metals or
+/// charged atoms) where the bonded hydrogens are counted. Multiple bonds are written explicitly
+/// (Kekulé form), disconnected fragments are separated by dots. Stereochemistry is not written.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let alanine = pdb.residues().find(|r| r.name() == Some("ALA")).unwrap();
+/// let atoms: Vec<&Atom> = alanine.atoms().collect();
+/// let bonds = chemistry::infer_bonds(&atoms);
+/// assert_eq!(chemistry::smiles(&atoms, &bonds), "NC(C=O)C");
+/// ```
+pub fn smiles(atoms: &[&Atom], bonds: &[(usize, usize, BondOrder)]) -> String {
+    let is_hydrogen = |index: usize| atoms[index].element() == Some(&Element::H);
+    let mut writer = SmilesWriter {
+        atoms,
+        neighbours: vec![Vec::new(); atoms.len()],
+        hydrogens: vec![0; atoms.len()],
+        children: vec![Vec::new(); atoms.len()],
+        rings: Vec::new(),
+        ring_numbers: Vec::new(),
+        visited: vec![false; atoms.len()],
+    };
+    for (a, b, order) in bonds {
+        match (is_hydrogen(*a), is_hydrogen(*b)) {
+            (false, false) => {
+                writer.neighbours[*a].push((*b, *order));
+                writer.neighbours[*b].push((*a, *order));
+            }
+            (false, true) => writer.hydrogens[*a] += 1,
+            (true, false) => writer.hydrogens[*b] += 1,
+            (true, true) => (),
+        }
+    }
+    let mut seen = HashSet::new();
+    let mut roots = Vec::new();
+    for atom in 0..atoms.len() {
+        if !writer.visited[atom] && !is_hydrogen(atom) {
+            roots.push(atom);
+            writer.visit(atom, None, &mut seen);
+        }
+    }
+    writer.ring_numbers = vec![None; writer.rings.len()];
+    let mut output = String::new();
+    for (index, root) in roots.into_iter().enumerate() {
+        if index > 0 {
+            output.push('.');
+        }
+        writer.write(root, &mut output);
+    }
+    output
+}
+
+/// An atom in a chemical component definition, see [`Component`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentAtom {
+    /// The name of the atom, eg `C4A`
+    pub name: String,
+    /// The element of the atom
+    pub element: Option<Element>,
+    /// The formal charge of the atom
+    pub charge: isize,
+}
+
+/// A chemical component definition, like the entries of the Chemical Component Dictionary (CCD)
+/// of the wwPDB, describing the atoms and bonds of a residue or ligand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// The identifier of the component, eg `PLP`
+    pub id: String,
+    /// The atoms of the component
+    pub atoms: Vec<ComponentAtom>,
+    /// The bonds of the component, as indices into the atoms
+    pub bonds: Vec<(usize, usize, BondOrder)>,
+}
+
+impl Component {
+    /// Parse a component definition from the mmCIF text of a CCD entry, as can be downloaded from
+    /// `https://files.rcsb.org/ligands/view/<ID>.cif`. Only the atoms (`_chem_comp_atom`) and
+    /// bonds (`_chem_comp_bond`) are read.
+    ///
+    /// ## Errors
+    /// Fails if the text is not valid CIF, if it does not define any atoms, or if a bond refers to
+    /// an undefined atom.
+    pub fn from_cif(text: &str) -> Result<Self, PDBError> {
+        crate::read::parse_component(text)
+    }
+}
+
+/// The result of matching a residue against a [`Component`], see [`match_component`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentMatch {
+    /// The heavy atoms of the residue with the names of the component atoms they are matched to,
+    /// as (residue atom name, component atom name)
+    pub mapping: Vec<(String, String)>,
+    /// The names of the heavy atoms of the component which are missing in the residue
+    pub missing: Vec<String>,
+}
+
+impl ComponentMatch {
+    /// Whether all heavy atoms of the component are present in the residue
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// The atoms of the residue with names not matching the name of the component atom they are
+    /// matched to, as (residue atom name, component atom name)
+    pub fn renamed(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.mapping
+            .iter()
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| (a.as_str(), b.as_str()))
+    }
+}
+
+/// The maximal number of steps taken while searching a match with a component
+const MATCH_MAX_STEPS: usize = 1_000_000;
+
+/// The state while matching the atoms of a residue to the atoms of a component
+struct Matcher<'a> {
+    /// The heavy atoms of the residue
+    atoms: Vec<&'a Atom>,
+    /// The heavy atoms of the component
+    component: Vec<&'a ComponentAtom>,
+    /// The neighbours of the residue atoms
+    atom_neighbours: Vec<Vec<usize>>,
+    /// The neighbours of the component atoms
+    component_neighbours: Vec<Vec<usize>>,
+    /// The component atom matched to each residue atom
+    mapping: Vec<Option<usize>>,
+    /// Which component atoms are matched
+    used: Vec<bool>,
+    /// The order to match the residue atoms in
+    order: Vec<usize>,
+    /// The steps left
+    steps: usize,
+}
+
+impl Matcher<'_> {
+    /// Try to match the residue atom at the given position in the order, and all after it
+    fn extend(&mut self, position: usize) -> bool {
+        let atom = match self.order.get(position) {
+            Some(atom) => *atom,
+            None => return true,
+        };
+        if self.steps == 0 {
+            return false;
+        }
+        self.steps -= 1;
+        // Only the neighbours of an already matched neighbour are possible
+        let mapped_neighbours: Vec<usize> = self.atom_neighbours[atom]
+            .iter()
+            .filter_map(|n| self.mapping[*n])
+            .collect();
+        let mut candidates: Vec<usize> = match mapped_neighbours.first() {
+            Some(neighbour) => self.component_neighbours[*neighbour].clone(),
+            None => (0..self.component.len()).collect(),
+        };
+        candidates.retain(|candidate| {
+            !self.used[*candidate]
+                && self.component[*candidate].element.as_ref() == self.atoms[atom].element()
+                && self.component_neighbours[*candidate].len() >= self.atom_neighbours[atom].len()
+                && mapped_neighbours
+                    .iter()
+                    .all(|n| self.component_neighbours[*candidate].contains(n))
+        });
+        // Try the atom with the same name first
+        candidates
+            .sort_by_key(|candidate| self.component[*candidate].name != self.atoms[atom].name());
+        for candidate in candidates {
+            self.mapping[atom] = Some(candidate);
+            self.used[candidate] = true;
+            if self.extend(position + 1) {
+                return true;
+            }
+            self.mapping[atom] = None;
+            self.used[candidate] = false;
+        }
+        false
+    }
+}
+
+/// Match the heavy atoms of a residue against a chemical component definition, to confirm its
+/// identity, find missing atoms, and map nonstandard atom names. The bonds of the residue are
+/// inferred from the geometry (see [`infer_bonds`]), and every heavy atom of the residue is
+/// matched to a component atom with the same element, such that every bond in the residue is a
+/// bond in the component as well. Atoms with the same name are preferred, so symmetric groups
+/// keep their names. Bond orders and hydrogens are ignored.
+///
+/// Returns `None` if the residue does not fit the component, for example because it contains
+/// atoms or bonds not in the component, or if it has no heavy atoms.
+pub fn match_component(atoms: &[&Atom], component: &Component) -> Option<ComponentMatch> {
+    let is_heavy = |element: Option<&Element>| element != Some(&Element::H);
+    let atoms: Vec<&Atom> = atoms
+        .iter()
+        .copied()
+        .filter(|a| is_heavy(a.element()))
+        .collect();
+    if atoms.is_empty() {
+        return None;
+    }
+    let component_indices: Vec<usize> = (0..component.atoms.len())
+        .filter(|i| is_heavy(component.atoms[*i].element.as_ref()))
+        .collect();
+    let mut atom_neighbours = vec![Vec::new(); atoms.len()];
+    for (a, b, _) in infer_bonds(&atoms) {
+        atom_neighbours[a].push(b);
+        atom_neighbours[b].push(a);
+    }
+    let mut component_neighbours = vec![Vec::new(); component_indices.len()];
+    for (a, b, _) in &component.bonds {
+        let a = component_indices.iter().position(|i| i == a);
+        let b = component_indices.iter().position(|i| i == b);
+        if let (Some(a), Some(b)) = (a, b) {
+            component_neighbours[a].push(b);
+            component_neighbours[b].push(a);
+        }
+    }
+    // Match in breadth first order, starting from the most connected atom of each fragment
+    let mut order = Vec::new();
+    let mut seen = vec![false; atoms.len()];
+    let mut starts: Vec<usize> = (0..atoms.len()).collect();
+    starts.sort_by_key(|a| std::cmp::Reverse(atom_neighbours[*a].len()));
+    for start in starts {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut queue = std::collections::VecDeque::from([start]);
+        while let Some(atom) = queue.pop_front() {
+            order.push(atom);
+            for neighbour in &atom_neighbours[atom] {
+                if !seen[*neighbour] {
+                    seen[*neighbour] = true;
+                    queue.push_back(*neighbour);
+                }
+            }
+        }
+    }
+    let mut matcher = Matcher {
+        component: component_indices
+            .iter()
+            .map(|i| &component.atoms[*i])
+            .collect(),
+        mapping: vec![None; atoms.len()],
+        used: vec![false; component_indices.len()],
+        atoms,
+        atom_neighbours,
+        component_neighbours,
+        order,
+        steps: MATCH_MAX_STEPS,
+    };
+    if !matcher.extend(0) {
+        return None;
+    }
+    Some(ComponentMatch {
+        mapping: matcher
+            .atoms
+            .iter()
+            .zip(&matcher.mapping)
+            .filter_map(|(atom, index)| {
+                index.map(|i| (atom.name().to_string(), matcher.component[i].name.clone()))
+            })
+            .collect(),
+        missing: matcher
+            .component
+            .iter()
+            .zip(&matcher.used)
+            .filter(|(_, used)| !**used)
+            .map(|(atom, _)| atom.name.clone())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atom(name: &str, element: &str, position: (f64, f64, f64)) -> Atom {
+        Atom::new(
+            true, 1, name, position.0, position.1, position.2, 1.0, 0.0, element, 0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn benzene() {
+        let atoms: Vec<Atom> = (0..6)
+            .map(|i| {
+                let angle = f64::from(i) * std::f64::consts::PI / 3.0;
+                atom("C", "C", (1.39 * angle.cos(), 1.39 * angle.sin(), 0.0))
+            })
+            .collect();
+        let atoms: Vec<&Atom> = atoms.iter().collect();
+        let bonds = infer_bonds(&atoms);
+        assert_eq!(bonds.len(), 6);
+        assert_eq!(bonds.iter().filter(|b| b.2 == BondOrder::Double).count(), 3);
+        assert_eq!(smiles(&atoms, &bonds), "C=1C=CC=CC1");
+        assert_eq!(formula(&atoms), "C6");
+        assert_eq!(rings(atoms.len(), &bonds).len(), 1);
+        assert_eq!(aromatic_rings(&atoms, &bonds), rings(atoms.len(), &bonds));
+    }
+
+    #[test]
+    fn cyclohexane() {
+        // A chair, with alternating atoms above and below the plane
+        let atoms: Vec<Atom> = (0..6)
+            .map(|i| {
+                let angle = f64::from(i) * std::f64::consts::PI / 3.0;
+                let z = if i % 2 == 0 { 0.25 } else { -0.25 };
+                atom("C", "C", (1.45 * angle.cos(), 1.45 * angle.sin(), z))
+            })
+            .collect();
+        let atoms: Vec<&Atom> = atoms.iter().collect();
+        let bonds = infer_bonds(&atoms);
+        assert!(bonds.iter().all(|b| b.2 == BondOrder::Single));
+        assert_eq!(rings(atoms.len(), &bonds).len(), 1);
+        assert!(aromatic_rings(&atoms, &bonds).is_empty());
+    }
+
+    #[test]
+    fn fragments() {
+        let atoms = [
+            atom("C1", "C", (0.0, 0.0, 0.0)),
+            atom("N1", "N", (1.16, 0.0, 0.0)),
+            atom("ZN", "ZN", (10.0, 0.0, 0.0)),
+        ];
+        let atoms: Vec<&Atom> = atoms.iter().collect();
+        let bonds = infer_bonds(&atoms);
+        assert_eq!(bonds, vec![(0, 1, BondOrder::Triple)]);
+        assert_eq!(smiles(&atoms, &bonds), "C#N.[Zn]");
+        assert_eq!(formula(&atoms), "C N Zn");
+        assert!((molecular_weight(atoms) - 91.4).abs() < 0.01);
+    }
+}
@@ -0,0 +1,83 @@
+use super::{Context, ErrorLevel, PDBError};
+
+/// A filter to select errors, used to silence or downgrade errors while reading, see
+/// [`crate::ReadOptions::suppress_errors`] and [`crate::ReadOptions::downgrade_errors`].
+/// An error matches a filter if it matches all criteria set on the filter.
+///
+/// # Example
+/// ```rust
+/// use pdbtbx::*;
+/// let filter = ErrorFilter::title("MASTER checksum failed").with_level(ErrorLevel::LooseWarning);
+/// let error = PDBError::new(
+///     ErrorLevel::LooseWarning,
+///     "MASTER checksum failed",
+///     "The number of Atoms is different",
+///     Context::full_line(10, "MASTER      0    0    0    0    0    0    0    0    1    0    0    0"),
+/// );
+/// assert!(filter.matches(&error));
+/// assert!(ErrorFilter::record("MASTER").matches(&error));
+/// assert!(!ErrorFilter::record("ATOM").matches(&error));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ErrorFilter {
+    /// The short description (title) of the errors to match
+    title: Option<String>,
+    /// The record type (eg `ATOM` or `REMARK`) of the line the errors are located on
+    record: Option<String>,
+    /// The level of the errors to match
+    level: Option<ErrorLevel>,
+}
+
+impl ErrorFilter {
+    /// Create a filter which matches all errors with the given short description (title).
+    pub fn title(title: impl Into<String>) -> Self {
+        ErrorFilter {
+            title: Some(title.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Create a filter which matches all errors located on a line of the given record type, for
+    /// example `ATOM` or `REMARK`. Errors without a line in their context never match.
+    pub fn record(record: impl Into<String>) -> Self {
+        ErrorFilter {
+            record: Some(record.into().trim().to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Only match errors with the given level.
+    #[must_use]
+    pub fn with_level(mut self, level: ErrorLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Only match errors with the given record type, see [`ErrorFilter::record`].
+    #[must_use]
+    pub fn with_record(mut self, record: impl Into<String>) -> Self {
+        self.record = Some(record.into().trim().to_string());
+        self
+    }
+
+    /// Test if the given error matches this filter.
+    pub fn matches(&self, error: &PDBError) -> bool {
+        self.title
+            .as_ref()
+            .map_or(true, |title| title == error.short_description())
+            && self.level.map_or(true, |level| level == error.level())
+            && self.record.as_ref().map_or(true, |record| {
+                record_type(error.context()).map_or(false, |r| r == record)
+            })
+    }
+}
+
+/// Get the record type of the line in the given context, if there is a single line
+fn record_type(context: &Context) -> Option<&str> {
+    match context {
+        Context::FullLine { line, .. } | Context::Line { line, .. } => {
+            Some(line.get(..6).unwrap_or(line).trim())
+        }
+        _ => None,
+    }
+}
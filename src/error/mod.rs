@@ -2,9 +2,12 @@
 mod context;
 /// The severity of an error
 mod errorlevel;
+/// Filters to select errors
+mod filter;
 /// An error with all its properties
 mod pdberror;
 
 pub use context::{Context, Position};
 pub use errorlevel::ErrorLevel;
+pub use filter::ErrorFilter;
 pub use pdberror::PDBError;
@@ -16,6 +16,8 @@ pub struct PDBError {
     long_description: String,
     /// The context, in the most general sense this produces output which leads the user to the right place in the code or file
     context: Context,
+    /// The number of times this error occurred, more than one for aggregated errors
+    occurrences: usize,
 }
 
 impl PDBError {
@@ -37,6 +39,7 @@ impl PDBError {
             short_description: short_desc.to_string(),
             long_description: long_descr.to_string(),
             context,
+            occurrences: 1,
         }
     }
 
@@ -45,6 +48,11 @@ impl PDBError {
         self.level
     }
 
+    /// Change the level of the error
+    pub(crate) fn set_level(&mut self, level: ErrorLevel) {
+        self.level = level;
+    }
+
     /// Tests if this errors is breaking with the given strictness level
     pub fn fails(&self, level: StrictnessLevel) -> bool {
         self.level.fails(level)
@@ -64,25 +72,71 @@ impl PDBError {
     pub const fn context(&self) -> &Context {
         &self.context
     }
+
+    /// Gives the number of times this error occurred, this is more than one for aggregated errors
+    /// see [`PDBError::aggregate`].
+    pub const fn occurrences(&self) -> usize {
+        self.occurrences
+    }
+
+    /// Aggregate all errors with the same level and short description into a single error. The
+    /// aggregated error keeps the long description of the first error, shows at most
+    /// `max_contexts` of the contexts as representatives, and counts the number of occurrences.
+    /// The order of the errors is kept, based on the first occurrence of every error.
+    pub fn aggregate(
+        errors: impl IntoIterator<Item = PDBError>,
+        max_contexts: usize,
+    ) -> Vec<PDBError> {
+        let mut groups: Vec<(PDBError, Vec<Context>)> = Vec::new();
+        for error in errors {
+            if let Some((first, contexts)) = groups.iter_mut().find(|(e, _)| {
+                e.level == error.level && e.short_description == error.short_description
+            }) {
+                first.occurrences += error.occurrences;
+                if contexts.len() < max_contexts && error.context != Context::None {
+                    contexts.push(error.context);
+                }
+            } else {
+                let contexts = if error.context == Context::None {
+                    Vec::new()
+                } else {
+                    vec![error.context.clone()]
+                };
+                groups.push((error, contexts));
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(mut error, mut contexts)| {
+                if error.occurrences > 1 {
+                    contexts.truncate(max_contexts);
+                    error.context = match contexts.len() {
+                        0 => Context::None,
+                        1 => contexts.remove(0),
+                        _ => Context::Multiple {
+                            contexts: contexts.into_iter().map(|c| (None, c)).collect(),
+                        },
+                    };
+                }
+                error
+            })
+            .collect()
+    }
 }
 
 impl fmt::Debug for PDBError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}: {}{}\n{}\n",
-            self.level, self.short_description, self.context, self.long_description
-        )
+        fmt::Display::fmt(self, f)
     }
 }
 
 impl fmt::Display for PDBError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}: {}{}\n{}\n",
-            self.level, self.short_description, self.context, self.long_description
-        )
+        write!(f, "{}: {}", self.level, self.short_description)?;
+        if self.occurrences > 1 {
+            write!(f, " (occurred {} times)", self.occurrences)?;
+        }
+        write!(f, "{}\n{}\n", self.context, self.long_description)
     }
 }
 
@@ -159,6 +213,37 @@ mod tests {
         assert_eq!(pos2.column, 13);
     }
 
+    #[test]
+    fn aggregate() {
+        let mut errors: Vec<PDBError> = (1..=100)
+            .map(|i| {
+                PDBError::new(
+                    ErrorLevel::LooseWarning,
+                    "Missing element",
+                    "test",
+                    Context::full_line(i, "ATOM"),
+                )
+            })
+            .collect();
+        errors.push(PDBError::new(
+            ErrorLevel::BreakingError,
+            "No Atoms",
+            "test",
+            Context::none(),
+        ));
+        let aggregated = PDBError::aggregate(errors, 3);
+        assert_eq!(aggregated.len(), 2);
+        assert_eq!(aggregated[0].occurrences(), 100);
+        assert_eq!(aggregated[1].occurrences(), 1);
+        if let Context::Multiple { contexts } = aggregated[0].context() {
+            assert_eq!(contexts.len(), 3);
+        } else {
+            panic!("Aggregated error should have multiple contexts");
+        }
+        assert!(format!("{}", aggregated[0])
+            .starts_with("LooseWarning: Missing element (occurred 100 times)"));
+    }
+
     #[test]
     fn ordering_and_equality() {
         let a = PDBError::new(ErrorLevel::GeneralWarning, "test", "test", Context::none());
@@ -0,0 +1,3432 @@
+use doc_cfg::doc_cfg;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+
+use crate::alignment::Scoring;
+use crate::{
+    reference_tables, Atom, BiologicalAssembly, Chain, Conformer, Context, Element, ErrorLevel,
+    Model, PDBError, Residue, TransformationMatrix, PDB,
+};
+
+/// Gives the distance between the centers of two atoms in Aͦ, see [`Atom::distance`].
+pub fn distance(a: &Atom, b: &Atom) -> f64 {
+    a.distance(b)
+}
+
+/// Gives the angle between the centers of three atoms in degrees, with `b` as the vertex, see
+/// [`Atom::angle`].
+pub fn angle(a: &Atom, b: &Atom, c: &Atom) -> f64 {
+    a.angle(b, c)
+}
+
+/// Gives the signed dihedral angle between the centers of four atoms in degrees, in the range
+/// (-180, 180]. The sign follows the IUPAC convention: looking along the bond from `b` to `c`,
+/// a clockwise rotation of `a` onto `d` is positive. This is the convention used for backbone
+/// (phi/psi) and side chain torsion angles, in contrast to [`Atom::dihedral`] which gives the
+/// unsigned angle between the planes.
+#[allow(clippy::similar_names)]
+pub fn dihedral(a: &Atom, b: &Atom, c: &Atom, d: &Atom) -> f64 {
+    let (a, b, c, d) = (a.pos(), b.pos(), c.pos(), d.pos());
+    let b1 = [b.0 - a.0, b.1 - a.1, b.2 - a.2];
+    let b2 = [c.0 - b.0, c.1 - b.1, c.2 - b.2];
+    let b3 = [d.0 - c.0, d.1 - c.1, d.2 - c.2];
+
+    let n1 = cross(b1, b2);
+    let n2 = cross(b2, b3);
+    let length_b2 = dot(b2, b2).sqrt();
+    let m1 = cross(
+        n1,
+        [b2[0] / length_b2, b2[1] / length_b2, b2[2] / length_b2],
+    );
+
+    (-dot(m1, n2)).atan2(dot(n1, n2)).to_degrees()
+}
+
+/// The cross product of two vectors
+pub(crate) fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// The dot product of two vectors
+pub(crate) fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Select a single atom with a selection string of the form `chain/residue/atom`, for example
+/// `A/42/CA`. The residue can include an insertion code (`A/42B/CA`). If the selection matches
+/// multiple atoms (for example in multiple models or alternative locations), the first one is
+/// returned.
+///
+/// # Errors
+/// Fails if the selection string is invalid or does not match any atom.
+pub fn select<'a>(pdb: &'a PDB, selection: &str) -> Result<&'a Atom, PDBError> {
+    let invalid = |long_description: &str| {
+        PDBError::new(
+            ErrorLevel::BreakingError,
+            "Invalid selection",
+            long_description,
+            Context::show(selection),
+        )
+    };
+    let parts: Vec<&str> = selection.split('/').map(str::trim).collect();
+    let (chain, residue, atom) = match parts.as_slice() {
+        [chain, residue, atom] => (chain, residue, atom),
+        _ => {
+            return Err(invalid(
+                "A selection should be of the form 'chain/residue/atom'.",
+            ))
+        }
+    };
+    let split = residue
+        .char_indices()
+        .find(|(index, c)| !(c.is_ascii_digit() || (*index == 0 && *c == '-')))
+        .map_or(residue.len(), |(index, _)| index);
+    let serial_number = residue[..split].parse().map_err(|_| {
+        invalid("The residue should be a serial number with an optional insertion code.")
+    })?;
+    let insertion_code = Some(&residue[split..]).filter(|code| !code.is_empty());
+
+    pdb.chains()
+        .filter(|c| c.id() == *chain)
+        .flat_map(Chain::residues)
+        .filter(|r| r.id() == (serial_number, insertion_code))
+        .flat_map(Residue::atoms)
+        .find(|a| a.name() == *atom)
+        .ok_or_else(|| {
+            PDBError::new(
+                ErrorLevel::BreakingError,
+                "Empty selection",
+                "No atom matches this selection.",
+                Context::show(selection),
+            )
+        })
+}
+
+/// A dense matrix of distances between two sets of atoms or residues, see [`distance_matrix`].
+/// The values are stored in row major order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix {
+    /// The number of rows, the size of the first set
+    rows: usize,
+    /// The number of columns, the size of the second set
+    columns: usize,
+    /// The distances in row major order
+    values: Vec<f64>,
+}
+
+impl DistanceMatrix {
+    /// The number of rows, which is the size of the first set.
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns, which is the size of the second set.
+    pub const fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Get the distance between item `row` of the first set and item `column` of the second
+    /// set, or `None` if either is out of bounds.
+    pub fn get(&self, row: usize, column: usize) -> Option<f64> {
+        (row < self.rows && column < self.columns).then(|| self.values[row * self.columns + column])
+    }
+
+    /// Get the distances of a single row, or `None` if the row is out of bounds.
+    pub fn row(&self, row: usize) -> Option<&[f64]> {
+        (row < self.rows).then(|| &self.values[row * self.columns..(row + 1) * self.columns])
+    }
+
+    /// Get all distances in row major order.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Get all distances in row major order.
+    pub fn into_vec(self) -> Vec<f64> {
+        self.values
+    }
+}
+
+/// The positions of the given atoms, gathered once for faster distance calculations
+fn positions<'a>(atoms: impl IntoIterator<Item = &'a Atom>) -> Vec<(f64, f64, f64)> {
+    atoms.into_iter().map(Atom::pos).collect()
+}
+
+/// The distance between two positions
+fn position_distance(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2) + (a.2 - b.2).powi(2)).sqrt()
+}
+
+/// The minimal distance between any two positions of the given sets
+fn min_distance(a: &[(f64, f64, f64)], b: &[(f64, f64, f64)]) -> f64 {
+    a.iter()
+        .flat_map(|a| b.iter().map(move |b| position_distance(*a, *b)))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Calculate the distances between all atoms of the first set (rows) and all atoms of the
+/// second set (columns) in Aͦ. The sets can be gathered from a structure with, for example,
+/// [`PDB::atoms`] or [`PDB::find`].
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let ca: Vec<&Atom> = pdb.atoms().filter(|a| a.name() == "CA").collect();
+/// let matrix = geometry::distance_matrix(&ca, &ca);
+/// assert_eq!(matrix.rows(), 76);
+/// assert_eq!(matrix.get(3, 3), Some(0.0));
+/// ```
+pub fn distance_matrix(a: &[&Atom], b: &[&Atom]) -> DistanceMatrix {
+    let (a, b) = (positions(a.iter().copied()), positions(b.iter().copied()));
+    DistanceMatrix {
+        rows: a.len(),
+        columns: b.len(),
+        values: a
+            .iter()
+            .flat_map(|a| b.iter().map(move |b| position_distance(*a, *b)))
+            .collect(),
+    }
+}
+
+/// Calculate the distances between all atoms of the first set (rows) and all atoms of the
+/// second set (columns) in Aͦ in parallel, see [`distance_matrix`].
+#[doc_cfg(feature = "rayon")]
+pub fn par_distance_matrix(a: &[&Atom], b: &[&Atom]) -> DistanceMatrix {
+    let (a, b) = (positions(a.iter().copied()), positions(b.iter().copied()));
+    let mut values = vec![0.0; a.len() * b.len()];
+    if !b.is_empty() {
+        values
+            .par_chunks_mut(b.len())
+            .zip(a.par_iter())
+            .for_each(|(row, a)| {
+                for (value, b) in row.iter_mut().zip(&b) {
+                    *value = position_distance(*a, *b);
+                }
+            });
+    }
+    DistanceMatrix {
+        rows: a.len(),
+        columns: b.len(),
+        values,
+    }
+}
+
+/// Calculate the condensed distance matrix of the given atoms in Aͦ: the distances between all
+/// pairs `i < j`, in the order `(0, 1), (0, 2), .., (1, 2), ..`, as used by SciPy. This takes
+/// half the memory of the full symmetric matrix.
+pub fn condensed_distance_matrix(atoms: &[&Atom]) -> Vec<f64> {
+    let positions = positions(atoms.iter().copied());
+    positions
+        .iter()
+        .enumerate()
+        .flat_map(|(index, a)| {
+            positions[index + 1..]
+                .iter()
+                .map(move |b| position_distance(*a, *b))
+        })
+        .collect()
+}
+
+/// Calculate the minimal distances between any atoms of the residues of the first set (rows)
+/// and any atoms of the residues of the second set (columns) in Aͦ. Residues without atoms have
+/// infinite distances.
+pub fn residue_distance_matrix(a: &[&Residue], b: &[&Residue]) -> DistanceMatrix {
+    let (a, b): (Vec<_>, Vec<_>) = (
+        a.iter().map(|r| positions(r.atoms())).collect(),
+        b.iter().map(|r| positions(r.atoms())).collect(),
+    );
+    DistanceMatrix {
+        rows: a.len(),
+        columns: b.len(),
+        values: a
+            .iter()
+            .flat_map(|a| b.iter().map(move |b| min_distance(a, b)))
+            .collect(),
+    }
+}
+
+/// Calculate the minimal distances between the residues of the first set (rows) and the residues
+/// of the second set (columns) in Aͦ in parallel, see [`residue_distance_matrix`].
+#[doc_cfg(feature = "rayon")]
+pub fn par_residue_distance_matrix(a: &[&Residue], b: &[&Residue]) -> DistanceMatrix {
+    let (a, b): (Vec<_>, Vec<_>) = (
+        a.par_iter().map(|r| positions(r.atoms())).collect(),
+        b.par_iter().map(|r| positions(r.atoms())).collect(),
+    );
+    DistanceMatrix {
+        rows: a.len(),
+        columns: b.len(),
+        values: a
+            .par_iter()
+            .flat_map_iter(|a| b.iter().map(move |b| min_distance(a, b)))
+            .collect(),
+    }
+}
+
+/// Find the residues in contact at the interface of two chains: all pairs of residues, one from
+/// each chain, which have atoms within `cutoff` Aͦ of each other. The pairs are given with the
+/// minimal distance between their atoms, sorted by the position of the residues in the chains.
+/// A spatial index is used so this scales to large chains.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let chain = pdb.chain(0).unwrap();
+/// for (a, b, distance) in geometry::interface_residues(chain, chain, 4.0) {
+///     assert!(distance <= 4.0);
+/// }
+/// ```
+#[doc_cfg(feature = "rstar")]
+pub fn interface_residues<'a>(
+    chain_a: &'a Chain,
+    chain_b: &'a Chain,
+    cutoff: f64,
+) -> Vec<(&'a Residue, &'a Residue, f64)> {
+    use rstar::primitives::GeomWithData;
+    use std::collections::BTreeMap;
+
+    let residues_b: Vec<&Residue> = chain_b.residues().collect();
+    let tree = rstar::RTree::bulk_load(
+        residues_b
+            .iter()
+            .enumerate()
+            .flat_map(|(index, residue)| {
+                residue.atoms().map(move |atom| {
+                    let (x, y, z) = atom.pos();
+                    GeomWithData::new([x, y, z], index)
+                })
+            })
+            .collect(),
+    );
+
+    let mut contacts = BTreeMap::new();
+    for (index_a, residue) in chain_a.residues().enumerate() {
+        for atom in residue.atoms() {
+            let (x, y, z) = atom.pos();
+            for point in tree.locate_within_distance([x, y, z], cutoff * cutoff) {
+                let [px, py, pz] = *point.geom();
+                let distance = position_distance(atom.pos(), (px, py, pz));
+                contacts
+                    .entry((index_a, point.data))
+                    .and_modify(|d: &mut f64| *d = d.min(distance))
+                    .or_insert(distance);
+            }
+        }
+    }
+
+    let residues_a: Vec<&Residue> = chain_a.residues().collect();
+    contacts
+        .into_iter()
+        .map(|((a, b), distance)| (residues_a[a], residues_b[b], distance))
+        .collect()
+}
+
+/// The radius of the solvent probe (water) used for solvent accessible surface areas in Aͦ
+pub const PROBE_RADIUS: f64 = 1.4;
+
+/// The number of points placed on the sphere around each atom in [`sasa`]
+const SPHERE_POINTS: usize = 100;
+
+/// The radius used for atoms without a known element or van der Waals radius in Aͦ
+const DEFAULT_RADIUS: f64 = 1.8;
+
+/// The van der Waals radius of the given atom, or a default value if it is not known
+fn radius(atom: &Atom) -> f64 {
+    atom.element()
+        .and_then(|element| element.atomic_radius().van_der_waals)
+        .unwrap_or(DEFAULT_RADIUS)
+}
+
+/// Evenly distributed points on the unit sphere, using the golden section spiral
+#[allow(clippy::cast_precision_loss)]
+fn sphere_points() -> Vec<[f64; 3]> {
+    let increment = std::f64::consts::PI * (3.0 - 5.0_f64.sqrt());
+    (0..SPHERE_POINTS)
+        .map(|index| {
+            let y = 1.0 - 2.0 * (index as f64 + 0.5) / SPHERE_POINTS as f64;
+            let r = (1.0 - y * y).sqrt();
+            let phi = index as f64 * increment;
+            [phi.cos() * r, y, phi.sin() * r]
+        })
+        .collect()
+}
+
+/// Calculate the solvent accessible surface area (SASA) of each of the given atoms in Aͦ², with
+/// the Shrake-Rupley algorithm. Only the given atoms are taken into account as possible
+/// occluders, so the SASA of a single chain can be calculated by only giving its atoms. The
+/// van der Waals radii of the elements are used, with a probe radius of [`PROBE_RADIUS`].
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let atoms: Vec<&Atom> = pdb.atoms().collect();
+/// let total: f64 = geometry::sasa(&atoms).iter().sum();
+/// assert!(total > 4000.0 && total < 6000.0);
+/// ```
+#[allow(clippy::cast_precision_loss)]
+pub fn sasa(atoms: &[&Atom]) -> Vec<f64> {
+    accessible_points(atoms)
+        .iter()
+        .zip(atoms)
+        .map(|(points, atom)| {
+            let radius = radius(atom) + PROBE_RADIUS;
+            4.0 * std::f64::consts::PI * radius * radius * points.len() as f64
+                / SPHERE_POINTS as f64
+        })
+        .collect()
+}
+
+/// Get a function giving the cell containing a position, for a grid with the given cell size
+pub(crate) fn grid_cell(cell_size: f64) -> impl Fn((f64, f64, f64)) -> (i64, i64, i64) {
+    #[allow(clippy::cast_possible_truncation)]
+    move |p: (f64, f64, f64)| {
+        (
+            (p.0 / cell_size).floor() as i64,
+            (p.1 / cell_size).floor() as i64,
+            (p.2 / cell_size).floor() as i64,
+        )
+    }
+}
+
+/// Get the solvent accessible points on the sphere around each of the given atoms, these are the
+/// positions of the centre of the solvent probe where it touches the atom without overlapping
+/// any other atom. See [`sasa`] for the way these are determined.
+fn accessible_points(atoms: &[&Atom]) -> Vec<Vec<(f64, f64, f64)>> {
+    let radii: Vec<f64> = atoms.iter().map(|a| radius(a) + PROBE_RADIUS).collect();
+    let positions = positions(atoms.iter().copied());
+    let max_radius = radii.iter().copied().fold(0.0, f64::max);
+    let cell = grid_cell(2.0 * max_radius);
+
+    // Place all atoms in a grid to quickly find the neighbours of each atom
+    let mut grid: FxHashMap<(i64, i64, i64), Vec<usize>> = FxHashMap::default();
+    for (index, position) in positions.iter().enumerate() {
+        grid.entry(cell(*position)).or_default().push(index);
+    }
+
+    let sphere = sphere_points();
+    positions
+        .iter()
+        .zip(&radii)
+        .enumerate()
+        .map(|(index, (position, radius))| {
+            let (x, y, z) = cell(*position);
+            let neighbours: Vec<usize> = (-1..=1)
+                .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+                .filter_map(|(dx, dy, dz)| grid.get(&(x + dx, y + dy, z + dz)))
+                .flatten()
+                .copied()
+                .filter(|other| {
+                    *other != index
+                        && position_distance(*position, positions[*other]) < radius + radii[*other]
+                })
+                .collect();
+            sphere
+                .iter()
+                .map(|point| {
+                    (
+                        position.0 + point[0] * radius,
+                        position.1 + point[1] * radius,
+                        position.2 + point[2] * radius,
+                    )
+                })
+                .filter(|point| {
+                    neighbours
+                        .iter()
+                        .all(|other| position_distance(*point, positions[*other]) >= radii[*other])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The size of the grid cells used to find the nearest surface point in [`depth`] in Aͦ
+const DEPTH_GRID_SPACING: f64 = 4.0;
+
+/// Calculate the depth of each of the given atoms in Aͦ, the distance from the atom centre to the
+/// nearest point of the molecular surface. The surface is derived from the solvent accessible
+/// points found for [`sasa`]: the depth is the distance to the nearest position of the centre of
+/// the solvent probe minus the [`PROBE_RADIUS`]. Atoms at the surface have a depth close to
+/// their van der Waals radius, while buried atoms are deeper. Only the given atoms are taken
+/// into account to define the surface. If there is no accessible surface at all, every depth is
+/// `f64::INFINITY`.
+pub fn depth(atoms: &[&Atom]) -> Vec<f64> {
+    let surface: Vec<(f64, f64, f64)> = accessible_points(atoms).into_iter().flatten().collect();
+    let cell = grid_cell(DEPTH_GRID_SPACING);
+    let mut grid: FxHashMap<(i64, i64, i64), Vec<usize>> = FxHashMap::default();
+    for (index, point) in surface.iter().enumerate() {
+        grid.entry(cell(*point)).or_default().push(index);
+    }
+    // The number of shells of cells to search before every surface point has been seen
+    let extent = grid
+        .keys()
+        .flat_map(|(x, y, z)| [x.abs(), y.abs(), z.abs()])
+        .max()
+        .unwrap_or(0);
+
+    atoms
+        .iter()
+        .map(|atom| {
+            let position = atom.pos();
+            let (x, y, z) = cell(position);
+            let mut nearest = f64::INFINITY;
+            // Search ever larger shells of cells around the atom, until no closer point can exist
+            for shell in 0..=(extent + x.abs().max(y.abs()).max(z.abs()) + 1) {
+                for dx in -shell..=shell {
+                    for dy in -shell..=shell {
+                        for dz in -shell..=shell {
+                            if dx.abs().max(dy.abs()).max(dz.abs()) != shell {
+                                continue;
+                            }
+                            for index in grid.get(&(x + dx, y + dy, z + dz)).into_iter().flatten() {
+                                nearest = nearest.min(position_distance(position, surface[*index]));
+                            }
+                        }
+                    }
+                }
+                #[allow(clippy::cast_precision_loss)]
+                if nearest <= shell as f64 * DEPTH_GRID_SPACING {
+                    break;
+                }
+            }
+            nearest - PROBE_RADIUS
+        })
+        .collect()
+}
+
+/// Calculate the depth of each of the given residues in Aͦ, the average [`depth`] of its atoms,
+/// a measure of burial complementary to the solvent accessible surface area. The atoms of all
+/// given residues are used to define the surface, and the residues are returned in the order
+/// they were given. Residues without atoms are left out.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.chain(0).unwrap().residues().collect();
+/// let depths = geometry::residue_depth(&residues);
+/// let (deepest, depth) = depths.iter().fold(depths[0], |a, b| if b.1 > a.1 { *b } else { a });
+/// println!("The deepest residue is {} at {:.1} Å", deepest.name().unwrap(), depth);
+/// ```
+pub fn residue_depth<'a>(residues: &[&'a Residue]) -> Vec<(&'a Residue, f64)> {
+    let atoms: Vec<&Atom> = residues.iter().flat_map(|r| r.atoms()).collect();
+    let depths = depth(&atoms);
+    let mut result = Vec::with_capacity(residues.len());
+    let mut start = 0;
+    for residue in residues {
+        let end = start + residue.atom_count();
+        if end > start {
+            #[allow(clippy::cast_precision_loss)]
+            let mean = depths[start..end].iter().sum::<f64>() / (end - start) as f64;
+            result.push((*residue, mean));
+        }
+        start = end;
+    }
+    result
+}
+
+/// The relative solvent accessibility below which a residue is commonly classified as buried,
+/// see [`Exposure::classify`]
+pub const BURIED_MAX_RELATIVE_SASA: f64 = 0.25;
+
+/// Whether a residue is buried in the structure or exposed to the solvent, see [`exposure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exposure {
+    /// The relative solvent accessibility is below the threshold
+    Buried,
+    /// The relative solvent accessibility is at or above the threshold
+    Exposed,
+}
+
+impl Exposure {
+    /// Classify a residue based on its relative solvent accessibility (see [`relative_sasa`]) and
+    /// the given threshold, for example [`BURIED_MAX_RELATIVE_SASA`].
+    pub fn classify(relative_sasa: f64, threshold: f64) -> Self {
+        if relative_sasa < threshold {
+            Exposure::Buried
+        } else {
+            Exposure::Exposed
+        }
+    }
+}
+
+/// Calculate the relative solvent accessibility (RSA) of each of the given residues, the SASA of
+/// its heavy atoms divided by the maximal SASA of that amino acid (Tien et al. 2013). The atoms
+/// of all given residues are used as occluders, see [`sasa`]. Only the standard amino acids are
+/// returned, in the order they were given. Values can be slightly above 1 for very exposed
+/// residues, for example at the termini.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.chain(0).unwrap().residues().collect();
+/// let relative = geometry::relative_sasa(&residues);
+/// assert_eq!(relative.len(), 76);
+/// ```
+pub fn relative_sasa<'a>(residues: &[&'a Residue]) -> Vec<(&'a Residue, f64)> {
+    let heavy = |residue: &'a Residue| residue.atoms().filter(|a| a.element() != Some(&Element::H));
+    let atoms: Vec<&Atom> = residues.iter().flat_map(|r| heavy(r)).collect();
+    let areas = sasa(&atoms);
+    let mut result = Vec::new();
+    let mut start = 0;
+    for residue in residues {
+        let end = start + heavy(residue).count();
+        if let Some(max) = residue.name().and_then(reference_tables::max_sasa) {
+            result.push((*residue, areas[start..end].iter().sum::<f64>() / max));
+        }
+        start = end;
+    }
+    result
+}
+
+/// Classify each of the given residues as buried or exposed based on its relative solvent
+/// accessibility and the given threshold, see [`relative_sasa`] and [`Exposure::classify`].
+/// Only the standard amino acids are returned, in the order they were given, with their
+/// relative solvent accessibility.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.chain(0).unwrap().residues().collect();
+/// let buried = geometry::exposure(&residues, geometry::BURIED_MAX_RELATIVE_SASA)
+///     .into_iter()
+///     .filter(|(_, _, exposure)| *exposure == geometry::Exposure::Buried)
+///     .count();
+/// assert!(buried > 10 && buried < 50);
+/// ```
+pub fn exposure<'a>(residues: &[&'a Residue], threshold: f64) -> Vec<(&'a Residue, f64, Exposure)> {
+    relative_sasa(residues)
+        .into_iter()
+        .map(|(residue, relative)| (residue, relative, Exposure::classify(relative, threshold)))
+        .collect()
+}
+
+/// The surface area buried in the interface between two sets of residues, see [`bsa`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuriedSurface<'a> {
+    /// The buried surface area of the interface in Aͦ², (SASA(A) + SASA(B) - SASA(AB)) / 2
+    pub total: f64,
+    /// The surface area of each residue which is buried by the other set in Aͦ², only for the
+    /// residues with any buried surface. The sum of these values is twice the total.
+    pub residues: Vec<(&'a Residue, f64)>,
+}
+
+/// Calculate the buried surface area (BSA) between two sets of residues, for example two chains,
+/// in total and per residue. The total is (SASA(A) + SASA(B) - SASA(AB)) / 2, a standard metric
+/// for the size of an interface. See [`sasa`] for the way the surface area is calculated.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1yyf.pdb").unwrap();
+/// let a: Vec<&Residue> = pdb.chains().find(|c| c.id() == "A").unwrap().residues().collect();
+/// let b: Vec<&Residue> = pdb.chains().find(|c| c.id() == "B").unwrap().residues().collect();
+/// let interface = geometry::bsa(&a, &b);
+/// println!("The interface is {:.0} Å²", interface.total);
+/// ```
+pub fn bsa<'a>(a: &[&'a Residue], b: &[&'a Residue]) -> BuriedSurface<'a> {
+    let atoms = |residues: &[&'a Residue]| -> Vec<&'a Atom> {
+        residues.iter().flat_map(|r| r.atoms()).collect()
+    };
+    let (atoms_a, atoms_b) = (atoms(a), atoms(b));
+    let complex: Vec<&Atom> = atoms_a.iter().chain(&atoms_b).copied().collect();
+    let separate: Vec<f64> = sasa(&atoms_a).into_iter().chain(sasa(&atoms_b)).collect();
+    let buried: Vec<f64> = separate
+        .iter()
+        .zip(sasa(&complex))
+        .map(|(separate, complex)| separate - complex)
+        .collect();
+
+    let mut residues = Vec::new();
+    let mut start = 0;
+    for residue in a.iter().chain(b) {
+        let end = start + residue.atom_count();
+        let area: f64 = buried[start..end].iter().sum();
+        if area > 0.0 {
+            residues.push((*residue, area));
+        }
+        start = end;
+    }
+    BuriedSurface {
+        total: buried.iter().sum::<f64>() / 2.0,
+        residues,
+    }
+}
+
+/// The minimal buried surface area in Aͦ² for an interface to be classified as biological when
+/// the structure does not define any biological assemblies, following Ponstingl et al. (2000).
+pub const BIOLOGICAL_INTERFACE_AREA: f64 = 856.0;
+
+/// The maximal distance between atoms of two chains in contact in Aͦ, see [`classify_interfaces`]
+const INTERFACE_CONTACT_DISTANCE: f64 = 4.0;
+
+/// The maximal distance between the centres of two copies of a chain to be considered the same
+/// copy in Aͦ, used to match symmetry mates to the copies in a biological assembly
+const SAME_COPY_DISTANCE: f64 = 1.0;
+
+/// The likely nature of an interface between two chains, see [`classify_interfaces`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceKind {
+    /// The interface is part of the biologically relevant assembly
+    Biological,
+    /// The interface is only formed by the packing of the molecules in the crystal
+    Crystal,
+}
+
+/// An interface between two chains, see [`classify_interfaces`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainInterface {
+    /// The identifiers of the chains, the second chain is transformed by the symmetry operation
+    pub chains: (String, String),
+    /// The symmetry operation applied to the second chain in the notation used in PDB files, the
+    /// number of the operator followed by the translations along a, b, and c plus five (`7555`
+    /// is operator 7 without translation). `None` for interfaces within the asymmetric unit.
+    pub symmetry_operation: Option<String>,
+    /// The buried surface area of the interface in Aͦ², see [`bsa`]
+    pub buried_area: f64,
+    /// If both chains are part of the same biological assembly in the placement of this
+    /// interface, `None` if the structure does not define any biological assemblies
+    pub in_assembly: Option<bool>,
+    /// The classification of this interface
+    pub kind: InterfaceKind,
+}
+
+/// Find and classify all interfaces between the chains of the first model of the given
+/// structure as likely biological or crystal packing. The interfaces within the asymmetric unit
+/// and, if the unit cell and space group are given, with the symmetry mates in the neighbouring
+/// unit cells are considered. Two chains form an interface if they have any atoms within 4 Aͦ,
+/// waters are ignored. If the structure defines biological assemblies (REMARK 350, see
+/// [`PDB::biological_assemblies`]) an interface is biological if the placement of the two
+/// chains is found in any of the assemblies. Otherwise it is biological if its buried surface
+/// area is at least [`BIOLOGICAL_INTERFACE_AREA`]. Interfaces of a chain with its own symmetry
+/// mates are only reported once, not also for the inverse operation.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// for interface in geometry::classify_interfaces(&pdb) {
+///     // Ubiquitin is a monomer, so all interfaces with symmetry mates are small crystal contacts
+///     assert_eq!(interface.kind, geometry::InterfaceKind::Crystal);
+/// }
+/// ```
+pub fn classify_interfaces(pdb: &PDB) -> Vec<ChainInterface> {
+    let model = match pdb.models().next() {
+        Some(model) => model,
+        None => return Vec::new(),
+    };
+    let chains: Vec<Chain> = model
+        .chains()
+        .map(|chain| {
+            let mut chain = chain.clone();
+            chain.remove_residues_by(|r| r.conformers().all(Conformer::is_water));
+            chain
+        })
+        .filter(|chain| chain.atom_count() > 0)
+        .collect();
+    let spheres: Vec<((f64, f64, f64), f64)> = chains
+        .iter()
+        .map(|chain| {
+            let positions = positions(chain.atoms());
+            let centre = centroid(&positions);
+            let radius = positions
+                .iter()
+                .map(|p| position_distance(centre, *p))
+                .fold(0.0, f64::max);
+            (centre, radius)
+        })
+        .collect();
+    let assemblies = pdb.biological_assemblies();
+    let operations = crystal_operations(pdb);
+
+    let mut interfaces = Vec::new();
+    for (i, chain_a) in chains.iter().enumerate() {
+        let residues_a: Vec<&Residue> = chain_a.residues().collect();
+        let mut self_operations: Vec<&TransformationMatrix> = Vec::new();
+        for (j, chain_b) in chains.iter().enumerate().skip(i) {
+            for (code, operation) in &operations {
+                if i == j && code.is_none() {
+                    continue;
+                }
+                let (centre_a, radius_a) = spheres[i];
+                let (centre_b, radius_b) = spheres[j];
+                let placed = operation.apply(centre_b);
+                if position_distance(centre_a, placed)
+                    > radius_a + radius_b + INTERFACE_CONTACT_DISTANCE
+                {
+                    continue;
+                }
+                // The interface with the mate from the inverse operation is the same interface
+                if i == j
+                    && self_operations.iter().any(|other| {
+                        position_distance(other.apply(placed), centre_a) < SAME_COPY_DISTANCE
+                    })
+                {
+                    continue;
+                }
+                let mut mate = chain_b.clone();
+                mate.apply_transformation(operation);
+                if !in_contact(chain_a, &mate, INTERFACE_CONTACT_DISTANCE) {
+                    continue;
+                }
+                if i == j {
+                    self_operations.push(operation);
+                }
+                let residues_b: Vec<&Residue> = mate.residues().collect();
+                let buried_area = bsa(&residues_a, &residues_b).total;
+                let in_assembly = (!assemblies.is_empty()).then(|| {
+                    assemblies.iter().any(|assembly| {
+                        in_same_assembly(assembly, (chain_a.id(), chain_b.id()), centre_b, placed)
+                    })
+                });
+                let biological = in_assembly.unwrap_or(buried_area >= BIOLOGICAL_INTERFACE_AREA);
+                interfaces.push(ChainInterface {
+                    chains: (chain_a.id().to_string(), chain_b.id().to_string()),
+                    symmetry_operation: code.clone(),
+                    buried_area,
+                    in_assembly,
+                    kind: if biological {
+                        InterfaceKind::Biological
+                    } else {
+                        InterfaceKind::Crystal
+                    },
+                });
+            }
+        }
+    }
+    interfaces
+}
+
+/// Get the operations placing the symmetry mates in the unit cell and its direct neighbours in
+/// orthogonal coordinates, with the symmetry operation code as used in PDB files. The first
+/// operation is the identity, without code. If the unit cell or space group is not given only
+/// the identity is returned.
+fn crystal_operations(pdb: &PDB) -> Vec<(Option<String>, TransformationMatrix)> {
+    let mut operations = vec![(None, TransformationMatrix::identity())];
+    let (unit_cell, symmetry) = match (&pdb.unit_cell, &pdb.symmetry) {
+        (Some(unit_cell), Some(symmetry)) => (unit_cell, symmetry),
+        _ => return operations,
+    };
+    let fractionalization = unit_cell.fractionalization_matrix();
+    let orthogonalization = unit_cell.orthogonalization_matrix();
+    for (index, operator) in symmetry.transformations().iter().enumerate() {
+        for a in -1..=1 {
+            for b in -1..=1 {
+                for c in -1..=1 {
+                    if index == 0 && (a, b, c) == (0, 0, 0) {
+                        continue;
+                    }
+                    let translation =
+                        TransformationMatrix::translation(f64::from(a), f64::from(b), f64::from(c));
+                    operations.push((
+                        Some(format!("{}{}{}{}", index + 1, 5 + a, 5 + b, 5 + c)),
+                        fractionalization
+                            .combine(operator)
+                            .combine(&translation)
+                            .combine(&orthogonalization),
+                    ));
+                }
+            }
+        }
+    }
+    operations
+}
+
+/// Check if any atoms of the two chains are within the given distance of each other
+fn in_contact(a: &Chain, b: &Chain, distance: f64) -> bool {
+    let cell = grid_cell(distance);
+    let positions = positions(a.atoms());
+    let mut grid: FxHashMap<(i64, i64, i64), Vec<usize>> = FxHashMap::default();
+    for (index, position) in positions.iter().enumerate() {
+        grid.entry(cell(*position)).or_default().push(index);
+    }
+    b.atoms().any(|atom| {
+        let (x, y, z) = cell(atom.pos());
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter_map(|(dx, dy, dz)| grid.get(&(x + dx, y + dy, z + dz)))
+            .flatten()
+            .any(|index| position_distance(positions[*index], atom.pos()) <= distance)
+    })
+}
+
+/// Check if the given assembly contains a copy of the first chain and a copy of the second
+/// chain placed relative to each other as the given placement of the second chain (with its
+/// centre at `placed` instead of `centre`) is placed relative to the first chain.
+fn in_same_assembly(
+    assembly: &BiologicalAssembly,
+    (a, b): (&str, &str),
+    centre: (f64, f64, f64),
+    placed: (f64, f64, f64),
+) -> bool {
+    let transformations = |id: &str| -> Vec<&TransformationMatrix> {
+        assembly
+            .parts
+            .iter()
+            .filter(|part| part.chains.iter().any(|chain| chain == id))
+            .flat_map(|part| part.transformations.iter())
+            .collect()
+    };
+    let copies_b: Vec<(f64, f64, f64)> = transformations(b)
+        .iter()
+        .map(|transformation| transformation.apply(centre))
+        .collect();
+    transformations(a).iter().any(|transformation| {
+        let expected = transformation.apply(placed);
+        copies_b
+            .iter()
+            .any(|copy| position_distance(*copy, expected) < SAME_COPY_DISTANCE)
+    })
+}
+
+/// A candidate pocket (cavity) on the surface of a structure, see [`pockets`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pocket<'a> {
+    /// The centre of the pocket in Aͦ
+    pub centroid: (f64, f64, f64),
+    /// The approximate volume of the pocket in Aͦ³
+    pub volume: f64,
+    /// The grid points making up the pocket
+    pub points: Vec<(f64, f64, f64)>,
+    /// The residues lining the pocket, in the order they were given
+    pub residues: Vec<&'a Residue>,
+}
+
+/// The spacing of the grid used for pocket detection in Aͦ
+const POCKET_GRID_SPACING: f64 = 1.0;
+
+/// The maximal distance searched for protein atoms from a grid point in Aͦ
+const POCKET_RAY_LENGTH: f64 = 8.0;
+
+/// The minimal number of directions (out of 14) in which a grid point has to be enclosed by
+/// protein atoms to be part of a pocket
+const POCKET_MIN_BURIEDNESS: usize = 10;
+
+/// The minimal number of grid points for a pocket
+const POCKET_MIN_POINTS: usize = 10;
+
+/// Detect pockets in the structure formed by the given residues with a grid based algorithm,
+/// similar to LIGSITE. A grid is placed over the structure, and every grid point where a solvent
+/// probe fits is tested for how enclosed it is by looking for protein atoms in 14 directions
+/// (along the axes and the cube diagonals). Points enclosed in most directions are clustered
+/// into pockets, which are returned sorted from large to small. The results are approximate and
+/// meant as a quick screen, not as a replacement for dedicated tools.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// for pocket in geometry::pockets(&residues) {
+///     println!("Pocket of {:.0} Å³ lined by {} residues", pocket.volume, pocket.residues.len());
+/// }
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn pockets<'a>(residues: &[&'a Residue]) -> Vec<Pocket<'a>> {
+    /// The state of a grid point
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum State {
+        /// A solvent probe fits here
+        Free,
+        /// Too close to an atom for a solvent probe
+        Excluded,
+        /// Inside an atom
+        Occupied,
+    }
+
+    let atoms: Vec<(usize, (f64, f64, f64), f64)> = residues
+        .iter()
+        .enumerate()
+        .flat_map(|(index, residue)| residue.atoms().map(move |a| (index, a.pos(), radius(a))))
+        .collect();
+    if atoms.is_empty() {
+        return Vec::new();
+    }
+
+    // Set up the grid over the bounding box of the structure
+    let margin = POCKET_GRID_SPACING + PROBE_RADIUS;
+    let min = atoms
+        .iter()
+        .fold((f64::MAX, f64::MAX, f64::MAX), |m, (_, p, _)| {
+            (m.0.min(p.0), m.1.min(p.1), m.2.min(p.2))
+        });
+    let max = atoms
+        .iter()
+        .fold((f64::MIN, f64::MIN, f64::MIN), |m, (_, p, _)| {
+            (m.0.max(p.0), m.1.max(p.1), m.2.max(p.2))
+        });
+    let origin = (min.0 - margin, min.1 - margin, min.2 - margin);
+    let size =
+        |low: f64, high: f64| ((high - low + 2.0 * margin) / POCKET_GRID_SPACING) as usize + 1;
+    let dims = (size(min.0, max.0), size(min.1, max.1), size(min.2, max.2));
+    let index = |x: usize, y: usize, z: usize| (x * dims.1 + y) * dims.2 + z;
+    let position = |x: usize, y: usize, z: usize| {
+        (
+            origin.0 + x as f64 * POCKET_GRID_SPACING,
+            origin.1 + y as f64 * POCKET_GRID_SPACING,
+            origin.2 + z as f64 * POCKET_GRID_SPACING,
+        )
+    };
+    // All grid points within the given distance of the given position
+    let around = |p: (f64, f64, f64), distance: f64| {
+        let range = |centre: f64, low: f64, dim: usize| {
+            let from = ((centre - distance - low) / POCKET_GRID_SPACING)
+                .floor()
+                .max(0.0) as usize;
+            let to =
+                (((centre + distance - low) / POCKET_GRID_SPACING).ceil() as usize).min(dim - 1);
+            from..=to
+        };
+        let (rx, ry, rz) = (
+            range(p.0, origin.0, dims.0),
+            range(p.1, origin.1, dims.1),
+            range(p.2, origin.2, dims.2),
+        );
+        rx.flat_map(move |x| {
+            let rz = rz.clone();
+            ry.clone()
+                .flat_map(move |y| rz.clone().map(move |z| (x, y, z)))
+        })
+        .filter(move |(x, y, z)| position_distance(p, position(*x, *y, *z)) < distance)
+    };
+
+    let mut grid = vec![State::Free; dims.0 * dims.1 * dims.2];
+    for (_, p, r) in &atoms {
+        for (x, y, z) in around(*p, r + PROBE_RADIUS) {
+            let point = &mut grid[index(x, y, z)];
+            if *point == State::Free {
+                *point = State::Excluded;
+            }
+        }
+        for (x, y, z) in around(*p, *r) {
+            grid[index(x, y, z)] = State::Occupied;
+        }
+    }
+
+    // Find the buried free grid points
+    let directions: Vec<(isize, isize, isize)> = (-1..=1)
+        .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| (x, y, z))))
+        .filter(|(x, y, z)| {
+            let nonzero = [x, y, z].iter().filter(|v| ***v != 0).count();
+            nonzero == 1 || nonzero == 3
+        })
+        .collect();
+    let steps = (POCKET_RAY_LENGTH / POCKET_GRID_SPACING) as isize;
+    let get = |x: isize, y: isize, z: isize| {
+        (x >= 0
+            && y >= 0
+            && z >= 0
+            && (x as usize) < dims.0
+            && (y as usize) < dims.1
+            && (z as usize) < dims.2)
+            .then(|| grid[index(x as usize, y as usize, z as usize)])
+    };
+    let mut buried = vec![false; grid.len()];
+    for x in 0..dims.0 {
+        for y in 0..dims.1 {
+            for z in 0..dims.2 {
+                if grid[index(x, y, z)] != State::Free {
+                    continue;
+                }
+                let enclosed = directions
+                    .iter()
+                    .filter(|(dx, dy, dz)| {
+                        (1..=steps).any(|step| {
+                            get(
+                                x as isize + dx * step,
+                                y as isize + dy * step,
+                                z as isize + dz * step,
+                            ) == Some(State::Occupied)
+                        })
+                    })
+                    .count();
+                buried[index(x, y, z)] = enclosed >= POCKET_MIN_BURIEDNESS;
+            }
+        }
+    }
+
+    // Cluster the buried points into pockets
+    let mut pocket_of = vec![usize::MAX; grid.len()];
+    let mut clusters: Vec<Vec<(usize, usize, usize)>> = Vec::new();
+    for x in 0..dims.0 {
+        for y in 0..dims.1 {
+            for z in 0..dims.2 {
+                if !buried[index(x, y, z)] || pocket_of[index(x, y, z)] != usize::MAX {
+                    continue;
+                }
+                let id = clusters.len();
+                let mut cluster = vec![(x, y, z)];
+                pocket_of[index(x, y, z)] = id;
+                let mut next = 0;
+                while let Some(&(cx, cy, cz)) = cluster.get(next) {
+                    next += 1;
+                    for (dx, dy, dz) in (-1..=1)
+                        .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| (x, y, z))))
+                    {
+                        let (nx, ny, nz) = (cx as isize + dx, cy as isize + dy, cz as isize + dz);
+                        if get(nx, ny, nz).is_some() {
+                            let neighbour = index(nx as usize, ny as usize, nz as usize);
+                            if buried[neighbour] && pocket_of[neighbour] == usize::MAX {
+                                pocket_of[neighbour] = id;
+                                cluster.push((nx as usize, ny as usize, nz as usize));
+                            }
+                        }
+                    }
+                }
+                clusters.push(cluster);
+            }
+        }
+    }
+
+    // Find the lining residues of each pocket
+    let mut lining = vec![std::collections::BTreeSet::new(); clusters.len()];
+    for (residue, p, r) in &atoms {
+        for (x, y, z) in around(*p, r + PROBE_RADIUS + POCKET_GRID_SPACING) {
+            if let Some(set) = lining.get_mut(pocket_of[index(x, y, z)]) {
+                set.insert(*residue);
+            }
+        }
+    }
+
+    let mut pockets: Vec<Pocket<'a>> = clusters
+        .into_iter()
+        .zip(lining)
+        .filter(|(cluster, _)| cluster.len() >= POCKET_MIN_POINTS)
+        .map(|(cluster, lining)| {
+            let points: Vec<(f64, f64, f64)> = cluster
+                .iter()
+                .map(|(x, y, z)| position(*x, *y, *z))
+                .collect();
+            let n = points.len() as f64;
+            let sum = points
+                .iter()
+                .fold((0.0, 0.0, 0.0), |s, p| (s.0 + p.0, s.1 + p.1, s.2 + p.2));
+            Pocket {
+                centroid: (sum.0 / n, sum.1 / n, sum.2 / n),
+                volume: n * POCKET_GRID_SPACING.powi(3),
+                points,
+                residues: lining.into_iter().map(|index| residues[index]).collect(),
+            }
+        })
+        .collect();
+    pockets.sort_by(|a, b| b.volume.total_cmp(&a.volume));
+    pockets
+}
+
+/// Find the eigenvector with the largest eigenvalue of a symmetric 4x4 matrix, with the Jacobi
+/// eigenvalue algorithm
+fn largest_eigenvector(mut matrix: [[f64; 4]; 4]) -> [f64; 4] {
+    let mut vectors = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+    for _ in 0..50 {
+        let off_diagonal: f64 = (0..4)
+            .flat_map(|i| (i + 1..4).map(move |j| (i, j)))
+            .map(|(i, j)| matrix[i][j] * matrix[i][j])
+            .sum();
+        if off_diagonal < 1e-22 {
+            break;
+        }
+        for p in 0..4 {
+            for q in p + 1..4 {
+                if matrix[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+                let t = theta.signum() / (theta.abs() + theta.mul_add(theta, 1.0).sqrt());
+                let c = 1.0 / t.mul_add(t, 1.0).sqrt();
+                let s = t * c;
+                for row in &mut matrix {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (row_p, row_q) = (matrix[p], matrix[q]);
+                matrix[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                matrix[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+                for vector in &mut vectors {
+                    let (vp, vq) = (vector[p], vector[q]);
+                    vector[p] = c * vp - s * vq;
+                    vector[q] = s * vp + c * vq;
+                }
+            }
+        }
+    }
+    let largest = (0..4)
+        .max_by(|a, b| matrix[*a][*a].total_cmp(&matrix[*b][*b]))
+        .unwrap_or(0);
+    [
+        vectors[0][largest],
+        vectors[1][largest],
+        vectors[2][largest],
+        vectors[3][largest],
+    ]
+}
+
+/// The centroid of the given positions
+#[allow(clippy::cast_precision_loss)]
+fn centroid(positions: &[(f64, f64, f64)]) -> (f64, f64, f64) {
+    let n = positions.len() as f64;
+    let sum = positions
+        .iter()
+        .fold((0.0, 0.0, 0.0), |s, p| (s.0 + p.0, s.1 + p.1, s.2 + p.2));
+    (sum.0 / n, sum.1 / n, sum.2 / n)
+}
+
+/// A rotation as unit quaternion (w, x, y, z), with the centroids of the mobile and target positions
+type Superposition = ([f64; 4], (f64, f64, f64), (f64, f64, f64));
+
+/// Find the optimal rotation of the mobile positions onto the target positions, given as a unit
+/// quaternion (w, x, y, z), with the centroids of both sets
+fn superpose_positions(mobile: &[(f64, f64, f64)], target: &[(f64, f64, f64)]) -> Superposition {
+    let (cm, ct) = (centroid(mobile), centroid(target));
+    let mut s = [[0.0; 3]; 3];
+    for (m, t) in mobile.iter().zip(target) {
+        let m = [m.0 - cm.0, m.1 - cm.1, m.2 - cm.2];
+        let t = [t.0 - ct.0, t.1 - ct.1, t.2 - ct.2];
+        for a in 0..3 {
+            for b in 0..3 {
+                s[a][b] += m[a] * t[b];
+            }
+        }
+    }
+    let n = [
+        [
+            s[0][0] + s[1][1] + s[2][2],
+            s[1][2] - s[2][1],
+            s[2][0] - s[0][2],
+            s[0][1] - s[1][0],
+        ],
+        [
+            s[1][2] - s[2][1],
+            s[0][0] - s[1][1] - s[2][2],
+            s[0][1] + s[1][0],
+            s[2][0] + s[0][2],
+        ],
+        [
+            s[2][0] - s[0][2],
+            s[0][1] + s[1][0],
+            -s[0][0] + s[1][1] - s[2][2],
+            s[1][2] + s[2][1],
+        ],
+        [
+            s[0][1] - s[1][0],
+            s[2][0] + s[0][2],
+            s[1][2] + s[2][1],
+            -s[0][0] - s[1][1] + s[2][2],
+        ],
+    ];
+    (largest_eigenvector(n), cm, ct)
+}
+
+/// The rotation matrix for the given unit quaternion (w, x, y, z)
+fn quaternion_matrix(q: [f64; 4]) -> [[f64; 3]; 3] {
+    let [w, x, y, z] = q;
+    [
+        [
+            w * w + x * x - y * y - z * z,
+            2.0 * (x * y - w * z),
+            2.0 * (x * z + w * y),
+        ],
+        [
+            2.0 * (x * y + w * z),
+            w * w - x * x + y * y - z * z,
+            2.0 * (y * z - w * x),
+        ],
+        [
+            2.0 * (x * z - w * y),
+            2.0 * (y * z + w * x),
+            w * w - x * x - y * y + z * z,
+        ],
+    ]
+}
+
+/// Apply a rotation matrix to a position
+fn rotate(r: &[[f64; 3]; 3], p: (f64, f64, f64)) -> (f64, f64, f64) {
+    (
+        r[0][0] * p.0 + r[0][1] * p.1 + r[0][2] * p.2,
+        r[1][0] * p.0 + r[1][1] * p.1 + r[1][2] * p.2,
+        r[2][0] * p.0 + r[2][1] * p.1 + r[2][2] * p.2,
+    )
+}
+
+/// Find the transformation which optimally superposes the mobile atoms onto the target atoms
+/// (minimising the RMSD), using the quaternion method of Horn (1987). The atoms are paired in
+/// the given order. Returns the transformation with the RMSD after superposition in Aͦ, or
+/// `None` if the sets are empty or have different lengths.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let atoms: Vec<&Atom> = pdb.atoms().collect();
+/// let (transformation, rmsd) = geometry::superpose(&atoms, &atoms).unwrap();
+/// assert!(rmsd < 1e-6);
+/// ```
+pub fn superpose(mobile: &[&Atom], target: &[&Atom]) -> Option<(TransformationMatrix, f64)> {
+    if mobile.is_empty() || mobile.len() != target.len() {
+        return None;
+    }
+    Some(superpose_all_positions(
+        &positions(mobile.iter().copied()),
+        &positions(target.iter().copied()),
+    ))
+}
+
+/// Find the transformation which optimally superposes the mobile positions onto the target
+/// positions, with the RMSD after superposition in Aͦ, see [`superpose`]
+#[allow(clippy::cast_precision_loss)]
+fn superpose_all_positions(
+    mobile: &[(f64, f64, f64)],
+    target: &[(f64, f64, f64)],
+) -> (TransformationMatrix, f64) {
+    let (q, cm, ct) = superpose_positions(mobile, target);
+    let r = quaternion_matrix(q);
+    let shift = rotate(&r, cm);
+    let t = (ct.0 - shift.0, ct.1 - shift.1, ct.2 - shift.2);
+    let transformation = TransformationMatrix::from_matrix([
+        [r[0][0], r[0][1], r[0][2], t.0],
+        [r[1][0], r[1][1], r[1][2], t.1],
+        [r[2][0], r[2][1], r[2][2], t.2],
+    ]);
+    let squared: f64 = mobile
+        .iter()
+        .zip(target)
+        .map(|(m, t)| position_distance(transformation.apply(*m), *t).powi(2))
+        .sum();
+    (transformation, (squared / mobile.len() as f64).sqrt())
+}
+
+/// Superpose the mobile Chain onto the target Chain, see [`superpose`]. The residues of both
+/// chains are paired with a global alignment of their sequences (see [`Chain::align_sequence`]),
+/// and the alpha carbons (or phosphorus atoms for nucleic acids) of the aligned residues are
+/// superposed. Returns `None` if fewer than three residues could be paired.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1yyf.pdb").unwrap();
+/// let chains: Vec<&Chain> = pdb.chains().collect();
+/// let (transformation, rmsd) = geometry::superpose_chains(chains[0], chains[1]).unwrap();
+/// assert!(rmsd < 2.0);
+/// ```
+pub fn superpose_chains(mobile: &Chain, target: &Chain) -> Option<(TransformationMatrix, f64)> {
+    let alignment = mobile.align_sequence(target, Scoring::default());
+    let residues_mobile: Vec<&Residue> = mobile.polymer_residues().collect();
+    let residues_target: Vec<&Residue> = target.polymer_residues().collect();
+    let (atoms_mobile, atoms_target): (Vec<&Atom>, Vec<&Atom>) = alignment
+        .aligned()
+        .filter_map(|(i, j)| {
+            Some((
+                reference_atom(residues_mobile[i])?,
+                reference_atom(residues_target[j])?,
+            ))
+        })
+        .unzip();
+    if atoms_mobile.len() < 3 {
+        return None;
+    }
+    superpose(&atoms_mobile, &atoms_target)
+}
+
+/// The positions of the alpha carbons (or phosphorus atoms for nucleic acids) of the given
+/// models, if all models have the same number of these (at least three)
+fn model_reference_positions(models: &[&Model]) -> Option<Vec<Vec<(f64, f64, f64)>>> {
+    let positions: Vec<Vec<(f64, f64, f64)>> = models
+        .iter()
+        .map(|model| {
+            model
+                .residues()
+                .filter_map(reference_atom)
+                .map(Atom::pos)
+                .collect()
+        })
+        .collect();
+    let count = positions.first().map_or(0, Vec::len);
+    (count >= 3 && positions.iter().all(|p| p.len() == count)).then_some(positions)
+}
+
+/// Calculate the RMSD in Aͦ between every pair of the given models after optimal superposition
+/// (see [`superpose`]), over the alpha carbons (or phosphorus atoms for nucleic acids), which
+/// are paired in order. Returns `None` if the models do not have the same number of these atoms
+/// or fewer than three.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/pTLS-6484.pdb").unwrap();
+/// let models: Vec<&Model> = pdb.models().collect();
+/// let matrix = geometry::model_rmsd_matrix(&models).unwrap();
+/// assert_eq!(matrix.rows(), 50);
+/// assert!(matrix.get(0, 0).unwrap() < 1e-6);
+/// ```
+pub fn model_rmsd_matrix(models: &[&Model]) -> Option<DistanceMatrix> {
+    let positions = model_reference_positions(models)?;
+    let n = positions.len();
+    let mut values = vec![0.0; n * n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let (_, rmsd) = superpose_all_positions(&positions[i], &positions[j]);
+            values[i * n + j] = rmsd;
+            values[j * n + i] = rmsd;
+        }
+    }
+    Some(DistanceMatrix {
+        rows: n,
+        columns: n,
+        values,
+    })
+}
+
+/// The clusters of models in an ensemble, see [`cluster_models`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelClusters {
+    /// The cluster of every model, as index into `representatives`
+    pub assignments: Vec<usize>,
+    /// The representative model of every cluster as index into the models, the medoid of the
+    /// cluster (the model with the lowest summed RMSD to the other models in the cluster). The
+    /// clusters are sorted from large to small.
+    pub representatives: Vec<usize>,
+}
+
+impl ModelClusters {
+    /// The number of clusters
+    pub fn len(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// Whether there are no clusters, which only happens if there are no models
+    pub fn is_empty(&self) -> bool {
+        self.representatives.is_empty()
+    }
+
+    /// The models in the given cluster, as indices into the models
+    pub fn members(&self, cluster: usize) -> Vec<usize> {
+        (0..self.assignments.len())
+            .filter(|model| self.assignments[*model] == cluster)
+            .collect()
+    }
+}
+
+/// Cluster the given models of an ensemble, like an NMR ensemble or snapshots of a molecular
+/// dynamics simulation, by their RMSD (see [`model_rmsd_matrix`]). The models are clustered
+/// hierarchically with average linkage: the two closest clusters are merged until the average
+/// RMSD between the models of any two clusters is larger than the cutoff in Aͦ. Returns `None`
+/// if the models do not have the same number of alpha carbons (or phosphorus atoms for nucleic
+/// acids) or fewer than three.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/pTLS-6484.pdb").unwrap();
+/// let models: Vec<&Model> = pdb.models().collect();
+/// let clusters = geometry::cluster_models(&models, 1.0).unwrap();
+/// for (cluster, representative) in clusters.representatives.iter().enumerate() {
+///     println!("Cluster {cluster} of {} models: model {}", clusters.members(cluster).len(), models[*representative].serial_number());
+/// }
+/// ```
+pub fn cluster_models(models: &[&Model], cutoff: f64) -> Option<ModelClusters> {
+    let rmsd = model_rmsd_matrix(models)?;
+    let n = models.len();
+    let mut clusters: Vec<Vec<usize>> = (0..n).map(|model| vec![model]).collect();
+    // The average RMSD between the clusters, updated with the Lance-Williams formula
+    let mut distances: Vec<Vec<f64>> = (0..n)
+        .map(|i| rmsd.row(i).expect("The matrix is square").to_vec())
+        .collect();
+    loop {
+        let closest = (0..clusters.len())
+            .flat_map(|i| (i + 1..clusters.len()).map(move |j| (i, j)))
+            .min_by(|a, b| distances[a.0][a.1].total_cmp(&distances[b.0][b.1]));
+        let (i, j) = match closest.filter(|(i, j)| distances[*i][*j] <= cutoff) {
+            Some(pair) => pair,
+            None => break,
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let (size_i, size_j) = (clusters[i].len() as f64, clusters[j].len() as f64);
+        let merged: Vec<f64> = distances[i]
+            .iter()
+            .zip(&distances[j])
+            .map(|(a, b)| (size_i * a + size_j * b) / (size_i + size_j))
+            .collect();
+        for (k, value) in merged.into_iter().enumerate() {
+            distances[i][k] = value;
+            distances[k][i] = value;
+        }
+        distances[i][i] = 0.0;
+        let members = clusters.remove(j);
+        clusters[i].extend(members);
+        distances.remove(j);
+        for row in &mut distances {
+            row.remove(j);
+        }
+    }
+
+    clusters.sort_by_key(|members| std::cmp::Reverse(members.len()));
+    let mut assignments = vec![0; n];
+    let representatives = clusters
+        .iter()
+        .enumerate()
+        .map(|(cluster, members)| {
+            for model in members {
+                assignments[*model] = cluster;
+            }
+            *members
+                .iter()
+                .min_by(|a, b| {
+                    let sum = |model: usize| {
+                        members
+                            .iter()
+                            .map(|other| rmsd.get(model, *other).unwrap_or(0.0))
+                            .sum::<f64>()
+                    };
+                    sum(**a).total_cmp(&sum(**b))
+                })
+                .expect("Clusters are not empty")
+        })
+        .collect();
+    Some(ModelClusters {
+        assignments,
+        representatives,
+    })
+}
+
+/// Find the eigenvalues and eigenvectors of a symmetric matrix with the Jacobi eigenvalue
+/// algorithm. Returns the eigenvalues from large to small, with the eigenvectors in the same
+/// order as unit vectors.
+fn symmetric_eigen(mut matrix: Vec<Vec<f64>>) -> Vec<(f64, Vec<f64>)> {
+    let n = matrix.len();
+    let mut vectors: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    let scale: f64 = matrix.iter().flatten().map(|v| v * v).sum();
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .map(|(i, j)| matrix[i][j] * matrix[i][j])
+            .sum();
+        if off_diagonal <= 1e-24 * scale {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if matrix[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+                let t = theta.signum() / (theta.abs() + theta.mul_add(theta, 1.0).sqrt());
+                let c = 1.0 / t.mul_add(t, 1.0).sqrt();
+                let s = t * c;
+                for row in &mut matrix {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (row_p, row_q) = (matrix[p].clone(), matrix[q].clone());
+                matrix[p] = row_p
+                    .iter()
+                    .zip(&row_q)
+                    .map(|(a, b)| c * a - s * b)
+                    .collect();
+                matrix[q] = row_p
+                    .iter()
+                    .zip(&row_q)
+                    .map(|(a, b)| s * a + c * b)
+                    .collect();
+                for vector in &mut vectors {
+                    let (vp, vq) = (vector[p], vector[q]);
+                    vector[p] = c * vp - s * vq;
+                    vector[q] = s * vp + c * vq;
+                }
+            }
+        }
+    }
+    let mut eigen: Vec<(f64, Vec<f64>)> = (0..n)
+        .map(|k| (matrix[k][k], vectors.iter().map(|row| row[k]).collect()))
+        .collect();
+    eigen.sort_by(|a, b| b.0.total_cmp(&a.0));
+    eigen
+}
+
+/// The principal components of the coordinate variation in an ensemble, see
+/// [`principal_components`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrincipalComponents {
+    /// The average positions of the alpha carbons (or phosphorus atoms) over the superposed models
+    pub mean: Vec<(f64, f64, f64)>,
+    /// The variance along every component in Aͦ², from large to small
+    pub eigenvalues: Vec<f64>,
+    /// The components as unit vectors, with the x, y, and z displacement of every atom in turn
+    pub eigenvectors: Vec<Vec<f64>>,
+    /// The projections of every model onto every component in Aͦ, one row per model
+    pub projections: Vec<Vec<f64>>,
+}
+
+impl PrincipalComponents {
+    /// The fraction of the total variance described by every component
+    pub fn explained_variance(&self) -> Vec<f64> {
+        let total: f64 = self.eigenvalues.iter().sum();
+        self.eigenvalues
+            .iter()
+            .map(|value| if total > 0.0 { value / total } else { 0.0 })
+            .collect()
+    }
+}
+
+/// Analyse the coordinate variation over the given models with a principal component analysis
+/// (essential dynamics), over the alpha carbons (or phosphorus atoms for nucleic acids), which
+/// are paired in order. The models are iteratively superposed onto their average structure (see
+/// [`superpose`]) first. As an ensemble of `n` models has at most `n - 1` components with
+/// variance, only these are given. Returns `None` if the models do not have the same number of
+/// alpha carbons (or phosphorus atoms), fewer than three, or if there are fewer than two models.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/pTLS-6484.pdb").unwrap();
+/// let models: Vec<&Model> = pdb.models().collect();
+/// let pca = geometry::principal_components(&models).unwrap();
+/// println!("The first component describes {:.0}% of the variance", pca.explained_variance()[0] * 100.0);
+/// ```
+#[allow(clippy::cast_precision_loss)]
+pub fn principal_components(models: &[&Model]) -> Option<PrincipalComponents> {
+    let mut positions = model_reference_positions(models)?;
+    if positions.len() < 2 {
+        return None;
+    }
+    let (count, atoms) = (positions.len(), positions[0].len());
+
+    // Superpose onto the first model, then onto the average until it no longer changes
+    let mut reference = positions[0].clone();
+    let mut mean = reference.clone();
+    for _ in 0..20 {
+        for model in &mut positions {
+            let (transformation, _) = superpose_all_positions(model, &reference);
+            for position in model.iter_mut() {
+                *position = transformation.apply(*position);
+            }
+        }
+        mean = (0..atoms)
+            .map(|atom| {
+                let sum = positions.iter().fold((0.0, 0.0, 0.0), |s, model| {
+                    (
+                        s.0 + model[atom].0,
+                        s.1 + model[atom].1,
+                        s.2 + model[atom].2,
+                    )
+                });
+                (
+                    sum.0 / count as f64,
+                    sum.1 / count as f64,
+                    sum.2 / count as f64,
+                )
+            })
+            .collect();
+        let change = mean
+            .iter()
+            .zip(&reference)
+            .map(|(a, b)| position_distance(*a, *b))
+            .fold(0.0, f64::max);
+        reference.clone_from(&mean);
+        if change < 1e-6 {
+            break;
+        }
+    }
+
+    // The displacements from the average, one row of 3N values per model
+    let displacements: Vec<Vec<f64>> = positions
+        .iter()
+        .map(|model| {
+            model
+                .iter()
+                .zip(&mean)
+                .flat_map(|(p, m)| [p.0 - m.0, p.1 - m.1, p.2 - m.2])
+                .collect()
+        })
+        .collect();
+    // The eigenvectors of the covariance matrix follow from the much smaller matrix of inner
+    // products between the models, as there are fewer models than coordinates
+    let degrees = (count - 1) as f64;
+    let gram: Vec<Vec<f64>> = displacements
+        .iter()
+        .map(|a| {
+            displacements
+                .iter()
+                .map(|b| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>() / degrees)
+                .collect()
+        })
+        .collect();
+    let mut components = PrincipalComponents {
+        mean,
+        eigenvalues: Vec::new(),
+        eigenvectors: Vec::new(),
+        projections: vec![Vec::new(); count],
+    };
+    for (value, vector) in symmetric_eigen(gram).into_iter().take(count - 1) {
+        if value <= 0.0 {
+            break;
+        }
+        let norm = (value * degrees).sqrt();
+        let eigenvector: Vec<f64> = (0..atoms * 3)
+            .map(|i| {
+                displacements
+                    .iter()
+                    .zip(&vector)
+                    .map(|(row, u)| row[i] * u)
+                    .sum::<f64>()
+                    / norm
+            })
+            .collect();
+        for (projection, u) in components.projections.iter_mut().zip(&vector) {
+            projection.push(u * norm);
+        }
+        components.eigenvalues.push(value);
+        components.eigenvectors.push(eigenvector);
+    }
+    Some(components)
+}
+
+/// Get the alpha carbon, or phosphorus atom for nucleic acids, of the given residue
+fn reference_atom(residue: &Residue) -> Option<&Atom> {
+    residue
+        .atoms()
+        .find(|a| a.name() == "CA" || a.name() == "P")
+}
+
+/// A point group describing the symmetry of an assembly, see [`point_group`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointGroup {
+    /// Cyclic symmetry with the given order, all chains are related by rotations around a
+    /// single axis
+    Cyclic(usize),
+    /// Dihedral symmetry with the given order, a cyclic axis with perpendicular two fold axes
+    Dihedral(usize),
+    /// Tetrahedral, octahedral, or icosahedral symmetry (like in capsids), with rotations of
+    /// order three or higher around multiple axes. This is only flagged, not classified further.
+    Higher,
+}
+
+impl std::fmt::Display for PointGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointGroup::Cyclic(n) => write!(f, "C{n}"),
+            PointGroup::Dihedral(n) => write!(f, "D{n}"),
+            PointGroup::Higher => write!(f, "T/O/I"),
+        }
+    }
+}
+
+/// The point group symmetry of an assembly of identical chains, see [`point_group`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointGroupSymmetry {
+    /// The point group
+    pub point_group: PointGroup,
+    /// The centre of the assembly, through which all symmetry axes go
+    pub center: (f64, f64, f64),
+    /// The principal symmetry axis (unit vector), the axis with the highest order
+    pub axis: (f64, f64, f64),
+    /// The highest RMSD of the superpositions of the first chain on the others in Aͦ
+    pub rmsd: f64,
+}
+
+/// The maximal deviation in degrees of a rotation from the ideal symmetry
+const SYMMETRY_ANGLE_TOLERANCE: f64 = 10.0;
+
+/// The maximal RMSD in Aͦ of superposed chains to count as symmetric
+const SYMMETRY_MAX_RMSD: f64 = 3.0;
+
+/// Detect the point group symmetry of an assembly of chains with identical sequences (see
+/// [`PDB::identical_chains`]). The first chain is superposed on all others, based on the alpha
+/// carbons (or phosphorus atoms for nucleic acids) of the residues present in all chains, and
+/// the resulting rotations are analysed for cyclic (Cn) or dihedral (Dn) symmetry. Higher
+/// symmetries (tetrahedral, octahedral, icosahedral) are flagged as [`PointGroup::Higher`].
+/// Returns `None` if there are fewer than two chains, if the chains have no common residues, or
+/// if the chains are not related by a point group symmetry.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn point_group(chains: &[&Chain]) -> Option<PointGroupSymmetry> {
+    let n = chains.len();
+    if n < 2 {
+        return None;
+    }
+    // Find the reference atoms present in all chains
+    type ResidueId = (isize, Option<String>);
+    let reference = |chain: &Chain| -> Vec<(ResidueId, (f64, f64, f64))> {
+        chain
+            .residues()
+            .filter_map(|residue| {
+                residue
+                    .atoms()
+                    .find(|a| a.name() == "CA" || a.name() == "P")
+                    .map(|a| {
+                        let (serial, insertion) = residue.id();
+                        ((serial, insertion.map(str::to_string)), a.pos())
+                    })
+            })
+            .collect()
+    };
+    let references: Vec<_> = chains.iter().map(|c| reference(c)).collect();
+    let common: Vec<&ResidueId> = references[0]
+        .iter()
+        .map(|(id, _)| id)
+        .filter(|id| {
+            references[1..]
+                .iter()
+                .all(|r| r.iter().any(|(i, _)| i == *id))
+        })
+        .collect();
+    if common.len() < 3 {
+        return None;
+    }
+    let coordinates: Vec<Vec<(f64, f64, f64)>> = references
+        .iter()
+        .map(|r| {
+            common
+                .iter()
+                .filter_map(|id| r.iter().find(|(i, _)| i == *id).map(|(_, p)| *p))
+                .collect()
+        })
+        .collect();
+    let center = centroid(&coordinates.iter().flatten().copied().collect::<Vec<_>>());
+
+    // Superpose the first chain on all others, and check that the rotations go through the centre
+    let mut rotations = Vec::new();
+    let mut rmsd: f64 = 0.0;
+    for target in &coordinates[1..] {
+        let (q, cm, ct) = superpose_positions(&coordinates[0], target);
+        let r = quaternion_matrix(q);
+        let deviation: f64 = coordinates[0]
+            .iter()
+            .zip(target)
+            .map(|(m, t)| {
+                let p = rotate(&r, (m.0 - cm.0, m.1 - cm.1, m.2 - cm.2));
+                position_distance((p.0 + ct.0, p.1 + ct.1, p.2 + ct.2), *t).powi(2)
+            })
+            .sum();
+        rmsd = rmsd.max((deviation / common.len() as f64).sqrt());
+        let moved = rotate(&r, (cm.0 - center.0, cm.1 - center.1, cm.2 - center.2));
+        let moved = (moved.0 + center.0, moved.1 + center.1, moved.2 + center.2);
+        if position_distance(moved, ct) > SYMMETRY_MAX_RMSD {
+            return None;
+        }
+        let w = q[0].abs().min(1.0);
+        let sign = if q[0] < 0.0 { -1.0 } else { 1.0 };
+        let length = (q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+        if length < 1e-9 {
+            return None; // No rotation, so two chains are on top of each other
+        }
+        let axis = [
+            sign * q[1] / length,
+            sign * q[2] / length,
+            sign * q[3] / length,
+        ];
+        rotations.push((2.0 * w.acos().to_degrees(), axis));
+    }
+    if rmsd > SYMMETRY_MAX_RMSD {
+        return None;
+    }
+
+    let parallel =
+        |a: [f64; 3], b: [f64; 3]| dot(a, b).abs() >= SYMMETRY_ANGLE_TOLERANCE.to_radians().cos();
+    let perpendicular =
+        |a: [f64; 3], b: [f64; 3]| dot(a, b).abs() <= SYMMETRY_ANGLE_TOLERANCE.to_radians().sin();
+    let order = |angle: f64| (360.0 / angle).round() as usize;
+    let two_fold = |angle: f64| (angle - 180.0).abs() <= SYMMETRY_ANGLE_TOLERANCE;
+    let (principal_angle, principal) = rotations
+        .iter()
+        .copied()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .expect("There are at least two chains");
+    let result = |point_group, axis: [f64; 3]| {
+        Some(PointGroupSymmetry {
+            point_group,
+            center,
+            axis: (axis[0], axis[1], axis[2]),
+            rmsd,
+        })
+    };
+
+    // Cyclic: all rotations around the same axis
+    if order(principal_angle) == n && rotations.iter().all(|(_, axis)| parallel(*axis, principal)) {
+        return result(PointGroup::Cyclic(n), principal);
+    }
+    // Dihedral: rotations around the principal axis, and two folds perpendicular to it
+    if n % 2 == 0 && n >= 4 {
+        let m = n / 2;
+        let candidates: Vec<[f64; 3]> = if m == 2 {
+            rotations.iter().map(|(_, axis)| *axis).collect()
+        } else {
+            vec![principal]
+        };
+        for axis in candidates {
+            let around = rotations
+                .iter()
+                .filter(|(angle, a)| parallel(*a, axis) && order(*angle) <= m)
+                .count();
+            let perpendicular_two_folds = rotations
+                .iter()
+                .filter(|(angle, a)| two_fold(*angle) && perpendicular(*a, axis))
+                .count();
+            if around == m - 1
+                && perpendicular_two_folds == m
+                && (m == 2 || order(principal_angle) == m)
+            {
+                return result(PointGroup::Dihedral(m), axis);
+            }
+        }
+    }
+    // Higher: rotations of order three or more around different axes
+    let high: Vec<&(f64, [f64; 3])> = rotations
+        .iter()
+        .filter(|(angle, _)| order(*angle) >= 3 && *angle < 180.0 - SYMMETRY_ANGLE_TOLERANCE)
+        .collect();
+    if high
+        .iter()
+        .any(|(_, a)| high.iter().any(|(_, b)| !parallel(*a, *b)))
+    {
+        return result(PointGroup::Higher, principal);
+    }
+    None
+}
+
+/// The type of a base pair, see [`base_pairs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasePairType {
+    /// A canonical Watson–Crick pair, A–T, A–U, or G–C
+    WatsonCrick,
+    /// A wobble pair, G–U or G–T
+    Wobble,
+}
+
+/// A pair of hydrogen bonded nucleotide bases, see [`base_pairs`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasePair<'a> {
+    /// The first residue, the purine (A or G)
+    pub purine: &'a Residue,
+    /// The second residue, the pyrimidine (C, T, or U)
+    pub pyrimidine: &'a Residue,
+    /// The type of the pair
+    pub kind: BasePairType,
+    /// The hydrogen bonds between the bases, as the atoms of the purine and pyrimidine and
+    /// their distance in Aͦ
+    pub hydrogen_bonds: Vec<(&'a Atom, &'a Atom, f64)>,
+}
+
+/// The maximal distance between the donor and acceptor of a hydrogen bond in a base pair in Aͦ
+const BASE_PAIR_MAX_DISTANCE: f64 = 3.5;
+
+/// The definition of a base pair, see [`BASE_PAIR_TYPES`]
+type BasePairDefinition = (
+    char,
+    char,
+    BasePairType,
+    &'static [(&'static str, &'static str)],
+);
+
+/// The base pairs which are detected, as the purine, the pyrimidine, the type, and the hydrogen
+/// bonds (purine atom, pyrimidine atom). The first hydrogen bond is the central bond between the
+/// ring nitrogens, which is always required.
+const BASE_PAIR_TYPES: &[BasePairDefinition] = &[
+    (
+        'A',
+        'T',
+        BasePairType::WatsonCrick,
+        &[("N1", "N3"), ("N6", "O4")],
+    ),
+    (
+        'A',
+        'U',
+        BasePairType::WatsonCrick,
+        &[("N1", "N3"), ("N6", "O4")],
+    ),
+    (
+        'G',
+        'C',
+        BasePairType::WatsonCrick,
+        &[("N1", "N3"), ("O6", "N4"), ("N2", "O2")],
+    ),
+    (
+        'G',
+        'U',
+        BasePairType::Wobble,
+        &[("N1", "O2"), ("O6", "N3")],
+    ),
+    (
+        'G',
+        'T',
+        BasePairType::Wobble,
+        &[("N1", "O2"), ("O6", "N3")],
+    ),
+];
+
+/// Detect Watson–Crick (A–T, A–U, G–C) and wobble (G–U, G–T) base pairs between the given
+/// residues from their geometry. Two nucleotides (see [`Conformer::is_nucleotide`]) pair when
+/// the hydrogen bond between the ring nitrogens (or N1 and O2 for wobble pairs) and at least
+/// one other hydrogen bond of the pair are within 3.5 Aͦ. Every residue is part of at most one
+/// pair, when a residue could pair with multiple others the pair with the most and then the
+/// shortest hydrogen bonds is chosen. Only the first conformer of each residue is used. The
+/// pairs are returned in the order of their purines in the given residues.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/nucleic.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// // The two nucleotides are neighbours in a single strand, so they do not pair
+/// assert!(geometry::base_pairs(&residues).is_empty());
+/// ```
+pub fn base_pairs<'a>(residues: &[&'a Residue]) -> Vec<BasePair<'a>> {
+    let bases: Vec<Option<(char, &'a Conformer)>> = residues
+        .iter()
+        .map(|residue| {
+            residue
+                .conformer(0)
+                .filter(|conformer| conformer.is_nucleotide())
+                .and_then(|conformer| Some((conformer.name().chars().last()?, conformer)))
+        })
+        .collect();
+    let atom = |conformer: &'a Conformer, name: &str| conformer.atoms().find(|a| a.name() == name);
+
+    let mut candidates = Vec::new();
+    for (i, base_i) in bases.iter().enumerate() {
+        for (j, base_j) in bases.iter().enumerate() {
+            let (purine, conformer_i, pyrimidine, conformer_j) = match (base_i, base_j) {
+                (Some((a, conformer_a)), Some((b, conformer_b))) => {
+                    (a, conformer_a, b, conformer_b)
+                }
+                _ => continue,
+            };
+            let (kind, bonds) = match BASE_PAIR_TYPES
+                .iter()
+                .find(|(a, b, _, _)| a == purine && b == pyrimidine)
+            {
+                Some((_, _, kind, bonds)) => (kind, bonds),
+                None => continue,
+            };
+            let hydrogen_bonds: Vec<(&'a Atom, &'a Atom, f64)> = bonds
+                .iter()
+                .filter_map(|(a, b)| {
+                    let (a, b) = (atom(conformer_i, a)?, atom(conformer_j, b)?);
+                    let distance = a.distance(b);
+                    (distance <= BASE_PAIR_MAX_DISTANCE).then_some((a, b, distance))
+                })
+                .collect();
+            let central = hydrogen_bonds
+                .first()
+                .map_or(false, |(a, _, _)| a.name() == bonds[0].0);
+            if central && hydrogen_bonds.len() >= 2 {
+                let length: f64 = hydrogen_bonds.iter().map(|(_, _, d)| d).sum();
+                candidates.push((i, j, *kind, hydrogen_bonds, length));
+            }
+        }
+    }
+
+    // Choose the pairs with the most and the shortest hydrogen bonds first
+    candidates.sort_by(|a, b| b.3.len().cmp(&a.3.len()).then(a.4.total_cmp(&b.4)));
+    let mut paired = vec![false; residues.len()];
+    let mut pairs = Vec::new();
+    for (i, j, kind, hydrogen_bonds, _) in candidates {
+        if !paired[i] && !paired[j] {
+            paired[i] = true;
+            paired[j] = true;
+            pairs.push((i, j, kind, hydrogen_bonds));
+        }
+    }
+    pairs.sort_by_key(|pair| pair.0);
+    pairs
+        .into_iter()
+        .map(|(i, j, kind, hydrogen_bonds)| BasePair {
+            purine: residues[i],
+            pyrimidine: residues[j],
+            kind,
+            hydrogen_bonds,
+        })
+        .collect()
+}
+
+/// The backbone torsion angles, the glycosidic torsion angle, and the sugar pucker of a
+/// nucleotide in degrees, see [`nucleic_torsions`]. The angles are `None` when atoms needed for
+/// them are missing, or for alpha, epsilon, and zeta when there is no (bonded) neighbouring
+/// nucleotide.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NucleicTorsions {
+    /// O3'(i-1)-P-O5'-C5'
+    pub alpha: Option<f64>,
+    /// P-O5'-C5'-C4'
+    pub beta: Option<f64>,
+    /// O5'-C5'-C4'-C3'
+    pub gamma: Option<f64>,
+    /// C5'-C4'-C3'-O3'
+    pub delta: Option<f64>,
+    /// C4'-C3'-O3'-P(i+1)
+    pub epsilon: Option<f64>,
+    /// C3'-O3'-P(i+1)-O5'(i+1)
+    pub zeta: Option<f64>,
+    /// O4'-C1'-N9-C4 for purines, O4'-C1'-N1-C2 for pyrimidines
+    pub chi: Option<f64>,
+    /// The pucker of the sugar ring
+    pub pucker: Option<SugarPucker>,
+}
+
+/// The pucker of the ribose ring of a nucleotide as pseudorotation parameters (Altona and
+/// Sundaralingam), see [`NucleicTorsions`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SugarPucker {
+    /// The phase angle of pseudorotation in degrees, in the range [0, 360)
+    pub phase: f64,
+    /// The puckering amplitude in degrees
+    pub amplitude: f64,
+}
+
+impl SugarPucker {
+    /// Get the conformation of the sugar ring, as the sector of the pseudorotation cycle the
+    /// phase angle is in
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn conformation(&self) -> PuckerConformation {
+        match (self.phase.rem_euclid(360.0) / 36.0) as usize {
+            0 => PuckerConformation::C3Endo,
+            1 => PuckerConformation::C4Exo,
+            2 => PuckerConformation::O4Endo,
+            3 => PuckerConformation::C1Exo,
+            4 => PuckerConformation::C2Endo,
+            5 => PuckerConformation::C3Exo,
+            6 => PuckerConformation::C4Endo,
+            7 => PuckerConformation::O4Exo,
+            8 => PuckerConformation::C1Endo,
+            _ => PuckerConformation::C2Exo,
+        }
+    }
+}
+
+/// The conformations of the sugar ring of a nucleotide, see [`SugarPucker::conformation`]. The
+/// A form of nucleic acids (and RNA) has C3'-endo sugars, the B form of DNA C2'-endo sugars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuckerConformation {
+    /// C3'-endo, a phase angle from 0 to 36 degrees
+    C3Endo,
+    /// C4'-exo, a phase angle from 36 to 72 degrees
+    C4Exo,
+    /// O4'-endo, a phase angle from 72 to 108 degrees
+    O4Endo,
+    /// C1'-exo, a phase angle from 108 to 144 degrees
+    C1Exo,
+    /// C2'-endo, a phase angle from 144 to 180 degrees
+    C2Endo,
+    /// C3'-exo, a phase angle from 180 to 216 degrees
+    C3Exo,
+    /// C4'-endo, a phase angle from 216 to 252 degrees
+    C4Endo,
+    /// O4'-exo, a phase angle from 252 to 288 degrees
+    O4Exo,
+    /// C1'-endo, a phase angle from 288 to 324 degrees
+    C1Endo,
+    /// C2'-exo, a phase angle from 324 to 360 degrees
+    C2Exo,
+}
+
+impl std::fmt::Display for PuckerConformation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PuckerConformation::C3Endo => "C3'-endo",
+            PuckerConformation::C4Exo => "C4'-exo",
+            PuckerConformation::O4Endo => "O4'-endo",
+            PuckerConformation::C1Exo => "C1'-exo",
+            PuckerConformation::C2Endo => "C2'-endo",
+            PuckerConformation::C3Exo => "C3'-exo",
+            PuckerConformation::C4Endo => "C4'-endo",
+            PuckerConformation::O4Exo => "O4'-exo",
+            PuckerConformation::C1Endo => "C1'-endo",
+            PuckerConformation::C2Exo => "C2'-exo",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The maximal length of the O3'-P bond between two nucleotides in Aͦ
+const PHOSPHODIESTER_MAX_LENGTH: f64 = 2.0;
+
+/// Find the atom with the given name in the first conformer of the residue, also accepting the
+/// old naming convention with a star instead of a prime (`O3*` for `O3'`)
+fn nucleotide_atom<'a>(residue: &'a Residue, name: &str) -> Option<&'a Atom> {
+    let conformer = residue.conformer(0)?;
+    let old = name.replace('\'', "*");
+    conformer
+        .atoms()
+        .find(|atom| atom.name() == name || atom.name() == old)
+}
+
+/// Calculate the backbone torsion angles (alpha to zeta), the glycosidic torsion angle (chi),
+/// and the sugar pucker of a nucleotide, with the previous and next nucleotide in the chain for
+/// the torsions around the phosphodiester bonds. Neighbours which are not bonded to the
+/// nucleotide (O3'-P longer than 2 Aͦ) are ignored, so chain breaks are handled. See
+/// [`Chain::nucleic_torsions`] to calculate these for a whole chain.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/nucleic.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// let torsions = geometry::nucleic_torsions(None, residues[0], Some(residues[1]));
+/// assert!(torsions.alpha.is_none() && torsions.zeta.is_some());
+/// ```
+pub fn nucleic_torsions(
+    previous: Option<&Residue>,
+    residue: &Residue,
+    next: Option<&Residue>,
+) -> NucleicTorsions {
+    let own = |name| nucleotide_atom(residue, name);
+    let torsion = |a: Option<&Atom>, b: Option<&Atom>, c: Option<&Atom>, d: Option<&Atom>| {
+        Some(dihedral(a?, b?, c?, d?))
+    };
+    let bonded = |o3: Option<&Atom>, p: Option<&Atom>| match (o3, p) {
+        (Some(o3), Some(p)) => o3.distance(p) <= PHOSPHODIESTER_MAX_LENGTH,
+        _ => false,
+    };
+    let previous_o3 = previous
+        .and_then(|r| nucleotide_atom(r, "O3'"))
+        .filter(|o3| bonded(Some(o3), own("P")));
+    let next_p = next
+        .and_then(|r| nucleotide_atom(r, "P"))
+        .filter(|p| bonded(own("O3'"), Some(p)));
+    let next_o5 = next_p.and(next.and_then(|r| nucleotide_atom(r, "O5'")));
+    let chi = if own("N9").is_some() {
+        torsion(own("O4'"), own("C1'"), own("N9"), own("C4"))
+    } else {
+        torsion(own("O4'"), own("C1'"), own("N1"), own("C2"))
+    };
+
+    NucleicTorsions {
+        alpha: torsion(previous_o3, own("P"), own("O5'"), own("C5'")),
+        beta: torsion(own("P"), own("O5'"), own("C5'"), own("C4'")),
+        gamma: torsion(own("O5'"), own("C5'"), own("C4'"), own("C3'")),
+        delta: torsion(own("C5'"), own("C4'"), own("C3'"), own("O3'")),
+        epsilon: torsion(own("C4'"), own("C3'"), own("O3'"), next_p),
+        zeta: torsion(own("C3'"), own("O3'"), next_p, next_o5),
+        chi,
+        pucker: sugar_pucker(residue),
+    }
+}
+
+/// Calculate the pseudorotation parameters of the sugar ring from its five endocyclic torsions
+fn sugar_pucker(residue: &Residue) -> Option<SugarPucker> {
+    let ring = ["C4'", "O4'", "C1'", "C2'", "C3'"]
+        .iter()
+        .map(|name| nucleotide_atom(residue, name))
+        .collect::<Option<Vec<&Atom>>>()?;
+    // nu0 (C4'-O4'-C1'-C2') to nu4 (C3'-C4'-O4'-C1')
+    let nu: Vec<f64> = (0..5)
+        .map(|i| {
+            dihedral(
+                ring[i],
+                ring[(i + 1) % 5],
+                ring[(i + 2) % 5],
+                ring[(i + 3) % 5],
+            )
+        })
+        .collect();
+    let (sin36, sin72) = (36.0_f64.to_radians().sin(), 72.0_f64.to_radians().sin());
+    let phase = ((nu[4] + nu[1]) - (nu[3] + nu[0]))
+        .atan2(2.0 * nu[2] * (sin36 + sin72))
+        .to_degrees()
+        .rem_euclid(360.0);
+    Some(SugarPucker {
+        phase,
+        amplitude: nu[2] / phase.to_radians().cos(),
+    })
+}
+
+/// The backbone torsion angles of an amino acid in degrees, see [`backbone_torsions`]. The
+/// angles are `None` when atoms needed for them are missing, or when there is no (bonded)
+/// neighbouring amino acid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackboneTorsions {
+    /// C(i-1)-N-CA-C
+    pub phi: Option<f64>,
+    /// N-CA-C-N(i+1)
+    pub psi: Option<f64>,
+    /// CA-C-N(i+1)-CA(i+1)
+    pub omega: Option<f64>,
+}
+
+/// The maximal length of the C-N peptide bond between two amino acids in Aͦ
+const PEPTIDE_BOND_MAX_LENGTH: f64 = 2.0;
+
+/// Find the atom with the given name in the first conformer of the residue
+fn residue_atom<'a>(residue: &'a Residue, name: &str) -> Option<&'a Atom> {
+    residue
+        .conformer(0)?
+        .atoms()
+        .find(|atom| atom.name() == name)
+}
+
+/// Calculate the backbone torsion angles (phi, psi, and omega) of an amino acid, with the
+/// previous and next amino acid in the chain for the torsions around the peptide bonds.
+/// Neighbours which are not bonded to the amino acid (C-N longer than 2 Aͦ) are ignored, so
+/// chain breaks are handled. See [`Chain::backbone_torsions`] to calculate these for a whole
+/// chain.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// let torsions = geometry::backbone_torsions(None, residues[0], Some(residues[1]));
+/// assert!(torsions.phi.is_none() && torsions.psi.is_some());
+/// ```
+pub fn backbone_torsions(
+    previous: Option<&Residue>,
+    residue: &Residue,
+    next: Option<&Residue>,
+) -> BackboneTorsions {
+    let own = |name| residue_atom(residue, name);
+    let torsion = |a: Option<&Atom>, b: Option<&Atom>, c: Option<&Atom>, d: Option<&Atom>| {
+        Some(dihedral(a?, b?, c?, d?))
+    };
+    let bonded = |c: Option<&Atom>, n: Option<&Atom>| match (c, n) {
+        (Some(c), Some(n)) => c.distance(n) <= PEPTIDE_BOND_MAX_LENGTH,
+        _ => false,
+    };
+    let previous_c = previous
+        .and_then(|r| residue_atom(r, "C"))
+        .filter(|c| bonded(Some(c), own("N")));
+    let next_n = next
+        .and_then(|r| residue_atom(r, "N"))
+        .filter(|n| bonded(own("C"), Some(n)));
+    let next_ca = next_n.and(next.and_then(|r| residue_atom(r, "CA")));
+
+    BackboneTorsions {
+        phi: torsion(previous_c, own("N"), own("CA"), own("C")),
+        psi: torsion(own("N"), own("CA"), own("C"), next_n),
+        omega: torsion(own("CA"), own("C"), next_n, next_ca),
+    }
+}
+
+/// A break in the polymer of a chain between two consecutive residues, see [`chain_breaks`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChainBreak<'a> {
+    /// The last residue before the break
+    pub before: &'a Residue,
+    /// The first residue after the break
+    pub after: &'a Residue,
+    /// The distance between the linking atoms (C-N for amino acids, O3'-P for nucleotides) in
+    /// Aͦ, `None` if any of these atoms is missing
+    pub distance: Option<f64>,
+}
+
+impl<'a> ChainBreak<'a> {
+    /// Whether the residues are physically disconnected, as their linking atoms are too far
+    /// apart to be bonded. If this is `false` the break is only a gap in the numbering.
+    pub fn is_physical(&self) -> bool {
+        self.distance.map_or(false, |d| {
+            d > PEPTIDE_BOND_MAX_LENGTH.max(PHOSPHODIESTER_MAX_LENGTH)
+        })
+    }
+
+    /// The number of residues missing in the break according to the residue serial numbers
+    pub fn missing_residues(&self) -> usize {
+        (self.after.serial_number() - self.before.serial_number() - 1)
+            .try_into()
+            .unwrap_or(0)
+    }
+}
+
+/// Find the breaks in the polymer of the given chain: consecutive residues whose linking atoms
+/// (C-N for amino acids, O3'-P for nucleotides) are too far apart to be bonded (longer than 2
+/// Aͦ), or whose serial numbers are not consecutive. This reveals missing residues which are not
+/// visible from the SEQRES records. Only the polymer residues with atoms (see
+/// [`Chain::sequence`]) and their first conformer are used, and a gap in the numbering of
+/// residues without linking atoms is reported with an unknown distance.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// assert!(geometry::chain_breaks(pdb.chain(0).unwrap()).is_empty());
+/// ```
+pub fn chain_breaks(chain: &Chain) -> Vec<ChainBreak<'_>> {
+    // Residues known only from the SEQRES records have no atoms, these are missing residues
+    let residues: Vec<&Residue> = chain
+        .polymer_residues()
+        .filter(|residue| residue.atom_count() > 0)
+        .collect();
+    residues
+        .windows(2)
+        .filter_map(|pair| {
+            let (before, after) = (pair[0], pair[1]);
+            let link =
+                |a: &str, b: &str| Some(residue_atom(before, a)?.distance(residue_atom(after, b)?));
+            let distance = link("C", "N").or_else(|| link("O3'", "P"));
+            let gap = after.serial_number() - before.serial_number() > 1;
+            let chain_break = ChainBreak {
+                before,
+                after,
+                distance,
+            };
+            (gap || chain_break.is_physical()).then_some(chain_break)
+        })
+        .collect()
+}
+
+/// Calculate the side chain torsion angles (chi1, chi2, ..) of an amino acid in degrees, using
+/// the first conformer of the residue. The angles are given in order up to the first angle
+/// with missing atoms, so a truncated side chain gives fewer angles. Residues without side
+/// chain torsions (like glycine, alanine, or any residue which is not an amino acid) give no
+/// angles.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let methionine = pdb.residues().next().unwrap();
+/// assert_eq!(geometry::side_chain_torsions(methionine).len(), 3);
+/// ```
+pub fn side_chain_torsions(residue: &Residue) -> Vec<f64> {
+    let name = match residue.conformer(0) {
+        Some(conformer) => conformer.name(),
+        None => return Vec::new(),
+    };
+    reference_tables::side_chain_torsions(name)
+        .iter()
+        .map_while(|names| {
+            let atoms = names
+                .iter()
+                .map(|name| residue_atom(residue, name))
+                .collect::<Option<Vec<&Atom>>>()?;
+            Some(dihedral(atoms[0], atoms[1], atoms[2], atoms[3]))
+        })
+        .collect()
+}
+
+/// The geometry of the atoms coordinating a metal, see [`coordination_sphere`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoordinationGeometry {
+    /// Two ligands at 180 degrees
+    Linear,
+    /// Three ligands in a plane at 120 degrees
+    TrigonalPlanar,
+    /// Three ligands in a plane in a T shape
+    TShaped,
+    /// Four ligands at the corners of a tetrahedron
+    Tetrahedral,
+    /// Four ligands at the corners of a square around the metal
+    SquarePlanar,
+    /// Five ligands, three in a plane with two on the axis through the metal
+    TrigonalBipyramidal,
+    /// Five ligands, four in a square with one on the axis through the metal
+    SquarePyramidal,
+    /// Six ligands at the corners of an octahedron
+    Octahedral,
+    /// Seven ligands, five in a plane with two on the axis through the metal
+    PentagonalBipyramidal,
+    /// No ideal geometry fits the ligands, or there is no ideal geometry for the number of
+    /// ligands
+    Irregular,
+}
+
+/// The atoms coordinating a metal, see [`coordination_sphere`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinationSphere<'a> {
+    /// The metal atom
+    pub metal: &'a Atom,
+    /// The coordinating atoms with their distance to the metal in Aͦ, sorted from close to far
+    pub ligands: Vec<(&'a Atom, f64)>,
+    /// The ideal geometry best matching the ligands
+    pub geometry: CoordinationGeometry,
+    /// The root mean square deviation in degrees of the ligand-metal-ligand angles from the
+    /// angles of the ideal geometry, `None` for irregular geometries
+    pub angle_deviation: Option<f64>,
+    /// Warnings about the coordination, like an unusual number of ligands for the metal
+    pub warnings: Vec<PDBError>,
+}
+
+impl<'a> CoordinationSphere<'a> {
+    /// Get the coordination number, the number of coordinating atoms
+    pub fn coordination_number(&self) -> usize {
+        self.ligands.len()
+    }
+}
+
+/// The maximal RMSD of the angles in degrees to classify a coordination as an ideal geometry
+const COORDINATION_MAX_DEVIATION: f64 = 20.0;
+
+/// The ligand-metal-ligand angles of the ideal coordination geometries, sorted from small to
+/// large
+fn ideal_coordination_angles() -> Vec<(CoordinationGeometry, Vec<f64>)> {
+    let tetrahedral = 109.47;
+    let repeat = |angles: &[(f64, usize)]| -> Vec<f64> {
+        angles
+            .iter()
+            .flat_map(|(angle, count)| std::iter::repeat(*angle).take(*count))
+            .collect()
+    };
+    vec![
+        (CoordinationGeometry::Linear, repeat(&[(180.0, 1)])),
+        (CoordinationGeometry::TrigonalPlanar, repeat(&[(120.0, 3)])),
+        (
+            CoordinationGeometry::TShaped,
+            repeat(&[(90.0, 2), (180.0, 1)]),
+        ),
+        (
+            CoordinationGeometry::Tetrahedral,
+            repeat(&[(tetrahedral, 6)]),
+        ),
+        (
+            CoordinationGeometry::SquarePlanar,
+            repeat(&[(90.0, 4), (180.0, 2)]),
+        ),
+        (
+            CoordinationGeometry::TrigonalBipyramidal,
+            repeat(&[(90.0, 6), (120.0, 3), (180.0, 1)]),
+        ),
+        (
+            CoordinationGeometry::SquarePyramidal,
+            repeat(&[(90.0, 8), (180.0, 2)]),
+        ),
+        (
+            CoordinationGeometry::Octahedral,
+            repeat(&[(90.0, 12), (180.0, 3)]),
+        ),
+        (
+            CoordinationGeometry::PentagonalBipyramidal,
+            repeat(&[(72.0, 5), (90.0, 10), (144.0, 5), (180.0, 1)]),
+        ),
+    ]
+}
+
+/// Analyse the coordination sphere of the given metal atom: all atoms of the given atoms
+/// within the cutoff distance (in Aͦ) of the metal are taken as ligands, except for hydrogens,
+/// carbons, and other metals. The ligand-metal-ligand angles are compared to the ideal
+/// geometries with the same number of ligands, and the best fitting geometry is reported, or
+/// [`CoordinationGeometry::Irregular`] if none fits within 20 degrees RMSD. Warnings are given
+/// for coordination numbers which are unusual for the metal and when the atom is not a metal.
+/// See [`PDB::coordination_sphere`] to use all atoms of a structure.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/3nig.pdb").unwrap();
+/// let calcium = pdb.atoms().find(|a| a.element() == Some(&Element::Ca)).unwrap();
+/// let atoms: Vec<&Atom> = pdb.atoms().collect();
+/// let sphere = geometry::coordination_sphere(calcium, &atoms, 3.0);
+/// assert!(sphere.coordination_number() >= 6);
+/// assert!(sphere.warnings.is_empty());
+/// ```
+pub fn coordination_sphere<'a>(
+    metal: &'a Atom,
+    atoms: &[&'a Atom],
+    cutoff: f64,
+) -> CoordinationSphere<'a> {
+    let mut ligands: Vec<(&Atom, f64)> = atoms
+        .iter()
+        .filter(|atom| {
+            !std::ptr::eq(**atom, metal)
+                && atom.element().map_or(false, |element| {
+                    !matches!(element, Element::H | Element::C) && !element.is_metal()
+                })
+        })
+        .map(|atom| (*atom, atom.distance(metal)))
+        .filter(|(_, distance)| *distance <= cutoff)
+        .collect();
+    ligands.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let mut angles: Vec<f64> = ligands
+        .iter()
+        .enumerate()
+        .flat_map(|(index, (a, _))| {
+            ligands[index + 1..]
+                .iter()
+                .map(move |(b, _)| angle(a, metal, b))
+        })
+        .collect();
+    angles.sort_by(f64::total_cmp);
+    #[allow(clippy::cast_precision_loss)]
+    let best = ideal_coordination_angles()
+        .into_iter()
+        .filter(|(_, ideal)| ideal.len() == angles.len())
+        .map(|(geometry, ideal)| {
+            let squared: f64 = ideal
+                .iter()
+                .zip(&angles)
+                .map(|(ideal, angle)| (ideal - angle).powi(2))
+                .sum();
+            (geometry, (squared / angles.len() as f64).sqrt())
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .filter(|(_, deviation)| *deviation <= COORDINATION_MAX_DEVIATION);
+
+    let mut warnings = Vec::new();
+    let name = metal
+        .element()
+        .map_or_else(|| metal.name().to_string(), ToString::to_string);
+    let context = Context::show(format!("{} {}", metal.serial_number(), metal.name()));
+    if metal.element().map_or(true, |element| !element.is_metal()) {
+        warnings.push(PDBError::new(
+            ErrorLevel::GeneralWarning,
+            "Not a metal",
+            format!("The coordination sphere is analysed for a {name} atom, which is not a metal."),
+            context.clone(),
+        ));
+    }
+    let (low, high) = metal
+        .element()
+        .and_then(reference_tables::coordination_numbers)
+        .unwrap_or((2, 9));
+    if !(low..=high).contains(&ligands.len()) {
+        warnings.push(PDBError::new(
+            ErrorLevel::GeneralWarning,
+            "Unusual coordination number",
+            format!(
+                "The {name} atom has {} coordinating atoms within {cutoff} Aͦ, while {low} to {high} is expected.",
+                ligands.len()
+            ),
+            context,
+        ));
+    }
+
+    CoordinationSphere {
+        metal,
+        ligands,
+        geometry: best.map_or(CoordinationGeometry::Irregular, |(geometry, _)| geometry),
+        angle_deviation: best.map(|(_, deviation)| deviation),
+        warnings,
+    }
+}
+
+/// Calculate the center of mass of the given atoms, weighted by the standard atomic weights of
+/// their elements (see [`Element::weight`]). Atoms without an element or known weight are
+/// ignored. Returns `None` if no atom has a known weight.
+pub fn center_of_mass(atoms: &[&Atom]) -> Option<(f64, f64, f64)> {
+    let (mut sum, mut total) = ((0.0, 0.0, 0.0), 0.0);
+    for atom in atoms {
+        if let Some(weight) = atom.element().and_then(Element::weight) {
+            let (x, y, z) = atom.pos();
+            sum = (sum.0 + weight * x, sum.1 + weight * y, sum.2 + weight * z);
+            total += weight;
+        }
+    }
+    (total > 0.0).then(|| (sum.0 / total, sum.1 / total, sum.2 / total))
+}
+
+/// The conversion factor from e·Aͦ to Debye
+const DEBYE_PER_ELECTRON_ANGSTROM: f64 = 4.803_204;
+
+/// An electric dipole moment, see [`dipole_moment`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DipoleMoment {
+    /// The dipole vector in e·Aͦ, pointing from the negative to the positive charges
+    pub vector: (f64, f64, f64),
+    /// The length of the dipole vector in e·Aͦ
+    pub magnitude: f64,
+}
+
+impl DipoleMoment {
+    /// Get the magnitude of the dipole moment in Debye
+    pub fn debye(&self) -> f64 {
+        self.magnitude * DEBYE_PER_ELECTRON_ANGSTROM
+    }
+}
+
+/// Calculate the electric dipole moment of the given atoms with the given partial charges (in
+/// elementary charges) relative to their center of mass (see [`center_of_mass`]). The partial
+/// charges are given by a function, so they can come from any source. For PQR files, which
+/// can be read as PDB files, the charges are stored in the occupancy column so
+/// `|atom| atom.occupancy()` can be used. For charged selections the dipole moment depends on
+/// the origin, which is why the center of mass is used. If no atom has a known weight the
+/// geometric center is used instead.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let positive = Atom::new(false, 1, "NA", 0.0, 0.0, 1.0, 1.0, 0.0, "NA", 1).unwrap();
+/// let negative = Atom::new(false, 2, "CL", 0.0, 0.0, -1.0, 1.0, 0.0, "CL", -1).unwrap();
+/// let dipole = geometry::dipole_moment(&[&positive, &negative], |atom| atom.charge() as f64);
+/// assert!((dipole.magnitude - 2.0).abs() < 1e-9);
+/// assert!(dipole.vector.2 > 0.0);
+/// ```
+pub fn dipole_moment(atoms: &[&Atom], charge: impl Fn(&Atom) -> f64) -> DipoleMoment {
+    let center =
+        center_of_mass(atoms).unwrap_or_else(|| centroid(&positions(atoms.iter().copied())));
+    let mut vector = (0.0, 0.0, 0.0);
+    for atom in atoms {
+        let (q, (x, y, z)) = (charge(atom), atom.pos());
+        vector.0 += q * (x - center.0);
+        vector.1 += q * (y - center.1);
+        vector.2 += q * (z - center.2);
+    }
+    DipoleMoment {
+        vector,
+        magnitude: dot(
+            [vector.0, vector.1, vector.2],
+            [vector.0, vector.1, vector.2],
+        )
+        .sqrt(),
+    }
+}
+
+/// A steric clash between two atoms, see [`clashes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clash<'a> {
+    /// The first atom
+    pub atom_a: &'a Atom,
+    /// The second atom
+    pub atom_b: &'a Atom,
+    /// The overlap of the van der Waals spheres of the atoms in Aͦ
+    pub overlap: f64,
+}
+
+/// The minimal overlap of the van der Waals spheres of two atoms in Aͦ to count as a clash, as
+/// used by MolProbity
+pub const CLASH_MIN_OVERLAP: f64 = 0.4;
+
+/// The maximal number of bonds between two atoms which are not checked for clashes, so bonded
+/// atoms and atoms in angles and torsions are excluded
+const CLASH_MAX_BOND_SEPARATION: usize = 3;
+
+/// Test if the two atoms could form a hydrogen bond, when both are nitrogen or oxygen atoms or
+/// when one of them is a hydrogen bonded to a nitrogen or oxygen (a polar hydrogen)
+fn hydrogen_bond_pair(a: &Atom, b: &Atom, a_polar_hydrogen: bool, b_polar_hydrogen: bool) -> bool {
+    let acceptor = |atom: &Atom| matches!(atom.element(), Some(Element::N | Element::O));
+    (acceptor(a) || a_polar_hydrogen) && (acceptor(b) || b_polar_hydrogen)
+}
+
+/// Find the steric clashes between the atoms of the given residues: pairs of atoms with an
+/// overlap of their van der Waals spheres of at least [`CLASH_MIN_OVERLAP`]. Only the first
+/// conformer of every residue is used. Atoms which are separated by three bonds or less (with
+/// the bonds inferred from the covalent radii), pairs which could form a hydrogen bond, and
+/// pairs with metals are not counted as clashes. The clashes are sorted from the largest to the
+/// smallest overlap. A spatial index is used so this scales to large structures. See
+/// [`crate::clash_score`] for a normalized score for a whole structure.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// for clash in geometry::clashes(&residues) {
+///     assert!(clash.overlap >= geometry::CLASH_MIN_OVERLAP);
+/// }
+/// ```
+#[doc_cfg(feature = "rstar")]
+pub fn clashes<'a>(residues: &[&'a Residue]) -> Vec<Clash<'a>> {
+    use rstar::primitives::GeomWithData;
+
+    let atoms: Vec<&Atom> = residues
+        .iter()
+        .filter_map(|residue| residue.conformer(0))
+        .flat_map(Conformer::atoms)
+        .filter(|atom| atom.element().is_some())
+        .collect();
+    let tree = rstar::RTree::bulk_load(
+        atoms
+            .iter()
+            .enumerate()
+            .map(|(index, atom)| {
+                let (x, y, z) = atom.pos();
+                GeomWithData::new([x, y, z], index)
+            })
+            .collect(),
+    );
+    let max_radius = atoms.iter().map(|atom| radius(atom)).fold(0.0, f64::max);
+    let neighbours = |index: usize, cutoff: f64| {
+        let (x, y, z) = atoms[index].pos();
+        tree.locate_within_distance([x, y, z], cutoff * cutoff)
+            .map(|point| point.data)
+            .filter(move |other| *other != index)
+    };
+
+    let covalent = |atom: &Atom| {
+        atom.element()
+            .map_or(0.0, |e| e.atomic_radius().covalent_single)
+    };
+    let bonds: Vec<Vec<usize>> = (0..atoms.len())
+        .map(|i| {
+            neighbours(i, 2.0 * max_radius)
+                .filter(|j| {
+                    atoms[i].distance(atoms[*j]) <= covalent(atoms[i]) + covalent(atoms[*j]) + 0.4
+                })
+                .collect()
+        })
+        .collect();
+    let polar_hydrogen: Vec<bool> = (0..atoms.len())
+        .map(|i| {
+            atoms[i].element() == Some(&Element::H)
+                && bonds[i]
+                    .iter()
+                    .any(|j| matches!(atoms[*j].element(), Some(Element::N | Element::O)))
+        })
+        .collect();
+    // The atoms within the given number of bonds of the start atom
+    let bonded_within = |start: usize| {
+        let mut found = vec![start];
+        let mut front = vec![start];
+        for _ in 0..CLASH_MAX_BOND_SEPARATION {
+            front = front
+                .iter()
+                .flat_map(|i| bonds[*i].iter().copied())
+                .filter(|j| !found.contains(j))
+                .collect();
+            found.extend(front.iter().copied());
+        }
+        found
+    };
+
+    let mut clashes = Vec::new();
+    for i in 0..atoms.len() {
+        if atoms[i].element().map_or(false, Element::is_metal) {
+            continue;
+        }
+        let excluded = bonded_within(i);
+        for j in neighbours(i, 2.0 * max_radius).filter(|j| *j > i) {
+            let (a, b) = (atoms[i], atoms[j]);
+            if b.element().map_or(false, Element::is_metal)
+                || excluded.contains(&j)
+                || hydrogen_bond_pair(a, b, polar_hydrogen[i], polar_hydrogen[j])
+            {
+                continue;
+            }
+            let overlap = radius(a) + radius(b) - a.distance(b);
+            if overlap >= CLASH_MIN_OVERLAP {
+                clashes.push(Clash {
+                    atom_a: a,
+                    atom_b: b,
+                    overlap,
+                });
+            }
+        }
+    }
+    clashes.sort_by(|a, b| b.overlap.total_cmp(&a.overlap));
+    clashes
+}
+
+/// The intrinsic width (standard deviation) in Aͦ of the electron density of an atom at rest, used
+/// by [`AtomKernel::Gaussian`]
+const DENSITY_INTRINSIC_WIDTH: f64 = 0.5;
+/// The number of standard deviations after which a Gaussian atom is cut off
+const DENSITY_CUTOFF: f64 = 3.0;
+
+/// How every atom contributes to a density grid, see [`density_grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AtomKernel {
+    /// Add the occupancy of the atom to the grid point nearest to its center, giving atom counts
+    #[default]
+    Count,
+    /// Spread the electrons of the atom (its atomic number minus its charge, times its
+    /// occupancy) over the grid as a Gaussian, with a width following from its B factor, giving
+    /// electron counts. Atoms without an element are skipped.
+    Gaussian,
+}
+
+/// A regular three dimensional grid of values around a structure, see [`density_grid`]. The
+/// values are stored with x changing fastest and z slowest, as in CCP4/MRC maps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DensityGrid {
+    /// The position in Aͦ of the first grid point, always a multiple of the spacing
+    origin: (f64, f64, f64),
+    /// The distance between neighbouring grid points in Aͦ
+    spacing: f64,
+    /// The number of grid points along x, y, and z
+    dimensions: (usize, usize, usize),
+    /// The values at all grid points
+    values: Vec<f64>,
+}
+
+impl DensityGrid {
+    /// The position in Aͦ of the first grid point.
+    pub const fn origin(&self) -> (f64, f64, f64) {
+        self.origin
+    }
+
+    /// The distance between neighbouring grid points in Aͦ.
+    pub const fn spacing(&self) -> f64 {
+        self.spacing
+    }
+
+    /// The number of grid points along x, y, and z.
+    pub const fn dimensions(&self) -> (usize, usize, usize) {
+        self.dimensions
+    }
+
+    /// Get the value at the given grid point, or `None` if it is out of bounds.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<f64> {
+        let (nx, ny, nz) = self.dimensions;
+        (x < nx && y < ny && z < nz).then(|| self.values[x + nx * (y + ny * z)])
+    }
+
+    /// Get the position in Aͦ of the given grid point.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn position(&self, x: usize, y: usize, z: usize) -> (f64, f64, f64) {
+        (
+            self.origin.0 + x as f64 * self.spacing,
+            self.origin.1 + y as f64 * self.spacing,
+            self.origin.2 + z as f64 * self.spacing,
+        )
+    }
+
+    /// Get the value at the grid point nearest to the given position, or `None` if the
+    /// position is outside the grid.
+    pub fn value_at(&self, position: (f64, f64, f64)) -> Option<f64> {
+        let (x, y, z) = self.nearest(position)?;
+        self.get(x, y, z)
+    }
+
+    /// Get all values, with x changing fastest and z slowest.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Get a mask of this grid, with 1.0 at all grid points with a value of at least the
+    /// given threshold and 0.0 everywhere else.
+    #[must_use]
+    pub fn mask(&self, threshold: f64) -> Self {
+        Self {
+            values: self
+                .values
+                .iter()
+                .map(|v| if *v >= threshold { 1.0 } else { 0.0 })
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// The grid point nearest to the given position, if it is inside the grid
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn nearest(&self, position: (f64, f64, f64)) -> Option<(usize, usize, usize)> {
+        let index = |p: f64, o: f64, n: usize| {
+            let i = ((p - o) / self.spacing).round();
+            (i >= 0.0 && i < n as f64).then_some(i as usize)
+        };
+        Some((
+            index(position.0, self.origin.0, self.dimensions.0)?,
+            index(position.1, self.origin.1, self.dimensions.1)?,
+            index(position.2, self.origin.2, self.dimensions.2)?,
+        ))
+    }
+
+    /// Create a grid covering the given atoms with the given margin, with all values set to the
+    /// given value. The origin is aligned to a multiple of the spacing.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn around(atoms: &[&Atom], spacing: f64, margin: f64, value: f64) -> Self {
+        let (mut min, mut max) = ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]);
+        for atom in atoms {
+            for (axis, value) in [atom.x(), atom.y(), atom.z()].into_iter().enumerate() {
+                min[axis] = min[axis].min(value);
+                max[axis] = max[axis].max(value);
+            }
+        }
+        let start = min.map(|m| ((m - margin) / spacing).floor());
+        let size =
+            |axis: usize| (((max[axis] + margin) / spacing).ceil() - start[axis]) as usize + 1;
+        let dimensions = (size(0), size(1), size(2));
+        Self {
+            origin: (start[0] * spacing, start[1] * spacing, start[2] * spacing),
+            spacing,
+            dimensions,
+            values: vec![value; dimensions.0 * dimensions.1 * dimensions.2],
+        }
+    }
+
+    /// The indices of the grid points within the given distance of the given position, with
+    /// their distance to the position
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn points_within(&self, position: (f64, f64, f64), reach: f64) -> Vec<(usize, f64)> {
+        let (nx, ny, nz) = self.dimensions;
+        // The range of grid points within reach along one axis
+        let range = |p: f64, o: f64, n: usize| {
+            let low = ((p - reach - o) / self.spacing).ceil().max(0.0) as usize;
+            let high = ((((p + reach - o) / self.spacing).floor()).max(0.0) as usize).min(n - 1);
+            low..=high
+        };
+        let mut points = Vec::new();
+        for z in range(position.2, self.origin.2, nz) {
+            for y in range(position.1, self.origin.1, ny) {
+                for x in range(position.0, self.origin.0, nx) {
+                    let distance = position_distance(position, self.position(x, y, z));
+                    if distance <= reach {
+                        points.push((x + nx * (y + ny * z), distance));
+                    }
+                }
+            }
+        }
+        points
+    }
+}
+
+/// Voxelize the given atoms onto a grid with the given spacing in Aͦ, with every atom
+/// contributing according to the given kernel. The grid covers all atoms, with a margin to fit
+/// the Gaussian atoms, and its origin is aligned to a multiple of the spacing so grids with the
+/// same spacing line up. The grid can be saved as a CCP4/MRC map with [`crate::save_map`].
+/// Returns `None` if there are no atoms or the spacing is not positive.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let atoms: Vec<&Atom> = pdb.atoms().collect();
+/// let grid = geometry::density_grid(&atoms, 1.0, geometry::AtomKernel::Count).unwrap();
+/// let total: f64 = grid.as_slice().iter().sum();
+/// assert!((total - atoms.iter().map(|a| a.occupancy()).sum::<f64>()).abs() < 1e-6);
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn density_grid(atoms: &[&Atom], spacing: f64, kernel: AtomKernel) -> Option<DensityGrid> {
+    if atoms.is_empty() || spacing <= 0.0 || !spacing.is_finite() {
+        return None;
+    }
+    // The electrons and the width of the Gaussian of every atom
+    let gaussians: Vec<(f64, f64)> = match kernel {
+        AtomKernel::Count => Vec::new(),
+        AtomKernel::Gaussian => atoms
+            .iter()
+            .map(|atom| {
+                let electrons = atom.element().map_or(0.0, |e| {
+                    (e.atomic_number() as f64 - atom.charge() as f64) * atom.occupancy()
+                });
+                let variance = DENSITY_INTRINSIC_WIDTH.powi(2)
+                    + atom.b_factor().max(0.0) / (8.0 * std::f64::consts::PI.powi(2));
+                (electrons, variance.sqrt())
+            })
+            .collect(),
+    };
+    let margin = gaussians
+        .iter()
+        .map(|(_, width)| DENSITY_CUTOFF * width)
+        .fold(0.0, f64::max)
+        + spacing;
+
+    let mut grid = DensityGrid::around(atoms, spacing, margin, 0.0);
+    let (nx, ny) = (grid.dimensions.0, grid.dimensions.1);
+
+    if kernel == AtomKernel::Count {
+        for atom in atoms {
+            if let Some((x, y, z)) = grid.nearest(atom.pos()) {
+                grid.values[x + nx * (y + ny * z)] += atom.occupancy();
+            }
+        }
+        return Some(grid);
+    }
+
+    for (atom, (electrons, width)) in atoms.iter().zip(gaussians) {
+        if electrons == 0.0 {
+            continue;
+        }
+        let weights: Vec<(usize, f64)> = grid
+            .points_within(atom.pos(), DENSITY_CUTOFF * width)
+            .into_iter()
+            .map(|(index, distance)| (index, (-distance * distance / (2.0 * width * width)).exp()))
+            .collect();
+        // Normalise over the grid points, so the cut off and coarse grids keep all electrons
+        let total: f64 = weights.iter().map(|(_, w)| w).sum();
+        for (index, weight) in weights {
+            grid.values[index] += electrons * weight / total;
+        }
+    }
+    Some(grid)
+}
+
+/// The six tetrahedra a cube is split into for [`DensityGrid::isosurface`], as corners of the
+/// cube numbered with bit 0 for x, bit 1 for y, and bit 2 for z. All share the diagonal from
+/// corner 0 to corner 7, so the faces of neighbouring cubes are split in the same way.
+const CUBE_TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 7, 1, 3],
+    [0, 7, 3, 2],
+    [0, 7, 2, 6],
+    [0, 7, 6, 4],
+    [0, 7, 4, 5],
+    [0, 7, 5, 1],
+];
+
+/// A grid point of a [`DensityGrid`] as its index and position
+type GridPoint = (usize, (f64, f64, f64));
+
+/// A triangle mesh of a surface, see [`surface_mesh`] and [`DensityGrid::isosurface`]. It can be
+/// saved as an OBJ or PLY file with [`crate::save_mesh`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Mesh {
+    /// The positions of the vertices in Aͦ
+    pub vertices: Vec<(f64, f64, f64)>,
+    /// The unit normals at the vertices, pointing outwards
+    pub normals: Vec<(f64, f64, f64)>,
+    /// The triangles as indices into the vertices, counterclockwise when seen from outside
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    /// Whether this mesh has no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// The total area of all triangles in Aͦ².
+    pub fn area(&self) -> f64 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let n = self.face_normal(t);
+                (n.0 * n.0 + n.1 * n.1 + n.2 * n.2).sqrt() / 2.0
+            })
+            .sum()
+    }
+
+    /// The (not normalised) normal of the given triangle, with a length of twice its area
+    fn face_normal(&self, triangle: &[usize; 3]) -> (f64, f64, f64) {
+        let [a, b, c] = triangle.map(|i| self.vertices[i]);
+        let (u, v) = (
+            (b.0 - a.0, b.1 - a.1, b.2 - a.2),
+            (c.0 - a.0, c.1 - a.1, c.2 - a.2),
+        );
+        (
+            u.1 * v.2 - u.2 * v.1,
+            u.2 * v.0 - u.0 * v.2,
+            u.0 * v.1 - u.1 * v.0,
+        )
+    }
+}
+
+impl DensityGrid {
+    /// Generate a triangle mesh of the surface where this grid crosses the given level, with
+    /// marching cubes. Every cube is split into six tetrahedra to avoid the ambiguous cases of
+    /// the original algorithm. The grid points with a value of at least the level are inside.
+    /// Vertices on the same edge of the grid are shared between triangles, and the normals
+    /// point outwards.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn isosurface(&self, level: f64) -> Mesh {
+        let (nx, ny, nz) = self.dimensions;
+        let mut mesh = Mesh::default();
+        let mut edges: FxHashMap<(usize, usize), usize> = FxHashMap::default();
+        // The vertex on the edge between the given grid points
+        let mut vertex = |mesh: &mut Mesh, a: GridPoint, b: GridPoint| {
+            *edges
+                .entry((a.0.min(b.0), a.0.max(b.0)))
+                .or_insert_with(|| {
+                    let (va, vb) = (self.values[a.0], self.values[b.0]);
+                    let t = (level - va) / (vb - va);
+                    mesh.vertices.push((
+                        a.1 .0 + t * (b.1 .0 - a.1 .0),
+                        a.1 .1 + t * (b.1 .1 - a.1 .1),
+                        a.1 .2 + t * (b.1 .2 - a.1 .2),
+                    ));
+                    mesh.vertices.len() - 1
+                })
+        };
+        for z in 0..nz.saturating_sub(1) {
+            for y in 0..ny.saturating_sub(1) {
+                for x in 0..nx.saturating_sub(1) {
+                    let corners: Vec<GridPoint> = (0..8)
+                        .map(|c| {
+                            let (cx, cy, cz) = (x + (c & 1), y + (c >> 1 & 1), z + (c >> 2 & 1));
+                            (cx + nx * (cy + ny * cz), self.position(cx, cy, cz))
+                        })
+                        .collect();
+                    for tetrahedron in CUBE_TETRAHEDRA {
+                        let points = tetrahedron.map(|c| corners[c]);
+                        let (inside, outside): (Vec<&GridPoint>, Vec<_>) =
+                            points.iter().partition(|p| self.values[p.0] >= level);
+                        // The triangles as the edges of the tetrahedron their vertices are on
+                        let triangles = match (inside.len(), outside.len()) {
+                            (1, 3) => vec![[0, 1, 2].map(|i| (inside[0], outside[i]))],
+                            (3, 1) => vec![[0, 1, 2].map(|i| (inside[i], outside[0]))],
+                            (2, 2) => {
+                                let quad = [(0, 0), (0, 1), (1, 1), (1, 0)]
+                                    .map(|(i, o)| (inside[i], outside[o]));
+                                vec![[quad[0], quad[1], quad[2]], [quad[0], quad[2], quad[3]]]
+                            }
+                            _ => continue,
+                        };
+                        // Orient the triangles to point from the inside to the outside, using
+                        // the midpoints of the edges as the vertices can coincide
+                        let centre = |points: &[&GridPoint]| {
+                            let n = points.len() as f64;
+                            points.iter().fold((0.0, 0.0, 0.0), |s, p| {
+                                (s.0 + p.1 .0 / n, s.1 + p.1 .1 / n, s.2 + p.1 .2 / n)
+                            })
+                        };
+                        let (i, o) = (centre(&inside), centre(&outside));
+                        let direction = (o.0 - i.0, o.1 - i.1, o.2 - i.2);
+                        for edges in triangles {
+                            let [a, b, c] = edges.map(|(p, q)| {
+                                (
+                                    (p.1 .0 + q.1 .0) / 2.0,
+                                    (p.1 .1 + q.1 .1) / 2.0,
+                                    (p.1 .2 + q.1 .2) / 2.0,
+                                )
+                            });
+                            let (u, v) = (
+                                (b.0 - a.0, b.1 - a.1, b.2 - a.2),
+                                (c.0 - a.0, c.1 - a.1, c.2 - a.2),
+                            );
+                            let dot = (u.1 * v.2 - u.2 * v.1) * direction.0
+                                + (u.2 * v.0 - u.0 * v.2) * direction.1
+                                + (u.0 * v.1 - u.1 * v.0) * direction.2;
+                            let mut triangle = edges.map(|(p, q)| vertex(&mut mesh, *p, *q));
+                            if dot < 0.0 {
+                                triangle.swap(1, 2);
+                            }
+                            mesh.triangles.push(triangle);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Average the normals of the triangles around every vertex, weighted by their area
+        let mut normals = vec![(0.0, 0.0, 0.0); mesh.vertices.len()];
+        for triangle in &mesh.triangles {
+            let n = mesh.face_normal(triangle);
+            for index in triangle {
+                let normal: &mut (f64, f64, f64) = &mut normals[*index];
+                *normal = (normal.0 + n.0, normal.1 + n.1, normal.2 + n.2);
+            }
+        }
+        mesh.normals = normals
+            .into_iter()
+            .map(|n| {
+                let length = (n.0 * n.0 + n.1 * n.1 + n.2 * n.2).sqrt();
+                if length > 0.0 {
+                    (n.0 / length, n.1 / length, n.2 / length)
+                } else {
+                    n
+                }
+            })
+            .collect();
+        mesh
+    }
+}
+
+/// The type of molecular surface generated by [`surface_mesh`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurfaceType {
+    /// The solvent accessible surface, traced by the centre of the solvent probe rolling over
+    /// the van der Waals spheres of the atoms
+    Accessible,
+    /// The solvent excluded (Connolly) surface, traced by the surface of the solvent probe
+    #[default]
+    Excluded,
+}
+
+/// Generate a triangle mesh of the molecular surface of the given atoms, with a grid of the
+/// given spacing in Aͦ (see [`DensityGrid::isosurface`]). The van der Waals radii of the
+/// elements are used, with a probe radius of [`PROBE_RADIUS`]. The solvent excluded surface is
+/// approximated from the accessible surface, as the points at one probe radius from it, so
+/// its accuracy depends on the spacing. Cavities in the structure give separate surfaces.
+/// Returns `None` if there are no atoms or the spacing is not positive.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let atoms: Vec<&Atom> = pdb.atoms().collect();
+/// let mesh = geometry::surface_mesh(&atoms, geometry::SurfaceType::Accessible, 1.0).unwrap();
+/// println!("{} triangles, {:.0} Aͦ²", mesh.triangles.len(), mesh.area());
+/// ```
+pub fn surface_mesh(atoms: &[&Atom], surface: SurfaceType, spacing: f64) -> Option<Mesh> {
+    if atoms.is_empty() || spacing <= 0.0 || !spacing.is_finite() {
+        return None;
+    }
+    let radii: Vec<f64> = atoms.iter().map(|a| radius(a) + PROBE_RADIUS).collect();
+    let max_radius = radii.iter().copied().fold(0.0, f64::max);
+
+    // The distance inside the accessible surface, down to minus the spacing outside of it
+    let mut grid = DensityGrid::around(atoms, spacing, max_radius + 2.0 * spacing, -spacing);
+    for (atom, radius) in atoms.iter().zip(&radii) {
+        for (index, distance) in grid.points_within(atom.pos(), radius + spacing) {
+            grid.values[index] = grid.values[index].max(radius - distance);
+        }
+    }
+    if surface == SurfaceType::Accessible {
+        return Some(grid.isosurface(0.0));
+    }
+
+    // The positions of the probe centres are on the edges crossing the accessible surface
+    let (nx, ny, nz) = grid.dimensions;
+    let mut probes = Vec::new();
+    for z in 0..nz {
+        for y in 0..ny {
+            for x in 0..nx {
+                let a = x + nx * (y + ny * z);
+                for (dx, dy, dz) in [(1, 0, 0), (0, 1, 0), (0, 0, 1)] {
+                    if x + dx >= nx || y + dy >= ny || z + dz >= nz {
+                        continue;
+                    }
+                    let b = a + dx + nx * (dy + ny * dz);
+                    let (va, vb) = (grid.values[a], grid.values[b]);
+                    if (va >= 0.0) != (vb >= 0.0) {
+                        let t = va / (va - vb);
+                        let (pa, pb) = (
+                            grid.position(x, y, z),
+                            grid.position(x + dx, y + dy, z + dz),
+                        );
+                        probes.push((
+                            pa.0 + t * (pb.0 - pa.0),
+                            pa.1 + t * (pb.1 - pa.1),
+                            pa.2 + t * (pb.2 - pa.2),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    // The distance to the nearest probe centre minus the probe radius inside the accessible
+    // surface, which is zero at the excluded surface
+    let accessible = std::mem::take(&mut grid.values);
+    grid.values = accessible
+        .iter()
+        .map(|v| if *v >= 0.0 { spacing } else { -PROBE_RADIUS })
+        .collect();
+    for probe in probes {
+        for (index, distance) in grid.points_within(probe, PROBE_RADIUS + spacing) {
+            if accessible[index] >= 0.0 {
+                grid.values[index] = grid.values[index].min(distance - PROBE_RADIUS);
+            }
+        }
+    }
+    Some(grid.isosurface(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_dihedral() {
+        let a = Atom::new(false, 0, "", 1.0, 0.0, 0.0, 1.0, 0.0, "", 0).unwrap();
+        let b = Atom::new(false, 0, "", 0.0, 0.0, 0.0, 1.0, 0.0, "", 0).unwrap();
+        let c = Atom::new(false, 0, "", 0.0, 1.0, 0.0, 1.0, 0.0, "", 0).unwrap();
+        let d = Atom::new(false, 0, "", 0.0, 1.0, 1.0, 1.0, 0.0, "", 0).unwrap();
+        let e = Atom::new(false, 0, "", 0.0, 1.0, -1.0, 1.0, 0.0, "", 0).unwrap();
+        assert!((dihedral(&a, &b, &c, &d) + 90.0).abs() < 1e-9);
+        assert!((dihedral(&a, &b, &c, &e) - 90.0).abs() < 1e-9);
+        assert!((dihedral(&a, &b, &c, &d).abs() - a.dihedral(&b, &c, &d)).abs() < 1e-9);
+        assert!((angle(&a, &b, &c) - 90.0).abs() < 1e-9);
+        assert!((distance(&a, &c) - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn depth_single_atom() {
+        let a = Atom::new(false, 0, "C", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap();
+        let b = Atom::new(false, 0, "C", 20.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap();
+        let van_der_waals = Element::C.atomic_radius().van_der_waals.unwrap();
+        for value in depth(&[&a, &b]) {
+            assert!((value - van_der_waals).abs() < 1e-9);
+        }
+        assert!(depth(&[]).is_empty());
+    }
+
+    #[test]
+    fn exposure_classification() {
+        assert_eq!(Exposure::classify(0.1, 0.25), Exposure::Buried);
+        assert_eq!(Exposure::classify(0.25, 0.25), Exposure::Exposed);
+        assert_eq!(Exposure::classify(0.1, 0.05), Exposure::Exposed);
+        let residue = Residue::new(
+            1,
+            None,
+            Some(
+                Conformer::new(
+                    "ALA",
+                    None,
+                    Some(Atom::new(false, 1, "CA", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap()),
+                )
+                .unwrap(),
+            ),
+        )
+        .unwrap();
+        let relative = relative_sasa(&[&residue]);
+        assert_eq!(relative.len(), 1);
+        assert!(relative[0].1 > 0.0);
+    }
+
+    #[test]
+    fn matrices() {
+        let a = Atom::new(false, 0, "", 0.0, 0.0, 0.0, 1.0, 0.0, "", 0).unwrap();
+        let b = Atom::new(false, 0, "", 3.0, 4.0, 0.0, 1.0, 0.0, "", 0).unwrap();
+        let c = Atom::new(false, 0, "", 0.0, 0.0, 1.0, 1.0, 0.0, "", 0).unwrap();
+        let matrix = distance_matrix(&[&a, &b], &[&a, &b, &c]);
+        assert_eq!(matrix.rows(), 2);
+        assert_eq!(matrix.columns(), 3);
+        assert_eq!(matrix.row(1), Some(&[5.0, 0.0, 26.0_f64.sqrt()][..]));
+        assert_eq!(matrix.get(0, 2), Some(1.0));
+        assert_eq!(matrix.get(2, 0), None);
+        #[cfg(feature = "rayon")]
+        assert_eq!(par_distance_matrix(&[&a, &b], &[&a, &b, &c]), matrix);
+        assert_eq!(
+            condensed_distance_matrix(&[&a, &b, &c]),
+            vec![5.0, 1.0, 26.0_f64.sqrt()]
+        );
+    }
+
+    #[test]
+    fn dipole() {
+        let atom = |x: f64, element: &str| {
+            Atom::new(false, 0, element, x, 0.0, 0.0, 1.0, 0.0, element, 0).unwrap()
+        };
+        // A single ion has no dipole moment around its own center of mass
+        let sodium = atom(5.0, "NA");
+        assert_eq!(center_of_mass(&[&sodium]), Some((5.0, 0.0, 0.0)));
+        assert_eq!(dipole_moment(&[&sodium], |_| 1.0).magnitude, 0.0);
+
+        // Two equal masses with opposite charges 1 Aͦ apart
+        let (a, b) = (atom(0.0, "C"), atom(1.0, "C"));
+        let dipole = dipole_moment(&[&a, &b], |atom| atom.x() - 0.5);
+        assert!((dipole.vector.0 - 0.5).abs() < 1e-9);
+        assert!((dipole.debye() - 0.5 * 4.803_204).abs() < 1e-9);
+        assert_eq!(center_of_mass(&[]), None);
+    }
+
+    #[test]
+    fn coordination() {
+        let atom = |x: f64, y: f64, z: f64, element: &str| {
+            Atom::new(true, 0, element, x, y, z, 1.0, 0.0, element, 0).unwrap()
+        };
+        let zinc = atom(0.0, 0.0, 0.0, "ZN");
+        let octahedron = [
+            atom(2.1, 0.0, 0.0, "O"),
+            atom(-2.1, 0.0, 0.0, "O"),
+            atom(0.0, 2.1, 0.0, "N"),
+            atom(0.0, -2.1, 0.0, "N"),
+            atom(0.0, 0.0, 2.1, "S"),
+            atom(0.0, 0.0, -2.1, "O"),
+            atom(0.0, 0.0, 1.0, "H"),
+            atom(1.5, 1.5, 0.0, "C"),
+            atom(4.0, 0.0, 0.0, "O"),
+        ];
+        let atoms: Vec<&Atom> = octahedron.iter().chain([&zinc]).collect();
+        let sphere = coordination_sphere(&zinc, &atoms, 2.5);
+        assert_eq!(sphere.coordination_number(), 6);
+        assert_eq!(sphere.geometry, CoordinationGeometry::Octahedral);
+        assert!(sphere.angle_deviation.unwrap() < 1e-6);
+        assert!(sphere.warnings.is_empty());
+
+        let c = 2.0 / 3.0_f64.sqrt();
+        let tetrahedron = [
+            atom(c, c, c, "S"),
+            atom(c, -c, -c, "S"),
+            atom(-c, c, -c, "S"),
+            atom(-c, -c, c, "S"),
+        ];
+        let atoms: Vec<&Atom> = tetrahedron.iter().collect();
+        let sphere = coordination_sphere(&zinc, &atoms, 2.5);
+        assert_eq!(sphere.geometry, CoordinationGeometry::Tetrahedral);
+        assert!(sphere.angle_deviation.unwrap() < 0.01);
+
+        let sphere = coordination_sphere(&zinc, &atoms[..1], 2.5);
+        assert_eq!(sphere.geometry, CoordinationGeometry::Irregular);
+        assert_eq!(sphere.angle_deviation, None);
+        assert_eq!(
+            sphere.warnings[0].short_description(),
+            "Unusual coordination number"
+        );
+        let oxygen = atom(0.0, 0.0, 0.0, "O");
+        let sphere = coordination_sphere(&oxygen, &atoms, 2.5);
+        assert_eq!(sphere.warnings[0].short_description(), "Not a metal");
+    }
+}
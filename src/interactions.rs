@@ -0,0 +1,502 @@
+use crate::{reference_tables, Atom, Element, Residue};
+
+/// An aromatic ring of a residue, see [`aromatic_rings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ring<'a> {
+    /// The residue the ring is part of
+    pub residue: &'a Residue,
+    /// The atoms making up the ring, in order around the ring
+    pub atoms: Vec<&'a Atom>,
+    /// The centre of the ring in Aͦ
+    pub centroid: (f64, f64, f64),
+    /// The normal of the plane of the ring (unit vector)
+    pub normal: (f64, f64, f64),
+}
+
+impl<'a> Ring<'a> {
+    /// Create a ring from its atoms, calculating the centroid and normal
+    #[allow(clippy::cast_precision_loss)]
+    fn new(residue: &'a Residue, atoms: Vec<&'a Atom>) -> Self {
+        let n = atoms.len() as f64;
+        let centroid = atoms.iter().fold([0.0; 3], |acc, atom| {
+            let (x, y, z) = atom.pos();
+            [acc[0] + x / n, acc[1] + y / n, acc[2] + z / n]
+        });
+        // The sum of the cross products of consecutive atoms around the ring (Newell's method)
+        let relative = |atom: &Atom| {
+            let (x, y, z) = atom.pos();
+            [x - centroid[0], y - centroid[1], z - centroid[2]]
+        };
+        let mut normal = [0.0; 3];
+        for (index, atom) in atoms.iter().enumerate() {
+            let cross = cross(relative(atom), relative(atoms[(index + 1) % atoms.len()]));
+            for axis in 0..3 {
+                normal[axis] += cross[axis];
+            }
+        }
+        let length = dot(normal, normal).sqrt();
+        Ring {
+            residue,
+            atoms,
+            centroid: (centroid[0], centroid[1], centroid[2]),
+            normal: (normal[0] / length, normal[1] / length, normal[2] / length),
+        }
+    }
+
+    /// The vector from the centroid of this ring to the given position
+    fn to(&self, position: (f64, f64, f64)) -> [f64; 3] {
+        [
+            position.0 - self.centroid.0,
+            position.1 - self.centroid.1,
+            position.2 - self.centroid.2,
+        ]
+    }
+
+    /// The distance from the given position to the centroid of this ring, and the offset, the
+    /// distance between the centroid and the projection of the position on the ring plane
+    fn distance_and_offset(&self, position: (f64, f64, f64)) -> (f64, f64) {
+        let vector = self.to(position);
+        let distance = dot(vector, vector).sqrt();
+        let height = dot(vector, normal(self)).abs();
+        (
+            distance,
+            (distance * distance - height * height).max(0.0).sqrt(),
+        )
+    }
+}
+
+/// The normal of a ring as an array
+const fn normal(ring: &Ring<'_>) -> [f64; 3] {
+    [ring.normal.0, ring.normal.1, ring.normal.2]
+}
+
+/// The cross product of two vectors
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// The dot product of two vectors
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Get the aromatic rings of the given residue, for the aromatic amino acids (PHE, TYR, TRP,
+/// HIS) and the nucleotide bases, using the first conformer. Rings with missing atoms are
+/// skipped.
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let rings: usize = pdb.residues().map(|r| interactions::aromatic_rings(r).len()).sum();
+/// assert_eq!(rings, 4); // Two PHE, one TYR, and one HIS
+/// ```
+pub fn aromatic_rings(residue: &Residue) -> Vec<Ring<'_>> {
+    let conformer = match residue.conformer(0) {
+        Some(conformer) => conformer,
+        None => return Vec::new(),
+    };
+    reference_tables::aromatic_rings(conformer.name())
+        .iter()
+        .filter_map(|names| {
+            let atoms = names
+                .iter()
+                .map(|name| conformer.atoms().find(|atom| atom.name() == *name))
+                .collect::<Option<Vec<&Atom>>>()?;
+            Some(Ring::new(residue, atoms))
+        })
+        .collect()
+}
+
+/// The maximal distance between ring centroids for pi-stacking in Aͦ
+pub const PI_STACKING_MAX_DISTANCE: f64 = 5.5;
+
+/// The maximal distance between a cation and a ring centroid for cation-pi interactions in Aͦ
+pub const CATION_PI_MAX_DISTANCE: f64 = 6.0;
+
+/// The maximal offset between ring centroids, or between the cation and the ring centroid,
+/// projected on the ring plane in Aͦ
+pub const PI_MAX_OFFSET: f64 = 2.0;
+
+/// The maximal deviation in degrees of the angle between the ring planes from the ideal angle
+/// (0 or 90 degrees) for pi-stacking
+pub const PI_STACKING_ANGLE_TOLERANCE: f64 = 30.0;
+
+/// The orientation of two stacked rings, see [`PiStacking`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackingType {
+    /// Face to face, with the ring planes (nearly) parallel
+    Parallel,
+    /// Edge to face, with the ring planes (nearly) perpendicular
+    TShaped,
+}
+
+/// A pi-stacking interaction between two aromatic rings, see [`pi_stacking`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiStacking<'a> {
+    /// The ring from the first set of residues
+    pub ring_a: Ring<'a>,
+    /// The ring from the second set of residues
+    pub ring_b: Ring<'a>,
+    /// The orientation of the rings
+    pub kind: StackingType,
+    /// The distance between the ring centroids in Aͦ
+    pub distance: f64,
+    /// The angle between the ring planes in degrees, from 0 to 90
+    pub angle: f64,
+    /// The smallest offset of one centroid from the other, projected on the ring plane, in Aͦ
+    pub offset: f64,
+}
+
+/// A cation-pi interaction between a positively charged group and an aromatic ring, see
+/// [`cation_pi`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CationPi<'a> {
+    /// The residue with the positive charge
+    pub cation_residue: &'a Residue,
+    /// The charged atom, NZ for lysine and CZ (the centre of the guanidinium group) for arginine
+    pub cation: &'a Atom,
+    /// The aromatic ring
+    pub ring: Ring<'a>,
+    /// The distance between the cation and the ring centroid in Aͦ
+    pub distance: f64,
+    /// The offset of the cation from the centroid, projected on the ring plane, in Aͦ
+    pub offset: f64,
+}
+
+/// Detect pi-stacking between the aromatic rings (see [`aromatic_rings`]) of the two sets of
+/// residues. Rings stack when their centroids are within 5.5 Aͦ, the centroid of one ring is
+/// within 2.0 Aͦ of the other ring's centroid when projected on its plane, and the planes are
+/// parallel or perpendicular within 30 degrees. The same residues can be given for both sets
+/// to find stacking within a structure, every pair of rings is then only reported once. Rings
+/// of the same residue are never paired.
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// for stacking in interactions::pi_stacking(&residues, &residues) {
+///     println!("{:?} {:?} at {:.1} Å", stacking.ring_a.residue.id(), stacking.ring_b.residue.id(), stacking.distance);
+/// }
+/// ```
+pub fn pi_stacking<'a>(a: &[&'a Residue], b: &[&'a Residue]) -> Vec<PiStacking<'a>> {
+    let rings_b: Vec<Ring<'a>> = b.iter().flat_map(|r| aromatic_rings(r)).collect();
+    let mut found: Vec<PiStacking<'a>> = Vec::new();
+    for ring_a in a.iter().flat_map(|r| aromatic_rings(r)) {
+        for ring_b in &rings_b {
+            if std::ptr::eq(ring_a.residue, ring_b.residue)
+                || found.iter().any(|s| {
+                    std::ptr::eq(s.ring_a.atoms[0], ring_b.atoms[0])
+                        && std::ptr::eq(s.ring_b.atoms[0], ring_a.atoms[0])
+                })
+            {
+                continue;
+            }
+            let (distance, offset_a) = ring_a.distance_and_offset(ring_b.centroid);
+            if distance > PI_STACKING_MAX_DISTANCE {
+                continue;
+            }
+            let (_, offset_b) = ring_b.distance_and_offset(ring_a.centroid);
+            let offset = offset_a.min(offset_b);
+            let angle = dot(normal(&ring_a), normal(ring_b))
+                .abs()
+                .min(1.0)
+                .acos()
+                .to_degrees();
+            let kind = if angle <= PI_STACKING_ANGLE_TOLERANCE {
+                StackingType::Parallel
+            } else if angle >= 90.0 - PI_STACKING_ANGLE_TOLERANCE {
+                StackingType::TShaped
+            } else {
+                continue;
+            };
+            if offset <= PI_MAX_OFFSET {
+                found.push(PiStacking {
+                    ring_a: ring_a.clone(),
+                    ring_b: ring_b.clone(),
+                    kind,
+                    distance,
+                    angle,
+                    offset,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Get the positively charged atom of the given residue, if it is a lysine or arginine
+fn cation(residue: &Residue) -> Option<&Atom> {
+    let conformer = residue.conformer(0)?;
+    let name = match conformer.name() {
+        "LYS" => "NZ",
+        "ARG" => "CZ",
+        _ => return None,
+    };
+    conformer.atoms().find(|atom| atom.name() == name)
+}
+
+/// Detect cation-pi interactions between the positively charged groups (lysine and arginine)
+/// of one set of residues and the aromatic rings (see [`aromatic_rings`]) of the other set, in
+/// both directions. The cation has to be within 6.0 Aͦ of the ring centroid, and within 2.0 Aͦ of
+/// the centroid when projected on the ring plane, so it lies above the ring. The same residues
+/// can be given for both sets to find interactions within a structure, every interaction is
+/// then only reported once.
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// for contact in interactions::cation_pi(&residues, &residues) {
+///     assert!(contact.distance <= interactions::CATION_PI_MAX_DISTANCE);
+/// }
+/// ```
+pub fn cation_pi<'a>(a: &[&'a Residue], b: &[&'a Residue]) -> Vec<CationPi<'a>> {
+    let mut found: Vec<CationPi<'a>> = Vec::new();
+    for (cations, rings) in [(a, b), (b, a)] {
+        let rings: Vec<Ring<'a>> = rings.iter().flat_map(|r| aromatic_rings(r)).collect();
+        for residue in cations {
+            let atom = match cation(residue) {
+                Some(atom) => atom,
+                None => continue,
+            };
+            for ring in &rings {
+                if std::ptr::eq(*residue, ring.residue)
+                    || found.iter().any(|c| {
+                        std::ptr::eq(c.cation, atom) && std::ptr::eq(c.ring.atoms[0], ring.atoms[0])
+                    })
+                {
+                    continue;
+                }
+                let (distance, offset) = ring.distance_and_offset(atom.pos());
+                if distance <= CATION_PI_MAX_DISTANCE && offset <= PI_MAX_OFFSET {
+                    found.push(CationPi {
+                        cation_residue: residue,
+                        cation: atom,
+                        ring: ring.clone(),
+                        distance,
+                        offset,
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+/// The default maximal distance between carbons for hydrophobic contacts in Aͦ
+pub const HYDROPHOBIC_MAX_DISTANCE: f64 = 4.0;
+
+/// The maximal distance between two covalently bonded atoms in Aͦ, used to find the polar atoms
+/// bonded to carbons
+const COVALENT_MAX_DISTANCE: f64 = 1.7;
+
+/// A hydrophobic contact between two residues, see [`hydrophobic_contacts`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HydrophobicContact<'a> {
+    /// The residue from the first set of residues
+    pub residue_a: &'a Residue,
+    /// The apolar carbon of the first residue
+    pub atom_a: &'a Atom,
+    /// The residue from the second set of residues
+    pub residue_b: &'a Residue,
+    /// The apolar carbon of the second residue
+    pub atom_b: &'a Atom,
+    /// The distance between the carbons in Aͦ
+    pub distance: f64,
+}
+
+/// Get the apolar carbons of the given residue: the carbons of the first conformer which are
+/// not bonded to a nitrogen or oxygen, judged by distance
+fn apolar_carbons(residue: &Residue) -> Vec<&Atom> {
+    let conformer = match residue.conformer(0) {
+        Some(conformer) => conformer,
+        None => return Vec::new(),
+    };
+    let polar = |atom: &Atom| matches!(atom.element(), Some(Element::N | Element::O));
+    conformer
+        .atoms()
+        .filter(|atom| {
+            atom.element() == Some(&Element::C)
+                && !conformer
+                    .atoms()
+                    .any(|other| polar(other) && atom.distance(other) <= COVALENT_MAX_DISTANCE)
+        })
+        .collect()
+}
+
+/// Detect hydrophobic contacts between the two sets of residues, residues with apolar carbons
+/// (carbons not bonded to nitrogen or oxygen, like in the side chains of leucine or
+/// phenylalanine, or in ligands) within the cutoff distance (see [`HYDROPHOBIC_MAX_DISTANCE`]
+/// for a common value). Only the closest pair of carbons is reported for every pair of
+/// residues. The same residues can be given for both sets to find contacts within a
+/// structure, every pair of residues is then only reported once. A residue is never in contact
+/// with itself.
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let residues: Vec<&Residue> = pdb.residues().collect();
+/// let contacts = interactions::hydrophobic_contacts(&residues, &residues, interactions::HYDROPHOBIC_MAX_DISTANCE);
+/// assert!(!contacts.is_empty());
+/// ```
+pub fn hydrophobic_contacts<'a>(
+    a: &[&'a Residue],
+    b: &[&'a Residue],
+    cutoff: f64,
+) -> Vec<HydrophobicContact<'a>> {
+    let carbons = |residues: &[&'a Residue]| -> Vec<(&'a Residue, Vec<&'a Atom>)> {
+        residues
+            .iter()
+            .map(|residue| (*residue, apolar_carbons(residue)))
+            .filter(|(_, carbons)| !carbons.is_empty())
+            .collect()
+    };
+    let carbons_b = carbons(b);
+    let mut found: Vec<HydrophobicContact<'a>> = Vec::new();
+    for (residue_a, atoms_a) in carbons(a) {
+        for (residue_b, atoms_b) in &carbons_b {
+            if std::ptr::eq(residue_a, *residue_b)
+                || found.iter().any(|c| {
+                    std::ptr::eq(c.residue_a, *residue_b) && std::ptr::eq(c.residue_b, residue_a)
+                })
+            {
+                continue;
+            }
+            let closest = atoms_a
+                .iter()
+                .flat_map(|atom_a| {
+                    atoms_b
+                        .iter()
+                        .map(move |atom_b| (*atom_a, *atom_b, atom_a.distance(atom_b)))
+                })
+                .min_by(|x, y| x.2.total_cmp(&y.2));
+            if let Some((atom_a, atom_b, distance)) = closest {
+                if distance <= cutoff {
+                    found.push(HydrophobicContact {
+                        residue_a,
+                        atom_a,
+                        residue_b,
+                        atom_b,
+                        distance,
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chain;
+
+    /// A benzene like ring of six carbons with the given centre, in the plane given by two
+    /// perpendicular unit vectors
+    fn ring(chain: &mut Chain, serial: isize, centre: [f64; 3], u: [f64; 3], v: [f64; 3]) {
+        let names = ["CG", "CD1", "CE1", "CZ", "CE2", "CD2"];
+        for (index, name) in names.iter().enumerate() {
+            let phi = std::f64::consts::PI / 3.0 * index as f64;
+            let (s, c) = (1.39 * phi.sin(), 1.39 * phi.cos());
+            let position: Vec<f64> = (0..3).map(|i| centre[i] + c * u[i] + s * v[i]).collect();
+            chain.add_atom(
+                Atom::new(
+                    false,
+                    index,
+                    *name,
+                    position[0],
+                    position[1],
+                    position[2],
+                    1.0,
+                    0.0,
+                    "C",
+                    0,
+                )
+                .unwrap(),
+                (serial, None),
+                ("PHE", None),
+            );
+        }
+    }
+
+    #[test]
+    fn stacking() {
+        let (x, y, z) = ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]);
+        let mut chain = Chain::new("A").unwrap();
+        ring(&mut chain, 1, [0.0, 0.0, 0.0], x, y);
+        ring(&mut chain, 2, [0.5, 0.0, 3.6], x, y);
+        ring(&mut chain, 3, [0.0, 0.0, -5.0], x, z);
+        ring(&mut chain, 4, [20.0, 0.0, 0.0], x, y);
+        chain.add_atom(
+            Atom::new(false, 1, "NZ", 0.5, 0.0, 3.6 + 3.5, 1.0, 0.0, "N", 0).unwrap(),
+            (5, None),
+            ("LYS", None),
+        );
+        let residues: Vec<&Residue> = chain.residues().collect();
+
+        let rings = aromatic_rings(residues[0]);
+        assert_eq!(rings.len(), 1);
+        assert!(rings[0].centroid.0.abs() < 1e-9 && rings[0].centroid.2.abs() < 1e-9);
+        assert!((rings[0].normal.2.abs() - 1.0).abs() < 1e-9);
+
+        let stacking = pi_stacking(&residues, &residues);
+        let found: Vec<(isize, isize, StackingType)> = stacking
+            .iter()
+            .map(|s| {
+                (
+                    s.ring_a.residue.serial_number(),
+                    s.ring_b.residue.serial_number(),
+                    s.kind,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, 2, StackingType::Parallel),
+                (1, 3, StackingType::TShaped)
+            ]
+        );
+        assert!((stacking[0].offset - 0.5).abs() < 1e-9);
+
+        let contacts = cation_pi(&residues, &residues);
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].ring.residue.serial_number(), 2);
+        assert!((contacts[0].distance - 3.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hydrophobic() {
+        let mut chain = Chain::new("A").unwrap();
+        let mut add = |serial: usize, name: &str, x: f64, residue: (isize, &str)| {
+            chain.add_atom(
+                Atom::new(false, serial, name, x, 0.0, 0.0, 1.0, 0.0, &name[..1], 0).unwrap(),
+                (residue.0, None),
+                (residue.1, None),
+            );
+        };
+        add(1, "CD1", 0.0, (1, "LEU"));
+        add(2, "CG", 1.5, (1, "LEU"));
+        add(3, "CB", 5.2, (2, "SER"));
+        add(4, "OG", 6.6, (2, "SER"));
+        add(5, "CB", 5.0, (3, "ALA"));
+        add(6, "CD1", -3.8, (4, "ILE"));
+        let residues: Vec<&Residue> = chain.residues().collect();
+
+        let contacts = hydrophobic_contacts(&residues, &residues, HYDROPHOBIC_MAX_DISTANCE);
+        let found: Vec<(isize, isize, f64)> = contacts
+            .iter()
+            .map(|c| {
+                (
+                    c.residue_a.serial_number(),
+                    c.residue_b.serial_number(),
+                    c.distance,
+                )
+            })
+            .collect();
+        // The serine CB is bonded to OG so it is not apolar
+        assert_eq!(found, vec![(1, 3, 3.5), (1, 4, 3.8)]);
+        assert_eq!(contacts[0].atom_a.name(), "CG");
+        assert!(hydrophobic_contacts(&residues[..1], &residues[1..], 3.6).len() == 1);
+    }
+}
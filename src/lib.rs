@@ -57,6 +57,14 @@
 //! atoms is very fast. See the documentation of this crate for more information on how to make use of all of its
 //! features.
 //!
+//! ## Downloading entries
+//! Enable the `fetch` feature to download entries from the RCSB PDB by their identifier, with `fetch("1CRN", level)`
+//! or `ReadOptions::fetch`. The entries are downloaded gzipped over HTTPS with [ureq](https://crates.io/crates/ureq).
+//!
+//! ## Numeric arrays
+//! Enable the `ndarray` feature for [ndarray](https://crates.io/crates/ndarray) support. This enables you to
+//! export the coordinates, B factors, and occupancies of a model as arrays, see `Model::to_ndarray`.
+//!
 #![cfg_attr(
     feature = "rstar",
     doc = r##"
@@ -120,14 +128,29 @@ println!("There are {} backbone atoms within 3.5Aͦ of the atom at index 42", to
 #![allow(clippy::upper_case_acronyms)] // Allow PDB (and derived) names to be used
 #![cfg_attr(feature = "unstable-doc-cfg", feature(doc_cfg))]
 
+/// Pairwise alignment of residue sequences
+pub mod alignment;
+/// Quality metrics of structures, for rendering quality control reports
+pub mod analysis;
+/// Construction of structures from scratch, like peptides built from their sequence
+pub mod build;
+/// Small molecule chemistry for ligands, like bond perception, SMILES export, and matching against
+/// chemical component definitions
+pub mod chemistry;
 /// To save and display errors
 mod error;
+/// Geometric measurements on atoms, like distances, angles, and dihedrals
+pub mod geometry;
+/// Detection of non-covalent interactions between residues, like aromatic stacking
+pub mod interactions;
 /// To open PDB files
 mod read;
 /// Reference tables for constants
 mod reference_tables;
 /// To save PDB files
 mod save;
+/// Physicochemical properties of protein sequences, like the isoelectric point and hydropathy
+pub mod sequence;
 /// To determine the level of scrutiny that a step should display
 mod strictness_level;
 mod structs;
@@ -145,7 +168,13 @@ pub use save::*;
 pub use strictness_level::StrictnessLevel;
 pub use structs::*;
 pub use transformation::*;
-pub use validate::{validate, validate_pdb};
+#[cfg(feature = "rstar")]
+pub use validate::clash_score;
+pub use validate::{
+    ramachandran, rotamers, validate, validate_atom_names, validate_b_factors,
+    validate_chain_breaks, validate_pdb, ClashScore, RamachandranClass, RamachandranReport,
+    RotamerReport,
+};
 
 /// Helper function to check extensions in filenames
 fn check_extension(filename: impl AsRef<str>, extension: impl AsRef<str>) -> bool {
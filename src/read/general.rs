@@ -1,12 +1,18 @@
 use crate::error::*;
 use crate::structs::PDB;
 use crate::StrictnessLevel;
+use doc_cfg::doc_cfg;
 
+use super::read_options::detect_format;
 use super::*;
 
 /// Standard return type for reading a file.
 pub type ReadResult = Result<(PDB, Vec<PDBError>), Vec<PDBError>>;
 
+/// Standard return type for reading a file lazily, see [`LazyModels`].
+pub type LazyReadResult<R = Box<dyn std::io::BufRead>> =
+    Result<(LazyModels<R>, Vec<PDBError>), Vec<PDBError>>;
+
 /// Open an atomic data file, either PDB or mmCIF/PDBx.
 ///
 /// This function is equivalent to [`ReadOptions::read()`] with default options.
@@ -26,6 +32,110 @@ pub fn open(filename: impl AsRef<str>) -> ReadResult {
     ReadOptions::default().read(filename)
 }
 
+/// Open a PDB file to read its Models one at a time, with bounded memory use.
+///
+/// This function is equivalent to [`ReadOptions::read_lazy()`] with default options.
+///
+/// # Errors
+/// Returns a `PDBError` if the file could not be opened or a `BreakingError` is found in the
+/// header. Otherwise it returns the iterator over the Models with all errors/warnings found while
+/// parsing the header.
+pub fn open_lazy(filename: impl AsRef<str>) -> LazyReadResult {
+    ReadOptions::default().read_lazy(filename)
+}
+
+/// Validate an atomic data file, either PDB or mmCIF/PDBx, without retaining the structure.
+/// It returns all errors/warnings found, and fails if any of these fail with the given level.
+///
+/// This function is equivalent to [`ReadOptions::validate_file()`] with the given level, see
+/// there for the checks that are done.
+///
+/// # Errors
+/// Returns all errors/warnings found if any of them fails with the given level.
+pub fn validate_file(
+    filename: impl AsRef<str>,
+    level: StrictnessLevel,
+) -> Result<Vec<PDBError>, Vec<PDBError>> {
+    let errors = ReadOptions::default()
+        .set_level(level)
+        .validate_file(filename);
+    if errors.iter().any(|e| e.fails(level)) {
+        Err(errors)
+    } else {
+        Ok(errors)
+    }
+}
+
+/// Open many atomic data files, either PDB or mmCIF/PDBx, in parallel. The results are given
+/// back as soon as they are available, together with the path of the file.
+///
+/// This function is equivalent to [`ReadOptions::read_many()`] with the given level.
+///
+/// # Example
+/// ```rust
+/// use pdbtbx::*;
+/// let files = ["example-pdbs/1ubq.pdb", "example-pdbs/1yyf.cif", "file.pdb"];
+/// let opened = open_many(files, StrictnessLevel::Loose).filter(|(_, r)| r.is_ok()).count();
+/// assert_eq!(opened, 2);
+/// ```
+#[doc_cfg(feature = "rayon")]
+pub fn open_many<P: AsRef<str>>(
+    paths: impl IntoIterator<Item = P>,
+    level: StrictnessLevel,
+) -> impl Iterator<Item = (String, ReadResult)> {
+    ReadOptions::default().set_level(level).read_many(paths)
+}
+
+/// Open an entry from a local copy of the wwPDB archive by its PDB identifier, using the divided
+/// directory layout (eg `pdb/ab/pdb1abc.ent.gz` or `mmCIF/ab/1abc.cif.gz`).
+///
+/// This function is equivalent to [`ReadOptions::read_from_mirror()`] with default options.
+///
+/// # Errors
+/// Returns a `PDBError` if the entry could not be found or a `BreakingError` is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
+pub fn open_from_mirror(root: impl AsRef<str>, identifier: &str) -> ReadResult {
+    ReadOptions::default().read_from_mirror(root, identifier)
+}
+
+/// Download an entry from the RCSB PDB by its PDB identifier, in mmCIF format.
+///
+/// This function is equivalent to [`ReadOptions::fetch()`] with the given level.
+///
+/// # Errors
+/// Returns a `PDBError` if the entry could not be downloaded or a `BreakingError` is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
+#[doc_cfg(feature = "fetch")]
+pub fn fetch(identifier: &str, level: StrictnessLevel) -> ReadResult {
+    ReadOptions::default().set_level(level).fetch(identifier)
+}
+
+/// Parse an atomic data file, either PDB or mmCIF/PDBx, given as a string. The format is detected
+/// from the content, input opening with a `data_` block is read as mmCIF, all other input as PDB.
+///
+/// # Errors
+/// Returns a `PDBError` if a `BreakingError` is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
+///
+/// # Related
+/// See [`parse_bytes`] for byte slices, and [`ReadOptions::read_raw`] to set more options.
+pub fn parse_str(input: &str, level: StrictnessLevel) -> ReadResult {
+    parse_bytes(input.as_bytes(), level)
+}
+
+/// Parse an atomic data file, either PDB or mmCIF/PDBx, given as a byte slice. The format is
+/// detected from the content, input opening with a `data_` block is read as mmCIF, all other input
+/// as PDB.
+///
+/// # Errors
+/// Returns a `PDBError` if a `BreakingError` is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
+///
+/// # Related
+/// See [`parse_str`] for strings, and [`ReadOptions::read_raw`] to set more options.
+pub fn parse_bytes(input: &[u8], level: StrictnessLevel) -> ReadResult {
+    ReadOptions::default()
+        .set_level(level)
+        .set_format(detect_format(input))
+        .read_raw(input)
+}
+
 /// Open a compressed atomic data file, either PDB or mmCIF/PDBx. The correct type will be
 /// determined based on the file extension (.pdb.gz or .cif.gz).
 ///
@@ -61,6 +171,55 @@ mod tests {
         assert!(open("file.pd").is_err());
     }
 
+    #[test]
+    fn parse_from_memory() {
+        let pdb =
+            "ATOM      1  N   MET A   1      27.340  24.430   2.614  1.00  9.67           N\nEND\n";
+        let (pdb, _errors) = parse_str(pdb, StrictnessLevel::Loose).unwrap();
+        assert_eq!(pdb.atom_count(), 1);
+        let cif = std::fs::read("example-pdbs/1ubq.cif").unwrap();
+        let (cif, _errors) = parse_bytes(&cif, StrictnessLevel::Loose).unwrap();
+        assert_eq!(cif.atom_count(), 660);
+    }
+
+    #[test]
+    fn open_mirror() {
+        let root = std::env::temp_dir().join("pdbtbx_mirror_test");
+        let folder = root.join("data/structures/divided/pdb/ub");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::copy("example-pdbs/1ubq.pdb", folder.join("pdb1ubq.ent")).unwrap();
+        let root = root.to_string_lossy();
+        let (pdb, _errors) = ReadOptions::new()
+            .set_level(StrictnessLevel::Loose)
+            .read_from_mirror(&root, "1UBQ")
+            .unwrap();
+        assert_eq!(pdb.atom_count(), 1271);
+        let errors = open_from_mirror(&root, "2abc").unwrap_err();
+        assert_eq!(errors[0].short_description(), "Entry not found in mirror");
+        let errors = open_from_mirror(&root, "../1").unwrap_err();
+        assert_eq!(errors[0].short_description(), "Invalid PDB identifier");
+    }
+
+    #[test]
+    #[cfg(feature = "fetch")]
+    fn fetch_invalid_identifier() {
+        let errors = fetch("1ab", StrictnessLevel::Loose).unwrap_err();
+        assert_eq!(errors[0].short_description(), "Invalid PDB identifier");
+    }
+
+    #[test]
+    fn validate_without_reading() {
+        let errors = validate_file("example-pdbs/1ubq.pdb", StrictnessLevel::Loose).unwrap();
+        let (_, read_errors) = ReadOptions::new()
+            .set_level(StrictnessLevel::Loose)
+            .read("example-pdbs/1ubq.pdb")
+            .unwrap();
+        assert_eq!(errors.len(), read_errors.len());
+        assert!(validate_file("example-pdbs/1ubq.cif", StrictnessLevel::Loose).is_ok());
+        let errors = validate_file("file.pdb", StrictnessLevel::Loose).unwrap_err();
+        assert_eq!(errors[0].short_description(), "Could not open file");
+    }
+
     #[test]
     fn open_not_existing() {
         let pdb = open("file.pdb").expect_err("This file should not exist.");
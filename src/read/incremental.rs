@@ -0,0 +1,187 @@
+use super::general::ReadResult;
+use super::read_options::detect_format;
+use super::{Format, ReadOptions};
+use std::io::{BufReader, Read};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::JoinHandle;
+
+/// A resumable parser which accepts its input in arbitrary chunks, for use with sources where no
+/// [`std::io::Read`] is available, like async network streams or custom I/O layers.
+///
+/// The input is parsed on a background thread while it is fed, so for PDB files every complete
+/// line is lexed and parsed as soon as it arrives and only the partial last line is kept in the
+/// buffer. mmCIF files are lexed as a whole, so their input is collected until [`Parser::finish`]
+/// is called. If the format is left at [`Format::Auto`] it is detected from the first line with
+/// content: input starting with a `data_` block is read as mmCIF, all other input as PDB.
+///
+/// # Example
+/// ```rust
+/// use pdbtbx::*;
+/// let mut parser = Parser::new(ReadOptions::new().set_level(StrictnessLevel::Loose));
+/// let input = std::fs::read("example-pdbs/1ubq.pdb").unwrap();
+/// for chunk in input.chunks(1024) {
+///     parser.feed(chunk);
+/// }
+/// let (pdb, _errors) = parser.finish().unwrap();
+/// assert_eq!(pdb.atom_count(), 1271);
+/// ```
+#[derive(Debug)]
+pub struct Parser {
+    /// The options to use when parsing
+    options: ReadOptions,
+    /// The input received while the format is not yet known
+    buffer: Vec<u8>,
+    /// The number of bytes fed so far
+    length: usize,
+    /// The channel to the running parse, once the format is known
+    running: Option<(Sender<Vec<u8>>, JoinHandle<ReadResult>)>,
+}
+
+impl Parser {
+    /// Create a new parser which will parse its input with the given options.
+    pub fn new(options: &ReadOptions) -> Self {
+        Parser {
+            options: options.clone(),
+            buffer: Vec::new(),
+            length: 0,
+            running: None,
+        }
+    }
+
+    /// Feed the next chunk of input to the parser. Chunks do not have to be aligned with lines.
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.length += chunk.len();
+        if let Some((sender, _)) = &self.running {
+            // If the parse stopped early on a breaking error the rest of the input is not needed
+            let _ = sender.send(chunk.to_vec());
+        } else {
+            self.buffer.extend_from_slice(chunk);
+            if !matches!(self.options.format, Format::Auto) || self.has_content_line() {
+                self.start();
+            }
+        }
+    }
+
+    /// The number of bytes fed to this parser so far.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Test if no input has been fed to this parser yet.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Parse the remaining input fed to this parser and return the resulting [`crate::PDB`].
+    ///
+    /// # Errors
+    /// Returns a `PDBError` if a `BreakingError` is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
+    ///
+    /// # Panics
+    /// It resumes any panic raised while parsing.
+    pub fn finish(mut self) -> ReadResult {
+        if self.running.is_none() {
+            self.start();
+        }
+        let (sender, handle) = self.running.take().expect("The parse is started");
+        drop(sender); // Signals the end of the input
+        handle
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    }
+
+    /// Test if the buffer contains a complete line with content (ignoring comments), which
+    /// determines the format.
+    fn has_content_line(&self) -> bool {
+        let mut lines = self.buffer.split(|c| *c == b'\n');
+        lines.next_back(); // The last line is not yet complete
+        lines.any(|line| {
+            let start = line.iter().take_while(|c| c.is_ascii_whitespace()).count();
+            start < line.len() && line[start] != b'#'
+        })
+    }
+
+    /// Start parsing the input on a background thread, with the format detected from the buffer.
+    fn start(&mut self) {
+        if let Format::Auto = self.options.format {
+            self.options.set_format(detect_format(&self.buffer));
+        }
+        let (sender, receiver) = channel();
+        let _ = sender.send(std::mem::take(&mut self.buffer));
+        let options = self.options.clone();
+        let reader = ChunkReader {
+            receiver,
+            chunk: Vec::new(),
+            position: 0,
+        };
+        let handle = std::thread::spawn(move || options.read_raw(BufReader::new(reader)));
+        self.running = Some((sender, handle));
+    }
+}
+
+/// A reader over the chunks fed to a [`Parser`], which blocks until the next chunk is fed and
+/// ends when the parser is finished.
+struct ChunkReader {
+    /// The chunks fed to the parser
+    receiver: Receiver<Vec<u8>>,
+    /// The current chunk
+    chunk: Vec<u8>,
+    /// The position of the next byte to read in the current chunk
+    position: usize,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.chunk.len() {
+            match self.receiver.recv() {
+                Ok(chunk) => {
+                    self.chunk = chunk;
+                    self.position = 0;
+                }
+                Err(_) => return Ok(0),
+            }
+        }
+        let length = buf.len().min(self.chunk.len() - self.position);
+        buf[..length].copy_from_slice(&self.chunk[self.position..self.position + length]);
+        self.position += length;
+        Ok(length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StrictnessLevel;
+
+    #[test]
+    fn chunked_mmcif() {
+        let input = std::fs::read("example-pdbs/1ubq.cif").unwrap();
+        let mut parser = Parser::new(ReadOptions::new().set_level(StrictnessLevel::Loose));
+        for chunk in input.chunks(7) {
+            parser.feed(chunk);
+        }
+        assert_eq!(parser.len(), input.len());
+        let (pdb, _errors) = parser.finish().unwrap();
+        assert_eq!(pdb.atom_count(), 660);
+    }
+
+    #[test]
+    fn chunked_pdb() {
+        let input = std::fs::read("example-pdbs/1ubq.pdb").unwrap();
+        let mut parser = Parser::new(ReadOptions::new().set_level(StrictnessLevel::Loose));
+        for chunk in input.chunks(7) {
+            parser.feed(chunk);
+        }
+        assert_eq!(parser.len(), input.len());
+        let (pdb, _errors) = parser.finish().unwrap();
+        assert_eq!(pdb.atom_count(), 1271);
+    }
+
+    #[test]
+    fn empty_input() {
+        let parser = Parser::new(&ReadOptions::new());
+        assert!(parser.is_empty());
+        let errors = parser.finish().unwrap_err();
+        assert_eq!(errors[0].short_description(), "No Atoms");
+    }
+}
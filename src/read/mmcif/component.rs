@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use super::lexer::lex_cif;
+use super::lexitem::*;
+use crate::chemistry::{BondOrder, Component, ComponentAtom};
+use crate::error::*;
+use crate::Element;
+
+/// Get the rows of the given category in a data block, as maps from the column names (without
+/// the category) to the values. Categories with a single row are not written as loops in CIF,
+/// so both forms are handled.
+pub(super) fn rows<'a>(block: &'a DataBlock, category: &str) -> Vec<HashMap<&'a str, &'a Value>> {
+    let prefix = format!("{category}.");
+    let mut single = HashMap::new();
+    for item in &block.items {
+        match item {
+            Item::DataItem(DataItem::Loop(multiple))
+                if multiple.header.iter().any(|h| h.starts_with(&prefix)) =>
+            {
+                return multiple
+                    .data
+                    .iter()
+                    .map(|row| {
+                        multiple
+                            .header
+                            .iter()
+                            .map(|h| h.trim_start_matches(&prefix))
+                            .zip(row)
+                            .collect()
+                    })
+                    .collect();
+            }
+            Item::DataItem(DataItem::Single(item)) if item.name.starts_with(&prefix) => {
+                single.insert(item.name.trim_start_matches(&prefix), &item.content);
+            }
+            _ => (),
+        }
+    }
+    if single.is_empty() {
+        Vec::new()
+    } else {
+        vec![single]
+    }
+}
+
+/// Get the textual content of the given column in a row
+pub(super) fn get_text(row: &HashMap<&str, &Value>, column: &str) -> Option<String> {
+    match row.get(column) {
+        Some(Value::Text(text)) => Some(text.clone()),
+        Some(Value::Numeric(n)) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a chemical component definition, as found in the Chemical Component Dictionary (CCD),
+/// from the given mmCIF text. Only the first data block is used.
+pub(crate) fn parse_component(text: &str) -> Result<Component, PDBError> {
+    let block = lex_cif(text)?;
+    let invalid = |message: String| {
+        PDBError::new(
+            ErrorLevel::BreakingError,
+            "Invalid component",
+            message,
+            Context::none(),
+        )
+    };
+    let mut atoms = Vec::new();
+    for row in rows(&block, "chem_comp_atom") {
+        let name = get_text(&row, "atom_id").ok_or_else(|| {
+            invalid("An atom in the component definition does not have a name.".to_string())
+        })?;
+        #[allow(clippy::cast_possible_truncation)]
+        let charge = match row.get("charge") {
+            Some(Value::Numeric(n)) => *n as isize,
+            _ => 0,
+        };
+        atoms.push(ComponentAtom {
+            element: get_text(&row, "type_symbol").and_then(Element::from_symbol),
+            name,
+            charge,
+        });
+    }
+    if atoms.is_empty() {
+        return Err(invalid(format!(
+            "The component definition '{}' does not contain any atoms (chem_comp_atom).",
+            block.name
+        )));
+    }
+    let mut bonds = Vec::new();
+    for row in rows(&block, "chem_comp_bond") {
+        let index = |column: &str| {
+            let name = get_text(&row, column).unwrap_or_default();
+            atoms.iter().position(|a| a.name == name).ok_or_else(|| {
+                invalid(format!(
+                    "The bond to atom '{name}' refers to an atom not defined in the component."
+                ))
+            })
+        };
+        let (a, b) = (index("atom_id_1")?, index("atom_id_2")?);
+        let order = match get_text(&row, "value_order").as_deref() {
+            Some("DOUB" | "doub") => BondOrder::Double,
+            Some("TRIP" | "trip") => BondOrder::Triple,
+            _ => BondOrder::Single,
+        };
+        bonds.push((a.min(b), a.max(b), order));
+    }
+    Ok(Component {
+        id: block.name,
+        atoms,
+        bonds,
+    })
+}
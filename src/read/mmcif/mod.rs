@@ -1,3 +1,5 @@
+/// Parse chemical component definitions
+mod component;
 /// Parse CIF files into intermediate structure
 mod lexer;
 /// Save the CIF intermediate structure
@@ -5,4 +7,5 @@ mod lexitem;
 /// Parse intermediate structure to PDB structure
 mod parser;
 
+pub(crate) use component::parse_component;
 pub use parser::*;
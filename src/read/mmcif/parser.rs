@@ -1,3 +1,4 @@
+use super::component::{get_text as row_text, rows};
 use super::lexitem::*;
 use crate::error::*;
 use crate::structs::*;
@@ -79,13 +80,10 @@ pub fn open_mmcif_raw(
 }
 
 /// Parse the given stream into a [`PDB`] struct.
-pub(crate) fn open_mmcif_raw_with_options<T>(
-    mut input: std::io::BufReader<T>,
+pub(crate) fn open_mmcif_raw_with_options(
+    mut input: impl BufRead,
     options: &ReadOptions,
-) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>>
-where
-    T: std::io::Read,
-{
+) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>> {
     let mut contents = String::new();
     if input.read_to_string(&mut contents).is_ok() {
         match super::lexer::lex_cif(contents.as_str()) {
@@ -123,7 +121,9 @@ fn parse_mmcif_with_options(
     let mut unit_cell = UnitCell::default();
     let mut mtrix_id = None;
 
-    pdb.identifier = Some(input.name.clone());
+    if !options.discard_metadata {
+        pdb.identifier = Some(input.name.clone());
+    }
 
     for item in &input.items {
         let result = match item {
@@ -270,8 +270,17 @@ fn parse_mmcif_with_options(
         pdb.unit_cell = Some(unit_cell);
     }
 
+    if !options.discard_metadata {
+        parse_entities(input, &mut pdb);
+    }
+
     reshuffle_conformers(&mut pdb);
-    errors.extend(validate(&pdb));
+    errors.extend(options.run_hooks(&mut pdb));
+    if !options.skip_validation {
+        errors.extend(validate(&pdb));
+    }
+    options.filter_errors(&mut errors, 0);
+    options.enforce_error_limit(&mut errors);
     if errors.iter().any(|e| e.fails(options.level)) {
         Err(errors)
     } else {
@@ -279,6 +288,37 @@ fn parse_mmcif_with_options(
     }
 }
 
+/// Add the polymer entities defined in the `_entity_poly` category, with their description from
+/// `_entity` and full sequence from `_entity_poly_seq`.
+fn parse_entities(input: &DataBlock, pdb: &mut PDB) {
+    let descriptions = rows(input, "entity");
+    let sequence = rows(input, "entity_poly_seq");
+    for row in rows(input, "entity_poly") {
+        let id = match row_text(&row, "entity_id").and_then(|id| id.parse::<usize>().ok()) {
+            Some(id) => id,
+            None => continue,
+        };
+        let description = descriptions
+            .iter()
+            .find(|e| row_text(e, "id").as_deref() == Some(id.to_string().as_str()))
+            .and_then(|e| row_text(e, "pdbx_description"));
+        let chains = row_text(&row, "pdbx_strand_id")
+            .map(|ids| {
+                ids.split(',')
+                    .map(|c| c.trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let residues = sequence
+            .iter()
+            .filter(|r| row_text(r, "entity_id").as_deref() == Some(id.to_string().as_str()))
+            .filter_map(|r| row_text(r, "mon_id"))
+            .collect();
+        pdb.add_entity(Entity::new(id, description, chains, residues));
+    }
+}
+
 /// Parse the name of this matrix defining line to find out the index it is pointing at and change that value in the given matrix.
 fn parse_matrix(
     name: &str,
@@ -1,10 +1,23 @@
-pub use general::{open, open_gz};
+#[cfg(feature = "fetch")]
+pub use general::fetch;
+#[cfg(feature = "rayon")]
+pub use general::open_many;
+pub use general::{
+    open, open_from_mirror, open_gz, open_lazy, parse_bytes, parse_str, validate_file,
+};
+pub use incremental::Parser;
+pub(crate) use mmcif::parse_component;
 pub use mmcif::{open_mmcif, open_mmcif_bufread, open_mmcif_raw};
-pub use pdb::{open_pdb, open_pdb_raw};
-pub use read_options::{Format, ReadOptions};
+pub use pdb::lexer as pdb_lexer;
+pub use pdb::{open_pdb, open_pdb_raw, parse_lines, ComplianceReport, LazyModels};
+pub use read_options::{
+    AltLocPolicy, Format, PostParseHook, ReadOptions, RecordHandler, TrailingColumns,
+};
 
 /// Give a high level interface for users
 mod general;
+/// Parse input fed in chunks
+mod incremental;
 /// Parse mmCIF/PDBx files
 mod mmcif;
 /// Read options
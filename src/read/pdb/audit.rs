@@ -0,0 +1,438 @@
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use super::lines::lossy_lines;
+use crate::error::*;
+
+/// The result of auditing a PDB file for conformance to the wwPDB v3.30 format, see
+/// [`crate::ReadOptions::audit`]. All issues found are given as `StrictWarning`s, as the
+/// normal reader accepts files with any of these issues.
+#[derive(Debug, Clone, Default)]
+pub struct ComplianceReport {
+    /// The number of lines checked
+    lines: usize,
+    /// The number of occurrences of each record type
+    records: BTreeMap<String, usize>,
+    /// All issues found
+    issues: Vec<PDBError>,
+}
+
+impl ComplianceReport {
+    /// Create a report with only the given issues.
+    pub(crate) fn from_issues(issues: Vec<PDBError>) -> Self {
+        ComplianceReport {
+            issues,
+            ..Self::default()
+        }
+    }
+
+    /// Get mutable access to the issues found.
+    pub(crate) fn issues_mut(&mut self) -> &mut Vec<PDBError> {
+        &mut self.issues
+    }
+
+    /// Test if the file fully conforms to the format, meaning no issues were found.
+    pub fn is_compliant(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Get all issues found, in the order of the lines they are found on. The issues concerning
+    /// missing mandatory records are given last.
+    pub fn issues(&self) -> &[PDBError] {
+        &self.issues
+    }
+
+    /// Get all issues found, taking ownership of them.
+    pub fn into_issues(self) -> Vec<PDBError> {
+        self.issues
+    }
+
+    /// Get the number of lines checked.
+    pub fn line_count(&self) -> usize {
+        self.lines
+    }
+
+    /// Get the number of records of the given type (eg `ATOM` or `REMARK`) found.
+    pub fn record_count(&self, record: &str) -> usize {
+        self.records.get(record.trim()).copied().unwrap_or(0)
+    }
+
+    /// Get the number of records found for every record type found in the file.
+    pub fn record_counts(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.records.iter().map(|(r, c)| (r.as_str(), *c))
+    }
+}
+
+/// The kinds of fields defined in the format, used to check their content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    /// A right justified integer
+    Integer,
+    /// A right justified real with the given number of decimals
+    Real(usize),
+    /// Columns which have to be left blank
+    Blank,
+    /// A right justified string, which can be empty
+    RightString,
+    /// A charge in the form `[0-9][+-]`, which can be empty
+    Charge,
+}
+
+impl FieldKind {
+    /// Test if the given field content conforms to this kind
+    fn conforms(self, field: &str) -> bool {
+        let right_justified = !field.ends_with(' ');
+        let content = field.trim();
+        match self {
+            FieldKind::Integer => right_justified && content.parse::<isize>().is_ok(),
+            FieldKind::Real(decimals) => {
+                right_justified
+                    && content.parse::<f64>().is_ok()
+                    && content
+                        .split_once('.')
+                        .map_or(false, |(_, fraction)| fraction.len() == decimals)
+            }
+            FieldKind::Blank => content.is_empty(),
+            FieldKind::RightString => content.is_empty() || right_justified,
+            FieldKind::Charge => {
+                let chars = field.as_bytes();
+                content.is_empty()
+                    || (chars.len() == 2
+                        && chars[0].is_ascii_digit()
+                        && (chars[1] == b'+' || chars[1] == b'-'))
+            }
+        }
+    }
+
+    /// A description of the expected content, to be used in error messages
+    fn describe(self) -> String {
+        match self {
+            FieldKind::Integer => "a right justified integer".to_string(),
+            FieldKind::Real(decimals) => format!("a right justified real with {decimals} decimals"),
+            FieldKind::Blank => "blank".to_string(),
+            FieldKind::RightString => "right justified".to_string(),
+            FieldKind::Charge => "empty or a charge in the form [0-9][+-]".to_string(),
+        }
+    }
+}
+
+/// A field definition: the first and last column (1 based, inclusive), kind, and name
+type Field = (usize, usize, FieldKind, &'static str);
+
+/// The checked fields of ATOM and HETATM records
+const ATOM_FIELDS: &[Field] = &[
+    (7, 11, FieldKind::Integer, "serial"),
+    (12, 12, FieldKind::Blank, "spacing"),
+    (18, 20, FieldKind::RightString, "resName"),
+    (21, 21, FieldKind::Blank, "spacing"),
+    (23, 26, FieldKind::Integer, "resSeq"),
+    (28, 30, FieldKind::Blank, "spacing"),
+    (31, 38, FieldKind::Real(3), "x"),
+    (39, 46, FieldKind::Real(3), "y"),
+    (47, 54, FieldKind::Real(3), "z"),
+    (55, 60, FieldKind::Real(2), "occupancy"),
+    (61, 66, FieldKind::Real(2), "tempFactor"),
+    (67, 76, FieldKind::Blank, "spacing"),
+    (77, 78, FieldKind::RightString, "element"),
+    (79, 80, FieldKind::Charge, "charge"),
+];
+
+/// The checked fields of ANISOU records
+const ANISOU_FIELDS: &[Field] = &[
+    (7, 11, FieldKind::Integer, "serial"),
+    (12, 12, FieldKind::Blank, "spacing"),
+    (18, 20, FieldKind::RightString, "resName"),
+    (21, 21, FieldKind::Blank, "spacing"),
+    (23, 26, FieldKind::Integer, "resSeq"),
+    (28, 28, FieldKind::Blank, "spacing"),
+    (29, 35, FieldKind::Integer, "u[0][0]"),
+    (36, 42, FieldKind::Integer, "u[1][1]"),
+    (43, 49, FieldKind::Integer, "u[2][2]"),
+    (50, 56, FieldKind::Integer, "u[0][1]"),
+    (57, 63, FieldKind::Integer, "u[0][2]"),
+    (64, 70, FieldKind::Integer, "u[1][2]"),
+    (71, 76, FieldKind::Blank, "spacing"),
+    (77, 78, FieldKind::RightString, "element"),
+    (79, 80, FieldKind::Charge, "charge"),
+];
+
+/// The checked fields of TER records
+const TER_FIELDS: &[Field] = &[
+    (7, 11, FieldKind::Integer, "serial"),
+    (12, 17, FieldKind::Blank, "spacing"),
+    (18, 20, FieldKind::RightString, "resName"),
+    (21, 21, FieldKind::Blank, "spacing"),
+    (23, 26, FieldKind::Integer, "resSeq"),
+    (28, 80, FieldKind::Blank, "spacing"),
+];
+
+/// The checked fields of CRYST1 records
+const CRYST_FIELDS: &[Field] = &[
+    (7, 15, FieldKind::Real(3), "a"),
+    (16, 24, FieldKind::Real(3), "b"),
+    (25, 33, FieldKind::Real(3), "c"),
+    (34, 40, FieldKind::Real(2), "alpha"),
+    (41, 47, FieldKind::Real(2), "beta"),
+    (48, 54, FieldKind::Real(2), "gamma"),
+    (55, 55, FieldKind::Blank, "spacing"),
+    (67, 70, FieldKind::Integer, "z"),
+];
+
+/// The checked fields of MODEL records
+const MODEL_FIELDS: &[Field] = &[
+    (7, 10, FieldKind::Blank, "spacing"),
+    (11, 14, FieldKind::Integer, "serial"),
+    (15, 80, FieldKind::Blank, "spacing"),
+];
+
+/// The checked fields of MASTER records
+const MASTER_FIELDS: &[Field] = &[
+    (7, 10, FieldKind::Blank, "spacing"),
+    (11, 15, FieldKind::Integer, "numRemark"),
+    (16, 20, FieldKind::Integer, "0"),
+    (21, 25, FieldKind::Integer, "numHet"),
+    (26, 30, FieldKind::Integer, "numHelix"),
+    (31, 35, FieldKind::Integer, "numSheet"),
+    (36, 40, FieldKind::Integer, "numTurn"),
+    (41, 45, FieldKind::Integer, "numSite"),
+    (46, 50, FieldKind::Integer, "numXform"),
+    (51, 55, FieldKind::Integer, "numCoord"),
+    (56, 60, FieldKind::Integer, "numTer"),
+    (61, 65, FieldKind::Integer, "numConect"),
+    (66, 70, FieldKind::Integer, "numSeq"),
+];
+
+/// The checked fields of records without any content (END and ENDMDL)
+const EMPTY_FIELDS: &[Field] = &[(7, 80, FieldKind::Blank, "spacing")];
+
+/// All record types in the order they have to appear in, records within the same group can be
+/// interleaved.
+const RECORD_ORDER: &[&[&str]] = &[
+    &["HEADER"],
+    &["OBSLTE"],
+    &["TITLE"],
+    &["SPLIT"],
+    &["CAVEAT"],
+    &["COMPND"],
+    &["SOURCE"],
+    &["KEYWDS"],
+    &["EXPDTA"],
+    &["NUMMDL"],
+    &["MDLTYP"],
+    &["AUTHOR"],
+    &["REVDAT"],
+    &["SPRSDE"],
+    &["JRNL"],
+    &["REMARK"],
+    &["DBREF", "DBREF1", "DBREF2"],
+    &["SEQADV"],
+    &["SEQRES"],
+    &["MODRES"],
+    &["HET"],
+    &["HETNAM"],
+    &["HETSYN"],
+    &["FORMUL"],
+    &["HELIX"],
+    &["SHEET"],
+    &["SSBOND"],
+    &["LINK"],
+    &["CISPEP"],
+    &["SITE"],
+    &["CRYST1"],
+    &["ORIGX1", "ORIGX2", "ORIGX3"],
+    &["SCALE1", "SCALE2", "SCALE3"],
+    &["MTRIX1", "MTRIX2", "MTRIX3"],
+    &["MODEL", "ATOM", "ANISOU", "HETATM", "TER", "ENDMDL"],
+    &["CONECT"],
+    &["MASTER"],
+    &["END"],
+];
+
+/// The records which are mandatory in every entry
+const MANDATORY_RECORDS: &[&str] = &[
+    "HEADER", "TITLE", "COMPND", "SOURCE", "KEYWDS", "EXPDTA", "AUTHOR", "REVDAT", "REMARK",
+    "CRYST1", "ORIGX1", "ORIGX2", "ORIGX3", "SCALE1", "SCALE2", "SCALE3", "MASTER", "END",
+];
+
+/// The maximal length of a line
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Get the position of the given record type in the record order, if it is a known record
+fn record_rank(record: &str) -> Option<usize> {
+    RECORD_ORDER
+        .iter()
+        .position(|group| group.contains(&record))
+}
+
+/// Get the field definitions for the given record type, if its fields are checked
+fn record_fields(record: &str) -> Option<&'static [Field]> {
+    match record {
+        "ATOM" | "HETATM" => Some(ATOM_FIELDS),
+        "ANISOU" => Some(ANISOU_FIELDS),
+        "TER" => Some(TER_FIELDS),
+        "CRYST1" => Some(CRYST_FIELDS),
+        "MODEL" => Some(MODEL_FIELDS),
+        "MASTER" => Some(MASTER_FIELDS),
+        "END" | "ENDMDL" => Some(EMPTY_FIELDS),
+        _ => None,
+    }
+}
+
+/// Audit the input stream for column level conformance to the wwPDB v3.30 format. It checks the
+/// line lengths, the record types, the record order, the presence of all mandatory records, and
+/// the widths and justification of the fields of the coordinate records.
+pub(crate) fn audit_pdb_raw(input: impl BufRead, context: Context) -> ComplianceReport {
+    let mut report = ComplianceReport::default();
+    let mut highest_rank = 0;
+
+    // Invalid bytes are decoded lossily and reported as non ASCII characters
+    for (mut linenumber, read_line) in lossy_lines(input).enumerate() {
+        linenumber += 1; // 1 based indexing in files
+        let line = if let Ok((l, _)) = read_line {
+            l
+        } else {
+            report.issues.push(PDBError::new(
+                ErrorLevel::BreakingError,
+                "Could read line",
+                format!("Could not read line {linenumber} while auditing the input file."),
+                context,
+            ));
+            return report;
+        };
+        report.lines += 1;
+        let issue = |short: &str, long: String, offset: usize, length: usize| {
+            PDBError::new(
+                ErrorLevel::StrictWarning,
+                short,
+                long,
+                Context::line(linenumber, &line, offset, length),
+            )
+        };
+
+        if !line.is_ascii() {
+            report.issues.push(PDBError::new(
+                ErrorLevel::StrictWarning,
+                "Non ASCII characters",
+                "The line contains non ASCII characters, only ASCII characters are allowed.",
+                Context::full_line(linenumber, &line),
+            ));
+            continue;
+        }
+        if let Some(tab) = line.find('\t') {
+            report.issues.push(issue(
+                "Tab character",
+                "The line contains a tab character, fields should be separated by spaces."
+                    .to_string(),
+                tab,
+                1,
+            ));
+        }
+        if line.len() > MAX_LINE_LENGTH {
+            report.issues.push(issue(
+                "Line too long",
+                format!(
+                    "The line is longer than the maximal length of {MAX_LINE_LENGTH} characters."
+                ),
+                MAX_LINE_LENGTH,
+                line.len() - MAX_LINE_LENGTH,
+            ));
+        }
+
+        let record = line.get(..6).unwrap_or(&line).trim_end();
+        *report.records.entry(record.to_string()).or_insert(0) += 1;
+        match record_rank(record) {
+            Some(rank) if rank < highest_rank => report.issues.push(issue(
+                "Record out of order",
+                format!(
+                    "The {record} record should be placed before the {} records.",
+                    RECORD_ORDER[highest_rank][0]
+                ),
+                0,
+                record.len(),
+            )),
+            Some(rank) => highest_rank = rank,
+            None => report.issues.push(issue(
+                "Unknown record",
+                format!("The record type \"{record}\" is not defined in the format."),
+                0,
+                6,
+            )),
+        }
+
+        if let Some(fields) = record_fields(record) {
+            let padded = format!("{line:MAX_LINE_LENGTH$}");
+            for (start, end, kind, name) in fields {
+                if !kind.conforms(&padded[start - 1..*end]) {
+                    report.issues.push(issue(
+                        "Field not conforming",
+                        format!(
+                            "The {name} field (columns {start}-{end}) of a {record} record should be {}.",
+                            kind.describe()
+                        ),
+                        start - 1,
+                        end - start + 1,
+                    ));
+                }
+            }
+        }
+    }
+
+    for record in MANDATORY_RECORDS {
+        if !report.records.contains_key(*record) {
+            report.issues.push(PDBError::new(
+                ErrorLevel::StrictWarning,
+                "Missing mandatory record",
+                format!("The mandatory {record} record is missing."),
+                context.clone(),
+            ));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_kinds() {
+        assert!(FieldKind::Integer.conforms("   12"));
+        assert!(!FieldKind::Integer.conforms("12   "));
+        assert!(FieldKind::Real(3).conforms("  27.340"));
+        assert!(!FieldKind::Real(3).conforms("  27.34 "));
+        assert!(!FieldKind::Real(3).conforms("  27.34"));
+        assert!(FieldKind::Charge.conforms("  "));
+        assert!(FieldKind::Charge.conforms("2+"));
+        assert!(!FieldKind::Charge.conforms("+2"));
+        assert!(FieldKind::RightString.conforms(" N"));
+        assert!(!FieldKind::RightString.conforms("N "));
+    }
+
+    #[test]
+    fn audit_lines() {
+        let input = "ATOM      1  N   MET A   1      27.340  24.430   2.614  1.00  9.67           N\n\
+                     HEADER    PROTEIN\n\
+                     ATOM    2    CA  MET A   1      26.266  25.413   2.842  1.00 10.38           C\n\
+                     FOOBAR\n\
+                     END\n";
+        let report = audit_pdb_raw(input.as_bytes(), Context::None);
+        assert_eq!(report.line_count(), 5);
+        assert_eq!(report.record_count("ATOM"), 2);
+        let titles: Vec<_> = report
+            .issues()
+            .iter()
+            .map(PDBError::short_description)
+            .collect();
+        assert_eq!(
+            titles
+                .iter()
+                .filter(|t| **t == "Missing mandatory record")
+                .count(),
+            MANDATORY_RECORDS.len() - 2
+        );
+        assert_eq!(titles[0], "Record out of order");
+        assert!(titles.contains(&"Field not conforming"));
+        assert!(titles.contains(&"Unknown record"));
+        assert!(!report.is_compliant());
+    }
+}
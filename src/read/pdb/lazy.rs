@@ -0,0 +1,166 @@
+use std::io::BufRead;
+
+use crate::error::*;
+use crate::structs::{Model, PDB};
+use crate::ReadOptions;
+
+use super::lines::{lossy_lines, LossyLines};
+use super::parser::open_pdb_lines_from;
+
+/// Check if the line starts the coordinate section, or a new Model within it
+fn starts_coordinates(line: &str) -> bool {
+    line.starts_with("MODEL") || line.starts_with("ATOM") || line.starts_with("HETATM")
+}
+
+/// Check if the line is part of the connectivity and bookkeeping section after the coordinate
+/// section (CONECT, MASTER, and END), which ends the last Model
+fn ends_coordinates(line: &str) -> bool {
+    !line.starts_with("ENDMDL")
+        && ["CONECT", "MASTER", "END"]
+            .iter()
+            .any(|record| line.starts_with(record))
+}
+
+/// The error for a line which could not be read
+fn read_error(linenumber: usize, context: &Context) -> PDBError {
+    PDBError::new(
+        ErrorLevel::BreakingError,
+        "Could not read line",
+        format!("Could not read line {linenumber} while parsing the input file."),
+        context.clone(),
+    )
+}
+
+/// An iterator over the Models of a PDB file, which reads and parses a single Model at a time
+/// to keep the memory use bounded for files with many Models, like NMR ensembles and molecular
+/// dynamics trajectories. The records before the first Model (like the metadata, remarks, and
+/// unit cell) are parsed once into the header, see [`LazyModels::header`]. The records after the
+/// last Model (CONECT, MASTER, and END) are not read, so bonds given in CONECT records are not
+/// available. Created with [`ReadOptions::read_lazy`] or [`ReadOptions::read_lazy_raw`].
+///
+/// Every Model is given with the errors/warnings found while parsing it, or the errors if it
+/// failed to parse with the level of the [`ReadOptions`]. Checks which need the full structure,
+/// like the MASTER checksums and the comparison of the SEQRES records with the Chains, are not
+/// done.
+pub struct LazyModels<R> {
+    /// The options to parse the Models with
+    options: ReadOptions,
+    /// The context of the full input, to place error messages
+    context: Context,
+    /// The remaining lines of the input
+    lines: LossyLines<R>,
+    /// The line number of the last line read (not counting the read ahead line)
+    linenumber: usize,
+    /// The first line of the next Model, read ahead
+    next_line: Option<(String, bool)>,
+    /// The header of the file, containing everything but the Models
+    header: PDB,
+}
+
+impl<R: BufRead> LazyModels<R> {
+    /// Read and parse the header from the given input, up to the first Model.
+    pub(crate) fn new(
+        input: R,
+        context: Context,
+        options: &ReadOptions,
+    ) -> Result<(Self, Vec<PDBError>), Vec<PDBError>> {
+        let mut lines = lossy_lines(input);
+        let mut header = Vec::new();
+        let mut next_line = None;
+        for line in lines.by_ref() {
+            match line {
+                Ok(line) if starts_coordinates(&line.0) => {
+                    next_line = Some(line);
+                    break;
+                }
+                Ok(line) => header.push(line),
+                Err(_) => return Err(vec![read_error(header.len() + 1, &context)]),
+            }
+        }
+        let linenumber = header.len();
+        // The header does not contain any Atoms, so it cannot be validated on its own
+        let mut header_options = options.clone();
+        header_options.skip_validation = true;
+        let (header, errors) = options.process_errors(open_pdb_lines_from(
+            header.into_iter().map(Ok),
+            1,
+            context.clone(),
+            &header_options,
+        ))?;
+        Ok((
+            LazyModels {
+                options: options.clone(),
+                context,
+                lines,
+                linenumber,
+                next_line,
+                header,
+            },
+            errors,
+        ))
+    }
+
+    /// Get the header of the file, all data except the Models. This does not contain any Models.
+    pub const fn header(&self) -> &PDB {
+        &self.header
+    }
+}
+
+impl<R> std::fmt::Debug for LazyModels<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyModels")
+            .field("context", &self.context)
+            .field("linenumber", &self.linenumber)
+            .field("header", &self.header)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: BufRead> Iterator for LazyModels<R> {
+    type Item = Result<(Model, Vec<PDBError>), Vec<PDBError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.next_line.take()?;
+        let serial_number = first
+            .0
+            .strip_prefix("MODEL")
+            .and_then(|number| number.trim().parse().ok())
+            .unwrap_or(0);
+        self.linenumber += 1;
+        let first_linenumber = self.linenumber;
+        let mut block = vec![first];
+        let mut closed = false; // If the ENDMDL record of this Model is read
+        for line in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => return Some(Err(vec![read_error(self.linenumber + 1, &self.context)])),
+            };
+            if line.0.starts_with("MODEL") || (closed && starts_coordinates(&line.0)) {
+                self.next_line = Some(line);
+                break;
+            }
+            self.linenumber += 1;
+            if ends_coordinates(&line.0) {
+                break;
+            }
+            if !closed {
+                closed = line.0.starts_with("ENDMDL");
+                block.push(line);
+            }
+        }
+
+        let result = self.options.process_errors(open_pdb_lines_from(
+            block.into_iter().map(Ok),
+            first_linenumber,
+            self.context.clone(),
+            &self.options,
+        ));
+        Some(result.map(|(mut pdb, errors)| {
+            let model = pdb.models_mut().next().map_or_else(
+                || Model::new(serial_number),
+                |model| std::mem::replace(model, Model::new(serial_number)),
+            );
+            (model, errors)
+        }))
+    }
+}
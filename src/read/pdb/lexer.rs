@@ -1,11 +1,14 @@
-use super::lexitem::*;
+pub use super::lexitem::{LexItem, LinkAtom};
 use crate::error::*;
 use crate::reference_tables;
-use crate::ReadOptions;
+use crate::Element;
 use crate::StrictnessLevel;
+use crate::{
+    decode_hybrid36, BoundaryResidue, Helix, HetResidue, ReadOptions, SiteResidue, Strand,
+    TrailingColumns,
+};
 
 use std::cmp;
-use std::convert::TryFrom;
 use std::ops::Range;
 use std::str::FromStr;
 
@@ -17,11 +20,30 @@ pub fn lex_line(
 ) -> Result<(LexItem, Vec<PDBError>), PDBError> {
     match line.len() {
         len if len > 6 => match (options.only_atomic_coords, &line[..6]) {
-            (false, "HEADER") => lex_header(linenumber, line),
-            (false, "REMARK") => lex_remark(linenumber, line, options.level),
-            (_, "ATOM  ") => lex_atom(linenumber, line, false),
-            (false, "ANISOU") => Ok(lex_anisou(linenumber, line)),
-            (_, "HETATM") => lex_atom(linenumber, line, true),
+            (false, "HEADER") if !options.discard_metadata => lex_header(linenumber, line),
+            (false, "REMARK") if !options.discard_metadata => {
+                lex_remark(linenumber, line, options.level)
+            }
+            (false, "TITLE ") if !options.discard_metadata => {
+                Ok((LexItem::Title(continued_text(line)), Vec::new()))
+            }
+            (false, "COMPND") if !options.discard_metadata => Ok(lex_compnd(line)),
+            (false, "SOURCE") if !options.discard_metadata => {
+                Ok((LexItem::Source(continued_text(line)), Vec::new()))
+            }
+            (false, "KEYWDS") if !options.discard_metadata => {
+                Ok((LexItem::Keywords(continued_text(line)), Vec::new()))
+            }
+            (false, "EXPDTA") if !options.discard_metadata => {
+                Ok((LexItem::ExperimentalData(continued_text(line)), Vec::new()))
+            }
+            (false, "AUTHOR") if !options.discard_metadata => {
+                Ok((LexItem::Author(continued_text(line)), Vec::new()))
+            }
+            (false, "JRNL  ") if !options.discard_metadata => Ok(lex_jrnl(line)),
+            (_, "ATOM  ") => lex_atom(linenumber, line, false, options),
+            (false, "ANISOU") => Ok(lex_anisou(linenumber, line, options)),
+            (_, "HETATM") => lex_atom(linenumber, line, true, options),
             (false, "CRYST1") => Ok(lex_cryst(linenumber, line)),
             (false, "SCALE1") => Ok(lex_scale(linenumber, line, 0)),
             (false, "SCALE2") => Ok(lex_scale(linenumber, line, 1)),
@@ -41,6 +63,15 @@ pub fn lex_line(
             (false, "SEQADV") => Ok(lex_seqadv(linenumber, line)),
             (false, "MODRES") => Ok(lex_modres(linenumber, line)),
             (false, "SSBOND") => Ok(lex_ssbond(linenumber, line)),
+            (false, "LINK  ") => Ok(lex_link(linenumber, line)),
+            (false, "HET   ") => Ok(lex_het(linenumber, line)),
+            (false, "HETNAM") => Ok(lex_hetnam(linenumber, line)),
+            (false, "HETSYN") => Ok(lex_hetsyn(linenumber, line)),
+            (false, "FORMUL") => Ok(lex_formul(linenumber, line)),
+            (false, "HELIX ") => Ok(lex_helix(linenumber, line)),
+            (false, "SHEET ") => Ok(lex_sheet(linenumber, line)),
+            (false, "CONECT") => Ok(lex_conect(linenumber, line)),
+            (false, "SITE  ") if !options.discard_metadata => Ok(lex_site(linenumber, line)),
             (_, "ENDMDL") => Ok((LexItem::EndModel(), Vec::new())),
             (_, "TER   ") => Ok((LexItem::TER(), Vec::new())),
             (_, "END   ") => Ok((LexItem::End(), Vec::new())),
@@ -58,7 +89,7 @@ pub fn lex_line(
 /// Lex a REMARK
 /// ## Fails
 /// It fails on incorrect numbers for the remark-type-number
-fn lex_remark(
+pub fn lex_remark(
     linenumber: usize,
     line: &str,
     level: StrictnessLevel,
@@ -95,10 +126,35 @@ fn lex_remark(
     ))
 }
 
+/// Get the text of a record of the title section which can be continued over multiple lines,
+/// like TITLE and AUTHOR, which starts after the continuation number
+fn continued_text(line: &str) -> String {
+    line.get(10..).unwrap_or("").trim().to_string()
+}
+
+/// Lex a COMPND, the text is continued over multiple lines so it is only split into tokens
+/// once all lines are read
+pub fn lex_compnd(line: &str) -> (LexItem, Vec<PDBError>) {
+    (LexItem::Compnd(continued_text(line)), Vec::new())
+}
+
+/// Lex a JRNL, the fields of the REF sub-record are only split once the record is added to the
+/// citation
+pub fn lex_jrnl(line: &str) -> (LexItem, Vec<PDBError>) {
+    (
+        LexItem::Journal(
+            line.get(12..16).unwrap_or("").trim().to_string(),
+            line.get(16..18).map_or(false, |c| !c.trim().is_empty()),
+            line.get(19..).unwrap_or("").trim_end().to_string(),
+        ),
+        Vec::new(),
+    )
+}
+
 /// Lex a HEADER
 /// ## Fails
 /// Fails if the header is too short (below 66 lines)
-fn lex_header(linenumber: usize, line: &str) -> Result<(LexItem, Vec<PDBError>), PDBError> {
+pub fn lex_header(linenumber: usize, line: &str) -> Result<(LexItem, Vec<PDBError>), PDBError> {
     if line.len() < 66 {
         Err(PDBError::new(
             ErrorLevel::LooseWarning,
@@ -127,7 +183,7 @@ fn lex_header(linenumber: usize, line: &str) -> Result<(LexItem, Vec<PDBError>),
 /// Lex a MODEL
 /// ## Fails
 /// It fails on incorrect numbers for the serial number
-fn lex_model(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_model(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
     let number = parse(linenumber, line, 6..line.len(), &mut errors);
     (LexItem::Model(number), errors)
@@ -136,18 +192,31 @@ fn lex_model(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 /// Lex an ATOM
 /// ## Fails
 /// It fails on incorrect numbers in the line
-fn lex_atom(
+pub fn lex_atom(
     linenumber: usize,
     line: &str,
     hetero: bool,
+    options: &ReadOptions,
 ) -> Result<(LexItem, Vec<PDBError>), PDBError> {
     let mut errors = Vec::new();
 
     let x = parse(linenumber, line, 30..38, &mut errors);
     let y = parse(linenumber, line, 38..46, &mut errors);
     let z = parse(linenumber, line, 46..54, &mut errors);
-    let occupancy = parse_default(linenumber, line, 54..60, &mut errors, 1.0);
-    let b_factor = parse(linenumber, line, 60..66, &mut errors);
+    let occupancy = parse_missing(
+        (linenumber, line, 54..60),
+        &mut errors,
+        options.default_occupancy,
+        "occupancy",
+    )
+    .unwrap_or_else(|| parse_default(linenumber, line, 54..60, &mut errors, 1.0));
+    let b_factor = parse_missing(
+        (linenumber, line, 60..66),
+        &mut errors,
+        options.default_b_factor,
+        "B factor",
+    )
+    .unwrap_or_else(|| parse(linenumber, line, 60..66, &mut errors));
 
     let (
         (
@@ -161,11 +230,30 @@ fn lex_atom(
             segment_id,
             element,
             charge,
+            trailing_columns,
         ),
         basic_errors,
-    ) = lex_atom_basics(linenumber, line);
+    ) = lex_atom_basics(linenumber, line, options);
     errors.extend(basic_errors);
 
+    let element = if element.is_empty() {
+        let name_field = line.get(12..16).unwrap_or_default();
+        let inferred = infer_element(name_field, hetero);
+        errors.push(PDBError::new(
+            ErrorLevel::GeneralWarning,
+            "Element inferred",
+            if inferred.is_empty() {
+                "The element column (77-78) is empty and the element could not be inferred from the atom name.".to_string()
+            } else {
+                format!("The element column (77-78) is empty, the element is inferred from the atom name to be {inferred}.")
+            },
+            Context::line(linenumber, line, 12, 4),
+        ));
+        inferred.to_string()
+    } else {
+        element
+    };
+
     Ok((
         LexItem::Atom(
             hetero,
@@ -184,15 +272,39 @@ fn lex_atom(
             segment_id,
             element,
             charge,
+            trailing_columns,
         ),
         errors,
     ))
 }
 
+/// Infer the element of an atom from the unparsed atom name field (columns 13-16). Following the
+/// format, the element symbol is right justified in columns 13-14, so names of atoms with a one
+/// letter element start in column 14. Two letter elements are only considered for HETATM
+/// records, and never for four character hydrogen names (like `HG12`). Returns an empty string if
+/// no element could be inferred.
+fn infer_element(name_field: &str, hetero: bool) -> &'static str {
+    let name = name_field.trim();
+    let starts_in_13 = name_field.starts_with(|c: char| c.is_ascii_alphabetic());
+    if hetero && starts_in_13 && !(name.len() == 4 && name.starts_with('H')) {
+        if let Some(element) = name.get(..2).and_then(Element::from_symbol) {
+            return element.symbol();
+        }
+    }
+    name.chars()
+        .find(char::is_ascii_alphabetic)
+        .and_then(|c| Element::from_symbol(c.to_string()))
+        .map_or("", |element| element.symbol())
+}
+
 /// Lex an ANISOU
 /// ## Fails
 /// It fails on incorrect numbers in the line
-fn lex_anisou(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_anisou(
+    linenumber: usize,
+    line: &str,
+    options: &ReadOptions,
+) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
 
     let ai: isize = parse(linenumber, line, 28..35, &mut errors);
@@ -232,9 +344,10 @@ fn lex_anisou(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
             segment_id,
             element,
             charge,
+            _,
         ),
         basic_errors,
-    ) = lex_atom_basics(linenumber, line);
+    ) = lex_atom_basics(linenumber, line, options);
     errors.extend(basic_errors);
 
     (
@@ -260,9 +373,10 @@ fn lex_anisou(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 fn lex_atom_basics(
     linenumber: usize,
     line: &str,
+    options: &ReadOptions,
 ) -> (
     (
-        usize,
+        Option<usize>,
         String,
         Option<String>,
         String,
@@ -272,44 +386,87 @@ fn lex_atom_basics(
         String,
         String,
         isize,
+        Option<String>,
     ),
     Vec<PDBError>,
 ) {
     let mut errors = Vec::new();
     let chars: Vec<char> = line.chars().collect();
 
-    let serial_number = parse(linenumber, line, 6..11, &mut errors);
+    // Overflowing serial numbers (like `*****` or hexadecimal numbers) are reconstructed by the parser
+    let serial_number = match line.get(6..11).map(|s| s.trim().parse()) {
+        Some(Ok(serial)) => Some(serial),
+        _ if options.reconstruct_serials => None,
+        _ => Some(parse_serial(linenumber, line, 6..11, &mut errors)),
+    };
     let atom_name = parse(linenumber, line, 12..16, &mut errors);
     let alternate_location = parse_char(linenumber, line, 16, &mut errors);
     let residue_name = parse(linenumber, line, 17..20, &mut errors);
     let chain_id = String::from(parse_char(linenumber, line, 21, &mut errors));
-    let residue_serial_number = parse(linenumber, line, 22..26, &mut errors);
+    let residue_serial_number = match line.get(22..26).and_then(|s| decode_hybrid36(s, 4)) {
+        Some(serial) => isize::try_from(serial).unwrap_or(isize::MAX),
+        None => parse(linenumber, line, 22..26, &mut errors),
+    };
     let insertion = parse_char(linenumber, line, 26, &mut errors);
-    let segment_id = parse(linenumber, line, 72..76, &mut errors);
-    let element = parse(linenumber, line, 76..78, &mut errors);
+    // The segment id and element are often left out, so these are allowed to be missing
+    let optional_field = |range: Range<usize>| {
+        line.get(range.start..cmp::min(range.end, line.len()))
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    let segment_id = optional_field(72..76);
+    let element = optional_field(76..78);
+
+    // Charge columns not holding a valid charge are seen as the start of the trailing text
+    let charge_columns: String = chars.iter().skip(78).take(2).collect();
+    let continues_charge = chars.len() > 80
+        && !charge_columns.trim().is_empty()
+        && parse_charge(charge_columns.trim()).is_none();
+    let trailing_start = if continues_charge { 78 } else { 80 };
+    let trailing_columns = chars
+        .get(trailing_start..)
+        .map(|text| text.iter().collect::<String>().trim().to_string())
+        .filter(|text| !text.is_empty());
+    if trailing_columns.is_some() && options.trailing_columns == TrailingColumns::Warn {
+        errors.push(PDBError::new(
+            ErrorLevel::LooseWarning,
+            "Trailing columns",
+            "The line contains text after column 80, this is ignored.",
+            Context::line(
+                linenumber,
+                line,
+                trailing_start,
+                chars.len() - trailing_start,
+            ),
+        ));
+    }
 
     let mut charge = 0;
-    #[allow(clippy::unwrap_used)]
-    if chars.len() >= 80 && !(chars[78] == ' ' && chars[79] == ' ') {
-        if !chars[78].is_ascii_digit() {
-            errors.push(PDBError::new(
+    let charge_field = if continues_charge {
+        String::new()
+    } else {
+        charge_columns
+    };
+    if !charge_field.trim().is_empty() {
+        match parse_charge(charge_field.trim()) {
+            Some((value, canonical)) => {
+                charge = value;
+                if !canonical || charge_field.len() != 2 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::GeneralWarning,
+                        "Atom charge not canonical",
+                        format!("The charge is not written in the canonical form, it is defined to be [0-9][+-], so two characters in total. It is interpreted as {charge}."),
+                        Context::line(linenumber, line, 78, charge_field.len()),
+                    ));
+                }
+            }
+            None => errors.push(PDBError::new(
                 ErrorLevel::InvalidatingError,
                 "Atom charge is not correct",
                 "The charge is not numeric, it is defined to be [0-9][+-], so two characters in total.",
-                Context::line(linenumber, line, 78, 1),
-            ));
-        } else if chars[79] != '-' && chars[79] != '+' {
-            errors.push(PDBError::new(
-                ErrorLevel::InvalidatingError,
-                "Atom charge is not correct",
-                "The charge is not properly signed, it is defined to be [0-9][+-], so two characters in total.",
-                Context::line(linenumber, line, 79, 1),
-            ));
-        } else {
-            charge = isize::try_from(chars[78].to_digit(10).unwrap()).unwrap();
-            if chars[79] == '-' {
-                charge *= -1;
-            }
+                Context::line(linenumber, line, 78, charge_field.len()),
+            )),
         }
     }
 
@@ -333,15 +490,40 @@ fn lex_atom_basics(
             segment_id,
             element,
             charge,
+            if options.trailing_columns == TrailingColumns::Capture {
+                trailing_columns
+            } else {
+                None
+            },
         ),
         errors,
     )
 }
 
+/// Parse the charge of an atom, accepting the canonical form `[0-9][+-]` but also the common
+/// variants `[+-][0-9]`, `[0-9]` (positive), and `[+-]` (a charge of one). Gives the charge and
+/// whether it was in the canonical form, or `None` if it could not be parsed.
+fn parse_charge(text: &str) -> Option<(isize, bool)> {
+    let sign = |c: u8| match c {
+        b'+' => Some(1),
+        b'-' => Some(-1),
+        _ => None,
+    };
+    let digit = |c: u8| c.is_ascii_digit().then(|| isize::from(c - b'0'));
+    match *text.as_bytes() {
+        [d, s] => match (digit(d), sign(s)) {
+            (Some(d), Some(s)) => Some((d * s, true)),
+            _ => sign(d).zip(digit(s)).map(|(s, d)| (d * s, false)),
+        },
+        [c] => digit(c).or_else(|| sign(c)).map(|charge| (charge, false)),
+        _ => None,
+    }
+}
+
 /// Lex a CRYST1
 /// ## Fails
 /// It fails on incorrect numbers in the line
-fn lex_cryst(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_cryst(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
     let chars: Vec<char> = line.chars().collect();
 
@@ -372,7 +554,7 @@ fn lex_cryst(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 /// Lex an SCALEn (where `n` is given)
 /// ## Fails
 /// It fails on incorrect numbers in the line
-fn lex_scale(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBError>) {
+pub fn lex_scale(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBError>) {
     let (data, errors) = lex_transformation(linenumber, line);
 
     (LexItem::Scale(row, data), errors)
@@ -381,7 +563,7 @@ fn lex_scale(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBErro
 /// Lex an ORIGXn (where `n` is given)
 /// ## Fails
 /// It fails on incorrect numbers in the line
-fn lex_origx(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBError>) {
+pub fn lex_origx(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBError>) {
     let (data, errors) = lex_transformation(linenumber, line);
 
     (LexItem::OrigX(row, data), errors)
@@ -390,7 +572,7 @@ fn lex_origx(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBErro
 /// Lex an MTRIXn (where `n` is given)
 /// ## Fails
 /// It fails on incorrect numbers in the line
-fn lex_mtrix(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBError>) {
+pub fn lex_mtrix(linenumber: usize, line: &str, row: usize) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
     let chars: Vec<char> = line.chars().collect();
 
@@ -421,7 +603,7 @@ fn lex_transformation(linenumber: usize, line: &str) -> ([f64; 4], Vec<PDBError>
 /// Lex a MASTER
 /// ## Fails
 /// It fails on incorrect numbers in the line
-fn lex_master(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_master(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
 
     let num_remark = parse(linenumber, line, 10..15, &mut errors);
@@ -457,7 +639,7 @@ fn lex_master(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 }
 
 /// Lexes a SEQRES record
-fn lex_seqres(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_seqres(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
     let chars: Vec<char> = line.chars().collect();
 
@@ -482,7 +664,7 @@ fn lex_seqres(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 }
 
 /// Lexes a DBREF record
-fn lex_dbref(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_dbref(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
 
     let id_code = parse(linenumber, line, 7..11, &mut errors);
@@ -519,7 +701,7 @@ fn lex_dbref(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 }
 
 /// Lexes a DBREF1 record
-fn lex_dbref1(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_dbref1(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
 
     let id_code = parse(linenumber, line, 7..11, &mut errors);
@@ -544,7 +726,7 @@ fn lex_dbref1(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 }
 
 /// Lexes a DBREF2 record
-fn lex_dbref2(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_dbref2(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
 
     let id_code = parse(linenumber, line, 7..11, &mut errors);
@@ -566,7 +748,7 @@ fn lex_dbref2(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 }
 
 /// Lexes a SEQADV record
-fn lex_seqadv(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_seqadv(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
     let chars: Vec<char> = line.chars().collect();
 
@@ -607,7 +789,7 @@ fn lex_seqadv(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 }
 
 /// Lexes a MODRES record
-fn lex_modres(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_modres(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
     let chars: Vec<char> = line.chars().collect();
 
@@ -638,7 +820,7 @@ fn lex_modres(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
 }
 
 /// Parse a SSBond line into the corresponding LexItem
-fn lex_ssbond(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+pub fn lex_ssbond(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     let mut errors = Vec::new();
     let chars: Vec<char> = line.chars().collect();
     // The Serial number field is ignored
@@ -678,6 +860,230 @@ fn lex_ssbond(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
     )
 }
 
+/// Parse a LINK line into the corresponding LexItem
+pub fn lex_link(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    let optional = |position: usize| {
+        line.chars()
+            .nth(position)
+            .filter(|c| *c != ' ')
+            .map(String::from)
+    };
+    let mut atom = |offset: usize| -> LinkAtom {
+        (
+            parse(linenumber, line, 12 + offset..16 + offset, &mut errors),
+            optional(16 + offset),
+            parse(linenumber, line, 17 + offset..20 + offset, &mut errors),
+            parse(linenumber, line, 22 + offset..26 + offset, &mut errors),
+            optional(26 + offset),
+            parse_char(linenumber, line, 21 + offset, &mut errors).to_string(),
+        )
+    };
+    let atom_1 = atom(0);
+    let atom_2 = atom(30);
+
+    let mut extra = None;
+
+    // The symmetry operations and length are optional, so could be blank
+    if line
+        .get(59..78)
+        .map_or(false, |extra| !extra.trim().is_empty())
+    {
+        let sym1 = parse(linenumber, line, 59..65, &mut errors);
+        let sym2 = parse(linenumber, line, 66..72, &mut errors);
+        let distance: f64 = parse(linenumber, line, 73..78, &mut errors);
+        extra = Some((sym1, sym2, distance));
+    }
+
+    (LexItem::Link(atom_1, atom_2, extra), errors)
+}
+
+/// Parse the residue at the start or end of a HELIX or SHEET record, with the residue name
+/// starting at the given index, followed by the chain id, serial number, and insertion code at
+/// the given offsets from the name.
+fn boundary_residue(
+    linenumber: usize,
+    line: &str,
+    start: usize,
+    (chain, serial): (usize, usize),
+    errors: &mut Vec<PDBError>,
+) -> BoundaryResidue {
+    let insertion_code = line
+        .chars()
+        .nth(start + serial + 4)
+        .filter(|c| *c != ' ')
+        .map(String::from);
+    BoundaryResidue {
+        name: parse(linenumber, line, start..start + 3, errors),
+        chain: String::from(parse_char(linenumber, line, start + chain, errors)),
+        serial_number: parse(linenumber, line, start + serial..start + serial + 4, errors),
+        insertion_code,
+    }
+}
+
+/// Lexes a HELIX record
+pub fn lex_helix(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    let comment = line.get(40..70).map(str::trim).unwrap_or_default();
+    let helix = Helix {
+        serial_number: parse(linenumber, line, 7..10, &mut errors),
+        id: parse(linenumber, line, 11..14, &mut errors),
+        class: parse(linenumber, line, 38..40, &mut errors),
+        start: boundary_residue(linenumber, line, 15, (4, 6), &mut errors),
+        end: boundary_residue(linenumber, line, 27, (4, 6), &mut errors),
+        comment: (!comment.is_empty()).then(|| comment.to_string()),
+        length: if line.get(71..76).map_or(true, |f| f.trim().is_empty()) {
+            0
+        } else {
+            parse(linenumber, line, 71..76, &mut errors)
+        },
+    };
+    (LexItem::Helix(helix), errors)
+}
+
+/// Lexes a SHEET record, the registration of the strand (columns 41-70) is ignored
+pub fn lex_sheet(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    let strand = Strand {
+        number: parse(linenumber, line, 7..10, &mut errors),
+        start: boundary_residue(linenumber, line, 17, (4, 5), &mut errors),
+        end: boundary_residue(linenumber, line, 28, (4, 5), &mut errors),
+        sense: parse(linenumber, line, 38..40, &mut errors),
+    };
+    (
+        LexItem::Sheet(
+            parse(linenumber, line, 11..14, &mut errors),
+            parse(linenumber, line, 14..16, &mut errors),
+            strand,
+        ),
+        errors,
+    )
+}
+
+/// Get the free text field starting at the given index, trimmed, gives an empty string for
+/// lines which are too short
+fn text_field(line: &str, start: usize) -> &str {
+    line.get(start..cmp::min(line.len(), 70))
+        .map(str::trim)
+        .unwrap_or_default()
+}
+
+/// Lexes a HET record
+pub fn lex_het(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    let insertion_code = parse_char(linenumber, line, 17, &mut errors);
+    let description = text_field(line, 30);
+    let residue = HetResidue {
+        chain: String::from(parse_char(linenumber, line, 12, &mut errors)),
+        serial_number: parse(linenumber, line, 13..17, &mut errors),
+        insertion_code: (insertion_code != ' ').then(|| String::from(insertion_code)),
+        atom_count: parse(linenumber, line, 20..25, &mut errors),
+        description: (!description.is_empty()).then(|| description.to_string()),
+    };
+    (
+        LexItem::Het(parse(linenumber, line, 7..10, &mut errors), residue),
+        errors,
+    )
+}
+
+/// Lexes a HETNAM record, continued names are given as separate items
+pub fn lex_hetnam(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    (
+        LexItem::HetName(
+            parse(linenumber, line, 11..14, &mut errors),
+            text_field(line, 15).to_string(),
+        ),
+        errors,
+    )
+}
+
+/// Lexes a HETSYN record, continued synonyms are given as separate items
+pub fn lex_hetsyn(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    (
+        LexItem::HetSynonyms(
+            parse(linenumber, line, 11..14, &mut errors),
+            text_field(line, 15).to_string(),
+        ),
+        errors,
+    )
+}
+
+/// Lexes a FORMUL record, continued formulas are given as separate items
+pub fn lex_formul(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    let component_number = if line.get(8..10).map_or(true, |f| f.trim().is_empty()) {
+        None
+    } else {
+        Some(parse(linenumber, line, 8..10, &mut errors))
+    };
+    (
+        LexItem::Formula(
+            component_number,
+            parse(linenumber, line, 12..15, &mut errors),
+            line.chars().nth(18) == Some('*'),
+            text_field(line, 19).to_string(),
+        ),
+        errors,
+    )
+}
+
+/// Lexes a CONECT record, giving the atom and up to four atoms bonded to it. Blank fields for the
+/// bonded atoms are skipped.
+pub fn lex_conect(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+    let serial_number = parse_serial(linenumber, line, 6..11, &mut errors);
+    let bonded = [11, 16, 21, 26]
+        .into_iter()
+        .filter(|start| {
+            line.get(*start..start + 5)
+                .map_or(false, |field| !field.trim().is_empty())
+        })
+        .map(|start| parse_serial(linenumber, line, start..start + 5, &mut errors))
+        .collect();
+    (LexItem::Conect(serial_number, bonded), errors)
+}
+
+/// Lexes a SITE record, containing up to four residues per line
+pub fn lex_site(linenumber: usize, line: &str) -> (LexItem, Vec<PDBError>) {
+    let mut errors = Vec::new();
+
+    let seq_num = parse(linenumber, line, 7..10, &mut errors);
+    let site_id = parse(linenumber, line, 11..14, &mut errors);
+    let num_res = parse(linenumber, line, 15..17, &mut errors);
+    let mut residues = Vec::new();
+    let mut index = 18;
+    while index + 10 <= line.len() && !line[index..index + 10].trim().is_empty() {
+        let insertion_code = parse_char(linenumber, line, index + 9, &mut errors);
+        residues.push(SiteResidue {
+            name: parse(linenumber, line, index..index + 3, &mut errors),
+            chain: String::from(parse_char(linenumber, line, index + 4, &mut errors)),
+            serial_number: parse(linenumber, line, index + 5..index + 9, &mut errors),
+            insertion_code: (insertion_code != ' ').then(|| String::from(insertion_code)),
+        });
+        index += 11;
+    }
+    (LexItem::Site(seq_num, site_id, num_res, residues), errors)
+}
+
+/// Parse an atom serial number, which is given in decimal or as a hybrid-36 number for serial
+/// numbers which do not fit in decimal, leave errors in the given mutable vec.
+fn parse_serial(
+    linenumber: usize,
+    line: &str,
+    range: Range<usize>,
+    errors: &mut Vec<PDBError>,
+) -> usize {
+    match line
+        .get(range.clone())
+        .and_then(|s| decode_hybrid36(s, range.len()))
+    {
+        Some(serial) => serial,
+        None => parse(linenumber, line, range, errors),
+    }
+}
+
 /// Parse a field from a line, with T::default() as fall back, leave errors in the given mutable vec.
 fn parse<T: FromStr + Default>(
     linenumber: usize,
@@ -725,6 +1131,30 @@ fn parse_default<T: FromStr>(
     }
 }
 
+/// Give the given default value if the field is missing (the line is too short) or blank, and a
+/// default is given. A warning is added to the given errors to show the value is not from the
+/// file. Gives `None` if the field has to be parsed normally.
+fn parse_missing(
+    (linenumber, line, range): (usize, &str, Range<usize>),
+    errors: &mut Vec<PDBError>,
+    default: Option<f64>,
+    name: &str,
+) -> Option<f64> {
+    let default = default?;
+    let field = line.get(range.start..cmp::min(range.end, line.len()));
+    if field.map_or(true, |f| f.trim().is_empty()) {
+        errors.push(PDBError::new(
+            ErrorLevel::GeneralWarning,
+            "Missing value",
+            format!("The {name} is missing, the default value of {default} is used."),
+            Context::line(linenumber, line, range.start, range.len()),
+        ));
+        Some(default)
+    } else {
+        None
+    }
+}
+
 /// Parse a character, needed because the trim in the generic `parse` could leave us with an empty character leading to errors
 fn parse_char(linenumber: usize, line: &str, position: usize, errors: &mut Vec<PDBError>) -> char {
     let context = Context::line(linenumber, line, position, 1);
@@ -1,8 +1,10 @@
 /// A definition of all lines that a PDB file can contain (and can be parsed by this program)
 /// with all properties saved as primitive data types.
 ///
-/// See wwPDB v3.30 for detailed explanation of the meaning of all fields
-#[derive(Debug, Clone)]
+/// See wwPDB v3.30 for detailed explanation of the meaning of all fields. New record types can
+/// be added in future versions, so matches on this enum need a wildcard arm.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum LexItem {
     /// A HEADER in a PDB file
     /// * classification
@@ -11,9 +13,26 @@ pub enum LexItem {
     Header(String, String, String),
     /// A REMARK saved as the remark-type-number and the remark line itself
     Remark(usize, String),
+    /// A TITLE line, saved as the text of the line (the title continues over lines)
+    Title(String),
+    /// A COMPND line, saved as the text of the line (the specification continues over lines)
+    Compnd(String),
+    /// A SOURCE line, saved as the text of the line (the specification continues over lines)
+    Source(String),
+    /// A KEYWDS line, saved as the text of the line (the list continues over lines)
+    Keywords(String),
+    /// An EXPDTA line, saved as the text of the line (the list continues over lines)
+    ExperimentalData(String),
+    /// An AUTHOR line, saved as the text of the line (the list continues over lines)
+    Author(String),
+    /// A JRNL line
+    /// * The sub-record type, eg `AUTH` or `TITL`
+    /// * If this line is a continuation of the sub-record
+    /// * The text of the line, starting at column 20
+    Journal(String, bool, String),
     /// An Atom with all its information, including the deprecated and rarely used fields.
     /// * hetatom (true) or atom (false)
-    /// * serial number
+    /// * serial number, `None` if it overflowed and has to be reconstructed
     /// * name
     /// * alternate location
     /// * residue name
@@ -28,9 +47,10 @@ pub enum LexItem {
     /// * segment id
     /// * element
     /// * charge
+    /// * trailing columns, if captured
     Atom(
         bool,
-        usize,
+        Option<usize>,
         String,
         Option<String>,
         String,
@@ -45,9 +65,10 @@ pub enum LexItem {
         String,
         String,
         isize,
+        Option<String>,
     ),
     /// An Anisou record with all its information, including the deprecated and rarely used fields.
-    /// * serial number
+    /// * serial number, `None` if it overflowed and has to be reconstructed
     /// * name
     /// * alternate location
     /// * residue name
@@ -59,7 +80,7 @@ pub enum LexItem {
     /// * element
     /// * charge
     Anisou(
-        usize,
+        Option<usize>,
         String,
         Option<String>,
         String,
@@ -188,6 +209,48 @@ pub enum LexItem {
         (String, isize, Option<String>, String),
         Option<(String, String, f64)>,
     ),
+    /// A link between two atoms, a covalent bond or a metal coordination
+    /// * Atom 1, see [`LinkAtom`]
+    /// * Atom 2, see [`LinkAtom`]
+    /// * Symmetry operation atom 1
+    /// * Symmetry operation atom 2
+    /// * Bond length
+    Link(LinkAtom, LinkAtom, Option<(String, String, f64)>),
+    /// A HELIX record, see [`Helix`](crate::Helix)
+    Helix(crate::Helix),
+    /// A SHEET record
+    /// * SheetID - The identifier of the sheet
+    /// * NumStrands - The number of strands in the sheet
+    /// * Strand - The strand given on this row, see [`Strand`](crate::Strand)
+    Sheet(String, usize, crate::Strand),
+    /// A HET record
+    /// * HetID - The identifier of the hetero group
+    /// * Residue - The residue given on this row, see [`HetResidue`](crate::HetResidue)
+    Het(String, crate::HetResidue),
+    /// A HETNAM record
+    /// * HetID - The identifier of the hetero group
+    /// * Text - The (part of the) chemical name given on this row
+    HetName(String, String),
+    /// A HETSYN record
+    /// * HetID - The identifier of the hetero group
+    /// * Text - The (part of the) synonyms given on this row, separated by semicolons
+    HetSynonyms(String, String),
+    /// A FORMUL record
+    /// * CompNum - The component number, if given
+    /// * HetID - The identifier of the hetero group
+    /// * Water - If the row is marked with an asterisk for water
+    /// * Text - The (part of the) chemical formula given on this row
+    Formula(Option<usize>, String, bool, String),
+    /// A CONECT record, the connectivity of an atom
+    /// * Serial number of the atom
+    /// * Serial numbers of the atoms bonded to it (up to four per line)
+    Conect(usize, Vec<usize>),
+    /// A SITE row
+    /// * SeqNum - Serial number of the SITE record for the current site
+    /// * SiteID - The identifier of the site
+    /// * NumRes - The number of residues in the site (repeated every row)
+    /// * Residues - The residues on this row, see [`SiteResidue`](crate::SiteResidue)
+    Site(usize, String, usize, Vec<crate::SiteResidue>),
     /// ENDMODEL, end of the current model
     EndModel(),
     /// TER =, termination of ATOM lines to allow for HETATMs to be defined
@@ -197,3 +260,19 @@ pub enum LexItem {
     /// Empty line, just ignore
     Empty(),
 }
+
+/// An atom in a LINK record
+/// * Atom name
+/// * Alternative location
+/// * Residue name
+/// * Residue serial number
+/// * Insertion code
+/// * Chain id
+pub type LinkAtom = (
+    String,
+    Option<String>,
+    String,
+    isize,
+    Option<String>,
+    String,
+);
@@ -0,0 +1,71 @@
+use std::io::BufRead;
+
+use crate::error::*;
+
+/// An iterator over the lines of a reader, like [`BufRead::lines`], which decodes lines that are
+/// not valid UTF-8 lossily instead of failing. Files from older software regularly contain Latin-1
+/// encoded text, for example in author names in remarks.
+pub(crate) struct LossyLines<R> {
+    /// The reader to take the lines from
+    input: R,
+}
+
+impl<R: BufRead> Iterator for LossyLines<R> {
+    /// The line without the line ending, and whether it had to be decoded lossily
+    type Item = std::io::Result<(String, bool)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buffer = Vec::new();
+        match self.input.read_until(b'\n', &mut buffer) {
+            Ok(0) => None,
+            Ok(_) => {
+                if buffer.last() == Some(&b'\n') {
+                    buffer.pop();
+                    if buffer.last() == Some(&b'\r') {
+                        buffer.pop();
+                    }
+                }
+                Some(Ok(match String::from_utf8(buffer) {
+                    Ok(line) => (line, false),
+                    Err(e) => (String::from_utf8_lossy(e.as_bytes()).into_owned(), true),
+                }))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Iterate over the lines of the given reader, see [`LossyLines`].
+pub(crate) fn lossy_lines<R: BufRead>(input: R) -> LossyLines<R> {
+    LossyLines { input }
+}
+
+/// The warning given for a line which had to be decoded lossily.
+pub(crate) fn encoding_warning(linenumber: usize, line: &str) -> PDBError {
+    PDBError::new(
+        ErrorLevel::GeneralWarning,
+        "Invalid text encoding",
+        "This line is not valid UTF-8, the invalid bytes are replaced by '\u{FFFD}'.",
+        Context::full_line(linenumber, line),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lossy() {
+        let input: &[u8] = b"REMARK   1 AUTH   M.M\xdcLLER\r\nEND\n\nlast";
+        let lines: Vec<_> = lossy_lines(input).map(Result::unwrap).collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("REMARK   1 AUTH   M.M\u{FFFD}LLER".to_string(), true),
+                ("END".to_string(), false),
+                (String::new(), false),
+                ("last".to_string(), false),
+            ]
+        );
+    }
+}
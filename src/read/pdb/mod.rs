@@ -1,7 +1,16 @@
-/// Lexing methods
-mod lexer;
+/// Auditing conformance to the format
+mod audit;
+/// Reading files one Model at a time
+mod lazy;
+/// The lexer for PDB files, which turns single lines into [`LexItem`](lexer::LexItem)s without
+/// building up a full [`crate::PDB`]. This can be used to build custom pipelines, like filtering
+/// records, gathering statistics, or rewriting files. Each function takes the line number (for
+/// error messages) and the line itself, and returns the lexed record with any errors found.
+pub mod lexer;
 /// The items as generated by the lexer
 mod lexitem;
+/// Reading lines with lossy text decoding
+mod lines;
 /// The parser and lexer
 mod parser;
 /// Structs to help in building up the PDB struct
@@ -9,4 +18,7 @@ mod temporary_structs;
 /// Methods to validate the parsed structure
 mod validate;
 
+pub(crate) use audit::audit_pdb_raw;
+pub use audit::ComplianceReport;
+pub use lazy::LazyModels;
 pub use parser::*;
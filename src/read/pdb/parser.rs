@@ -1,8 +1,9 @@
-use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
 use indexmap::IndexMap;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+use std::hash::BuildHasherDefault;
 
 use crate::error::*;
 use crate::structs::*;
@@ -12,9 +13,23 @@ use crate::StrictnessLevel;
 
 use super::lexer::*;
 use super::lexitem::*;
+use super::lines::*;
 use super::temporary_structs::*;
 use super::validate::*;
 
+/// The SEQRES records per chain, as (serial number, number of residues, residue names)
+type SeqresRecords = FxHashMap<String, Vec<(usize, usize, Vec<String>)>>;
+/// The Chains of the Model currently being parsed, with their Residues by serial number and insertion code
+type ModelBuildUp = FxIndexMap<String, FxIndexMap<(isize, Option<String>), Residue>>;
+/// The atoms of a Model by serial number, as their counter and whether they are metals, and the
+/// bonds already known as pairs of counters, used to resolve CONECT records
+type Connectivity = (FxHashMap<usize, (usize, bool)>, FxHashSet<(usize, usize)>);
+/// An `IndexMap` using the faster (but not DoS resistant) hasher used in rustc, as the keys are
+/// short and hashed for every atom
+type FxIndexMap<K, V> = IndexMap<K, V, FxBuildHasher>;
+/// The builder for the rustc hasher, see [`FxIndexMap`]
+type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
 /// Parse the given file into a PDB struct.
 /// Returns a PDBError if a BreakingError is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
 ///
@@ -80,20 +95,80 @@ where
 /// # Related
 /// See [`ReadOptions::read_raw`] for a version of this function with sane defaults.
 /// Note that the file type should be set explicitly with [`ReadOptions::set_format`].
-pub(crate) fn open_pdb_raw_with_options<T>(
-    input: std::io::BufReader<T>,
+pub(crate) fn open_pdb_raw_with_options(
+    input: impl BufRead,
     context: Context,
     options: &ReadOptions,
-) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>>
-where
-    T: std::io::Read,
-{
+) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>> {
+    open_pdb_lines_with_options(lossy_lines(input), context, options)
+}
+
+/// Parse the given lines into a PDB struct. For lines coming from sources other than readers,
+/// like archives, databases, or in memory caches. Any line endings are removed from the lines.
+/// Returns a PDBError if a BreakingError is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
+///
+/// ## Arguments
+/// * `lines` - the lines of the file
+/// * `context` - the context of the full input, to place error messages correctly, for files this is `Context::show(filename)`.
+/// * `level` - the strictness level to operate in. If errors are generated which are breaking in the given level the parsing will fail.
+///
+/// # Related
+/// See [`ReadOptions::read_lines`] to set more options.
+pub fn parse_lines(
+    lines: impl IntoIterator<Item = String>,
+    context: Context,
+    level: StrictnessLevel,
+) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>> {
+    let mut options = ReadOptions::default();
+    options.set_level(level);
+    options.process_errors(open_pdb_lines_with_options(
+        string_lines(lines),
+        context,
+        &options,
+    ))
+}
+
+/// Remove the line endings from the given lines, to get them in the same form as read lines.
+pub(crate) fn string_lines(
+    lines: impl IntoIterator<Item = String>,
+) -> impl Iterator<Item = std::io::Result<(String, bool)>> {
+    lines.into_iter().map(|mut line| {
+        let length = line.trim_end_matches(&['\r', '\n'][..]).len();
+        line.truncate(length);
+        Ok((line, false))
+    })
+}
+
+/// Parse the given lines into a PDB struct, each line is given with a flag indicating whether it
+/// was decoded lossily.
+pub(crate) fn open_pdb_lines_with_options(
+    lines: impl Iterator<Item = std::io::Result<(String, bool)>>,
+    context: Context,
+    options: &ReadOptions,
+) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>> {
+    open_pdb_lines_from(lines, 1, context, options)
+}
+
+/// Parse the given lines into a PDB struct, see [`open_pdb_lines_with_options`], with the given
+/// line number for the first line, for parsing parts of files.
+pub(crate) fn open_pdb_lines_from(
+    lines: impl Iterator<Item = std::io::Result<(String, bool)>>,
+    first_linenumber: usize,
+    context: Context,
+    options: &ReadOptions,
+) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>> {
     let mut errors = Vec::new();
+    let mut filtered = 0; // The number of errors already passed through the error filters
     let mut pdb = PDB::new();
     let mut current_model_number = 0;
-    let mut current_model: IndexMap<String, IndexMap<(isize, Option<String>), Residue>> =
-        IndexMap::new();
-    let mut sequence: HashMap<String, Vec<(usize, usize, Vec<String>)>> = HashMap::new();
+    let mut current_model = ModelBuildUp::default();
+    let mut metadata = Metadata::new();
+    let mut keywords = String::new();
+    let mut experimental_methods = String::new();
+    let mut authors = String::new();
+    let mut citation: Option<Citation> = None;
+    let mut citation_authors = String::new();
+    let mut sequence = SeqresRecords::default();
     let mut seqres_lines = Vec::new();
     let mut seqres_start_linenumber = usize::MAX;
     let mut database_references = Vec::new();
@@ -102,18 +177,31 @@ where
     let mut temp_scale = BuildUpMatrix::empty();
     let mut temp_origx = BuildUpMatrix::empty();
     let mut temp_mtrix: Vec<(usize, BuildUpMatrix, bool)> = Vec::new();
+    let mut sites: Vec<Site> = Vec::new();
+    let mut site_lines = 0;
+    let mut helix_lines = 0;
+    let mut sheets: Vec<Sheet> = Vec::new();
+    let mut sheet_lines = 0;
+    let mut het_groups: Vec<HetGroup> = Vec::new();
+    let mut het_synonyms: Vec<(String, String)> = Vec::new();
+    let mut het_lines = 0;
     let mut last_residue_serial_number = 0;
     let mut residue_serial_addition = 0;
     let mut last_atom_serial_number = 0;
     let mut atom_serial_addition = 0;
+    let mut last_full_atom_serial_number = 0; // Including the addition or reconstruction
+    let mut reconstructing_serials = false;
     let mut chain_iter = ('A'..='Z').cycle();
     // Initialize chain_id value
     let mut chain_id_new = chain_iter.next();
 
-    'all_lines: for (mut linenumber, read_line) in input.lines().enumerate() {
-        linenumber += 1; // 1 based indexing in files
+    'all_lines: for (mut linenumber, read_line) in lines.enumerate() {
+        linenumber += first_linenumber; // 1 based indexing in files
 
-        let line = if let Ok(l) = read_line {
+        let line = if let Ok((l, lossy)) = read_line {
+            if lossy {
+                errors.push(encoding_warning(linenumber, &l));
+            }
             l
         } else {
             return Err(vec![PDBError::new(
@@ -123,7 +211,11 @@ where
                 context,
             )]);
         };
-        let line_result = lex_line(&line, linenumber, options);
+        let line_result = if let Some(handler) = options.record_handler(&line) {
+            Ok((LexItem::Empty(), handler(linenumber, &line, &mut pdb)))
+        } else {
+            lex_line(&line, linenumber, options)
+        };
         let line_context = Context::FullLine {
             linenumber,
             line: line.clone(),
@@ -134,9 +226,35 @@ where
             Ok((result, line_errors)) => {
                 errors.extend(line_errors);
                 match result {
-                    LexItem::Header(_, _, identifier) => pdb.identifier = Some(identifier),
+                    LexItem::Header(classification, date, identifier) => {
+                        let field = |text: String| {
+                            (!text.trim().is_empty()).then(|| text.trim().to_string())
+                        };
+                        metadata.classification = field(classification);
+                        metadata.deposition_date = field(date);
+                        pdb.identifier = Some(identifier);
+                    }
+                    LexItem::Title(text) => {
+                        continue_text(metadata.title.get_or_insert_with(String::new), &text);
+                    }
+                    LexItem::Source(text) => {
+                        continue_text(metadata.source.get_or_insert_with(String::new), &text);
+                    }
+                    LexItem::Keywords(text) => continue_list(&mut keywords, &text),
+                    LexItem::ExperimentalData(text) => {
+                        continue_list(&mut experimental_methods, &text);
+                    }
+                    LexItem::Author(text) => continue_list(&mut authors, &text),
+                    LexItem::Journal(record, continued, text) => add_journal_record(
+                        citation.get_or_insert_with(Citation::default),
+                        &mut citation_authors,
+                        (&record, continued, &text),
+                    ),
                     LexItem::Remark(num, text) => {
-                        let _ = pdb.add_remark(num, text.to_string()); // Better error messages are created downstream
+                        let _ = pdb.add_remark(num, text); // Better error messages are created downstream
+                    }
+                    LexItem::Compnd(text) => {
+                        continue_text(metadata.compound.get_or_insert_with(String::new), &text);
                     }
                     LexItem::Atom(
                         hetero,
@@ -152,30 +270,52 @@ where
                         z,
                         occ,
                         b,
-                        _,
+                        segment_id,
                         element,
                         charge,
+                        trailing_columns,
                     ) => {
                         if options.discard_hydrogens & (element == "H") {
                             continue;
                         }
-                        if serial_number == 0 && last_atom_serial_number == 99_999 {
-                            atom_serial_addition += 100_000
-                        }
+                        let serial_number = match serial_number {
+                            Some(serial) if !reconstructing_serials => {
+                                if serial == 0 && last_atom_serial_number == 99_999 {
+                                    atom_serial_addition += 100_000
+                                }
+                                last_atom_serial_number = serial;
+                                serial + atom_serial_addition
+                            }
+                            _ => {
+                                if !reconstructing_serials {
+                                    reconstructing_serials = true;
+                                    errors.push(PDBError::new(
+                                        ErrorLevel::GeneralWarning,
+                                        "Atom serial numbers reconstructed",
+                                        "The atom serial number does not fit in its columns, from this atom onwards the serial numbers are assigned sequentially.",
+                                        line_context.clone(),
+                                    ));
+                                }
+                                last_full_atom_serial_number + 1
+                            }
+                        };
+                        last_full_atom_serial_number = serial_number;
 
                         if residue_serial_number == 0 && last_residue_serial_number == 9999 {
                             residue_serial_addition += 10000;
                         }
 
-                        if chain_id.trim().is_empty() {
+                        if options.chains_from_segments && !segment_id.is_empty() {
+                            chain_id = segment_id;
+                        } else if chain_id.trim().is_empty() {
                             chain_id = chain_id_new
                                 .expect("Chain ID iterator is exhausted")
                                 .to_string();
                         }
 
-                        let atom = Atom::new(
+                        let mut atom = Atom::new(
                             hetero,
-                            serial_number + atom_serial_addition,
+                            serial_number,
                             name,
                             x,
                             y,
@@ -186,12 +326,19 @@ where
                             charge,
                         )
                         .expect("Invalid characters in atom creation");
+                        atom.set_trailing_columns(trailing_columns);
                         let conformer_id = (residue_name.as_str(), alt_loc.as_deref());
 
                         let current_chain = if let Some(chain) = current_model.get_mut(&chain_id) {
                             chain
                         } else {
-                            current_model.insert(chain_id.clone(), IndexMap::new());
+                            current_model.insert(
+                                chain_id.clone(),
+                                FxIndexMap::with_capacity_and_hasher(
+                                    seqres_length(&sequence, &chain_id),
+                                    FxBuildHasher::default(),
+                                ),
+                            );
                             current_model.get_mut(&chain_id).expect("Element that was just inserted into this IndexMap was not found in this IndexMap.")
                         };
 
@@ -223,9 +370,12 @@ where
                         }
 
                         last_residue_serial_number = residue_serial_number;
-                        last_atom_serial_number = serial_number;
                     }
                     LexItem::Anisou(s, n, _, _r, _c, _rs, _, factors, _, _e, _ch) => {
+                        let s = match s {
+                            Some(s) if !reconstructing_serials => s,
+                            _ => last_full_atom_serial_number,
+                        };
                         let mut found = false;
                         for atom in current_model
                             .values_mut()
@@ -253,12 +403,18 @@ where
                             ));
 
                             if options.only_first_model {
-                                current_model = IndexMap::new();
+                                current_model = ModelBuildUp::default();
                                 break 'all_lines;
                             }
                         }
                         current_model_number = number;
-                        current_model = IndexMap::new();
+                        // Every Model starts its own atom serial numbering
+                        last_full_atom_serial_number = 0;
+                        reconstructing_serials = false;
+                        current_model = ModelBuildUp::with_capacity_and_hasher(
+                            sequence.len(),
+                            FxBuildHasher::default(),
+                        );
                     }
                     LexItem::Scale(n, row) => {
                         temp_scale.set_row(n, row);
@@ -364,16 +520,59 @@ where
                         ))
                         }
                     }
+                    LexItem::Site(_, id, _, residues) => {
+                        site_lines += 1;
+                        match sites.last_mut() {
+                            Some(site) if site.id == id => site.residues.extend(residues),
+                            _ => sites.push(Site::new(id, residues)),
+                        }
+                    }
+                    LexItem::Helix(helix) => {
+                        helix_lines += 1;
+                        pdb.add_helix(helix);
+                    }
+                    LexItem::Sheet(id, _, strand) => {
+                        sheet_lines += 1;
+                        match sheets.iter_mut().find(|sheet| sheet.id == id) {
+                            Some(sheet) => sheet.strands.push(strand),
+                            None => sheets.push(Sheet {
+                                id,
+                                strands: vec![strand],
+                            }),
+                        }
+                    }
+                    LexItem::Het(id, residue) => {
+                        het_lines += 1;
+                        het_group_mut(&mut het_groups, id).residues.push(residue);
+                    }
+                    LexItem::HetName(id, text) => {
+                        let group = het_group_mut(&mut het_groups, id);
+                        continue_text(group.name.get_or_insert_with(String::new), &text);
+                    }
+                    LexItem::HetSynonyms(id, text) => {
+                        match het_synonyms.iter_mut().find(|(group, _)| *group == id) {
+                            Some((_, synonyms)) => continue_text(synonyms, &text),
+                            None => het_synonyms.push((id, text)),
+                        }
+                    }
+                    LexItem::Formula(component_number, id, water, text) => {
+                        let group = het_group_mut(&mut het_groups, id);
+                        group.component_number = group.component_number.or(component_number);
+                        group.water |= water;
+                        continue_text(group.formula.get_or_insert_with(String::new), &text);
+                    }
                     item @ LexItem::Modres(..) => modifications.push((line_context.clone(), item)),
-                    item @ LexItem::SSBond(..) => bonds.push((line_context.clone(), item)),
+                    item @ (LexItem::SSBond(..) | LexItem::Link(..) | LexItem::Conect(..)) => {
+                        bonds.push((line_context.clone(), item));
+                    }
                     LexItem::Master(
                         num_remark,
                         num_empty,
-                        _num_het,
-                        _num_helix,
-                        _num_sheet,
+                        num_het,
+                        num_helix,
+                        num_sheet,
                         _num_turn,
-                        _num_site,
+                        num_site,
                         num_xform,
                         num_coord,
                         _num_ter,
@@ -389,60 +588,102 @@ where
                                         .expect("Invalid characters in Chain definition")
                                 }),
                             ));
-                            current_model = IndexMap::new();
-                        }
-                        // The for now forgotten numbers will have to be added when the appropriate records are added to the parser
-                        if num_remark != pdb.remark_count() {
-                            errors.push(
-                            PDBError::new(
-                                ErrorLevel::StrictWarning,
-                                "MASTER checksum failed",
-                                format!("The number of REMARKS ({}) is different then posed in the MASTER Record ({})", pdb.remark_count(), num_remark),
-                                line_context.clone()
-                            )
-                        );
+                            current_model = ModelBuildUp::default();
                         }
-                        if num_empty != 0 {
-                            errors.push(
-                            PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "MASTER checksum failed",
-                                format!("The empty checksum number is not empty (value: {num_empty}) while it is defined to be empty."),
-                                line_context.clone()
-                            )
-                        );
-                        }
-                        let mut xform = 0;
-                        if temp_origx.is_set() {
-                            xform += 3;
-                        }
-                        if temp_scale.is_set() {
-                            xform += 3;
-                        }
-                        for (_, mtrix, _) in &temp_mtrix {
-                            if mtrix.is_set() {
+                        if !options.skip_validation {
+                            // The for now forgotten numbers will have to be added when the appropriate records are added to the parser
+                            if !options.discard_metadata && num_remark != pdb.remark_count() {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::StrictWarning,
+                                    "MASTER checksum failed",
+                                    format!("The number of REMARKS ({}) is different then posed in the MASTER Record ({})", pdb.remark_count(), num_remark),
+                                    line_context.clone()
+                                )
+                            );
+                            }
+                            if !options.discard_metadata && num_site != site_lines {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::StrictWarning,
+                                    "MASTER checksum failed",
+                                    format!("The number of SITE records ({site_lines}) is different then posed in the MASTER Record ({num_site})"),
+                                    line_context.clone()
+                                )
+                            );
+                            }
+                            if num_het != het_lines {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::StrictWarning,
+                                    "MASTER checksum failed",
+                                    format!("The number of HET records ({het_lines}) is different then posed in the MASTER Record ({num_het})"),
+                                    line_context.clone()
+                                )
+                            );
+                            }
+                            if num_helix != helix_lines {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::StrictWarning,
+                                    "MASTER checksum failed",
+                                    format!("The number of HELIX records ({helix_lines}) is different then posed in the MASTER Record ({num_helix})"),
+                                    line_context.clone()
+                                )
+                            );
+                            }
+                            if num_sheet != sheet_lines {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::StrictWarning,
+                                    "MASTER checksum failed",
+                                    format!("The number of SHEET records ({sheet_lines}) is different then posed in the MASTER Record ({num_sheet})"),
+                                    line_context.clone()
+                                )
+                            );
+                            }
+                            if num_empty != 0 {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::LooseWarning,
+                                    "MASTER checksum failed",
+                                    format!("The empty checksum number is not empty (value: {num_empty}) while it is defined to be empty."),
+                                    line_context.clone()
+                                )
+                            );
+                            }
+                            let mut xform = 0;
+                            if temp_origx.is_set() {
                                 xform += 3;
                             }
-                        }
-                        if num_xform != xform {
-                            errors.push(
-                            PDBError::new(
-                                ErrorLevel::StrictWarning,
-                                "MASTER checksum failed",
-                                format!("The number of coordinate transformation records ({xform}) is different then posed in the MASTER Record ({num_xform})"),
-                                line_context.clone()
-                            )
-                        );
-                        }
-                        if num_coord != pdb.total_atom_count() {
-                            errors.push(
-                            PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "MASTER checksum failed",
-                                format!("The number of Atoms ({}) is different then posed in the MASTER Record ({})", pdb.total_atom_count(), num_coord),
-                                line_context.clone()
-                            )
-                        );
+                            if temp_scale.is_set() {
+                                xform += 3;
+                            }
+                            for (_, mtrix, _) in &temp_mtrix {
+                                if mtrix.is_set() {
+                                    xform += 3;
+                                }
+                            }
+                            if num_xform != xform {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::StrictWarning,
+                                    "MASTER checksum failed",
+                                    format!("The number of coordinate transformation records ({xform}) is different then posed in the MASTER Record ({num_xform})"),
+                                    line_context.clone()
+                                )
+                            );
+                            }
+                            if num_coord != pdb.total_atom_count() {
+                                errors.push(
+                                PDBError::new(
+                                    ErrorLevel::LooseWarning,
+                                    "MASTER checksum failed",
+                                    format!("The number of Atoms ({}) is different then posed in the MASTER Record ({})", pdb.total_atom_count(), num_coord),
+                                    line_context.clone()
+                                )
+                            );
+                            }
                         }
                     }
                     LexItem::TER() => chain_id_new = chain_iter.next(),
@@ -451,6 +692,11 @@ where
             }
             Err(e) => errors.push(e),
         }
+        options.filter_errors(&mut errors, filtered);
+        filtered = errors.len();
+        if options.enforce_error_limit(&mut errors) {
+            return Err(errors);
+        }
     }
     if !current_model.is_empty() {
         pdb.add_model(Model::from_iter(
@@ -510,20 +756,59 @@ where
         }
     }
 
+    describe_sites(&mut sites, pdb.remarks());
+    for sheet in sheets {
+        pdb.add_sheet(sheet);
+    }
+    for site in sites {
+        pdb.add_site(site);
+    }
+    for (id, synonyms) in het_synonyms {
+        het_group_mut(&mut het_groups, id).synonyms = split_synonyms(&synonyms);
+    }
+    for group in het_groups {
+        pdb.add_het_group(group);
+    }
+
     reshuffle_conformers(&mut pdb);
+    if options.normalize_nomenclature {
+        pdb.normalize_nomenclature();
+    }
 
     merge_long_remark_warnings(&mut errors);
-    errors.extend(validate_seqres(
+    add_entities(
         &mut pdb,
-        sequence,
-        seqres_lines,
-        seqres_start_linenumber - 1, // Convert from 1 based to 0 based numbering
-        &context,
-    ));
+        metadata.compound.as_deref().unwrap_or_default(),
+        &sequence,
+    );
+    metadata.keywords = split_list(&keywords, ',');
+    metadata.experimental_methods = split_list(&experimental_methods, ';');
+    metadata.authors = split_list(&authors, ',');
+    metadata.citation = citation.map(|mut citation| {
+        citation.authors = split_list(&citation_authors, ',');
+        citation
+    });
+    metadata.resolution = parse_resolution(pdb.remarks());
+    pdb.set_metadata(metadata);
+    errors.extend(restore_annotations(&mut pdb));
+    if !options.skip_validation {
+        errors.extend(validate_seqres(
+            &mut pdb,
+            sequence,
+            seqres_lines,
+            seqres_start_linenumber - 1, // Convert from 1 based to 0 based numbering
+            &context,
+        ));
+    }
     errors.extend(add_modifications(&mut pdb, modifications));
     errors.extend(add_bonds(&mut pdb, bonds));
-    errors.extend(validate(&pdb));
+    errors.extend(options.run_hooks(&mut pdb));
+    if !options.skip_validation {
+        errors.extend(validate(&pdb));
+    }
 
+    options.filter_errors(&mut errors, filtered);
+    options.enforce_error_limit(&mut errors);
     if errors.iter().any(|e| e.fails(options.level)) {
         Err(errors)
     } else {
@@ -531,6 +816,254 @@ where
     }
 }
 
+/// Validate the input stream as a PDB file without building the [`PDB`] struct. All lines are
+/// lexed and the atom and remark counts are checked against the MASTER record. Invariants
+/// which need the full hierarchy (like corresponding atoms in all models) are not checked.
+pub(crate) fn validate_pdb_raw_with_options(
+    input: impl BufRead,
+    context: Context,
+    options: &ReadOptions,
+) -> Vec<PDBError> {
+    let mut errors = Vec::new();
+    let mut filtered = 0; // The number of errors already passed through the error filters
+    let mut atoms = 0;
+    let mut remarks = 0;
+    let mut master = None;
+
+    for (mut linenumber, read_line) in lossy_lines(input).enumerate() {
+        linenumber += 1; // 1 based indexing in files
+        let line = if let Ok((l, lossy)) = read_line {
+            if lossy {
+                errors.push(encoding_warning(linenumber, &l));
+            }
+            l
+        } else {
+            errors.push(PDBError::new(
+                ErrorLevel::BreakingError,
+                "Could read line",
+                format!("Could not read line {linenumber} while parsing the input file."),
+                context,
+            ));
+            return errors;
+        };
+        match lex_line(&line, linenumber, options) {
+            Ok((result, line_errors)) => {
+                errors.extend(line_errors);
+                match result {
+                    LexItem::Remark(..) => remarks += 1,
+                    LexItem::Atom(.., element, _, _)
+                        if !(options.discard_hydrogens && element == "H") =>
+                    {
+                        atoms += 1;
+                    }
+                    LexItem::Master(num_remark, .., num_coord, _, _, _) => {
+                        master = Some((
+                            num_remark,
+                            num_coord,
+                            Context::FullLine {
+                                linenumber,
+                                line: line.clone(),
+                            },
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+            Err(e) => errors.push(e),
+        }
+        options.filter_errors(&mut errors, filtered);
+        filtered = errors.len();
+        if options.enforce_error_limit(&mut errors) {
+            return errors;
+        }
+    }
+
+    if let Some((num_remark, num_coord, line_context)) = master {
+        if !options.discard_metadata && num_remark != remarks {
+            errors.push(PDBError::new(
+                ErrorLevel::StrictWarning,
+                "MASTER checksum failed",
+                format!("The number of REMARKS ({remarks}) is different then posed in the MASTER Record ({num_remark})"),
+                line_context.clone(),
+            ));
+        }
+        if num_coord != atoms {
+            errors.push(PDBError::new(
+                ErrorLevel::LooseWarning,
+                "MASTER checksum failed",
+                format!("The number of Atoms ({atoms}) is different then posed in the MASTER Record ({num_coord})"),
+                line_context,
+            ));
+        }
+    }
+    if atoms == 0 {
+        errors.push(PDBError::new(
+            ErrorLevel::BreakingError,
+            "No Atoms",
+            "No Atoms in the given PDB struct while validating.",
+            Context::None,
+        ));
+    }
+    options.filter_errors(&mut errors, filtered);
+    options.enforce_error_limit(&mut errors);
+    merge_long_remark_warnings(&mut errors);
+    errors
+}
+
+/// Get the number of Residues given for the Chain with the given id in the SEQRES records, used to
+/// pre-size the Chain while parsing. Returns 0 if the Chain has no SEQRES records.
+fn seqres_length(sequence: &SeqresRecords, chain_id: &str) -> usize {
+    sequence
+        .get(chain_id)
+        .and_then(|records| records.first())
+        .map_or(0, |(_, num_res, _)| *num_res)
+}
+
+/// Add the polymer entities as defined by the MOL_ID specifications in the COMPND records. If
+/// these are not given the chains are grouped by their SEQRES sequences. The full sequence of
+/// each entity is taken from the SEQRES records of its first chain.
+fn add_entities(pdb: &mut PDB, compound: &str, sequence: &SeqresRecords) {
+    let chain_sequence = |chain_id: &str| -> Option<Vec<String>> {
+        let mut rows = sequence.get(chain_id)?.clone();
+        rows.sort_by_key(|row| row.0);
+        Some(rows.into_iter().flat_map(|row| row.2).collect())
+    };
+
+    let mut entities: Vec<Entity> = Vec::new();
+    for token in compound.split(';') {
+        let (key, value) = match token.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => continue,
+        };
+        match (key, entities.last_mut()) {
+            ("MOL_ID", _) => {
+                if let Ok(id) = value.parse() {
+                    entities.push(Entity::new(id, None, Vec::new(), Vec::new()));
+                }
+            }
+            ("MOLECULE", Some(entity)) => entity.description = Some(value.to_string()),
+            ("CHAIN", Some(entity)) => {
+                entity.chains = value
+                    .split(',')
+                    .map(|id| id.trim().to_string())
+                    .filter(|id| !id.is_empty())
+                    .collect();
+            }
+            _ => (),
+        }
+    }
+
+    if entities.is_empty() {
+        // Group the chains with identical SEQRES sequences, in the order of the chains in the file
+        let mut chain_ids: Vec<&String> = sequence.keys().collect();
+        let order: Vec<&str> = pdb.chains().map(Chain::id).collect();
+        chain_ids.sort_by_key(|id| {
+            (
+                order.iter().position(|o| o == id).unwrap_or(usize::MAX),
+                id.as_str(),
+            )
+        });
+        let mut sequences: Vec<Vec<String>> = Vec::new();
+        for chain_id in chain_ids {
+            let seq = chain_sequence(chain_id).unwrap_or_default();
+            if let Some(index) = sequences.iter().position(|s| *s == seq) {
+                entities[index].chains.push(chain_id.clone());
+            } else {
+                entities.push(Entity::new(
+                    entities.len() + 1,
+                    None,
+                    vec![chain_id.clone()],
+                    Vec::new(),
+                ));
+                sequences.push(seq);
+            }
+        }
+    }
+
+    for mut entity in entities {
+        if let Some(seq) = entity.chains.iter().find_map(|id| chain_sequence(id)) {
+            entity.sequence = seq;
+        }
+        pdb.add_entity(entity);
+    }
+}
+
+/// Move the annotations saved in REMARK records (see [`Annotations`]) from the remarks to the
+/// PDB, Chains, Residues, and Atoms they belong to.
+fn restore_annotations(pdb: &mut PDB) -> Vec<PDBError> {
+    let is_annotation = |remark: &(usize, String)| {
+        remark.0 == ANNOTATION_REMARK_TYPE
+            && remark.1.split_whitespace().next() == Some(ANNOTATION_REMARK_TAG)
+    };
+    let (records, invalid) = parse_annotation_remarks(
+        pdb.remarks()
+            .filter(|remark| is_annotation(remark))
+            .map(|remark| remark.1.as_str()),
+    );
+    pdb.delete_remarks_by(is_annotation);
+
+    let mut errors: Vec<PDBError> = invalid
+        .into_iter()
+        .map(|text| {
+            PDBError::new(
+                ErrorLevel::LooseWarning,
+                "Invalid annotation",
+                "This annotation could not be parsed, it is ignored.",
+                Context::show(text),
+            )
+        })
+        .collect();
+    for record in records {
+        if let Some(annotations) = annotation_target(pdb, &record.target) {
+            annotations.insert(record.key, record.value);
+        } else {
+            errors.push(PDBError::new(
+                ErrorLevel::LooseWarning,
+                "Annotation target not found",
+                "The item this annotation belongs to could not be found, it is ignored.",
+                Context::show(format!("{} {}", record.target.join(" "), record.key)),
+            ));
+        }
+    }
+    errors
+}
+
+/// Find the annotations of the item identified by the given target tokens, as written by the
+/// PDB writer: `PDB`, `CHAIN model chain`, `RESIDUE model chain serial insertion_code`, or
+/// `ATOM model serial`, with the Model index starting at 1.
+fn annotation_target<'a>(pdb: &'a mut PDB, target: &[String]) -> Option<&'a mut Annotations> {
+    /// Find the Model with the given index (starting at 1)
+    fn model<'a>(pdb: &'a mut PDB, index: &str) -> Option<&'a mut Model> {
+        pdb.models_mut()
+            .nth(index.parse::<usize>().ok()?.checked_sub(1)?)
+    }
+    match target {
+        [kind] if kind == "PDB" => Some(pdb.annotations_mut()),
+        [kind, index, chain] if kind == "CHAIN" => model(pdb, index)?
+            .chains_mut()
+            .find(|c| c.id() == chain)
+            .map(Chain::annotations_mut),
+        [kind, index, chain, serial, insertion_code] if kind == "RESIDUE" => {
+            let serial = serial.parse::<isize>().ok()?;
+            let insertion_code = (insertion_code != ".").then_some(insertion_code.as_str());
+            model(pdb, index)?
+                .chains_mut()
+                .find(|c| c.id() == chain)?
+                .residues_mut()
+                .find(|r| r.id() == (serial, insertion_code))
+                .map(Residue::annotations_mut)
+        }
+        [kind, index, serial] if kind == "ATOM" => {
+            let serial = serial.parse::<usize>().ok()?;
+            model(pdb, index)?
+                .atoms_mut()
+                .find(|a| a.serial_number() == serial)
+                .map(Atom::annotations_mut)
+        }
+        _ => None,
+    }
+}
+
 /// Merge all warnings about long REMARK definitions into a single warning
 fn merge_long_remark_warnings(errors: &mut Vec<PDBError>) {
     // Weed out all remark too long warnings
@@ -645,6 +1178,8 @@ fn add_modifications(pdb: &mut PDB, modifications: Vec<(Context, LexItem)>) -> V
 #[allow(clippy::unwrap_used)]
 fn add_bonds(pdb: &mut PDB, bonds: Vec<(Context, LexItem)>) -> Vec<PDBError> {
     let mut errors = Vec::new();
+    // The connectivity of the first model, only built when the first CONECT record is found
+    let mut connectivity: Option<Connectivity> = None;
     for (context, bond) in bonds {
         match bond {
             LexItem::SSBond(atom1, atom2, ..) => {
@@ -680,6 +1215,102 @@ fn add_bonds(pdb: &mut PDB, bonds: Vec<(Context, LexItem)>) -> Vec<PDBError> {
                     ));
                 }
             }
+            LexItem::Link(atom1, atom2, extra) => {
+                // Links to symmetry copies are not bonds within the structure
+                if extra.map_or(false, |(sym1, sym2, _)| sym1 != sym2) {
+                    continue;
+                }
+                let find = |atom: LinkAtom| {
+                    pdb.chains()
+                        .find(|c| c.id() == atom.5)
+                        .and_then(|c| {
+                            c.residues().find(|r| {
+                                r.serial_number() == atom.3
+                                    && r.insertion_code() == atom.4.as_deref()
+                            })
+                        })
+                        .and_then(|r| {
+                            r.conformers().find(|c| {
+                                c.name() == atom.2
+                                    && (atom.1.is_none()
+                                        || c.alternative_location() == atom.1.as_deref())
+                            })
+                        })
+                        .and_then(|c| c.atoms().find(|a| a.name() == atom.0))
+                        .map(|a| (a.counter(), a.element().map_or(false, Element::is_metal)))
+                };
+                let ref1 = find(atom1);
+                let ref2 = find(atom2);
+
+                if let (Some((counter1, metal1)), Some((counter2, metal2))) = (ref1, ref2) {
+                    let bond = if metal1 || metal2 {
+                        Bond::MetalCoordination
+                    } else {
+                        Bond::Covalent
+                    };
+                    pdb.add_bond_counters(counter1, counter2, bond);
+                } else {
+                    errors.push(PDBError::new(
+                        ErrorLevel::InvalidatingError,
+                        "Could not find a bond partner",
+                        "One of the atoms could not be found while parsing a link.",
+                        context,
+                    ));
+                }
+            }
+            LexItem::Conect(serial_number, bonded) => {
+                let (atoms, known) = connectivity.get_or_insert_with(|| {
+                    (
+                        pdb.models()
+                            .take(1)
+                            .flat_map(Model::atoms)
+                            .map(|a| {
+                                (
+                                    a.serial_number(),
+                                    (a.counter(), a.element().map_or(false, Element::is_metal)),
+                                )
+                            })
+                            .collect(),
+                        pdb.bond_counters()
+                            .map(|(a, b, _)| (a.min(b), a.max(b)))
+                            .collect(),
+                    )
+                });
+                for partner in std::iter::once(serial_number).chain(bonded.iter().copied()) {
+                    if !atoms.contains_key(&partner) {
+                        errors.push(PDBError::new(
+                            ErrorLevel::LooseWarning,
+                            "Could not find a bond partner",
+                            format!(
+                                "Atom {partner} could not be found while parsing a CONECT record."
+                            ),
+                            context.clone(),
+                        ));
+                    }
+                }
+                let (counter1, metal1) = match atoms.get(&serial_number) {
+                    Some(&atom) => atom,
+                    None => continue,
+                };
+                for partner in bonded {
+                    let (counter2, metal2) = match atoms.get(&partner) {
+                        Some(&atom) => atom,
+                        None => continue,
+                    };
+                    // Bonds are listed for both atoms, and could also be given by SSBOND or LINK
+                    if counter1 == counter2
+                        || !known.insert((counter1.min(counter2), counter1.max(counter2)))
+                    {
+                        continue;
+                    }
+                    let bond = if metal1 || metal2 {
+                        Bond::MetalCoordination
+                    } else {
+                        Bond::Covalent
+                    };
+                    pdb.add_bond_counters(counter1, counter2, bond);
+                }
+            }
             _ => {
                 panic!(
                     "Found an invalid element in the bonds list, it is not a valid bond LexItem"
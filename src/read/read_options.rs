@@ -1,8 +1,13 @@
 use std::{ffi::OsStr, path::Path};
 
-use crate::{Context, PDBError, StrictnessLevel};
+use doc_cfg::doc_cfg;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
-use super::general::ReadResult;
+use crate::{Context, ErrorFilter, ErrorLevel, PDBError, StrictnessLevel};
+
+use super::general::{LazyReadResult, ReadResult};
+use super::pdb::{ComplianceReport, LazyModels};
 
 /// Used to set which format to read the file in.
 #[derive(Debug, Clone, Copy, Default)]
@@ -26,6 +31,40 @@ impl From<&str> for Format {
     }
 }
 
+/// Used to set how text after column 80 of ATOM and HETATM records in PDB files is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingColumns {
+    /// Ignore the trailing text
+    #[default]
+    Ignore,
+    /// Give a `LooseWarning` for every line with trailing text
+    Warn,
+    /// Store the trailing text in the atom, see [`crate::Atom::trailing_columns`]
+    Capture,
+}
+
+/// Used to set how alternative locations are handled after reading a structure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AltLocPolicy {
+    /// Keep all alternative locations as separate Conformers
+    #[default]
+    Keep,
+    /// Keep only the alternative location with the highest occupancy, see
+    /// [`crate::PDB::keep_highest_occupancy_alt_locs`]
+    HighestOccupancy,
+    /// Average the alternative locations into single atoms, see
+    /// [`crate::PDB::average_alt_locs`]
+    Average,
+}
+
+/// A handler for a custom record type in PDB files, see [`ReadOptions::register_handler`]. It
+/// receives the line number, the full line, and the PDB being built, and returns any errors found.
+pub type RecordHandler = fn(usize, &str, &mut crate::PDB) -> Vec<PDBError>;
+
+/// A transformation run on the structure after it is built but before it is validated, see
+/// [`ReadOptions::register_hook`]. It returns any errors found.
+pub type PostParseHook = fn(&mut crate::PDB) -> Vec<PDBError>;
+
 /// Options and flags which can be used to configure how a structure file is
 /// opened.
 ///
@@ -51,7 +90,7 @@ impl From<&str> for Format {
 ///
 /// The format of the file is inferred by [`ReadOptions::guess_format`]
 /// when it is not set explicitly with [`ReadOptions::set_format`].
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ReadOptions {
     /// The format to read the file in.
     pub(crate) format: Format,
@@ -74,8 +113,53 @@ pub struct ReadOptions {
 
     /// Only read atomic coordinates
     pub(crate) only_atomic_coords: bool,
+
+    /// Assign chains from the segment identifiers
+    pub(crate) chains_from_segments: bool,
+
+    /// Do not retain header metadata (HEADER and REMARK records)
+    pub(crate) discard_metadata: bool,
+
+    /// Aggregate errors with the same level and title into a single error
+    pub(crate) aggregate_errors: bool,
+
+    /// The maximal number of errors before reading is aborted
+    pub(crate) max_errors: Option<usize>,
+
+    /// Filters to suppress (`None`) or downgrade errors
+    pub(crate) error_filters: Vec<(ErrorFilter, Option<ErrorLevel>)>,
+
+    /// Convert old (v2.3) nomenclature to the current conventions
+    pub(crate) normalize_nomenclature: bool,
+
+    /// Reconstruct overflowing atom serial numbers sequentially
+    pub(crate) reconstruct_serials: bool,
+
+    /// Skip the validation of the structure after parsing
+    pub(crate) skip_validation: bool,
+
+    /// The occupancy to use for atoms without one
+    pub(crate) default_occupancy: Option<f64>,
+
+    /// The B factor to use for atoms without one
+    pub(crate) default_b_factor: Option<f64>,
+
+    /// How to handle text after column 80
+    pub(crate) trailing_columns: TrailingColumns,
+
+    /// How to handle alternative locations
+    pub(crate) alt_loc_policy: AltLocPolicy,
+
+    /// Handlers for custom record types, with the record tag they handle
+    pub(crate) record_handlers: Vec<(String, RecordHandler)>,
+
+    /// Transformations to run after building the structure
+    pub(crate) post_parse_hooks: Vec<PostParseHook>,
 }
 
+/// The number of representative contexts kept for aggregated errors
+const AGGREGATED_CONTEXTS: usize = 5;
+
 impl ReadOptions {
     /// Constructs a new [`ReadOptions`] object with default values.
     pub fn new() -> Self {
@@ -109,7 +193,11 @@ impl ReadOptions {
         self
     }
 
-    /// Sets whether to decompress the file.
+    /// Sets whether to decompress the file, or the input stream given to
+    /// [`ReadOptions::read_raw`], as gzip. Files with a `.gz` extension are always decompressed.
+    /// If the format is set explicitly with [`ReadOptions::set_format`] it is used instead of
+    /// the format given by the file extension, so compressed files with non-standard names can
+    /// be read as well.
     #[cfg(feature = "compression")]
     pub fn set_decompress(&mut self, decompress: bool) -> &mut Self {
         self.decompress = decompress;
@@ -134,6 +222,268 @@ impl ReadOptions {
         self
     }
 
+    /// Sets whether to assign atoms to chains based on their segment identifier (columns 73-76)
+    /// instead of the chain identifier column in PDB files. Programs like CHARMM and NAMD often
+    /// leave the chain identifiers blank and distinguish the molecules by segment, which would
+    /// otherwise end up in a single chain. Atoms without a segment identifier keep using the
+    /// chain identifier column.
+    pub fn set_chains_from_segments(&mut self, chains_from_segments: bool) -> &mut Self {
+        self.chains_from_segments = chains_from_segments;
+        self
+    }
+
+    /// Sets whether to skip retaining the header metadata (the identifier and remarks).
+    /// This saves memory when only the coordinates are of interest, as the remarks are otherwise
+    /// kept as owned text (one `String` per REMARK line) for as long as the PDB lives. The MASTER
+    /// remark checksum is not checked when this is set.
+    pub fn set_discard_metadata(&mut self, discard_metadata: bool) -> &mut Self {
+        self.discard_metadata = discard_metadata;
+        self
+    }
+
+    /// Sets whether to aggregate errors. When set all errors with the same level and short
+    /// description are merged into a single error with a few representative contexts and the
+    /// number of occurrences, see [`PDBError::aggregate`]. This prevents huge lists of errors for
+    /// files which trigger the same issue on every line.
+    pub fn set_aggregate_errors(&mut self, aggregate_errors: bool) -> &mut Self {
+        self.aggregate_errors = aggregate_errors;
+        self
+    }
+
+    /// Sets the maximal number of errors (including warnings) to collect. When more errors are
+    /// found reading is aborted with a `BreakingError`, protecting against pathological inputs
+    /// which would otherwise generate enormous lists of errors. By default there is no limit.
+    pub fn set_max_errors(&mut self, max_errors: usize) -> &mut Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Sets whether to convert the names of atoms and residues following the old (v2.3) conventions
+    /// of the PDB format to the current conventions, see [`crate::PDB::normalize_nomenclature`]. Only
+    /// applies to PDB files.
+    pub fn set_normalize_nomenclature(&mut self, normalize: bool) -> &mut Self {
+        self.normalize_nomenclature = normalize;
+        self
+    }
+
+    /// Sets whether to reconstruct atom serial numbers which do not fit in their columns in PDB
+    /// files. Some tools write `*****` or hexadecimal numbers for serial numbers above 99999,
+    /// which normally fail with "Invalid data in field". With this option, from the first
+    /// serial number which is not a valid number onwards all atoms are numbered sequentially.
+    pub fn set_reconstruct_serials(&mut self, reconstruct: bool) -> &mut Self {
+        self.reconstruct_serials = reconstruct;
+        self
+    }
+
+    /// Sets whether to skip the validation after parsing, for trusted inputs like files written
+    /// by pdbtbx itself. This skips [`crate::validate`], the checks of the SEQRES records, and
+    /// the MASTER checksums, which speeds up reading in high throughput pipelines. Note that
+    /// residues only defined in the SEQRES records are then not added to the chains, and a file
+    /// without atoms does not fail.
+    pub fn set_skip_validation(&mut self, skip_validation: bool) -> &mut Self {
+        self.skip_validation = skip_validation;
+        self
+    }
+
+    /// Sets the occupancy to use for atoms in PDB files where the occupancy column is blank or
+    /// missing because the line is too short. Every atom using this default gets a
+    /// `GeneralWarning`, so fabricated values can be recognised. Without a default (the default)
+    /// a missing occupancy is an `InvalidatingError`.
+    pub fn set_default_occupancy(&mut self, occupancy: f64) -> &mut Self {
+        self.default_occupancy = Some(occupancy);
+        self
+    }
+
+    /// Sets the B factor to use for atoms in PDB files where the B factor column is blank or
+    /// missing, see [`ReadOptions::set_default_occupancy`].
+    pub fn set_default_b_factor(&mut self, b_factor: f64) -> &mut Self {
+        self.default_b_factor = Some(b_factor);
+        self
+    }
+
+    /// Sets how to handle text after column 80 of ATOM and HETATM records in PDB files, as
+    /// appended by some pipelines. By default the trailing text is ignored. If the charge
+    /// columns (79-80) do not hold a valid charge they are seen as part of the trailing text.
+    pub fn set_trailing_columns(&mut self, trailing_columns: TrailingColumns) -> &mut Self {
+        self.trailing_columns = trailing_columns;
+        self
+    }
+
+    /// Sets how alternative locations are handled, by default all alternative locations are
+    /// kept. The policy is applied after the structure is built, before the post parse hooks
+    /// are run and the structure is validated.
+    pub fn set_alt_loc_policy(&mut self, alt_loc_policy: AltLocPolicy) -> &mut Self {
+        self.alt_loc_policy = alt_loc_policy;
+        self
+    }
+
+    /// Register a handler for lines starting with the given record tag (eg `"USER  "`) in PDB
+    /// files, to parse site specific extensions. Handlers take precedence over the built in
+    /// parsing, so they can also be used to replace the handling of a standard record type. The
+    /// handler can attach data to the PDB, but note that the models are only added to it after
+    /// all lines are read. Handlers are not used when only validating a file.
+    ///
+    /// # Example
+    /// ```rust
+    /// use pdbtbx::*;
+    /// fn user(_linenumber: usize, line: &str, pdb: &mut PDB) -> Vec<PDBError> {
+    ///     pdb.identifier = Some(line[6..].trim().to_string());
+    ///     Vec::new()
+    /// }
+    /// let input = "USER  1ABC
+    /// ATOM      1  CA  GLY A   1      26.266  25.413   2.842  1.00 20.00           C
+    /// END
+    /// ";
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_format(Format::Pdb)
+    ///     .register_handler("USER  ", user)
+    ///     .read_raw(input.as_bytes())
+    ///     .unwrap();
+    /// assert_eq!(pdb.identifier.as_deref(), Some("1ABC"));
+    /// ```
+    pub fn register_handler(
+        &mut self,
+        record: impl Into<String>,
+        handler: RecordHandler,
+    ) -> &mut Self {
+        self.record_handlers.push((record.into(), handler));
+        self
+    }
+
+    /// Get the handler for the given line, if any.
+    pub(crate) fn record_handler(&self, line: &str) -> Option<RecordHandler> {
+        self.record_handlers
+            .iter()
+            .find(|(record, _)| line.starts_with(record.as_str()))
+            .map(|(_, handler)| *handler)
+    }
+
+    /// Register a transformation to run after the structure is built but before it is validated,
+    /// for example to strip hydrogens, rename chains, or fix elements. Hooks are run in the order
+    /// they are registered, for both PDB and mmCIF files.
+    ///
+    /// # Example
+    /// ```rust
+    /// use pdbtbx::*;
+    /// fn rename_chains(pdb: &mut PDB) -> Vec<PDBError> {
+    ///     for chain in pdb.chains_mut() {
+    ///         chain.set_id("X");
+    ///     }
+    ///     Vec::new()
+    /// }
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .register_hook(rename_chains)
+    ///     .read("example-pdbs/1ubq.pdb")
+    ///     .unwrap();
+    /// assert!(pdb.chains().all(|chain| chain.id() == "X"));
+    /// ```
+    pub fn register_hook(&mut self, hook: PostParseHook) -> &mut Self {
+        self.post_parse_hooks.push(hook);
+        self
+    }
+
+    /// Apply the alternative location policy and run all post parse hooks on the given structure.
+    pub(crate) fn run_hooks(&self, pdb: &mut crate::PDB) -> Vec<PDBError> {
+        match self.alt_loc_policy {
+            AltLocPolicy::Keep => (),
+            AltLocPolicy::HighestOccupancy => pdb.keep_highest_occupancy_alt_locs(),
+            AltLocPolicy::Average => pdb.average_alt_locs(),
+        }
+        self.post_parse_hooks
+            .iter()
+            .flat_map(|hook| hook(pdb))
+            .collect()
+    }
+
+    /// Silence all errors matching the given filter, these errors are removed while reading.
+    /// Breaking errors cannot be silenced.
+    pub fn suppress_errors(&mut self, filter: ErrorFilter) -> &mut Self {
+        self.error_filters.push((filter, None));
+        self
+    }
+
+    /// Change the level of all errors matching the given filter to the given level while
+    /// reading, for example to allow a specific warning to pass with a stricter level.
+    /// Breaking errors cannot be downgraded.
+    pub fn downgrade_errors(&mut self, filter: ErrorFilter, level: ErrorLevel) -> &mut Self {
+        self.error_filters.push((filter, Some(level)));
+        self
+    }
+
+    /// Apply the error filters to the given errors, starting at the given index so errors that
+    /// were already filtered are skipped. The first matching filter is used for every error.
+    pub(crate) fn filter_errors(&self, errors: &mut Vec<PDBError>, start: usize) {
+        if self.error_filters.is_empty() || start >= errors.len() {
+            return;
+        }
+        let mut new_errors = errors.split_off(start);
+        new_errors.retain_mut(|error| {
+            if error.level() == ErrorLevel::BreakingError {
+                return true;
+            }
+            match self.error_filters.iter().find(|(f, _)| f.matches(error)) {
+                Some((_, Some(level))) => {
+                    error.set_level(*level);
+                    true
+                }
+                Some((_, None)) => false,
+                None => true,
+            }
+        });
+        errors.append(&mut new_errors);
+    }
+
+    /// Enforce the maximal number of errors. If the given errors exceed the maximum they are
+    /// truncated to the maximum and a `BreakingError` is added to abort reading with, in which
+    /// case `true` is returned.
+    pub(crate) fn enforce_error_limit(&self, errors: &mut Vec<PDBError>) -> bool {
+        match self.max_errors {
+            Some(max)
+                if errors.len() > max
+                    && errors.last().map(PDBError::short_description)
+                        != Some("Too many errors") =>
+            {
+                errors.truncate(max);
+                errors.push(PDBError::new(
+                    crate::ErrorLevel::BreakingError,
+                    "Too many errors",
+                    format!("More than the maximal number of errors ({max}) were found, reading was aborted."),
+                    Context::None,
+                ));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Post process the errors of a read result, based on the options. The result is decided
+    /// again on the processed errors, so a result is never `Ok` with errors that fail.
+    pub(crate) fn process_errors(&self, result: ReadResult) -> ReadResult {
+        match result {
+            Ok((pdb, errors)) => {
+                let errors = self.process_error_list(errors);
+                if errors.iter().any(|e| e.fails(self.level)) {
+                    Err(errors)
+                } else {
+                    Ok((pdb, errors))
+                }
+            }
+            Err(errors) => Err(self.process_error_list(errors)),
+        }
+    }
+
+    /// Post process a list of errors, based on the options. The error filters are already
+    /// applied while reading, so these are not applied again.
+    fn process_error_list(&self, mut errors: Vec<PDBError>) -> Vec<PDBError> {
+        self.enforce_error_limit(&mut errors);
+        if self.aggregate_errors {
+            PDBError::aggregate(errors, AGGREGATED_CONTEXTS)
+        } else {
+            errors
+        }
+    }
+
     /// Open an atomic data file, either PDB or mmCIF/PDBx, into a [`PDB`] structure.
     /// The correct type will be determined based on the file extension.
     ///
@@ -144,11 +494,19 @@ impl ReadOptions {
     /// If you want to open a file from memory, see [`ReadOptions::read_raw`].
     /// If your file extensions are not canonical, set the format explicitly with [`ReadOptions::set_format`].
     pub fn read(&self, path: impl AsRef<str>) -> ReadResult {
+        self.process_errors(self.read_unprocessed(path))
+    }
+
+    /// Open an atomic data file, see [`ReadOptions::read`], without post processing the errors.
+    fn read_unprocessed(&self, path: impl AsRef<str>) -> ReadResult {
         if self.decompress {
             // open a decompression stream
             let filename = path.as_ref();
 
-            self.read_auto(filename)
+            match self.format {
+                Format::Auto => self.read_auto(filename),
+                format => self.read_gz(filename, format),
+            }
         } else {
             match self.format {
                 Format::Pdb => super::pdb::open_pdb_with_options(path, self),
@@ -158,34 +516,278 @@ impl ReadOptions {
         }
     }
 
-    /// Open an atomic data file, either PDB or mmCIF/PDBx, into a [`PDB`] structure
-    /// and automatically determine the file type based on the extension of `path`.
-    fn read_auto(&self, path: impl AsRef<str>) -> ReadResult {
+    /// Validate an atomic data file, either PDB or mmCIF/PDBx, without retaining the [`PDB`]
+    /// structure, returning all errors/warnings found. The format is determined like in
+    /// [`ReadOptions::read`]. For PDB files only the lexer and the MASTER checks are run, without
+    /// building the hierarchy, which makes this faster and lighter than reading the file. Because
+    /// of this, invariants that need the full hierarchy (like corresponding atoms in all models)
+    /// are not checked for PDB files. mmCIF files are fully read and validated.
+    ///
+    /// Use [`PDBError::fails`] on the returned errors to determine if the file is valid with
+    /// the strictness level of interest.
+    pub fn validate_file(&self, path: impl AsRef<str>) -> Vec<PDBError> {
+        self.process_error_list(self.validate_file_unprocessed(path.as_ref()))
+    }
+
+    /// Audit a PDB file for column level conformance to the wwPDB v3.30 format, beyond the
+    /// semantic checks done when reading. It checks the line lengths, the record types, the
+    /// record order, the presence of all mandatory records, and the widths and justification of
+    /// the fields of the coordinate records (ATOM, HETATM, ANISOU, TER, MODEL, CRYST1, MASTER).
+    /// Gzipped files are decompressed based on the extension or [`ReadOptions::set_decompress`].
+    /// The error filters set on these options are applied to the issues found.
+    ///
+    /// mmCIF files cannot be audited, for these the report contains a single `BreakingError`.
+    pub fn audit(&self, path: impl AsRef<str>) -> ComplianceReport {
         let filename = path.as_ref();
-        if let Some((file_format, is_compressed)) = guess_format(filename) {
-            if is_compressed {
-                let file = std::fs::File::open(filename).map_err(|_| {
-                    vec![PDBError::new(
+        let format = match self.format {
+            Format::Auto => guess_format(filename),
+            format => Some((format, self.decompress)),
+        };
+        let report = match format {
+            Some((Format::Mmcif, _)) => ComplianceReport::from_issues(vec![PDBError::new(
+                ErrorLevel::BreakingError,
+                "Not a PDB file",
+                "Only PDB files can be audited for conformance to the format.",
+                Context::show(filename),
+            )]),
+            _ => match std::fs::File::open(filename) {
+                Ok(file) if format.map_or(self.decompress, |(_, c)| c) => super::pdb::audit_pdb_raw(
+                    std::io::BufReader::new(flate2::read::GzDecoder::new(file)),
+                    Context::show(filename),
+                ),
+                Ok(file) => super::pdb::audit_pdb_raw(
+                    std::io::BufReader::new(file),
+                    Context::show(filename),
+                ),
+                Err(_) => ComplianceReport::from_issues(vec![PDBError::new(
+                    ErrorLevel::BreakingError,
+                    "Could not open file",
+                    "Could not open the specified file, make sure the path is correct, you have permission, and that it is not open in another program.",
+                    Context::show(filename),
+                )]),
+            },
+        };
+        self.filter_report(report)
+    }
+
+    /// Audit a PDB file from the given input stream, see [`ReadOptions::audit`].
+    pub fn audit_raw(&self, input: impl std::io::BufRead) -> ComplianceReport {
+        self.filter_report(super::pdb::audit_pdb_raw(input, Context::None))
+    }
+
+    /// Open a PDB file to read its Models one at a time, see [`LazyModels`]. The header (all
+    /// records before the first Model) is parsed directly, every Model is only parsed when the
+    /// iterator reaches it. This keeps the memory use bounded for files with many Models, like NMR
+    /// ensembles and molecular dynamics trajectories. Gzipped files are decompressed based on the
+    /// extension or [`ReadOptions::set_decompress`].
+    ///
+    /// # Errors
+    /// Returns a `PDBError` if the file could not be opened, if it is an mmCIF file, or if a
+    /// `BreakingError` is found in the header. Otherwise it returns the iterator with all
+    /// errors/warnings found while parsing the header.
+    pub fn read_lazy(&self, path: impl AsRef<str>) -> LazyReadResult {
+        let filename = path.as_ref();
+        let format = match self.format {
+            Format::Auto => guess_format(filename),
+            format => Some((format, self.decompress)),
+        };
+        if let Some((Format::Mmcif, _)) = format {
+            return Err(vec![PDBError::new(
+                ErrorLevel::BreakingError,
+                "Not a PDB file",
+                "Only PDB files can be read lazily.",
+                Context::show(filename),
+            )]);
+        }
+        let file = std::fs::File::open(filename).map_err(|_| {
+            vec![PDBError::new(
+                ErrorLevel::BreakingError,
+                "Could not open file",
+                "Could not open the specified file, make sure the path is correct, you have permission, and that it is not open in another program.",
+                Context::show(filename),
+            )]
+        })?;
+        let input: Box<dyn std::io::BufRead> = match format {
+            #[cfg(feature = "compression")]
+            Some((_, true)) => {
+                Box::new(std::io::BufReader::new(flate2::read::GzDecoder::new(file)))
+            }
+            _ => Box::new(std::io::BufReader::new(file)),
+        };
+        LazyModels::new(input, Context::show(filename), self)
+    }
+
+    /// Read the Models of a PDB file one at a time from the given input stream, see
+    /// [`ReadOptions::read_lazy`]. The input is always parsed as PDB.
+    ///
+    /// # Errors
+    /// Returns a `PDBError` if a `BreakingError` is found in the header. Otherwise it returns the
+    /// iterator with all errors/warnings found while parsing the header.
+    pub fn read_lazy_raw<R: std::io::BufRead>(&self, input: R) -> LazyReadResult<R> {
+        LazyModels::new(input, Context::None, self)
+    }
+
+    /// Apply the error filters to the issues of the given compliance report.
+    fn filter_report(&self, mut report: ComplianceReport) -> ComplianceReport {
+        self.filter_errors(report.issues_mut(), 0);
+        report
+    }
+
+    /// Validate an atomic data file, see [`ReadOptions::validate_file`], without post processing the errors.
+    fn validate_file_unprocessed(&self, filename: &str) -> Vec<PDBError> {
+        let format = match self.format {
+            Format::Auto => guess_format(filename),
+            format => Some((format, self.decompress)),
+        };
+        if let Some((Format::Pdb, is_compressed)) = format {
+            let file = match std::fs::File::open(filename) {
+                Ok(file) => file,
+                Err(_) => {
+                    return vec![PDBError::new(
                         crate::ErrorLevel::BreakingError,
                         "Could not open file",
-                        "Could not open the given file, make sure it exists and you have the correct permissions",
+                        "Could not open the specified file, make sure the path is correct, you have permission, and that it is not open in another program.",
                         Context::show(filename),
                     )]
-                })?;
-                let decompressor = flate2::read::GzDecoder::new(file);
-                let reader = std::io::BufReader::new(decompressor);
-                match file_format {
-                    Format::Pdb => {
-                        super::pdb::open_pdb_raw_with_options(reader, Context::None, self)
-                    }
-                    Format::Mmcif => super::mmcif::open_mmcif_raw_with_options(reader, self),
-                    Format::Auto => Err(vec![PDBError::new(
-                        crate::ErrorLevel::BreakingError,
-                        "Could not determine file type",
-                        "Could not determine the type of the gzipped file, use .pdb.gz or .cif.gz",
-                        Context::show(filename),
-                    )]),
                 }
+            };
+            if is_compressed {
+                let reader = std::io::BufReader::new(flate2::read::GzDecoder::new(file));
+                super::pdb::validate_pdb_raw_with_options(reader, Context::show(filename), self)
+            } else {
+                let reader = std::io::BufReader::new(file);
+                super::pdb::validate_pdb_raw_with_options(reader, Context::show(filename), self)
+            }
+        } else {
+            match self.read_unprocessed(filename) {
+                Ok((_, errors)) | Err(errors) => errors,
+            }
+        }
+    }
+
+    /// Open many atomic data files in parallel, see [`ReadOptions::read`]. The files are parsed on
+    /// the rayon thread pool and the results are given back as soon as they are done, so not
+    /// necessarily in the same order as the given paths. Every result is accompanied by its path.
+    #[doc_cfg(feature = "rayon")]
+    pub fn read_many<P: AsRef<str>>(
+        &self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> impl Iterator<Item = (String, ReadResult)> {
+        let paths: Vec<String> = paths.into_iter().map(|p| p.as_ref().to_string()).collect();
+        let options = self.clone();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        rayon::spawn(move || {
+            paths.into_par_iter().for_each_with(sender, |sender, path| {
+                let result = options.read(&path);
+                // The receiver could have been dropped, in which case the result is not needed
+                let _ = sender.send((path, result));
+            });
+        });
+        receiver.into_iter()
+    }
+
+    /// Open an entry from a local copy of the wwPDB archive, based on its four character PDB
+    /// identifier. The divided directory layout is used to find the entry, so for `1abc` the
+    /// following files are tried in order: `pdb/ab/pdb1abc.ent.gz`, `pdb/ab/pdb1abc.ent`,
+    /// `mmCIF/ab/1abc.cif.gz`, and `mmCIF/ab/1abc.cif`. The `root` can point to the directory
+    /// containing these folders or to the root of the rsynced archive (which contains
+    /// `data/structures/divided`). The format is determined from the found file, so any format
+    /// set with [`ReadOptions::set_format`] is ignored.
+    ///
+    /// # Errors
+    /// Returns a `PDBError` if the identifier is invalid, if the entry could not be found, or if a
+    /// `BreakingError` is found while reading. Otherwise it returns the PDB with all errors/warnings
+    /// found while parsing it.
+    pub fn read_from_mirror(&self, root: impl AsRef<str>, identifier: &str) -> ReadResult {
+        let root = root.as_ref();
+        let id = check_identifier(identifier)?;
+        let middle = &id[1..3];
+        let bases = [
+            Path::new(root).to_path_buf(),
+            Path::new(root)
+                .join("data")
+                .join("structures")
+                .join("divided"),
+        ];
+        for base in &bases {
+            for candidate in [
+                base.join("pdb")
+                    .join(middle)
+                    .join(format!("pdb{id}.ent.gz")),
+                base.join("pdb").join(middle).join(format!("pdb{id}.ent")),
+                base.join("mmCIF").join(middle).join(format!("{id}.cif.gz")),
+                base.join("mmCIF").join(middle).join(format!("{id}.cif")),
+            ] {
+                if candidate.is_file() {
+                    return self.process_errors(self.read_auto(candidate.to_string_lossy()));
+                }
+            }
+        }
+        Err(vec![PDBError::new(
+            crate::ErrorLevel::BreakingError,
+            "Entry not found in mirror",
+            format!("Could not find the entry '{identifier}' in the divided layout of the given wwPDB mirror, make sure the path points to the mirror."),
+            Context::show(root),
+        )])
+    }
+
+    /// Download an entry from the RCSB PDB, based on its four character PDB identifier, over
+    /// HTTPS. The entry is downloaded gzipped from `https://files.rcsb.org/download/` and
+    /// decompressed while parsing. If the format is set to [`Format::Pdb`] the PDB file is
+    /// downloaded, otherwise the mmCIF file is downloaded as it is available for all entries.
+    ///
+    /// # Errors
+    /// Returns a `PDBError` if the identifier is invalid, if the entry could not be downloaded, or
+    /// if a `BreakingError` is found while reading. Otherwise it returns the PDB with all
+    /// errors/warnings found while parsing it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .fetch("1CRN")
+    ///     .unwrap();
+    /// assert_eq!(pdb.chain_count(), 1);
+    /// ```
+    #[doc_cfg(feature = "fetch")]
+    pub fn fetch(&self, identifier: &str) -> ReadResult {
+        let id = check_identifier(identifier)?;
+        let (format, extension) = match self.format {
+            Format::Pdb => (Format::Pdb, "pdb"),
+            Format::Mmcif | Format::Auto => (Format::Mmcif, "cif"),
+        };
+        let url = format!("https://files.rcsb.org/download/{id}.{extension}.gz");
+        let response = ureq::get(&url).call().map_err(|error| {
+            vec![match error {
+                ureq::Error::Status(404, _) => PDBError::new(
+                    crate::ErrorLevel::BreakingError,
+                    "Entry not found",
+                    format!("The entry '{identifier}' could not be found on the RCSB PDB, make sure the identifier is correct and the entry is released."),
+                    Context::show(&url),
+                ),
+                error => PDBError::new(
+                    crate::ErrorLevel::BreakingError,
+                    "Could not download entry",
+                    format!("The entry '{identifier}' could not be downloaded from the RCSB PDB: {error}"),
+                    Context::show(&url),
+                ),
+            }]
+        })?;
+        let reader = std::io::BufReader::new(flate2::read::GzDecoder::new(response.into_reader()));
+        self.process_errors(match format {
+            Format::Pdb => super::pdb::open_pdb_raw_with_options(reader, Context::None, self),
+            _ => super::mmcif::open_mmcif_raw_with_options(reader, self),
+        })
+    }
+
+    /// Open an atomic data file, either PDB or mmCIF/PDBx, into a [`PDB`] structure
+    /// and automatically determine the file type based on the extension of `path`.
+    fn read_auto(&self, path: impl AsRef<str>) -> ReadResult {
+        let filename = path.as_ref();
+        if let Some((file_format, is_compressed)) = guess_format(filename) {
+            if is_compressed {
+                self.read_gz(filename, file_format)
             } else {
                 match file_format {
                     Format::Pdb => super::pdb::open_pdb_with_options(path, self),
@@ -208,16 +810,50 @@ impl ReadOptions {
         }
     }
 
+    /// Open a gzipped atomic data file in the given format, regardless of its extension.
+    fn read_gz(&self, filename: &str, format: Format) -> ReadResult {
+        let file = std::fs::File::open(filename).map_err(|_| {
+            vec![PDBError::new(
+                crate::ErrorLevel::BreakingError,
+                "Could not open file",
+                "Could not open the given file, make sure it exists and you have the correct permissions",
+                Context::show(filename),
+            )]
+        })?;
+        let reader = std::io::BufReader::new(flate2::read::GzDecoder::new(file));
+        match format {
+            Format::Pdb => super::pdb::open_pdb_raw_with_options(reader, Context::None, self),
+            Format::Mmcif => super::mmcif::open_mmcif_raw_with_options(reader, self),
+            Format::Auto => Err(vec![PDBError::new(
+                crate::ErrorLevel::BreakingError,
+                "Could not determine file type",
+                "Could not determine the type of the gzipped file, use .pdb.gz or .cif.gz",
+                Context::show(filename),
+            )]),
+        }
+    }
+
     /// Parse the input stream into a [`PDB`] struct. To allow for direct streaming from sources, like from RCSB.org.
     /// The file format **must** be set explicitly with [`ReadOptions::set_format`].
     /// Returns a PDBError if a BreakingError is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
     ///
+    /// Any [`std::io::BufRead`] can be given, for a plain [`std::io::Read`] see [`ReadOptions::read_unbuffered`].
+    /// If [`ReadOptions::set_decompress`] is set the input is decompressed as a gzip stream, so
+    /// compressed sources without a file name can be read directly.
+    ///
     /// # Related
     /// If you want to open a file, see [`ReadOptions::read`].
-    pub fn read_raw<T>(&self, input: std::io::BufReader<T>) -> ReadResult
-    where
-        T: std::io::Read,
-    {
+    pub fn read_raw(&self, input: impl std::io::BufRead) -> ReadResult {
+        #[cfg(feature = "compression")]
+        if self.decompress {
+            let reader = std::io::BufReader::new(flate2::read::GzDecoder::new(input));
+            return self.process_errors(self.read_raw_unprocessed(reader));
+        }
+        self.process_errors(self.read_raw_unprocessed(input))
+    }
+
+    /// Parse the input stream, see [`ReadOptions::read_raw`], without post processing the errors.
+    fn read_raw_unprocessed(&self, input: impl std::io::BufRead) -> ReadResult {
         match self.format {
             Format::Pdb => super::pdb::open_pdb_raw_with_options(input, Context::None, self),
             Format::Mmcif => super::mmcif::open_mmcif_raw_with_options(input, self),
@@ -229,6 +865,53 @@ impl ReadOptions {
             )]),
         }
     }
+
+    /// Parse the given lines into a [`PDB`] struct, for lines coming from sources other than
+    /// readers, like archives, databases, or in memory caches. The lines are parsed as PDB,
+    /// unless the format is explicitly set to [`Format::Mmcif`]. Any line endings are removed.
+    ///
+    /// # Errors
+    /// Returns a `PDBError` if a `BreakingError` is found. Otherwise it returns the PDB with all errors/warnings found while parsing it.
+    pub fn read_lines(&self, lines: impl IntoIterator<Item = String>) -> ReadResult {
+        match self.format {
+            Format::Mmcif => {
+                let mut input = String::new();
+                for line in lines {
+                    input.push_str(&line);
+                    input.push('\n');
+                }
+                self.process_errors(self.read_raw_unprocessed(input.as_bytes()))
+            }
+            Format::Pdb | Format::Auto => {
+                self.process_errors(super::pdb::open_pdb_lines_with_options(
+                    super::pdb::string_lines(lines),
+                    Context::None,
+                    self,
+                ))
+            }
+        }
+    }
+
+    /// Parse the input stream into a [`PDB`] struct, see [`ReadOptions::read_raw`]. The input is
+    /// wrapped in a [`std::io::BufReader`], so use [`ReadOptions::read_raw`] if the input is already
+    /// buffered to prevent buffering twice.
+    pub fn read_unbuffered(&self, input: impl std::io::Read) -> ReadResult {
+        self.read_raw(std::io::BufReader::new(input))
+    }
+}
+
+/// Check that the given PDB identifier consists of four alphanumeric characters, and give it
+/// back in lowercase.
+fn check_identifier(identifier: &str) -> Result<String, Vec<PDBError>> {
+    if identifier.len() != 4 || !identifier.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(vec![PDBError::new(
+            crate::ErrorLevel::BreakingError,
+            "Invalid PDB identifier",
+            "A PDB identifier should consist of four alphanumeric characters, like '1abc'.",
+            Context::show(identifier),
+        )]);
+    }
+    Ok(identifier.to_ascii_lowercase())
 }
 
 /// Guess the file format based on the file name extensions.
@@ -236,12 +919,12 @@ fn guess_format(filename: &str) -> Option<(Format, bool)> {
     let path = Path::new(filename);
 
     match path.extension().and_then(OsStr::to_str) {
-        Some("pdb") | Some("pdb1") => Some((Format::Pdb, false)),
+        Some("pdb") | Some("pdb1") | Some("ent") => Some((Format::Pdb, false)),
         Some("cif") | Some("mmcif") => Some((Format::Mmcif, false)),
         Some("gz") => {
             let path_ext = Path::new(path.file_stem().and_then(OsStr::to_str).unwrap_or(""));
             match path_ext.extension().and_then(OsStr::to_str) {
-                Some("pdb") | Some("pdb1") => Some((Format::Pdb, true)),
+                Some("pdb") | Some("pdb1") | Some("ent") => Some((Format::Pdb, true)),
                 Some("cif") | Some("mmcif") => Some((Format::Mmcif, true)),
                 _ => None,
             }
@@ -249,3 +932,20 @@ fn guess_format(filename: &str) -> Option<(Format, bool)> {
         _ => None,
     }
 }
+
+/// Detect the format based on the content: input of which the first line with content (ignoring
+/// comments) opens a CIF data block is mmCIF, all other input is PDB.
+pub(crate) fn detect_format(input: &[u8]) -> Format {
+    for line in input.split(|c| *c == b'\n') {
+        let start = line.iter().take_while(|c| c.is_ascii_whitespace()).count();
+        let line = &line[start..];
+        if !line.is_empty() && !line.starts_with(b"#") {
+            return if line.starts_with(b"data_") {
+                Format::Mmcif
+            } else {
+                Format::Pdb
+            };
+        }
+    }
+    Format::Pdb
+}
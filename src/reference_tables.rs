@@ -1,3 +1,4 @@
+use crate::{Element, FormatVersion};
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
@@ -53,11 +54,156 @@ pub fn is_amino_acid(aa: impl AsRef<str>) -> bool {
     AMINO_ACIDS.contains(&aa.as_ref())
 }
 
+/// Returns if the given residue name is a common name for water
+pub fn is_water(name: impl AsRef<str>) -> bool {
+    WATERS.contains(&name.as_ref())
+}
+
+/// Returns if the given residue name is a common (deoxy)ribonucleotide
+pub fn is_nucleotide(name: impl AsRef<str>) -> bool {
+    NUCLEOTIDES.contains(&name.as_ref())
+}
+
+/// Returns if the given residue name is a common monosaccharide
+pub fn is_sugar(name: impl AsRef<str>) -> bool {
+    SUGARS.contains(&name.as_ref())
+}
+
+/// Returns the range of common coordination numbers in biological structures for the given
+/// metal, if known
+pub fn coordination_numbers(element: &Element) -> Option<(usize, usize)> {
+    COORDINATION_NUMBERS
+        .iter()
+        .find(|(e, _)| e == element)
+        .map(|(_, range)| *range)
+}
+
+/// Returns the aromatic rings of the given residue, as the names of the ring atoms in order
+/// around the ring
+pub fn aromatic_rings(name: &str) -> &'static [&'static [&'static str]] {
+    AROMATIC_RINGS
+        .iter()
+        .find(|(names, _)| names.contains(&name))
+        .map_or(&[], |(_, rings)| *rings)
+}
+
+/// Returns the favored and allowed regions of the Ramachandran plot for the given amino acid, as
+/// rectangles of (phi min, phi max, psi min, psi max) in degrees. Glycine and proline have their
+/// own regions, all other amino acids share the general regions.
+pub fn ramachandran_regions(name: &str) -> (&'static [[f64; 4]], &'static [[f64; 4]]) {
+    match name {
+        "GLY" => (RAMACHANDRAN_GLYCINE_FAVORED, RAMACHANDRAN_GLYCINE_ALLOWED),
+        "PRO" => (RAMACHANDRAN_PROLINE_FAVORED, RAMACHANDRAN_PROLINE_ALLOWED),
+        _ => (RAMACHANDRAN_GENERAL_FAVORED, RAMACHANDRAN_GENERAL_ALLOWED),
+    }
+}
+
+/// Returns the atoms defining the side chain torsion angles (chi1, chi2, ..) of the given amino
+/// acid, as the names of the four atoms for every angle
+pub fn side_chain_torsions(name: &str) -> &'static [[&'static str; 4]] {
+    SIDE_CHAIN_TORSIONS
+        .iter()
+        .find(|(names, _)| names.contains(&name))
+        .map_or(&[], |(_, torsions)| *torsions)
+}
+
+/// Returns the common rotamers of the given amino acid, as the name of the rotamer with its
+/// chi1 and (if defined) chi2 angles in degrees. The last angle of the rotamers is only defined
+/// modulo 180 degrees for side chains with a symmetric end, see [`has_symmetric_side_chain`].
+pub fn rotamers(name: &str) -> &'static [(&'static str, &'static [f64])] {
+    ROTAMERS
+        .iter()
+        .find(|(names, _)| names.contains(&name))
+        .map_or(&[], |(_, rotamers)| *rotamers)
+}
+
+/// Returns if the chi2 angle of the given amino acid is only defined modulo 180 degrees, because
+/// the atoms at the end of its side chain are equivalent
+pub fn has_symmetric_side_chain(name: &str) -> bool {
+    SYMMETRIC_SIDE_CHAINS.contains(&name)
+}
+
+/// Returns the charge of the side chain of the given amino acid at neutral pH, if it is a known
+/// amino acid. Histidine is taken to be neutral, unless named as protonated (HIP).
+pub fn amino_acid_charge(name: &str) -> Option<isize> {
+    if !is_amino_acid(name) {
+        return None;
+    }
+    Some(
+        AMINO_ACID_CHARGES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map_or(0, |(_, charge)| *charge),
+    )
+}
+
+/// Returns the names of the heavy atoms of the given standard amino acid, the backbone atoms
+/// (N, CA, C, O) followed by the side chain atoms, without the terminal OXT
+pub fn amino_acid_atoms(name: &str) -> Option<&'static [&'static str]> {
+    AMINO_ACID_ATOMS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, atoms)| *atoms)
+}
+
+/// Returns the name of the atom at the same position in the side chain of isosteric amino
+/// acids, like OG of serine and SG of cysteine, if there is one
+pub fn equivalent_atom_name(name: &str) -> Option<&'static str> {
+    EQUIVALENT_ATOM_NAMES.iter().find_map(|(a, b)| {
+        if *a == name {
+            Some(*b)
+        } else if *b == name {
+            Some(*a)
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the name of the given heavy atom in the nomenclature of the target version of the
+/// PDB format, if it was renamed between versions 2.3 and 3 (like O1P and OP1)
+pub fn renamed_atom(residue: &str, name: &str, target: FormatVersion) -> Option<&'static str> {
+    RENAMED_ATOMS
+        .iter()
+        .filter(|(residues, _, _)| residues.is_empty() || residues.contains(&residue))
+        .find_map(|(_, old, new)| match target {
+            FormatVersion::V3 if *old == name => Some(*new),
+            FormatVersion::V2 if *new == name => Some(*old),
+            _ => None,
+        })
+}
+
+/// Returns the maximal solvent accessible surface area in Aͦ² of the given amino acid, the
+/// theoretical values of Tien et al. (2013), if it is a standard amino acid
+pub fn max_sasa(name: &str) -> Option<f64> {
+    MAX_SASA
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, area)| *area)
+}
+
+/// Returns the Kyte–Doolittle hydropathy of the amino acid with the given one letter code
+pub fn hydropathy(code: char) -> Option<f64> {
+    HYDROPATHY
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, value)| *value)
+}
+
 /// Returns if the given atom name is a name for an atom in the backbone of a protein
 pub fn is_backbone(name: impl AsRef<str>) -> bool {
     BACKBONE_NAMES.contains(&name.as_ref())
 }
 
+/// Returns the one letter code for the given (uppercase) residue name of a standard amino acid
+/// or nucleotide
+pub fn one_letter_code(name: &str) -> Option<char> {
+    ONE_LETTER_CODES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, code)| *code)
+}
+
 /// Returns if the given number is a valid remark-type-number (according to wwPDB v 3.30)
 pub fn valid_remark_type_number(number: usize) -> bool {
     REMARK_TYPES.contains(&number)
@@ -78,6 +224,455 @@ const AMINO_ACIDS: &[&str] = &[
     "TRP", "TYR", "VAL", "SEC", "PYL",
 ];
 
+/// The residue names commonly used for water, including heavy water and simulation water models
+const WATERS: &[&str] = &["HOH", "WAT", "H2O", "DOD", "SOL", "TIP", "TIP3", "SPC"];
+
+/// All standard ribonucleotides and deoxyribonucleotides, including inosine
+const NUCLEOTIDES: &[&str] = &[
+    "A", "C", "G", "I", "U", "T", "DA", "DC", "DG", "DI", "DT", "DU",
+];
+
+/// Common monosaccharides in glycans, by their PDB chemical component identifier. Includes both
+/// anomers of the hexoses (GlcNAc, GalNAc, mannose, glucose, galactose), fucose, xylose,
+/// glucuronic and iduronic acid, the sialic acids, and KDN.
+const SUGARS: &[&str] = &[
+    "NAG", "NDG", "A2G", "NGA", "MAN", "BMA", "GLC", "BGC", "GAL", "GLA", "FUC", "FUL", "XYS",
+    "XYP", "GCU", "BDP", "IDR", "SIA", "SLB", "NGC", "NGE", "KDN",
+];
+
+/// The aromatic rings of the aromatic amino acids and nucleotides, with the residue names
+/// sharing the rings
+#[allow(clippy::type_complexity)]
+const AROMATIC_RINGS: &[(&[&str], &[&[&str]])] = &[
+    (
+        &["PHE", "TYR"],
+        &[&["CG", "CD1", "CE1", "CZ", "CE2", "CD2"]],
+    ),
+    (
+        &["TRP"],
+        &[
+            &["CG", "CD1", "NE1", "CE2", "CD2"],
+            &["CD2", "CE2", "CZ2", "CH2", "CZ3", "CE3"],
+        ],
+    ),
+    (
+        &["HIS", "HID", "HIE", "HIP"],
+        &[&["CG", "ND1", "CE1", "NE2", "CD2"]],
+    ),
+    (
+        &["A", "G", "I", "DA", "DG", "DI"],
+        &[
+            &["N1", "C2", "N3", "C4", "C5", "C6"],
+            &["C4", "C5", "N7", "C8", "N9"],
+        ],
+    ),
+    (
+        &["C", "T", "U", "DC", "DT", "DU"],
+        &[&["N1", "C2", "N3", "C4", "C5", "C6"]],
+    ),
+];
+
+/// The common coordination numbers (lowest and highest) of metals in biological structures
+const COORDINATION_NUMBERS: &[(Element, (usize, usize))] = &[
+    (Element::Na, (4, 7)),
+    (Element::Mg, (4, 6)),
+    (Element::K, (5, 9)),
+    (Element::Ca, (5, 8)),
+    (Element::Mn, (4, 6)),
+    (Element::Fe, (4, 6)),
+    (Element::Co, (4, 6)),
+    (Element::Ni, (4, 6)),
+    (Element::Cu, (2, 6)),
+    (Element::Zn, (3, 6)),
+    (Element::Cd, (4, 7)),
+    (Element::Hg, (2, 4)),
+];
+
+/// The one letter codes of the amino acids (including the Amber-specific names) and nucleotides
+const ONE_LETTER_CODES: &[(&str, char)] = &[
+    ("ALA", 'A'),
+    ("ARG", 'R'),
+    ("ASH", 'D'),
+    ("ASN", 'N'),
+    ("ASP", 'D'),
+    ("ASX", 'B'),
+    ("CYS", 'C'),
+    ("CYX", 'C'),
+    ("GLH", 'E'),
+    ("GLN", 'Q'),
+    ("GLU", 'E'),
+    ("GLX", 'Z'),
+    ("GLY", 'G'),
+    ("HID", 'H'),
+    ("HIE", 'H'),
+    ("HIP", 'H'),
+    ("HIS", 'H'),
+    ("ILE", 'I'),
+    ("LEU", 'L'),
+    ("LYN", 'K'),
+    ("LYS", 'K'),
+    ("MET", 'M'),
+    ("PHE", 'F'),
+    ("PRO", 'P'),
+    ("SER", 'S'),
+    ("THR", 'T'),
+    ("TRP", 'W'),
+    ("TYR", 'Y'),
+    ("VAL", 'V'),
+    ("SEC", 'U'),
+    ("PYL", 'O'),
+    ("UNK", 'X'),
+    ("DA", 'A'),
+    ("DC", 'C'),
+    ("DG", 'G'),
+    ("DT", 'T'),
+    ("DU", 'U'),
+    ("DI", 'I'),
+];
+
+/// The favored regions of the Ramachandran plot for amino acids other than glycine and proline:
+/// the beta sheet region, the right handed alpha helix region, and the left handed helix region.
+/// These are rectangular approximations of the contours of Lovell et al. (2003).
+const RAMACHANDRAN_GENERAL_FAVORED: &[[f64; 4]] = &[
+    [-180.0, -45.0, 100.0, 180.0],
+    [-180.0, -45.0, -180.0, -170.0],
+    [-160.0, -40.0, -80.0, 15.0],
+    [45.0, 90.0, 0.0, 80.0],
+];
+
+/// The allowed regions of the Ramachandran plot for amino acids other than glycine and proline
+const RAMACHANDRAN_GENERAL_ALLOWED: &[[f64; 4]] = &[
+    [-180.0, -30.0, -110.0, 180.0],
+    [-180.0, -30.0, -180.0, -155.0],
+    [30.0, 110.0, -40.0, 100.0],
+];
+
+/// The favored regions of the Ramachandran plot for glycine, which is symmetric as glycine has
+/// no side chain
+const RAMACHANDRAN_GLYCINE_FAVORED: &[[f64; 4]] = &[
+    [-110.0, -45.0, -70.0, 30.0],
+    [45.0, 110.0, -30.0, 70.0],
+    [-180.0, -60.0, 110.0, 180.0],
+    [-180.0, -60.0, -180.0, -150.0],
+    [60.0, 180.0, 150.0, 180.0],
+    [60.0, 180.0, -180.0, -110.0],
+];
+
+/// The allowed regions of the Ramachandran plot for glycine
+const RAMACHANDRAN_GLYCINE_ALLOWED: &[[f64; 4]] =
+    &[[-180.0, -30.0, -180.0, 180.0], [30.0, 180.0, -180.0, 180.0]];
+
+/// The favored regions of the Ramachandran plot for proline, which has its phi angle restricted
+/// by the pyrrolidine ring
+const RAMACHANDRAN_PROLINE_FAVORED: &[[f64; 4]] =
+    &[[-95.0, -45.0, -60.0, -10.0], [-95.0, -45.0, 110.0, 180.0]];
+
+/// The allowed regions of the Ramachandran plot for proline
+const RAMACHANDRAN_PROLINE_ALLOWED: &[[f64; 4]] = &[
+    [-110.0, -35.0, -80.0, 180.0],
+    [-110.0, -35.0, -180.0, -160.0],
+];
+
+/// The atoms defining the side chain torsion angles of the amino acids, with the residue names
+/// sharing the definitions
+#[allow(clippy::type_complexity)]
+const SIDE_CHAIN_TORSIONS: &[(&[&str], &[[&str; 4]])] = &[
+    (
+        &["ARG"],
+        &[
+            ["N", "CA", "CB", "CG"],
+            ["CA", "CB", "CG", "CD"],
+            ["CB", "CG", "CD", "NE"],
+            ["CG", "CD", "NE", "CZ"],
+        ],
+    ),
+    (
+        &["ASN", "ASP", "ASH"],
+        &[["N", "CA", "CB", "CG"], ["CA", "CB", "CG", "OD1"]],
+    ),
+    (&["CYS", "CYX"], &[["N", "CA", "CB", "SG"]]),
+    (
+        &["GLN", "GLU", "GLH"],
+        &[
+            ["N", "CA", "CB", "CG"],
+            ["CA", "CB", "CG", "CD"],
+            ["CB", "CG", "CD", "OE1"],
+        ],
+    ),
+    (
+        &["HIS", "HID", "HIE", "HIP"],
+        &[["N", "CA", "CB", "CG"], ["CA", "CB", "CG", "ND1"]],
+    ),
+    (
+        &["ILE"],
+        &[["N", "CA", "CB", "CG1"], ["CA", "CB", "CG1", "CD1"]],
+    ),
+    (
+        &["LEU"],
+        &[["N", "CA", "CB", "CG"], ["CA", "CB", "CG", "CD1"]],
+    ),
+    (
+        &["LYS", "LYN"],
+        &[
+            ["N", "CA", "CB", "CG"],
+            ["CA", "CB", "CG", "CD"],
+            ["CB", "CG", "CD", "CE"],
+            ["CG", "CD", "CE", "NZ"],
+        ],
+    ),
+    (
+        &["MET"],
+        &[
+            ["N", "CA", "CB", "CG"],
+            ["CA", "CB", "CG", "SD"],
+            ["CB", "CG", "SD", "CE"],
+        ],
+    ),
+    (
+        &["PHE", "TYR", "TRP", "PRO"],
+        &[["N", "CA", "CB", "CG"], ["CA", "CB", "CG", "CD1"]],
+    ),
+    (&["SER"], &[["N", "CA", "CB", "OG"]]),
+    (&["THR"], &[["N", "CA", "CB", "OG1"]]),
+    (&["VAL"], &[["N", "CA", "CB", "CG1"]]),
+];
+
+/// A compact rotamer library: the most common rotamers of the amino acids with their modal chi1
+/// and chi2 angles, after the penultimate rotamer library of Lovell et al. (2000). The chi3 and
+/// chi4 angles of the long side chains are not included.
+#[allow(clippy::type_complexity)]
+const ROTAMERS: &[(&[&str], &[(&str, &[f64])])] = &[
+    (
+        &["SER", "CYS", "CYX"],
+        &[("p", &[62.0]), ("t", &[-177.0]), ("m", &[-65.0])],
+    ),
+    (
+        &["THR"],
+        &[("p", &[59.0]), ("t", &[-171.0]), ("m", &[-60.0])],
+    ),
+    (
+        &["VAL"],
+        &[("p", &[63.0]), ("t", &[175.0]), ("m", &[-60.0])],
+    ),
+    (
+        &["PRO"],
+        &[("Cg_endo", &[30.0, -35.0]), ("Cg_exo", &[-30.0, 40.0])],
+    ),
+    (
+        &["LEU"],
+        &[
+            ("pp", &[62.0, 80.0]),
+            ("tp", &[-177.0, 65.0]),
+            ("tt", &[-172.0, 145.0]),
+            ("mp", &[-85.0, 65.0]),
+            ("mt", &[-65.0, 175.0]),
+        ],
+    ),
+    (
+        &["ILE"],
+        &[
+            ("pp", &[62.0, 100.0]),
+            ("pt", &[62.0, 170.0]),
+            ("tp", &[-177.0, 66.0]),
+            ("tt", &[-177.0, 165.0]),
+            ("mp", &[-65.0, 100.0]),
+            ("mt", &[-65.0, 170.0]),
+            ("mm", &[-57.0, -60.0]),
+        ],
+    ),
+    (
+        &["PHE", "TYR"],
+        &[
+            ("p90", &[62.0, 90.0]),
+            ("t80", &[-177.0, 80.0]),
+            ("m-85", &[-65.0, -85.0]),
+            ("m-30", &[-65.0, -30.0]),
+        ],
+    ),
+    (
+        &["TRP"],
+        &[
+            ("p-90", &[62.0, -90.0]),
+            ("p90", &[62.0, 90.0]),
+            ("t-105", &[-177.0, -105.0]),
+            ("t90", &[-177.0, 90.0]),
+            ("m-90", &[-65.0, -90.0]),
+            ("m0", &[-65.0, -5.0]),
+            ("m95", &[-65.0, 95.0]),
+        ],
+    ),
+    (
+        &["HIS", "HID", "HIE", "HIP"],
+        &[
+            ("p-80", &[62.0, -75.0]),
+            ("p80", &[62.0, 80.0]),
+            ("t-160", &[-177.0, -160.0]),
+            ("t-80", &[-177.0, -80.0]),
+            ("t60", &[-177.0, 60.0]),
+            ("m-70", &[-65.0, -70.0]),
+            ("m170", &[-65.0, 165.0]),
+            ("m80", &[-65.0, 80.0]),
+        ],
+    ),
+    (
+        &["ASN"],
+        &[
+            ("p-10", &[62.0, -10.0]),
+            ("p30", &[62.0, 30.0]),
+            ("t-20", &[-174.0, -20.0]),
+            ("t30", &[-177.0, 30.0]),
+            ("m-20", &[-65.0, -20.0]),
+            ("m-80", &[-65.0, -75.0]),
+            ("m120", &[-65.0, 120.0]),
+        ],
+    ),
+    (
+        &["ASP", "ASH"],
+        &[
+            ("p-10", &[62.0, -10.0]),
+            ("p30", &[62.0, 30.0]),
+            ("t0", &[-177.0, 0.0]),
+            ("t70", &[-177.0, 65.0]),
+            ("m-20", &[-70.0, -15.0]),
+        ],
+    ),
+    (
+        &["MET", "GLU", "GLH", "GLN", "LYS", "LYN", "ARG"],
+        &[
+            ("pt", &[62.0, 180.0]),
+            ("tp", &[-177.0, 65.0]),
+            ("tt", &[-177.0, 180.0]),
+            ("mp", &[-65.0, 85.0]),
+            ("mt", &[-65.0, 180.0]),
+            ("mm", &[-65.0, -65.0]),
+        ],
+    ),
+];
+
+/// The amino acids with a chi2 angle only defined modulo 180 degrees
+const SYMMETRIC_SIDE_CHAINS: &[&str] = &["ASP", "ASH", "PHE", "TYR"];
+
+/// The amino acids with a charged side chain at neutral pH, all other amino acids are neutral
+const AMINO_ACID_CHARGES: &[(&str, isize)] =
+    &[("ARG", 1), ("LYS", 1), ("HIP", 1), ("ASP", -1), ("GLU", -1)];
+
+/// The heavy atoms of the standard amino acids, see [`amino_acid_atoms`]
+const AMINO_ACID_ATOMS: &[(&str, &[&str])] = &[
+    ("ALA", &["N", "CA", "C", "O", "CB"]),
+    (
+        "ARG",
+        &[
+            "N", "CA", "C", "O", "CB", "CG", "CD", "NE", "CZ", "NH1", "NH2",
+        ],
+    ),
+    ("ASN", &["N", "CA", "C", "O", "CB", "CG", "OD1", "ND2"]),
+    ("ASP", &["N", "CA", "C", "O", "CB", "CG", "OD1", "OD2"]),
+    ("CYS", &["N", "CA", "C", "O", "CB", "SG"]),
+    (
+        "GLN",
+        &["N", "CA", "C", "O", "CB", "CG", "CD", "OE1", "NE2"],
+    ),
+    (
+        "GLU",
+        &["N", "CA", "C", "O", "CB", "CG", "CD", "OE1", "OE2"],
+    ),
+    ("GLY", &["N", "CA", "C", "O"]),
+    (
+        "HIS",
+        &["N", "CA", "C", "O", "CB", "CG", "ND1", "CD2", "CE1", "NE2"],
+    ),
+    ("ILE", &["N", "CA", "C", "O", "CB", "CG1", "CG2", "CD1"]),
+    ("LEU", &["N", "CA", "C", "O", "CB", "CG", "CD1", "CD2"]),
+    ("LYS", &["N", "CA", "C", "O", "CB", "CG", "CD", "CE", "NZ"]),
+    ("MET", &["N", "CA", "C", "O", "CB", "CG", "SD", "CE"]),
+    (
+        "PHE",
+        &[
+            "N", "CA", "C", "O", "CB", "CG", "CD1", "CD2", "CE1", "CE2", "CZ",
+        ],
+    ),
+    ("PRO", &["N", "CA", "C", "O", "CB", "CG", "CD"]),
+    ("SER", &["N", "CA", "C", "O", "CB", "OG"]),
+    ("THR", &["N", "CA", "C", "O", "CB", "OG1", "CG2"]),
+    (
+        "TRP",
+        &[
+            "N", "CA", "C", "O", "CB", "CG", "CD1", "CD2", "NE1", "CE2", "CE3", "CZ2", "CZ3", "CH2",
+        ],
+    ),
+    (
+        "TYR",
+        &[
+            "N", "CA", "C", "O", "CB", "CG", "CD1", "CD2", "CE1", "CE2", "CZ", "OH",
+        ],
+    ),
+    ("VAL", &["N", "CA", "C", "O", "CB", "CG1", "CG2"]),
+];
+
+/// Pairs of atoms at the same position in the side chains of isosteric amino acids (Ser/Cys,
+/// Thr/Val, Asp/Asn, Glu/Gln), see [`equivalent_atom_name`]
+const EQUIVALENT_ATOM_NAMES: &[(&str, &str)] =
+    &[("OG", "SG"), ("OG1", "CG1"), ("OD2", "ND2"), ("OE2", "NE2")];
+
+/// The heavy atoms renamed in version 3 of the PDB format: the residues the name is used in (all
+/// residues if empty), the version 2.3 name, and the version 3 name, see [`renamed_atom`]
+const RENAMED_ATOMS: &[(&[&str], &str, &str)] = &[
+    (&[], "O1P", "OP1"),
+    (&[], "O2P", "OP2"),
+    (&[], "O3P", "OP3"),
+    (&["T", "DT"], "C5M", "C7"),
+    (&["ILE"], "CD", "CD1"),
+];
+
+/// The theoretical maximal solvent accessible surface areas of the amino acids in Aͦ² in a
+/// Gly-X-Gly tripeptide, from Tien et al. (2013)
+const MAX_SASA: &[(&str, f64)] = &[
+    ("ALA", 129.0),
+    ("ARG", 274.0),
+    ("ASN", 195.0),
+    ("ASP", 193.0),
+    ("CYS", 167.0),
+    ("GLN", 225.0),
+    ("GLU", 223.0),
+    ("GLY", 104.0),
+    ("HIS", 224.0),
+    ("ILE", 197.0),
+    ("LEU", 201.0),
+    ("LYS", 236.0),
+    ("MET", 224.0),
+    ("PHE", 240.0),
+    ("PRO", 159.0),
+    ("SER", 155.0),
+    ("THR", 172.0),
+    ("TRP", 285.0),
+    ("TYR", 263.0),
+    ("VAL", 174.0),
+];
+
+/// The hydropathy scale of Kyte and Doolittle (1982) for the standard amino acids
+const HYDROPATHY: &[(char, f64)] = &[
+    ('I', 4.5),
+    ('V', 4.2),
+    ('L', 3.8),
+    ('F', 2.8),
+    ('C', 2.5),
+    ('M', 1.9),
+    ('A', 1.8),
+    ('G', -0.4),
+    ('T', -0.7),
+    ('S', -0.8),
+    ('W', -0.9),
+    ('Y', -1.3),
+    ('P', -1.6),
+    ('H', -3.2),
+    ('E', -3.5),
+    ('Q', -3.5),
+    ('D', -3.5),
+    ('N', -3.5),
+    ('K', -3.9),
+    ('R', -4.5),
+];
+
 /// The names of atom in the backbone of proteins
 const BACKBONE_NAMES: &[&str] = &[
     "N", "CA", "C", "O", "H", "H1", "H2", "H3", "HA", "HA2", "HA3",
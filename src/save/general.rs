@@ -3,7 +3,7 @@ use flate2::Compression;
 use super::*;
 use crate::structs::PDB;
 use crate::StrictnessLevel;
-use crate::{check_extension, error::*};
+use crate::{check_extension, error::*, ReadOptions};
 
 /// Save the given PDB struct to the given file, validating it beforehand.
 /// If validation gives rise to problems, use the `save_raw` function. The correct file
@@ -73,3 +73,43 @@ pub fn save_gz(
         )])
     }
 }
+
+/// Convert the given input file to the given output file. The formats of both files (pdb or
+/// mmCIF/PDBx, optionally gzipped) are determined based on their file extensions. The input is
+/// read with the given options and the output is validated with the strictness level from these
+/// options.
+///
+/// # Errors
+/// Fails if the input could not be read, or if the output could not be validated or saved. On
+/// success it returns all errors/warnings found while reading the input.
+///
+/// # Example
+/// ```rust,no_run
+/// use pdbtbx::*;
+/// let warnings = convert(
+///     "example-pdbs/1ubq.pdb",
+///     "1ubq.cif.gz",
+///     ReadOptions::new().set_level(StrictnessLevel::Loose),
+/// )
+/// .unwrap();
+/// ```
+pub fn convert(
+    input: impl AsRef<str>,
+    output: impl AsRef<str>,
+    options: &ReadOptions,
+) -> Result<Vec<PDBError>, Vec<PDBError>> {
+    let (pdb, mut errors) = options.read(input)?;
+    let output = output.as_ref();
+    let result = if check_extension(output, "gz") {
+        save_gz(&pdb, output, options.level, None)
+    } else {
+        save(&pdb, output, options.level)
+    };
+    match result {
+        Ok(()) => Ok(errors),
+        Err(e) => {
+            errors.extend(e);
+            Err(errors)
+        }
+    }
+}
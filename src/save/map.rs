@@ -0,0 +1,102 @@
+use crate::error::*;
+use crate::geometry::DensityGrid;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// The length of the header of a CCP4/MRC map in bytes
+const HEADER_LENGTH: usize = 1024;
+/// The length of a label in the header of a CCP4/MRC map in bytes
+const LABEL_LENGTH: usize = 80;
+
+/// Save the given density grid to the given file as a CCP4/MRC map (mode 2, 32 bit floats),
+/// see [`crate::geometry::density_grid`].
+///
+/// # Errors
+/// It fails if the file could not be opened.
+pub fn save_map(grid: &DensityGrid, filename: impl AsRef<str>) -> Result<(), PDBError> {
+    let filename = filename.as_ref();
+    let file = File::create(filename).map_err(|_| {
+        PDBError::new(
+            ErrorLevel::BreakingError,
+            "Could not open file",
+            "Could not open the file for writing, make sure you have permission for this file and no other program is currently using it.",
+            Context::show(filename),
+        )
+    })?;
+    save_map_raw(grid, BufWriter::new(file));
+    Ok(())
+}
+
+/// Save the given density grid to the given BufWriter as a CCP4/MRC map (mode 2, 32 bit floats).
+/// The map is written in little endian byte order with x as the fastest changing axis. The
+/// position of the grid is given by the start indices in the header, as its origin is a
+/// multiple of the spacing.
+#[allow(
+    clippy::unwrap_used,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss
+)]
+pub fn save_map_raw<T: Write>(grid: &DensityGrid, mut sink: BufWriter<T>) {
+    let (nx, ny, nz) = grid.dimensions();
+    let (origin, spacing) = (grid.origin(), grid.spacing());
+    let values = grid.as_slice();
+    let count = values.len().max(1) as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let rms = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count).sqrt();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let mut header = Vec::with_capacity(HEADER_LENGTH);
+    let int = |header: &mut Vec<u8>, value: i32| header.extend(value.to_le_bytes());
+    let float = |header: &mut Vec<u8>, value: f64| header.extend((value as f32).to_le_bytes());
+    // Number of columns, rows, and sections, and the mode (32 bit floats)
+    for size in [nx, ny, nz] {
+        int(&mut header, size as i32);
+    }
+    int(&mut header, 2);
+    // The start of the grid in grid points
+    for start in [origin.0, origin.1, origin.2] {
+        int(&mut header, (start / spacing).round() as i32);
+    }
+    // The sampling along the unit cell and the dimensions of the unit cell
+    for size in [nx, ny, nz] {
+        int(&mut header, size as i32);
+    }
+    for size in [nx, ny, nz] {
+        float(&mut header, size as f64 * spacing);
+    }
+    for _ in 0..3 {
+        float(&mut header, 90.0);
+    }
+    // The axes of the columns, rows, and sections
+    for axis in 1..=3 {
+        int(&mut header, axis);
+    }
+    for value in [min, max, mean] {
+        float(&mut header, value);
+    }
+    // Space group P1 and no extended header
+    int(&mut header, 1);
+    int(&mut header, 0);
+    // Unused, except for the MRC2014 format version at word 28
+    for word in 25..=52 {
+        int(&mut header, if word == 28 { 20140 } else { 0 });
+    }
+    header.extend(b"MAP ");
+    // Machine stamp for little endian
+    header.extend([0x44, 0x41, 0x00, 0x00]);
+    float(&mut header, rms);
+    int(&mut header, 1);
+    let mut label = format!("Created by pdbtbx {}", env!("CARGO_PKG_VERSION")).into_bytes();
+    label.resize(LABEL_LENGTH, b' ');
+    header.extend(label);
+    header.resize(HEADER_LENGTH, 0);
+
+    sink.write_all(&header).unwrap();
+    for value in values {
+        sink.write_all(&(*value as f32).to_le_bytes()).unwrap();
+    }
+    sink.flush().unwrap();
+}
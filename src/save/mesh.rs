@@ -0,0 +1,89 @@
+use crate::check_extension;
+use crate::error::*;
+use crate::geometry::Mesh;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Save the given mesh to the given file, as a Wavefront OBJ file or an ASCII PLY file based on
+/// the extension of the file (`.obj` or `.ply`), see [`crate::geometry::surface_mesh`].
+///
+/// # Errors
+/// It fails if the extension is not known or if the file could not be opened.
+pub fn save_mesh(mesh: &Mesh, filename: impl AsRef<str>) -> Result<(), PDBError> {
+    let filename = filename.as_ref();
+    let obj = check_extension(filename, "obj");
+    if !obj && !check_extension(filename, "ply") {
+        return Err(PDBError::new(
+            ErrorLevel::BreakingError,
+            "Incorrect extension",
+            "Could not determine the type of the given file, make it .obj or .ply",
+            Context::show(filename),
+        ));
+    }
+    let file = File::create(filename).map_err(|_| {
+        PDBError::new(
+            ErrorLevel::BreakingError,
+            "Could not open file",
+            "Could not open the file for writing, make sure you have permission for this file and no other program is currently using it.",
+            Context::show(filename),
+        )
+    })?;
+    if obj {
+        save_obj_raw(mesh, BufWriter::new(file));
+    } else {
+        save_ply_raw(mesh, BufWriter::new(file));
+    }
+    Ok(())
+}
+
+/// Save the given mesh to the given BufWriter as a Wavefront OBJ file, with vertex normals.
+#[allow(clippy::unwrap_used)]
+pub fn save_obj_raw<T: Write>(mesh: &Mesh, mut sink: BufWriter<T>) {
+    writeln!(sink, "# Created by pdbtbx {}", env!("CARGO_PKG_VERSION")).unwrap();
+    for (x, y, z) in &mesh.vertices {
+        writeln!(sink, "v {x:.3} {y:.3} {z:.3}").unwrap();
+    }
+    for (x, y, z) in &mesh.normals {
+        writeln!(sink, "vn {x:.4} {y:.4} {z:.4}").unwrap();
+    }
+    // The indices are one based, and every vertex has its own normal
+    for [a, b, c] in &mesh.triangles {
+        let (a, b, c) = (a + 1, b + 1, c + 1);
+        writeln!(sink, "f {a}//{a} {b}//{b} {c}//{c}").unwrap();
+    }
+    sink.flush().unwrap();
+}
+
+/// Save the given mesh to the given BufWriter as an ASCII PLY file, with vertex normals.
+#[allow(clippy::unwrap_used)]
+pub fn save_ply_raw<T: Write>(mesh: &Mesh, mut sink: BufWriter<T>) {
+    write!(
+        sink,
+        "ply
+format ascii 1.0
+comment Created by pdbtbx {}
+element vertex {}
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+element face {}
+property list uchar int vertex_indices
+end_header
+",
+        env!("CARGO_PKG_VERSION"),
+        mesh.vertices.len(),
+        mesh.triangles.len()
+    )
+    .unwrap();
+    for ((x, y, z), (nx, ny, nz)) in mesh.vertices.iter().zip(&mesh.normals) {
+        writeln!(sink, "{x:.3} {y:.3} {z:.3} {nx:.4} {ny:.4} {nz:.4}").unwrap();
+    }
+    for [a, b, c] in &mesh.triangles {
+        writeln!(sink, "3 {a} {b} {c}").unwrap();
+    }
+    sink.flush().unwrap();
+}
@@ -6,6 +6,7 @@ use crate::structs::*;
 use crate::validate;
 use crate::StrictnessLevel;
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
@@ -260,6 +261,44 @@ _symmetry.Int_Tables_number                {}",
         );
     }
 
+    if pdb.entity_count() > 0 {
+        write!(
+            "# Entity definitions
+loop_
+_entity.id
+_entity.type
+_entity.pdbx_description"
+        );
+        for entity in pdb.entities() {
+            write!(
+                "{} polymer {}",
+                entity.id,
+                quote(entity.description.as_deref().unwrap_or("?"))
+            );
+        }
+        write!(
+            "loop_
+_entity_poly.entity_id
+_entity_poly.pdbx_strand_id"
+        );
+        for entity in pdb.entities() {
+            write!("{} {}", entity.id, quote(&entity.chains.join(",")));
+        }
+        if pdb.entities().any(|e| !e.sequence.is_empty()) {
+            write!(
+                "loop_
+_entity_poly_seq.entity_id
+_entity_poly_seq.num
+_entity_poly_seq.mon_id"
+            );
+            for entity in pdb.entities() {
+                for (index, name) in entity.sequence.iter().enumerate() {
+                    write!("{} {} {}", entity.id, index + 1, name);
+                }
+            }
+        }
+    }
+
     let anisou = pdb
         .atoms()
         .any(|a| a.anisotropic_temperature_factors().is_some());
@@ -302,10 +341,22 @@ _atom_site.aniso_U[3][3]"
 
     let mut lines = Vec::new();
 
+    // Chains which are not part of an entity get their own entity ID, consistent over all models
+    let mut entity_ids: HashMap<&str, usize> = HashMap::new();
+    let mut next_entity_id = pdb.entities().map(|e| e.id).max().unwrap_or(0);
     for model in pdb.models() {
         let mut chain_index = 0;
         for chain in model.chains() {
             chain_index += 1;
+            let entity_id = *entity_ids.entry(chain.id()).or_insert_with(|| {
+                pdb.chain_entity(chain.id()).map_or_else(
+                    || {
+                        next_entity_id += 1;
+                        next_entity_id
+                    },
+                    |e| e.id,
+                )
+            });
             for (residue_index, residue) in chain.residues().enumerate() {
                 for conformer in residue.conformers() {
                     for atom in conformer.atoms() {
@@ -320,7 +371,7 @@ _atom_site.aniso_U[3][3]"
                             conformer.name().to_string(), // Residue name
                             number_to_base26(chain_index), // Label Chain name, defined to be without gaps
                             chain.id().to_string(),        // Auth Chain name
-                            chain_index.to_string(),       // Entity ID, using chain serial number
+                            entity_id.to_string(),         // Entity ID
                             (residue_index + 1).to_string(), // `label_seq_id` defined to be [1-N] where N is the index
                             residue.serial_number().to_string(), // Residue serial number
                             residue.insertion_code().unwrap_or(".").to_string(), // Insertion code
@@ -400,6 +451,23 @@ _atom_site.aniso_U[3][3]"
     sink.flush().unwrap();
 }
 
+/// Quote the given text if needed to be a single value in a CIF file
+fn quote(text: &str) -> String {
+    if text.is_empty() {
+        "?".to_string()
+    } else if text.contains(char::is_whitespace)
+        || text.starts_with(['_', '#', '$', '\'', '"', '[', ']', ';'])
+    {
+        if text.contains('\'') {
+            format!("\"{text}\"")
+        } else {
+            format!("'{text}'")
+        }
+    } else {
+        text.to_string()
+    }
+}
+
 /// Print a floating point with at least 1 decimal place and at max 5 decimals
 #[allow(clippy::cast_possible_truncation)]
 fn print_float(num: f64) -> String {
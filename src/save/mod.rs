@@ -1,10 +1,23 @@
 /// Give a high level interface for users
 mod general;
+/// Save density grids as CCP4/MRC maps
+mod map;
+/// Save triangle meshes as OBJ or PLY files
+mod mesh;
 /// Save mmCIF/PDBx files
 mod mmcif;
 /// Save PDB files
 mod pdb;
+/// Options for writing files
+mod write_options;
+/// Write PDB files record-by-record
+mod writer;
 
-pub use general::{save, save_gz};
+pub use general::{convert, save, save_gz};
+pub use map::{save_map, save_map_raw};
+pub use mesh::{save_mesh, save_obj_raw, save_ply_raw};
 pub use mmcif::{save_mmcif, save_mmcif_gz, save_mmcif_raw};
+pub(crate) use pdb::atom_line;
 pub use pdb::{save_pdb, save_pdb_gz, save_pdb_raw};
+pub use write_options::{SerialOverflow, TerPlacement, WriteOptions};
+pub use writer::PDBWriter;
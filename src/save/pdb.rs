@@ -9,9 +9,13 @@ use std::iter;
 
 use std::io::Write;
 
+use rustc_hash::FxHashMap;
+
 use crate::PDB;
 use crate::{validate, validate_pdb, Context, ErrorLevel, PDBError};
 
+use super::write_options::{SerialOverflow, TerPlacement, WriteOptions};
+
 #[cfg(feature = "compression")]
 use flate2::{write::GzEncoder, Compression};
 
@@ -29,7 +33,16 @@ pub fn save_pdb(
     filename: impl AsRef<str>,
     level: StrictnessLevel,
 ) -> Result<(), Vec<PDBError>> {
-    save_pdb_(pdb, filename, level, BufWriter::new)
+    save_pdb_with_options(pdb, filename, WriteOptions::new().set_level(level))
+}
+
+/// Save the given PDB struct to the given file with the given options, validating it beforehand.
+pub(crate) fn save_pdb_with_options(
+    pdb: &PDB,
+    filename: impl AsRef<str>,
+    options: &WriteOptions,
+) -> Result<(), Vec<PDBError>> {
+    save_pdb_(pdb, filename, options, BufWriter::new)
 }
 
 /// Save the given PDB struct to the given file, validating it beforehand, and use gzip compression.
@@ -49,20 +62,25 @@ pub fn save_pdb_gz(
     level: StrictnessLevel,
     compression_level: Option<Compression>,
 ) -> Result<(), Vec<PDBError>> {
-    save_pdb_(pdb, filename, level, |file| {
-        let encoder = match compression_level {
-            Some(level) => GzEncoder::new(file, level),
-            None => GzEncoder::new(file, Compression::default()),
-        };
-        BufWriter::new(encoder)
-    })
+    save_pdb_(
+        pdb,
+        filename,
+        WriteOptions::new().set_level(level),
+        |file| {
+            let encoder = match compression_level {
+                Some(level) => GzEncoder::new(file, level),
+                None => GzEncoder::new(file, Compression::default()),
+            };
+            BufWriter::new(encoder)
+        },
+    )
 }
 
 /// Generic function to save the given PDB struct to the given file, validating it beforehand.
 fn save_pdb_<T, W>(
     pdb: &PDB,
     filename: impl AsRef<str>,
-    level: StrictnessLevel,
+    options: &WriteOptions,
     writer: W,
 ) -> Result<(), Vec<PDBError>>
 where
@@ -75,7 +93,7 @@ where
     let mut errors = validate(pdb);
     errors.extend(validate_pdb(pdb));
     for error in &errors {
-        if error.fails(level) {
+        if error.fails(options.level) {
             return Err(errors);
         }
     }
@@ -97,11 +115,730 @@ where
     let writer = writer(file);
 
     // Now call the writer function
-    save_pdb_raw(pdb, writer, level);
+    save_pdb_raw_with_options(pdb, writer, options);
 
     Ok(())
 }
 
+/// Format the given fields into a single line. Each field is given as a width and a text, the text
+/// is right aligned in the given width (with leading zeros trimmed), a width of 0 adds the text as is.
+#[allow(clippy::unwrap_used)]
+pub(crate) fn get_line(fields: Vec<(usize, &str)>) -> String {
+    let mut line = String::with_capacity(70);
+    for (length, text) in fields {
+        if length > 0 {
+            let cell = &text[text.len() - cmp::min(length, text.len())..];
+            let trimmed = cell.trim_start_matches('0');
+            if !cell.is_empty() && trimmed.is_empty() {
+                std::fmt::write(&mut line, format_args!("{0:1$}", "0", length)).unwrap();
+            } else {
+                std::fmt::write(&mut line, format_args!("{trimmed:length$}")).unwrap();
+            }
+        } else {
+            line += text;
+        }
+    }
+    line
+}
+
+/// Pad the given line to 70 characters, unless the level is `Loose`
+pub(crate) fn pad_line(line: &mut String, level: StrictnessLevel) {
+    if level != StrictnessLevel::Loose && line.len() < 70 {
+        let dif = 70 - line.len();
+        line.reserve(dif);
+        line.extend(iter::repeat(" ").take(dif));
+    }
+}
+
+/// Get the HEADER line with the given classification, deposition date, and identifier
+pub(crate) fn header_record(classification: &str, date: &str, identifier: &str) -> String {
+    format!("HEADER    {classification:<40}{date:<9}   {identifier}")
+}
+
+/// Get the lines of a record of the title section with the given lines of text, numbering the
+/// continuation lines
+fn continued_records(record: &str, lines: Vec<String>) -> Vec<String> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| {
+            if index == 0 {
+                format!("{record:<6}    {text}")
+            } else {
+                format!("{record:<6}  {:>2} {text}", index + 1)
+            }
+        })
+        .collect()
+}
+
+/// Get the lines of a COMPND or SOURCE record with the given specification, starting every
+/// token on a new line
+fn specification_records(record: &str, specification: &str) -> Vec<String> {
+    let tokens: Vec<&str> = specification
+        .split(';')
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .collect();
+    let lines = tokens
+        .iter()
+        .enumerate()
+        .flat_map(|(index, token)| {
+            let end = if index + 1 < tokens.len() { ";" } else { "" };
+            wrap_text(&format!("{token}{end}"), 69, 69, &['-'])
+        })
+        .collect();
+    continued_records(record, lines)
+}
+
+/// Get the JRNL lines for the given citation
+fn journal_records(citation: &Citation) -> Vec<String> {
+    let record = |record: &str, lines: Vec<String>| -> Vec<String> {
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let continuation = if index == 0 {
+                    String::new()
+                } else {
+                    (index + 1).to_string()
+                };
+                format!("JRNL        {record:<4}{continuation:>2} {text}")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    };
+    let mut lines = Vec::new();
+    if !citation.authors.is_empty() {
+        lines.extend(record(
+            "AUTH",
+            wrap_text(&citation.authors.join(","), 60, 60, &[',', '-']),
+        ));
+    }
+    if let Some(title) = &citation.title {
+        lines.extend(record("TITL", wrap_text(title, 60, 60, &['-'])));
+    }
+    if let Some(journal) = &citation.journal {
+        let mut journal = wrap_text(journal, 28, 28, &['-']);
+        if citation.volume.is_some() || citation.first_page.is_some() || citation.year.is_some() {
+            journal[0] = format!(
+                "{:<28}  V.{:>4} {:>5} {:>4}",
+                journal[0],
+                citation.volume.as_deref().unwrap_or_default(),
+                citation.first_page.as_deref().unwrap_or_default(),
+                citation.year.map(|y| y.to_string()).unwrap_or_default()
+            );
+        }
+        lines.extend(record("REF", journal));
+    }
+    if let Some(reference_number) = &citation.reference_number {
+        lines.extend(record("REFN", vec![format!("{:16}{reference_number}", "")]));
+    }
+    if let Some(pubmed_id) = &citation.pubmed_id {
+        lines.extend(record("PMID", vec![pubmed_id.clone()]));
+    }
+    if let Some(doi) = &citation.doi {
+        lines.extend(record("DOI", vec![doi.clone()]));
+    }
+    lines
+}
+
+/// Get the lines of the title section (HEADER, TITLE, COMPND, SOURCE, KEYWDS, EXPDTA, AUTHOR,
+/// and JRNL) for the given identifier and metadata
+pub(crate) fn title_section_records(identifier: Option<&str>, metadata: &Metadata) -> Vec<String> {
+    let mut lines = Vec::new();
+    if identifier.is_some()
+        || metadata.classification.is_some()
+        || metadata.deposition_date.is_some()
+    {
+        lines.push(header_record(
+            metadata.classification.as_deref().unwrap_or_default(),
+            metadata.deposition_date.as_deref().unwrap_or_default(),
+            identifier.unwrap_or("    "),
+        ));
+    }
+    if let Some(title) = &metadata.title {
+        lines.extend(continued_records("TITLE", wrap_text(title, 69, 69, &['-'])));
+    }
+    if let Some(compound) = &metadata.compound {
+        lines.extend(specification_records("COMPND", compound));
+    }
+    if let Some(source) = &metadata.source {
+        lines.extend(specification_records("SOURCE", source));
+    }
+    let lists: [(&str, String, &[char]); 3] = [
+        ("KEYWDS", metadata.keywords.join(", "), &['-']),
+        ("EXPDTA", metadata.experimental_methods.join("; "), &['-']),
+        ("AUTHOR", metadata.authors.join(","), &[',', '-']),
+    ];
+    for (record, text, breaks) in lists {
+        if !text.is_empty() {
+            lines.extend(continued_records(record, wrap_text(&text, 68, 68, breaks)));
+        }
+    }
+    if let Some(citation) = &metadata.citation {
+        lines.extend(journal_records(citation));
+    }
+    lines
+}
+
+/// Get the remarks to write, with the REMARK 2 records generated from the resolution in the
+/// metadata if it is set
+fn remarks_with_resolution(pdb: &PDB) -> Vec<(usize, String)> {
+    let mut remarks: Vec<(usize, String)> = pdb.remarks().cloned().collect();
+    if let Some(resolution) = pdb.metadata().resolution {
+        remarks.retain(|(number, _)| *number != RESOLUTION_REMARK);
+        let index = remarks
+            .iter()
+            .position(|(number, _)| *number > RESOLUTION_REMARK)
+            .unwrap_or(remarks.len());
+        remarks.splice(
+            index..index,
+            [
+                (RESOLUTION_REMARK, String::new()),
+                (
+                    RESOLUTION_REMARK,
+                    format!("RESOLUTION. {resolution:>7.2} ANGSTROMS."),
+                ),
+            ],
+        );
+    }
+    remarks
+}
+
+/// Get the REMARK line with the given remark-type-number and text
+pub(crate) fn remark_record(remark_type: usize, text: &str) -> String {
+    get_line(vec![
+        (6, "REMARK"),
+        (0, " "),
+        (3, &remark_type.to_string()),
+        (0, " "),
+        (0, text),
+    ])
+}
+
+/// Get the REMARK lines saving the custom annotations of the PDB and all its Chains, Residues,
+/// and Atoms. Chains, Residues, and Atoms are identified by the index of their Model (starting
+/// at 1, as single Models are saved without MODEL records) and their IDs, see [`Annotations`].
+pub(crate) fn annotation_records(pdb: &PDB) -> Vec<String> {
+    let mut lines = pdb.annotations().remark_lines("PDB");
+    for (index, model) in pdb.models().enumerate() {
+        let model_serial = index + 1;
+        for chain in model.chains() {
+            lines.extend(
+                chain
+                    .annotations()
+                    .remark_lines(&format!("CHAIN {model_serial} {}", chain.id())),
+            );
+            for residue in chain.residues() {
+                lines.extend(residue.annotations().remark_lines(&format!(
+                    "RESIDUE {model_serial} {} {} {}",
+                    chain.id(),
+                    residue.serial_number(),
+                    residue.insertion_code().unwrap_or(".")
+                )));
+            }
+            for atom in chain.atoms() {
+                lines.extend(
+                    atom.annotations()
+                        .remark_lines(&format!("ATOM {model_serial} {}", atom.serial_number())),
+                );
+            }
+        }
+    }
+    lines
+        .iter()
+        .map(|text| remark_record(ANNOTATION_REMARK_TYPE, text))
+        .collect()
+}
+
+/// Get the CRYST1 line for the given unit cell and symmetry, with `P 1` as default symmetry
+pub(crate) fn cryst_record(unit_cell: &UnitCell, symmetry: Option<&Symmetry>) -> String {
+    let sym = if let Some(symmetry) = symmetry {
+        format!("{:10}{:3}", symmetry.herman_mauguin_symbol(), symmetry.z(),)
+    } else {
+        "P 1         1".to_string()
+    };
+    get_line(vec![
+        (6, "CRYST1"),
+        (9, &format!("{:9.3}", unit_cell.a())),
+        (9, &format!("{:9.3}", unit_cell.b())),
+        (9, &format!("{:9.3}", unit_cell.c())),
+        (7, &format!("{:7.2}", unit_cell.alpha())),
+        (7, &format!("{:7.2}", unit_cell.beta())),
+        (7, &format!("{:7.2}", unit_cell.gamma())),
+        (0, "  "),
+        (0, &sym),
+    ])
+}
+
+/// Get the TER line closing the chain with the given serial number (one more than the serial
+/// number of the last atom), residue name, chain id, and residue serial number
+pub(crate) fn ter_record(
+    atom_serial: usize,
+    residue_name: &str,
+    chain_id: &str,
+    residue_serial: isize,
+    overflow: SerialOverflow,
+) -> String {
+    get_line(vec![
+        (0, "TER   "),
+        (5, &format_atom_serial(atom_serial, overflow)),
+        (0, "      "),
+        (3, residue_name),
+        (0, " "),
+        (1, chain_id),
+        (4, &overflow.format(residue_serial, 4)),
+    ])
+}
+
+/// Get the SITE lines for the given site, with up to four residues per line
+pub(crate) fn site_records(site: &Site) -> Vec<String> {
+    let total = site.residues.len().to_string();
+    site.residues
+        .chunks(4)
+        .enumerate()
+        .map(|(index, residues)| {
+            let mut line = get_line(vec![
+                (6, "SITE"),
+                (0, " "),
+                (3, (index + 1).to_string().as_str()),
+                (0, " "),
+                (3, &site.id),
+                (0, " "),
+                (2, &total),
+            ]);
+            for residue in residues {
+                line += &get_line(vec![
+                    (0, " "),
+                    (0, &format!("{:>3}", residue.name)),
+                    (0, " "),
+                    (1, &residue.chain),
+                    (4, residue.serial_number.to_string().as_str()),
+                    (1, residue.insertion_code.as_deref().unwrap_or(" ")),
+                ]);
+            }
+            line
+        })
+        .collect()
+}
+
+/// Format the residue at the start or end of a HELIX or SHEET record, with the given separator
+/// between the chain id and the serial number
+fn boundary_residue(residue: &BoundaryResidue, separator: &str) -> String {
+    format!(
+        "{:>3} {}{separator}{:>4}{}",
+        residue.name,
+        residue.chain,
+        residue.serial_number,
+        residue.insertion_code.as_deref().unwrap_or(" ")
+    )
+}
+
+/// Get the HELIX line for the given helix
+pub(crate) fn helix_record(helix: &Helix) -> String {
+    format!(
+        "HELIX  {:>3} {:>3} {} {}{:>2}{:<30} {:>5}",
+        helix.serial_number,
+        helix.id,
+        boundary_residue(&helix.start, " "),
+        boundary_residue(&helix.end, " "),
+        helix.class,
+        helix.comment.as_deref().unwrap_or_default(),
+        helix.length
+    )
+}
+
+/// Get the SHEET lines for the given sheet, one for every strand
+pub(crate) fn sheet_records(sheet: &Sheet) -> Vec<String> {
+    sheet
+        .strands
+        .iter()
+        .map(|strand| {
+            format!(
+                "SHEET  {:>3} {:>3}{:>2} {} {}{:>2}",
+                strand.number,
+                sheet.id,
+                sheet.strands.len(),
+                boundary_residue(&strand.start, ""),
+                boundary_residue(&strand.end, ""),
+                strand.sense
+            )
+        })
+        .collect()
+}
+
+/// Split the given text into lines of at most the given widths, the first line can have a
+/// different width than the continuation lines. Lines are broken at spaces (which are dropped)
+/// or after one of the given break characters, and only if there is no such place in the words
+/// themselves.
+fn wrap_text(text: &str, first_width: usize, width: usize, breaks: &[char]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = text.trim();
+    let mut max = first_width;
+    while rest.len() > max {
+        let split = rest
+            .char_indices()
+            .take_while(|(index, c)| index + c.len_utf8() <= max + 1)
+            .filter_map(|(index, c)| match c {
+                ' ' => Some((index, index + 1)),
+                c if breaks.contains(&c) && index < max => Some((index + 1, index + 1)),
+                _ => None,
+            })
+            .last()
+            .unwrap_or((max, max));
+        lines.push(rest[..split.0].to_string());
+        rest = rest[split.1..].trim_start();
+        max = width;
+    }
+    lines.push(rest.to_string());
+    lines
+}
+
+/// Get the HET lines for the given hetero group, one for every residue
+pub(crate) fn het_records(group: &HetGroup) -> Vec<String> {
+    group
+        .residues
+        .iter()
+        .map(|residue| {
+            format!(
+                "HET    {:>3}  {}{:>4}{}  {:>5}     {}",
+                group.id,
+                residue.chain,
+                residue.serial_number,
+                residue.insertion_code.as_deref().unwrap_or(" "),
+                residue.atom_count,
+                residue.description.as_deref().unwrap_or_default()
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect()
+}
+
+/// Get the lines of a HETNAM or HETSYN record with the given text for the given group, with
+/// continuation lines if the text does not fit on a single line
+fn het_text_records(record: &str, id: &str, text: &str) -> Vec<String> {
+    wrap_text(text, 55, 54, &['-'])
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| {
+            if index == 0 {
+                format!("{record}     {id:>3} {text}")
+            } else {
+                format!("{record}  {:>2} {id:>3}  {text}", index + 1)
+            }
+        })
+        .collect()
+}
+
+/// Get the HETNAM and HETSYN lines for the given hetero group
+pub(crate) fn het_name_records(group: &HetGroup) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(name) = &group.name {
+        lines.extend(het_text_records("HETNAM", &group.id, name));
+    }
+    if !group.synonyms.is_empty() {
+        lines.extend(het_text_records(
+            "HETSYN",
+            &group.id,
+            &group.synonyms.join("; "),
+        ));
+    }
+    lines
+}
+
+/// Get the FORMUL lines for the given hetero group, if it has a formula
+pub(crate) fn formula_records(group: &HetGroup) -> Vec<String> {
+    let formula = match &group.formula {
+        Some(formula) => formula,
+        None => return Vec::new(),
+    };
+    let component_number = group
+        .component_number
+        .map(|n| n.to_string())
+        .unwrap_or_default();
+    wrap_text(formula, 51, 51, &['-'])
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let continuation = if index == 0 {
+                String::new()
+            } else {
+                (index + 1).to_string()
+            };
+            format!(
+                "FORMUL  {component_number:>2}  {:>3} {continuation:>2}{}{text}",
+                group.id,
+                if group.water { '*' } else { ' ' }
+            )
+        })
+        .collect()
+}
+
+/// The number of records of each type for the MASTER record, the records which are not written
+/// are counted as 0
+#[derive(Debug, Default)]
+pub(crate) struct MasterCounts {
+    /// REMARK records
+    pub(crate) remarks: usize,
+    /// HET records
+    pub(crate) het: usize,
+    /// HELIX records
+    pub(crate) helices: usize,
+    /// SHEET records
+    pub(crate) sheets: usize,
+    /// SITE records
+    pub(crate) sites: usize,
+    /// Coordinate transformation records (ORIGX, SCALE, and MTRIX)
+    pub(crate) xform: usize,
+    /// ATOM and HETATM records
+    pub(crate) atoms: usize,
+    /// TER records
+    pub(crate) ter: usize,
+    /// SEQRES records
+    pub(crate) seqres: usize,
+    /// CONECT records
+    pub(crate) conect: usize,
+}
+
+/// Get the MASTER line with the given counts
+pub(crate) fn master_record(counts: &MasterCounts) -> String {
+    get_line(vec![
+        (0, "MASTER    "),
+        (5, counts.remarks.to_string().as_str()),
+        (5, "0"), //defined to be empty
+        (5, counts.het.to_string().as_str()),
+        (5, counts.helices.to_string().as_str()),
+        (5, counts.sheets.to_string().as_str()),
+        (5, "0"), //numTurn (deprecated)
+        (5, counts.sites.to_string().as_str()),
+        (5, counts.xform.to_string().as_str()),
+        (5, counts.atoms.to_string().as_str()),
+        (5, counts.ter.to_string().as_str()),
+        (5, counts.conect.to_string().as_str()),
+        (5, counts.seqres.to_string().as_str()),
+    ])
+}
+
+/// Get the CONECT records for the bonds between the given atoms, given as their counter and the
+/// serial number they are written with, in the order they are written. Every bond is listed for
+/// both of its atoms, with at most four bonded atoms per record. The type of the bonds is not
+/// kept, so these are read back as covalent (or metal coordination) bonds.
+fn conect_records(pdb: &PDB, atoms: &[(usize, usize)], overflow: SerialOverflow) -> Vec<String> {
+    let serials: FxHashMap<usize, usize> = atoms.iter().copied().collect();
+    let mut partners: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+    for (atom1, atom2, _) in pdb.bond_counters() {
+        if let (Some(&serial1), Some(&serial2)) = (serials.get(&atom1), serials.get(&atom2)) {
+            partners.entry(atom1).or_default().push(serial2);
+            partners.entry(atom2).or_default().push(serial1);
+        }
+    }
+    atoms
+        .iter()
+        .filter_map(|(counter, serial)| Some((*serial, partners.get(counter)?)))
+        .flat_map(|(serial, bonded)| {
+            bonded.chunks(4).map(move |chunk| {
+                iter::once(serial)
+                    .chain(chunk.iter().copied())
+                    .fold(String::from("CONECT"), |line, serial| {
+                        line + &format!("{:>5}", format_atom_serial(serial, overflow))
+                    })
+            })
+        })
+        .collect()
+}
+
+/// The position of an atom in the hierarchy as needed to write its records: the alternative
+/// location, the residue name, the chain id, and the residue serial number and insertion code.
+pub(crate) type AtomPlacement<'a> = (Option<&'a str>, &'a str, &'a str, (isize, Option<&'a str>));
+
+/// Format an atom serial number for the five columns available, using the given strategy
+#[allow(clippy::cast_possible_wrap)]
+fn format_atom_serial(serial: usize, overflow: SerialOverflow) -> String {
+    overflow.format(serial as isize, 5)
+}
+
+/// Get the atom name field (columns 13-16) of ATOM, HETATM, and ANISOU lines. Following the
+/// format, names of atoms with a one letter element start in column 14 and names of atoms with a
+/// two letter element start in column 13, so the element symbol always lines up in columns 13-14.
+/// Names of four characters or starting with a digit (old hydrogen names like `1HB`) always start
+/// in column 13. If the element is unknown the name is treated as having a one letter element.
+pub(crate) fn atom_name_field(atom: &Atom) -> String {
+    let name = atom.name();
+    let name = &name[name.len().saturating_sub(4)..];
+    let two_letter_element = atom.element().map_or(false, |e| e.symbol().len() == 2);
+    if name.len() >= 4 || two_letter_element || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{name:<4}")
+    } else {
+        format!(" {name:<3}")
+    }
+}
+
+/// Get the identifying part of ATOM, HETATM, and ANISOU lines (columns 7-27)
+fn atom_identification(
+    atom: &Atom,
+    placement: AtomPlacement<'_>,
+    overflow: SerialOverflow,
+) -> String {
+    let (alt_loc, residue_name, chain_id, (serial_number, insertion_code)) = placement;
+    get_line(vec![
+        (5, &format_atom_serial(atom.serial_number(), overflow)),
+        (0, " "),
+        (0, &atom_name_field(atom)),
+        (1, alt_loc.unwrap_or(" ")),
+        (4, residue_name),
+        (1, chain_id),
+        (4, &overflow.format(serial_number, 4)),
+        (1, insertion_code.unwrap_or(" ")),
+    ])
+}
+
+/// Get the ATOM or HETATM line for the given atom, followed by an ANISOU line if the atom has
+/// anisotropic temperature factors. The lines are not padded.
+pub(crate) fn atom_records(
+    atom: &Atom,
+    placement: AtomPlacement<'_>,
+    overflow: SerialOverflow,
+) -> Vec<String> {
+    let element = atom.element().map_or_else(|| "", Element::symbol);
+    let identification = atom_identification(atom, placement, overflow);
+    let mut lines = vec![get_line(vec![
+        (6, if atom.hetero() { "HETATM" } else { "ATOM  " }),
+        (0, &identification),
+        (0, "   "),
+        (8, &format!("{:8.3}", atom.pos().0)),
+        (8, &format!("{:8.3}", atom.pos().1)),
+        (8, &format!("{:8.3}", atom.pos().2)),
+        (6, &format!("{:6.2}", atom.occupancy())),
+        (6, &format!("{:6.2}", atom.b_factor())),
+        (0, "          "),
+        (2, element),
+        (0, &atom.pdb_charge()),
+    ])];
+    #[allow(clippy::cast_possible_truncation)]
+    if let Some(f) = atom.anisotropic_temperature_factors() {
+        lines.push(get_line(vec![
+            (6, "ANISOU"),
+            (0, &identification),
+            (0, " "),
+            (7, &format!("{:8.3}", (f[0][0] * 10000.0) as isize)),
+            (7, &format!("{:8.3}", (f[1][1] * 10000.0) as isize)),
+            (7, &format!("{:8.3}", (f[2][2] * 10000.0) as isize)),
+            (7, &format!("{:8.3}", (f[0][1] * 10000.0) as isize)),
+            (7, &format!("{:8.3}", (f[0][2] * 10000.0) as isize)),
+            (7, &format!("{:8.3}", (f[1][2] * 10000.0) as isize)),
+            (0, "      "),
+            (2, element),
+            (0, &atom.pdb_charge()),
+        ]));
+    }
+    lines
+}
+
+/// Get the ATOM or HETATM line for the given atom, without any ANISOU line, see [`atom_records`].
+pub(crate) fn atom_line(atom: &Atom, placement: AtomPlacement<'_>) -> String {
+    atom_records(atom, placement, SerialOverflow::default()).swap_remove(0)
+}
+
+/// Get the indices of the residues of the given chain which are followed by a TER record
+fn ter_positions(chain: &Chain, placement: TerPlacement) -> Vec<usize> {
+    let residues: Vec<&Residue> = chain.residues().collect();
+    let mut positions: Vec<usize> = match placement {
+        TerPlacement::ChainEnds => residues.iter().rposition(|r| r.atom_count() > 0),
+        TerPlacement::PolymerEnds | TerPlacement::PolymerEndsAndBreaks => residues
+            .iter()
+            .rposition(|r| r.atoms().any(|a| !a.hetero())),
+    }
+    .into_iter()
+    .collect();
+    if placement == TerPlacement::PolymerEndsAndBreaks {
+        positions.extend(
+            chain
+                .breaks()
+                .iter()
+                .filter(|chain_break| chain_break.is_physical())
+                .filter_map(|chain_break| {
+                    residues
+                        .iter()
+                        .position(|r| std::ptr::eq(*r, chain_break.before))
+                }),
+        );
+    }
+    positions
+}
+
+/// A run of residues of one chain written together in the coordinate section, with the indices
+/// of the residues after which a TER record is written
+type CoordinateSegment<'a> = (&'a Chain, Vec<&'a Residue>, Vec<usize>);
+
+/// Get the residues of the given model in the order they are written, see
+/// [`WriteOptions::set_canonical_order`]. Normally every chain with atoms is a single segment.
+fn coordinate_segments<'a>(model: &'a Model, options: &WriteOptions) -> Vec<CoordinateSegment<'a>> {
+    let chains = model.chains().filter(|c| c.atoms().next().is_some());
+    if !options.canonical_order {
+        return chains
+            .map(|chain| {
+                let ter_after = ter_positions(chain, options.ter_placement);
+                (chain, chain.residues().collect(), ter_after)
+            })
+            .collect();
+    }
+    let mut chains: Vec<&Chain> = chains.collect();
+    chains.sort_by_key(|chain| chain.id());
+    let is_water = |residue: &Residue| residue.conformers().all(Conformer::is_water);
+    let mut polymers = Vec::new();
+    let mut hetero = Vec::new();
+    let mut waters = Vec::new();
+    for chain in chains {
+        let residues: Vec<&Residue> = chain.residues().collect();
+        let end = residues
+            .iter()
+            .rposition(|r| r.atoms().any(|a| !a.hetero()))
+            .map_or(0, |index| index + 1);
+        let polymer: Vec<&Residue> = residues[..end]
+            .iter()
+            .copied()
+            .filter(|r| !is_water(r))
+            .collect();
+        // The polymer always ends in a TER record, breaks are kept if requested
+        let mut ter_after: Vec<usize> = ter_positions(chain, options.ter_placement)
+            .into_iter()
+            .filter_map(|index| {
+                polymer
+                    .iter()
+                    .position(|r| std::ptr::eq(*r, residues[index]))
+            })
+            .filter(|index| {
+                options.ter_placement == TerPlacement::PolymerEndsAndBreaks
+                    && index + 1 < polymer.len()
+            })
+            .collect();
+        ter_after.extend(polymer.len().checked_sub(1));
+        polymers.push((chain, polymer, ter_after));
+        hetero.push((
+            chain,
+            residues[end..]
+                .iter()
+                .copied()
+                .filter(|r| !is_water(r))
+                .collect(),
+            Vec::new(),
+        ));
+        waters.push((
+            chain,
+            residues.iter().copied().filter(|r| is_water(r)).collect(),
+            Vec::new(),
+        ));
+    }
+    polymers
+        .into_iter()
+        .chain(hetero)
+        .chain(waters)
+        .filter(|(_, residues, _): &CoordinateSegment<'a>| !residues.is_empty())
+        .collect()
+}
+
 /// Save the given PDB struct to the given BufWriter.
 /// It does not validate or renumber the PDB, so if that is needed, that needs to be done in preparation.
 /// It does change the output format based on the StrictnessLevel given.
@@ -109,32 +846,24 @@ where
 /// ## Loose
 /// * Does not pad all lines to 70 chars length
 /// * Does not save the MASTER record
+///
+/// To write serial numbers that do not fit in their columns differently, see [`WriteOptions::write_raw`].
+pub fn save_pdb_raw<T: Write>(pdb: &PDB, sink: BufWriter<T>, level: StrictnessLevel) {
+    save_pdb_raw_with_options(pdb, sink, WriteOptions::new().set_level(level));
+}
+
+/// Save the given PDB struct to the given BufWriter with the given options, see [`save_pdb_raw`].
 #[allow(clippy::unwrap_used)]
-pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: StrictnessLevel) {
-    let get_line = |fields: Vec<(usize, &str)>| {
-        let mut line = String::with_capacity(70);
-        for (length, text) in fields {
-            if length > 0 {
-                let cell = &text[text.len() - cmp::min(length, text.len())..];
-                let trimmed = cell.trim_start_matches('0');
-                if !cell.is_empty() && trimmed.is_empty() {
-                    std::fmt::write(&mut line, format_args!("{0:1$}", "0", length)).unwrap();
-                } else {
-                    std::fmt::write(&mut line, format_args!("{trimmed:length$}")).unwrap();
-                }
-            } else {
-                line += text;
-            }
-        }
-        line
-    };
+pub(crate) fn save_pdb_raw_with_options<T: Write>(
+    pdb: &PDB,
+    mut sink: BufWriter<T>,
+    options: &WriteOptions,
+) {
+    let level = options.level;
+    let overflow = options.serial_overflow;
     let mut print_line = |fields: Vec<(usize, &str)>| {
         let mut line = get_line(fields);
-        if level != StrictnessLevel::Loose && line.len() < 70 {
-            let dif = 70 - line.len();
-            line.reserve(dif);
-            line.extend(iter::repeat(" ").take(dif));
-        }
+        pad_line(&mut line, level);
         sink.write_all(line.as_bytes()).unwrap();
         sink.write_all(b"\n").unwrap();
     };
@@ -145,27 +874,25 @@ pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: Strictne
         };
     }
 
-    if let Some(name) = &pdb.identifier {
-        print_line(vec![
-            (
-                0,
-                "HEADER                                                        ",
-            ),
-            (0, name),
-        ]);
+    // Title section
+    for line in title_section_records(pdb.identifier.as_deref(), pdb.metadata()) {
+        print_line(vec![(0, &line)]);
     }
 
     // Remarks
-    for line in pdb.remarks() {
-        print_line(vec![
-            (6, "REMARK"),
-            (0, " "),
-            (3, &line.0.to_string()),
-            (0, " "),
-            (0, &line.1),
-        ]);
+    let remarks = remarks_with_resolution(pdb);
+    for line in &remarks {
+        print_line(vec![(0, &remark_record(line.0, &line.1))]);
+    }
+    let annotations = annotation_records(pdb);
+    for line in &annotations {
+        print_line(vec![(0, line)]);
     }
 
+    // The number of SEQRES and TER records written, for the MASTER record
+    let mut seqres_lines = 0;
+    let mut ter_lines = 0;
+
     if let Some(model) = pdb.models().next() {
         // DBREF
         let mut seqres = level == StrictnessLevel::Strict;
@@ -303,6 +1030,7 @@ pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: Strictne
                         .chunks(13)
                         .enumerate()
                     {
+                        seqres_lines += 1;
                         print_line(vec![
                             (6, "SEQRES"),
                             (0, " "),
@@ -338,6 +1066,7 @@ pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: Strictne
                         .chunks(13)
                         .enumerate()
                     {
+                        seqres_lines += 1;
                         print_line(vec![
                             (6, "SEQRES"),
                             (0, " "),
@@ -386,24 +1115,41 @@ pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: Strictne
             }
         }
     }
+
+    // HET, HETNAM, HETSYN, and FORMUL
+    let het: Vec<String> = pdb.het_groups().flat_map(het_records).collect();
+    for line in het
+        .iter()
+        .chain(
+            &pdb.het_groups()
+                .flat_map(het_name_records)
+                .collect::<Vec<_>>(),
+        )
+        .chain(
+            &pdb.het_groups()
+                .flat_map(formula_records)
+                .collect::<Vec<_>>(),
+        )
+    {
+        print_line(vec![(0, line)]);
+    }
+
+    // HELIX and SHEET
+    let helices: Vec<String> = pdb.helices().map(helix_record).collect();
+    let sheets: Vec<String> = pdb.sheets().flat_map(sheet_records).collect();
+    for line in helices.iter().chain(&sheets) {
+        print_line(vec![(0, line)]);
+    }
+
+    // SITE
+    let sites: Vec<String> = pdb.sites().flat_map(site_records).collect();
+    for line in &sites {
+        print_line(vec![(0, line)]);
+    }
+
     // Cryst
     if let Some(unit_cell) = &pdb.unit_cell {
-        let sym = if let Some(symmetry) = &pdb.symmetry {
-            format!("{:10}{:3}", symmetry.herman_mauguin_symbol(), symmetry.z(),)
-        } else {
-            "P 1         1".to_string()
-        };
-        print_line(vec![
-            (6, "CRYST1"),
-            (9, &format!("{:9.3}", unit_cell.a())),
-            (9, &format!("{:9.3}", unit_cell.b())),
-            (9, &format!("{:9.3}", unit_cell.c())),
-            (7, &format!("{:7.2}", unit_cell.alpha())),
-            (7, &format!("{:7.2}", unit_cell.beta())),
-            (7, &format!("{:7.2}", unit_cell.gamma())),
-            (0, "  "),
-            (0, &sym),
-        ]);
+        print_line(vec![(0, &cryst_record(unit_cell, pdb.symmetry.as_ref()))]);
     }
 
     let mut write_matrix = |name, matrix: [[f64; 4]; 3]| {
@@ -506,7 +1252,9 @@ pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: Strictne
 
     // Models
     let multiple_models = pdb.models().size_hint().0 > 1;
-    for model in pdb.models() {
+    // The counter and written serial number of the atoms of the first model, for the CONECT records
+    let mut conect_atoms = Vec::new();
+    for (model_index, model) in pdb.models().enumerate() {
         if multiple_models {
             print_line(vec![
                 (0, "MODEL        "),
@@ -514,75 +1262,73 @@ pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: Strictne
             ]);
         }
 
-        let atom_line = |atom: &Atom, conformer: &Conformer, residue: &Residue, chain: &Chain| {
-            get_line(vec![
-                (5, atom.serial_number().to_string().as_str()),
-                (0, " "),
-                (4, atom.name()),
-                (1, conformer.alternative_location().unwrap_or(" ")),
-                (4, conformer.name()),
-                (1, chain.id()),
-                (4, residue.serial_number().to_string().as_str()),
-                (1, residue.insertion_code().unwrap_or(" ")),
-            ])
-        };
-
-        for chain in model.chains().filter(|c| c.atoms().next().is_some()) {
-            for residue in chain.residues() {
+        // The serial number of the last record, only used when renumbering in canonical order
+        let mut serial_number = 0;
+        for (chain, residues, ter_after) in coordinate_segments(model, options) {
+            for (index, residue) in residues.iter().enumerate() {
                 for conformer in residue.conformers() {
-                    for atom in conformer.atoms() {
-                        let element = atom.element().map_or_else(|| "", Element::symbol);
-                        print_line(vec![
-                            (6, if atom.hetero() { "HETATM" } else { "ATOM  " }),
-                            (0, &atom_line(atom, conformer, residue, chain)),
-                            (0, "   "),
-                            (8, &format!("{:8.3}", atom.pos().0)),
-                            (8, &format!("{:8.3}", atom.pos().1)),
-                            (8, &format!("{:8.3}", atom.pos().2)),
-                            (6, &format!("{:6.2}", atom.occupancy())),
-                            (6, &format!("{:6.2}", atom.b_factor())),
-                            (0, "          "),
-                            (2, element),
-                            (0, &atom.pdb_charge()),
-                        ]);
-                        #[allow(clippy::cast_possible_truncation)]
-                        if atom.anisotropic_temperature_factors().is_some() {
-                            let f = atom.anisotropic_temperature_factors().unwrap();
-                            print_line(vec![
-                                (6, "ANISOU"),
-                                (0, &atom_line(atom, conformer, residue, chain)),
-                                (0, " "),
-                                (7, &format!("{:8.3}", (f[0][0] * 10000.0) as isize)),
-                                (7, &format!("{:8.3}", (f[1][1] * 10000.0) as isize)),
-                                (7, &format!("{:8.3}", (f[2][2] * 10000.0) as isize)),
-                                (7, &format!("{:8.3}", (f[0][1] * 10000.0) as isize)),
-                                (7, &format!("{:8.3}", (f[0][2] * 10000.0) as isize)),
-                                (7, &format!("{:8.3}", (f[1][2] * 10000.0) as isize)),
-                                (0, "      "),
-                                (2, element),
-                                (0, &atom.pdb_charge()),
-                            ]);
+                    for original in conformer.atoms() {
+                        let renumbered;
+                        let atom = if options.canonical_order {
+                            serial_number += 1;
+                            let mut copy = original.clone();
+                            copy.set_serial_number(serial_number);
+                            renumbered = copy;
+                            &renumbered
+                        } else {
+                            original
+                        };
+                        if model_index == 0 {
+                            conect_atoms.push((original.counter(), atom.serial_number()));
+                        }
+                        for line in atom_records(
+                            atom,
+                            (
+                                conformer.alternative_location(),
+                                conformer.name(),
+                                chain.id(),
+                                residue.id(),
+                            ),
+                            overflow,
+                        ) {
+                            print_line(vec![(0, &line)]);
                         }
                     }
                 }
+                if !ter_after.contains(&index) {
+                    continue;
+                }
+                if let (Some(last_atom), Some(last_conformer)) = (
+                    residue.atoms().nth_back(0),
+                    residue.conformers().nth_back(0),
+                ) {
+                    serial_number += 1;
+                    ter_lines += 1;
+                    print_line(vec![(
+                        0,
+                        &ter_record(
+                            if options.canonical_order {
+                                serial_number
+                            } else {
+                                last_atom.serial_number() + 1
+                            },
+                            last_conformer.name(),
+                            chain.id(),
+                            residue.serial_number(),
+                            overflow,
+                        ),
+                    )]);
+                }
             }
-            let last_atom = chain.atoms().nth_back(0).unwrap();
-            let last_residue = chain.residues().nth_back(0).unwrap();
-            let last_conformer = chain.conformers().nth_back(0).unwrap();
-            print_line(vec![
-                (0, "TER"),
-                (5, last_atom.serial_number().to_string().as_str()),
-                (0, "      "),
-                (3, last_conformer.name()),
-                (0, " "),
-                (1, chain.id()),
-                (4, last_residue.serial_number().to_string().as_str()),
-            ]);
         }
         if multiple_models {
             print_line(vec![(0, "ENDMDL")]);
         }
     }
+    let conect = conect_records(pdb, &conect_atoms, overflow);
+    for line in &conect {
+        print_line(vec![(0, line)]);
+    }
     if level != StrictnessLevel::Loose {
         let mut xform = 0;
         if pdb.origx.is_some() || level == StrictnessLevel::Strict {
@@ -594,21 +1340,21 @@ pub fn save_pdb_raw<T: Write>(pdb: &PDB, mut sink: BufWriter<T>, level: Strictne
         for _ in pdb.mtrix() {
             xform += 3;
         }
-        print_line(vec![
-            (0, "MASTER    "),
-            (5, pdb.remark_count().to_string().as_str()),
-            (5, "0"), //defined to be empty
-            (5, "0"), //numHet
-            (5, "0"), //numHelix
-            (5, "0"), //numSheet
-            (5, "0"), //numTurn (deprecated)
-            (5, "0"), //numSite
-            (5, xform.to_string().as_str()),
-            (5, pdb.total_atom_count().to_string().as_str()),
-            (5, pdb.model_count().to_string().as_str()),
-            (5, "0"), //numConnect
-            (5, "0"), //numSeq
-        ]);
+        print_line(vec![(
+            0,
+            &master_record(&MasterCounts {
+                remarks: remarks.len() + annotations.len(),
+                het: het.len(),
+                helices: helices.len(),
+                sheets: sheets.len(),
+                sites: sites.len(),
+                xform,
+                atoms: pdb.total_atom_count(),
+                ter: ter_lines,
+                seqres: seqres_lines,
+                conect: conect.len(),
+            }),
+        )]);
     }
     print_line(vec![(0, "END")]);
 
@@ -0,0 +1,166 @@
+use std::io::{BufWriter, Write};
+
+use crate::structs::PDB;
+use crate::{check_extension, PDBError, StrictnessLevel};
+
+/// The strategy used to write serial numbers which do not fit in their columns in PDB files, so
+/// atom serial numbers above 99999 and residue serial numbers above 9999.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerialOverflow {
+    /// Only write the last digits, so the numbers wrap around (100000 is written as 0).
+    #[default]
+    Wrap,
+    /// Fill the columns with stars (`*****`), as done by Amber and other tools.
+    Stars,
+    /// Write the number in hexadecimal (100000 is written as `186a0`), as done by VMD. Numbers
+    /// which do not fit in hexadecimal either wrap around, negative numbers are written as stars.
+    Hexadecimal,
+    /// Write the number in the hybrid-36 format (100000 is written as `A0000`), as used by the
+    /// CCTBX and PHENIX, see [`crate::encode_hybrid36`]. Hybrid-36 numbers are always decoded
+    /// when reading, unless the serial numbers are reconstructed. Numbers which do not fit and
+    /// negative numbers are written as stars.
+    Hybrid36,
+}
+
+impl SerialOverflow {
+    /// Format the given serial number to fit in the given width using this strategy. The result
+    /// still has to be right aligned and, for the `Wrap` strategy, truncated to the width.
+    pub(crate) fn format(self, serial: isize, width: usize) -> String {
+        let text = serial.to_string();
+        if text.len() <= width {
+            return text;
+        }
+        match self {
+            // Keep the sign of negative numbers, so -10000 is written as -000
+            SerialOverflow::Wrap if serial < 0 => {
+                format!("-{}", &text[text.len() + 1 - width..])
+            }
+            SerialOverflow::Wrap => text,
+            SerialOverflow::Hexadecimal if serial >= 0 => format!("{serial:x}"),
+            SerialOverflow::Hybrid36 => usize::try_from(serial)
+                .ok()
+                .and_then(|serial| crate::encode_hybrid36(serial, width))
+                .unwrap_or_else(|| "*".repeat(width)),
+            SerialOverflow::Stars | SerialOverflow::Hexadecimal => "*".repeat(width),
+        }
+    }
+}
+
+/// Where TER records are written in PDB files, closing the polymers of the chains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TerPlacement {
+    /// After the last atom of every chain, including its waters and ligands.
+    ChainEnds,
+    /// After the last polymer residue of every chain (the last residue with ATOM records), so the
+    /// waters and ligands of the chain follow the TER record as defined by the PDB format. Chains
+    /// without any polymer do not get a TER record.
+    #[default]
+    PolymerEnds,
+    /// At the polymer ends, and also after every physical chain break, where consecutive
+    /// residues are not bonded, see [`crate::Chain::breaks`].
+    PolymerEndsAndBreaks,
+}
+
+/// Options to write a [`PDB`] to a file, with a builder interface like [`crate::ReadOptions`].
+///
+/// # Example
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new()
+///     .set_level(StrictnessLevel::Loose)
+///     .read("example-pdbs/1ubq.pdb")
+///     .unwrap();
+/// WriteOptions::new()
+///     .set_level(StrictnessLevel::Loose)
+///     .set_serial_overflow(SerialOverflow::Hexadecimal)
+///     .write(&pdb, std::env::temp_dir().join("1ubq_options.pdb").to_string_lossy())
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// The strictness level used to validate the structure and determine the output format
+    pub(crate) level: StrictnessLevel,
+    /// The strategy to write serial numbers which do not fit in their columns
+    pub(crate) serial_overflow: SerialOverflow,
+    /// Where TER records are written
+    pub(crate) ter_placement: TerPlacement,
+    /// Reorder the coordinate records in the wwPDB layout, see [`WriteOptions::set_canonical_order`]
+    pub(crate) canonical_order: bool,
+}
+
+impl WriteOptions {
+    /// Constructs a new [`WriteOptions`] object with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the strictness level to use when validating and writing the file.
+    pub fn set_level(&mut self, level: StrictnessLevel) -> &mut Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the strategy to write serial numbers which do not fit in their columns in PDB files.
+    pub fn set_serial_overflow(&mut self, serial_overflow: SerialOverflow) -> &mut Self {
+        self.serial_overflow = serial_overflow;
+        self
+    }
+
+    /// Sets where TER records are written in PDB files, by default at the end of the polymer of
+    /// every chain.
+    pub fn set_ter_placement(&mut self, ter_placement: TerPlacement) -> &mut Self {
+        self.ter_placement = ter_placement;
+        self
+    }
+
+    /// Sets if the coordinate records in PDB files are reordered following the wwPDB conventions:
+    /// first the polymers of all chains sorted by chain ID, each closed by a TER record, then
+    /// the hetero groups (ligands) of all chains, and finally the waters of all chains. The atom
+    /// serial numbers are renumbered consecutively in the new order, counting the TER records as
+    /// well. Residues which are not part of a polymer are the residues after the last residue
+    /// with ATOM records. The structure itself is not changed. Off by default.
+    pub fn set_canonical_order(&mut self, canonical_order: bool) -> &mut Self {
+        self.canonical_order = canonical_order;
+        self
+    }
+
+    /// Save the given PDB struct to the given file, validating it beforehand. The correct file
+    /// type (pdb or mmCIF/PDBx) will be determined based on the given file extension, see
+    /// [`crate::save`].
+    ///
+    /// # Errors
+    /// Fails if the validation fails with the given `level`.
+    pub fn write(&self, pdb: &PDB, filename: impl AsRef<str>) -> Result<(), Vec<PDBError>> {
+        if check_extension(&filename, "pdb") {
+            super::pdb::save_pdb_with_options(pdb, filename, self)
+        } else {
+            super::save(pdb, filename, self.level)
+        }
+    }
+
+    /// Save the given PDB struct to the given sink in the PDB format, without validating it, see
+    /// [`crate::save_pdb_raw`].
+    pub fn write_raw<T: Write>(&self, pdb: &PDB, sink: BufWriter<T>) {
+        super::pdb::save_pdb_raw_with_options(pdb, sink, self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serial_overflow() {
+        assert_eq!(SerialOverflow::Stars.format(99_999, 5), "99999");
+        assert_eq!(SerialOverflow::Stars.format(100_000, 5), "*****");
+        assert_eq!(SerialOverflow::Hexadecimal.format(100_000, 5), "186a0");
+        assert_eq!(SerialOverflow::Wrap.format(100_000, 5), "100000");
+        assert_eq!(SerialOverflow::Hexadecimal.format(10_000, 4), "2710");
+        assert_eq!(SerialOverflow::Wrap.format(-2, 4), "-2");
+        assert_eq!(SerialOverflow::Wrap.format(-10_000, 4), "-000");
+        assert_eq!(SerialOverflow::Hexadecimal.format(-10_000, 4), "****");
+        assert_eq!(SerialOverflow::Hybrid36.format(100_000, 5), "A0000");
+        assert_eq!(SerialOverflow::Hybrid36.format(10_000, 4), "A000");
+        assert_eq!(SerialOverflow::Hybrid36.format(-10_000, 4), "****");
+    }
+}
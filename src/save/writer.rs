@@ -0,0 +1,281 @@
+use std::io::Write;
+
+use super::pdb::{
+    atom_records, cryst_record, header_record, master_record, pad_line, remark_record, ter_record,
+    MasterCounts,
+};
+use super::write_options::SerialOverflow;
+use crate::structs::*;
+use crate::{Context, ErrorLevel, PDBError, StrictnessLevel};
+
+/// A push style writer for PDB files, which writes every record as soon as it is given. This
+/// allows writing huge files record-by-record without building a full [`PDB`] in memory first.
+///
+/// The records are written in the order the methods are called, so the caller is responsible for
+/// following the order defined by the PDB format: header, remarks, crystal, models containing
+/// atoms, and finally [`PDBWriter::finish`] which writes the MASTER and END records.
+///
+/// # Example
+/// ```rust
+/// use pdbtbx::*;
+/// let mut writer = PDBWriter::start(Vec::new());
+/// writer.write_header("1ABC").unwrap();
+/// let atom = Atom::new(false, 1, "CA", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap();
+/// writer.write_atom(&atom, None, "ALA", "A", (1, None)).unwrap();
+/// writer.write_ter().unwrap();
+/// let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+/// assert!(output.lines().any(|line| line.starts_with("ATOM") && line.contains("ALA A")));
+/// ```
+#[derive(Debug)]
+pub struct PDBWriter<W: Write> {
+    /// The sink to write to
+    sink: W,
+    /// The strictness level, used to determine the padding and if a MASTER record is written
+    level: StrictnessLevel,
+    /// The strategy to write serial numbers which do not fit in their columns
+    serial_overflow: SerialOverflow,
+    /// The number of REMARK records written
+    remarks: usize,
+    /// The number of atoms written
+    atoms: usize,
+    /// The number of TER records written
+    ter: usize,
+    /// Whether a model is currently open
+    in_model: bool,
+    /// The last written atom: serial number, residue name, chain id, and residue serial number
+    last_atom: Option<(usize, String, String, isize)>,
+}
+
+impl<W: Write> PDBWriter<W> {
+    /// Start writing a PDB file to the given sink, with the default (`Medium`) strictness level.
+    pub fn start(sink: W) -> Self {
+        Self::start_with_level(sink, StrictnessLevel::Medium)
+    }
+
+    /// Start writing a PDB file to the given sink. With a `Loose` level the lines are not padded
+    /// to 70 characters and no MASTER record is written, the same as [`crate::save_pdb_raw`].
+    pub fn start_with_level(sink: W, level: StrictnessLevel) -> Self {
+        PDBWriter {
+            sink,
+            level,
+            serial_overflow: SerialOverflow::default(),
+            remarks: 0,
+            atoms: 0,
+            ter: 0,
+            in_model: false,
+            last_atom: None,
+        }
+    }
+
+    /// Set the strategy to write serial numbers which do not fit in their columns.
+    pub fn set_serial_overflow(&mut self, serial_overflow: SerialOverflow) -> &mut Self {
+        self.serial_overflow = serial_overflow;
+        self
+    }
+
+    /// Write a single line to the sink, padding it if needed.
+    fn write_line(&mut self, mut line: String) -> Result<(), PDBError> {
+        pad_line(&mut line, self.level);
+        line.push('\n');
+        self.sink.write_all(line.as_bytes()).map_err(|e| {
+            PDBError::new(
+                ErrorLevel::BreakingError,
+                "Could not write",
+                format!("Could not write to the output of the PDBWriter: {e}"),
+                Context::show(line.trim_end()),
+            )
+        })
+    }
+
+    /// Write the HEADER record with the given identifier.
+    /// # Errors
+    /// Fails if the sink could not be written to.
+    pub fn write_header(&mut self, identifier: &str) -> Result<(), PDBError> {
+        self.write_line(header_record("", "", identifier))
+    }
+
+    /// Write a REMARK record with the given remark-type-number and text.
+    /// # Errors
+    /// Fails if the sink could not be written to.
+    pub fn write_remark(&mut self, remark_type: usize, text: &str) -> Result<(), PDBError> {
+        self.remarks += 1;
+        self.write_line(remark_record(remark_type, text))
+    }
+
+    /// Write the CRYST1 record for the given unit cell and optional symmetry.
+    /// # Errors
+    /// Fails if the sink could not be written to.
+    pub fn write_crystal(
+        &mut self,
+        unit_cell: &UnitCell,
+        symmetry: Option<&Symmetry>,
+    ) -> Result<(), PDBError> {
+        self.write_line(cryst_record(unit_cell, symmetry))
+    }
+
+    /// Start a new model with the given serial number, closing the previous model if one was open.
+    /// There is no need to call this for files with a single model.
+    /// # Errors
+    /// Fails if the sink could not be written to.
+    pub fn start_model(&mut self, serial_number: usize) -> Result<(), PDBError> {
+        self.end_model()?;
+        self.in_model = true;
+        self.write_line(format!("MODEL        {serial_number}"))
+    }
+
+    /// Close the currently open model, if any.
+    /// # Errors
+    /// Fails if the sink could not be written to.
+    pub fn end_model(&mut self) -> Result<(), PDBError> {
+        if self.in_model {
+            self.in_model = false;
+            self.write_line("ENDMDL".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write an ATOM or HETATM record for the given atom, followed by an ANISOU record if the atom
+    /// has anisotropic temperature factors.
+    ///
+    /// ## Arguments
+    /// * `atom` - the atom to write
+    /// * `alt_loc` - the alternative location of the conformer containing this atom
+    /// * `residue_name` - the name of the conformer containing this atom
+    /// * `chain_id` - the id of the chain containing this atom
+    /// * `residue_id` - the serial number and insertion code of the residue containing this atom
+    ///
+    /// # Errors
+    /// Fails if the sink could not be written to.
+    pub fn write_atom(
+        &mut self,
+        atom: &Atom,
+        alt_loc: Option<&str>,
+        residue_name: &str,
+        chain_id: &str,
+        residue_id: (isize, Option<&str>),
+    ) -> Result<(), PDBError> {
+        self.atoms += 1;
+        self.last_atom = Some((
+            atom.serial_number(),
+            residue_name.to_string(),
+            chain_id.to_string(),
+            residue_id.0,
+        ));
+        for line in atom_records(
+            atom,
+            (alt_loc, residue_name, chain_id, residue_id),
+            self.serial_overflow,
+        ) {
+            self.write_line(line)?;
+        }
+        Ok(())
+    }
+
+    /// Write a TER record closing the chain of the last written atom. Does nothing if no atom was
+    /// written since the last TER record.
+    /// # Errors
+    /// Fails if the sink could not be written to.
+    pub fn write_ter(&mut self) -> Result<(), PDBError> {
+        if let Some((serial, residue_name, chain_id, residue_serial)) = self.last_atom.take() {
+            self.ter += 1;
+            self.write_line(ter_record(
+                serial + 1,
+                &residue_name,
+                &chain_id,
+                residue_serial,
+                self.serial_overflow,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Finish the file by closing the open model, writing the MASTER (unless the level is `Loose`)
+    /// and END records, and flushing the sink. Gives back the sink.
+    /// # Errors
+    /// Fails if the sink could not be written to or flushed.
+    pub fn finish(mut self) -> Result<W, PDBError> {
+        self.end_model()?;
+        if self.level != StrictnessLevel::Loose {
+            self.write_line(master_record(&MasterCounts {
+                remarks: self.remarks,
+                atoms: self.atoms,
+                ter: self.ter,
+                ..MasterCounts::default()
+            }))?;
+        }
+        self.write_line("END".to_string())?;
+        self.sink.flush().map_err(|e| {
+            PDBError::new(
+                ErrorLevel::BreakingError,
+                "Could not flush",
+                format!("Could not flush the output of the PDBWriter: {e}"),
+                Context::None,
+            )
+        })?;
+        Ok(self.sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Format, ReadOptions};
+    use std::io::BufReader;
+
+    #[test]
+    fn write_and_read_back() {
+        let mut writer = PDBWriter::start(Vec::new());
+        writer.write_header("1ABC").unwrap();
+        writer
+            .write_remark(2, "RESOLUTION. 1.80 ANGSTROMS.")
+            .unwrap();
+        for model in 1..=2 {
+            writer.start_model(model).unwrap();
+            for (serial, residue) in (1..=3).zip(1..=3) {
+                let atom =
+                    Atom::new(false, serial, "CA", 1.0, 2.0, 3.0, 1.0, 10.0, "C", 0).unwrap();
+                writer
+                    .write_atom(&atom, None, "GLY", "A", (residue, None))
+                    .unwrap();
+            }
+            writer.write_ter().unwrap();
+        }
+        let output = writer.finish().unwrap();
+        let (pdb, errors) = ReadOptions::default()
+            .set_format(Format::Pdb)
+            .read_raw(BufReader::new(output.as_slice()))
+            .unwrap();
+        assert!(errors
+            .iter()
+            .all(|e| e.short_description() != "MASTER checksum failed"));
+        assert_eq!(pdb.identifier.as_deref(), Some("1ABC"));
+        assert_eq!(pdb.remark_count(), 1);
+        assert_eq!(pdb.model_count(), 2);
+        assert_eq!(pdb.atom_count(), 3);
+        assert_eq!(pdb.residue_count(), 3);
+    }
+
+    #[test]
+    fn atom_name_justification() {
+        let mut writer = PDBWriter::start_with_level(Vec::new(), StrictnessLevel::Loose);
+        for (name, element) in [("CA", "C"), ("CA", "Ca"), ("HG12", "H"), ("1HB", "H")] {
+            let atom = Atom::new(false, 1, name, 0.0, 0.0, 0.0, 1.0, 0.0, element, 0).unwrap();
+            writer
+                .write_atom(&atom, None, "ALA", "A", (1, None))
+                .unwrap();
+        }
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let names: Vec<_> = output.lines().take(4).map(|line| &line[12..16]).collect();
+        assert_eq!(names, [" CA ", "CA  ", "HG12", "1HB "]);
+    }
+
+    #[test]
+    fn ter_only_after_atoms() {
+        let mut writer = PDBWriter::start_with_level(Vec::new(), StrictnessLevel::Loose);
+        writer.write_ter().unwrap();
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(output, "END\n");
+    }
+}
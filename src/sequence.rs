@@ -0,0 +1,167 @@
+use crate::alignment::one_letter_code;
+use crate::reference_tables;
+
+/// The pKa values of the N terminus, the C terminus, and the ionizable side chains, as used by
+/// EMBOSS
+const PKA_N_TERMINUS: f64 = 8.6;
+/// The pKa of the C terminus, see [`PKA_N_TERMINUS`]
+const PKA_C_TERMINUS: f64 = 3.6;
+/// The pKa values of the positively charged side chains (Lys, Arg, His)
+const PKA_POSITIVE: &[(char, f64)] = &[('K', 10.8), ('R', 12.5), ('H', 6.5)];
+/// The pKa values of the negatively charged side chains (Asp, Glu, Cys, Tyr)
+const PKA_NEGATIVE: &[(char, f64)] = &[('D', 3.9), ('E', 4.1), ('C', 8.5), ('Y', 10.1)];
+
+/// The molar extinction coefficients at 280 nm in M⁻¹cm⁻¹ of tryptophan, tyrosine, and a
+/// cystine (disulfide bond), after Pace et al. (1995)
+const EXTINCTION_TRP: f64 = 5500.0;
+/// The extinction coefficient of tyrosine, see [`EXTINCTION_TRP`]
+const EXTINCTION_TYR: f64 = 1490.0;
+/// The extinction coefficient of a cystine, see [`EXTINCTION_TRP`]
+const EXTINCTION_CYSTINE: f64 = 125.0;
+
+/// Get the one letter codes of the given protein sequence, residue names which are not standard
+/// amino acids are skipped
+fn codes(sequence: &[impl AsRef<str>]) -> Vec<char> {
+    sequence.iter().filter_map(one_letter_code).collect()
+}
+
+/// Calculate the net charge of the given protein sequence at the given pH, from the pKa values
+/// of the termini and the ionizable side chains with the Henderson–Hasselbalch equation. The
+/// sequence can be given as residue names (`MET`), one letter codes (`M`), or a mix of both,
+/// names which are not standard amino acids are ignored.
+pub fn charge_at_ph(sequence: &[impl AsRef<str>], ph: f64) -> f64 {
+    let codes = codes(sequence);
+    if codes.is_empty() {
+        return 0.0;
+    }
+    let positive = |pka: f64| 1.0 / (1.0 + 10_f64.powf(ph - pka));
+    let negative = |pka: f64| -1.0 / (1.0 + 10_f64.powf(pka - ph));
+    let side_chains: f64 = codes
+        .iter()
+        .map(|code| {
+            let find = |table: &[(char, f64)]| table.iter().find(|(c, _)| c == code).map(|p| p.1);
+            find(PKA_POSITIVE).map_or_else(|| find(PKA_NEGATIVE).map_or(0.0, negative), positive)
+        })
+        .sum();
+    positive(PKA_N_TERMINUS) + negative(PKA_C_TERMINUS) + side_chains
+}
+
+/// Estimate the isoelectric point of the given protein sequence, the pH at which its net charge
+/// is zero, see [`charge_at_ph`]. The sequence can be given as residue names (`MET`), one letter
+/// codes (`M`), or a mix of both, names which are not standard amino acids are ignored.
+/// ```rust
+/// use pdbtbx::sequence;
+/// let pi = sequence::isoelectric_point(&["ASP", "GLU", "ALA"]);
+/// assert!(pi > 2.0 && pi < 4.0);
+/// ```
+pub fn isoelectric_point(sequence: &[impl AsRef<str>]) -> f64 {
+    // The charge decreases monotonically with the pH, so bisect to a precision of 0.001
+    let (mut low, mut high) = (0.0, 14.0);
+    while high - low > 0.001 {
+        let middle = (low + high) / 2.0;
+        if charge_at_ph(sequence, middle) > 0.0 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// The molar extinction coefficient at 280 nm of a protein, see [`extinction_coefficient`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExtinctionCoefficient {
+    /// The extinction coefficient in M⁻¹cm⁻¹ with all cysteines reduced
+    pub reduced: f64,
+    /// The extinction coefficient in M⁻¹cm⁻¹ with all pairs of cysteines forming cystines
+    pub oxidized: f64,
+}
+
+/// Calculate the molar extinction coefficient at 280 nm of the given protein sequence from its
+/// tryptophans, tyrosines, and cystines (Pace et al. 1995). The sequence can be given as residue
+/// names (`MET`), one letter codes (`M`), or a mix of both.
+pub fn extinction_coefficient(sequence: &[impl AsRef<str>]) -> ExtinctionCoefficient {
+    let codes = codes(sequence);
+    let count = |code: char| codes.iter().filter(|c| **c == code).count();
+    #[allow(clippy::cast_precision_loss)]
+    let reduced = count('W') as f64 * EXTINCTION_TRP + count('Y') as f64 * EXTINCTION_TYR;
+    #[allow(clippy::cast_precision_loss)]
+    let cystines = (count('C') / 2) as f64;
+    ExtinctionCoefficient {
+        reduced,
+        oxidized: reduced + cystines * EXTINCTION_CYSTINE,
+    }
+}
+
+/// Calculate the grand average of hydropathy (GRAVY) of the given protein sequence, the mean of
+/// the Kyte–Doolittle hydropathy values of its amino acids. Positive values indicate hydrophobic
+/// proteins. The sequence can be given as residue names (`MET`), one letter codes (`M`), or a
+/// mix of both, names which are not standard amino acids are ignored. Returns `None` if there
+/// are no amino acids.
+pub fn gravy(sequence: &[impl AsRef<str>]) -> Option<f64> {
+    let values: Vec<f64> = codes(sequence)
+        .into_iter()
+        .filter_map(reference_tables::hydropathy)
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Calculate the hydropathy profile of the given protein sequence, the mean Kyte–Doolittle
+/// hydropathy in a sliding window centered on each amino acid. The profile has a value for
+/// every window fitting in the sequence, so the first value is for the amino acid at index
+/// `window / 2`. A window of 9 is commonly used for surface regions, 19 for transmembrane
+/// helices. Names which are not standard amino acids are ignored. Returns an empty profile if
+/// the window is zero or longer than the sequence.
+/// ```rust
+/// use pdbtbx::sequence;
+/// let profile = sequence::hydropathy_profile(&"MQIFVKTLTG".chars().map(String::from).collect::<Vec<_>>(), 3);
+/// assert_eq!(profile.len(), 8);
+/// ```
+pub fn hydropathy_profile(sequence: &[impl AsRef<str>], window: usize) -> Vec<f64> {
+    let values: Vec<f64> = codes(sequence)
+        .into_iter()
+        .filter_map(reference_tables::hydropathy)
+        .collect();
+    if window == 0 {
+        return Vec::new();
+    }
+    #[allow(clippy::cast_precision_loss)]
+    values
+        .windows(window)
+        .map(|values| values.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charges() {
+        // Only the termini are charged
+        assert!(
+            (isoelectric_point(&["G", "G"]) - (PKA_N_TERMINUS + PKA_C_TERMINUS) / 2.0).abs() < 0.01
+        );
+        assert!(charge_at_ph(&["LYS", "ARG"], 2.0) > 2.9);
+        assert!(charge_at_ph(&["ASP", "GLU"], 12.0) < -2.9);
+        let none: &[&str] = &[];
+        assert_eq!(charge_at_ph(none, 7.0), 0.0);
+    }
+
+    #[test]
+    fn extinction() {
+        let coefficient = extinction_coefficient(&["W", "Y", "CYS", "C", "C", "HOH"]);
+        assert_eq!(coefficient.reduced, 6990.0);
+        assert_eq!(coefficient.oxidized, 7115.0);
+    }
+
+    #[test]
+    fn hydropathy() {
+        assert_eq!(gravy(&["ILE", "ARG"]), Some(0.0));
+        assert_eq!(gravy(&["HOH"]), None);
+        assert_eq!(hydropathy_profile(&["I", "R", "I"], 2), vec![0.0, 0.0]);
+        assert!(hydropathy_profile(&["I"], 2).is_empty());
+        assert!(hydropathy_profile(&["I"], 0).is_empty());
+    }
+}
@@ -28,3 +28,19 @@ impl Display for StrictnessLevel {
         )
     }
 }
+
+impl std::str::FromStr for StrictnessLevel {
+    type Err = String;
+
+    /// Parse a strictness level from its name, case insensitive
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "strict" => Ok(StrictnessLevel::Strict),
+            "medium" => Ok(StrictnessLevel::Medium),
+            "loose" => Ok(StrictnessLevel::Loose),
+            _ => Err(format!(
+                "Unknown strictness level: '{s}', use strict, medium, or loose"
+            )),
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use std::fmt;
+
+/// The remark-type-number used to save annotations in PDB files
+pub(crate) const ANNOTATION_REMARK_TYPE: usize = 999;
+/// The first token of every REMARK line containing an annotation, to distinguish them from
+/// normal remarks with the same remark-type-number
+pub(crate) const ANNOTATION_REMARK_TAG: &str = "PDBTBX";
+/// The maximal length of the text of an annotation REMARK, so the full line fits in 80 columns
+const ANNOTATION_REMARK_WIDTH: usize = 69;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+/// A typed value in an [`Annotations`] store
+pub enum AnnotationValue {
+    /// A piece of free text
+    Text(String),
+    /// An integer
+    Integer(isize),
+    /// A floating point number
+    Float(f64),
+    /// A boolean
+    Boolean(bool),
+}
+
+impl AnnotationValue {
+    /// Get the text, if this is a text value
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Get the integer, if this is an integer value
+    pub const fn as_integer(&self) -> Option<isize> {
+        match self {
+            Self::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Get the floating point number, if this is a float value
+    pub const fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Get the boolean, if this is a boolean value
+    pub const fn as_boolean(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// The character used to denote the type of this value in PDB files
+    const fn type_tag(&self) -> char {
+        match self {
+            Self::Text(_) => 'T',
+            Self::Integer(_) => 'I',
+            Self::Float(_) => 'F',
+            Self::Boolean(_) => 'B',
+        }
+    }
+
+    /// Parse a value from its type tag and (decoded) textual representation
+    fn parse(tag: &str, text: String) -> Option<Self> {
+        match tag {
+            "T" => Some(Self::Text(text)),
+            "I" => text.parse().ok().map(Self::Integer),
+            "F" => text.parse().ok().map(Self::Float),
+            "B" => text.parse().ok().map(Self::Boolean),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AnnotationValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Integer(n) => write!(f, "{n}"),
+            // The exponential notation is the shortest representation that parses back exactly
+            Self::Float(n) => write!(f, "{n:e}"),
+            Self::Boolean(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+/// Floats are compared on their bit patterns, so `NaN` equals itself and the values satisfy
+/// the properties needed for Eq.
+impl PartialEq for AnnotationValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Integer(a), Self::Integer(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => a.to_bits() == b.to_bits(),
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for AnnotationValue {}
+
+impl From<String> for AnnotationValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for AnnotationValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<isize> for AnnotationValue {
+    fn from(value: isize) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for AnnotationValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for AnnotationValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A key-value store to attach custom data to a PDB, Chain, Residue, or Atom. The annotations
+/// are saved in PDB files in REMARK 999 records starting with `PDBTBX` and restored when the
+/// file is opened again, so they travel with the file. The keys are kept in insertion order.
+/// ```rust
+/// use pdbtbx::*;
+/// let mut pdb = PDB::new();
+/// pdb.annotations_mut().insert("pipeline", "docking");
+/// pdb.annotations_mut().insert("score", -7.5);
+/// assert_eq!(pdb.annotations().get_text("pipeline"), Some("docking"));
+/// assert_eq!(pdb.annotations().get_float("score"), Some(-7.5));
+/// assert_eq!(pdb.annotations().get_integer("score"), None);
+/// ```
+pub struct Annotations {
+    /// The key-value pairs, in insertion order
+    values: Vec<(String, AnnotationValue)>,
+}
+
+impl Annotations {
+    /// Create an empty annotation store
+    pub const fn new() -> Self {
+        Annotations { values: Vec::new() }
+    }
+
+    /// Get the number of annotations
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if there are no annotations
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get an iterator over the keys and values of the annotations, in insertion order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&str, &AnnotationValue)> + '_ {
+        self.values.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Check if there is an annotation with the given key
+    pub fn contains_key(&self, key: impl AsRef<str>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Get the value of the annotation with the given key
+    pub fn get(&self, key: impl AsRef<str>) -> Option<&AnnotationValue> {
+        self.values
+            .iter()
+            .find(|(k, _)| k == key.as_ref())
+            .map(|(_, v)| v)
+    }
+
+    /// Get the value of the annotation with the given key, if it is a text value
+    pub fn get_text(&self, key: impl AsRef<str>) -> Option<&str> {
+        self.get(key).and_then(AnnotationValue::as_text)
+    }
+
+    /// Get the value of the annotation with the given key, if it is an integer value
+    pub fn get_integer(&self, key: impl AsRef<str>) -> Option<isize> {
+        self.get(key).and_then(AnnotationValue::as_integer)
+    }
+
+    /// Get the value of the annotation with the given key, if it is a float value
+    pub fn get_float(&self, key: impl AsRef<str>) -> Option<f64> {
+        self.get(key).and_then(AnnotationValue::as_float)
+    }
+
+    /// Get the value of the annotation with the given key, if it is a boolean value
+    pub fn get_boolean(&self, key: impl AsRef<str>) -> Option<bool> {
+        self.get(key).and_then(AnnotationValue::as_boolean)
+    }
+
+    /// Set the annotation with the given key, returning the previous value if there was one.
+    /// A new key is added after all existing keys.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<AnnotationValue>,
+    ) -> Option<AnnotationValue> {
+        let key = key.into();
+        let value = value.into();
+        if let Some((_, existing)) = self.values.iter_mut().find(|(k, _)| *k == key) {
+            Some(std::mem::replace(existing, value))
+        } else {
+            self.values.push((key, value));
+            None
+        }
+    }
+
+    /// Remove the annotation with the given key, returning its value if it was present
+    pub fn remove(&mut self, key: impl AsRef<str>) -> Option<AnnotationValue> {
+        let index = self.values.iter().position(|(k, _)| k == key.as_ref())?;
+        Some(self.values.remove(index).1)
+    }
+
+    /// Remove all annotations
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Get the text of the REMARK lines (after the remark-type-number) saving these annotations
+    /// for the given target, which identifies the annotated item and cannot contain spaces
+    /// in its parts. Keys and values are percent encoded so they are single ASCII tokens, long
+    /// values are continued on lines starting with `+`.
+    pub(crate) fn remark_lines(&self, target: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (key, value) in &self.values {
+            let mut line = format!(
+                "{ANNOTATION_REMARK_TAG} {target} {} {}",
+                encode(key),
+                value.type_tag()
+            );
+            let mut rest = encode(&value.to_string());
+            loop {
+                let space = ANNOTATION_REMARK_WIDTH.saturating_sub(line.len() + 1);
+                let chunk: String = rest.drain(..space.min(rest.len())).collect();
+                if !chunk.is_empty() {
+                    line.push(' ');
+                    line.push_str(&chunk);
+                }
+                if rest.is_empty() {
+                    lines.push(line);
+                    break;
+                }
+                lines.push(std::mem::replace(
+                    &mut line,
+                    format!("{ANNOTATION_REMARK_TAG} +"),
+                ));
+            }
+        }
+        lines
+    }
+}
+
+/// An annotation read from the REMARK lines of a PDB file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AnnotationRecord {
+    /// The tokens identifying the annotated item
+    pub target: Vec<String>,
+    /// The key of the annotation
+    pub key: String,
+    /// The value of the annotation
+    pub value: AnnotationValue,
+}
+
+/// Parse the annotations from the given REMARK texts (after the remark-type-number), the
+/// texts not starting with the annotation tag are ignored. The number of tokens identifying
+/// the target is determined by its first token: `PDB` (1), `CHAIN` (3), `RESIDUE` (5), or
+/// `ATOM` (3). Returns the annotations and the texts that could not be parsed.
+pub(crate) fn parse_annotation_remarks<'a>(
+    remarks: impl IntoIterator<Item = &'a str>,
+) -> (Vec<AnnotationRecord>, Vec<String>) {
+    let mut records = Vec::new();
+    let mut invalid = Vec::new();
+    // The target, key, type tag, and encoded value of the annotation being read
+    let mut current: Option<(Vec<String>, String, String, String)> = None;
+    let mut finish = |current: Option<(Vec<String>, String, String, String)>,
+                      invalid: &mut Vec<String>| {
+        if let Some((target, key, tag, value)) = current {
+            match (decode(&key), decode(&value)) {
+                (Some(key), Some(value)) => match AnnotationValue::parse(&tag, value) {
+                    Some(value) => records.push(AnnotationRecord { target, key, value }),
+                    None => invalid.push(format!("{} {key} {tag}", target.join(" "))),
+                },
+                _ => invalid.push(format!("{} {key} {tag}", target.join(" "))),
+            }
+        }
+    };
+    for text in remarks {
+        let mut tokens = text.split_whitespace();
+        if tokens.next() != Some(ANNOTATION_REMARK_TAG) {
+            continue;
+        }
+        let tokens: Vec<&str> = tokens.collect();
+        if tokens.first() == Some(&"+") {
+            match (&mut current, tokens.get(1)) {
+                (Some((_, _, _, value)), Some(chunk)) => value.push_str(chunk),
+                (Some(_), None) => (),
+                (None, _) => invalid.push(text.to_string()),
+            }
+            continue;
+        }
+        finish(current.take(), &mut invalid);
+        let target_length = match tokens.first() {
+            Some(&"PDB") => 1,
+            Some(&"CHAIN" | &"ATOM") => 3,
+            Some(&"RESIDUE") => 5,
+            _ => {
+                invalid.push(text.to_string());
+                continue;
+            }
+        };
+        if tokens.len() < target_length + 2 {
+            invalid.push(text.to_string());
+            continue;
+        }
+        current = Some((
+            tokens[..target_length]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            tokens[target_length].to_string(),
+            tokens[target_length + 1].to_string(),
+            tokens.get(target_length + 2).unwrap_or(&"").to_string(),
+        ));
+    }
+    finish(current, &mut invalid);
+    (records, invalid)
+}
+
+/// Percent encode all characters which are not ASCII graphic characters, and the percent sign
+/// itself, so the text is a single token in a PDB file
+fn encode(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_graphic() && byte != b'%' {
+            output.push(byte as char);
+        } else {
+            output.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    output
+}
+
+/// Decode percent encoded text, returns `None` if the encoding is invalid
+fn decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut iter = text.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let high = (iter.next()? as char).to_digit(16)?;
+            let low = (iter.next()? as char).to_digit(16)?;
+            bytes.push(u8::try_from(high * 16 + low).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding() {
+        for text in ["", "plain", "with space", "100%", "Ångström\n"] {
+            assert_eq!(decode(&encode(text)).as_deref(), Some(text));
+        }
+        assert_eq!(encode("a b%"), "a%20b%25");
+        assert_eq!(decode("%2"), None);
+    }
+
+    #[test]
+    fn remark_round_trip() {
+        let mut annotations = Annotations::new();
+        annotations.insert("name", "a long text value ".repeat(10));
+        annotations.insert("count", 42_isize);
+        annotations.insert("score", 0.1);
+        annotations.insert("flag", true);
+        annotations.insert("empty", "");
+        let lines = annotations.remark_lines("RESIDUE 1 A 12 .");
+        assert!(lines
+            .iter()
+            .all(|line| line.len() <= ANNOTATION_REMARK_WIDTH));
+        let (records, invalid) = parse_annotation_remarks(lines.iter().map(String::as_str));
+        assert!(invalid.is_empty());
+        assert_eq!(records.len(), annotations.len());
+        for (record, (key, value)) in records.iter().zip(annotations.iter()) {
+            assert_eq!(record.target, vec!["RESIDUE", "1", "A", "12", "."]);
+            assert_eq!(record.key, key);
+            assert_eq!(&record.value, value);
+        }
+    }
+
+    #[test]
+    fn insert_replaces() {
+        let mut annotations = Annotations::new();
+        assert_eq!(annotations.insert("a", 1_isize), None);
+        assert_eq!(annotations.insert("b", 2_isize), None);
+        assert_eq!(
+            annotations.insert("a", 3_isize),
+            Some(AnnotationValue::Integer(1))
+        );
+        assert_eq!(
+            annotations.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(annotations.remove("b"), Some(AnnotationValue::Integer(2)));
+        assert_eq!(annotations.len(), 1);
+    }
+}
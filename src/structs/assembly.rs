@@ -0,0 +1,157 @@
+use crate::TransformationMatrix;
+
+/// The REMARK number of the biological assemblies
+const ASSEMBLY_REMARK: usize = 350;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A biological assembly (biomolecule) as given in the REMARK 350 records, the oligomeric state
+/// of the molecule which is thought to be biologically relevant. It is generated by applying the
+/// transformations of each part to the chains of that part.
+pub struct BiologicalAssembly {
+    /// The serial number of the biomolecule
+    pub serial_number: usize,
+    /// The biological unit as determined by the authors, eg `DIMERIC`
+    pub author_determined: Option<String>,
+    /// The quaternary structure as determined by software, eg `DIMERIC`
+    pub software_determined: Option<String>,
+    /// The parts of the assembly, each with the chains and the transformations to apply to them
+    pub parts: Vec<AssemblyPart>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A set of chains with the transformations (the BIOMT records) needed to generate their copies
+/// in a [`BiologicalAssembly`]. The transformations are given in orthogonal coordinates in Aͦ.
+pub struct AssemblyPart {
+    /// The identifiers of the chains
+    pub chains: Vec<String>,
+    /// The transformations applied to every chain
+    pub transformations: Vec<TransformationMatrix>,
+}
+
+impl BiologicalAssembly {
+    /// Get the identifiers of all chains in this assembly, in the order they were given.
+    pub fn chains(&self) -> impl Iterator<Item = &str> + '_ {
+        self.parts
+            .iter()
+            .flat_map(|part| part.chains.iter().map(String::as_str))
+    }
+
+    /// Parse the assemblies from the text of the given remarks, all remarks with a different
+    /// number than 350 are ignored, as well as any BIOMT rows which cannot be parsed.
+    pub(crate) fn from_remarks<'a>(
+        remarks: impl Iterator<Item = &'a (usize, String)>,
+    ) -> Vec<Self> {
+        let mut assemblies: Vec<BiologicalAssembly> = Vec::new();
+        for (_, text) in remarks.filter(|(number, _)| *number == ASSEMBLY_REMARK) {
+            let text = text.trim();
+            if let Some(number) = text.strip_prefix("BIOMOLECULE:") {
+                if let Ok(serial_number) = number.trim().parse() {
+                    assemblies.push(BiologicalAssembly {
+                        serial_number,
+                        author_determined: None,
+                        software_determined: None,
+                        parts: Vec::new(),
+                    });
+                }
+                continue;
+            }
+            let assembly = match assemblies.last_mut() {
+                Some(assembly) => assembly,
+                None => continue,
+            };
+            let chains = |list: &str| -> Vec<String> {
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            };
+            if let Some(unit) = text.strip_prefix("AUTHOR DETERMINED BIOLOGICAL UNIT:") {
+                assembly.author_determined = Some(unit.trim().to_string());
+            } else if let Some(unit) =
+                text.strip_prefix("SOFTWARE DETERMINED QUATERNARY STRUCTURE:")
+            {
+                assembly.software_determined = Some(unit.trim().to_string());
+            } else if let Some(list) = text.strip_prefix("APPLY THE FOLLOWING TO CHAINS:") {
+                assembly.parts.push(AssemblyPart {
+                    chains: chains(list),
+                    transformations: Vec::new(),
+                });
+            } else if let Some(list) = text.strip_prefix("AND CHAINS:") {
+                if let Some(part) = assembly.parts.last_mut() {
+                    part.chains.extend(chains(list));
+                }
+            } else if text.starts_with("BIOMT") {
+                if let (Some(part), Some(row)) = (assembly.parts.last_mut(), biomt_row(text)) {
+                    if row.0 == 0 {
+                        part.transformations.push(TransformationMatrix::identity());
+                    }
+                    if let Some(transformation) = part.transformations.last_mut() {
+                        transformation.matrix_mut()[row.0] = row.1;
+                    }
+                }
+            }
+        }
+        assemblies
+    }
+}
+
+/// Parse a BIOMT row (`BIOMTn serial m1 m2 m3 v`), giving the zero based row index and values
+fn biomt_row(text: &str) -> Option<(usize, [f64; 4])> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let (name, a, b, c, d) = match tokens.as_slice() {
+        [name, _serial, a, b, c, d] => (name, a, b, c, d),
+        _ => return None,
+    };
+    let row = match name.strip_prefix("BIOMT")? {
+        "1" => 0,
+        "2" => 1,
+        "3" => 2,
+        _ => return None,
+    };
+    Some((
+        row,
+        [
+            a.parse().ok()?,
+            b.parse().ok()?,
+            c.parse().ok()?,
+            d.parse().ok()?,
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let remarks: Vec<(usize, String)> = [
+            "BIOMOLECULE: 1",
+            "AUTHOR DETERMINED BIOLOGICAL UNIT: DIMERIC",
+            "APPLY THE FOLLOWING TO CHAINS: A,",
+            "   AND CHAINS: B",
+            "  BIOMT1   1  1.000000  0.000000  0.000000        0.00000",
+            "  BIOMT2   1  0.000000  1.000000  0.000000        0.00000",
+            "  BIOMT3   1  0.000000  0.000000  1.000000        0.00000",
+            "  BIOMT1   2 -1.000000  0.000000  0.000000       10.00000",
+            "  BIOMT2   2  0.000000 -1.000000  0.000000        0.00000",
+            "  BIOMT3   2  0.000000  0.000000  1.000000        5.00000",
+        ]
+        .iter()
+        .map(|text| (350, (*text).to_string()))
+        .collect();
+        let assemblies = BiologicalAssembly::from_remarks(remarks.iter());
+        assert_eq!(assemblies.len(), 1);
+        assert_eq!(assemblies[0].author_determined.as_deref(), Some("DIMERIC"));
+        assert_eq!(assemblies[0].chains().collect::<Vec<_>>(), ["A", "B"]);
+        let part = &assemblies[0].parts[0];
+        assert_eq!(part.transformations[0], TransformationMatrix::identity());
+        assert_eq!(
+            part.transformations[1].apply((0.0, 0.0, 0.0)),
+            (10.0, 0.0, 5.0)
+        );
+    }
+}
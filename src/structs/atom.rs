@@ -2,6 +2,7 @@
 use crate::reference_tables;
 use crate::structs::*;
 use crate::transformation::TransformationMatrix;
+use crate::{Context, ErrorLevel, PDBError, ReadOptions, StrictnessLevel};
 use std::cmp::Ordering;
 use std::convert::TryInto;
 use std::fmt;
@@ -37,6 +38,10 @@ pub struct Atom {
     charge: isize,
     /// The anisotropic temperature factors, if applicable
     atf: Option<[[f64; 3]; 3]>,
+    /// Extra text found after column 80 in PDB files, if captured
+    trailing_columns: Option<String>,
+    /// Custom annotations on this Atom
+    annotations: Annotations,
 }
 
 impl Atom {
@@ -93,6 +98,8 @@ impl Atom {
                 element,
                 charge,
                 atf: None,
+                trailing_columns: None,
+                annotations: Annotations::new(),
             })
         } else {
             None
@@ -220,6 +227,12 @@ impl Atom {
         self.serial_number = new_serial_number;
     }
 
+    /// Get the key used to order Atoms, the serial number. This is the ordering used by
+    /// [`Conformer::sort`] and the `Ord` implementation of Atom.
+    pub const fn sort_key(&self) -> usize {
+        self.serial_number
+    }
+
     /// Get the name of the atom. The name will be trimmed (whitespace removed) and changed to ASCII uppercase.
     pub fn name(&self) -> &str {
         &self.name
@@ -312,6 +325,12 @@ impl Atom {
         self.element = Some(element);
     }
 
+    /// Get the mass of this atom in Dalton, the standard atomic weight of its element (see
+    /// [`Element::weight`]). Returns `None` if the element is unknown or has no standard weight.
+    pub fn mass(&self) -> Option<f64> {
+        self.element.as_ref().and_then(Element::weight)
+    }
+
     /// Get the charge of the atom.
     /// In PDB files the charge is one digit with a sign.
     pub const fn charge(&self) -> isize {
@@ -345,6 +364,27 @@ impl Atom {
         self.atf
     }
 
+    /// Get the extra text found after column 80 of the line defining this atom in a PDB file, if
+    /// it was captured, see [`crate::ReadOptions::set_trailing_columns`]. This text is not saved.
+    pub fn trailing_columns(&self) -> Option<&str> {
+        self.trailing_columns.as_deref()
+    }
+
+    /// Set the extra text found after column 80 of the line defining this atom.
+    pub fn set_trailing_columns(&mut self, text: Option<String>) {
+        self.trailing_columns = text;
+    }
+
+    /// Get the custom annotations of this Atom, see [`Annotations`].
+    pub const fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
+
+    /// Get the custom annotations of this Atom mutably, see [`Annotations`].
+    pub fn annotations_mut(&mut self) -> &mut Annotations {
+        &mut self.annotations
+    }
+
     /// Set the anisotropic temperature factors.
     pub fn set_anisotropic_temperature_factors(&mut self, factors: [[f64; 3]; 3]) {
         self.atf = Some(factors);
@@ -574,27 +614,124 @@ impl Atom {
                     })
             })
     }
+
+    /// Check if this Atom is approximately equal to the given Atom. All properties are compared
+    /// as for `==`, except for the position, occupancy, B factor, and anisotropic temperature
+    /// factors, which are seen as equal if they differ at most `tolerance`. This is useful to
+    /// compare structures after a round trip through a file format with limited precision.
+    pub fn approx_eq(&self, other: &Atom, tolerance: f64) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() <= tolerance;
+        self.serial_number == other.serial_number
+            && self.name() == other.name()
+            && self.element() == other.element()
+            && self.charge() == other.charge()
+            && close(self.x, other.x)
+            && close(self.y, other.y)
+            && close(self.z, other.z)
+            && close(self.occupancy, other.occupancy)
+            && close(self.b_factor, other.b_factor)
+            && match (self.atf, other.atf) {
+                (Some(a), Some(b)) => a
+                    .iter()
+                    .flatten()
+                    .zip(b.iter().flatten())
+                    .all(|(a, b)| close(*a, *b)),
+                (None, None) => true,
+                _ => false,
+            }
+    }
 }
 
 impl fmt::Display for Atom {
+    /// Format this Atom as a fixed-width ATOM or HETATM line as written in PDB files. As the
+    /// Atom does not know its context, the residue name and chain id are left empty and the
+    /// residue serial number is 0. To get the complete line use the [`Display`](fmt::Display)
+    /// implementation of a hierarchy containing this Atom, like [`AtomConformerResidueChain`].
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "ATOM ID: {}, Number: {}, Element: {}, X: {}, Y: {}, Z: {}, OCC: {}, B: {}, ANISOU: {}",
-            self.name(),
-            self.serial_number(),
-            self.element()
-                .map_or_else(|| "".to_string(), ToString::to_string),
-            self.x(),
-            self.y(),
-            self.z(),
-            self.occupancy(),
-            self.b_factor(),
-            self.atf.is_some()
+            "{}",
+            crate::save::atom_line(self, (None, "", "", (0, None)))
         )
     }
 }
 
+impl TryFrom<&str> for Atom {
+    type Error = PDBError;
+    /// Parse a single ATOM or HETATM line into an Atom. The residue and chain information in the
+    /// line is ignored, as an Atom does not contain these.
+    ///
+    /// ## Errors
+    /// Fails if the line is not an ATOM or HETATM line, or if it contains errors that fail at
+    /// [`StrictnessLevel::Medium`], like invalid numbers.
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let hetero = match line.get(..6) {
+            Some("ATOM  ") => false,
+            Some("HETATM") => true,
+            _ => {
+                return Err(PDBError::new(
+                    ErrorLevel::BreakingError,
+                    "Not an atom record",
+                    "The line is not an ATOM or HETATM record.",
+                    Context::show(line),
+                ))
+            }
+        };
+        let (item, errors) = crate::pdb_lexer::lex_atom(1, line, hetero, &ReadOptions::default())?;
+        if let Some(error) = errors
+            .into_iter()
+            .find(|e| e.fails(StrictnessLevel::Medium))
+        {
+            return Err(error);
+        }
+        match item {
+            crate::pdb_lexer::LexItem::Atom(
+                hetero,
+                serial_number,
+                name,
+                _,
+                _,
+                _,
+                _,
+                _,
+                x,
+                y,
+                z,
+                occupancy,
+                b_factor,
+                _,
+                element,
+                charge,
+                trailing_columns,
+            ) => {
+                let mut atom = Self::new(
+                    hetero,
+                    serial_number.unwrap_or_default(),
+                    name,
+                    x,
+                    y,
+                    z,
+                    occupancy,
+                    b_factor,
+                    element,
+                    charge,
+                )
+                .ok_or_else(|| {
+                    PDBError::new(
+                        ErrorLevel::BreakingError,
+                        "Invalid atom",
+                        "The atom name or element contains invalid characters.",
+                        Context::show(line),
+                    )
+                })?;
+                atom.set_trailing_columns(trailing_columns);
+                Ok(atom)
+            }
+            _ => unreachable!("The lexer gives an atom for ATOM and HETATM lines"),
+        }
+    }
+}
+
 impl Clone for Atom {
     /// The clone implementation needs to use the constructor to guarantee the uniqueness of the counter
     fn clone(&self) -> Self {
@@ -612,6 +749,8 @@ impl Clone for Atom {
         )
         .expect("Invalid Atom properties in a clone");
         atom.atf = self.atf;
+        atom.trailing_columns = self.trailing_columns.clone();
+        atom.annotations = self.annotations.clone();
         atom
     }
 }
@@ -635,13 +774,14 @@ impl Eq for Atom {}
 
 impl PartialOrd for Atom {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.serial_number.cmp(&other.serial_number))
+        Some(self.cmp(other))
     }
 }
 
+/// Atoms are ordered by their serial number, see [`Atom::sort_key`].
 impl Ord for Atom {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.serial_number.cmp(&other.serial_number)
+        self.sort_key().cmp(&other.sort_key())
     }
 }
 
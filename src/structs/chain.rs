@@ -1,4 +1,6 @@
 #![allow(dead_code)]
+use crate::alignment::{self, Alignment};
+use crate::geometry;
 use crate::structs::*;
 use crate::transformation::TransformationMatrix;
 use doc_cfg::doc_cfg;
@@ -13,9 +15,11 @@ pub struct Chain {
     /// The identifier of this Chain
     id: String,
     /// The Residues making up this Chain
-    residues: Vec<Residue>,
+    residues: Children<Residue>,
     /// A possible reference to a database for this chain
     database_reference: Option<DatabaseReference>,
+    /// Custom annotations on this Chain
+    annotations: Annotations,
 }
 
 impl<'a> Chain {
@@ -30,8 +34,28 @@ impl<'a> Chain {
     pub fn new(id: impl AsRef<str>) -> Option<Chain> {
         prepare_identifier(id).map(|id| Chain {
             id,
-            residues: Vec::new(),
+            residues: Children::new(),
             database_reference: None,
+            annotations: Annotations::new(),
+        })
+    }
+
+    /// Create a new Chain with space for at least the given number of Residues, to prevent
+    /// reallocations when the number of Residues is known beforehand.
+    ///
+    /// ## Arguments
+    /// * `id` - the identifier
+    /// * `residues` - the number of Residues to reserve space for
+    ///
+    /// ## Fails
+    /// It returns `None` if the identifier is an invalid character.
+    #[must_use]
+    pub fn with_capacity(id: impl AsRef<str>, residues: usize) -> Option<Chain> {
+        prepare_identifier(id).map(|id| Chain {
+            id,
+            residues: Vec::with_capacity(residues).into(),
+            database_reference: None,
+            annotations: Annotations::new(),
         })
     }
 
@@ -47,6 +71,7 @@ impl<'a> Chain {
             id,
             residues: residues.collect(),
             database_reference: None,
+            annotations: Annotations::new(),
         })
     }
 
@@ -55,6 +80,12 @@ impl<'a> Chain {
         &self.id
     }
 
+    /// Get the key used to order Chains, the identifier compared as text (`A`, `AA`, `B`). This
+    /// is the ordering used by [`Model::sort`] and the `Ord` implementation of Chain.
+    pub fn sort_key(&self) -> &str {
+        self.id()
+    }
+
     /// Set the ID of the Chain, returns `false` if the new id is an invalid character.
     /// The ID will be changed to uppercase as requested by PDB/PDBx standard.
     pub fn set_id(&mut self, new_id: impl AsRef<str>) -> bool {
@@ -76,6 +107,81 @@ impl<'a> Chain {
         self.database_reference = Some(reference);
     }
 
+    /// Get the custom annotations of this Chain, see [`Annotations`].
+    pub const fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
+
+    /// Get the custom annotations of this Chain mutably, see [`Annotations`].
+    pub fn annotations_mut(&mut self) -> &mut Annotations {
+        &mut self.annotations
+    }
+
+    /// Renumber the Residues of this Chain to the numbering of the referenced database sequence
+    /// (eg UniProt), as given by the DBREF record. Residues within the PDB segment of the
+    /// reference are shifted by the offset between the start of the PDB and database segments,
+    /// insertion codes are kept. Residues outside of the segment (like expression tags, ligands,
+    /// and waters) are left untouched. The positions of the database reference and its sequence
+    /// differences (SEQADV) are updated as well, so these stay consistent with the new numbering.
+    ///
+    /// ## Errors
+    /// Fails if this Chain has no database reference, or if the new numbering would give two
+    /// Residues the same number. In both cases the Chain is not changed.
+    pub fn renumber_to_database(&mut self) -> Result<(), String> {
+        let reference = self
+            .database_reference
+            .as_ref()
+            .ok_or_else(|| format!("Chain {} has no database reference", self.id))?;
+        let offset = reference.database_position.start - reference.pdb_position.start;
+        let segment = reference.pdb_position.clone();
+        let within = |serial: isize, insertion: Option<&str>| {
+            (serial, insertion) >= (segment.start, segment.start_insert.as_deref())
+                && (serial, insertion) <= (segment.end, segment.end_insert.as_deref())
+        };
+        if offset == 0 {
+            return Ok(());
+        }
+
+        let mut ids = std::collections::HashSet::new();
+        for residue in self.residues() {
+            let (serial, insertion) = residue.id();
+            let serial = if within(serial, insertion) {
+                serial + offset
+            } else {
+                serial
+            };
+            if !ids.insert((serial, insertion)) {
+                return Err(format!(
+                    "Renumbering chain {} to the database numbering gives multiple residues number {}{}",
+                    self.id,
+                    serial,
+                    insertion.unwrap_or_default()
+                ));
+            }
+        }
+
+        for residue in &mut self.residues {
+            if within(residue.serial_number(), residue.insertion_code()) {
+                residue.set_serial_number(residue.serial_number() + offset);
+            }
+        }
+        if let Some(reference) = self.database_reference.as_mut() {
+            for difference in &mut reference.differences {
+                if within(difference.residue.1, difference.residue.2.as_deref()) {
+                    difference.residue.1 += offset;
+                }
+            }
+            reference.pdb_position.start += offset;
+            reference.pdb_position.end += offset;
+        }
+        Ok(())
+    }
+
+    /// Reserve space for at least `additional` more Residues in this Chain.
+    pub fn reserve(&mut self, additional: usize) {
+        self.residues.reserve(additional);
+    }
+
     /// Get the number of Residues making up this Chain
     pub fn residue_count(&self) -> usize {
         self.residues.len()
@@ -94,7 +200,14 @@ impl<'a> Chain {
 
     /// Get the number of Atoms making up this Chain
     pub fn atom_count(&self) -> usize {
-        self.residues().map(Residue::atom_count).sum()
+        self.residues
+            .atom_count(|residues| residues.iter().map(Residue::atom_count).sum())
+    }
+
+    /// Clear the cached number of Atoms, for changes made to the children of this Chain without
+    /// going through it, like through the mutable hierarchies.
+    pub(crate) fn clear_atom_count(&mut self) {
+        self.residues.clear_atom_count();
     }
 
     /// Get the number of Atoms making up this Chain in parallel
@@ -363,6 +476,15 @@ impl<'a> Chain {
             .flat_map(|r| r.atoms_with_hierarchy().map(move |h| h.extend(r)))
     }
 
+    /// Get a parallel iterator of references to a struct containing all atoms with their hierarchy making up this Chain.
+    #[doc_cfg(feature = "rayon")]
+    pub fn par_atoms_with_hierarchy(
+        &'a self,
+    ) -> impl ParallelIterator<Item = hierarchy::AtomConformerResidue<'a>> + 'a {
+        self.par_residues()
+            .flat_map_iter(|r| r.atoms_with_hierarchy().map(move |h| h.extend(r)))
+    }
+
     /// Get an iterator of mutable references to a struct containing all atoms with their hierarchy making up this Chain.
     pub fn atoms_with_hierarchy_mut(
         &'a mut self,
@@ -476,6 +598,18 @@ impl<'a> Chain {
         }
     }
 
+    /// Remove all Residues with the given serial number, regardless of their insertion code.
+    /// Returns `true` if any Residue was removed.
+    ///
+    /// ## Arguments
+    /// * `serial_number` - the serial number of the Residues to remove (see Residue.serial_number())
+    pub fn remove_residue_by_serial_number(&mut self, serial_number: isize) -> bool {
+        let count = self.residues.len();
+        self.residues
+            .retain(|residue| residue.serial_number() != serial_number);
+        self.residues.len() != count
+    }
+
     /// Remove the specified Residue. Returns `true` if a matching Residue was found and removed.
     /// Removes the first matching Residue from the list.
     ///
@@ -530,9 +664,344 @@ impl<'a> Chain {
     pub fn par_sort(&mut self) {
         self.residues.par_sort();
     }
+
+    /// Get the sequence of this Chain as the names of its polymer residues, using the name of
+    /// the first Conformer of each Residue. The hetero residues after the last residue with
+    /// standard (non hetero) Atoms, like ligands and waters, are not part of the sequence.
+    pub fn sequence(&self) -> Vec<&str> {
+        self.polymer_residues()
+            .filter_map(|residue| residue.conformer(0).map(Conformer::name))
+            .collect()
+    }
+
+    /// Get the Residues making up the sequence of this Chain, see [`Chain::sequence`].
+    pub(crate) fn polymer_residues(&self) -> impl DoubleEndedIterator<Item = &Residue> + '_ {
+        let end = self
+            .residues
+            .iter()
+            .rposition(|residue| residue.atoms().any(|atom| !atom.hetero()))
+            .map_or(0, |index| index + 1);
+        self.residues[..end]
+            .iter()
+            .filter(|residue| residue.conformer(0).is_some())
+    }
+
+    /// Returns whether this Chain is a nucleic acid (DNA or RNA) chain, meaning that most of the
+    /// residues making up its sequence (see [`Chain::sequence`]) are nucleotides.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/nucleic.pdb").unwrap();
+    /// assert!(pdb.chains().all(Chain::is_nucleic));
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// assert!(!pdb.chains().any(Chain::is_nucleic));
+    /// ```
+    pub fn is_nucleic(&self) -> bool {
+        let (mut nucleotides, mut total) = (0, 0);
+        for residue in self.polymer_residues() {
+            total += 1;
+            if residue.conformer(0).map_or(false, Conformer::is_nucleotide) {
+                nucleotides += 1;
+            }
+        }
+        nucleotides * 2 > total
+    }
+
+    /// Calculate the backbone torsion angles, the glycosidic torsion angle, and the sugar pucker
+    /// of all nucleotides of this Chain, see [`crate::geometry::nucleic_torsions`]. The residues
+    /// which are not nucleotides (see [`Conformer::is_nucleotide`]) are skipped.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/nucleic.pdb").unwrap();
+    /// for (residue, torsions) in pdb.chain(0).unwrap().nucleic_torsions() {
+    ///     if let Some(pucker) = torsions.pucker {
+    ///         println!("{:?} {}", residue.id(), pucker.conformation());
+    ///     }
+    /// }
+    /// ```
+    pub fn nucleic_torsions(&self) -> Vec<(&Residue, geometry::NucleicTorsions)> {
+        let nucleotides: Vec<&Residue> = self
+            .polymer_residues()
+            .filter(|residue| residue.conformer(0).map_or(false, Conformer::is_nucleotide))
+            .collect();
+        nucleotides
+            .iter()
+            .enumerate()
+            .map(|(index, residue)| {
+                let previous = index.checked_sub(1).map(|i| nucleotides[i]);
+                let next = nucleotides.get(index + 1).copied();
+                (
+                    *residue,
+                    geometry::nucleic_torsions(previous, residue, next),
+                )
+            })
+            .collect()
+    }
+
+    /// Get the sequence of the amino acids of this Chain, see [`Chain::sequence`]
+    fn protein_sequence(&self) -> Vec<&str> {
+        self.sequence()
+            .into_iter()
+            .filter(|name| crate::reference_tables::is_amino_acid(name))
+            .collect()
+    }
+
+    /// Estimate the isoelectric point of this Chain from the amino acids in its sequence, see
+    /// [`crate::sequence::isoelectric_point`].
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let pi = pdb.chain(0).unwrap().isoelectric_point();
+    /// assert!(pi > 6.5 && pi < 8.0);
+    /// ```
+    pub fn isoelectric_point(&self) -> f64 {
+        crate::sequence::isoelectric_point(&self.protein_sequence())
+    }
+
+    /// Calculate the molar extinction coefficient at 280 nm of this Chain from the amino acids in
+    /// its sequence, see [`crate::sequence::extinction_coefficient`].
+    pub fn extinction_coefficient(&self) -> crate::sequence::ExtinctionCoefficient {
+        crate::sequence::extinction_coefficient(&self.protein_sequence())
+    }
+
+    /// Calculate the grand average of hydropathy (GRAVY) of this Chain from the amino acids in
+    /// its sequence, see [`crate::sequence::gravy`]. Returns `None` if there are no amino acids.
+    pub fn gravy(&self) -> Option<f64> {
+        crate::sequence::gravy(&self.protein_sequence())
+    }
+
+    /// Calculate the hydropathy profile of this Chain from the amino acids in its sequence with
+    /// the given window size, see [`crate::sequence::hydropathy_profile`].
+    pub fn hydropathy_profile(&self, window: usize) -> Vec<f64> {
+        crate::sequence::hydropathy_profile(&self.protein_sequence(), window)
+    }
+
+    /// Get the molecular weight of this Chain in Dalton, as the sum of the molecular weights of
+    /// the first Conformer of every Residue (see [`Residue::molecular_weight`]), so
+    /// alternative locations are only counted once. This includes ligands and waters in this
+    /// Chain. Only the atoms present are counted, so hydrogens are missing for most
+    /// experimental structures.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let weight = pdb.chain(0).unwrap().molecular_weight();
+    /// assert!(weight > 8000.0 && weight < 10000.0);
+    /// ```
+    pub fn molecular_weight(&self) -> f64 {
+        self.residues().map(Residue::molecular_weight).sum()
+    }
+
+    /// Get the center of mass of this Chain, using the Atoms of the first Conformer of every
+    /// Residue weighted by their masses, see [`crate::geometry::center_of_mass`]. Returns `None`
+    /// if no Atom has a known mass.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let (x, y, z) = pdb.chain(0).unwrap().center_of_mass().unwrap();
+    /// println!("Center of mass at {x:.2} {y:.2} {z:.2}");
+    /// ```
+    pub fn center_of_mass(&self) -> Option<(f64, f64, f64)> {
+        let atoms: Vec<&Atom> = self
+            .residues()
+            .filter_map(|residue| residue.conformer(0))
+            .flat_map(Conformer::atoms)
+            .collect();
+        geometry::center_of_mass(&atoms)
+    }
+
+    /// Get the net formal charge of this Chain, as the sum of the charges of the Atoms of the
+    /// first Conformer of every Residue, see [`Residue::net_charge`].
+    pub fn net_charge(&self) -> isize {
+        self.residues().map(Residue::net_charge).sum()
+    }
+
+    /// Get the net charge of this Chain assuming the standard protonation states at neutral pH,
+    /// see [`Residue::net_charge_at_neutral_ph`]. This is useful when preparing systems for
+    /// simulation, as formal charges are often missing in experimental structures.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// // Ubiquitin has 11 Arg and Lys, and 11 Asp and Glu
+    /// assert_eq!(pdb.chain(0).unwrap().net_charge_at_neutral_ph(), 0);
+    /// ```
+    pub fn net_charge_at_neutral_ph(&self) -> isize {
+        self.residues().map(Residue::net_charge_at_neutral_ph).sum()
+    }
+
+    /// Calculate the backbone torsion angles (phi, psi, and omega) of all amino acids of this
+    /// Chain, see [`crate::geometry::backbone_torsions`]. The residues which are not amino acids
+    /// (see [`Conformer::is_amino_acid`]) are skipped.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// for (residue, torsions) in pdb.chain(0).unwrap().backbone_torsions() {
+    ///     if let (Some(phi), Some(psi)) = (torsions.phi, torsions.psi) {
+    ///         println!("{:?} {phi:.0} {psi:.0}", residue.id());
+    ///     }
+    /// }
+    /// ```
+    pub fn backbone_torsions(&self) -> Vec<(&Residue, geometry::BackboneTorsions)> {
+        let amino_acids: Vec<&Residue> = self
+            .polymer_residues()
+            .filter(|residue| residue.conformer(0).map_or(false, Conformer::is_amino_acid))
+            .collect();
+        amino_acids
+            .iter()
+            .enumerate()
+            .map(|(index, residue)| {
+                let previous = index.checked_sub(1).map(|i| amino_acids[i]);
+                let next = amino_acids.get(index + 1).copied();
+                (
+                    *residue,
+                    geometry::backbone_torsions(previous, residue, next),
+                )
+            })
+            .collect()
+    }
+
+    /// Get the backbone torsion angles phi and psi in degrees of all amino acids of this Chain,
+    /// for example to draw a Ramachandran plot, see [`Chain::backbone_torsions`]. An angle is
+    /// `None` for the residues at the termini, next to chain breaks, or with missing backbone
+    /// atoms. Use [`crate::ramachandran`] to classify the angles of a whole structure.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let chain = pdb.chain(0).unwrap();
+    /// let angles: Vec<_> = chain.ramachandran().collect();
+    /// assert!(angles[0].1.is_none() && angles[0].2.is_some());
+    /// assert_eq!(angles.iter().filter(|(_, phi, psi)| phi.is_some() && psi.is_some()).count(), 74);
+    /// ```
+    pub fn ramachandran(&self) -> impl Iterator<Item = (&Residue, Option<f64>, Option<f64>)> + '_ {
+        self.backbone_torsions()
+            .into_iter()
+            .map(|(residue, torsions)| (residue, torsions.phi, torsions.psi))
+    }
+
+    /// Mutate the Residue with the given id (see [`Residue::id`]) into the given standard amino
+    /// acid, see [`Residue::mutate`]. Returns the names of the atoms of the new amino acid which
+    /// are missing and need to be rebuilt.
+    ///
+    /// ## Errors
+    /// Fails if there is no Residue with the given id or if the given name is not a standard
+    /// amino acid, the Chain is not changed.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let chain = pdb.chain_mut(0).unwrap();
+    /// let missing = chain.mutate((48, None), "ALA").unwrap();
+    /// assert!(missing.is_empty());
+    /// assert!(chain.mutate((1000, None), "ALA").is_err());
+    /// ```
+    pub fn mutate(
+        &mut self,
+        residue_id: (isize, Option<&str>),
+        name: &str,
+    ) -> Result<Vec<String>, String> {
+        let id = self.id.clone();
+        self.residues
+            .iter_mut()
+            .find(|residue| residue.id() == residue_id)
+            .ok_or_else(|| {
+                format!(
+                    "Chain {id} has no residue {}{}",
+                    residue_id.0,
+                    residue_id.1.unwrap_or_default()
+                )
+            })?
+            .mutate(name)
+    }
+
+    /// Find the breaks in the polymer of this Chain, where consecutive residues are not bonded or
+    /// residues are missing from the numbering, see [`crate::geometry::chain_breaks`].
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// for chain_break in pdb.chain(0).unwrap().breaks() {
+    ///     println!("Break after {:?} ({:?} Å)", chain_break.before.id(), chain_break.distance);
+    /// }
+    /// ```
+    pub fn breaks(&self) -> Vec<geometry::ChainBreak<'_>> {
+        geometry::chain_breaks(self)
+    }
+
+    /// Get the B factor profile of this Chain, giving the mean and the (population) standard
+    /// deviation of the B factors of the Atoms of each Residue, in the order of the Residues.
+    /// Residues without Atoms are skipped. High values point to flexible or poorly ordered
+    /// regions, see [`crate::validate_b_factors`] to find residues that stand out.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// for (residue, mean, deviation) in pdb.chain(0).unwrap().bfactor_profile() {
+    ///     println!("{:?} {mean:.2} ± {deviation:.2}", residue.id());
+    /// }
+    /// ```
+    pub fn bfactor_profile(&self) -> Vec<(&Residue, f64, f64)> {
+        self.residues()
+            .filter_map(|residue| {
+                let b_factors: Vec<f64> = residue.atoms().map(Atom::b_factor).collect();
+                if b_factors.is_empty() {
+                    return None;
+                }
+                #[allow(clippy::cast_precision_loss)]
+                let count = b_factors.len() as f64;
+                let mean = b_factors.iter().sum::<f64>() / count;
+                let variance = b_factors.iter().map(|b| (b - mean).powi(2)).sum::<f64>() / count;
+                Some((residue, mean, variance.sqrt()))
+            })
+            .collect()
+    }
+
+    /// Cap the termini of the protein in this Chain with ideal geometry, with neutral ACE and NME
+    /// residues or with charged termini, see [`crate::build::cap_termini`].
+    ///
+    /// ## Errors
+    /// Fails if the Chain has no amino acid residues or if the C terminal residue has no O atom,
+    /// the Chain is not changed.
+    pub fn cap_termini(&mut self, caps: crate::build::TerminalCaps) -> Result<(), String> {
+        crate::build::cap_termini(self, caps)
+    }
+
+    /// Align the sequence of this Chain to the sequence of the other Chain (see
+    /// [`Chain::sequence`]) with a global alignment, see [`crate::alignment::global`]. The
+    /// positions in the alignment are indices into the sequences.
+    pub fn align_sequence(&self, other: &Chain, scoring: alignment::Scoring) -> Alignment {
+        alignment::global(&self.sequence(), &other.sequence(), scoring)
+    }
+
+    /// Get the sequence identity between this Chain and the other Chain, as the number of
+    /// identical residues in the optimal global alignment of the sequences (see
+    /// [`Chain::sequence`]) divided by the length of the longest sequence. This gives a value
+    /// between 0.0 and 1.0, where 1.0 means both chains have exactly the same sequence.
+    pub fn sequence_identity(&self, other: &Chain) -> f64 {
+        let (a, b) = (self.sequence(), other.sequence());
+        let longest = a.len().max(b.len());
+        if longest == 0 {
+            return 0.0;
+        }
+        // Maximise the number of identical positions, with free gaps and mismatches
+        let alignment = alignment::global(&a, &b, alignment::Scoring::IDENTITY);
+        #[allow(clippy::cast_precision_loss)]
+        {
+            alignment.identical as f64 / longest as f64
+        }
+    }
+
+    /// Check if this Chain is approximately equal to the given Chain, with all Atoms compared
+    /// with [`Atom::approx_eq`] and all other properties compared as for `==`.
+    pub fn approx_eq(&self, other: &Chain, tolerance: f64) -> bool {
+        self.id == other.id
+            && self.database_reference == other.database_reference
+            && self.annotations == other.annotations
+            && self.residues.len() == other.residues.len()
+            && self
+                .residues
+                .iter()
+                .zip(&other.residues)
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
 }
 
 use std::fmt;
+use std::ops;
 impl fmt::Display for Chain {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -546,13 +1015,14 @@ impl fmt::Display for Chain {
 
 impl PartialOrd for Chain {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.id().cmp(other.id()))
+        Some(self.cmp(other))
     }
 }
 
+/// Chains are ordered by their identifier, see [`Chain::sort_key`].
 impl Ord for Chain {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.id().cmp(other.id())
+        self.sort_key().cmp(other.sort_key())
     }
 }
 
@@ -563,6 +1033,65 @@ impl Extend<Residue> for Chain {
     }
 }
 
+impl FromIterator<Residue> for Chain {
+    /// Create a Chain with id `A` from the given Residues, see [`Chain::from_iter`] to give the
+    /// id.
+    fn from_iter<T: IntoIterator<Item = Residue>>(iter: T) -> Self {
+        let mut chain = Self::new("A").expect("A is a valid Chain id");
+        chain.extend(iter);
+        chain
+    }
+}
+
+impl<'a> IntoIterator for &'a Chain {
+    type Item = &'a Residue;
+    type IntoIter = std::slice::Iter<'a, Residue>;
+    /// Iterate over the Residues of this Chain, see [`Chain::residues`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.residues.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Chain {
+    type Item = &'a mut Residue;
+    type IntoIter = std::slice::IterMut<'a, Residue>;
+    /// Iterate mutably over the Residues of this Chain, see [`Chain::residues_mut`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.residues.iter_mut()
+    }
+}
+
+impl ops::Index<isize> for Chain {
+    type Output = Residue;
+    /// Get the first Residue with the given serial number, regardless of its insertion code,
+    /// see [`Chain::residues`] for the fallible lookup.
+    ///
+    /// ## Panics
+    /// It panics if there is no Residue with the given serial number.
+    fn index(&self, serial_number: isize) -> &Self::Output {
+        self.residues()
+            .find(|r| r.serial_number() == serial_number)
+            .unwrap_or_else(|| {
+                panic!("there is no Residue with serial number {serial_number} in this Chain")
+            })
+    }
+}
+
+impl ops::IndexMut<isize> for Chain {
+    /// Get the first Residue with the given serial number mutably, regardless of its insertion
+    /// code.
+    ///
+    /// ## Panics
+    /// It panics if there is no Residue with the given serial number.
+    fn index_mut(&mut self, serial_number: isize) -> &mut Self::Output {
+        self.residues_mut()
+            .find(|r| r.serial_number() == serial_number)
+            .unwrap_or_else(|| {
+                panic!("there is no Residue with serial number {serial_number} in this Chain")
+            })
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -578,6 +1107,17 @@ mod tests {
         assert_eq!(a.id(), "atom");
     }
 
+    #[test]
+    fn test_capacity() {
+        let mut a = Chain::with_capacity("A", 76).unwrap();
+        assert_eq!(Chain::with_capacity("R̊", 76), None);
+        assert!(a.residues.capacity() >= 76);
+        assert_eq!(a.residue_count(), 0);
+        a.reserve(100);
+        assert!(a.residues.capacity() >= 100);
+        assert_eq!(a, Chain::new("A").unwrap());
+    }
+
     #[test]
     fn ordering_and_equality() {
         let a = Chain::new("A").unwrap();
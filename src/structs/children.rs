@@ -0,0 +1,161 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The value of the cached count when it is not known
+const UNKNOWN: usize = usize::MAX;
+
+/// The children of a Model, Chain, or Residue together with the cached number of Atoms they
+/// contain. Any mutable access to the children clears the cached count, so it is recomputed
+/// on the next request. The mutable hierarchies, which hand out children through raw pointers,
+/// clear the counts of all levels above the one they give access to. As the counts of the children are cached as well, only the path to a
+/// changed child has to be recounted. The cache does not take part in comparisons.
+pub(crate) struct Children<T> {
+    /// The children themselves
+    items: Vec<T>,
+    /// The number of Atoms in all children, or [`UNKNOWN`]
+    atom_count: AtomicUsize,
+}
+
+impl<T> Children<T> {
+    /// Create an empty list of children.
+    pub(crate) const fn new() -> Self {
+        Children {
+            items: Vec::new(),
+            atom_count: AtomicUsize::new(UNKNOWN),
+        }
+    }
+
+    /// Get the number of Atoms in all children, using the cached value if known and otherwise
+    /// counting them with the given function and caching the result.
+    pub(crate) fn atom_count(&self, count: impl FnOnce(&[T]) -> usize) -> usize {
+        match self.atom_count.load(Ordering::Relaxed) {
+            UNKNOWN => {
+                let total = count(&self.items);
+                self.atom_count.store(total, Ordering::Relaxed);
+                total
+            }
+            total => total,
+        }
+    }
+
+    /// Clear the cached count, for changes to the children made without mutable access to this
+    /// list, like through the raw pointers of the mutable hierarchies.
+    pub(crate) fn clear_atom_count(&mut self) {
+        *self.atom_count.get_mut() = UNKNOWN;
+    }
+}
+
+impl<T> Deref for Children<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.items
+    }
+}
+
+impl<T> DerefMut for Children<T> {
+    /// Get the children mutably, this clears the cached count as they could be changed.
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        self.clear_atom_count();
+        &mut self.items
+    }
+}
+
+impl<T> From<Vec<T>> for Children<T> {
+    fn from(items: Vec<T>) -> Self {
+        Children {
+            items,
+            atom_count: AtomicUsize::new(UNKNOWN),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Children<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
+impl<T> IntoIterator for Children<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Children<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Children<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
+
+impl<T: Clone> Clone for Children<T> {
+    fn clone(&self) -> Self {
+        Children {
+            items: self.items.clone(),
+            atom_count: AtomicUsize::new(self.atom_count.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Children<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.items.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Children<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T: Eq> Eq for Children<T> {}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Children<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.items.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Children<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Children::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_cleared_on_change() {
+        let mut children: Children<usize> = vec![1, 2, 3].into();
+        let sum = |items: &[usize]| items.iter().sum();
+        assert_eq!(children.atom_count(sum), 6);
+        // The cached value is used while unchanged
+        assert_eq!(children.atom_count(|_| 0), 6);
+        children.push(4);
+        assert_eq!(children.atom_count(sum), 10);
+        children[0] = 5;
+        assert_eq!(children.atom_count(sum), 14);
+        assert_eq!(children.clone().atom_count(|_| 0), 14);
+        assert_eq!(children, vec![5, 2, 3, 4].into());
+    }
+}
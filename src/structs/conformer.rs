@@ -7,6 +7,7 @@ use doc_cfg::doc_cfg;
 use rayon::prelude::*;
 use std::cmp::Ordering;
 use std::fmt;
+use std::ops;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -99,6 +100,13 @@ impl Conformer {
         (&self.name, self.alternative_location())
     }
 
+    /// Get the key used to order Conformers, the name followed by the alternative location,
+    /// where a Conformer without an alternative location comes first. This is the ordering
+    /// used by [`Residue::sort`] and the `Ord` implementation of Conformer.
+    pub fn sort_key(&self) -> (&str, Option<&str>) {
+        self.id()
+    }
+
     /// Get the modification of this Conformer e.g., chemical or post-translational. These is saved in the MODRES records in the PDB file.
     pub const fn modification(&self) -> Option<&(String, String)> {
         self.modification.as_ref()
@@ -229,6 +237,48 @@ impl Conformer {
         reference_tables::is_amino_acid(self.name())
     }
 
+    /// Returns whether this Conformer is a (deoxy)ribonucleotide.
+    pub fn is_nucleotide(&self) -> bool {
+        reference_tables::is_nucleotide(self.name())
+    }
+
+    /// Returns whether this Conformer is a water molecule.
+    pub fn is_water(&self) -> bool {
+        reference_tables::is_water(self.name())
+    }
+
+    /// Get the aromatic rings of this Conformer, with the bonds and bond orders inferred from the
+    /// geometry, see [`crate::chemistry::infer_bonds`] and [`crate::chemistry::aromatic_rings`].
+    /// This is intended for ligands (hetero residues). Every ring is given as its atoms in order
+    /// around the ring.
+    pub fn aromatic_rings(&self) -> Vec<Vec<&Atom>> {
+        let atoms: Vec<&Atom> = self.atoms().collect();
+        crate::chemistry::aromatic_rings(&atoms, &crate::chemistry::infer_bonds(&atoms))
+            .into_iter()
+            .map(|ring| ring.into_iter().map(|index| atoms[index]).collect())
+            .collect()
+    }
+
+    /// Write this Conformer as a SMILES string, with the bonds and bond orders inferred from the
+    /// geometry, see [`crate::chemistry::infer_bonds`] and [`crate::chemistry::smiles`]. This is
+    /// intended for ligands (hetero residues), to hand them to cheminformatics toolkits.
+    pub fn to_smiles(&self) -> String {
+        let atoms: Vec<&Atom> = self.atoms().collect();
+        crate::chemistry::smiles(&atoms, &crate::chemistry::infer_bonds(&atoms))
+    }
+
+    /// Get the molecular formula of this Conformer, see [`crate::chemistry::formula`].
+    pub fn formula(&self) -> String {
+        let atoms: Vec<&Atom> = self.atoms().collect();
+        crate::chemistry::formula(&atoms)
+    }
+
+    /// Get the molecular weight of this Conformer in Dalton, see
+    /// [`crate::chemistry::molecular_weight`].
+    pub fn molecular_weight(&self) -> f64 {
+        crate::chemistry::molecular_weight(self.atoms())
+    }
+
     /// Remove all Atoms matching the given predicate. As this is done in place this is the fastest way to remove Atoms from this Conformer.
     pub fn remove_atoms_by<F>(&mut self, predicate: F)
     where
@@ -364,6 +414,20 @@ impl Conformer {
     pub fn par_sort(&mut self) {
         self.atoms.par_sort();
     }
+
+    /// Check if this Conformer is approximately equal to the given Conformer, with all Atoms
+    /// compared with [`Atom::approx_eq`] and all other properties compared as for `==`.
+    pub fn approx_eq(&self, other: &Conformer, tolerance: f64) -> bool {
+        self.name == other.name
+            && self.alternative_location == other.alternative_location
+            && self.modification == other.modification
+            && self.atoms.len() == other.atoms.len()
+            && self
+                .atoms
+                .iter()
+                .zip(&other.atoms)
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
 }
 
 #[allow(clippy::use_debug)]
@@ -380,13 +444,14 @@ impl fmt::Display for Conformer {
 
 impl PartialOrd for Conformer {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.id().cmp(&other.id()))
+        Some(self.cmp(other))
     }
 }
 
+/// Conformers are ordered by their name and alternative location, see [`Conformer::sort_key`].
 impl Ord for Conformer {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.id().cmp(&other.id())
+        self.sort_key().cmp(&other.sort_key())
     }
 }
 
@@ -397,6 +462,59 @@ impl Extend<Atom> for Conformer {
     }
 }
 
+impl FromIterator<Atom> for Conformer {
+    /// Create a Conformer named `UNK` without alternative location from the given Atoms.
+    fn from_iter<T: IntoIterator<Item = Atom>>(iter: T) -> Self {
+        let mut conformer = Self::new("UNK", None, None).expect("UNK is a valid Conformer name");
+        conformer.extend(iter);
+        conformer
+    }
+}
+
+impl<'a> IntoIterator for &'a Conformer {
+    type Item = &'a Atom;
+    type IntoIter = std::slice::Iter<'a, Atom>;
+    /// Iterate over the Atoms of this Conformer, see [`Conformer::atoms`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.atoms.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Conformer {
+    type Item = &'a mut Atom;
+    type IntoIter = std::slice::IterMut<'a, Atom>;
+    /// Iterate mutably over the Atoms of this Conformer, see [`Conformer::atoms_mut`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.atoms.iter_mut()
+    }
+}
+
+impl ops::Index<&str> for Conformer {
+    type Output = Atom;
+    /// Get the first Atom with the given name, see [`Conformer::atoms`] for the fallible
+    /// lookup.
+    ///
+    /// ## Panics
+    /// It panics if there is no Atom with the given name.
+    fn index(&self, name: &str) -> &Self::Output {
+        self.atoms()
+            .find(|a| a.name() == name)
+            .unwrap_or_else(|| panic!("there is no Atom with name `{name}` in this Conformer"))
+    }
+}
+
+impl ops::IndexMut<&str> for Conformer {
+    /// Get the first Atom with the given name mutably.
+    ///
+    /// ## Panics
+    /// It panics if there is no Atom with the given name.
+    fn index_mut(&mut self, name: &str) -> &mut Self::Output {
+        self.atoms_mut()
+            .find(|a| a.name() == name)
+            .unwrap_or_else(|| panic!("there is no Atom with name `{name}` in this Conformer"))
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -0,0 +1,304 @@
+/// The REMARK number of the geometry and stereochemistry tables
+const GEOMETRY_REMARK: usize = 500;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A residue as listed in the REMARK 500 tables
+pub struct RemarkResidue {
+    /// The serial number of the Model, only given for structures with multiple models
+    pub model: Option<usize>,
+    /// The name of the residue
+    pub name: String,
+    /// The identifier of the Chain
+    pub chain: String,
+    /// The serial number of the residue
+    pub serial_number: isize,
+    /// The insertion code of the residue, if any
+    pub insertion_code: Option<String>,
+}
+
+impl RemarkResidue {
+    /// The serial number and insertion code of the residue, as used in [`crate::Residue::id`].
+    pub fn id(&self) -> (isize, Option<&str>) {
+        (self.serial_number, self.insertion_code.as_deref())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An atom as listed in the REMARK 500 tables
+pub struct RemarkAtom {
+    /// The residue of the atom
+    pub residue: RemarkResidue,
+    /// The name of the atom
+    pub name: String,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Two atoms which are closer together than expected
+pub struct CloseContact {
+    /// The atoms in close contact
+    pub atoms: (RemarkAtom, RemarkAtom),
+    /// The symmetry operation (eg `7555`) applied to the second atom, `None` for contacts in the
+    /// same asymmetric unit
+    pub symmetry_operation: Option<String>,
+    /// The distance between the atoms in Aͦ
+    pub distance: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A covalent bond with a length deviating from the expected value
+pub struct BondDeviation {
+    /// The bonded atoms
+    pub atoms: (RemarkAtom, RemarkAtom),
+    /// The deviation from the expected length in Aͦ
+    pub deviation: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A covalent bond angle deviating from the expected value
+pub struct AngleDeviation {
+    /// The residue the angle is listed for
+    pub residue: RemarkResidue,
+    /// The names of the atoms making up the angle
+    pub atoms: [String; 3],
+    /// The deviation from the expected angle in degrees
+    pub deviation: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A residue with backbone torsion angles outside the expected Ramachandran regions
+pub struct TorsionOutlier {
+    /// The residue
+    pub residue: RemarkResidue,
+    /// The phi angle in degrees
+    pub phi: f64,
+    /// The psi angle in degrees
+    pub psi: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The geometry outliers as deposited in the REMARK 500 records of a PDB file, see
+/// [`crate::PDB::deposited_geometry`]. These can be compared to the checks computed by this
+/// crate, like [`crate::ramachandran`] and [`crate::clash_score`].
+pub struct DepositedGeometry {
+    /// The close contacts, both within the asymmetric unit and with symmetry mates
+    pub close_contacts: Vec<CloseContact>,
+    /// The covalent bond lengths deviating from the expected values
+    pub bond_deviations: Vec<BondDeviation>,
+    /// The covalent bond angles deviating from the expected values
+    pub angle_deviations: Vec<AngleDeviation>,
+    /// The residues with torsion angles outside the expected Ramachandran regions
+    pub torsion_outliers: Vec<TorsionOutlier>,
+}
+
+impl DepositedGeometry {
+    /// Parse the tables from the text of the given remarks, all remarks with a different number
+    /// than 500 are ignored. Rows which cannot be parsed are skipped.
+    pub(crate) fn from_remarks<'a>(remarks: impl Iterator<Item = &'a (usize, String)>) -> Self {
+        let mut geometry = DepositedGeometry::default();
+        let mut subtopic = "";
+        for (_, text) in remarks.filter(|(number, _)| *number == GEOMETRY_REMARK) {
+            if let Some(topic) = text.trim().strip_prefix("SUBTOPIC:") {
+                subtopic = topic.trim();
+                continue;
+            }
+            let tokens: Vec<&str> = text.split_whitespace().collect();
+            match subtopic {
+                "CLOSE CONTACTS IN SAME ASYMMETRIC UNIT" | "CLOSE CONTACTS" => {
+                    if let Some(contact) = close_contact(&tokens) {
+                        geometry.close_contacts.push(contact);
+                    }
+                }
+                "COVALENT BOND LENGTHS" => {
+                    if let Some(deviation) = bond_deviation(&tokens) {
+                        geometry.bond_deviations.push(deviation);
+                    }
+                }
+                "COVALENT BOND ANGLES" => {
+                    if let Some(deviation) = angle_deviation(&tokens) {
+                        geometry.angle_deviations.push(deviation);
+                    }
+                }
+                "TORSION ANGLES" => {
+                    if let Some(outlier) = torsion_outlier(&tokens) {
+                        geometry.torsion_outliers.push(outlier);
+                    }
+                }
+                _ => (),
+            }
+        }
+        geometry
+    }
+
+    /// Check if there are no outliers at all.
+    pub fn is_empty(&self) -> bool {
+        self.close_contacts.is_empty()
+            && self.bond_deviations.is_empty()
+            && self.angle_deviations.is_empty()
+            && self.torsion_outliers.is_empty()
+    }
+}
+
+/// Parse a residue (`[model] name chain serial[insertion code]`) from the start of the given
+/// tokens, returning the remaining tokens. The model number is only parsed if `model` is set.
+fn residue<'a, 'b>(tokens: &'a [&'b str], model: bool) -> Option<(RemarkResidue, &'a [&'b str])> {
+    let (model, tokens) = match tokens.split_first() {
+        Some((first, rest)) if model => match first.parse::<usize>() {
+            Ok(number) => (Some(number), rest),
+            Err(_) => (None, tokens),
+        },
+        _ => (None, tokens),
+    };
+    let (name, chain, serial, rest) = match tokens {
+        [name, chain, serial, rest @ ..] => (name, chain, serial, rest),
+        _ => return None,
+    };
+    if name.len() > 3 {
+        return None;
+    }
+    let split = serial
+        .char_indices()
+        .find(|(index, c)| !(c.is_ascii_digit() || (*index == 0 && *c == '-')))
+        .map_or(serial.len(), |(index, _)| index);
+    let insertion_code = &serial[split..];
+    Some((
+        RemarkResidue {
+            model,
+            name: (*name).to_string(),
+            chain: (*chain).to_string(),
+            serial_number: serial[..split].parse().ok()?,
+            insertion_code: (!insertion_code.is_empty()).then(|| insertion_code.to_string()),
+        },
+        rest,
+    ))
+}
+
+/// Parse a row of a close contacts table: `atom residue atom residue [symmetry] distance`
+fn close_contact(tokens: &[&str]) -> Option<CloseContact> {
+    let (first, tokens) = tokens.split_first()?;
+    let (first_residue, tokens) = residue(tokens, false)?;
+    let (second, tokens) = tokens.split_first()?;
+    let (second_residue, tokens) = residue(tokens, false)?;
+    let (symmetry_operation, distance) = match tokens {
+        [distance] => (None, distance),
+        [symmetry, distance] => (Some((*symmetry).to_string()), distance),
+        _ => return None,
+    };
+    Some(CloseContact {
+        atoms: (
+            RemarkAtom {
+                residue: first_residue,
+                name: (*first).to_string(),
+            },
+            RemarkAtom {
+                residue: second_residue,
+                name: (*second).to_string(),
+            },
+        ),
+        symmetry_operation,
+        distance: distance.parse().ok()?,
+    })
+}
+
+/// Parse a row of a bond lengths table: `[model] residue atom residue atom deviation`
+fn bond_deviation(tokens: &[&str]) -> Option<BondDeviation> {
+    let (first_residue, tokens) = residue(tokens, true)?;
+    let (first, tokens) = tokens.split_first()?;
+    let (mut second_residue, tokens) = residue(tokens, false)?;
+    second_residue.model = first_residue.model;
+    let (second, deviation) = match tokens {
+        [second, deviation] => (second, deviation),
+        _ => return None,
+    };
+    Some(BondDeviation {
+        atoms: (
+            RemarkAtom {
+                residue: first_residue,
+                name: (*first).to_string(),
+            },
+            RemarkAtom {
+                residue: second_residue,
+                name: (*second).to_string(),
+            },
+        ),
+        deviation: deviation.parse().ok()?,
+    })
+}
+
+/// Parse a row of a bond angles table: `[model] residue atom - atom - atom ANGL. DEV. = deviation DEGREES`
+fn angle_deviation(tokens: &[&str]) -> Option<AngleDeviation> {
+    let (residue, tokens) = residue(tokens, true)?;
+    let (a, b, c, deviation) = match tokens {
+        [a, "-", b, "-", c, "ANGL.", "DEV.", "=", deviation, ..] => (a, b, c, deviation),
+        _ => return None,
+    };
+    Some(AngleDeviation {
+        residue,
+        atoms: [(*a).to_string(), (*b).to_string(), (*c).to_string()],
+        deviation: deviation.parse().ok()?,
+    })
+}
+
+/// Parse a row of a torsion angles table: `[model] residue psi phi`
+fn torsion_outlier(tokens: &[&str]) -> Option<TorsionOutlier> {
+    let (residue, tokens) = residue(tokens, true)?;
+    let (psi, phi) = match tokens {
+        [psi, phi] => (psi, phi),
+        _ => return None,
+    };
+    Some(TorsionOutlier {
+        residue,
+        phi: phi.parse().ok()?,
+        psi: psi.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remarks(lines: &[&str]) -> Vec<(usize, String)> {
+        lines
+            .iter()
+            .map(|line| (500, (*line).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn tables() {
+        let remarks = remarks(&[
+            "SUBTOPIC: CLOSE CONTACTS",
+            " ATM1  RES C  SSEQI   ATM2  RES C  SSEQI  SSYMOP   DISTANCE",
+            "  O    HOH A   950     O    HOH A   950     7555     1.42",
+            "SUBTOPIC: COVALENT BOND LENGTHS",
+            "2 MET A  -1A  CG    MET A  -1A  SD      0.211",
+            "SUBTOPIC: COVALENT BOND ANGLES",
+            "   ARG A   9   NE  -  CZ  -  NH1 ANGL. DEV. =   5.2 DEGREES",
+            "SUBTOPIC: TORSION ANGLES",
+            " M RES CSSEQI        PSI       PHI",
+            "   HIS A  55       49.70    -98.63",
+            "SUBTOPIC: CHIRAL CENTERS",
+            "   HIS A  55       49.70    -98.63",
+        ]);
+        let geometry = DepositedGeometry::from_remarks(remarks.iter());
+        assert_eq!(geometry.close_contacts.len(), 1);
+        assert_eq!(
+            geometry.close_contacts[0].symmetry_operation.as_deref(),
+            Some("7555")
+        );
+        let bond = &geometry.bond_deviations[0];
+        assert_eq!(bond.atoms.1.residue.model, Some(2));
+        assert_eq!(bond.atoms.1.residue.id(), (-1, Some("A")));
+        assert_eq!(bond.atoms.1.name, "SD");
+        assert_eq!(geometry.angle_deviations[0].atoms[2], "NH1");
+        assert_eq!(geometry.torsion_outliers.len(), 1);
+        assert_eq!(geometry.torsion_outliers[0].phi, -98.63);
+        assert!(DepositedGeometry::from_remarks(Vec::new().iter()).is_empty());
+    }
+}
@@ -406,6 +406,40 @@ impl Element {
     pub const fn electro_negativity(&self) -> Option<f64> {
         ELEMENT_ELECTRON_NEGATIVITY[self.atomic_number() - 1]
     }
+
+    /// Returns whether this element is a metal. The nonmetals, noble gasses, and the metalloids
+    /// (B, Si, Ge, As, Sb, Te) are not metals.
+    pub const fn is_metal(&self) -> bool {
+        !matches!(
+            self,
+            Element::H
+                | Element::He
+                | Element::B
+                | Element::C
+                | Element::N
+                | Element::O
+                | Element::F
+                | Element::Ne
+                | Element::Si
+                | Element::P
+                | Element::S
+                | Element::Cl
+                | Element::Ar
+                | Element::Ge
+                | Element::As
+                | Element::Se
+                | Element::Br
+                | Element::Kr
+                | Element::Sb
+                | Element::Te
+                | Element::I
+                | Element::Xe
+                | Element::At
+                | Element::Rn
+                | Element::Ts
+                | Element::Og
+        )
+    }
 }
 
 #[allow(clippy::use_debug)]
@@ -452,6 +486,14 @@ mod tests {
         let element: Element = "Cl".try_into().unwrap();
         assert_eq!(Element::Cl.atomic_number(), element.atomic_number());
     }
+
+    #[test]
+    fn metals() {
+        assert!(Element::Zn.is_metal());
+        assert!(Element::Ca.is_metal());
+        assert!(!Element::C.is_metal());
+        assert!(!Element::Se.is_metal());
+    }
 }
 
 /// The symbols of the elements of the periodic table
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+use crate::alignment::{self, Alignment};
+use crate::structs::Chain;
+use std::cmp::Ordering;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A polymer entity, a group of chains sharing the same polymer sequence. This mirrors the
+/// entities in mmCIF files and the molecules (MOL_ID) in the COMPND records of PDB files.
+pub struct Entity {
+    /// The identifier of this entity, unique within a PDB
+    pub id: usize,
+    /// The description of the molecule, as given in the `MOLECULE` token of the COMPND record
+    /// or `_entity.pdbx_description` in mmCIF
+    pub description: Option<String>,
+    /// The identifiers of the chains which are instances of this entity
+    pub chains: Vec<String>,
+    /// The full sequence of the polymer as residue names, including residues which are not
+    /// modelled, as given in SEQRES or `_entity_poly_seq`. Empty if not known.
+    pub sequence: Vec<String>,
+}
+
+impl Entity {
+    /// Create a new Entity with the given arguments
+    #[must_use]
+    pub const fn new(
+        id: usize,
+        description: Option<String>,
+        chains: Vec<String>,
+        sequence: Vec<String>,
+    ) -> Self {
+        Entity {
+            id,
+            description,
+            chains,
+            sequence,
+        }
+    }
+
+    /// Check if the chain with the given identifier is an instance of this entity
+    pub fn contains_chain(&self, chain_id: impl AsRef<str>) -> bool {
+        self.chains.iter().any(|c| c == chain_id.as_ref())
+    }
+
+    /// Align the full sequence of this entity to the sequence of the given Chain (see
+    /// [`Chain::sequence`]) with a global alignment, see [`alignment::global`]. The positions
+    /// of the full sequence aligned to a gap are residues which are not part of the Chain.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1yyf.pdb").unwrap();
+    /// let chain = pdb.chains().next().unwrap();
+    /// let alignment = pdb.chain_entity(chain.id()).unwrap().align_chain(chain);
+    /// assert_eq!(alignment.identical, chain.sequence().len());
+    /// ```
+    pub fn align_chain(&self, chain: &Chain) -> Alignment {
+        alignment::global(
+            &self.sequence,
+            &chain.sequence(),
+            alignment::Scoring::default(),
+        )
+    }
+}
+
+impl PartialOrd for Entity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.id.cmp(&other.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accessors() {
+        let a = Entity::new(
+            1,
+            Some("UBIQUITIN".to_string()),
+            vec!["A".to_string(), "B".to_string()],
+            Vec::new(),
+        );
+        assert!(a.contains_chain("B"));
+        assert!(!a.contains_chain("C"));
+        assert!(Entity::default() < a);
+    }
+}
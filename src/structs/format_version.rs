@@ -0,0 +1,271 @@
+use crate::reference_tables;
+use std::fmt;
+
+/// The version of the PDB format conventions a structure follows. Version 3 (introduced in
+/// 2007) changed the nomenclature of hydrogen atoms (`1HB` became `HB1`), nucleic acid sugar
+/// atoms (`O5*` became `O5'`), and deoxyribonucleotides (`  A` became ` DA`).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FormatVersion {
+    /// Version 2.3 or older
+    V2,
+    /// Version 3.0 or newer
+    V3,
+}
+
+impl fmt::Display for FormatVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatVersion::V2 => write!(f, "v2.3"),
+            FormatVersion::V3 => write!(f, "v3.x"),
+        }
+    }
+}
+
+impl FormatVersion {
+    /// Get the version stated in the text of a REMARK 4 record
+    /// (eg `1UBQ COMPLIES WITH FORMAT V. 3.30, 13-JUL-11`), if any.
+    pub(crate) fn from_remark(text: &str) -> Option<Self> {
+        let (_, version) = text.split_once("FORMAT V.")?;
+        match version.trim_start().chars().next() {
+            Some('1' | '2') => Some(FormatVersion::V2),
+            Some('3') => Some(FormatVersion::V3),
+            _ => None,
+        }
+    }
+
+    /// Get the version of the nomenclature used for the given atom name, if it is specific to
+    /// one version.
+    pub(crate) fn from_atom_name(name: &str) -> Option<Self> {
+        if name.contains('*') || is_old_hydrogen_name(name) {
+            Some(FormatVersion::V2)
+        } else if name.contains('\'') {
+            Some(FormatVersion::V3)
+        } else {
+            None
+        }
+    }
+}
+
+/// Test if the given atom name is a hydrogen name in the version 2 nomenclature, which starts
+/// with a digit (eg `1HB` or `2HG1`).
+fn is_old_hydrogen_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!((chars.next(), chars.next()), (Some('1'..='3'), Some('H')))
+}
+
+/// Convert the given atom name from the version 2 to the version 3 nomenclature, returns `None`
+/// if the name does not need to be changed.
+pub(crate) fn normalize_atom_name(name: &str) -> Option<String> {
+    if is_old_hydrogen_name(name) && name.contains('*') {
+        // Sugar hydrogens are distinguished by the number of primes (1H5* becomes H5')
+        let primes = if name.starts_with('2') { "''" } else { "'" };
+        Some(name[1..].replace('*', primes))
+    } else if is_old_hydrogen_name(name) {
+        Some(format!("{}{}", &name[1..], &name[..1]))
+    } else if name.contains('*') {
+        Some(name.replace('*', "'"))
+    } else {
+        None
+    }
+}
+
+/// Convert the given nucleotide residue name from the version 2 to the version 3 nomenclature,
+/// returns `None` if the name does not need to be changed. Version 2 used the same names for
+/// ribo- and deoxyribonucleotides, so this needs to know if the residue has a 2' oxygen.
+pub(crate) fn normalize_residue_name(name: &str, has_o2: bool) -> Option<&'static str> {
+    match (name, has_o2) {
+        ("A", false) => Some("DA"),
+        ("C", false) => Some("DC"),
+        ("G", false) => Some("DG"),
+        ("T", _) => Some("DT"),
+        ("I", false) => Some("DI"),
+        _ => None,
+    }
+}
+
+/// Test if the hydrogens with the given name stem (like `HB` for `HB2` and `2HB`) are the two
+/// hydrogens of a methylene group, which are numbered 1 and 2 in version 2.3 but 2 and 3 in
+/// version 3. The heavy atom they are bonded to is named after the stem (`CB`).
+fn is_methylene(stem: &str, siblings: usize, names: &[&str]) -> bool {
+    siblings == 2
+        && names
+            .iter()
+            .any(|name| name.starts_with('C') && name.get(1..) == stem.get(1..))
+}
+
+/// Convert the given atom name to the nomenclature of the target version of the PDB format,
+/// returns `None` if the name does not need to be changed. The names of all atoms in the
+/// residue are needed to tell which hydrogens are bonded to the same heavy atom.
+pub(crate) fn remap_atom_name(
+    residue: &str,
+    name: &str,
+    names: &[&str],
+    target: FormatVersion,
+) -> Option<String> {
+    if let Some(renamed) = reference_tables::renamed_atom(residue, name, target) {
+        return Some(renamed.to_string());
+    }
+    match target {
+        FormatVersion::V3 if is_old_hydrogen_name(name) && !name.contains('*') => {
+            // 1HB becomes HB1, or HB2 if it is part of a methylene group
+            let stem = &name[1..];
+            let siblings = names
+                .iter()
+                .filter(|other| is_old_hydrogen_name(other) && other.get(1..) == Some(stem))
+                .count();
+            let number =
+                name[..1].parse::<usize>().ok()? + usize::from(is_methylene(stem, siblings, names));
+            Some(format!("{stem}{number}"))
+        }
+        FormatVersion::V3 => normalize_atom_name(name),
+        FormatVersion::V2 if name.contains('\'') => {
+            // Sugar hydrogens are distinguished by a number instead of the number of primes
+            let stem = name.trim_end_matches('\'');
+            let pair = name.starts_with('H')
+                && names
+                    .iter()
+                    .filter(|other| other.trim_end_matches('\'') == stem)
+                    .count()
+                    == 2;
+            match (pair, name.ends_with("''")) {
+                (true, true) => Some(format!("2{stem}*")),
+                (true, false) => Some(format!("1{stem}*")),
+                _ => Some(name.replace('\'', "*")),
+            }
+        }
+        FormatVersion::V2 => {
+            // HB1 becomes 1HB, or HB2 becomes 1HB if it is part of a methylene group. Hydrogens
+            // named after their heavy atom (like HD1 bonded to ND1) are not numbered.
+            let is_numbered = |other: &str| {
+                other.starts_with('H')
+                    && other.ends_with(|c: char| c.is_ascii_digit())
+                    && !names
+                        .iter()
+                        .any(|heavy| !heavy.starts_with('H') && heavy.get(1..) == other.get(1..))
+            };
+            if !is_numbered(name) || name.len() > 4 {
+                return None;
+            }
+            let stem = &name[..name.len() - 1];
+            let siblings = names
+                .iter()
+                .filter(|other| {
+                    is_numbered(other) && other.len() == name.len() && other.starts_with(stem)
+                })
+                .count();
+            let number = name[stem.len()..].parse::<usize>().ok()?;
+            if siblings < 2 {
+                None
+            } else if is_methylene(stem, siblings, names) && number > 1 {
+                Some(format!("{}{stem}", number - 1))
+            } else {
+                Some(format!("{number}{stem}"))
+            }
+        }
+    }
+}
+
+/// Convert the given deoxyribonucleotide residue name from the version 3 to the version 2.3
+/// nomenclature, returns `None` if the name does not need to be changed.
+pub(crate) fn old_residue_name(name: &str) -> Option<&'static str> {
+    match name {
+        "DA" => Some("A"),
+        "DC" => Some("C"),
+        "DG" => Some("G"),
+        "DT" => Some("T"),
+        "DI" => Some("I"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect() {
+        assert_eq!(
+            FormatVersion::from_remark("1UBQ COMPLIES WITH FORMAT V. 3.30, 13-JUL-11"),
+            Some(FormatVersion::V3)
+        );
+        assert_eq!(
+            FormatVersion::from_remark("1ABC COMPLIES WITH FORMAT V. 2.3, 09-JULY-1998"),
+            Some(FormatVersion::V2)
+        );
+        assert_eq!(FormatVersion::from_remark(""), None);
+        assert_eq!(
+            FormatVersion::from_atom_name("1HB"),
+            Some(FormatVersion::V2)
+        );
+        assert_eq!(
+            FormatVersion::from_atom_name("O5*"),
+            Some(FormatVersion::V2)
+        );
+        assert_eq!(
+            FormatVersion::from_atom_name("O5'"),
+            Some(FormatVersion::V3)
+        );
+        assert_eq!(FormatVersion::from_atom_name("CA"), None);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(normalize_atom_name("1HB").as_deref(), Some("HB1"));
+        assert_eq!(normalize_atom_name("2HG1").as_deref(), Some("HG12"));
+        assert_eq!(normalize_atom_name("C1*").as_deref(), Some("C1'"));
+        assert_eq!(normalize_atom_name("2H5*").as_deref(), Some("H5''"));
+        assert_eq!(normalize_atom_name("CA"), None);
+        assert_eq!(normalize_residue_name("A", false), Some("DA"));
+        assert_eq!(normalize_residue_name("A", true), None);
+        assert_eq!(normalize_residue_name("ALA", false), None);
+    }
+
+    #[test]
+    fn remap() {
+        let round_trip = |residue: &str, old: &[&str], new: &[&str]| {
+            for (version, from, to) in
+                [(FormatVersion::V3, old, new), (FormatVersion::V2, new, old)]
+            {
+                let remapped: Vec<String> = from
+                    .iter()
+                    .map(|name| {
+                        remap_atom_name(residue, name, from, version)
+                            .unwrap_or_else(|| (*name).to_string())
+                    })
+                    .collect();
+                assert_eq!(remapped, to, "{residue} to {version}");
+            }
+        };
+        round_trip(
+            "ALA",
+            &["CB", "1HB", "2HB", "3HB"],
+            &["CB", "HB1", "HB2", "HB3"],
+        );
+        round_trip(
+            "SER",
+            &["CB", "1HB", "2HB", "HG"],
+            &["CB", "HB2", "HB3", "HG"],
+        );
+        round_trip(
+            "ILE",
+            &["CG1", "CD", "1HG1", "2HG1", "1HD1", "2HD1", "3HD1"],
+            &["CG1", "CD1", "HG12", "HG13", "HD11", "HD12", "HD13"],
+        );
+        round_trip("ASN", &["ND2", "1HD2", "2HD2"], &["ND2", "HD21", "HD22"]);
+        round_trip(
+            "HIS",
+            &["ND1", "CD2", "HD1", "HD2"],
+            &["ND1", "CD2", "HD1", "HD2"],
+        );
+        round_trip("LYS", &["N", "1H", "2H", "3H"], &["N", "H1", "H2", "H3"]);
+        round_trip(
+            "DA",
+            &["O1P", "C5*", "1H5*", "2H5*", "H1*"],
+            &["OP1", "C5'", "H5'", "H5''", "H1'"],
+        );
+        round_trip("T", &["C5M"], &["C7"]);
+        assert_eq!(old_residue_name("DA"), Some("A"));
+        assert_eq!(old_residue_name("A"), None);
+    }
+}
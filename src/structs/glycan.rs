@@ -0,0 +1,189 @@
+#![allow(dead_code)]
+use crate::reference_tables;
+use crate::structs::*;
+use rustc_hash::FxHashMap;
+
+/// The way a glycan is attached to the glycosylated residue, based on the atom of the residue
+/// bonded to the first sugar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlycosylationType {
+    /// Bonded to a nitrogen, like the ND2 of asparagine
+    NLinked,
+    /// Bonded to an oxygen, like the OG of serine or the OG1 of threonine
+    OLinked,
+    /// Bonded to a carbon, like the CD1 of tryptophan
+    CLinked,
+    /// Bonded to a sulfur, like the SG of cysteine
+    SLinked,
+    /// Bonded to any other atom
+    Other,
+}
+
+/// A single sugar residue in a [`Glycan`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sugar<'a> {
+    /// The residue of this sugar
+    pub residue: &'a Residue,
+    /// The index of the sugar this sugar is attached to in [`Glycan::sugars`], `None` for the
+    /// first sugar which is attached to the glycosylated residue
+    pub parent: Option<usize>,
+    /// The anomeric carbon of this sugar (generally C1, or C2 for sialic acids), which is
+    /// bonded to the parent
+    pub anomeric_atom: &'a Atom,
+    /// The atom of the parent sugar or of the glycosylated residue bonded to the anomeric carbon
+    pub parent_atom: &'a Atom,
+}
+
+impl<'a> Sugar<'a> {
+    /// Get the linkage of this sugar to its parent sugar as the position of the anomeric carbon
+    /// and the position on the parent, so `(1, 4)` for a 1-4 linkage based on the atoms C1 and
+    /// O4. Gives `None` for the first sugar, or if the atom names are not numbered.
+    pub fn linkage(&self) -> Option<(usize, usize)> {
+        self.parent?;
+        Some((
+            position(self.anomeric_atom.name())?,
+            position(self.parent_atom.name())?,
+        ))
+    }
+}
+
+/// The position of an atom in a sugar ring, the number after the element (`O4` gives 4)
+fn position(name: &str) -> Option<usize> {
+    name.trim_start_matches(|c: char| c.is_ascii_alphabetic())
+        .parse()
+        .ok()
+}
+
+/// A glycan, a tree of sugars covalently attached to a residue, see [`PDB::glycans`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glycan<'a> {
+    /// The glycosylated residue
+    pub site: &'a Residue,
+    /// The way the glycan is attached to the glycosylated residue
+    pub kind: GlycosylationType,
+    /// The sugars making up the tree, starting with the sugar attached to the glycosylated
+    /// residue. Parents are always given before their children.
+    pub sugars: Vec<Sugar<'a>>,
+}
+
+impl<'a> Glycan<'a> {
+    /// Get the number of sugars in this glycan
+    pub fn len(&self) -> usize {
+        self.sugars.len()
+    }
+
+    /// Returns whether this glycan has no sugars, which cannot happen for glycans found with
+    /// [`PDB::glycans`]
+    pub fn is_empty(&self) -> bool {
+        self.sugars.is_empty()
+    }
+
+    /// Get the indices of the sugars directly attached to the sugar at the given index
+    pub fn children(&self, index: usize) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.sugars
+            .iter()
+            .enumerate()
+            .filter(move |(_, sugar)| sugar.parent == Some(index))
+            .map(|(child, _)| child)
+    }
+
+    /// Returns whether this glycan is branched, having a sugar with multiple sugars attached
+    pub fn is_branched(&self) -> bool {
+        (0..self.sugars.len()).any(|index| self.children(index).nth(1).is_some())
+    }
+}
+
+/// Returns whether the given atom is the anomeric carbon of a sugar
+fn is_anomeric(atom: &Atom) -> bool {
+    atom.element() == Some(&Element::C) && matches!(atom.name(), "C1" | "C2")
+}
+
+/// Returns whether the given residue is a sugar, based on the name of its first conformer
+fn is_sugar(residue: &Residue) -> bool {
+    residue
+        .conformer(0)
+        .map_or(false, |c| reference_tables::is_sugar(c.name()))
+}
+
+/// An atom in a bond together with its residue
+type BondedAtom<'a> = (&'a Residue, &'a Atom);
+
+/// Build the glycans from the covalent bonds of the given PDB, see [`PDB::glycans`]
+pub(crate) fn glycans(pdb: &PDB) -> Vec<Glycan<'_>> {
+    let atoms: FxHashMap<usize, BondedAtom<'_>> = pdb
+        .residues()
+        .flat_map(|residue| {
+            residue
+                .atoms()
+                .map(move |atom| (atom.counter(), (residue, atom)))
+        })
+        .collect();
+
+    // The glycosylation sites and the bonds between sugars, as the parent and child
+    let mut roots = Vec::new();
+    let mut links: Vec<(BondedAtom<'_>, BondedAtom<'_>)> = Vec::new();
+    for (a, b, bond) in pdb.bond_counters() {
+        if bond != Bond::Covalent {
+            continue;
+        }
+        let (a, b) = match (atoms.get(&a), atoms.get(&b)) {
+            (Some(a), Some(b)) if !std::ptr::eq(a.0, b.0) => (*a, *b),
+            _ => continue,
+        };
+        let (parent, child) = if is_sugar(b.0) && is_anomeric(b.1) {
+            (a, b)
+        } else if is_sugar(a.0) && is_anomeric(a.1) {
+            (b, a)
+        } else {
+            continue;
+        };
+        if is_sugar(parent.0) {
+            links.push((parent, child));
+        } else if parent
+            .0
+            .conformer(0)
+            .map_or(false, Conformer::is_amino_acid)
+        {
+            roots.push((parent, child));
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|((site, site_atom), (residue, anomeric_atom))| {
+            let mut sugars = vec![Sugar {
+                residue,
+                parent: None,
+                anomeric_atom,
+                parent_atom: site_atom,
+            }];
+            let mut index = 0;
+            while index < sugars.len() {
+                let current = sugars[index].residue;
+                for ((_, parent_atom), (child, anomeric_atom)) in links
+                    .iter()
+                    .filter(|((parent, _), _)| std::ptr::eq(*parent, current))
+                {
+                    // Prevent cycles from bonds in both directions
+                    if !sugars.iter().any(|s| std::ptr::eq(s.residue, *child)) {
+                        sugars.push(Sugar {
+                            residue: child,
+                            parent: Some(index),
+                            anomeric_atom,
+                            parent_atom,
+                        });
+                    }
+                }
+                index += 1;
+            }
+            let kind = match site_atom.element() {
+                Some(Element::N) => GlycosylationType::NLinked,
+                Some(Element::O) => GlycosylationType::OLinked,
+                Some(Element::C) => GlycosylationType::CLinked,
+                Some(Element::S) => GlycosylationType::SLinked,
+                _ => GlycosylationType::Other,
+            };
+            Glycan { site, kind, sugars }
+        })
+        .collect()
+}
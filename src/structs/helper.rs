@@ -45,6 +45,63 @@ pub fn number_to_base26(mut num: usize) -> String {
     output.iter().rev().collect::<String>()
 }
 
+const BASE36: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Encodes a number in the hybrid-36 format for a field of the given width, as used for atom
+/// (width 5) and residue (width 4) serial numbers which do not fit in decimal in PDB files.
+/// Numbers up to `10^width - 1` are written in decimal, followed by uppercase base 36 numbers
+/// starting at `A000`, and lowercase base 36 numbers starting at `a000`. Returns `None` if the
+/// number does not fit in the field.
+pub fn encode_hybrid36(num: usize, width: usize) -> Option<String> {
+    let width = u32::try_from(width).ok()?;
+    let decimal = 10_usize.checked_pow(width)?;
+    if num < decimal {
+        return Some(num.to_string());
+    }
+    let block = 26 * 36_usize.checked_pow(width - 1)?;
+    let offset = 10 * 36_usize.pow(width - 1);
+    let (mut value, lowercase) = match num - decimal {
+        n if n < block => (n + offset, false),
+        n if n < 2 * block => (n - block + offset, true),
+        _ => return None,
+    };
+    let mut output = Vec::with_capacity(width as usize);
+    for _ in 0..width {
+        output.push(BASE36.as_bytes()[value % 36] as char);
+        value /= 36;
+    }
+    let text: String = output.iter().rev().collect();
+    Some(if lowercase { text.to_lowercase() } else { text })
+}
+
+/// Decodes a number in the hybrid-36 format for a field of the given width, see
+/// [`encode_hybrid36`]. Surrounding whitespace is ignored. Returns `None` if the text is not a
+/// valid hybrid-36 number, this includes negative numbers.
+pub fn decode_hybrid36(text: &str, width: usize) -> Option<usize> {
+    let text = text.trim();
+    if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) {
+        return text.parse().ok();
+    }
+    let uppercase = text
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase());
+    let lowercase = text
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase());
+    if text.len() != width
+        || !text.starts_with(|c: char| c.is_ascii_alphabetic())
+        || !(uppercase || lowercase)
+    {
+        return None;
+    }
+    let width = u32::try_from(width).ok()?;
+    let value = usize::from_str_radix(text, 36).ok()?;
+    let decimal = 10_usize.checked_pow(width)?;
+    let block = 26 * 36_usize.checked_pow(width - 1)?;
+    let value = value - 10 * 36_usize.pow(width - 1) + decimal;
+    Some(if lowercase { value + block } else { value })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +135,32 @@ mod tests {
         assert_eq!(number_to_base26(15250), "WOO");
         assert_eq!(number_to_base26(396514), "WOOO");
     }
+    #[test]
+    fn hybrid36() {
+        assert_eq!(encode_hybrid36(99_999, 5).as_deref(), Some("99999"));
+        assert_eq!(encode_hybrid36(100_000, 5).as_deref(), Some("A0000"));
+        assert_eq!(encode_hybrid36(100_035, 5).as_deref(), Some("A000Z"));
+        assert_eq!(encode_hybrid36(10_000, 4).as_deref(), Some("A000"));
+        assert_eq!(
+            encode_hybrid36(10_000 + 26 * 36_usize.pow(3), 4).as_deref(),
+            Some("a000")
+        );
+        assert_eq!(encode_hybrid36(10_000 + 52 * 36_usize.pow(3), 4), None);
+        for num in [
+            0,
+            42,
+            9_999,
+            10_000,
+            123_456,
+            10_000 + 26 * 36_usize.pow(3) + 7,
+        ] {
+            let text = encode_hybrid36(num, 4).unwrap();
+            assert_eq!(decode_hybrid36(&text, 4), Some(num));
+        }
+        assert_eq!(decode_hybrid36(" A0000", 5), Some(100_000));
+        assert_eq!(decode_hybrid36("  -12", 5), None);
+        assert_eq!(decode_hybrid36("A00a0", 5), None);
+        assert_eq!(decode_hybrid36("A00", 5), None);
+        assert_eq!(decode_hybrid36("*****", 5), None);
+    }
 }
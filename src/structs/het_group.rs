@@ -0,0 +1,96 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A hetero group, like a ligand, ion, or modified residue, as described by the HET, HETNAM,
+/// HETSYN, and FORMUL records.
+pub struct HetGroup {
+    /// The identifier of the group, the residue name used in its HETATM records, eg `HEM`
+    pub id: String,
+    /// The chemical name from the HETNAM records, eg `PROTOPORPHYRIN IX CONTAINING FE`
+    pub name: Option<String>,
+    /// The synonyms from the HETSYN records
+    pub synonyms: Vec<String>,
+    /// The chemical formula from the FORMUL records, with the number of occurrences if more than
+    /// one, eg `2(C10 H15 N5 O10 P2)`
+    pub formula: Option<String>,
+    /// The component number from the FORMUL records
+    pub component_number: Option<usize>,
+    /// If the group is water, marked with an asterisk in the FORMUL records
+    pub water: bool,
+    /// The residues of this group as given by the HET records
+    pub residues: Vec<HetResidue>,
+}
+
+impl HetGroup {
+    /// Create a new HetGroup with the given identifier, without any description or residues.
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        HetGroup {
+            id: id.into(),
+            name: None,
+            synonyms: Vec::new(),
+            formula: None,
+            component_number: None,
+            water: false,
+            residues: Vec::new(),
+        }
+    }
+
+    /// Check if the given residue is an instance of this group.
+    pub fn contains(&self, chain: &str, id: (isize, Option<&str>)) -> bool {
+        self.residues
+            .iter()
+            .any(|residue| residue.chain == chain && residue.id() == id)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A residue listed in a HET record
+pub struct HetResidue {
+    /// The identifier of the Chain
+    pub chain: String,
+    /// The serial number of the residue
+    pub serial_number: isize,
+    /// The insertion code of the residue, if any
+    pub insertion_code: Option<String>,
+    /// The number of HETATM records for this residue
+    pub atom_count: usize,
+    /// A free text description, if any
+    pub description: Option<String>,
+}
+
+impl HetResidue {
+    /// The serial number and insertion code of the residue, as used in [`crate::Residue::id`].
+    pub fn id(&self) -> (isize, Option<&str>) {
+        (self.serial_number, self.insertion_code.as_deref())
+    }
+}
+
+/// Get the group with the given identifier, adding a new group if it is not present yet.
+pub(crate) fn het_group_mut(groups: &mut Vec<HetGroup>, id: String) -> &mut HetGroup {
+    let index = if let Some(index) = groups.iter().position(|group| group.id == id) {
+        index
+    } else {
+        groups.push(HetGroup::new(id));
+        groups.len() - 1
+    };
+    &mut groups[index]
+}
+
+/// Add the text of a continuation line to the given text. Text broken off after a hyphen is
+/// joined directly, otherwise the lines are joined with a space.
+pub(crate) fn continue_text(text: &mut String, continuation: &str) {
+    if !text.is_empty() && !text.ends_with('-') {
+        text.push(' ');
+    }
+    text.push_str(continuation);
+}
+
+/// Split the full text of the HETSYN records of a group into the separate synonyms.
+pub(crate) fn split_synonyms(text: &str) -> Vec<String> {
+    text.split(';')
+        .map(str::trim)
+        .filter(|synonym| !synonym.is_empty())
+        .map(String::from)
+        .collect()
+}
@@ -385,6 +385,23 @@ impl<'a> ContainsAtomConformerResidueChain for AtomConformerResidueChain<'a> {
     }
 }
 
+impl<'a> std::fmt::Display for AtomConformerResidueChain<'a> {
+    /// Format the Atom as a fixed-width ATOM or HETATM line, with the residue name, alternative
+    /// location, chain id, and residue serial number from its hierarchy.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = crate::save::atom_line(
+            self.atom,
+            (
+                self.conformer.alternative_location(),
+                self.conformer.name(),
+                self.chain.id(),
+                self.residue.id(),
+            ),
+        );
+        write!(f, "{line}")
+    }
+}
+
 // ______ AtomConformerResidueChainModel
 
 impl<'a> AtomConformerResidueChainModel<'a> {
@@ -443,6 +460,23 @@ impl<'a> ContainsAtomConformerResidueChainModel for AtomConformerResidueChainMod
     }
 }
 
+impl<'a> std::fmt::Display for AtomConformerResidueChainModel<'a> {
+    /// Format the Atom as a fixed-width ATOM or HETATM line, with the residue name, alternative
+    /// location, chain id, and residue serial number from its hierarchy.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = crate::save::atom_line(
+            self.atom,
+            (
+                self.conformer.alternative_location(),
+                self.conformer.name(),
+                self.chain.id(),
+                self.residue.id(),
+            ),
+        );
+        write!(f, "{line}")
+    }
+}
+
 // ______ AtomConformerMut
 
 impl<'a> AtomConformerMut<'a> {
@@ -557,7 +591,11 @@ impl<'a> ContainsAtomConformerMut for AtomConformerResidueMut<'a> {
         unsafe { self.atom.as_mut().unwrap() }
     }
     fn conformer_mut(&mut self) -> &mut Conformer {
-        unsafe { self.conformer.as_mut().unwrap() }
+        // The number of Atoms could change, so the counts cached higher up are cleared
+        unsafe {
+            self.residue.as_mut().unwrap().clear_atom_count();
+            self.conformer.as_mut().unwrap()
+        }
     }
 }
 
@@ -638,7 +676,12 @@ impl<'a> ContainsAtomConformerMut for AtomConformerResidueChainMut<'a> {
         unsafe { self.atom.as_mut().unwrap() }
     }
     fn conformer_mut(&mut self) -> &mut Conformer {
-        unsafe { self.conformer.as_mut().unwrap() }
+        // The number of Atoms could change, so the counts cached higher up are cleared
+        unsafe {
+            self.residue.as_mut().unwrap().clear_atom_count();
+            self.chain.as_mut().unwrap().clear_atom_count();
+            self.conformer.as_mut().unwrap()
+        }
     }
 }
 
@@ -650,7 +693,11 @@ impl<'a> ContainsAtomConformerResidue for AtomConformerResidueChainMut<'a> {
 
 impl<'a> ContainsAtomConformerResidueMut for AtomConformerResidueChainMut<'a> {
     fn residue_mut(&mut self) -> &mut Residue {
-        unsafe { self.residue.as_mut().unwrap() }
+        // The number of Atoms could change, so the counts cached higher up are cleared
+        unsafe {
+            self.chain.as_mut().unwrap().clear_atom_count();
+            self.residue.as_mut().unwrap()
+        }
     }
 }
 
@@ -731,7 +778,13 @@ impl<'a> ContainsAtomConformerMut for AtomConformerResidueChainModelMut<'a> {
         unsafe { self.atom.as_mut().unwrap() }
     }
     fn conformer_mut(&mut self) -> &mut Conformer {
-        unsafe { self.conformer.as_mut().unwrap() }
+        // The number of Atoms could change, so the counts cached higher up are cleared
+        unsafe {
+            self.residue.as_mut().unwrap().clear_atom_count();
+            self.chain.as_mut().unwrap().clear_atom_count();
+            self.model.as_mut().unwrap().clear_atom_count();
+            self.conformer.as_mut().unwrap()
+        }
     }
 }
 
@@ -743,7 +796,12 @@ impl<'a> ContainsAtomConformerResidue for AtomConformerResidueChainModelMut<'a>
 
 impl<'a> ContainsAtomConformerResidueMut for AtomConformerResidueChainModelMut<'a> {
     fn residue_mut(&mut self) -> &mut Residue {
-        unsafe { self.residue.as_mut().unwrap() }
+        // The number of Atoms could change, so the counts cached higher up are cleared
+        unsafe {
+            self.chain.as_mut().unwrap().clear_atom_count();
+            self.model.as_mut().unwrap().clear_atom_count();
+            self.residue.as_mut().unwrap()
+        }
     }
 }
 
@@ -755,7 +813,11 @@ impl<'a> ContainsAtomConformerResidueChain for AtomConformerResidueChainModelMut
 
 impl<'a> ContainsAtomConformerResidueChainMut for AtomConformerResidueChainModelMut<'a> {
     fn chain_mut(&mut self) -> &mut Chain {
-        unsafe { self.chain.as_mut().unwrap() }
+        // The number of Atoms could change, so the counts cached higher up are cleared
+        unsafe {
+            self.model.as_mut().unwrap().clear_atom_count();
+            self.chain.as_mut().unwrap()
+        }
     }
 }
 
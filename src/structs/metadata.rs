@@ -0,0 +1,154 @@
+use super::het_group::continue_text;
+
+/// The REMARK number of the resolution
+pub(crate) const RESOLUTION_REMARK: usize = 2;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The descriptive metadata of a structure, as given by the title section of a PDB file (HEADER,
+/// TITLE, COMPND, SOURCE, KEYWDS, EXPDTA, AUTHOR, and JRNL) and the resolution from REMARK 2.
+/// The identifier of the entry is kept in [`crate::PDB::identifier`].
+pub struct Metadata {
+    /// The classification of the molecule, eg `HYDROLASE`
+    pub classification: Option<String>,
+    /// The deposition date as given in the HEADER, eg `02-JAN-87`
+    pub deposition_date: Option<String>,
+    /// The title of the experiment
+    pub title: Option<String>,
+    /// The specification of the molecules as given in the COMPND records, made up of tokens like
+    /// `MOL_ID: 1` separated by semicolons
+    pub compound: Option<String>,
+    /// The biological source of the molecules as given in the SOURCE records, in the same format
+    /// as the compound
+    pub source: Option<String>,
+    /// The keywords describing the molecules
+    pub keywords: Vec<String>,
+    /// The experimental methods used to determine the structure, eg `X-RAY DIFFRACTION`
+    pub experimental_methods: Vec<String>,
+    /// The resolution in Å, if applicable for the experimental method
+    pub resolution: Option<f64>,
+    /// The authors of the entry
+    pub authors: Vec<String>,
+    /// The primary citation of the entry
+    pub citation: Option<Citation>,
+}
+
+impl Metadata {
+    /// Create empty metadata.
+    #[must_use]
+    pub const fn new() -> Self {
+        Metadata {
+            classification: None,
+            deposition_date: None,
+            title: None,
+            compound: None,
+            source: None,
+            keywords: Vec::new(),
+            experimental_methods: Vec::new(),
+            resolution: None,
+            authors: Vec::new(),
+            citation: None,
+        }
+    }
+
+    /// Check if no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Metadata::new()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// A literature citation as given by the JRNL records
+pub struct Citation {
+    /// The authors of the publication
+    pub authors: Vec<String>,
+    /// The title of the publication
+    pub title: Option<String>,
+    /// The (abbreviated) name of the journal, or `TO BE PUBLISHED`
+    pub journal: Option<String>,
+    /// The volume of the journal
+    pub volume: Option<String>,
+    /// The first page of the publication
+    pub first_page: Option<String>,
+    /// The year of publication
+    pub year: Option<usize>,
+    /// The reference number of the journal, eg `ISSN 0022-2836`
+    pub reference_number: Option<String>,
+    /// The PubMed identifier
+    pub pubmed_id: Option<String>,
+    /// The Digital Object Identifier
+    pub doi: Option<String>,
+}
+
+/// Add the text of a continuation line of a comma separated list to the given text. Lists broken
+/// off after a comma are joined directly, otherwise the text is continued as normal text.
+pub(crate) fn continue_list(text: &mut String, continuation: &str) {
+    if text.ends_with(',') {
+        text.push_str(continuation);
+    } else {
+        continue_text(text, continuation);
+    }
+}
+
+/// Split the given text into its trimmed, non empty, parts separated by the given separator.
+pub(crate) fn split_list(text: &str, separator: char) -> Vec<String> {
+    text.split(separator)
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Get the value of a field, `None` if it is missing or blank
+fn field(text: &str, range: std::ops::Range<usize>) -> Option<String> {
+    text.get(range.start..range.end.min(text.len()))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+/// Add a JRNL record to the given citation, with the given sub-record type (eg `AUTH`), if it
+/// is a continuation line, and the text starting at column 20. The author lists are kept as
+/// text and have to be split with [`split_list`] once all lines are read.
+pub(crate) fn add_journal_record(
+    citation: &mut Citation,
+    authors: &mut String,
+    (record, continued, text): (&str, bool, &str),
+) {
+    match record {
+        "AUTH" => continue_list(authors, text.trim()),
+        "TITL" => continue_text(citation.title.get_or_insert_with(String::new), text.trim()),
+        "REF" => {
+            if let Some(journal) = field(text, 0..28) {
+                continue_text(citation.journal.get_or_insert_with(String::new), &journal);
+            }
+            if !continued {
+                citation.volume = field(text, 32..36);
+                citation.first_page = field(text, 37..42);
+                citation.year = field(text, 43..47).and_then(|year| year.parse().ok());
+            }
+        }
+        "REFN" => citation.reference_number = field(text, 0..text.len()),
+        "PMID" => citation.pubmed_id = field(text, 0..text.len()),
+        "DOI" => citation.doi = field(text, 0..text.len()),
+        _ => (),
+    }
+}
+
+/// Get the resolution from the REMARK 2 records, if given
+pub(crate) fn parse_resolution<'a>(
+    mut remarks: impl Iterator<Item = &'a (usize, String)>,
+) -> Option<f64> {
+    remarks.find_map(|(number, text)| {
+        if *number != RESOLUTION_REMARK {
+            return None;
+        }
+        text.trim()
+            .strip_prefix("RESOLUTION.")?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()
+    })
+}
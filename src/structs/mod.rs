@@ -1,32 +1,69 @@
 #![allow(clippy::missing_docs_in_private_items)]
+mod annotations;
+mod assembly;
 mod atom;
 mod bond;
 mod chain;
+mod children;
 mod conformer;
 mod database_reference;
+mod deposited_geometry;
 mod elements;
+mod entity;
+mod format_version;
+mod glycan;
 mod helper;
+mod het_group;
 mod hierarchy;
+mod metadata;
 mod model;
 mod mtrix;
 mod pdb;
 mod residue;
 mod search;
+mod secondary_structure;
+mod selection;
+mod site;
 mod symmetry;
 mod unit_cell;
 
+pub(crate) use annotations::{
+    parse_annotation_remarks, ANNOTATION_REMARK_TAG, ANNOTATION_REMARK_TYPE,
+};
+pub use annotations::{AnnotationValue, Annotations};
+pub use assembly::{AssemblyPart, BiologicalAssembly};
 pub use atom::Atom;
 pub use bond::Bond;
 pub use chain::Chain;
+pub(crate) use children::Children;
 pub use conformer::Conformer;
 pub use database_reference::*;
+pub use deposited_geometry::{
+    AngleDeviation, BondDeviation, CloseContact, DepositedGeometry, RemarkAtom, RemarkResidue,
+    TorsionOutlier,
+};
 pub use elements::{AtomicRadius, Element};
+pub use entity::Entity;
+pub use format_version::FormatVersion;
+pub(crate) use format_version::{normalize_residue_name, old_residue_name, remap_atom_name};
+pub(crate) use glycan::glycans;
+pub use glycan::{Glycan, GlycosylationType, Sugar};
 pub use helper::*;
+pub(crate) use het_group::{continue_text, het_group_mut, split_synonyms};
+pub use het_group::{HetGroup, HetResidue};
 pub use hierarchy::*;
+pub(crate) use metadata::{
+    add_journal_record, continue_list, parse_resolution, split_list, RESOLUTION_REMARK,
+};
+pub use metadata::{Citation, Metadata};
 pub use model::Model;
 pub use mtrix::MtriX;
 pub use pdb::PDB;
 pub use residue::Residue;
 pub use search::*;
+pub use secondary_structure::{BoundaryResidue, Helix, SecondaryStructure, Sheet, Strand};
+pub use selection::Selection;
+pub(crate) use site::describe_sites;
+pub use site::{Site, SiteResidue};
 pub use symmetry::Symmetry;
 pub use unit_cell::UnitCell;
@@ -14,7 +14,7 @@ pub struct Model {
     /// The serial number of this Model
     serial_number: usize,
     /// The Chains making up this model
-    chains: Vec<Chain>,
+    chains: Children<Chain>,
 }
 
 impl<'a> Model {
@@ -26,7 +26,21 @@ impl<'a> Model {
     pub const fn new(serial_number: usize) -> Model {
         Model {
             serial_number,
-            chains: Vec::new(),
+            chains: Children::new(),
+        }
+    }
+
+    /// Create a new Model with space for at least the given number of Chains, to prevent
+    /// reallocations when the number of Chains is known beforehand.
+    ///
+    /// ## Arguments
+    /// * `serial_number` - the serial number
+    /// * `chains` - the number of Chains to reserve space for
+    #[must_use]
+    pub fn with_capacity(serial_number: usize, chains: usize) -> Model {
+        Model {
+            serial_number,
+            chains: Vec::with_capacity(chains).into(),
         }
     }
 
@@ -51,6 +65,17 @@ impl<'a> Model {
         self.serial_number = new_number;
     }
 
+    /// Get the key used to order Models, the serial number. This is the ordering used by
+    /// [`PDB::sort`] and the `Ord` implementation of Model.
+    pub const fn sort_key(&self) -> usize {
+        self.serial_number
+    }
+
+    /// Reserve space for at least `additional` more Chains in this Model.
+    pub fn reserve(&mut self, additional: usize) {
+        self.chains.reserve(additional);
+    }
+
     /// Get the number of Chains making up this Model.
     pub fn chain_count(&self) -> usize {
         self.chains.len()
@@ -80,7 +105,14 @@ impl<'a> Model {
 
     /// Get the number of Atoms making up this Model.
     pub fn atom_count(&self) -> usize {
-        self.chains().map(Chain::atom_count).sum()
+        self.chains
+            .atom_count(|chains| chains.iter().map(Chain::atom_count).sum())
+    }
+
+    /// Clear the cached number of Atoms, for changes made to the children of this Model without
+    /// going through it, like through the mutable hierarchies.
+    pub(crate) fn clear_atom_count(&mut self) {
+        self.chains.clear_atom_count();
     }
 
     /// Get the number of Atoms making up this Model in parallel.
@@ -89,6 +121,22 @@ impl<'a> Model {
         self.par_chains().map(Chain::par_atom_count).sum()
     }
 
+    /// Get the molecular weight of this Model in Dalton, see [`Chain::molecular_weight`].
+    pub fn molecular_weight(&self) -> f64 {
+        self.chains().map(Chain::molecular_weight).sum()
+    }
+
+    /// Get the net formal charge of this Model, see [`Chain::net_charge`].
+    pub fn net_charge(&self) -> isize {
+        self.chains().map(Chain::net_charge).sum()
+    }
+
+    /// Get the net charge of this Model assuming the standard protonation states at neutral pH,
+    /// see [`Chain::net_charge_at_neutral_ph`].
+    pub fn net_charge_at_neutral_ph(&self) -> isize {
+        self.chains().map(Chain::net_charge_at_neutral_ph).sum()
+    }
+
     /// Get a reference to a specific Chain from list of Chains making up this Model.
     ///
     /// ## Arguments
@@ -398,6 +446,15 @@ impl<'a> Model {
             .flat_map(|c| c.atoms_with_hierarchy().map(move |h| h.extend(c)))
     }
 
+    /// Get a parallel iterator of references to a struct containing all atoms with their hierarchy making up this Model.
+    #[doc_cfg(feature = "rayon")]
+    pub fn par_atoms_with_hierarchy(
+        &'a self,
+    ) -> impl ParallelIterator<Item = hierarchy::AtomConformerResidueChain<'a>> + 'a {
+        self.par_chains()
+            .flat_map(|c| c.par_atoms_with_hierarchy().map(move |h| h.extend(c)))
+    }
+
     /// Get an iterator of mutable references to a struct containing all atoms with their hierarchy making up this Model.
     pub fn atoms_with_hierarchy_mut(
         &'a mut self,
@@ -408,6 +465,37 @@ impl<'a> Model {
         })
     }
 
+    /// Create an R star tree of the Atoms of this Model which can be used for fast lookup of
+    /// spatially close atoms, for example to find all atoms within 4 Aͦ of an atom. See the crate
+    /// rstar for documentation on how to use the tree. (<https://crates.io/crates/rstar>)
+    ///
+    /// Keep in mind that this creates a tree that is separate from the original Model, so any
+    /// changes to one of the data structures is not seen in the other data structure (until you
+    /// generate a new tree of course).
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let model = pdb.model(0).unwrap();
+    /// let tree = model.create_atom_rtree();
+    /// let atom = model.atom(0).unwrap();
+    /// // The distance is given squared
+    /// let close = tree.locate_within_distance(atom.pos(), 4.0 * 4.0).count();
+    /// assert!(close > 1);
+    /// ```
+    #[doc_cfg(feature = "rstar")]
+    pub fn create_atom_rtree(&self) -> rstar::RTree<&Atom> {
+        rstar::RTree::bulk_load(self.atoms().collect())
+    }
+
+    /// Create an R star tree of structs containing the Atoms of this Model and their hierarchies
+    /// which can be used for fast lookup of spatially close atoms, see [`Model::create_atom_rtree`].
+    #[doc_cfg(feature = "rstar")]
+    pub fn create_hierarchy_rtree(
+        &'a self,
+    ) -> rstar::RTree<hierarchy::AtomConformerResidueChain<'a>> {
+        rstar::RTree::bulk_load(self.atoms_with_hierarchy().collect())
+    }
+
     /// Add a new Atom to this Model. It finds if there already is a Chain with the given `chain_id` if there is it will add this atom to that Chain, otherwise it will create a new Chain and add that to the list of Chains making up this Model. It does the same for the Residue, so it will create a new one if there does not yet exist a Residue with the given serial number.
     ///
     /// ## Arguments
@@ -585,9 +673,100 @@ impl<'a> Model {
     pub fn par_sort(&mut self) {
         self.chains.par_sort();
     }
+
+    /// Copy the coordinates of all Atoms in this Model into the given flat buffer, as
+    /// `[x0, y0, z0, x1, y1, z1, ..]` in the same order as [`Model::atoms`]. For interop with
+    /// engines working on flat arrays, like molecular dynamics engines and minimizers.
+    /// # Errors
+    /// It fails if the length of the buffer is not three times the number of Atoms.
+    pub fn copy_coordinates_into(&self, buffer: &mut [f64]) -> Result<(), String> {
+        if buffer.len() != self.atom_count() * 3 {
+            return Err(format!(
+                "The buffer has length {} but should have length {} to hold the coordinates of {} atoms",
+                buffer.len(),
+                self.atom_count() * 3,
+                self.atom_count()
+            ));
+        }
+        for (atom, position) in self.atoms().zip(buffer.chunks_exact_mut(3)) {
+            position.copy_from_slice(&[atom.x(), atom.y(), atom.z()]);
+        }
+        Ok(())
+    }
+
+    /// Set the coordinates of all Atoms in this Model from the given flat buffer, given as
+    /// `[x0, y0, z0, x1, y1, z1, ..]` in the same order as [`Model::atoms`], see
+    /// [`Model::copy_coordinates_into`]. No Atom is changed if this fails.
+    /// # Errors
+    /// It fails if the length of the buffer is not three times the number of Atoms, or if one
+    /// or more of the numbers is not finite (`f64.is_finite()`).
+    pub fn apply_coordinates(&mut self, buffer: &[f64]) -> Result<(), String> {
+        if buffer.len() != self.atom_count() * 3 {
+            return Err(format!(
+                "The buffer has length {} but should have length {} to give the coordinates of {} atoms",
+                buffer.len(),
+                self.atom_count() * 3,
+                self.atom_count()
+            ));
+        }
+        if let Some(index) = buffer.iter().position(|value| !value.is_finite()) {
+            return Err(format!(
+                "The value at index {index} of the buffer is not finite, for atom index {}",
+                index / 3
+            ));
+        }
+        for (atom, position) in self.atoms_mut().zip(buffer.chunks_exact(3)) {
+            atom.set_pos((position[0], position[1], position[2]))?;
+        }
+        Ok(())
+    }
+
+    /// Get the coordinates of all Atoms in this Model as an array with a row (x, y, z) per Atom,
+    /// in the same order as [`Model::atoms`].
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let coordinates = pdb.model(0).unwrap().to_ndarray();
+    /// assert_eq!(coordinates.shape(), &[1271, 3]);
+    /// let centre = coordinates.mean_axis(ndarray::Axis(0)).unwrap();
+    /// ```
+    #[doc_cfg(feature = "ndarray")]
+    pub fn to_ndarray(&self) -> ndarray::Array2<f64> {
+        let coordinates = self
+            .atoms()
+            .flat_map(|atom| [atom.x(), atom.y(), atom.z()])
+            .collect();
+        ndarray::Array2::from_shape_vec((self.atom_count(), 3), coordinates)
+            .expect("The number of coordinates should be three times the number of atoms")
+    }
+
+    /// Get the B factors of all Atoms in this Model, in the same order as [`Model::atoms`].
+    #[doc_cfg(feature = "ndarray")]
+    pub fn b_factors_ndarray(&self) -> ndarray::Array1<f64> {
+        self.atoms().map(Atom::b_factor).collect()
+    }
+
+    /// Get the occupancies of all Atoms in this Model, in the same order as [`Model::atoms`].
+    #[doc_cfg(feature = "ndarray")]
+    pub fn occupancies_ndarray(&self) -> ndarray::Array1<f64> {
+        self.atoms().map(Atom::occupancy).collect()
+    }
+
+    /// Check if this Model is approximately equal to the given Model, with all Atoms compared
+    /// with [`Atom::approx_eq`] and all other properties compared as for `==`.
+    pub fn approx_eq(&self, other: &Model, tolerance: f64) -> bool {
+        self.serial_number == other.serial_number
+            && self.chains.len() == other.chains.len()
+            && self
+                .chains
+                .iter()
+                .zip(&other.chains)
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
 }
 
 use std::fmt;
+use std::ops;
 impl fmt::Display for Model {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -601,13 +780,14 @@ impl fmt::Display for Model {
 
 impl PartialOrd for Model {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.serial_number.cmp(&other.serial_number))
+        Some(self.cmp(other))
     }
 }
 
+/// Models are ordered by their serial number, see [`Model::sort_key`].
 impl Ord for Model {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.serial_number.cmp(&other.serial_number)
+        self.sort_key().cmp(&other.sort_key())
     }
 }
 
@@ -618,6 +798,59 @@ impl Extend<Chain> for Model {
     }
 }
 
+impl FromIterator<Chain> for Model {
+    /// Create a Model with serial number 0 from the given Chains, see [`Model::from_iter`] to
+    /// give the serial number.
+    fn from_iter<T: IntoIterator<Item = Chain>>(iter: T) -> Self {
+        let mut model = Self::new(0);
+        model.extend(iter);
+        model
+    }
+}
+
+impl<'a> IntoIterator for &'a Model {
+    type Item = &'a Chain;
+    type IntoIter = std::slice::Iter<'a, Chain>;
+    /// Iterate over the Chains of this Model, see [`Model::chains`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.chains.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Model {
+    type Item = &'a mut Chain;
+    type IntoIter = std::slice::IterMut<'a, Chain>;
+    /// Iterate mutably over the Chains of this Model, see [`Model::chains_mut`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.chains.iter_mut()
+    }
+}
+
+impl ops::Index<&str> for Model {
+    type Output = Chain;
+    /// Get the first Chain with the given id, see [`Model::chains`] for the fallible lookup.
+    ///
+    /// ## Panics
+    /// It panics if there is no Chain with the given id.
+    fn index(&self, id: &str) -> &Self::Output {
+        self.chains()
+            .find(|c| c.id() == id)
+            .unwrap_or_else(|| panic!("there is no Chain with id `{id}` in this Model"))
+    }
+}
+
+impl ops::IndexMut<&str> for Model {
+    /// Get the first Chain with the given id mutably.
+    ///
+    /// ## Panics
+    /// It panics if there is no Chain with the given id.
+    fn index_mut(&mut self, id: &str) -> &mut Self::Output {
+        self.chains_mut()
+            .find(|c| c.id() == id)
+            .unwrap_or_else(|| panic!("there is no Chain with id `{id}` in this Model"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -633,6 +866,16 @@ mod tests {
         assert!(a < c);
     }
 
+    #[test]
+    fn test_capacity() {
+        let mut a = Model::with_capacity(1, 4);
+        assert!(a.chains.capacity() >= 4);
+        assert_eq!(a.chain_count(), 0);
+        a.reserve(8);
+        assert!(a.chains.capacity() >= 8);
+        assert_eq!(a, Model::new(1));
+    }
+
     #[test]
     #[allow(clippy::unwrap_used)]
     fn test_children() {
@@ -658,6 +901,40 @@ mod tests {
         assert_eq!(a.chain_count(), 0);
     }
 
+    #[test]
+    #[cfg(feature = "rstar")]
+    fn test_rtree() {
+        let mut model = Model::new(0);
+        model.add_atom(
+            Atom::new(false, 0, "", 0.0, 0.0, 0.0, 0.0, 0.0, "", 0).unwrap(),
+            "A",
+            (0, None),
+            ("MET", None),
+        );
+        model.add_atom(
+            Atom::new(false, 1, "", 3.0, 0.0, 0.0, 0.0, 0.0, "", 0).unwrap(),
+            "A",
+            (0, None),
+            ("MET", None),
+        );
+        model.add_atom(
+            Atom::new(false, 2, "", 9.0, 0.0, 0.0, 0.0, 0.0, "", 0).unwrap(),
+            "B",
+            (1, None),
+            ("GLY", None),
+        );
+        let tree = model.create_atom_rtree();
+        assert_eq!(tree.size(), 3);
+        assert_eq!(
+            tree.locate_within_distance((0.0, 0.0, 0.0), 16.0).count(),
+            2
+        );
+        let tree = model.create_hierarchy_rtree();
+        let nearest = tree.nearest_neighbor(&(8.0, 0.0, 0.0)).unwrap();
+        assert_eq!(nearest.chain().id(), "B");
+        assert_eq!(nearest.residue().name(), Some("GLY"));
+    }
+
     #[test]
     fn test_display() {
         let a = Model::new(0);
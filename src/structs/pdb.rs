@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::HashMap;
 use std::fmt;
+use std::ops;
 
 use doc_cfg::doc_cfg;
 #[cfg(feature = "rayon")]
@@ -9,11 +11,11 @@ use rayon::prelude::*;
 
 use crate::structs::hierarchy::*;
 use crate::transformation::TransformationMatrix;
-use crate::{reference_tables, PDBError};
+use crate::{analysis, geometry, reference_tables, PDBError};
 use crate::{structs::*, Context};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug)]
 /// A PDB struct is generated by opening a PDB or mmCIF file. It contains
 /// all information present in this file, like its atoms, bonds, hierarchy
 /// , and metadata. The struct can be used to access, interact with, and
@@ -37,6 +39,8 @@ use crate::{structs::*, Context};
 pub struct PDB {
     /// The identifier as posed in the PDB Header or mmCIF entry.id, normally a 4 char string like '1UBQ'.
     pub identifier: Option<String>,
+    /// The descriptive metadata, like the title, authors, and experimental method.
+    metadata: Metadata,
     /// The remarks above the PDB file, containing the remark-type-number and a line of free text.
     remarks: Vec<(usize, String)>,
     /// The Scale needed to transform orthogonal coordinates to fractional coordinates. This is inversely related to the unit cell.
@@ -45,6 +49,14 @@ pub struct PDB {
     pub origx: Option<TransformationMatrix>,
     /// The MtriXs needed to transform the Models to the full asymmetric subunit, if needed to contain the non-crystallographic symmetry.
     mtrix: Vec<MtriX>,
+    /// The sites of interest as given by the SITE records, with their REMARK 800 descriptions.
+    sites: Vec<Site>,
+    /// The helices as given by the HELIX records.
+    helices: Vec<Helix>,
+    /// The beta sheets as given by the SHEET records.
+    sheets: Vec<Sheet>,
+    /// The hetero groups as given by the HET, HETNAM, HETSYN, and FORMUL records.
+    het_groups: Vec<HetGroup>,
     /// The unit cell of the crystal, containing its size and shape. This is the size and shape of the repeating element in the crystal.
     pub unit_cell: Option<UnitCell>,
     /// The Symmetry or space group of the crystal. This is the way in which the protein is placed inside the unit cell.
@@ -53,6 +65,10 @@ pub struct PDB {
     models: Vec<Model>,
     /// Bonds in this PDB.
     bonds: Vec<(usize, usize, Bond)>,
+    /// The polymer entities in this PDB, grouping chains with the same sequence.
+    entities: Vec<Entity>,
+    /// Custom annotations on this PDB.
+    annotations: Annotations,
 }
 
 /// # Creators
@@ -62,18 +78,57 @@ impl PDB {
     pub const fn new() -> PDB {
         PDB {
             identifier: None,
+            metadata: Metadata::new(),
             remarks: Vec::new(),
             scale: None,
             origx: None,
             mtrix: Vec::new(),
+            sites: Vec::new(),
+            helices: Vec::new(),
+            sheets: Vec::new(),
+            het_groups: Vec::new(),
             unit_cell: None,
             symmetry: None,
             models: Vec::new(),
             bonds: Vec::new(),
+            entities: Vec::new(),
+            annotations: Annotations::new(),
         }
     }
 }
 
+/// # Metadata
+/// Functionality for working with the descriptive metadata, as given by the title section of a
+/// PDB file.
+impl PDB {
+    /// Get the metadata of this PDB.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1kmk.pdb")
+    ///     .unwrap();
+    /// let metadata = pdb.metadata();
+    /// assert_eq!(metadata.classification.as_deref(), Some("LYASE"));
+    /// assert_eq!(metadata.experimental_methods, vec!["X-RAY DIFFRACTION"]);
+    /// assert_eq!(metadata.resolution, Some(2.2));
+    /// assert_eq!(metadata.citation.as_ref().unwrap().year, Some(2002));
+    /// ```
+    pub const fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Get a mutable reference to the metadata of this PDB.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Set the metadata of this PDB.
+    pub fn set_metadata(&mut self, metadata: Metadata) {
+        self.metadata = metadata;
+    }
+}
+
 /// # Remarks
 /// Functionality for working with remarks.
 impl PDB {
@@ -152,6 +207,904 @@ impl PDB {
     {
         self.remarks.retain(|r| !predicate(r));
     }
+
+    /// Get the geometry outliers reported by the depositor in the REMARK 500 records, see
+    /// [`DepositedGeometry`]. This contains the close contacts, covalent bond length and angle
+    /// deviations, and torsion angle outliers. Any rows which cannot be parsed are skipped.
+    ///
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1yyf.pdb")
+    ///     .unwrap();
+    /// let geometry = pdb.deposited_geometry();
+    /// let bond = &geometry.bond_deviations[0];
+    /// assert_eq!((bond.atoms.0.name.as_str(), bond.atoms.1.name.as_str()), ("CG", "SD"));
+    /// ```
+    pub fn deposited_geometry(&self) -> DepositedGeometry {
+        DepositedGeometry::from_remarks(self.remarks.iter())
+    }
+
+    /// Get the biological assemblies given in the REMARK 350 records, see
+    /// [`BiologicalAssembly`]. Any BIOMT rows which cannot be parsed are skipped.
+    ///
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1kmk.pdb")
+    ///     .unwrap();
+    /// let assembly = &pdb.biological_assemblies()[0];
+    /// assert_eq!(assembly.author_determined.as_deref(), Some("DIMERIC"));
+    /// assert_eq!(assembly.parts[0].transformations.len(), 2);
+    /// ```
+    pub fn biological_assemblies(&self) -> Vec<BiologicalAssembly> {
+        BiologicalAssembly::from_remarks(self.remarks.iter())
+    }
+
+    /// Build the biological assembly with the given serial number, see
+    /// [`PDB::biological_assemblies`]. The result contains, for every Model, a copy of each
+    /// Chain of the assembly for every transformation applied to it. The first copy of a Chain
+    /// keeps its ID, the other copies get new IDs, see [`PDB::flatten_models`] for the scheme
+    /// used. Chains not part of the assembly are left out. Returns `None` if there is no
+    /// assembly with the given serial number.
+    ///
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1kmk.pdb")
+    ///     .unwrap();
+    /// let dimer = pdb.build_biological_assembly(1).unwrap();
+    /// assert_eq!(dimer.chain_count(), 2);
+    /// ```
+    pub fn build_biological_assembly(&self, serial_number: usize) -> Option<PDB> {
+        let assembly = self
+            .biological_assemblies()
+            .into_iter()
+            .find(|assembly| assembly.serial_number == serial_number)?;
+        let mut output = self.clone();
+        let mut copies = Vec::new();
+        for model in &mut output.models {
+            let mut ids = unused_chain_ids(model.chains().map(|c| c.id().to_string()).collect());
+            let mut chains = Vec::new();
+            let mut placed = std::collections::HashSet::new();
+            for part in &assembly.parts {
+                for id in &part.chains {
+                    let original = match model.chains().find(|c| c.id() == id) {
+                        Some(original) => original,
+                        None => continue,
+                    };
+                    for transformation in &part.transformations {
+                        let mut chain = original.clone();
+                        if !placed.insert(id.clone()) {
+                            let copy = ids.next().expect("There are infinitely many IDs");
+                            copies.push((id.clone(), copy.clone()));
+                            chain.set_id(copy);
+                        }
+                        chain.apply_transformation(transformation);
+                        chains.push(chain);
+                    }
+                }
+            }
+            model.remove_chains_by(|_| true);
+            for chain in chains {
+                model.add_chain(chain);
+            }
+        }
+        for (original, copy) in copies {
+            output.add_entity_chain_copy(&original, &copy);
+        }
+        Some(output)
+    }
+}
+
+/// # Annotations
+/// Functionality for working with the custom annotations of this PDB. The annotations of
+/// Chains, Residues, and Atoms are found on these structs themselves.
+impl PDB {
+    /// Get the custom annotations of this PDB, see [`Annotations`].
+    pub const fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
+
+    /// Get the custom annotations of this PDB mutably, see [`Annotations`].
+    pub fn annotations_mut(&mut self) -> &mut Annotations {
+        &mut self.annotations
+    }
+}
+
+/// # Format version
+/// Functionality for working with the version of the PDB format conventions.
+impl PDB {
+    /// Detect the version of the PDB format conventions this structure follows. The version
+    /// stated in REMARK 4 is used if present, otherwise the nomenclature of the atom names is
+    /// used: names like `1HB` or `O5*` are only used in version 2, names like `O5'` only in
+    /// version 3. Returns `None` if the version could not be determined.
+    pub fn format_version(&self) -> Option<FormatVersion> {
+        self.remarks
+            .iter()
+            .filter(|(number, _)| *number == 4)
+            .find_map(|(_, text)| FormatVersion::from_remark(text))
+            .or_else(|| {
+                self.atoms()
+                    .find_map(|atom| FormatVersion::from_atom_name(atom.name()))
+            })
+    }
+
+    /// Convert the names of atoms and residues from the version 2.3 to the version 3 conventions
+    /// of the PDB format, see [`PDB::remap_nomenclature`]. Returns the number of names changed.
+    pub fn normalize_nomenclature(&mut self) -> usize {
+        self.remap_nomenclature(FormatVersion::V3)
+    }
+
+    /// Convert the names of atoms and residues to the conventions of the given version of the
+    /// PDB format, so structures from different eras can be compared. Converting to version 3:
+    /// * hydrogen atom names starting with a digit are changed (`1HB` becomes `HB1`, `2HG1`
+    ///   becomes `HG12`), the hydrogens of methylene groups are numbered 2 and 3 instead of 1
+    ///   and 2 (`1HB` of serine becomes `HB2`),
+    /// * sugar atom names are changed (`O5*` becomes `O5'`, `2H5*` becomes `H5''`),
+    /// * renamed heavy atoms are changed (`O1P` becomes `OP1`, `C5M` of thymine becomes `C7`,
+    ///   `CD` of isoleucine becomes `CD1`),
+    /// * deoxyribonucleotides in residues using the old sugar atom names are renamed (`A`
+    ///   becomes `DA`).
+    ///
+    /// Converting to version 2.3 does the reverse. Returns the number of names changed.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let names: Vec<String> = pdb.atoms().map(|atom| atom.name().to_string()).collect();
+    /// let changed = pdb.remap_nomenclature(FormatVersion::V2);
+    /// assert!(pdb.atoms().any(|atom| atom.name() == "1HB"));
+    /// assert_eq!(pdb.remap_nomenclature(FormatVersion::V3), changed);
+    /// assert!(pdb.atoms().map(Atom::name).eq(names.iter().map(String::as_str)));
+    /// ```
+    pub fn remap_nomenclature(&mut self, target: FormatVersion) -> usize {
+        let mut changed = 0;
+        for conformer in self.conformers_mut() {
+            let names: Vec<String> = conformer
+                .atoms()
+                .map(|atom| atom.name().to_string())
+                .collect();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            let renamed: Vec<Option<String>> = names
+                .iter()
+                .map(|name| remap_atom_name(conformer.name(), name, &names, target))
+                .collect();
+            for (atom, name) in conformer.atoms_mut().zip(renamed) {
+                if let Some(name) = name {
+                    if atom.set_name(name).is_ok() {
+                        changed += 1;
+                    }
+                }
+            }
+            let residue = match target {
+                FormatVersion::V3 if names.iter().any(|name| name.contains('*')) => {
+                    let has_o2 = names.iter().any(|name| *name == "O2*" || *name == "O2'");
+                    normalize_residue_name(conformer.name(), has_o2)
+                }
+                FormatVersion::V2 if names.iter().any(|name| name.contains('\'')) => {
+                    old_residue_name(conformer.name())
+                }
+                _ => None,
+            };
+            if let Some(name) = residue {
+                if conformer.set_name(name) {
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// # Measurements
+/// Geometric measurements between atoms given by selection strings of the form `chain/residue/atom`,
+/// see [`crate::geometry::select`].
+impl PDB {
+    /// Gives the distance between the centers of the two selected atoms in Aͦ.
+    ///
+    /// # Errors
+    /// Fails if any of the selections is invalid or does not match any atom.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let distance = pdb.distance("A/1/N", "A/1/CA").unwrap();
+    /// assert!((distance - 1.47).abs() < 0.1);
+    /// ```
+    pub fn distance(&self, a: &str, b: &str) -> Result<f64, PDBError> {
+        Ok(geometry::distance(
+            geometry::select(self, a)?,
+            geometry::select(self, b)?,
+        ))
+    }
+
+    /// Gives the angle between the centers of the three selected atoms in degrees, with the
+    /// second atom as the vertex.
+    ///
+    /// # Errors
+    /// Fails if any of the selections is invalid or does not match any atom.
+    pub fn angle(&self, a: &str, b: &str, c: &str) -> Result<f64, PDBError> {
+        Ok(geometry::angle(
+            geometry::select(self, a)?,
+            geometry::select(self, b)?,
+            geometry::select(self, c)?,
+        ))
+    }
+
+    /// Gives the signed dihedral angle between the centers of the four selected atoms in
+    /// degrees, see [`crate::geometry::dihedral`].
+    ///
+    /// # Errors
+    /// Fails if any of the selections is invalid or does not match any atom.
+    pub fn dihedral(&self, a: &str, b: &str, c: &str, d: &str) -> Result<f64, PDBError> {
+        Ok(geometry::dihedral(
+            geometry::select(self, a)?,
+            geometry::select(self, b)?,
+            geometry::select(self, c)?,
+            geometry::select(self, d)?,
+        ))
+    }
+
+    /// Calculate the distances between all atoms matching the first search (rows) and all atoms
+    /// matching the second search (columns) in Aͦ, see [`crate::geometry::distance_matrix`].
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let matrix = pdb.distance_matrix(
+    ///     Search::Single(Term::ResidueSerialNumber(1)),
+    ///     Search::Single(Term::AtomName("CA".to_owned())),
+    /// );
+    /// assert_eq!(matrix.rows(), 19);
+    /// assert_eq!(matrix.columns(), 76);
+    /// ```
+    pub fn distance_matrix(&self, a: Search, b: Search) -> geometry::DistanceMatrix {
+        let (a, b): (Vec<_>, Vec<_>) = (self.find(a).collect(), self.find(b).collect());
+        geometry::distance_matrix(
+            &a.iter()
+                .map(ContainsAtomConformer::atom)
+                .collect::<Vec<_>>(),
+            &b.iter()
+                .map(ContainsAtomConformer::atom)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Get the amino acid residues surrounding the given (hetero) residue, for example a ligand,
+    /// with the minimal distance between any of their atoms in Aͦ. All residues with a distance of
+    /// at most `cutoff` are given, in the order of the structure. Only the model containing the
+    /// given residue is searched (or the first model if the residue is not part of this PDB).
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1kmk.pdb").unwrap();
+    /// let ligand = pdb.residues().find(|r| r.name() == Some("PLP")).unwrap();
+    /// for (residue, distance) in pdb.binding_site(ligand, 4.0) {
+    ///     println!("{} {} {distance:.2}", residue.name().unwrap(), residue.serial_number());
+    /// }
+    /// ```
+    pub fn binding_site(&self, ligand: &Residue, cutoff: f64) -> Vec<(&Residue, f64)> {
+        let model = self
+            .models()
+            .find(|m| m.residues().any(|r| std::ptr::eq(r, ligand)))
+            .or_else(|| self.models().next());
+        model
+            .into_iter()
+            .flat_map(Model::residues)
+            .filter(|r| !std::ptr::eq(*r, ligand) && r.conformers().any(Conformer::is_amino_acid))
+            .filter_map(|r| {
+                let distance = r
+                    .atoms()
+                    .flat_map(|a| ligand.atoms().map(move |l| a.distance(l)))
+                    .fold(f64::INFINITY, f64::min);
+                (distance <= cutoff).then_some((r, distance))
+            })
+            .collect()
+    }
+
+    /// Calculate quality metrics of this PDB, like the counts per Model, the B factor
+    /// distribution, anomalous occupancies, missing backbone atoms, and clashes, see
+    /// [`crate::analysis::statistics`].
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let statistics = pdb.statistics();
+    /// assert_eq!(statistics.models.len(), 1);
+    /// assert!(statistics.missing_backbone.is_empty());
+    /// ```
+    pub fn statistics(&self) -> analysis::Statistics<'_> {
+        analysis::statistics(self)
+    }
+}
+
+/// # Conformational editing
+/// Changing the conformation of the structure by rotating groups of atoms around bonds.
+impl PDB {
+    /// Rotate all atoms on the side of the second selected atom of the bond between the two
+    /// selected atoms (selection strings of the form `chain/residue/atom`, see
+    /// [`crate::geometry::select`]) around that bond by the given angle in degrees. This changes
+    /// all dihedral angles over the bond by the same angle, which makes it possible to adjust
+    /// the phi, psi, and chi angles of residues or to generate simple conformers. The bonds are
+    /// inferred from the geometry of the atoms in the first model matching the given search (see
+    /// [`crate::chemistry::infer_bonds`]), only atoms within that search are moved. Returns the
+    /// number of atoms moved.
+    ///
+    /// # Errors
+    /// Fails if any of the selections is invalid or does not match an atom in the search, if the
+    /// selected atoms are not bonded, or if the bond is part of a ring, as the atoms on either
+    /// side of the bond cannot be separated then.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let chi1 = pdb.dihedral("A/1/N", "A/1/CA", "A/1/CB", "A/1/CG").unwrap();
+    /// let search = Search::Single(Term::ResidueSerialNumber(1));
+    /// pdb.rotate_dihedral("A/1/CA", "A/1/CB", 60.0, search).unwrap();
+    /// let rotated = pdb.dihedral("A/1/N", "A/1/CA", "A/1/CB", "A/1/CG").unwrap();
+    /// assert!(((rotated - chi1 - 60.0 + 540.0) % 360.0 - 180.0).abs() < 1e-6);
+    /// ```
+    pub fn rotate_dihedral(
+        &mut self,
+        a: &str,
+        b: &str,
+        angle: f64,
+        selection: Search,
+    ) -> Result<usize, PDBError> {
+        let (a, b) = (geometry::select(self, a)?, geometry::select(self, b)?);
+        let invalid = |long_description: &str| {
+            PDBError::new(
+                crate::ErrorLevel::BreakingError,
+                "Invalid rotation",
+                long_description,
+                Context::show(format!("{} - {}", a.name(), b.name())),
+            )
+        };
+        let found: Vec<_> = self
+            .model(0)
+            .map(|model| model.find(selection).collect())
+            .unwrap_or_default();
+        let atoms: Vec<&Atom> = found.iter().map(ContainsAtomConformer::atom).collect();
+        let index = |atom: &Atom| {
+            atoms
+                .iter()
+                .position(|other| other.counter() == atom.counter())
+        };
+        let (start, end) = match (index(a), index(b)) {
+            (Some(start), Some(end)) => (start, end),
+            _ => {
+                return Err(invalid(
+                    "The atoms of the bond are not part of the search in the first model.",
+                ))
+            }
+        };
+        let mut neighbours = vec![Vec::new(); atoms.len()];
+        for (i, j, _) in crate::chemistry::infer_bonds(&atoms) {
+            neighbours[i].push(j);
+            neighbours[j].push(i);
+        }
+        if !neighbours[start].contains(&end) {
+            return Err(invalid("The selected atoms are not bonded."));
+        }
+
+        // Find all atoms connected to the second atom without passing the bond itself
+        let mut moving = vec![false; atoms.len()];
+        moving[end] = true;
+        let mut stack = vec![end];
+        while let Some(atom) = stack.pop() {
+            for &neighbour in &neighbours[atom] {
+                if atom == end && neighbour == start {
+                    continue;
+                }
+                if neighbour == start {
+                    return Err(invalid(
+                        "The bond is part of a ring, so the atoms on its sides cannot be rotated separately.",
+                    ));
+                }
+                if !moving[neighbour] {
+                    moving[neighbour] = true;
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        // Rotate around the axis from the first to the second atom (Rodrigues' formula)
+        let (origin, other) = (b.pos(), a.pos());
+        let axis = [origin.0 - other.0, origin.1 - other.1, origin.2 - other.2];
+        let length = geometry::dot(axis, axis).sqrt();
+        let axis = axis.map(|value| value / length);
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let counters: Vec<usize> = atoms
+            .iter()
+            .zip(&moving)
+            .filter(|(_, moving)| **moving)
+            .map(|(atom, _)| atom.counter())
+            .collect();
+        let model = self
+            .model_mut(0)
+            .expect("The first model contains the bond");
+        for atom in model
+            .atoms_mut()
+            .filter(|atom| counters.contains(&atom.counter()))
+        {
+            let (x, y, z) = atom.pos();
+            let v = [x - origin.0, y - origin.1, z - origin.2];
+            let k_v = geometry::cross(axis, v);
+            let k_dot_v = geometry::dot(axis, v);
+            let rotated =
+                [0, 1, 2].map(|i| v[i] * cos + k_v[i] * sin + axis[i] * k_dot_v * (1.0 - cos));
+            atom.set_pos((
+                rotated[0] + origin.0,
+                rotated[1] + origin.1,
+                rotated[2] + origin.2,
+            ))
+            .expect("Rotated positions are finite");
+        }
+        Ok(counters.len())
+    }
+}
+
+/// # Sequences
+/// Functionality for working with the sequences of the chains.
+impl PDB {
+    /// Group the Chains of the first Model by sequence identity, see [`Chain::sequence_identity`].
+    /// Each Chain is placed in the first group where the identity to the first Chain of that
+    /// group is at least `threshold` (0.0 to 1.0), otherwise it starts a new group. The first
+    /// Chain of each group can be used as the representative, for example to pick the unique
+    /// chains of a large assembly.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1yyf.pdb").unwrap();
+    /// let groups = pdb.identical_chains(0.95);
+    /// let unique: Vec<&str> = groups.iter().map(|group| group[0].id()).collect();
+    /// ```
+    pub fn identical_chains(&self, threshold: f64) -> Vec<Vec<&Chain>> {
+        let mut groups: Vec<Vec<&Chain>> = Vec::new();
+        for chain in self.models().take(1).flat_map(Model::chains) {
+            if let Some(group) = groups
+                .iter_mut()
+                .find(|group| group[0].sequence_identity(chain) >= threshold)
+            {
+                group.push(chain);
+            } else {
+                groups.push(vec![chain]);
+            }
+        }
+        groups
+    }
+
+    /// Detect the point group symmetry of the largest group of identical chains (at 95%
+    /// sequence identity) in the first Model, see [`crate::geometry::point_group`].
+    pub fn point_group(&self) -> Option<geometry::PointGroupSymmetry> {
+        let groups = self.identical_chains(0.95);
+        let largest = groups.iter().max_by_key(|group| group.len())?;
+        geometry::point_group(largest)
+    }
+}
+
+/// # Entities
+/// Functionality for working with the polymer entities, the groups of chains with the same
+/// polymer sequence. These are read from the COMPND and SEQRES records in PDB files and the
+/// `_entity_poly` category in mmCIF files, or can be detected with [`PDB::detect_entities`].
+impl PDB {
+    /// Get the number of entities in this PDB.
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Get an iterator of references to the entities of this PDB.
+    pub fn entities(&self) -> impl DoubleEndedIterator<Item = &Entity> + '_ {
+        self.entities.iter()
+    }
+
+    /// Get a parallel iterator of references to the entities of this PDB.
+    #[doc_cfg(feature = "rayon")]
+    pub fn par_entities(&self) -> impl ParallelIterator<Item = &Entity> + '_ {
+        self.entities.par_iter()
+    }
+
+    /// Get an iterator of mutable references to the entities of this PDB.
+    pub fn entities_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Entity> + '_ {
+        self.entities.iter_mut()
+    }
+
+    /// Get a parallel iterator of mutable references to the entities of this PDB.
+    #[doc_cfg(feature = "rayon")]
+    pub fn par_entities_mut(&mut self) -> impl ParallelIterator<Item = &mut Entity> + '_ {
+        self.entities.par_iter_mut()
+    }
+
+    /// Add an entity to this PDB.
+    pub fn add_entity(&mut self, entity: Entity) {
+        self.entities.push(entity);
+    }
+
+    /// Delete the entities matching the given predicate.
+    pub fn delete_entities_by<F>(&mut self, predicate: F)
+    where
+        F: Fn(&Entity) -> bool,
+    {
+        self.entities.retain(|e| !predicate(e));
+    }
+
+    /// Get the entity with the given identifier.
+    pub fn entity(&self, id: usize) -> Option<&Entity> {
+        self.entities.iter().find(|e| e.id == id)
+    }
+
+    /// Get the entity the chain with the given identifier is an instance of.
+    pub fn chain_entity(&self, chain_id: impl AsRef<str>) -> Option<&Entity> {
+        self.entities
+            .iter()
+            .find(|e| e.contains_chain(chain_id.as_ref()))
+    }
+
+    /// Get the chains of the first Model which are instances of the entity with the given
+    /// identifier.
+    pub fn entity_chains(&self, id: usize) -> impl Iterator<Item = &Chain> + '_ {
+        let entity = self.entity(id);
+        self.models()
+            .take(1)
+            .flat_map(Model::chains)
+            .filter(move |chain| entity.map_or(false, |e| e.contains_chain(chain.id())))
+    }
+
+    /// Replace the entities of this PDB by grouping the polymer chains of the first Model on
+    /// their sequence, see [`PDB::identical_chains`]. Chains without a polymer sequence (only
+    /// ligands or waters) are not part of any entity. The entities are numbered from 1 and
+    /// do not have a description or full sequence.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// pdb.detect_entities(1.0);
+    /// assert_eq!(pdb.entity_count(), 1);
+    /// assert_eq!(pdb.entity(1).unwrap().chains, vec!["A".to_string()]);
+    /// ```
+    pub fn detect_entities(&mut self, threshold: f64) {
+        let entities = self
+            .identical_chains(threshold)
+            .into_iter()
+            .filter(|group| !group[0].sequence().is_empty())
+            .enumerate()
+            .map(|(index, group)| {
+                Entity::new(
+                    index + 1,
+                    None,
+                    group.iter().map(|chain| chain.id().to_string()).collect(),
+                    Vec::new(),
+                )
+            })
+            .collect();
+        self.entities = entities;
+    }
+
+    /// Register the Chain with ID `copy` as an instance of the same entity as the Chain with ID
+    /// `original`, used to keep the entities up to date when copying chains.
+    fn add_entity_chain_copy(&mut self, original: &str, copy: &str) {
+        if let Some(entity) = self
+            .entities
+            .iter_mut()
+            .find(|e| e.contains_chain(original) && !e.contains_chain(copy))
+        {
+            entity.chains.push(copy.to_string());
+        }
+    }
+}
+
+/// # Ensembles
+/// Functionality for converting between alternative locations, Models, and Chains, so ensembles
+/// can be handled in the layout an analysis or tool needs.
+impl PDB {
+    /// Expand the alternative locations into separate Models, one Model for each alternative
+    /// location (in alphabetical order) per original Model. Each new Model contains the atoms
+    /// without alternative location and the atoms of its alternative location, with the
+    /// alternative location labels removed. Residues without the alternative location of a
+    /// Model use their first alternative location instead. Models without alternative locations
+    /// are kept as is. If any Model is expanded, the Models are renumbered consecutively starting
+    /// from the serial number of the first Model. The bonds are kept on the first copy of each
+    /// Atom. The inverse of this operation is [`PDB::collapse_alt_locs`].
+    pub fn expand_alt_locs(&mut self) {
+        self.keep_bonds(Self::expand_alt_locs_unbonded);
+    }
+
+    /// Expand the alternative locations into separate Models, see [`PDB::expand_alt_locs`],
+    /// without updating the bonds.
+    fn expand_alt_locs_unbonded(&mut self) {
+        let models = std::mem::take(&mut self.models);
+        let count = models.len();
+        let first = models.first().map_or(1, Model::serial_number);
+        for model in models {
+            let mut locations: Vec<String> = model
+                .conformers()
+                .filter_map(|c| c.alternative_location().map(str::to_string))
+                .collect();
+            locations.sort();
+            locations.dedup();
+            if locations.is_empty() {
+                self.models.push(model);
+                continue;
+            }
+            for location in &locations {
+                let mut expanded = model.clone();
+                for residue in expanded.residues_mut() {
+                    *residue = residue.select_alternative_location(location);
+                }
+                self.models.push(expanded);
+            }
+        }
+        if self.models.len() != count {
+            for (index, model) in self.models.iter_mut().enumerate() {
+                model.set_serial_number(first + index);
+            }
+        }
+    }
+
+    /// Collapse all Models into the first Model, using alternative locations for the atoms
+    /// that differ between the Models. Atoms at the same position in all Models are kept
+    /// without alternative location, the differing atoms of the first Model get alternative
+    /// location `A`, of the second Model `B`, and so on. If the atoms of a Residue are the same
+    /// in two Models, only the first of these gets an alternative location. The bonds are kept on
+    /// the Atoms of the first Model. This is the inverse of [`PDB::expand_alt_locs`], with the
+    /// alternative locations relabelled in order.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/3nig.pdb").unwrap();
+    /// pdb.expand_alt_locs();
+    /// assert_eq!(pdb.model_count(), 2);
+    /// pdb.collapse_alt_locs().unwrap();
+    /// assert_eq!(pdb.model_count(), 1);
+    /// assert!(pdb.bonds().count() > 0);
+    /// ```
+    ///
+    /// ## Errors
+    /// Fails if the Models do not contain the same Chains and Residues in the same order, in
+    /// which case nothing is changed.
+    pub fn collapse_alt_locs(&mut self) -> Result<(), String> {
+        let mut result = Ok(());
+        self.keep_bonds(|pdb| result = pdb.collapse_alt_locs_unbonded());
+        result
+    }
+
+    /// Collapse all Models into the first Model, see [`PDB::collapse_alt_locs`], without
+    /// updating the bonds.
+    fn collapse_alt_locs_unbonded(&mut self) -> Result<(), String> {
+        let first = match self.models.first() {
+            Some(model) => model,
+            None => return Ok(()),
+        };
+        for model in &self.models[1..] {
+            let same = model.chain_count() == first.chain_count()
+                && model.chains().zip(first.chains()).all(|(a, b)| {
+                    a.id() == b.id()
+                        && a.residue_count() == b.residue_count()
+                        && a.residues()
+                            .zip(b.residues())
+                            .all(|(a, b)| a.id() == b.id())
+                });
+            if !same {
+                return Err(format!(
+                    "Model {} does not have the same chains and residues as model {}",
+                    model.serial_number(),
+                    first.serial_number()
+                ));
+            }
+        }
+        let residues: Vec<Residue> = (0..first.residue_count())
+            .map(|index| {
+                let versions: Vec<&Residue> = self
+                    .models
+                    .iter()
+                    .filter_map(|model| model.residue(index))
+                    .collect();
+                Residue::combine_alternative_locations(&versions)
+            })
+            .collect();
+        self.models.truncate(1);
+        for (residue, combined) in self.models[0].residues_mut().zip(residues) {
+            *residue = combined;
+        }
+        Ok(())
+    }
+
+    /// Average the alternative locations into single atoms, the simplification many tools
+    /// expect. The positions of the alternative versions of an atom are averaged weighted by
+    /// their occupancies, the occupancies are summed, and the B factors are combined with the
+    /// spread of the positions, so the B factor still reflects the uncertainty of the position.
+    /// Atoms without alternative location are kept as is. If the alternative locations of a
+    /// Residue have different residue types (microheterogeneity), only the type with the highest
+    /// occupancy is kept. Unlike [`PDB::expand_alt_locs`] this loses information, the
+    /// alternative locations cannot be restored.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/3nig.pdb").unwrap();
+    /// pdb.average_alt_locs();
+    /// assert!(pdb.conformers().all(|c| c.alternative_location().is_none()));
+    /// ```
+    pub fn average_alt_locs(&mut self) {
+        self.keep_bonds(|pdb| {
+            for residue in pdb.residues_mut() {
+                *residue = residue.average_alternative_locations();
+            }
+        });
+    }
+
+    /// Apply the given change to this PDB, which may replace Atoms by copies, and move the bonds
+    /// over to the copies afterwards. Atoms are matched on their serial number, name, and
+    /// position, and bonds are moved to the first matching Atom. Bonds to Atoms without a match
+    /// (like averaged Atoms) are removed.
+    fn keep_bonds<T>(&mut self, change: impl FnOnce(&mut Self) -> T) -> T {
+        if self.bonds.is_empty() {
+            return change(self);
+        }
+        let key = |atom: &Atom| {
+            let (x, y, z) = atom.pos();
+            (
+                atom.serial_number(),
+                atom.name().to_string(),
+                [x.to_bits(), y.to_bits(), z.to_bits()],
+            )
+        };
+        let keys: FxHashMap<usize, _> = self
+            .atoms()
+            .map(|atom| (atom.counter(), key(atom)))
+            .collect();
+        let bonds: Vec<_> = std::mem::take(&mut self.bonds)
+            .into_iter()
+            .filter_map(|(a, b, bond)| Some((keys.get(&a)?.clone(), keys.get(&b)?.clone(), bond)))
+            .collect();
+        let result = change(self);
+        let mut counters = FxHashMap::default();
+        for atom in self.atoms() {
+            counters.entry(key(atom)).or_insert_with(|| atom.counter());
+        }
+        self.bonds = bonds
+            .into_iter()
+            .filter_map(|(a, b, bond)| Some((*counters.get(&a)?, *counters.get(&b)?, bond)))
+            .collect();
+        result
+    }
+
+    /// Keep only the alternative location with the highest mean occupancy of every Residue,
+    /// the atoms without alternative location are kept as well. With equal occupancies the
+    /// first alternative location in the file is kept. The alternative location labels are
+    /// removed, so every Residue ends up with a single version of each atom. Unlike
+    /// [`PDB::average_alt_locs`] the kept atoms are not changed.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/3nig.pdb").unwrap();
+    /// let count = pdb.atom_count();
+    /// pdb.keep_highest_occupancy_alt_locs();
+    /// assert!(pdb.conformers().all(|c| c.alternative_location().is_none()));
+    /// assert!(pdb.atom_count() < count);
+    /// ```
+    pub fn keep_highest_occupancy_alt_locs(&mut self) {
+        self.keep_bonds(|pdb| {
+            for residue in pdb.residues_mut() {
+                *residue = residue.highest_occupancy_location();
+            }
+        });
+    }
+
+    /// Cluster the Models of this ensemble by their RMSD over the alpha carbons (or phosphorus
+    /// atoms), with average linkage hierarchical clustering up to the given RMSD cutoff in Aͦ,
+    /// see [`crate::geometry::cluster_models`]. The assignments and representatives are given as
+    /// indices into the Models. Returns `None` if the Models do not have the same number of
+    /// alpha carbons (or phosphorus atoms) or fewer than three.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/pTLS-6484.pdb").unwrap();
+    /// let clusters = pdb.cluster_models(1.0).unwrap();
+    /// assert_eq!(clusters.assignments.len(), pdb.model_count());
+    /// ```
+    pub fn cluster_models(&self, cutoff: f64) -> Option<geometry::ModelClusters> {
+        geometry::cluster_models(&self.models.iter().collect::<Vec<_>>(), cutoff)
+    }
+
+    /// Analyse the coordinate variation over the Models of this ensemble with a principal
+    /// component analysis over the alpha carbons (or phosphorus atoms), after superposing the
+    /// Models, see [`crate::geometry::principal_components`]. Returns `None` if the Models do
+    /// not have the same number of alpha carbons (or phosphorus atoms), fewer than three, or if
+    /// there are fewer than two Models.
+    pub fn principal_components(&self) -> Option<geometry::PrincipalComponents> {
+        geometry::principal_components(&self.models.iter().collect::<Vec<_>>())
+    }
+
+    /// Flatten all Models into the first Model, by adding the Chains of the other Models as
+    /// new Chains. The Chains of the first Model keep their IDs, the other Chains get the first
+    /// unused ID from `A-Z`, `a-z`, and `0-9`, then two letter IDs (`AA`, `AB`, ...) when these
+    /// run out, and longer IDs after that. Only mmCIF supports IDs longer than one character.
+    /// Atom serial numbers are not changed, so these are not unique anymore. Returns for every
+    /// Chain the serial number of its original Model, its original ID, and its new ID.
+    pub fn flatten_models(&mut self) -> Vec<(usize, String, String)> {
+        let mut models = std::mem::take(&mut self.models).into_iter();
+        let mut flat = match models.next() {
+            Some(model) => model,
+            None => return Vec::new(),
+        };
+        let mut mapping: Vec<(usize, String, String)> = flat
+            .chains()
+            .map(|c| (flat.serial_number(), c.id().to_string(), c.id().to_string()))
+            .collect();
+        let mut ids = unused_chain_ids(flat.chains().map(|c| c.id().to_string()).collect());
+        for model in models {
+            let serial_number = model.serial_number();
+            for mut chain in model.chains().cloned().collect::<Vec<_>>() {
+                let id = ids.next().expect("There are infinitely many IDs");
+                mapping.push((serial_number, chain.id().to_string(), id.clone()));
+                chain.set_id(&id);
+                flat.add_chain(chain);
+            }
+        }
+        self.models.push(flat);
+        for (_, original, copy) in &mapping {
+            self.add_entity_chain_copy(original, copy);
+        }
+        mapping
+    }
+}
+
+/// # Unit cell
+/// Functionality for working with the crystal lattice.
+impl PDB {
+    /// Expand the asymmetric unit to the full unit cell (space group P 1), by applying all
+    /// operators of the space group to the Chains of every Model. Each copy is wrapped into the
+    /// unit cell as a whole, so the centre of each Chain lies in the cell and molecules are not
+    /// broken up. The copies get new Chain IDs, see [`PDB::flatten_models`] for the scheme used.
+    /// The coordinates are converted to fractional coordinates with the unit cell, so the
+    /// standard orientation (`a` along x, `b` in the xy plane) is assumed, as used in PDB files.
+    /// Afterwards the symmetry is set to P 1.
+    ///
+    /// ## Errors
+    /// Fails if the unit cell or symmetry is not given, in which case nothing is changed.
+    pub fn expand_to_p1(&mut self) -> Result<(), String> {
+        let unit_cell = self
+            .unit_cell
+            .as_ref()
+            .ok_or_else(|| "The unit cell is needed to expand to P 1".to_string())?;
+        let symmetry = self
+            .symmetry
+            .as_ref()
+            .ok_or_else(|| "The space group is needed to expand to P 1".to_string())?;
+        let fractionalization = unit_cell.fractionalization_matrix();
+        let orthogonalization = unit_cell.orthogonalization_matrix();
+        let operators = symmetry.transformations();
+        let mut copies = Vec::new();
+        for model in &mut self.models {
+            let originals: Vec<Chain> = model.chains().cloned().collect();
+            let mut ids = unused_chain_ids(originals.iter().map(|c| c.id().to_string()).collect());
+            let mut chains = Vec::with_capacity(originals.len() * operators.len());
+            for (index, operator) in operators.iter().enumerate() {
+                for original in &originals {
+                    let mut chain = original.clone();
+                    if index > 0 {
+                        let id = ids.next().expect("There are infinitely many IDs");
+                        copies.push((original.id().to_string(), id.clone()));
+                        chain.set_id(id);
+                    }
+                    chain.apply_transformation(&fractionalization.combine(operator));
+                    #[allow(clippy::cast_precision_loss)]
+                    let count = chain.atom_count().max(1) as f64;
+                    let sum = chain.atoms().fold((0.0, 0.0, 0.0), |sum, atom| {
+                        (sum.0 + atom.x(), sum.1 + atom.y(), sum.2 + atom.z())
+                    });
+                    let shift = TransformationMatrix::translation(
+                        -(sum.0 / count).floor(),
+                        -(sum.1 / count).floor(),
+                        -(sum.2 / count).floor(),
+                    );
+                    chain.apply_transformation(&shift.combine(&orthogonalization));
+                    chains.push(chain);
+                }
+            }
+            model.remove_chains_by(|_| true);
+            for chain in chains {
+                model.add_chain(chain);
+            }
+        }
+        for (original, copy) in copies {
+            self.add_entity_chain_copy(&original, &copy);
+        }
+        self.symmetry = Symmetry::new("P 1");
+        Ok(())
+    }
 }
 
 /// # MtriX
@@ -193,6 +1146,225 @@ impl PDB {
     {
         self.mtrix.retain(|m| !predicate(m));
     }
+
+    /// Generate the full asymmetric unit by applying the MtriX which are not contained in the
+    /// entry to the Chains of every Model, see [`MtriX`]. The copies get new Chain IDs, see
+    /// [`PDB::flatten_models`] for the scheme used, and the applied MtriX are marked as
+    /// contained so applying them again does nothing. Returns for every copy the serial number
+    /// of its MtriX, the ID of its original Chain, and its new ID.
+    ///
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/2btv.pdb")
+    ///     .unwrap();
+    /// let chains = pdb.chain_count();
+    /// let copies = pdb.expand_ncs();
+    /// assert_eq!(pdb.chain_count(), chains * 30);
+    /// assert!(pdb.mtrix().all(|m| m.contained));
+    /// assert!(pdb.expand_ncs().is_empty());
+    /// ```
+    pub fn expand_ncs(&mut self) -> Vec<(usize, String, String)> {
+        let operators: Vec<(usize, TransformationMatrix)> = self
+            .mtrix
+            .iter()
+            .filter(|m| !m.contained)
+            .map(|m| (m.serial_number, m.transformation.clone()))
+            .collect();
+        let mut copies = Vec::new();
+        for model in &mut self.models {
+            let originals: Vec<Chain> = model.chains().cloned().collect();
+            let mut ids = unused_chain_ids(originals.iter().map(|c| c.id().to_string()).collect());
+            for (serial_number, operator) in &operators {
+                for original in &originals {
+                    let mut chain = original.clone();
+                    let id = ids.next().expect("There are infinitely many IDs");
+                    copies.push((*serial_number, original.id().to_string(), id.clone()));
+                    chain.set_id(id);
+                    chain.apply_transformation(operator);
+                    model.add_chain(chain);
+                }
+            }
+        }
+        for (_, original, copy) in &copies {
+            self.add_entity_chain_copy(original, copy);
+        }
+        for mtrix in &mut self.mtrix {
+            mtrix.contained = true;
+        }
+        copies
+    }
+}
+
+/// # Sites
+/// Functionality for working with the sites of interest, like binding sites, as given by the
+/// SITE records and described by the REMARK 800 records.
+impl PDB {
+    /// Get an iterator of references to the Sites of this PDB.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1kmk.pdb")
+    ///     .unwrap();
+    /// let site = pdb.sites().next().unwrap();
+    /// assert_eq!(site.id, "AC1");
+    /// assert_eq!(site.description.as_deref(), Some("BINDING SITE FOR RESIDUE SEC A 502"));
+    /// ```
+    pub fn sites(&self) -> impl DoubleEndedIterator<Item = &Site> + '_ {
+        self.sites.iter()
+    }
+
+    /// Get an iterator of mutable references to the Sites of this PDB.
+    pub fn sites_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Site> + '_ {
+        self.sites.iter_mut()
+    }
+
+    /// Get the Site with the given identifier, if present.
+    pub fn site(&self, id: &str) -> Option<&Site> {
+        self.sites.iter().find(|site| site.id == id)
+    }
+
+    /// Add a Site to this PDB.
+    pub fn add_site(&mut self, site: Site) {
+        self.sites.push(site);
+    }
+
+    /// Delete the Sites matching the given predicate.
+    pub fn delete_sites_by<F>(&mut self, predicate: F)
+    where
+        F: Fn(&Site) -> bool,
+    {
+        self.sites.retain(|s| !predicate(s));
+    }
+}
+
+/// # Secondary structure
+/// Functionality for working with the secondary structure annotations, as given by the HELIX and
+/// SHEET records.
+impl PDB {
+    /// Get an iterator of references to the Helices of this PDB.
+    pub fn helices(&self) -> impl DoubleEndedIterator<Item = &Helix> + '_ {
+        self.helices.iter()
+    }
+
+    /// Add a Helix to this PDB.
+    pub fn add_helix(&mut self, helix: Helix) {
+        self.helices.push(helix);
+    }
+
+    /// Get an iterator of references to the Sheets of this PDB.
+    pub fn sheets(&self) -> impl DoubleEndedIterator<Item = &Sheet> + '_ {
+        self.sheets.iter()
+    }
+
+    /// Get the Sheet with the given identifier, if present.
+    pub fn sheet(&self, id: &str) -> Option<&Sheet> {
+        self.sheets.iter().find(|sheet| sheet.id == id)
+    }
+
+    /// Add a Sheet to this PDB.
+    pub fn add_sheet(&mut self, sheet: Sheet) {
+        self.sheets.push(sheet);
+    }
+
+    /// Get the secondary structure annotation of the residue with the given id (serial number and
+    /// insertion code, see [`Residue::id`]) in the given chain. Gives `None` for residues which
+    /// are not part of any helix or strand.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1kmk.pdb")
+    ///     .unwrap();
+    /// let helix = match pdb.secondary_structure("A", (5, None)) {
+    ///     Some(SecondaryStructure::Helix(helix)) => helix,
+    ///     _ => panic!("Residue 5 is part of the first helix"),
+    /// };
+    /// assert_eq!(helix.serial_number, 1);
+    /// assert!(pdb.secondary_structure("A", (1, None)).is_none());
+    /// ```
+    pub fn secondary_structure(
+        &self,
+        chain: &str,
+        id: (isize, Option<&str>),
+    ) -> Option<SecondaryStructure<'_>> {
+        self.helices
+            .iter()
+            .find(|helix| helix.contains(chain, id))
+            .map(SecondaryStructure::Helix)
+            .or_else(|| {
+                self.sheets.iter().find_map(|sheet| {
+                    sheet
+                        .strands
+                        .iter()
+                        .find(|strand| strand.contains(chain, id))
+                        .map(|strand| SecondaryStructure::Strand(sheet, strand))
+                })
+            })
+    }
+}
+
+/// # Hetero groups
+/// Functionality for working with the hetero groups, like ligands and ions, as given by the HET,
+/// HETNAM, HETSYN, and FORMUL records.
+impl PDB {
+    /// Get an iterator of references to the HetGroups of this PDB.
+    pub fn het_groups(&self) -> impl DoubleEndedIterator<Item = &HetGroup> + '_ {
+        self.het_groups.iter()
+    }
+
+    /// Get an iterator of mutable references to the HetGroups of this PDB.
+    pub fn het_groups_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut HetGroup> + '_ {
+        self.het_groups.iter_mut()
+    }
+
+    /// Get the HetGroup with the given identifier, which is the name of its residues, if present.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1kmk.pdb")
+    ///     .unwrap();
+    /// let group = pdb.het_group("PLP").unwrap();
+    /// assert_eq!(group.name.as_deref(), Some("PYRIDOXAL-5'-PHOSPHATE"));
+    /// assert_eq!(group.synonyms, vec!["VITAMIN B6 PHOSPHATE"]);
+    /// assert_eq!(group.formula.as_deref(), Some("C8 H10 N O6 P"));
+    /// ```
+    pub fn het_group(&self, id: &str) -> Option<&HetGroup> {
+        self.het_groups.iter().find(|group| group.id == id)
+    }
+
+    /// Get the HetGroup listing the residue with the given id (serial number and insertion code,
+    /// see [`Residue::id`]) in the given chain in its HET records, if present.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new()
+    ///     .set_level(StrictnessLevel::Loose)
+    ///     .read("example-pdbs/1kmk.pdb")
+    ///     .unwrap();
+    /// assert_eq!(pdb.residue_het_group("A", (502, None)).unwrap().id, "SEC");
+    /// assert!(pdb.residue_het_group("A", (1, None)).is_none());
+    /// ```
+    pub fn residue_het_group(&self, chain: &str, id: (isize, Option<&str>)) -> Option<&HetGroup> {
+        self.het_groups
+            .iter()
+            .find(|group| group.contains(chain, id))
+    }
+
+    /// Add a HetGroup to this PDB.
+    pub fn add_het_group(&mut self, group: HetGroup) {
+        self.het_groups.push(group);
+    }
+
+    /// Delete the HetGroups matching the given predicate.
+    pub fn delete_het_groups_by<F>(&mut self, predicate: F)
+    where
+        F: Fn(&HetGroup) -> bool,
+    {
+        self.het_groups.retain(|g| !predicate(g));
+    }
 }
 
 impl<'a> PDB {
@@ -488,6 +1660,41 @@ impl<'a> PDB {
             .flat_map(move |(m, search)| m.find(search).map(move |h| h.extend(m)))
     }
 
+    /// Select all atoms matching the given search, see [`Selection`] and [`PDB::find`].
+    pub fn select(&self, search: Search) -> Selection {
+        let found: FxHashSet<usize> = self.find(search).map(|h| h.atom().counter()).collect();
+        self.select_by(|h| found.contains(&h.atom().counter()))
+    }
+
+    /// Select all atoms for which the given predicate returns `true`, see [`Selection`].
+    pub fn select_by<F>(&self, predicate: F) -> Selection
+    where
+        F: Fn(&AtomConformerResidueChainModel<'_>) -> bool,
+    {
+        let mut selection = Selection::new(self.total_atom_count());
+        for (id, hierarchy) in self.atoms_with_hierarchy().enumerate() {
+            if predicate(&hierarchy) {
+                selection.insert(id);
+            }
+        }
+        selection
+    }
+
+    /// Get the selected atoms with their hierarchy, in the order of [`PDB::atoms`]. The
+    /// selection should be created from this PDB, without adding or removing atoms since.
+    pub fn selected<'b>(
+        &'a self,
+        selection: &'b Selection,
+    ) -> impl Iterator<Item = AtomConformerResidueChainModel<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        self.atoms_with_hierarchy()
+            .enumerate()
+            .filter(move |(id, _)| selection.contains(*id))
+            .map(|(_, hierarchy)| hierarchy)
+    }
+
     /// Find all hierarchies matching the given search. For more details see [Search].
     pub fn find_mut(
         &'a mut self,
@@ -633,6 +1840,25 @@ impl<'a> PDB {
             .flat_map(|m| m.atoms_with_hierarchy().map(move |h| h.extend(m)))
     }
 
+    /// Get a parallel iterator of references to a struct containing all atoms with their hierarchy making up this PDB.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// use rayon::prelude::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let alpha = pdb
+    ///     .par_atoms_with_hierarchy()
+    ///     .filter(|h| h.atom().name() == "CA" && h.chain().id() == "A")
+    ///     .count();
+    /// assert_eq!(alpha, 76);
+    /// ```
+    #[doc_cfg(feature = "rayon")]
+    pub fn par_atoms_with_hierarchy(
+        &'a self,
+    ) -> impl ParallelIterator<Item = hierarchy::AtomConformerResidueChainModel<'a>> + 'a {
+        self.par_models()
+            .flat_map(|m| m.par_atoms_with_hierarchy().map(move |h| h.extend(m)))
+    }
+
     /// Get an iterator of mutable references to a struct containing all atoms with their hierarchy making up this PDB.
     pub fn atoms_with_hierarchy_mut(
         &'a mut self,
@@ -646,6 +1872,7 @@ impl<'a> PDB {
 
     /// Remove all Atoms matching the given predicate. The predicate will be run on all Atoms.
     /// As this is done in place this is the fastest way to remove Atoms from this PDB.
+    /// The bonds to any removed Atoms are removed as well.
     pub fn remove_atoms_by<F>(&mut self, predicate: F)
     where
         F: Fn(&Atom) -> bool,
@@ -653,10 +1880,12 @@ impl<'a> PDB {
         for residue in self.residues_mut() {
             residue.remove_atoms_by(&predicate);
         }
+        self.remove_dangling_bonds();
     }
 
     /// Remove all Conformers matching the given predicate. The predicate will be run on all Conformers.
     /// As this is done in place this is the fastest way to remove Conformers from this PDB.
+    /// The bonds to any removed Atoms are removed as well.
     pub fn remove_conformers_by<F>(&mut self, predicate: F)
     where
         F: Fn(&Conformer) -> bool,
@@ -664,10 +1893,12 @@ impl<'a> PDB {
         for chain in self.chains_mut() {
             chain.remove_conformers_by(&predicate);
         }
+        self.remove_dangling_bonds();
     }
 
     /// Remove all Residues matching the given predicate. The predicate will be run on all Residues.
     /// As this is done in place this is the fastest way to remove Residues from this PDB.
+    /// The bonds to any removed Atoms are removed as well.
     pub fn remove_residues_by<F>(&mut self, predicate: F)
     where
         F: Fn(&Residue) -> bool,
@@ -675,10 +1906,12 @@ impl<'a> PDB {
         for chain in self.chains_mut() {
             chain.remove_residues_by(&predicate);
         }
+        self.remove_dangling_bonds();
     }
 
-    /// Remove all Residues matching the given predicate. The predicate will be run on all Residues.
-    /// As this is done in place this is the fastest way to remove Residues from this PDB.
+    /// Remove all Chains matching the given predicate. The predicate will be run on all Chains.
+    /// As this is done in place this is the fastest way to remove Chains from this PDB.
+    /// The bonds to any removed Atoms are removed as well.
     pub fn remove_chains_by<F>(&mut self, predicate: F)
     where
         F: Fn(&Chain) -> bool,
@@ -686,15 +1919,18 @@ impl<'a> PDB {
         for model in self.models_mut() {
             model.remove_chains_by(&predicate);
         }
+        self.remove_dangling_bonds();
     }
 
-    /// Remove all Chains matching the given predicate. The predicate will be run on all Chains.
-    /// As this is done in place this is the fastest way to remove Chains from this PDB.
+    /// Remove all Models matching the given predicate. The predicate will be run on all Models.
+    /// As this is done in place this is the fastest way to remove Models from this PDB.
+    /// The bonds to any removed Atoms are removed as well.
     pub fn remove_models_by<F>(&mut self, predicate: F)
     where
         F: Fn(&Model) -> bool,
     {
         self.models.retain(|model| !predicate(model));
+        self.remove_dangling_bonds();
     }
 
     /// Remove the Model specified.
@@ -800,6 +2036,19 @@ impl<'a> PDB {
         }
     }
 
+    /// Remove the bonds of which one or both Atoms are not part of this PDB anymore, so the
+    /// bonds stay consistent after removing Atoms. This is done automatically by the removal
+    /// methods on PDB, but is needed after removing Atoms from a Model, Chain, or Residue
+    /// directly.
+    pub fn remove_dangling_bonds(&mut self) {
+        if self.bonds.is_empty() {
+            return;
+        }
+        let present: FxHashSet<usize> = self.atoms().map(Atom::counter).collect();
+        self.bonds
+            .retain(|(a, b, _)| present.contains(a) && present.contains(b));
+    }
+
     /// Remove all empty Models from this PDB, and all empty Chains from the Model, and all empty Residues from the Chains.
     pub fn remove_empty(&mut self) {
         self.models.iter_mut().for_each(Model::remove_empty);
@@ -851,6 +2100,33 @@ impl<'a> PDB {
         }
     }
 
+    /// Renumber the Atoms of every Model sequentially starting at 1, and the Residues of every
+    /// Chain sequentially starting at 1, removing their insertion codes. In contrast to
+    /// [`PDB::renumber`] the Chain IDs, Model serial numbers, and alternative locations are
+    /// kept. The bonds and anisotropic temperature factors stay attached to their Atoms.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// pdb.remove_residues_by(|r| r.serial_number() == 10);
+    /// pdb.renumber_sequential();
+    /// let serials: Vec<isize> = pdb.chain(0).unwrap().residues().map(Residue::serial_number).take(11).collect();
+    /// assert_eq!(serials, (1..=11).collect::<Vec<_>>());
+    /// assert!(pdb.atoms().enumerate().all(|(i, a)| a.serial_number() == i + 1));
+    /// ```
+    pub fn renumber_sequential(&mut self) {
+        for model in self.models_mut() {
+            for (index, atom) in model.atoms_mut().enumerate() {
+                atom.set_serial_number(index + 1);
+            }
+            for chain in model.chains_mut() {
+                for (serial_number, residue) in (1..).zip(chain.residues_mut()) {
+                    residue.set_serial_number(serial_number);
+                    residue.remove_insertion_code();
+                }
+            }
+        }
+    }
+
     /// Apply a transformation to the position of all atoms making up this PDB, the new position is immediately set.
     pub fn apply_transformation(&mut self, transformation: &TransformationMatrix) {
         for atom in self.atoms_mut() {
@@ -979,6 +2255,56 @@ impl<'a> PDB {
         ((min[0], min[1], min[2]), (max[0], max[1], max[2]))
     }
 
+    /// Voxelize the atoms of the first Model onto a grid with the given spacing in Aͦ, as atom
+    /// counts or electron counts depending on the given kernel, see
+    /// [`crate::geometry::density_grid`]. The grid can be saved as a CCP4/MRC map with
+    /// [`crate::save_map`], for example to generate a quick mask or to compare with a cryo-EM
+    /// map. Returns `None` if there are no atoms or the spacing is not positive.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let grid = pdb.to_density_grid(1.0, geometry::AtomKernel::Gaussian).unwrap();
+    /// let total: f64 = grid.as_slice().iter().sum();
+    /// println!("{total:.0} electrons on a {:?} grid", grid.dimensions());
+    /// ```
+    pub fn to_density_grid(
+        &self,
+        spacing: f64,
+        kernel: geometry::AtomKernel,
+    ) -> Option<geometry::DensityGrid> {
+        let atoms: Vec<&Atom> = self
+            .models()
+            .next()
+            .into_iter()
+            .flat_map(Model::atoms)
+            .collect();
+        geometry::density_grid(&atoms, spacing, kernel)
+    }
+
+    /// Generate a triangle mesh of the solvent accessible or solvent excluded surface of the
+    /// first Model, with a grid of the given spacing in Aͦ, see
+    /// [`crate::geometry::surface_mesh`]. The mesh can be saved as an OBJ or PLY file with
+    /// [`crate::save_mesh`]. Returns `None` if there are no atoms or the spacing is not positive.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let mesh = pdb.surface_mesh(geometry::SurfaceType::Excluded, 1.0).unwrap();
+    /// assert!(!mesh.is_empty());
+    /// ```
+    pub fn surface_mesh(
+        &self,
+        surface: geometry::SurfaceType,
+        spacing: f64,
+    ) -> Option<geometry::Mesh> {
+        let atoms: Vec<&Atom> = self
+            .models()
+            .next()
+            .into_iter()
+            .flat_map(Model::atoms)
+            .collect();
+        geometry::surface_mesh(&atoms, surface, spacing)
+    }
+
     /// Get the bonds in this PDB file. Runtime is `O(bonds_count * 2 * atom_count)` because it
     /// has to iterate over all atoms to prevent borrowing problems.
     pub fn bonds(&self) -> impl DoubleEndedIterator<Item = (&Atom, &Atom, Bond)> + '_ {
@@ -995,6 +2321,35 @@ impl<'a> PDB {
         })
     }
 
+    /// Get the atoms bonded to the given atom, with the type of each bond, see [`PDB::bonds`].
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1yyf.pdb").unwrap();
+    /// let atom = pdb.bonds().next().unwrap().0;
+    /// assert!(pdb.bonded_atoms(atom).count() > 0);
+    /// ```
+    pub fn bonded_atoms(&'a self, atom: &Atom) -> impl Iterator<Item = (&'a Atom, Bond)> + 'a {
+        let counter = atom.counter();
+        let partners: Vec<(usize, Bond)> = self
+            .bonds
+            .iter()
+            .filter_map(|(a, b, bond)| match (*a == counter, *b == counter) {
+                (true, _) => Some((*b, *bond)),
+                (_, true) => Some((*a, *bond)),
+                _ => None,
+            })
+            .collect();
+        // Only look up the atoms if there are any bonds
+        let atoms: FxHashMap<usize, &Atom> = if partners.is_empty() {
+            FxHashMap::default()
+        } else {
+            self.atoms().map(|atom| (atom.counter(), atom)).collect()
+        };
+        partners
+            .into_iter()
+            .filter_map(move |(other, bond)| atoms.get(&other).map(|atom| (*atom, bond)))
+    }
+
     /// Add a bond of the given type to the list of bonds in this PDB.
     /// The atoms are selected by serial number and alternative location.
     /// It uses `binary_find_atom` in the background so the PDB should be sorted.
@@ -1020,6 +2375,144 @@ impl<'a> PDB {
         self.bonds.push((atom1, atom2, bond));
     }
 
+    /// Get the bonds in this PDB as the raw counters of the atoms.
+    pub(crate) fn bond_counters(&self) -> impl Iterator<Item = (usize, usize, Bond)> + '_ {
+        self.bonds.iter().copied()
+    }
+
+    /// Get the glycans in this PDB, the trees of sugars covalently attached to amino acids.
+    /// These are built from the covalent bonds between the residues, as given by the LINK
+    /// records in PDB files. Sugars are recognised by their residue name and are linked through
+    /// their anomeric carbon (C1, or C2 for sialic acids). The glycans are given in the order
+    /// of the bonds to the glycosylated residues.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/3nig.pdb").unwrap();
+    /// for glycan in pdb.glycans() {
+    ///     println!("{:?} {:?} with {} sugars", glycan.site.id(), glycan.kind, glycan.len());
+    /// }
+    /// ```
+    pub fn glycans(&self) -> Vec<Glycan<'_>> {
+        crate::structs::glycans(self)
+    }
+
+    /// Analyse the coordination sphere of the given metal atom, using all atoms of this PDB
+    /// within the cutoff distance (in Aͦ) as possible ligands, see
+    /// [`geometry::coordination_sphere`].
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/3nig.pdb").unwrap();
+    /// let calcium = pdb.atoms().find(|a| a.element() == Some(&Element::Ca)).unwrap();
+    /// let sphere = pdb.coordination_sphere(calcium, 3.0);
+    /// println!("{:?} with {} ligands", sphere.geometry, sphere.coordination_number());
+    /// ```
+    pub fn coordination_sphere(
+        &'a self,
+        metal: &'a Atom,
+        cutoff: f64,
+    ) -> geometry::CoordinationSphere<'a> {
+        let atoms: Vec<&Atom> = self.atoms().collect();
+        geometry::coordination_sphere(metal, &atoms, cutoff)
+    }
+
+    /// Find the water molecules with any atom within the cutoff distance (in Aͦ) of any atom of
+    /// the given selection, the solvation shell of the selection. Waters are recognised by their
+    /// name, see [`Conformer::is_water`], and waters which are part of the selection themselves
+    /// are not included. Only the first Model is used, and the waters are given in the order of
+    /// the structure. A spatial index is used so this scales to large structures.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let shell = pdb.solvent_shell(Search::Single(Term::ResidueSerialNumber(48)), 3.5);
+    /// println!("Lys48 is hydrated by {} waters", shell.len());
+    /// ```
+    #[doc_cfg(feature = "rstar")]
+    pub fn solvent_shell(&'a self, selection: Search, cutoff: f64) -> Vec<&'a Residue> {
+        use rstar::primitives::GeomWithData;
+
+        let model = match self.models.first() {
+            Some(model) => model,
+            None => return Vec::new(),
+        };
+        let selected: Vec<AtomConformerResidueChain<'a>> =
+            model.find(selection.add_model_info(model)).collect();
+        let waters: Vec<&Residue> = model
+            .residues()
+            .filter(|r| r.conformers().any(Conformer::is_water))
+            .filter(|r| !selected.iter().any(|h| std::ptr::eq(h.residue(), *r)))
+            .collect();
+        let tree = rstar::RTree::bulk_load(
+            waters
+                .iter()
+                .enumerate()
+                .flat_map(|(index, residue)| {
+                    residue.atoms().map(move |atom| {
+                        let (x, y, z) = atom.pos();
+                        GeomWithData::new([x, y, z], index)
+                    })
+                })
+                .collect(),
+        );
+
+        let mut in_shell = vec![false; waters.len()];
+        for hierarchy in &selected {
+            let (x, y, z) = hierarchy.atom().pos();
+            for point in tree.locate_within_distance([x, y, z], cutoff * cutoff) {
+                in_shell[point.data] = true;
+            }
+        }
+        waters
+            .into_iter()
+            .zip(in_shell)
+            .filter_map(|(water, in_shell)| in_shell.then_some(water))
+            .collect()
+    }
+
+    /// Find the bridging water molecules between two selections, the waters which are within the
+    /// cutoff distance (in Aͦ) of both selections, see [`PDB::solvent_shell`]. These mediate
+    /// hydrogen bonds between the selections, for example at a protein-protein or
+    /// protein-ligand interface.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let bridging = pdb.bridging_waters(
+    ///     Search::Single(Term::ResidueSerialNumberRange(1, 30)),
+    ///     Search::Single(Term::ResidueSerialNumberRange(40, 76)),
+    ///     3.5,
+    /// );
+    /// println!("{} waters bridge the two halves", bridging.len());
+    /// ```
+    #[doc_cfg(feature = "rstar")]
+    pub fn bridging_waters(&'a self, a: Search, b: Search, cutoff: f64) -> Vec<&'a Residue> {
+        let shell_b = self.solvent_shell(b, cutoff);
+        self.solvent_shell(a, cutoff)
+            .into_iter()
+            .filter(|water| shell_b.iter().any(|other| std::ptr::eq(*water, *other)))
+            .collect()
+    }
+
+    /// Get the molecular weight of this PDB in Dalton, using only the first Model so ensembles
+    /// are not counted multiple times, see [`Chain::molecular_weight`]. This is 0.0 if there are
+    /// no Models.
+    pub fn molecular_weight(&self) -> f64 {
+        self.models.first().map_or(0.0, Model::molecular_weight)
+    }
+
+    /// Get the net formal charge of this PDB, using only the first Model, see
+    /// [`Chain::net_charge`]. This is 0 if there are no Models.
+    pub fn net_charge(&self) -> isize {
+        self.models.first().map_or(0, Model::net_charge)
+    }
+
+    /// Get the net charge of this PDB assuming the standard protonation states at neutral pH,
+    /// using only the first Model, see [`Chain::net_charge_at_neutral_ph`]. This is 0 if there
+    /// are no Models.
+    pub fn net_charge_at_neutral_ph(&self) -> isize {
+        self.models
+            .first()
+            .map_or(0, Model::net_charge_at_neutral_ph)
+    }
+
     /// Returns a HashMap with the chains in contact within a given distance.
     ///
     /// # Arguments
@@ -1073,6 +2566,139 @@ impl<'a> PDB {
         }
         names
     }
+
+    /// Check if this PDB is approximately equal to the given PDB, with all Atoms compared with
+    /// [`Atom::approx_eq`]. All other properties, including the metadata like the unit cell, are
+    /// compared as for `==`. This is useful in regression tests, for example to check that a
+    /// structure survives a round trip through a file format with limited precision.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let mut moved = pdb.clone();
+    /// moved.atoms_mut().for_each(|atom| atom.set_x(atom.x() + 0.0001).unwrap());
+    /// assert!(pdb != moved);
+    /// assert!(pdb.approx_eq(&moved, 0.001));
+    /// assert!(!pdb.approx_eq(&moved, 0.00001));
+    /// ```
+    pub fn approx_eq(&self, other: &PDB, tolerance: f64) -> bool {
+        self.identifier == other.identifier
+            && self.metadata == other.metadata
+            && self.remarks == other.remarks
+            && self.scale == other.scale
+            && self.origx == other.origx
+            && self.mtrix == other.mtrix
+            && self.sites == other.sites
+            && self.helices == other.helices
+            && self.sheets == other.sheets
+            && self.het_groups == other.het_groups
+            && self.unit_cell == other.unit_cell
+            && self.symmetry == other.symmetry
+            && self.indexed_bonds() == other.indexed_bonds()
+            && self.entities == other.entities
+            && self.annotations == other.annotations
+            && self.models.len() == other.models.len()
+            && self
+                .models
+                .iter()
+                .zip(&other.models)
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
+}
+
+/// Generate Chain IDs not in the given set of used IDs: first `A-Z`, `a-z`, and `0-9`, then two
+/// letter IDs (`AA`, `AB`, ...), and longer IDs after that.
+fn unused_chain_ids(mut used: std::collections::HashSet<String>) -> impl Iterator<Item = String> {
+    let single = ('A'..='Z')
+        .chain('a'..='z')
+        .chain('0'..='9')
+        .map(String::from);
+    let double = ('A'..='Z').flat_map(|a| ('A'..='Z').map(move |b| format!("{a}{b}")));
+    let longer = (26 * 26..).map(number_to_base26);
+    single
+        .chain(double)
+        .chain(longer)
+        .filter(move |id| used.insert(id.clone()))
+}
+
+impl PDB {
+    /// Get the bonds with their Atoms given as the index in [`PDB::atoms`], instead of the
+    /// counters, which are different for copies of the same Atom. Atoms that are not present
+    /// are given as `None`.
+    fn indexed_bonds(&self) -> Vec<(Option<usize>, Option<usize>, Bond)> {
+        let indices: FxHashMap<usize, usize> = self
+            .atoms()
+            .enumerate()
+            .map(|(index, atom)| (atom.counter(), index))
+            .collect();
+        self.bonds
+            .iter()
+            .map(|(a, b, bond)| (indices.get(a).copied(), indices.get(b).copied(), *bond))
+            .collect()
+    }
+}
+
+impl Clone for PDB {
+    /// The cloned Atoms get new counters, so the bonds are moved over to the cloned Atoms.
+    fn clone(&self) -> Self {
+        let mut pdb = PDB {
+            identifier: self.identifier.clone(),
+            metadata: self.metadata.clone(),
+            remarks: self.remarks.clone(),
+            scale: self.scale.clone(),
+            origx: self.origx.clone(),
+            mtrix: self.mtrix.clone(),
+            sites: self.sites.clone(),
+            helices: self.helices.clone(),
+            sheets: self.sheets.clone(),
+            het_groups: self.het_groups.clone(),
+            unit_cell: self.unit_cell.clone(),
+            symmetry: self.symmetry.clone(),
+            models: self.models.clone(),
+            bonds: Vec::new(),
+            entities: self.entities.clone(),
+            annotations: self.annotations.clone(),
+        };
+        let counters: FxHashMap<usize, usize> = self
+            .atoms()
+            .zip(pdb.atoms())
+            .map(|(original, copy)| (original.counter(), copy.counter()))
+            .collect();
+        pdb.bonds = self
+            .bonds
+            .iter()
+            .map(|(a, b, bond)| {
+                (
+                    counters.get(a).copied().unwrap_or(*a),
+                    counters.get(b).copied().unwrap_or(*b),
+                    *bond,
+                )
+            })
+            .collect();
+        pdb
+    }
+}
+
+impl PartialEq for PDB {
+    /// The bonds are compared on the position of their Atoms in the structure, so copies of a
+    /// PDB are equal to the original.
+    fn eq(&self, other: &Self) -> bool {
+        self.identifier == other.identifier
+            && self.metadata == other.metadata
+            && self.remarks == other.remarks
+            && self.scale == other.scale
+            && self.origx == other.origx
+            && self.mtrix == other.mtrix
+            && self.sites == other.sites
+            && self.helices == other.helices
+            && self.sheets == other.sheets
+            && self.het_groups == other.het_groups
+            && self.unit_cell == other.unit_cell
+            && self.symmetry == other.symmetry
+            && self.models == other.models
+            && self.indexed_bonds() == other.indexed_bonds()
+            && self.entities == other.entities
+            && self.annotations == other.annotations
+    }
 }
 
 impl fmt::Display for PDB {
@@ -1102,6 +2728,45 @@ impl FromIterator<Model> for PDB {
     }
 }
 
+impl<'a> IntoIterator for &'a PDB {
+    type Item = &'a Model;
+    type IntoIter = std::slice::Iter<'a, Model>;
+    /// Iterate over the Models of this PDB, see [`PDB::models`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.models.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut PDB {
+    type Item = &'a mut Model;
+    type IntoIter = std::slice::IterMut<'a, Model>;
+    /// Iterate mutably over the Models of this PDB, see [`PDB::models_mut`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.models.iter_mut()
+    }
+}
+
+impl ops::Index<usize> for PDB {
+    type Output = Model;
+    /// Get the Model at the given index, see [`PDB::model`] for the fallible version.
+    ///
+    /// ## Panics
+    /// It panics if the index is out of bounds.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.models[index]
+    }
+}
+
+impl ops::IndexMut<usize> for PDB {
+    /// Get the Model at the given index mutably, see [`PDB::model_mut`] for the fallible version.
+    ///
+    /// ## Panics
+    /// It panics if the index is out of bounds.
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.models[index]
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -1413,6 +3078,29 @@ mod tests {
 
         assert_eq!(chainmap, my_map);
     }
+    #[test]
+    fn build_biological_assembly() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("example-pdbs")
+            .join("1kmk.pdb");
+        let (pdb, _) = ReadOptions::default()
+            .set_level(crate::StrictnessLevel::Loose)
+            .read(path.to_str().unwrap())
+            .unwrap();
+        let assembly = &pdb.biological_assemblies()[0];
+        let transformation = &assembly.parts[0].transformations[1];
+        let built = pdb.build_biological_assembly(1).unwrap();
+        let ids: Vec<&str> = built.chains().map(Chain::id).collect();
+        assert_eq!(ids, ["A", "B"]);
+        let original = pdb.chain(0).unwrap().atom(0).unwrap();
+        let copy = built.chain(1).unwrap().atom(0).unwrap();
+        let expected = transformation.apply(original.pos());
+        assert!((copy.x() - expected.0).abs() < 1e-6);
+        assert!((copy.y() - expected.1).abs() < 1e-6);
+        assert!((copy.z() - expected.2).abs() < 1e-6);
+        assert!(pdb.build_biological_assembly(2).is_none());
+    }
+
     #[test]
     fn test_unique_conformer_names() {
         let path = Path::new(env!("CARGO_MANIFEST_DIR"))
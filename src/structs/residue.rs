@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use crate::reference_tables;
 use crate::structs::*;
 use crate::transformation::TransformationMatrix;
 use doc_cfg::doc_cfg;
@@ -6,6 +7,7 @@ use doc_cfg::doc_cfg;
 use rayon::prelude::*;
 use std::cmp::Ordering;
 use std::fmt;
+use std::ops;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -16,7 +18,9 @@ pub struct Residue {
     /// The insertion code of this Residue, used in conjunction with the serial number to uniquely identify Residues.
     insertion_code: Option<String>,
     /// The list of conformers making up this Residue
-    conformers: Vec<Conformer>,
+    conformers: Children<Conformer>,
+    /// Custom annotations on this Residue
+    annotations: Annotations,
 }
 
 impl<'a> Residue {
@@ -38,7 +42,8 @@ impl<'a> Residue {
         let mut res = Self {
             serial_number: number,
             insertion_code: None,
-            conformers: Vec::new(),
+            conformers: Children::new(),
+            annotations: Annotations::new(),
         };
         if let Some(ic) = insertion_code {
             if !res.set_insertion_code(ic) {
@@ -53,6 +58,27 @@ impl<'a> Residue {
         Some(res)
     }
 
+    /// Create a new Residue with space for at least the given number of Conformers, to prevent
+    /// reallocations when the number of Conformers is known beforehand.
+    ///
+    /// ## Arguments
+    /// * `number` - the serial number
+    /// * `insertion_code` - the insertion code
+    /// * `conformers` - the number of Conformers to reserve space for
+    ///
+    /// ## Fails
+    /// It fails and returns `None` if any of the characters making up the `insertion_code` are invalid.
+    #[must_use]
+    pub fn with_capacity(
+        number: isize,
+        insertion_code: Option<&str>,
+        conformers: usize,
+    ) -> Option<Self> {
+        let mut res = Self::new(number, insertion_code, None)?;
+        res.conformers.reserve(conformers);
+        Some(res)
+    }
+
     /// Get the serial number of the Residue.
     #[must_use]
     pub const fn serial_number(&self) -> isize {
@@ -83,6 +109,16 @@ impl<'a> Residue {
         self.insertion_code = None;
     }
 
+    /// Get the custom annotations of this Residue, see [`Annotations`].
+    pub const fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
+
+    /// Get the custom annotations of this Residue mutably, see [`Annotations`].
+    pub fn annotations_mut(&mut self) -> &mut Annotations {
+        &mut self.annotations
+    }
+
     /// Returns the uniquely identifying construct for this Residue,
     /// consisting of the serial number and the insertion code.
     #[must_use]
@@ -90,6 +126,25 @@ impl<'a> Residue {
         (self.serial_number, self.insertion_code())
     }
 
+    /// Get the key used to order Residues, the serial number followed by the insertion code,
+    /// where a Residue without an insertion code comes before the Residues with the same serial
+    /// number with an insertion code (`1`, `1A`, `1B`, `2`). This is the ordering used by
+    /// [`Chain::sort`] and the `Ord` implementation of Residue.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let mut residues = vec![
+    ///     Residue::new(2, None, None).unwrap(),
+    ///     Residue::new(1, Some("A"), None).unwrap(),
+    ///     Residue::new(1, None, None).unwrap(),
+    /// ];
+    /// residues.sort();
+    /// assert_eq!(residues.binary_search_by_key(&(1, Some("A")), Residue::sort_key), Ok(1));
+    /// ```
+    #[must_use]
+    pub fn sort_key(&self) -> (isize, Option<&str>) {
+        self.id()
+    }
+
     /// The ID or name of the Residue, it will only give a value if there is only one conformer or if all conformers have the same name
     #[must_use]
     pub fn name(&self) -> Option<&str> {
@@ -108,6 +163,194 @@ impl<'a> Residue {
         }
     }
 
+    /// Write the first Conformer of this Residue as a SMILES string, see [`Conformer::to_smiles`].
+    /// This is intended for ligands (hetero residues). Returns `None` if there are no Conformers.
+    ///
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1kmk.pdb").unwrap();
+    /// let ligand = pdb.residues().find(|r| r.name() == Some("PLP")).unwrap();
+    /// println!("{}", ligand.to_smiles().unwrap());
+    /// ```
+    pub fn to_smiles(&self) -> Option<String> {
+        self.conformers.first().map(Conformer::to_smiles)
+    }
+
+    /// Get the molecular formula of the first Conformer of this Residue, see
+    /// [`Conformer::formula`]. Returns `None` if there are no Conformers.
+    ///
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/3nig.pdb").unwrap();
+    /// let glcnac = pdb.residues().find(|r| r.name() == Some("NAG")).unwrap();
+    /// // Without hydrogens, and without the O1 atom lost in the glycosidic bond
+    /// assert_eq!(glcnac.formula().unwrap(), "C8 N O5");
+    /// ```
+    pub fn formula(&self) -> Option<String> {
+        self.conformers.first().map(Conformer::formula)
+    }
+
+    /// Get the molecular weight of the first Conformer of this Residue in Dalton, see
+    /// [`Conformer::molecular_weight`]. This is 0.0 if there are no Conformers.
+    pub fn molecular_weight(&self) -> f64 {
+        self.conformers
+            .first()
+            .map_or(0.0, Conformer::molecular_weight)
+    }
+
+    /// Get the mass of this Residue in Dalton, the sum of the masses of the Atoms of its first
+    /// Conformer (see [`Atom::mass`]). This is the same as [`Residue::molecular_weight`].
+    pub fn mass(&self) -> f64 {
+        self.molecular_weight()
+    }
+
+    /// Get the net formal charge of the first Conformer of this Residue, as the sum of the
+    /// charges of its Atoms. This is 0 if there are no Conformers.
+    pub fn net_charge(&self) -> isize {
+        self.conformers
+            .first()
+            .map_or(0, |conformer| conformer.atoms().map(Atom::charge).sum())
+    }
+
+    /// Get the net charge of this Residue assuming the standard protonation states at neutral
+    /// pH, instead of the formal charges in the file which are often missing. Amino acids get
+    /// the charge of their side chain (+1 for Arg and Lys, -1 for Asp and Glu, histidine is
+    /// neutral), nucleotides -1 for their phosphate if present. All other Residues, like
+    /// ligands and ions, get their net formal charge, see [`Residue::net_charge`]. The charges
+    /// of the termini of a chain cancel so they are not included.
+    pub fn net_charge_at_neutral_ph(&self) -> isize {
+        let conformer = match self.conformers.first() {
+            Some(conformer) => conformer,
+            None => return 0,
+        };
+        if let Some(charge) = reference_tables::amino_acid_charge(conformer.name()) {
+            charge
+        } else if conformer.is_nucleotide() {
+            -isize::from(conformer.atoms().any(|atom| atom.name() == "P"))
+        } else {
+            self.net_charge()
+        }
+    }
+
+    /// Calculate the backbone torsion angles phi and psi in degrees of this Residue, with the
+    /// previous and next residue in the chain, see [`crate::geometry::backbone_torsions`]. An
+    /// angle is `None` if any of its backbone atoms (C, N, CA) is missing, or if the neighbour
+    /// needed is not given or not bonded to this Residue.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let chain = pdb.chain(0).unwrap();
+    /// let (first, second) = (chain.residue(0).unwrap(), chain.residue(1).unwrap());
+    /// assert_eq!(first.phi_psi(None, Some(second)).0, None);
+    /// let (phi, psi) = second.phi_psi(Some(first), chain.residue(2));
+    /// assert!(phi.is_some() && psi.is_some());
+    /// ```
+    pub fn phi_psi(
+        &self,
+        previous: Option<&Residue>,
+        next: Option<&Residue>,
+    ) -> (Option<f64>, Option<f64>) {
+        let torsions = crate::geometry::backbone_torsions(previous, self, next);
+        (torsions.phi, torsions.psi)
+    }
+
+    /// Mutate this Residue into the given standard amino acid, for all its Conformers. The
+    /// Residue is renamed and the backbone and the side chain atoms shared with the new amino
+    /// acid are kept, except for mutations to or from proline where only the CB is kept. Atoms at
+    /// the same position in isosteric amino acids are renamed (for example OG of serine becomes
+    /// SG of cysteine), all other side chain atoms are removed. The backbone hydrogens are kept,
+    /// the side chain hydrogens removed. Returns the names of the heavy atoms of the new amino
+    /// acid which are missing and need to be rebuilt, in the order of the template.
+    ///
+    /// ## Errors
+    /// Fails if the given name is not a standard amino acid, the Residue is not changed.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (mut pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let residue = pdb.chain_mut(0).unwrap().residue_mut(0).unwrap();
+    /// assert_eq!(residue.name(), Some("MET"));
+    /// assert_eq!(residue.mutate("ALA"), Ok(Vec::new()));
+    /// assert_eq!(residue.mutate("LEU"), Ok(vec!["CG".to_string(), "CD1".to_string(), "CD2".to_string()]));
+    /// ```
+    pub fn mutate(&mut self, name: &str) -> Result<Vec<String>, String> {
+        let name = name.trim().to_ascii_uppercase();
+        let template = reference_tables::amino_acid_atoms(&name)
+            .ok_or_else(|| format!("{name} is not a standard amino acid"))?;
+        for conformer in &mut self.conformers {
+            let from_glycine = conformer.name() == "GLY";
+            // The side chain of proline is bonded to its backbone, so it only shares the CB
+            let kept = if (conformer.name() == "PRO") == (name == "PRO") {
+                template
+            } else {
+                &template[..template.len().min(5)]
+            };
+            let present: Vec<String> = conformer.atoms().map(|a| a.name().to_string()).collect();
+            conformer.remove_atoms_by(|atom| {
+                let atom_name = atom.name();
+                if atom.element() == Some(&Element::H) || atom_name.starts_with('H') {
+                    // Only backbone hydrogens are kept, prolines have no amide hydrogen
+                    !reference_tables::is_backbone(atom_name)
+                        || (name == "PRO" && atom_name == "H")
+                        || (from_glycine && name != "GLY" && atom_name == "HA3")
+                } else {
+                    atom_name != "OXT"
+                        && !kept.contains(&atom_name)
+                        && !reference_tables::equivalent_atom_name(atom_name).map_or(
+                            false,
+                            |equivalent| {
+                                template.contains(&equivalent)
+                                    && !present.iter().any(|p| p == equivalent)
+                            },
+                        )
+                }
+            });
+            for atom in conformer.atoms_mut() {
+                let new_name = match atom.name() {
+                    "HA2" if from_glycine && name != "GLY" => Some("HA"),
+                    "HA" if name == "GLY" => Some("HA2"),
+                    other if !template.contains(&other) => {
+                        reference_tables::equivalent_atom_name(other)
+                    }
+                    _ => None,
+                };
+                if let Some(new_name) = new_name {
+                    atom.set_name(new_name)
+                        .expect("Template atom names are valid");
+                    if let Some(element) = Element::from_symbol(&new_name[..1]) {
+                        atom.set_element(element);
+                    }
+                }
+            }
+            conformer.set_name(&name);
+        }
+        Ok(template
+            .iter()
+            .filter(|atom| {
+                !self
+                    .conformers
+                    .iter()
+                    .all(|c| c.atoms().any(|a| a.name() == **atom))
+            })
+            .map(|atom| (*atom).to_string())
+            .collect())
+    }
+
+    /// Match the first Conformer of this Residue against a chemical component definition, see
+    /// [`crate::chemistry::match_component`]. Returns `None` if there are no Conformers or if the
+    /// Residue does not fit the component.
+    pub fn match_component(
+        &self,
+        component: &crate::chemistry::Component,
+    ) -> Option<crate::chemistry::ComponentMatch> {
+        let atoms: Vec<&Atom> = self.conformers.first()?.atoms().collect();
+        crate::chemistry::match_component(&atoms, component)
+    }
+
+    /// Reserve space for at least `additional` more Conformers in this Residue.
+    pub fn reserve(&mut self, additional: usize) {
+        self.conformers.reserve(additional);
+    }
+
     /// The number of Conformers making up this Residue.
     #[must_use]
     pub fn conformer_count(&self) -> usize {
@@ -117,7 +360,14 @@ impl<'a> Residue {
     /// Get the number of Atoms making up this Residue.
     #[must_use]
     pub fn atom_count(&self) -> usize {
-        self.conformers().fold(0, |sum, res| res.atom_count() + sum)
+        self.conformers
+            .atom_count(|conformers| conformers.iter().map(Conformer::atom_count).sum())
+    }
+
+    /// Clear the cached number of Atoms, for changes made to the children of this Residue without
+    /// going through it, like through the mutable hierarchies.
+    pub(crate) fn clear_atom_count(&mut self) {
+        self.conformers.clear_atom_count();
     }
 
     /// Get the number of Atoms making up this Residue in parallel.
@@ -482,12 +732,191 @@ impl<'a> Residue {
             .for_each(|conformer| conformer.apply_transformation(transformation));
     }
 
+    /// Get a copy of this Residue with only the Conformers with the given alternative location,
+    /// and the Conformers without alternative location. If this Residue has alternative
+    /// locations, but not the given one, the first alternative location is used instead. The
+    /// alternative locations are removed, so all Conformers with the same name are joined.
+    pub(crate) fn select_alternative_location(&self, alternative_location: &str) -> Residue {
+        let available: Vec<&str> = self
+            .conformers
+            .iter()
+            .filter_map(Conformer::alternative_location)
+            .collect();
+        let chosen = if available.contains(&alternative_location) {
+            Some(alternative_location)
+        } else {
+            available.first().copied()
+        };
+        let mut residue = Residue {
+            serial_number: self.serial_number,
+            insertion_code: self.insertion_code.clone(),
+            conformers: Children::new(),
+            annotations: self.annotations.clone(),
+        };
+        for conformer in &self.conformers {
+            let location = conformer.alternative_location();
+            if location.is_some() && location != chosen {
+                continue;
+            }
+            let mut conformer = conformer.clone();
+            conformer.remove_alternative_location();
+            if let Some(existing) = residue
+                .conformers
+                .iter_mut()
+                .find(|c| c.id() == conformer.id())
+            {
+                existing.join(conformer);
+            } else {
+                residue.conformers.push(conformer);
+            }
+        }
+        residue
+    }
+
+    /// Get a copy of this Residue with only the alternative location with the highest mean
+    /// occupancy, and the Conformers without alternative location, see
+    /// [`crate::PDB::keep_highest_occupancy_alt_locs`]. With equal occupancies the first
+    /// alternative location is kept.
+    pub(crate) fn highest_occupancy_location(&self) -> Residue {
+        let mut locations: Vec<(&str, f64, usize)> = Vec::new();
+        for conformer in &self.conformers {
+            if let Some(location) = conformer.alternative_location() {
+                let occupancy = conformer.atoms().map(Atom::occupancy).sum::<f64>();
+                match locations.iter_mut().find(|(l, _, _)| *l == location) {
+                    Some((_, total, count)) => {
+                        *total += occupancy;
+                        *count += conformer.atom_count();
+                    }
+                    None => locations.push((location, occupancy, conformer.atom_count())),
+                }
+            }
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let mean = |(_, total, count): &(&str, f64, usize)| total / (*count).max(1) as f64;
+        match locations
+            .iter()
+            .fold(None, |best: Option<&(&str, f64, usize)>, next| match best {
+                Some(best) if mean(best) >= mean(next) => Some(best),
+                _ => Some(next),
+            }) {
+            Some((location, _, _)) => self.select_alternative_location(location),
+            None => self.clone(),
+        }
+    }
+
     /// Join this Residue with another Residue, this moves all Conformers from the other Residue
     /// to this Residue. All other (meta) data of this Residue will stay the same.
     pub fn join(&mut self, other: Self) {
         self.conformers.extend(other.conformers);
     }
 
+    /// Combine the given versions of the same Residue (from different Models) into one Residue
+    /// with alternative locations, see [`crate::PDB::collapse_alt_locs`]. If all versions have
+    /// their atoms at the same positions the first version is used as is. Otherwise every version
+    /// becomes a Conformer with all its atoms, with the alternative location `A` for the first
+    /// version, `B` for the second, and so on, like alternative locations are read from files.
+    /// Versions that do not differ from an earlier version are left out.
+    pub(crate) fn combine_alternative_locations(versions: &[&Residue]) -> Residue {
+        /// The maximal distance in Aͦ between the same atom in two versions to count as equal
+        const TOLERANCE: f64 = 1e-3;
+        let same = |a: &Residue, b: &Residue| {
+            a.conformers.len() == b.conformers.len()
+                && a.conformers.iter().zip(&b.conformers).all(|(a, b)| {
+                    a.name() == b.name()
+                        && a.atom_count() == b.atom_count()
+                        && a.atoms()
+                            .zip(b.atoms())
+                            .all(|(a, b)| a.name() == b.name() && a.distance(b) <= TOLERANCE)
+                })
+        };
+        let mut distinct: Vec<(usize, &Residue)> = Vec::new();
+        for (index, version) in versions.iter().enumerate() {
+            if !distinct.iter().any(|(_, other)| same(other, version)) {
+                distinct.push((index, version));
+            }
+        }
+        if distinct.len() == 1 {
+            return versions[0].clone();
+        }
+        let mut residue = Residue {
+            serial_number: versions[0].serial_number,
+            insertion_code: versions[0].insertion_code.clone(),
+            conformers: Children::new(),
+            annotations: versions[0].annotations.clone(),
+        };
+        for (index, version) in distinct {
+            let location = number_to_base26(index);
+            for conformer in &version.conformers {
+                let mut conformer = conformer.clone();
+                conformer.set_alternative_location(&location);
+                residue.conformers.push(conformer);
+            }
+        }
+        residue
+    }
+
+    /// Get a copy of this Residue with its alternative locations averaged into single atoms, see
+    /// [`crate::PDB::average_alt_locs`].
+    pub(crate) fn average_alternative_locations(&self) -> Residue {
+        if self
+            .conformers
+            .iter()
+            .all(|c| c.alternative_location().is_none())
+        {
+            return self.clone();
+        }
+        // With microheterogeneity the residue type with the highest occupancy is kept
+        let occupancy = |conformer: &Conformer| {
+            #[allow(clippy::cast_precision_loss)]
+            let count = conformer.atom_count().max(1) as f64;
+            conformer.atoms().map(Atom::occupancy).sum::<f64>() / count
+        };
+        let mut names: Vec<(&str, f64)> = Vec::new();
+        for conformer in &self.conformers {
+            match names.iter_mut().find(|(name, _)| *name == conformer.name()) {
+                Some((_, total)) => *total += occupancy(conformer),
+                None => names.push((conformer.name(), occupancy(conformer))),
+            }
+        }
+        let name = names
+            .iter()
+            .fold(
+                names[0],
+                |best, next| if next.1 > best.1 { *next } else { best },
+            )
+            .0;
+
+        let mut averaged: Option<Conformer> = None;
+        let mut groups: Vec<Vec<&Atom>> = Vec::new();
+        for conformer in self.conformers.iter().filter(|c| c.name() == name) {
+            if averaged.is_none() {
+                let mut empty = conformer.clone();
+                empty.remove_alternative_location();
+                empty.remove_atoms_by(|_| true);
+                averaged = Some(empty);
+            }
+            for atom in conformer.atoms() {
+                match groups
+                    .iter_mut()
+                    .find(|group| group[0].name() == atom.name())
+                {
+                    Some(group) => group.push(atom),
+                    None => groups.push(vec![atom]),
+                }
+            }
+        }
+        let mut conformer = averaged.expect("The chosen residue type has a conformer");
+        for group in groups {
+            conformer.add_atom(average_atoms(&group));
+        }
+        Residue {
+            serial_number: self.serial_number,
+            insertion_code: self.insertion_code.clone(),
+            conformers: vec![conformer].into(),
+            annotations: self.annotations.clone(),
+        }
+    }
+
     /// Sort the Conformers of this Residue
     pub fn sort(&mut self) {
         self.conformers.sort();
@@ -498,6 +927,88 @@ impl<'a> Residue {
     pub fn par_sort(&mut self) {
         self.conformers.par_sort();
     }
+
+    /// Check if this Residue is approximately equal to the given Residue, with all Atoms
+    /// compared with [`Atom::approx_eq`] and all other properties compared as for `==`.
+    pub fn approx_eq(&self, other: &Residue, tolerance: f64) -> bool {
+        self.serial_number == other.serial_number
+            && self.insertion_code == other.insertion_code
+            && self.annotations == other.annotations
+            && self.conformers.len() == other.conformers.len()
+            && self
+                .conformers
+                .iter()
+                .zip(&other.conformers)
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
+}
+
+/// Average the given alternative positions of the same atom, weighted by their occupancies (or
+/// equally if these are all zero). The occupancies are summed (up to 1) and the B factor combines
+/// the B factors and the spread of the positions: B = Σ wᵢBᵢ + 8π²/3 Σ wᵢ|rᵢ - r̄|². The
+/// anisotropic temperature factors, if present, are combined in the same way.
+fn average_atoms(atoms: &[&Atom]) -> Atom {
+    let mut average = atoms[0].clone();
+    if atoms.len() == 1 {
+        return average;
+    }
+    let total: f64 = atoms.iter().map(|a| a.occupancy()).sum();
+    #[allow(clippy::cast_precision_loss)]
+    let weights: Vec<f64> = if total > 0.0 {
+        atoms.iter().map(|a| a.occupancy() / total).collect()
+    } else {
+        vec![1.0 / atoms.len() as f64; atoms.len()]
+    };
+    let mut mean = [0.0; 3];
+    for (atom, weight) in atoms.iter().zip(&weights) {
+        let (x, y, z) = atom.pos();
+        for (m, value) in mean.iter_mut().zip([x, y, z]) {
+            *m += weight * value;
+        }
+    }
+    let deviations: Vec<[f64; 3]> = atoms
+        .iter()
+        .map(|atom| {
+            let (x, y, z) = atom.pos();
+            [x - mean[0], y - mean[1], z - mean[2]]
+        })
+        .collect();
+    let eight_pi_squared = 8.0 * std::f64::consts::PI.powi(2);
+    let b_factor: f64 = atoms
+        .iter()
+        .zip(&deviations)
+        .zip(&weights)
+        .map(|((atom, d), weight)| {
+            weight
+                * (atom.b_factor()
+                    + eight_pi_squared / 3.0 * (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]))
+        })
+        .sum();
+    average
+        .set_pos((mean[0], mean[1], mean[2]))
+        .expect("The average of finite positions is finite");
+    average
+        .set_occupancy(total.min(1.0))
+        .expect("The sum of occupancies is valid");
+    average
+        .set_b_factor(b_factor)
+        .expect("The combined B factor is valid");
+    if average.anisotropic_temperature_factors().is_some() {
+        let mut factors = [[0.0; 3]; 3];
+        for ((atom, d), weight) in atoms.iter().zip(&deviations).zip(&weights) {
+            let own = atom.anisotropic_temperature_factors().unwrap_or_else(|| {
+                let u = atom.b_factor() / eight_pi_squared;
+                [[u, 0.0, 0.0], [0.0, u, 0.0], [0.0, 0.0, u]]
+            });
+            for i in 0..3 {
+                for j in 0..3 {
+                    factors[i][j] += weight * (own[i][j] + d[i] * d[j]);
+                }
+            }
+        }
+        average.set_anisotropic_temperature_factors(factors);
+    }
+    average
 }
 
 impl fmt::Display for Residue {
@@ -514,13 +1025,14 @@ impl fmt::Display for Residue {
 
 impl PartialOrd for Residue {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.id().cmp(&other.id()))
+        Some(self.cmp(other))
     }
 }
 
+/// Residues are ordered by their serial number and insertion code, see [`Residue::sort_key`].
 impl Ord for Residue {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.id().cmp(&other.id())
+        self.sort_key().cmp(&other.sort_key())
     }
 }
 
@@ -531,6 +1043,80 @@ impl Extend<Conformer> for Residue {
     }
 }
 
+impl Extend<Atom> for Residue {
+    /// Extend the Atoms of the first Conformer of this Residue by the given iterator of Atoms.
+    /// If this Residue has no Conformers a Conformer named `UNK` is added to hold them.
+    fn extend<T: IntoIterator<Item = Atom>>(&mut self, iter: T) {
+        if self.conformers.is_empty() {
+            self.conformers
+                .push(Conformer::new("UNK", None, None).expect("UNK is a valid Conformer name"));
+        }
+        self.conformers[0].extend(iter);
+    }
+}
+
+impl FromIterator<Conformer> for Residue {
+    /// Create a Residue with serial number 0 and no insertion code from the given Conformers.
+    fn from_iter<T: IntoIterator<Item = Conformer>>(iter: T) -> Self {
+        let mut residue =
+            Self::new(0, None, None).expect("A Residue without insertion code is valid");
+        residue.extend(iter);
+        residue
+    }
+}
+
+impl FromIterator<Atom> for Residue {
+    /// Create a Residue with serial number 0 and no insertion code from the given Atoms, which
+    /// are placed in a single Conformer named `UNK`.
+    fn from_iter<T: IntoIterator<Item = Atom>>(iter: T) -> Self {
+        std::iter::once(iter.into_iter().collect::<Conformer>()).collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a Residue {
+    type Item = &'a Conformer;
+    type IntoIter = std::slice::Iter<'a, Conformer>;
+    /// Iterate over the Conformers of this Residue, see [`Residue::conformers`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.conformers.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Residue {
+    type Item = &'a mut Conformer;
+    type IntoIter = std::slice::IterMut<'a, Conformer>;
+    /// Iterate mutably over the Conformers of this Residue, see [`Residue::conformers_mut`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.conformers.iter_mut()
+    }
+}
+
+impl ops::Index<&str> for Residue {
+    type Output = Atom;
+    /// Get the first Atom with the given name (over all its Conformers), see [`Residue::atoms`] for the fallible
+    /// lookup.
+    ///
+    /// ## Panics
+    /// It panics if there is no Atom with the given name.
+    fn index(&self, name: &str) -> &Self::Output {
+        self.atoms()
+            .find(|a| a.name() == name)
+            .unwrap_or_else(|| panic!("there is no Atom with name `{name}` in this Residue"))
+    }
+}
+
+impl ops::IndexMut<&str> for Residue {
+    /// Get the first Atom with the given name (over all its Conformers) mutably.
+    ///
+    /// ## Panics
+    /// It panics if there is no Atom with the given name.
+    fn index_mut(&mut self, name: &str) -> &mut Self::Output {
+        self.atoms_mut()
+            .find(|a| a.name() == name)
+            .unwrap_or_else(|| panic!("there is no Atom with name `{name}` in this Residue"))
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -546,6 +1132,17 @@ mod tests {
         assert_eq!(a.insertion_code(), Some("CONFORMER"));
     }
 
+    #[test]
+    fn test_capacity() {
+        let mut a = Residue::with_capacity(1, Some("A"), 2).unwrap();
+        assert_eq!(Residue::with_capacity(1, Some("Rͦ"), 2), None);
+        assert!(a.conformers.capacity() >= 2);
+        assert_eq!(a.conformer_count(), 0);
+        a.reserve(10);
+        assert!(a.conformers.capacity() >= 10);
+        assert_eq!(a, Residue::new(1, Some("A"), None).unwrap());
+    }
+
     #[test]
     fn ordering_and_equality() {
         let a = Residue::new(1, None, None).unwrap();
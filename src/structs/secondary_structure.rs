@@ -0,0 +1,130 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A residue at the start or end of a helix or strand, as given in the HELIX and SHEET records
+pub struct BoundaryResidue {
+    /// The name of the residue
+    pub name: String,
+    /// The identifier of the Chain
+    pub chain: String,
+    /// The serial number of the residue
+    pub serial_number: isize,
+    /// The insertion code of the residue, if any
+    pub insertion_code: Option<String>,
+}
+
+impl BoundaryResidue {
+    /// The serial number and insertion code of the residue, as used in [`crate::Residue::id`].
+    pub fn id(&self) -> (isize, Option<&str>) {
+        (self.serial_number, self.insertion_code.as_deref())
+    }
+}
+
+/// Check if the given residue lies between the given start and end residues (inclusive), the
+/// start and end are assumed to be in the same chain.
+fn in_range(
+    start: &BoundaryResidue,
+    end: &BoundaryResidue,
+    chain: &str,
+    id: (isize, Option<&str>),
+) -> bool {
+    start.chain == chain && id >= start.id() && id <= end.id()
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A helix as given by a HELIX record
+pub struct Helix {
+    /// The serial number of the helix
+    pub serial_number: usize,
+    /// The identifier of the helix
+    pub id: String,
+    /// The class of the helix, eg 1 for a right-handed alpha helix and 5 for a right-handed 3-10
+    /// helix, see the PDB format documentation for all classes
+    pub class: usize,
+    /// The first residue of the helix
+    pub start: BoundaryResidue,
+    /// The last residue of the helix
+    pub end: BoundaryResidue,
+    /// A free text comment, if any
+    pub comment: Option<String>,
+    /// The number of residues in the helix
+    pub length: usize,
+}
+
+impl Helix {
+    /// Check if the given residue is part of this helix.
+    pub fn contains(&self, chain: &str, id: (isize, Option<&str>)) -> bool {
+        in_range(&self.start, &self.end, chain, id)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A strand of a beta sheet as given by a SHEET record
+pub struct Strand {
+    /// The number of the strand within its sheet, starting at 1
+    pub number: usize,
+    /// The first residue of the strand
+    pub start: BoundaryResidue,
+    /// The last residue of the strand
+    pub end: BoundaryResidue,
+    /// The sense of the strand with respect to the previous strand, 0 for the first strand, 1 for
+    /// parallel, and -1 for anti-parallel strands
+    pub sense: isize,
+}
+
+impl Strand {
+    /// Check if the given residue is part of this strand.
+    pub fn contains(&self, chain: &str, id: (isize, Option<&str>)) -> bool {
+        in_range(&self.start, &self.end, chain, id)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A beta sheet, consisting of strands, as given by the SHEET records
+pub struct Sheet {
+    /// The identifier of the sheet
+    pub id: String,
+    /// The strands making up the sheet, in order
+    pub strands: Vec<Strand>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The secondary structure annotation of a residue, see [`crate::PDB::secondary_structure`]
+pub enum SecondaryStructure<'a> {
+    /// The residue is part of this helix
+    Helix(&'a Helix),
+    /// The residue is part of this strand of this sheet
+    Strand(&'a Sheet, &'a Strand),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn residue(serial_number: isize, insertion_code: Option<&str>) -> BoundaryResidue {
+        BoundaryResidue {
+            name: "ALA".to_string(),
+            chain: "A".to_string(),
+            serial_number,
+            insertion_code: insertion_code.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let strand = Strand {
+            number: 1,
+            start: residue(10, None),
+            end: residue(12, Some("A")),
+            sense: 0,
+        };
+        assert!(strand.contains("A", (10, None)));
+        assert!(strand.contains("A", (12, None)));
+        assert!(strand.contains("A", (12, Some("A"))));
+        assert!(!strand.contains("A", (12, Some("B"))));
+        assert!(!strand.contains("A", (9, None)));
+        assert!(!strand.contains("B", (11, None)));
+    }
+}
@@ -0,0 +1,438 @@
+use std::ops;
+
+use crate::structs::PDB;
+
+/// The number of atoms stored in every word of a [`Selection`]
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A set of atoms of a PDB, stored as a bit set over the atom ids. The id of an atom is its
+/// index in the order of [`crate::PDB::atoms`] (over all Models), which stays the same as long
+/// as no atoms are added or removed. A selection is created with [`crate::PDB::select`] or
+/// [`crate::PDB::select_by`], and can be combined with other selections of the same PDB with
+/// the set operations, or the operators `|` (union), `&` (intersection), `-` (difference), `^`
+/// (symmetric difference), and `!` (inversion). The selected atoms can be retrieved with
+/// [`crate::PDB::selected`].
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let backbone = pdb.select(Search::Single(Term::Backbone));
+/// let glycines = pdb.select(Search::Single(Term::ConformerName("GLY".to_owned())));
+/// let selection = &backbone - &glycines;
+/// assert!(pdb.selected(&selection).all(|h| h.conformer().name() != "GLY" && h.atom().is_backbone()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Selection {
+    /// The number of atoms this selection is over
+    size: usize,
+    /// The bits of the selected atoms, any bits beyond the size are always unset
+    words: Vec<u64>,
+}
+
+impl Selection {
+    /// Create an empty selection over the given number of atoms.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            words: vec![0; (size + WORD_BITS - 1) / WORD_BITS],
+        }
+    }
+
+    /// Create a selection of all of the given number of atoms.
+    pub fn all(size: usize) -> Self {
+        let mut selection = Self::new(size);
+        selection.words.iter_mut().for_each(|w| *w = u64::MAX);
+        selection.clear_unused();
+        selection
+    }
+
+    /// Create a selection over the given number of atoms from the given atom ids, ids that are
+    /// out of bounds are ignored.
+    pub fn from_ids(size: usize, ids: impl IntoIterator<Item = usize>) -> Self {
+        let mut selection = Self::new(size);
+        for id in ids {
+            selection.insert(id);
+        }
+        selection
+    }
+
+    /// The number of atoms this selection is over, the number of atoms in the PDB it was
+    /// created from.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of selected atoms.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Whether no atoms are selected.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// Whether the atom with the given id is selected.
+    pub fn contains(&self, id: usize) -> bool {
+        id < self.size && self.words[id / WORD_BITS] & (1 << (id % WORD_BITS)) != 0
+    }
+
+    /// Select the atom with the given id. Returns `false` if the id is out of bounds.
+    pub fn insert(&mut self, id: usize) -> bool {
+        if id >= self.size {
+            return false;
+        }
+        self.words[id / WORD_BITS] |= 1 << (id % WORD_BITS);
+        true
+    }
+
+    /// Deselect the atom with the given id. Returns whether the atom was selected.
+    pub fn remove(&mut self, id: usize) -> bool {
+        let selected = self.contains(id);
+        if selected {
+            self.words[id / WORD_BITS] &= !(1 << (id % WORD_BITS));
+        }
+        selected
+    }
+
+    /// Get the ids of the selected atoms in increasing order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, word)| {
+            (0..WORD_BITS)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| index * WORD_BITS + bit)
+        })
+    }
+
+    /// The atoms selected in this or the other selection. The result is over the largest of
+    /// both sizes.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// The atoms selected in both this and the other selection.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// The atoms selected in this but not in the other selection.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// The atoms selected in exactly one of this and the other selection.
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    /// The atoms not selected in this selection.
+    #[must_use]
+    pub fn invert(&self) -> Self {
+        let mut selection = Self {
+            size: self.size,
+            words: self.words.iter().map(|w| !w).collect(),
+        };
+        selection.clear_unused();
+        selection
+    }
+
+    /// Generate a PyMOL selection string for the selected atoms of the given PDB, using chains,
+    /// residue ranges, and atom names, for example `chain B or (chain A and resi 1-10+15)`. Only
+    /// the first Model is described, as the other Models are states in PyMOL.
+    /// ```rust
+    /// use pdbtbx::*;
+    /// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+    /// let selection = pdb.select(Search::Single(Term::ResidueSerialNumberRange(1, 10)));
+    /// assert_eq!(selection.to_pymol(&pdb), "(chain A and resi 1-10)");
+    /// ```
+    pub fn to_pymol(&self, pdb: &PDB) -> String {
+        self.to_viewer(pdb, Viewer::PyMol)
+    }
+
+    /// Generate a VMD selection string for the selected atoms of the given PDB, using chains,
+    /// residue ranges, and atom names, for example `chain B or (chain A and resid 1 to 10 15)`.
+    /// Only the first Model is described, as the other Models are frames in VMD.
+    pub fn to_vmd(&self, pdb: &PDB) -> String {
+        self.to_viewer(pdb, Viewer::Vmd)
+    }
+
+    /// Describe the selected atoms of the first Model of the given PDB in the syntax of the
+    /// given viewer
+    fn to_viewer(&self, pdb: &PDB, viewer: Viewer) -> String {
+        let model = match pdb.models().next() {
+            Some(model) => model,
+            None => return "none".to_string(),
+        };
+        let mut id = 0;
+        let mut clauses = Vec::new();
+        let mut complete = true;
+        for chain in model.chains() {
+            let chain_id = format!("chain {}", viewer.quote(chain.id()));
+            // The ranges of completely selected residues, as the first and last residue
+            let mut ranges: Vec<(String, String)> = Vec::new();
+            let mut partial = Vec::new();
+            // The serial number of the previous residue, if it was added to the last range
+            let mut previous: Option<isize> = None;
+            let mut whole_chain = true;
+            for residue in chain.residues() {
+                let mut names = Vec::new();
+                let mut selected = 0;
+                let total = residue.atoms().count();
+                for atom in residue.atoms() {
+                    if self.contains(id) {
+                        selected += 1;
+                        if !names.contains(&atom.name()) {
+                            names.push(atom.name());
+                        }
+                    }
+                    id += 1;
+                }
+                whole_chain &= selected == total;
+                let serial = residue.serial_number();
+                if selected == 0 {
+                    previous = None;
+                } else if selected < total {
+                    previous = None;
+                    partial.push(format!(
+                        "({chain_id} and {} and {})",
+                        viewer.single_residue(residue),
+                        viewer.names(&names)
+                    ));
+                } else if viewer == Viewer::Vmd && residue.insertion_code().is_some() {
+                    previous = None;
+                    partial.push(format!(
+                        "({chain_id} and {})",
+                        viewer.single_residue(residue)
+                    ));
+                } else {
+                    match (previous, ranges.last_mut()) {
+                        (Some(p), Some(range))
+                            if p + 1 == serial && residue.insertion_code().is_none() =>
+                        {
+                            range.1 = viewer.residue(residue);
+                        }
+                        _ => ranges.push((viewer.residue(residue), String::new())),
+                    }
+                    previous = residue.insertion_code().is_none().then_some(serial);
+                }
+            }
+            complete &= whole_chain;
+            if whole_chain {
+                if chain.atom_count() > 0 {
+                    clauses.push(chain_id);
+                }
+                continue;
+            }
+            if !ranges.is_empty() {
+                clauses.push(format!("({chain_id} and {})", viewer.residues(&ranges)));
+            }
+            clauses.extend(partial);
+        }
+        if clauses.is_empty() {
+            "none".to_string()
+        } else if complete {
+            "all".to_string()
+        } else {
+            clauses.join(" or ")
+        }
+    }
+
+    /// Combine the words of both selections with the given operation
+    fn combine(&self, other: &Self, operation: impl Fn(u64, u64) -> u64) -> Self {
+        let mut selection = Self::new(self.size.max(other.size));
+        for (index, word) in selection.words.iter_mut().enumerate() {
+            let a = self.words.get(index).copied().unwrap_or(0);
+            let b = other.words.get(index).copied().unwrap_or(0);
+            *word = operation(a, b);
+        }
+        selection.clear_unused();
+        selection
+    }
+
+    /// Unset the bits beyond the size in the last word
+    fn clear_unused(&mut self) {
+        let used = self.size % WORD_BITS;
+        if used != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+}
+
+/// The molecular viewers selection strings can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Viewer {
+    PyMol,
+    Vmd,
+}
+
+impl Viewer {
+    /// Quote a chain id or atom name if it contains characters with a special meaning
+    fn quote(self, text: &str) -> String {
+        if text.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return text.to_string();
+        }
+        match self {
+            Self::PyMol => text
+                .chars()
+                .flat_map(|c| {
+                    let escape = !c.is_ascii_alphanumeric();
+                    escape.then_some('\\').into_iter().chain(std::iter::once(c))
+                })
+                .collect(),
+            Self::Vmd => format!("\"{text}\""),
+        }
+    }
+
+    /// The identifier of a residue in a range, its serial number with its insertion code
+    fn residue(self, residue: &crate::Residue) -> String {
+        let serial = residue.serial_number();
+        match self {
+            Self::PyMol if serial < 0 => {
+                format!("\\{serial}{}", residue.insertion_code().unwrap_or(""))
+            }
+            Self::PyMol => format!("{serial}{}", residue.insertion_code().unwrap_or("")),
+            Self::Vmd => serial.to_string(),
+        }
+    }
+
+    /// Select a single residue, VMD cannot give insertion codes in a range
+    fn single_residue(self, residue: &crate::Residue) -> String {
+        match (self, residue.insertion_code()) {
+            (Self::Vmd, Some(code)) => format!(
+                "{} and insertion {}",
+                self.residues(&[(self.residue(residue), String::new())]),
+                self.quote(code)
+            ),
+            _ => self.residues(&[(self.residue(residue), String::new())]),
+        }
+    }
+
+    /// Select the given residue ranges, as the first and last residue (empty for a single residue)
+    fn residues(self, ranges: &[(String, String)]) -> String {
+        let (keyword, to, separator) = match self {
+            Self::PyMol => ("resi", "-", "+"),
+            Self::Vmd => ("resid", " to ", " "),
+        };
+        let ranges: Vec<String> = ranges
+            .iter()
+            .map(|(first, last)| {
+                if last.is_empty() {
+                    first.clone()
+                } else {
+                    format!("{first}{to}{last}")
+                }
+            })
+            .collect();
+        format!("{keyword} {}", ranges.join(separator))
+    }
+
+    /// Select the given atom names
+    fn names(self, names: &[&str]) -> String {
+        let separator = match self {
+            Self::PyMol => "+",
+            Self::Vmd => " ",
+        };
+        let names: Vec<String> = names.iter().map(|n| self.quote(n)).collect();
+        format!("name {}", names.join(separator))
+    }
+}
+
+impl<'a> IntoIterator for &'a Selection {
+    type Item = usize;
+    type IntoIter = Box<dyn DoubleEndedIterator<Item = usize> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+impl ops::BitOr for &Selection {
+    type Output = Selection;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl ops::BitAnd for &Selection {
+    type Output = Selection;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl ops::Sub for &Selection {
+    type Output = Selection;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl ops::BitXor for &Selection {
+    type Output = Selection;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl ops::Not for &Selection {
+    type Output = Selection;
+    fn not(self) -> Self::Output {
+        self.invert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_operations() {
+        let a = Selection::from_ids(130, [0, 1, 64, 129, 200]);
+        let b = Selection::from_ids(130, [1, 2, 129]);
+        assert_eq!(a.len(), 4);
+        assert!(!a.contains(200));
+        assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![0, 1, 2, 64, 129]);
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![1, 129]);
+        assert_eq!((&a - &b).iter().collect::<Vec<_>>(), vec![0, 64]);
+        assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), vec![0, 2, 64]);
+        assert_eq!((!&a).len(), 126);
+        assert_eq!(!&!&a, a);
+        assert_eq!(Selection::all(130), &a | &!&a);
+        assert!((&a & &!&a).is_empty());
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut selection = Selection::new(10);
+        assert!(selection.is_empty());
+        assert!(selection.insert(3));
+        assert!(!selection.insert(10));
+        assert!(selection.remove(3));
+        assert!(!selection.remove(3));
+        assert_eq!(Selection::all(0).len(), 0);
+        assert_eq!(Selection::all(64).len(), 64);
+        assert_eq!(
+            (&Selection::all(3)).into_iter().rev().collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn quoting() {
+        assert_eq!(Viewer::PyMol.quote("O5'"), "O5\\'");
+        assert_eq!(Viewer::Vmd.quote("O5'"), "\"O5'\"");
+        assert_eq!(Viewer::Vmd.quote("CA"), "CA");
+        assert_eq!(
+            Viewer::PyMol.residues(&[
+                ("1".to_string(), "4".to_string()),
+                ("6".to_string(), String::new())
+            ]),
+            "resi 1-4+6"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+/// The REMARK number of the site descriptions
+const SITE_REMARK: usize = 800;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A site of interest in the structure, like a binding site, as given by the SITE records and
+/// described in REMARK 800.
+pub struct Site {
+    /// The identifier of the site, eg `AC1`
+    pub id: String,
+    /// The residues making up the site
+    pub residues: Vec<SiteResidue>,
+    /// The evidence code from REMARK 800, eg `SOFTWARE` or `AUTHOR`
+    pub evidence_code: Option<String>,
+    /// The free text description from REMARK 800, eg `BINDING SITE FOR RESIDUE SEC A 502`
+    pub description: Option<String>,
+}
+
+impl Site {
+    /// Create a new Site with the given identifier and residues, without a description.
+    #[must_use]
+    pub fn new(id: impl Into<String>, residues: Vec<SiteResidue>) -> Self {
+        Site {
+            id: id.into(),
+            residues,
+            evidence_code: None,
+            description: None,
+        }
+    }
+
+    /// Check if the given residue is part of this site.
+    pub fn contains(&self, chain: &str, id: (isize, Option<&str>)) -> bool {
+        self.residues
+            .iter()
+            .any(|residue| residue.chain == chain && residue.id() == id)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A residue listed in a SITE record
+pub struct SiteResidue {
+    /// The name of the residue
+    pub name: String,
+    /// The identifier of the Chain
+    pub chain: String,
+    /// The serial number of the residue
+    pub serial_number: isize,
+    /// The insertion code of the residue, if any
+    pub insertion_code: Option<String>,
+}
+
+impl SiteResidue {
+    /// The serial number and insertion code of the residue, as used in [`crate::Residue::id`].
+    pub fn id(&self) -> (isize, Option<&str>) {
+        (self.serial_number, self.insertion_code.as_deref())
+    }
+}
+
+/// Attach the identifiers and descriptions given in the REMARK 800 records to the sites with
+/// the same identifier. Descriptions continued over multiple lines are joined with spaces.
+pub(crate) fn describe_sites<'a>(
+    sites: &mut [Site],
+    remarks: impl Iterator<Item = &'a (usize, String)>,
+) {
+    let mut current: Option<&mut Site> = None;
+    let mut in_description = false;
+    for (_, text) in remarks.filter(|(number, _)| *number == SITE_REMARK) {
+        let text = text.trim();
+        if let Some(id) = text.strip_prefix("SITE_IDENTIFIER:") {
+            current = sites.iter_mut().find(|site| site.id == id.trim());
+            in_description = false;
+        } else if let Some(code) = text.strip_prefix("EVIDENCE_CODE:") {
+            if let Some(site) = current.as_deref_mut() {
+                site.evidence_code = Some(code.trim().to_string());
+            }
+            in_description = false;
+        } else if let Some(description) = text.strip_prefix("SITE_DESCRIPTION:") {
+            if let Some(site) = current.as_deref_mut() {
+                site.description = Some(description.trim().to_string());
+            }
+            in_description = true;
+        } else if text.is_empty() {
+            in_description = false;
+        } else if in_description {
+            if let Some(description) = current.as_deref_mut().and_then(|s| s.description.as_mut()) {
+                description.push(' ');
+                description.push_str(text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptions() {
+        let mut sites = vec![Site::new("AC1", Vec::new()), Site::new("AC2", Vec::new())];
+        let remarks: Vec<(usize, String)> = [
+            "",
+            "SITE",
+            "SITE_IDENTIFIER: AC2",
+            "EVIDENCE_CODE: SOFTWARE",
+            "SITE_DESCRIPTION: BINDING SITE FOR RESIDUE PLP A",
+            "500",
+            "",
+            "SITE_IDENTIFIER: AC3",
+            "SITE_DESCRIPTION: NOT IN THE SITE RECORDS",
+        ]
+        .iter()
+        .map(|text| (800, (*text).to_string()))
+        .collect();
+        describe_sites(&mut sites, remarks.iter());
+        assert_eq!(sites[0].description, None);
+        assert_eq!(sites[1].evidence_code.as_deref(), Some("SOFTWARE"));
+        assert_eq!(
+            sites[1].description.as_deref(),
+            Some("BINDING SITE FOR RESIDUE PLP A 500")
+        );
+    }
+}
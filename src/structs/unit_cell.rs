@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use crate::TransformationMatrix;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -148,6 +149,45 @@ impl UnitCell {
     pub const fn size(&self) -> (f64, f64, f64) {
         (self.a, self.b, self.c)
     }
+
+    /// Get the transformation from fractional to orthogonal coordinates (in Å), in the standard
+    /// orientation with `a` along the x axis and `b` in the xy plane.
+    #[must_use]
+    pub fn orthogonalization_matrix(&self) -> TransformationMatrix {
+        let (cos_alpha, cos_beta) = (self.alpha.to_radians().cos(), self.beta.to_radians().cos());
+        let (sin_gamma, cos_gamma) = self.gamma.to_radians().sin_cos();
+        let volume = (1.0 - cos_alpha * cos_alpha - cos_beta * cos_beta - cos_gamma * cos_gamma
+            + 2.0 * cos_alpha * cos_beta * cos_gamma)
+            .sqrt();
+        TransformationMatrix::from_matrix([
+            [self.a, self.b * cos_gamma, self.c * cos_beta, 0.0],
+            [
+                0.0,
+                self.b * sin_gamma,
+                self.c * (cos_alpha - cos_beta * cos_gamma) / sin_gamma,
+                0.0,
+            ],
+            [0.0, 0.0, self.c * volume / sin_gamma, 0.0],
+        ])
+    }
+
+    /// Get the transformation from orthogonal (in Å) to fractional coordinates, the inverse of
+    /// [`UnitCell::orthogonalization_matrix`].
+    #[must_use]
+    pub fn fractionalization_matrix(&self) -> TransformationMatrix {
+        let m = self.orthogonalization_matrix().matrix();
+        let (m00, m01, m02, m11, m12, m22) = (m[0][0], m[0][1], m[0][2], m[1][1], m[1][2], m[2][2]);
+        TransformationMatrix::from_matrix([
+            [
+                1.0 / m00,
+                -m01 / (m00 * m11),
+                (m01 * m12 - m02 * m11) / (m00 * m11 * m22),
+                0.0,
+            ],
+            [0.0, 1.0 / m11, -m12 / (m11 * m22), 0.0],
+            [0.0, 0.0, 1.0 / m22, 0.0],
+        ])
+    }
 }
 
 impl Default for UnitCell {
@@ -169,4 +209,22 @@ mod tests {
         assert_eq!(a, b);
         assert_ne!(a, c);
     }
+
+    #[test]
+    fn fractional() {
+        let cell = UnitCell::new(10.0, 12.0, 15.0, 80.0, 95.0, 110.0);
+        let (fractionalization, orthogonalization) = (
+            cell.fractionalization_matrix(),
+            cell.orthogonalization_matrix(),
+        );
+        let a = orthogonalization.apply((1.0, 0.0, 0.0));
+        assert!((a.0 - 10.0).abs() < 1e-9 && a.1.abs() < 1e-9 && a.2.abs() < 1e-9);
+        let c = orthogonalization.apply((0.0, 0.0, 1.0));
+        assert!(((c.0 * c.0 + c.1 * c.1 + c.2 * c.2).sqrt() - 15.0).abs() < 1e-9);
+        let position = (3.0, -4.0, 7.5);
+        let back = orthogonalization.apply(fractionalization.apply(position));
+        assert!((back.0 - position.0).abs() < 1e-9);
+        assert!((back.1 - position.1).abs() < 1e-9);
+        assert!((back.2 - position.2).abs() < 1e-9);
+    }
 }
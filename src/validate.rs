@@ -1,5 +1,11 @@
+use doc_cfg::doc_cfg;
+
 use crate::error::*;
+use crate::geometry::{self, Clash};
+use crate::reference_tables;
 use crate::structs::*;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// Validate a given PDB file in terms of invariants that should be held up.
 /// It returns `PDBError`s with the warning messages.
@@ -39,194 +45,204 @@ pub fn validate(pdb: &PDB) -> Vec<PDBError> {
 #[must_use]
 pub fn validate_pdb(pdb: &PDB) -> Vec<PDBError> {
     let mut errors = validate(pdb);
-    for model in pdb.models() {
-        if model.serial_number() > 9999 {
+    errors.extend(validate_each(pdb.models().collect(), validate_pdb_model));
+    errors
+}
+
+/// Validate the values of a single Model for the PDB format, see [`validate_pdb`].
+fn validate_pdb_model(model: &Model) -> Vec<PDBError> {
+    let mut errors = Vec::new();
+    if model.serial_number() > 9999 {
+        errors.push(PDBError::new(
+            ErrorLevel::LooseWarning,
+            "Model serial number too high",
+            format!(
+                "Model {} has a serial number which is too high, max 9999.",
+                model.serial_number()
+            ),
+            Context::None,
+        ));
+    }
+    errors.extend(validate_each(model.chains().collect(), validate_pdb_chain));
+    errors
+}
+
+/// Validate the values of a single Chain for the PDB format, see [`validate_pdb`].
+fn validate_pdb_chain(chain: &Chain) -> Vec<PDBError> {
+    let mut errors = Vec::new();
+    if chain.id().len() > 1 {
+        errors.push(PDBError::new(
+            ErrorLevel::LooseWarning,
+            "Chain id too long",
+            format!(
+                "Chain {} has a name which is too long, max 1 character.",
+                chain.id()
+            ),
+            Context::None,
+        ));
+    }
+    for residue in chain.residues() {
+        if residue.serial_number() > 9999 {
             errors.push(PDBError::new(
                 ErrorLevel::LooseWarning,
-                "Model serial number too high",
+                "Residue serial number too high",
                 format!(
-                    "Model {} has a serial number which is too high, max 9999.",
-                    model.serial_number()
+                    "Residue {:?} has a serial number which is too high, max is 9999.",
+                    residue.id()
                 ),
                 Context::None,
             ));
         }
-        for chain in model.chains() {
-            if chain.id().len() > 1 {
+        if let Some(ic) = residue.insertion_code() {
+            if ic.len() > 1 {
                 errors.push(PDBError::new(
                     ErrorLevel::LooseWarning,
-                    "Chain id too long",
+                    "Residue insertion code too long",
                     format!(
-                        "Chain {} has a name which is too long, max 1 character.",
-                        chain.id()
+                        "Residue {:?} has an insertion code which is too long, max 1 char.",
+                        residue.id()
                     ),
                     Context::None,
                 ));
             }
-            for residue in chain.residues() {
-                if residue.serial_number() > 9999 {
+        }
+        for conformer in residue.conformers() {
+            if conformer.name().len() > 3 {
+                errors.push(PDBError::new(
+                    ErrorLevel::LooseWarning,
+                    "Conformer name too long",
+                    format!(
+                        "Conformer {:?} has a name which is too long, max 3 chars.",
+                        conformer.id()
+                    ),
+                    Context::None,
+                ));
+            }
+            if let Some(alt_loc) = conformer.alternative_location() {
+                if alt_loc.len() > 1 {
                     errors.push(PDBError::new(
                         ErrorLevel::LooseWarning,
-                        "Residue serial number too high",
+                        "Conformer alternative location too long",
                         format!(
-                            "Residue {:?} has a serial number which is too high, max is 9999.",
-                            residue.id()
+                            "Conformer {:?} has an alternative location which is too long, max 1 char.",
+                            conformer.id()
                         ),
                         Context::None,
                     ));
                 }
-                if let Some(ic) = residue.insertion_code() {
-                    if ic.len() > 1 {
-                        errors.push(PDBError::new(
-                            ErrorLevel::LooseWarning,
-                            "Residue insertion code too long",
-                            format!(
-                                "Residue {:?} has an insertion code which is too long, max 1 char.",
-                                residue.id()
-                            ),
-                            Context::None,
-                        ));
-                    }
+            }
+            if let Some((n, comment)) = conformer.modification() {
+                if n.len() > 3 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Residue modification name too long",
+                        format!(
+                            "Residue {} has a modification name which is too long, max 3 chars.",
+                            residue.serial_number()
+                        ),
+                        Context::None,
+                    ));
                 }
-                for conformer in residue.conformers() {
-                    if conformer.name().len() > 3 {
-                        errors.push(PDBError::new(
-                            ErrorLevel::LooseWarning,
-                            "Conformer name too long",
-                            format!(
-                                "Conformer {:?} has a name which is too long, max 3 chars.",
-                                conformer.id()
-                            ),
-                            Context::None,
-                        ));
-                    }
-                    if let Some(alt_loc) = conformer.alternative_location() {
-                        if alt_loc.len() > 1 {
-                            errors.push(PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "Conformer alternative location too long",
-                                format!(
-                                    "Conformer {:?} has an alternative location which is too long, max 1 char.",
-                                    conformer.id()
-                                ),
-                                Context::None,
-                            ));
-                        }
-                    }
-                    if let Some((n, comment)) = conformer.modification() {
-                        if n.len() > 3 {
-                            errors.push(PDBError::new(
-                            ErrorLevel::LooseWarning,
-                            "Residue modification name too long",
-                            format!(
-                                "Residue {} has a modification name which is too long, max 3 chars.",
-                                residue.serial_number()
-                            ),
-                            Context::None,
-                        ));
-                        }
-                        if comment.len() > 41 {
-                            errors.push(PDBError::new(
-                            ErrorLevel::LooseWarning,
-                            "Residue modification comment too long",
-                            format!(
-                                "Residue {} has a modification comment which is too long, max 41 chars.",
-                                residue.serial_number()
-                            ),
-                            Context::None,
-                        ));
-                        }
-                    }
-                    for atom in conformer.atoms() {
-                        if atom.name().len() > 4 {
-                            errors.push(PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "Atom name too long",
-                                format!(
-                                    "Atom {} has a name which is too long, max 4 chars.",
-                                    atom.serial_number()
-                                ),
-                                Context::None,
-                            ));
-                        }
-                        if atom.serial_number() > 99999 {
-                            errors.push(PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "Atom serial number too high",
-                                format!(
-                                    "Atom {} has a serial number which is too high, max is 99999.",
-                                    atom.serial_number()
-                                ),
-                                Context::None,
-                            ));
-                        }
-                        if atom.charge() > 9 || atom.charge() < -9 {
-                            errors.push(PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "Atom charge out of bounds",
-                                format!(
-                                "Atom {} has a charge which is out of bounds, max is 9 min is -9.",
-                                atom.serial_number()
-                            ),
-                                Context::None,
-                            ));
-                        }
-                        if atom.occupancy() > 999.99 {
-                            errors.push(PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "Atom occupancy out of bounds",
-                                format!(
-                                "Atom {} has a occupancy which is out of bounds, max is 999.99.",
-                                atom.serial_number()
-                            ),
-                                Context::None,
-                            ));
-                        }
-                        if atom.b_factor() > 999.99 {
-                            errors.push(PDBError::new(
-                                ErrorLevel::LooseWarning,
-                                "Atom b factor out of bounds",
-                                format!(
-                                    "Atom {} has a b factor which is out of bounds, max is 999.99.",
-                                    atom.serial_number()
-                                ),
-                                Context::None,
-                            ));
-                        }
-                        if atom.x() > 9999.999 || atom.x() < -999.999 {
-                            errors.push(PDBError::new(
-                            ErrorLevel::LooseWarning,
-                            "Atom x position out of bounds",
-                            format!(
-                                "Atom {} has an x which is out of bounds, max is 9999.999 min is -999.999.",
-                                atom.serial_number()
-                            ),
-                            Context::None,
-                        ));
-                        }
-                        if atom.y() > 9999.999 || atom.y() < -999.999 {
-                            errors.push(PDBError::new(
-                            ErrorLevel::LooseWarning,
-                            "Atom y position out of bounds",
-                            format!(
-                                "Atom {} has a y which is out of bounds, max is 9999.999 min is -999.999.",
-                                atom.serial_number()
-                            ),
-                            Context::None,
-                        ));
-                        }
-                        if atom.z() > 9999.999 || atom.z() < -999.999 {
-                            errors.push(PDBError::new(
-                            ErrorLevel::LooseWarning,
-                            "Atom z position out of bounds",
-                            format!(
-                                "Atom {} has a z which is out of bounds, max is 9999.999 min is -999.999.",
-                                atom.serial_number()
-                            ),
-                            Context::None,
-                        ));
-                        }
-                    }
+                if comment.len() > 41 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Residue modification comment too long",
+                        format!(
+                        "Residue {} has a modification comment which is too long, max 41 chars.",
+                        residue.serial_number()
+                    ),
+                        Context::None,
+                    ));
+                }
+            }
+            for atom in conformer.atoms() {
+                if atom.name().len() > 4 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom name too long",
+                        format!(
+                            "Atom {} has a name which is too long, max 4 chars.",
+                            atom.serial_number()
+                        ),
+                        Context::None,
+                    ));
+                }
+                if atom.serial_number() > 99999 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom serial number too high",
+                        format!(
+                            "Atom {} has a serial number which is too high, max is 99999.",
+                            atom.serial_number()
+                        ),
+                        Context::None,
+                    ));
+                }
+                if atom.charge() > 9 || atom.charge() < -9 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom charge out of bounds",
+                        format!(
+                            "Atom {} has a charge which is out of bounds, max is 9 min is -9.",
+                            atom.serial_number()
+                        ),
+                        Context::None,
+                    ));
+                }
+                if atom.occupancy() > 999.99 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom occupancy out of bounds",
+                        format!(
+                            "Atom {} has a occupancy which is out of bounds, max is 999.99.",
+                            atom.serial_number()
+                        ),
+                        Context::None,
+                    ));
+                }
+                if atom.b_factor() > 999.99 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom b factor out of bounds",
+                        format!(
+                            "Atom {} has a b factor which is out of bounds, max is 999.99.",
+                            atom.serial_number()
+                        ),
+                        Context::None,
+                    ));
+                }
+                if atom.x() > 9999.999 || atom.x() < -999.999 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom x position out of bounds",
+                        format!(
+                        "Atom {} has an x which is out of bounds, max is 9999.999 min is -999.999.",
+                        atom.serial_number()
+                    ),
+                        Context::None,
+                    ));
+                }
+                if atom.y() > 9999.999 || atom.y() < -999.999 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom y position out of bounds",
+                        format!(
+                        "Atom {} has a y which is out of bounds, max is 9999.999 min is -999.999.",
+                        atom.serial_number()
+                    ),
+                        Context::None,
+                    ));
+                }
+                if atom.z() > 9999.999 || atom.z() < -999.999 {
+                    errors.push(PDBError::new(
+                        ErrorLevel::LooseWarning,
+                        "Atom z position out of bounds",
+                        format!(
+                        "Atom {} has a z which is out of bounds, max is 9999.999 min is -999.999.",
+                        atom.serial_number()
+                    ),
+                        Context::None,
+                    ));
                 }
             }
         }
@@ -234,21 +250,567 @@ pub fn validate_pdb(pdb: &PDB) -> Vec<PDBError> {
     errors
 }
 
+/// The classification of the backbone conformation of an amino acid on the Ramachandran plot,
+/// see [`ramachandran`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RamachandranClass {
+    /// In the regions commonly seen in high quality structures
+    Favored,
+    /// In the regions which are sterically possible but uncommon
+    Allowed,
+    /// Outside of the allowed regions, likely an error in the model
+    Outlier,
+}
+
+impl RamachandranClass {
+    /// Classify the given backbone torsion angles (in degrees) for the amino acid with the given
+    /// name, using the regions for glycine, proline, or the general regions for the other amino
+    /// acids.
+    pub fn classify(name: &str, phi: f64, psi: f64) -> Self {
+        let (favored, allowed) = reference_tables::ramachandran_regions(name);
+        let inside = |regions: &[[f64; 4]]| {
+            regions.iter().any(|[phi_min, phi_max, psi_min, psi_max]| {
+                (*phi_min..=*phi_max).contains(&phi) && (*psi_min..=*psi_max).contains(&psi)
+            })
+        };
+        if inside(favored) {
+            RamachandranClass::Favored
+        } else if inside(allowed) {
+            RamachandranClass::Allowed
+        } else {
+            RamachandranClass::Outlier
+        }
+    }
+}
+
+/// The Ramachandran validation of a structure, see [`ramachandran`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RamachandranReport<'a> {
+    /// All amino acids with both phi and psi defined, with their phi and psi angles in degrees
+    /// and their classification, in the order of the structure
+    pub residues: Vec<(&'a Residue, f64, f64, RamachandranClass)>,
+}
+
+impl<'a> RamachandranReport<'a> {
+    /// Get the number of residues with the given classification
+    fn count(&self, class: RamachandranClass) -> usize {
+        self.residues.iter().filter(|r| r.3 == class).count()
+    }
+
+    /// Get the number of residues in the favored regions
+    pub fn favored_count(&self) -> usize {
+        self.count(RamachandranClass::Favored)
+    }
+
+    /// Get the number of residues in the allowed (but not favored) regions
+    pub fn allowed_count(&self) -> usize {
+        self.count(RamachandranClass::Allowed)
+    }
+
+    /// Get the number of outliers
+    pub fn outlier_count(&self) -> usize {
+        self.count(RamachandranClass::Outlier)
+    }
+
+    /// Get the fraction of the residues in the favored regions, between 0.0 and 1.0. This is 1.0
+    /// if there are no residues.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn favored_fraction(&self) -> f64 {
+        if self.residues.is_empty() {
+            1.0
+        } else {
+            self.favored_count() as f64 / self.residues.len() as f64
+        }
+    }
+
+    /// Get the outliers with their phi and psi angles in degrees
+    pub fn outliers(&self) -> impl Iterator<Item = (&'a Residue, f64, f64)> + '_ {
+        self.residues
+            .iter()
+            .filter(|r| r.3 == RamachandranClass::Outlier)
+            .map(|r| (r.0, r.1, r.2))
+    }
+
+    /// Get a `GeneralWarning` for every outlier.
+    pub fn warnings(&self) -> Vec<PDBError> {
+        self.outliers()
+            .map(|(residue, phi, psi)| {
+                let name = residue.conformer(0).map_or("?", Conformer::name);
+                PDBError::new(
+                    ErrorLevel::GeneralWarning,
+                    "Ramachandran outlier",
+                    format!(
+                        "Residue {name} {:?} has backbone torsion angles (phi {phi:.1}, psi {psi:.1}) outside of the allowed regions of the Ramachandran plot.",
+                        residue.id()
+                    ),
+                    Context::None,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Validate the backbone conformations of all amino acids in the given PDB against the
+/// Ramachandran plot. The phi and psi angles of every amino acid are calculated (see
+/// [`Chain::backbone_torsions`]) and classified as favored, allowed, or outlier, with
+/// separate regions for glycine and proline. Residues at the termini or next to chain breaks,
+/// which miss one of the angles, are not included. The bundled regions are rectangular
+/// approximations of the contours of Lovell et al. (2003), so the classification of residues
+/// at the borders of the regions can differ from MolProbity.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let report = ramachandran(&pdb);
+/// assert!(report.favored_fraction() > 0.9);
+/// for warning in report.warnings() {
+///     println!("{warning}");
+/// }
+/// ```
+pub fn ramachandran(pdb: &PDB) -> RamachandranReport<'_> {
+    let residues = pdb
+        .chains()
+        .flat_map(Chain::backbone_torsions)
+        .filter_map(|(residue, torsions)| {
+            let (phi, psi) = (torsions.phi?, torsions.psi?);
+            let class = RamachandranClass::classify(residue.conformer(0)?.name(), phi, psi);
+            Some((residue, phi, psi, class))
+        })
+        .collect();
+    RamachandranReport { residues }
+}
+
+/// The maximal deviation in degrees of a chi angle from the modal angle of a rotamer to still
+/// be classified as that rotamer
+const ROTAMER_MAX_DEVIATION: f64 = 40.0;
+
+/// The side chain validation of a structure, see [`rotamers`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotamerReport<'a> {
+    /// All amino acids with rotamers, with their side chain torsion angles in degrees and the
+    /// name of the matching rotamer, `None` for outliers, in the order of the structure
+    pub residues: Vec<(&'a Residue, Vec<f64>, Option<&'static str>)>,
+}
+
+impl<'a> RotamerReport<'a> {
+    /// Get the number of outliers
+    pub fn outlier_count(&self) -> usize {
+        self.residues.iter().filter(|r| r.2.is_none()).count()
+    }
+
+    /// Get the fraction of the residues which are outliers, between 0.0 and 1.0. This is 0.0 if
+    /// there are no residues.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn outlier_fraction(&self) -> f64 {
+        if self.residues.is_empty() {
+            0.0
+        } else {
+            self.outlier_count() as f64 / self.residues.len() as f64
+        }
+    }
+
+    /// Get the outliers with their side chain torsion angles in degrees
+    pub fn outliers(&self) -> impl Iterator<Item = (&'a Residue, &[f64])> + '_ {
+        self.residues
+            .iter()
+            .filter(|r| r.2.is_none())
+            .map(|r| (r.0, r.1.as_slice()))
+    }
+
+    /// Get a `GeneralWarning` for every outlier.
+    pub fn warnings(&self) -> Vec<PDBError> {
+        self.outliers()
+            .map(|(residue, chis)| {
+                let name = residue.conformer(0).map_or("?", Conformer::name);
+                let chis = chis
+                    .iter()
+                    .enumerate()
+                    .map(|(index, chi)| format!("chi{} {chi:.1}", index + 1))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                PDBError::new(
+                    ErrorLevel::GeneralWarning,
+                    "Rotamer outlier",
+                    format!(
+                        "Residue {name} {:?} has side chain torsion angles ({chis}) which do not match any common rotamer.",
+                        residue.id()
+                    ),
+                    Context::None,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Find the rotamer of the amino acid with the given name matching the given chi angles (in
+/// degrees), if any. Only the angles defined in the rotamer library are compared.
+fn matching_rotamer(name: &str, chis: &[f64]) -> Option<&'static str> {
+    let symmetric = reference_tables::has_symmetric_side_chain(name);
+    let difference = |a: f64, b: f64, period: f64| {
+        let d = (a - b).rem_euclid(period);
+        d.min(period - d)
+    };
+    reference_tables::rotamers(name)
+        .iter()
+        .find(|(_, modal)| {
+            modal
+                .iter()
+                .zip(chis)
+                .enumerate()
+                .all(|(index, (modal, chi))| {
+                    let period = if symmetric && index == 1 {
+                        180.0
+                    } else {
+                        360.0
+                    };
+                    difference(*modal, *chi, period) <= ROTAMER_MAX_DEVIATION
+                })
+        })
+        .map(|(name, _)| *name)
+}
+
+/// Validate the side chain conformations of all amino acids in the given PDB against a compact
+/// rotamer library. The chi angles of every amino acid are calculated (see
+/// [`geometry::side_chain_torsions`]) and compared to the modal chi1 and chi2 angles of
+/// the common rotamers of the amino acid. Side chains with no angle within 40 degrees of a
+/// rotamer are outliers. Residues without rotamers (like glycine and alanine) and residues with
+/// truncated side chains, which miss the angles of the library, are not included.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let report = rotamers(&pdb);
+/// assert!(report.outlier_fraction() < 0.1);
+/// for warning in report.warnings() {
+///     println!("{warning}");
+/// }
+/// ```
+pub fn rotamers(pdb: &PDB) -> RotamerReport<'_> {
+    let residues = pdb
+        .residues()
+        .filter_map(|residue| {
+            let name = residue.conformer(0)?.name();
+            let library = reference_tables::rotamers(name);
+            let chis = geometry::side_chain_torsions(residue);
+            if library.is_empty() || chis.len() < library[0].1.len() {
+                return None;
+            }
+            let rotamer = matching_rotamer(name, &chis);
+            Some((residue, chis, rotamer))
+        })
+        .collect();
+    RotamerReport { residues }
+}
+
+/// The clash score of a structure, see [`clash_score`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClashScore<'a> {
+    /// All clashes, sorted from the largest to the smallest overlap
+    pub clashes: Vec<Clash<'a>>,
+    /// The number of atoms checked for clashes
+    pub atom_count: usize,
+    /// The number of atoms checked for clashes which are not hydrogens
+    pub heavy_atom_count: usize,
+}
+
+impl<'a> ClashScore<'a> {
+    /// Get the clash score, the number of clashes per 1000 atoms. This is 0.0 if there are no
+    /// atoms.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn score(&self) -> f64 {
+        if self.atom_count == 0 {
+            0.0
+        } else {
+            self.clashes.len() as f64 * 1000.0 / self.atom_count as f64
+        }
+    }
+
+    /// Get the clashes which do not involve hydrogens
+    pub fn heavy_atom_clashes(&self) -> impl Iterator<Item = &Clash<'a>> + '_ {
+        let hydrogen = |atom: &Atom| atom.element() == Some(&Element::H);
+        self.clashes
+            .iter()
+            .filter(move |clash| !hydrogen(clash.atom_a) && !hydrogen(clash.atom_b))
+    }
+
+    /// Get the clash score ignoring hydrogens, the number of clashes between heavy atoms per
+    /// 1000 heavy atoms. This is 0.0 if there are no heavy atoms.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn score_without_hydrogens(&self) -> f64 {
+        if self.heavy_atom_count == 0 {
+            0.0
+        } else {
+            self.heavy_atom_clashes().count() as f64 * 1000.0 / self.heavy_atom_count as f64
+        }
+    }
+
+    /// Get a `GeneralWarning` for every clash.
+    pub fn warnings(&self) -> Vec<PDBError> {
+        self.clashes
+            .iter()
+            .map(|clash| {
+                PDBError::new(
+                    ErrorLevel::GeneralWarning,
+                    "Steric clash",
+                    format!(
+                        "Atoms {} {} and {} {} overlap by {:.2} Aͦ.",
+                        clash.atom_a.serial_number(),
+                        clash.atom_a.name(),
+                        clash.atom_b.serial_number(),
+                        clash.atom_b.name(),
+                        clash.overlap
+                    ),
+                    Context::None,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Calculate the clash score of the given PDB as used by MolProbity: the number of steric
+/// clashes (see [`geometry::clashes`]) per 1000 atoms, with and without hydrogens. Only the
+/// first model and the first conformer of every residue are used. Structures without explicit
+/// hydrogens should be compared with [`ClashScore::score_without_hydrogens`].
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// let clashes = clash_score(&pdb);
+/// println!("Clash score {:.1} with {} clashes", clashes.score(), clashes.clashes.len());
+/// ```
+#[doc_cfg(feature = "rstar")]
+pub fn clash_score(pdb: &PDB) -> ClashScore<'_> {
+    let residues: Vec<&Residue> = pdb
+        .model(0)
+        .map_or_else(Vec::new, |model| model.residues().collect());
+    let atoms = || {
+        residues
+            .iter()
+            .filter_map(|residue| residue.conformer(0))
+            .flat_map(Conformer::atoms)
+            .filter(|atom| atom.element().is_some())
+    };
+    ClashScore {
+        clashes: geometry::clashes(&residues),
+        atom_count: atoms().count(),
+        heavy_atom_count: atoms()
+            .filter(|atom| atom.element() != Some(&Element::H))
+            .count(),
+    }
+}
+
+/// Find the breaks in the polymers of all chains of the first model of the given PDB (see
+/// [`Chain::breaks`]) and give a `GeneralWarning` for each, as these are not revealed by the
+/// SEQRES records alone.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// assert!(validate_chain_breaks(&pdb).is_empty());
+/// ```
+pub fn validate_chain_breaks(pdb: &PDB) -> Vec<PDBError> {
+    let chains = pdb.models().take(1).flat_map(Model::chains).collect();
+    validate_each(chains, |chain| {
+        chain
+            .breaks()
+            .into_iter()
+            .map(|chain_break| {
+                let distance = chain_break
+                    .distance
+                    .map_or_else(|| "unknown".to_string(), |d| format!("{d:.2} Aͦ"));
+                PDBError::new(
+                    ErrorLevel::GeneralWarning,
+                    "Chain break",
+                    format!(
+                        "Chain {} is broken between residues {:?} and {:?}, the distance between the linking atoms is {distance} and {} residues are missing according to the numbering.",
+                        chain.id(),
+                        chain_break.before.id(),
+                        chain_break.after.id(),
+                        chain_break.missing_residues()
+                    ),
+                    Context::None,
+                )
+            })
+            .collect()
+    })
+}
+
+/// The number of residues on each side of a residue that are used as its neighbours when looking
+/// for B factor outliers
+const B_FACTOR_WINDOW: usize = 2;
+/// The number of standard deviations a residue can differ from its neighbours before its B
+/// factors are reported as an outlier
+const B_FACTOR_OUTLIER_THRESHOLD: f64 = 3.0;
+
+/// Find residues in the first model of the given PDB with B factors that deviate strongly from
+/// their neighbours in the chain, and give a `GeneralWarning` for each. For every residue the
+/// mean B factor (see [`Chain::bfactor_profile`]) is compared to the average of the means of
+/// the two residues on each side, and a residue is reported when this difference is more than
+/// three times the standard deviation of these differences over the whole chain. Waters are
+/// ignored. This points to residues that are much more (or less) flexible than their
+/// surroundings, which can be a sign of modelling errors.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// for warning in validate_b_factors(&pdb) {
+///     println!("{warning}");
+/// }
+/// ```
+pub fn validate_b_factors(pdb: &PDB) -> Vec<PDBError> {
+    let chains = pdb.models().take(1).flat_map(Model::chains).collect();
+    validate_each(chains, |chain| {
+        let profile: Vec<(&Residue, f64)> = chain
+            .bfactor_profile()
+            .into_iter()
+            .filter(|(residue, _, _)| !residue.conformers().any(Conformer::is_water))
+            .map(|(residue, mean, _)| (residue, mean))
+            .collect();
+        if profile.len() < 2 {
+            return Vec::new();
+        }
+        let differences: Vec<f64> = (0..profile.len())
+            .map(|index| {
+                let neighbours: Vec<f64> = (index.saturating_sub(B_FACTOR_WINDOW)
+                    ..(index + B_FACTOR_WINDOW + 1).min(profile.len()))
+                    .filter(|other| *other != index)
+                    .map(|other| profile[other].1)
+                    .collect();
+                #[allow(clippy::cast_precision_loss)]
+                let local = neighbours.iter().sum::<f64>() / neighbours.len() as f64;
+                profile[index].1 - local
+            })
+            .collect();
+        #[allow(clippy::cast_precision_loss)]
+        let count = differences.len() as f64;
+        let mean = differences.iter().sum::<f64>() / count;
+        let deviation =
+            (differences.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / count).sqrt();
+        profile
+            .iter()
+            .zip(&differences)
+            .filter(|(_, difference)| {
+                (*difference - mean).abs() > B_FACTOR_OUTLIER_THRESHOLD * deviation
+            })
+            .map(|((residue, b_factor), difference)| {
+                let (serial, insertion_code) = residue.id();
+                PDBError::new(
+                    ErrorLevel::GeneralWarning,
+                    "B factor outlier",
+                    format!(
+                        "Residue {serial}{} of chain {} has a mean B factor of {b_factor:.2} which differs {difference:.2} from its neighbours, more than {B_FACTOR_OUTLIER_THRESHOLD} times the standard deviation ({deviation:.2}) in this chain.",
+                        insertion_code.unwrap_or_default(),
+                        chain.id(),
+                    ),
+                    Context::show(format!(
+                        "{}/{serial}{}",
+                        chain.id(),
+                        insertion_code.unwrap_or_default()
+                    )),
+                )
+            })
+            .collect()
+    })
+}
+
+/// Test if the given atom name is known for an amino acid with the given heavy atoms. Hydrogens
+/// are named after the heavy atom they are bonded to, with an optional number (`HB2` on `CB`,
+/// `HD11` on `CD1`, `H1` on `N`), in the version 2.3 nomenclature the number comes first
+/// (`1HB`), and heavy atoms renamed in version 3 are known by their old name as well.
+fn is_known_atom_name(residue: &str, template: &[&str], name: &str) -> bool {
+    if name == "OXT"
+        || template.contains(&name)
+        || reference_tables::renamed_atom(residue, name, FormatVersion::V3)
+            .map_or(false, |new| template.contains(&new))
+    {
+        return true;
+    }
+    let hydrogen = name.trim_start_matches(|c: char| c.is_ascii_digit());
+    let suffix = match hydrogen.strip_prefix('H') {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let heavy = |suffix: &str| {
+        suffix == "XT"
+            || template
+                .iter()
+                .any(|atom| atom.get(1..) == Some(suffix) && !atom.starts_with('H'))
+    };
+    heavy(suffix)
+        || suffix
+            .strip_suffix(|c: char| c.is_ascii_digit())
+            .map_or(false, |suffix| {
+                heavy(suffix)
+                    || suffix
+                        .strip_suffix(|c: char| c.is_ascii_digit())
+                        .map_or(false, heavy)
+            })
+}
+
+/// Check that the standard amino acids in the first model of the given PDB only contain atoms
+/// with names known from their templates, and give a `GeneralWarning` for every unknown atom
+/// name, which often points to typos or corrupted files. Both the version 2.3 and 3 hydrogen
+/// names are accepted. Residues that are declared to be modified residues (by MODRES records)
+/// are not checked, as their atoms differ from the standard amino acid.
+///
+/// ```rust
+/// use pdbtbx::*;
+/// let (pdb, _errors) = ReadOptions::new().set_level(StrictnessLevel::Loose).read("example-pdbs/1ubq.pdb").unwrap();
+/// assert!(validate_atom_names(&pdb).is_empty());
+/// ```
+pub fn validate_atom_names(pdb: &PDB) -> Vec<PDBError> {
+    let chains = pdb.models().take(1).flat_map(Model::chains).collect();
+    validate_each(chains, |chain| {
+        let mut errors = Vec::new();
+        for residue in chain.residues() {
+            for conformer in residue.conformers() {
+                if conformer.modification().is_some() {
+                    continue;
+                }
+                let template = match reference_tables::amino_acid_atoms(conformer.name()) {
+                    Some(template) => template,
+                    None => continue,
+                };
+                for atom in conformer.atoms() {
+                    if is_known_atom_name(conformer.name(), template, atom.name()) {
+                        continue;
+                    }
+                    let (serial, insertion_code) = residue.id();
+                    errors.push(PDBError::new(
+                        ErrorLevel::GeneralWarning,
+                        "Unknown atom name",
+                        format!(
+                            "Atom {} (serial number {}) is not part of the standard amino acid {} in residue {serial}{} of chain {}, the name could contain a typo.",
+                            atom.name(),
+                            atom.serial_number(),
+                            conformer.name(),
+                            insertion_code.unwrap_or_default(),
+                            chain.id(),
+                        ),
+                        Context::show(format!(
+                            "{}/{serial}{}/{}",
+                            chain.id(),
+                            insertion_code.unwrap_or_default(),
+                            atom.name()
+                        )),
+                    ));
+                }
+            }
+        }
+        errors
+    })
+}
+
 /// Validate the models by enforcing that all models should contain the same atoms (with possibly different data).
 /// It checks this by matching all atoms (not hetatoms) for each model to see if they correspond (`Atom::correspond`).
 #[allow(clippy::unwrap_used)]
 fn validate_models(pdb: &PDB) -> Vec<PDBError> {
-    let mut errors = Vec::new();
-    let total_atoms = pdb.model(0).unwrap().atom_count();
-    let normal_atoms = pdb
-        .model(0)
-        .unwrap()
-        .atoms()
-        .filter(|a| !a.hetero())
-        .count();
-    for model in pdb.models().skip(1) {
+    let first = pdb.model(0).unwrap();
+    let total_atoms = first.atom_count();
+    let normal_atoms = first.atoms().filter(|a| !a.hetero()).count();
+    validate_each(pdb.models().skip(1).collect(), |model| {
         if model.atom_count() != total_atoms {
-            errors.push(PDBError::new(
+            return vec![PDBError::new(
                 ErrorLevel::LooseWarning,
                 "Invalid Model",
                 format!(
@@ -258,10 +820,9 @@ fn validate_models(pdb: &PDB) -> Vec<PDBError> {
                     total_atoms
                 ),
                 Context::None,
-            ));
-            continue;
+            )];
         } else if model.atoms().filter(|a| !a.hetero()).count() != normal_atoms {
-            errors.push(PDBError::new(
+            return vec![PDBError::new(
                 ErrorLevel::StrictWarning,
                 "Invalid Model",
                 format!(
@@ -271,14 +832,14 @@ fn validate_models(pdb: &PDB) -> Vec<PDBError> {
                     normal_atoms
                 ),
                 Context::None,
-            ));
-            continue;
+            )];
         }
-        for index in 0..model.atom_count() {
-            let current_atom = model.atom(index).unwrap();
-            let standard_atom = pdb.model(0).unwrap().atom(index).unwrap();
-            if !standard_atom.corresponds(current_atom) {
-                errors.push(PDBError::new(
+        model
+            .atoms()
+            .zip(first.atoms())
+            .filter(|(current_atom, standard_atom)| !standard_atom.corresponds(current_atom))
+            .map(|(current_atom, _)| {
+                PDBError::new(
                     ErrorLevel::StrictWarning,
                     "Atoms in Models not corresponding",
                     format!(
@@ -287,11 +848,24 @@ fn validate_models(pdb: &PDB) -> Vec<PDBError> {
                         model.serial_number()
                     ),
                     Context::None,
-                ));
-            }
-        }
-    }
-    errors
+                )
+            })
+            .collect()
+    })
+}
+
+/// Run the given validation on all items and gather the errors in the order of the items. The
+/// items are validated in parallel if the `rayon` feature is enabled, as validation can take
+/// up most of the time spent parsing structures with many models or chains.
+pub(crate) fn validate_each<T: Send>(
+    items: Vec<T>,
+    validation: impl Fn(T) -> Vec<PDBError> + Send + Sync,
+) -> Vec<PDBError> {
+    #[cfg(feature = "rayon")]
+    let errors: Vec<Vec<PDBError>> = items.into_par_iter().map(validation).collect();
+    #[cfg(not(feature = "rayon"))]
+    let errors: Vec<Vec<PDBError>> = items.into_iter().map(validation).collect();
+    errors.into_iter().flatten().collect()
 }
 
 /// Copy all atoms in blank alternative conformers into the other conformers.
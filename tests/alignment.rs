@@ -0,0 +1,62 @@
+// Test aligning the sequences of chains and superposing chains based on these alignments.
+
+mod common;
+
+use common::open;
+use pdbtbx::alignment::Scoring;
+use pdbtbx::*;
+
+#[test]
+fn chain_deletion() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain(0).unwrap();
+    let mut shorter = chain.clone();
+    shorter.remove_residues_by(|r| (20..25).contains(&r.serial_number()));
+
+    let alignment = chain.align_sequence(&shorter, Scoring::default());
+    assert_eq!(alignment.identical, shorter.sequence().len());
+    let gaps: Vec<usize> = alignment
+        .pairs
+        .iter()
+        .filter_map(|(a, b)| if b.is_none() { *a } else { None })
+        .collect();
+    assert_eq!(gaps, vec![19, 20, 21, 22, 23]);
+    assert!(alignment.aligned().all(|(a, b)| a == b || a == b + 5));
+}
+
+#[test]
+fn superpose_moved_chain() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain(0).unwrap();
+    let mut moved = chain.clone();
+    moved.remove_residues_by(|r| r.serial_number() < 5);
+    moved.apply_transformation(
+        &TransformationMatrix::rotation_z(40.0)
+            .combine(&TransformationMatrix::translation(5.0, -3.0, 12.0)),
+    );
+
+    let (transformation, rmsd) = geometry::superpose_chains(chain, &moved).unwrap();
+    assert!(rmsd < 1e-3);
+    let alpha_carbon = |chain: &Chain| {
+        chain
+            .residues()
+            .find(|r| r.serial_number() == 76)
+            .and_then(|r| r.atoms().find(|a| a.name() == "CA"))
+            .unwrap()
+            .pos()
+    };
+    let (x, y, z) = transformation.apply(alpha_carbon(chain));
+    let target = alpha_carbon(&moved);
+    assert!((x - target.0).abs() < 1e-3);
+    assert!((y - target.1).abs() < 1e-3);
+    assert!((z - target.2).abs() < 1e-3);
+}
+
+#[test]
+fn superpose_too_short() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain(0).unwrap();
+    let mut short = chain.clone();
+    short.remove_residues_by(|r| r.serial_number() > 2);
+    assert!(geometry::superpose_chains(chain, &short).is_none());
+}
@@ -0,0 +1,164 @@
+// Test expanding alternative locations into models, and collapsing them back.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+fn alternative_atoms(pdb: &PDB, location: Option<&str>) -> usize {
+    pdb.conformers()
+        .filter(|c| c.alternative_location() == location)
+        .map(Conformer::atom_count)
+        .sum()
+}
+
+#[test]
+fn expand() {
+    let mut pdb = open("example-pdbs/3nig.pdb");
+    let shared = alternative_atoms(&pdb, None);
+    // The atoms shared between alternative locations are part of both conformers
+    assert_eq!(alternative_atoms(&pdb, Some("A")), 61);
+    assert_eq!(alternative_atoms(&pdb, Some("B")), 61);
+
+    pdb.expand_alt_locs();
+    assert_eq!(pdb.model_count(), 2);
+    assert_eq!(
+        pdb.models().map(Model::serial_number).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    for model in pdb.models() {
+        assert_eq!(model.atom_count(), shared + 61);
+        assert!(model
+            .conformers()
+            .all(|c| c.alternative_location().is_none()));
+    }
+    assert_ne!(
+        pdb.model(0)
+            .unwrap()
+            .atoms()
+            .map(Atom::pos)
+            .collect::<Vec<_>>(),
+        pdb.model(1)
+            .unwrap()
+            .atoms()
+            .map(Atom::pos)
+            .collect::<Vec<_>>()
+    );
+
+    // Nothing to expand
+    let mut plain = open("example-pdbs/1ubq.pdb");
+    let before = plain.clone();
+    plain.expand_alt_locs();
+    assert_eq!(plain, before);
+}
+
+#[test]
+fn expand_then_collapse() {
+    let original = open("example-pdbs/3nig.pdb");
+    let mut pdb = original.clone();
+    pdb.expand_alt_locs();
+    pdb.collapse_alt_locs().unwrap();
+    assert_eq!(pdb.model_count(), 1);
+    assert_eq!(pdb.atom_count(), original.atom_count());
+    for location in [None, Some("A"), Some("B")] {
+        assert_eq!(
+            alternative_atoms(&pdb, location),
+            alternative_atoms(&original, location)
+        );
+    }
+    assert_eq!(pdb.model(0).unwrap().serial_number(), 0);
+    assert_eq!(pdb.bonds().count(), original.bonds().count());
+    assert!(pdb.bonds().count() > 0);
+    assert_eq!(pdb, original);
+}
+
+#[test]
+fn mismatched_models() {
+    let mut pdb = open("example-pdbs/3nig.pdb");
+    pdb.expand_alt_locs();
+    pdb.model_mut(1).unwrap().remove_chain(0);
+    let before = pdb.clone();
+    assert!(pdb.collapse_alt_locs().is_err());
+    assert_eq!(pdb, before);
+}
+
+#[test]
+fn average() {
+    let mut pdb = open("example-pdbs/3nig.pdb");
+    let shared = alternative_atoms(&pdb, None);
+    pdb.average_alt_locs();
+    assert!(pdb.conformers().all(|c| c.alternative_location().is_none()));
+    assert_eq!(pdb.atom_count(), shared + 61);
+
+    let residue = pdb
+        .chains()
+        .find(|c| c.id() == "A")
+        .unwrap()
+        .residues()
+        .find(|r| r.serial_number() == 239)
+        .unwrap();
+    assert_eq!(residue.conformer_count(), 1);
+    let atom = residue.atoms().find(|a| a.name() == "CG1").unwrap();
+    let (a, b) = ((49.871, 88.504, 58.644), (50.617, 89.371, 60.986));
+    let mean = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0, (a.2 + b.2) / 2.0);
+    let (x, y, z) = atom.pos();
+    assert!((x - mean.0).abs() < 1e-9 && (y - mean.1).abs() < 1e-9 && (z - mean.2).abs() < 1e-9);
+    assert!((atom.occupancy() - 1.0).abs() < 1e-9);
+    // The spread of the positions adds to the B factor
+    let spread = ((a.0 - mean.0).powi(2) + (a.1 - mean.1).powi(2) + (a.2 - mean.2).powi(2))
+        * 8.0
+        * std::f64::consts::PI.powi(2)
+        / 3.0;
+    assert!((atom.b_factor() - ((26.25 + 12.50) / 2.0 + spread)).abs() < 1e-6);
+
+    // Nothing to average
+    let mut plain = open("example-pdbs/1ubq.pdb");
+    let before = plain.clone();
+    plain.average_alt_locs();
+    assert_eq!(plain, before);
+}
+
+#[test]
+fn highest_occupancy_policy() {
+    let input = "\
+ATOM      1  N   SER A   1      10.000  10.000  10.000  1.00 10.00           N
+ATOM      2  CA ASER A   1      11.000  10.000  10.000  0.30 10.00           C
+ATOM      3  CA BSER A   1      11.500  10.000  10.000  0.70 10.00           C
+ATOM      4  OG ASER A   1      12.000  10.000  10.000  0.30 10.00           O
+ATOM      5  OG BSER A   1      12.500  10.000  10.000  0.70 10.00           O
+END
+";
+    let read = |policy| {
+        ReadOptions::new()
+            .set_format(Format::Pdb)
+            .set_level(StrictnessLevel::Loose)
+            .set_alt_loc_policy(policy)
+            .read_raw(input.as_bytes())
+            .unwrap()
+            .0
+    };
+    let kept = read(AltLocPolicy::Keep);
+    // The shared nitrogen is part of both conformers
+    assert_eq!(kept.atom_count(), 6);
+    assert_eq!(kept.conformer_count(), 2);
+
+    let pdb = read(AltLocPolicy::HighestOccupancy);
+    assert_eq!(pdb.atom_count(), 3);
+    assert!(pdb.conformers().all(|c| c.alternative_location().is_none()));
+    let mut serials: Vec<usize> = pdb.atoms().map(Atom::serial_number).collect();
+    serials.sort_unstable();
+    assert_eq!(serials, [1, 3, 5]);
+
+    let pdb = read(AltLocPolicy::Average);
+    assert_eq!(pdb.atom_count(), 3);
+    let ca = pdb.atoms().find(|a| a.name() == "CA").unwrap();
+    assert!((ca.x() - 11.35).abs() < 1e-6);
+}
+
+#[test]
+fn highest_occupancy_3nig() {
+    let mut pdb = open("example-pdbs/3nig.pdb");
+    let shared = alternative_atoms(&pdb, None);
+    pdb.keep_highest_occupancy_alt_locs();
+    assert_eq!(alternative_atoms(&pdb, None), shared + 61);
+}
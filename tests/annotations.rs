@@ -0,0 +1,101 @@
+// Test saving and restoring custom annotations in PDB files.
+
+mod common;
+
+use common::{open, open_with_errors, temp_path};
+use pdbtbx::*;
+
+#[test]
+fn annotations_survive_saving() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let remarks = pdb.remark_count();
+    pdb.annotations_mut().insert("pipeline", "docking run 3");
+    pdb.annotations_mut().insert(
+        "notes",
+        "a long text which does not fit on a single line ".repeat(4),
+    );
+    pdb.chains_mut()
+        .next()
+        .unwrap()
+        .annotations_mut()
+        .insert("selected", true);
+    pdb.residues_mut()
+        .find(|r| r.serial_number() == 11)
+        .unwrap()
+        .annotations_mut()
+        .insert("score", -1.25);
+    pdb.atoms_mut()
+        .nth(20)
+        .unwrap()
+        .annotations_mut()
+        .insert("cluster", 7_isize);
+    let path = temp_path("1ubq_annotations.pdb");
+    save(&pdb, &path, StrictnessLevel::Strict).unwrap();
+    let (saved, errors) = open_with_errors(&path);
+    assert!(!errors
+        .iter()
+        .any(|e| e.short_description().contains("MASTER")
+            || e.short_description().contains("nnotation")));
+
+    assert_eq!(saved.remark_count(), remarks);
+    assert_eq!(saved.annotations(), pdb.annotations());
+    assert_eq!(
+        saved
+            .chains()
+            .next()
+            .unwrap()
+            .annotations()
+            .get_boolean("selected"),
+        Some(true)
+    );
+    assert_eq!(
+        saved
+            .residues()
+            .find(|r| r.serial_number() == 11)
+            .unwrap()
+            .annotations()
+            .get_float("score"),
+        Some(-1.25)
+    );
+    assert_eq!(
+        saved
+            .atoms()
+            .nth(20)
+            .unwrap()
+            .annotations()
+            .get_integer("cluster"),
+        Some(7)
+    );
+    assert!(saved.atoms().nth(21).unwrap().annotations().is_empty());
+}
+
+#[test]
+fn unknown_target() {
+    let mut pdb = PDB::new();
+    pdb.annotations_mut().insert("kept", "yes");
+    let mut model = Model::new(1);
+    let mut chain = Chain::new("A").unwrap();
+    chain.annotations_mut().insert("kept", "yes");
+    chain.add_atom(
+        Atom::new(false, 1, "CA", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap(),
+        (1, None),
+        ("ALA", None),
+    );
+    model.add_chain(chain);
+    pdb.add_model(model);
+    let path = temp_path("annotations_unknown.pdb");
+    save(&pdb, &path, StrictnessLevel::Loose).unwrap();
+    let text = std::fs::read_to_string(&path)
+        .unwrap()
+        .replace("PDBTBX CHAIN 1 A", "PDBTBX CHAIN 1 B");
+    let (saved, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(text.as_bytes())
+        .unwrap();
+    assert_eq!(saved.annotations().get_text("kept"), Some("yes"));
+    assert!(saved.chains().next().unwrap().annotations().is_empty());
+    assert!(errors
+        .iter()
+        .any(|e| e.short_description() == "Annotation target not found"));
+}
@@ -0,0 +1,64 @@
+// Test comparing structures with a tolerance on the floating point values
+
+mod common;
+
+use common::{open, temp_path};
+use pdbtbx::*;
+
+#[test]
+fn rotation_round_trip() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let mut rotated = pdb.clone();
+    rotated.apply_transformation(&TransformationMatrix::rotation_x(37.0));
+    rotated.apply_transformation(&TransformationMatrix::rotation_x(-37.0));
+    assert!(rotated.approx_eq(&pdb, 1e-9));
+
+    let mut moved = pdb.clone();
+    moved
+        .atoms_mut()
+        .next()
+        .unwrap()
+        .set_b_factor(99.0)
+        .unwrap();
+    assert!(!moved.approx_eq(&pdb, 1.0));
+    assert!(moved.approx_eq(&pdb, 100.0));
+}
+
+#[test]
+fn file_round_trip() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let path = temp_path("1ubq_approx.pdb");
+    save_pdb(&pdb, &path, StrictnessLevel::Loose).unwrap();
+    let reread = open(&path);
+    assert!(reread
+        .model(0)
+        .unwrap()
+        .approx_eq(pdb.model(0).unwrap(), 0.001));
+}
+
+#[test]
+fn levels() {
+    let a = Atom::new(false, 1, "CA", 1.0, 2.0, 3.0, 1.0, 10.0, "C", 0).unwrap();
+    let mut b = a.clone();
+    b.set_x(1.0004).unwrap();
+    assert!(a.approx_eq(&b, 0.001));
+    assert!(!a.approx_eq(&b, 0.0001));
+    b.set_name("CB").unwrap();
+    assert!(!a.approx_eq(&b, 0.001));
+
+    let mut x = Conformer::new("ALA", None, Some(a.clone())).unwrap();
+    let y = Conformer::new("ALA", None, Some(b)).unwrap();
+    assert!(!x.approx_eq(&y, 0.001));
+    x.add_atom(a);
+    assert!(!x.approx_eq(&y, 1.0));
+
+    let r = Residue::new(1, None, Some(x.clone())).unwrap();
+    let s = Residue::new(2, None, Some(x)).unwrap();
+    assert!(r.approx_eq(&r.clone(), 0.0));
+    assert!(!r.approx_eq(&s, 1.0));
+    let c = Chain::from_iter("A", vec![r.clone()].into_iter()).unwrap();
+    let d = Chain::from_iter("A", vec![r, s].into_iter()).unwrap();
+    assert!(!c.approx_eq(&d, 1.0));
+    assert!(Model::from_iter(1, vec![c.clone()].into_iter())
+        .approx_eq(&Model::from_iter(1, vec![c].into_iter()), 0.0));
+}
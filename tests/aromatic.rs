@@ -0,0 +1,34 @@
+// Test detecting aromatic interactions in a full structure.
+
+mod common;
+
+use common::open;
+use pdbtbx::interactions::{self, StackingType};
+use pdbtbx::*;
+
+#[test]
+fn interactions_in_structure() {
+    let pdb = open("example-pdbs/3nig.pdb");
+    let residues: Vec<&Residue> = pdb.residues().collect();
+
+    let stacking = interactions::pi_stacking(&residues, &residues);
+    assert!(!stacking.is_empty());
+    for (index, s) in stacking.iter().enumerate() {
+        assert!(s.distance <= interactions::PI_STACKING_MAX_DISTANCE);
+        assert!(s.offset <= interactions::PI_MAX_OFFSET);
+        match s.kind {
+            StackingType::Parallel => assert!(s.angle <= 30.0),
+            StackingType::TShaped => assert!(s.angle >= 60.0),
+        }
+        // Every pair is only reported once
+        assert!(!stacking[index + 1..]
+            .iter()
+            .any(|o| o.ring_a == s.ring_b && o.ring_b == s.ring_a));
+    }
+
+    for contact in interactions::cation_pi(&residues, &residues) {
+        assert!(matches!(contact.cation_residue.name(), Some("LYS" | "ARG")));
+        assert!(contact.distance <= interactions::CATION_PI_MAX_DISTANCE);
+        assert!(contact.offset <= interactions::PI_MAX_OFFSET);
+    }
+}
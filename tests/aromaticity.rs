@@ -0,0 +1,61 @@
+// Test the perception of aromatic rings in residues and ligands of full structures.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+fn aromatic_ring_counts(file: &str, name: &str) -> Vec<usize> {
+    let pdb = open(file);
+    pdb.residues()
+        .filter(|r| r.name() == Some(name))
+        .map(|r| r.conformer(0).unwrap().aromatic_rings().len())
+        .collect()
+}
+
+#[test]
+fn residues() {
+    assert!(aromatic_ring_counts("example-pdbs/3nig.pdb", "TRP")
+        .iter()
+        .all(|n| *n == 2));
+    assert!(aromatic_ring_counts("example-pdbs/3nig.pdb", "HIS")
+        .iter()
+        .all(|n| *n == 1));
+    // Saturated rings are not aromatic, even when planar
+    assert!(aromatic_ring_counts("example-pdbs/3nig.pdb", "PRO")
+        .iter()
+        .all(|n| *n == 0));
+    assert!(aromatic_ring_counts("example-pdbs/3nig.pdb", "NAG")
+        .iter()
+        .all(|n| *n == 0));
+}
+
+#[test]
+fn nucleotides() {
+    // The purine has two fused rings, the pyrimidine one ring with a carbonyl
+    assert_eq!(
+        aromatic_ring_counts("example-pdbs/nucleic.pdb", "DG"),
+        vec![2]
+    );
+    assert_eq!(
+        aromatic_ring_counts("example-pdbs/nucleic.pdb", "DC"),
+        vec![1]
+    );
+    assert_eq!(
+        aromatic_ring_counts("example-pdbs/1yyf.pdb", "ADP"),
+        vec![2, 2]
+    );
+
+    let pdb = open("example-pdbs/nucleic.pdb");
+    let guanine = pdb.residues().find(|r| r.name() == Some("DG")).unwrap();
+    let atoms: Vec<&Atom> = guanine.atoms().collect();
+    let bonds = chemistry::infer_bonds(&atoms);
+    // The sugar and the two base rings
+    assert_eq!(chemistry::rings(atoms.len(), &bonds).len(), 3);
+    let mut sizes: Vec<usize> = chemistry::aromatic_rings(&atoms, &bonds)
+        .iter()
+        .map(Vec::len)
+        .collect();
+    sizes.sort_unstable();
+    assert_eq!(sizes, vec![5, 6]);
+}
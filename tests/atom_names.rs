@@ -0,0 +1,56 @@
+// Test the validation of atom names against the templates of the standard amino acids
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+const TYPOS: &str = "ATOM      1  N   ILE A   1      27.340  24.430   2.614  1.00  9.67           N
+ATOM      2  CA  ILE A   1      26.266  25.413   2.842  1.00 10.38           C
+ATOM      3  C   ILE A   1      26.913  26.639   3.531  1.00  9.62           C
+ATOM      4  O   ILE A   1      27.886  26.463   4.263  1.00  9.62           O
+ATOM      5  CB  ILE A   1      25.112  24.880   3.649  1.00 13.77           C
+ATOM      6  CG1 ILE A   1      25.353  24.860   5.134  1.00 16.29           C
+ATOM      7  CG2 ILE A   1      24.112  23.880   3.649  1.00 13.77           C
+ATOM      8  CD  ILE A   1      23.930  23.959   5.904  1.00 17.17           C
+ATOM      9 1HG1 ILE A   1      25.353  24.860   5.134  1.00 16.29           H
+ATOM     10 HD11 ILE A   1      23.930  23.959   5.904  1.00 17.17           H
+ATOM     11  CG  ILE A   1      23.930  23.959   5.904  1.00 17.17           C
+ATOM     12  N   SER A   2      26.335  27.770   3.258  1.00  9.27           N
+ATOM     13  CA  SER A   2      26.850  29.021   3.898  1.00  9.07           C
+ATOM     14  C   SER A   2      26.100  29.253   5.202  1.00  8.72           C
+ATOM     15  O   SER A   2      24.865  29.024   5.330  1.00  8.22           O
+ATOM     16  CB  SER A   2      26.733  30.148   2.905  1.00 10.55           C
+ATOM     17  OG  SER A   2      26.882  31.546   3.409  1.00 11.66           O
+ATOM     18  HG  SER A   2      26.882  31.546   3.409  1.00 11.66           H
+ATOM     19  OXT SER A   2      24.865  29.024   5.330  1.00  8.22           O
+ATOM     20  HQ  SER A   2      24.865  29.024   5.330  1.00  8.22           H
+END
+";
+
+#[test]
+fn clean() {
+    for file in ["example-pdbs/1ubq.pdb", "example-pdbs/1yyf.pdb"] {
+        let pdb = open(file);
+        assert_eq!(validate_atom_names(&pdb), Vec::new(), "{file}");
+    }
+}
+
+#[test]
+fn typos() {
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(TYPOS.as_bytes())
+        .unwrap();
+    let errors = validate_atom_names(&pdb);
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0]
+        .long_description()
+        .contains("Atom CG (serial number 11)"));
+    assert!(errors[1].long_description().contains("Atom HQ"));
+    assert!(errors[1]
+        .long_description()
+        .contains("residue 2 of chain A"));
+    assert_eq!(errors[0].level(), ErrorLevel::GeneralWarning);
+}
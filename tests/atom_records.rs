@@ -0,0 +1,59 @@
+// Test formatting atoms as PDB lines and parsing single ATOM/HETATM lines into atoms
+
+mod common;
+
+use common::{open, temp_path};
+use pdbtbx::*;
+use std::convert::TryFrom;
+
+#[test]
+fn display_matches_saved_file() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let path = temp_path("1ubq_records.pdb");
+    save_pdb(&pdb, &path, StrictnessLevel::Loose).unwrap();
+    let lines: Vec<String> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .filter(|l| l.starts_with("ATOM  ") || l.starts_with("HETATM"))
+        .map(str::to_string)
+        .collect();
+    let hierarchies: Vec<_> = pdb.atoms_with_hierarchy().collect();
+    assert_eq!(hierarchies.len(), lines.len());
+    for (hierarchy, line) in hierarchies.iter().zip(&lines) {
+        assert_eq!(&hierarchy.to_string(), line);
+        let atom = Atom::try_from(line.as_str()).unwrap();
+        assert_eq!(atom.name(), hierarchy.atom().name());
+        assert_eq!(atom.pos(), hierarchy.atom().pos());
+    }
+
+    // Without context the residue name and chain id are left empty
+    let atom = pdb.atoms().next().unwrap();
+    assert!(atom
+        .to_string()
+        .starts_with("ATOM  1      N        0         27.251"));
+}
+
+#[test]
+fn parse_line() {
+    let line = "HETATM 1234 ZN    ZN A 201      10.000  -5.250   3.125  0.50 20.00          ZN2+";
+    let atom = Atom::try_from(line).unwrap();
+    assert!(atom.hetero());
+    assert_eq!(atom.serial_number(), 1234);
+    assert_eq!(atom.name(), "ZN");
+    assert_eq!(atom.pos(), (10.0, -5.25, 3.125));
+    assert_eq!(atom.occupancy(), 0.5);
+    assert_eq!(atom.b_factor(), 20.0);
+    assert_eq!(atom.element(), Some(&Element::Zn));
+    assert_eq!(atom.charge(), 2);
+
+    // Parsing the formatted atom gives the same atom back
+    let again = Atom::try_from(atom.to_string().as_str()).unwrap();
+    assert_eq!(again.to_string(), atom.to_string());
+    assert_eq!(again.pos(), atom.pos());
+
+    assert!(Atom::try_from("REMARK   2 RESOLUTION. 1.80 ANGSTROMS.").is_err());
+    assert!(Atom::try_from(
+        "ATOM      1  N   MET A   1      3x.198  19.582  28.888  1.00 13.31           N"
+    )
+    .is_err());
+}
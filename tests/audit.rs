@@ -0,0 +1,44 @@
+// Test auditing PDB files for conformance to the format.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn audit_example_files() {
+    let report = ReadOptions::new().audit("example-pdbs/1ubq.pdb");
+    assert_eq!(report.record_count("ATOM"), 1231);
+    assert!(report
+        .issues()
+        .iter()
+        .all(|issue| issue.level() == ErrorLevel::StrictWarning));
+    assert!(report
+        .issues()
+        .iter()
+        .any(|issue| issue.short_description() == "Missing mandatory record"));
+
+    let report = ReadOptions::new().audit("example-pdbs/1ubq.cif");
+    assert_eq!(report.issues().len(), 1);
+    assert_eq!(report.issues()[0].level(), ErrorLevel::BreakingError);
+}
+
+#[test]
+fn audit_written_file() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let mut output = Vec::new();
+    save_pdb_raw(
+        &pdb,
+        std::io::BufWriter::new(&mut output),
+        StrictnessLevel::Medium,
+    );
+    let report = ReadOptions::new()
+        .suppress_errors(ErrorFilter::title("Missing mandatory record"))
+        .audit_raw(output.as_slice());
+    assert_eq!(report.record_count("MASTER"), 1);
+    assert_eq!(report.record_count("END"), 1);
+    assert!(report.issues().iter().all(|issue| {
+        issue.short_description() != "Record out of order"
+            && issue.short_description() != "Unknown record"
+    }));
+}
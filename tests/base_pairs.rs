@@ -0,0 +1,100 @@
+// Test recognising nucleic acid chains and detecting base pairs from the geometry.
+
+use pdbtbx::geometry::BasePairType;
+use pdbtbx::*;
+
+/// A nucleotide as chain, serial number, name, x coordinate, and base atoms, see [`DUPLEX`]
+type Nucleotide = (
+    &'static str,
+    isize,
+    &'static str,
+    f64,
+    &'static [(&'static str, f64)],
+);
+
+/// The nucleotides of a small synthetic duplex, as chain, serial number, name, x coordinate,
+/// and the base atoms with their y coordinate: G-C, A-T, a G-T wobble, and a C-A mismatch
+const DUPLEX: &[Nucleotide] = &[
+    ("A", 1, "DG", 0.0, &[("O6", 2.4), ("N1", 0.0), ("N2", -2.3)]),
+    ("B", 1, "DC", 2.9, &[("N4", 2.4), ("N3", 0.0), ("O2", -2.3)]),
+    ("A", 2, "DA", 0.0, &[("N6", 2.4), ("N1", 0.0)]),
+    ("B", 2, "DT", 2.9, &[("O4", 2.4), ("N3", 0.0), ("O2", -2.3)]),
+    ("A", 3, "DG", 0.0, &[("O6", 2.4), ("N1", 0.0), ("N2", -2.3)]),
+    ("B", 3, "DT", 2.9, &[("N3", 2.4), ("O2", 0.0), ("O4", 4.8)]),
+    ("A", 4, "DC", 0.0, &[("N4", 2.4), ("N3", 0.0), ("O2", -2.3)]),
+    ("B", 4, "DA", 2.9, &[("N6", 2.4), ("N1", 0.0)]),
+];
+
+fn duplex() -> PDB {
+    let mut model = Model::new(1);
+    for (chain, serial, name, x, atoms) in DUPLEX {
+        for (index, (atom, y)) in atoms.iter().enumerate() {
+            // Place every pair in its own layer, well separated from the neighbouring pairs
+            let z = *serial as f64 * 5.0;
+            model.add_atom(
+                Atom::new(false, index, *atom, *x, *y, z, 1.0, 0.0, &atom[..1], 0).unwrap(),
+                chain,
+                (*serial, None),
+                (*name, None),
+            );
+        }
+    }
+    let mut pdb = PDB::new();
+    pdb.add_model(model);
+    pdb
+}
+
+#[test]
+fn nucleic_chains() {
+    let pdb = duplex();
+    assert!(pdb.chains().all(Chain::is_nucleic));
+    let mut protein = Chain::new("C").unwrap();
+    protein.add_atom(
+        Atom::new(false, 1, "CA", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap(),
+        (1, None),
+        ("ALA", None),
+    );
+    assert!(!protein.is_nucleic());
+    assert!(!Chain::new("D").unwrap().is_nucleic());
+}
+
+#[test]
+fn detect_pairs() {
+    let pdb = duplex();
+    let residues: Vec<&Residue> = pdb.residues().collect();
+    let pairs = geometry::base_pairs(&residues);
+    let found: Vec<(&str, &str, BasePairType, usize)> = pairs
+        .iter()
+        .map(|pair| {
+            (
+                pair.purine.name().unwrap(),
+                pair.pyrimidine.name().unwrap(),
+                pair.kind,
+                pair.hydrogen_bonds.len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("DG", "DC", BasePairType::WatsonCrick, 3),
+            ("DA", "DT", BasePairType::WatsonCrick, 2),
+            ("DG", "DT", BasePairType::Wobble, 2),
+        ]
+    );
+    assert!(pairs
+        .iter()
+        .flat_map(|pair| &pair.hydrogen_bonds)
+        .all(|(_, _, distance)| (distance - 2.9).abs() < 1e-9));
+}
+
+#[test]
+fn no_pairs_when_apart() {
+    let mut pdb = duplex();
+    pdb.chains_mut()
+        .find(|chain| chain.id() == "B")
+        .unwrap()
+        .apply_transformation(&TransformationMatrix::translation(1.0, 0.0, 0.0));
+    let residues: Vec<&Residue> = pdb.residues().collect();
+    assert!(geometry::base_pairs(&residues).is_empty());
+}
@@ -0,0 +1,46 @@
+// Test the B factor profile of chains and the detection of B factor outliers
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn profile() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain(0).unwrap();
+    let profile = chain.bfactor_profile();
+    assert_eq!(profile.len(), chain.residue_count());
+    let (residue, mean, deviation) = profile[0];
+    let b_factors: Vec<f64> = residue.atoms().map(Atom::b_factor).collect();
+    let expected = b_factors.iter().sum::<f64>() / b_factors.len() as f64;
+    assert!((mean - expected).abs() < 1e-9);
+    assert!(deviation >= 0.0);
+    assert!(validate_b_factors(&pdb).is_empty());
+}
+
+#[test]
+fn outlier() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let residue = pdb
+        .residues_mut()
+        .find(|r| r.serial_number() == 30)
+        .unwrap();
+    for atom in residue.atoms_mut() {
+        atom.set_b_factor(150.0).unwrap();
+    }
+    let errors = validate_b_factors(&pdb);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].level(), ErrorLevel::GeneralWarning);
+    assert_eq!(errors[0].short_description(), "B factor outlier");
+    assert!(errors[0]
+        .long_description()
+        .contains("Residue 30 of chain A"));
+
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let errors = validate_b_factors(&pdb);
+    assert_eq!(errors.len(), 7);
+    assert!(errors
+        .iter()
+        .all(|e| e.short_description() == "B factor outlier"));
+}
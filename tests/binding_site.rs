@@ -0,0 +1,29 @@
+// Test listing the residues surrounding a ligand.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn plp_binding_site() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let ligand = pdb.residues().find(|r| r.name() == Some("PLP")).unwrap();
+    let site = pdb.binding_site(ligand, 4.0);
+    assert!(!site.is_empty());
+    for (residue, distance) in &site {
+        assert!(*distance <= 4.0);
+        assert!(residue.conformers().any(Conformer::is_amino_acid));
+        let expected = residue
+            .atoms()
+            .flat_map(|a| ligand.atoms().map(move |l| a.distance(l)))
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(*distance, expected);
+    }
+    // A larger cutoff includes at least the same residues
+    assert!(pdb.binding_site(ligand, 6.0).len() >= site.len());
+    // Waters and the ligand itself are never included
+    assert!(site
+        .iter()
+        .all(|(r, _)| r.name() != Some("HOH") && r.name() != Some("PLP")));
+}
@@ -0,0 +1,53 @@
+// Test building peptides from their sequence
+
+mod common;
+
+use common::{open, temp_path};
+use pdbtbx::build::{peptide, PeptideConformation};
+use pdbtbx::*;
+
+/// Get the positions of the CA atoms
+fn alpha_carbons(pdb: &PDB) -> Vec<&Atom> {
+    pdb.atoms().filter(|a| a.name() == "CA").collect()
+}
+
+#[test]
+fn helix() {
+    let pdb = peptide("ACDEFGHIKL MNPQRSTVWY", PeptideConformation::AlphaHelix).unwrap();
+    assert_eq!(pdb.residue_count(), 20);
+    // All heavy atoms, with an OXT on the last residue
+    assert_eq!(pdb.atom_count(), 168);
+    assert!(ramachandran(&pdb)
+        .residues
+        .iter()
+        .all(|r| r.3 == RamachandranClass::Favored));
+    // The rise of an alpha helix is about 1.5 Å per residue, and CA(i) to CA(i+4) about 6.2 Å
+    let ca = alpha_carbons(&pdb);
+    assert!((ca[0].distance(ca[19]) / 19.0 - 1.5).abs() < 0.1);
+    assert!((ca[0].distance(ca[4]) - 6.2).abs() < 0.3);
+    for atom in pdb.atoms() {
+        let element = atom.element().unwrap();
+        assert_eq!(element.symbol(), &atom.name()[..1]);
+    }
+}
+
+#[test]
+fn extended() {
+    let pdb = peptide("GAVLIGAVLI", PeptideConformation::Extended).unwrap();
+    let ca = alpha_carbons(&pdb);
+    for pair in ca.windows(2) {
+        assert!((pair[0].distance(pair[1]) - 3.8).abs() < 0.05);
+    }
+    assert!(ca[0].distance(ca[2]) > 6.0);
+    assert!(pdb.chain(0).unwrap().breaks().is_empty());
+
+    // The structure can be saved and read back
+    let path = temp_path("built_peptide.pdb");
+    save(&pdb, &path, StrictnessLevel::Loose).unwrap();
+    let read = open(&path);
+    assert_eq!(read.atom_count(), pdb.atom_count());
+    assert_eq!(
+        read.chain(0).unwrap().sequence(),
+        pdb.chain(0).unwrap().sequence()
+    );
+}
@@ -0,0 +1,51 @@
+// Test solvent accessible and buried surface areas.
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::*;
+use pdbtbx::*;
+
+#[test]
+fn isolated_atom() {
+    let atom = Atom::new(false, 1, "O", 0.0, 0.0, 0.0, 1.0, 0.0, "O", 0).unwrap();
+    let radius = 1.5 + PROBE_RADIUS; // van der Waals radius of O (Alvarez, 2013)
+    let area = sasa(&[&atom])[0];
+    assert!((area - 4.0 * std::f64::consts::PI * radius * radius).abs() < 1e-9);
+
+    // A second atom far away does not change anything, a close one does
+    let far = Atom::new(false, 2, "O", 20.0, 0.0, 0.0, 1.0, 0.0, "O", 0).unwrap();
+    let close = Atom::new(false, 3, "O", 2.0, 0.0, 0.0, 1.0, 0.0, "O", 0).unwrap();
+    assert_eq!(sasa(&[&atom, &far])[0], area);
+    assert!(sasa(&[&atom, &close])[0] < area);
+}
+
+#[test]
+fn chain_interface() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let chain = |id: &str| -> Vec<&Residue> {
+        pdb.chains()
+            .find(|c| c.id() == id)
+            .unwrap()
+            .residues()
+            .collect()
+    };
+    let (a, b) = (chain("A"), chain("B"));
+    let interface = bsa(&a, &b);
+    assert!(interface.total > 100.0, "{}", interface.total);
+    let sum: f64 = interface.residues.iter().map(|(_, area)| area).sum();
+    assert!((sum - 2.0 * interface.total).abs() < 1e-6);
+    // Buried residues are in contact with the other chain
+    let contacts = residue_distance_matrix(&a, &b);
+    for (residue, _) in &interface.residues {
+        if let Some(index) = a.iter().position(|r| std::ptr::eq(*r, *residue)) {
+            let closest = contacts
+                .row(index)
+                .unwrap()
+                .iter()
+                .copied()
+                .fold(f64::INFINITY, f64::min);
+            assert!(closest < 2.0 * (2.0 + PROBE_RADIUS));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Test that the cached atom counts stay correct when the structure is changed
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+fn check(pdb: &PDB) {
+    assert_eq!(pdb.total_atom_count(), pdb.atoms().count());
+    for model in pdb.models() {
+        assert_eq!(model.atom_count(), model.atoms().count());
+        for chain in model.chains() {
+            assert_eq!(chain.atom_count(), chain.atoms().count());
+            for residue in chain.residues() {
+                assert_eq!(residue.atom_count(), residue.atoms().count());
+            }
+        }
+    }
+}
+
+#[test]
+fn counts_follow_changes() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    check(&pdb);
+    assert_eq!(pdb.atom_count(), 1271);
+
+    pdb.remove_atoms_by(|atom| atom.element() == Some(&Element::O));
+    check(&pdb);
+
+    let atom = Atom::new(false, 9999, "X", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap();
+    pdb.residues_mut()
+        .next()
+        .unwrap()
+        .add_atom(atom.clone(), ("MET", None));
+    check(&pdb);
+
+    for conformer in pdb.conformers_mut().take(5) {
+        conformer.add_atom(atom.clone());
+    }
+    check(&pdb);
+
+    pdb[0]["A"][1].conformer_mut(0).unwrap().remove_atom(0);
+    check(&pdb);
+
+    for residue in &mut pdb[0]["A"] {
+        residue.extend(vec![atom.clone()]);
+    }
+    check(&pdb);
+
+    let copy = pdb.clone();
+    check(&copy);
+    pdb.remove_empty();
+    pdb.chain_mut(0).unwrap().remove_residue(0);
+    check(&pdb);
+    assert_ne!(copy.atom_count(), pdb.atom_count());
+}
+
+#[test]
+fn counts_follow_hierarchy_changes() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let atom = Atom::new(false, 9999, "X", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap();
+    let mut hierarchy = pdb.atoms_with_hierarchy_mut().next().unwrap();
+    let count = hierarchy.model().atom_count();
+    assert_eq!(
+        hierarchy.chain().atom_count(),
+        hierarchy.chain().atoms().count()
+    );
+
+    hierarchy.conformer_mut().add_atom(atom.clone());
+    assert_eq!(hierarchy.model().atom_count(), count + 1);
+    assert_eq!(
+        hierarchy.chain().atom_count(),
+        hierarchy.chain().atoms().count()
+    );
+    assert_eq!(
+        hierarchy.residue().atom_count(),
+        hierarchy.residue().atoms().count()
+    );
+
+    hierarchy
+        .residue_mut()
+        .add_atom(atom.clone(), ("MET", Some("B")));
+    assert_eq!(hierarchy.model().atom_count(), count + 2);
+
+    hierarchy.chain_mut().remove_residue(1);
+    assert_eq!(
+        hierarchy.model().atom_count(),
+        hierarchy.model().atoms().count()
+    );
+    check(&pdb);
+}
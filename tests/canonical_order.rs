@@ -0,0 +1,114 @@
+// Test writing PDB files with the coordinate records in the canonical wwPDB order.
+
+use pdbtbx::*;
+
+const INPUT: &str = "ATOM      1  CA  GLY B   1       0.000   0.000   0.000  1.00  0.00           C
+HETATM    2  O   HOH B 101       1.000   0.000   0.000  1.00  0.00           O
+ATOM      3  CA  GLY B   2       2.000   0.000   0.000  1.00  0.00           C
+HETATM    4 ZN    ZN B 201       3.000   0.000   0.000  1.00  0.00          ZN
+ATOM      5  CA  ALA A   1       4.000   0.000   0.000  1.00  0.00           C
+HETATM    6  CA  MSE A   2       5.000   0.000   0.000  1.00  0.00           C
+ATOM      7  CA  ALA A   3       6.000   0.000   0.000  1.00  0.00           C
+HETATM    8  O   HOH A 301       7.000   0.000   0.000  1.00  0.00           O
+HETATM    9  C1  NAG A 401       8.000   0.000   0.000  1.00  0.00           C
+HETATM   10  O   HOH C 501       9.000   0.000   0.000  1.00  0.00           O
+END
+";
+
+fn write(canonical: bool) -> Vec<String> {
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    let mut output = Vec::new();
+    WriteOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .set_canonical_order(canonical)
+        .write_raw(&pdb, std::io::BufWriter::new(&mut output));
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .filter(|line| {
+            ["ATOM", "HETATM", "TER"]
+                .iter()
+                .any(|r| line.starts_with(r))
+        })
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+/// Summarise the written records as record name, serial number, residue name, and chain
+fn summary(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            format!(
+                "{} {} {} {}",
+                line[..6].trim(),
+                line[6..11].trim(),
+                line[17..20].trim(),
+                &line[21..22]
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn canonical_layout() {
+    assert_eq!(
+        summary(&write(true)),
+        [
+            "ATOM 1 ALA A",
+            "HETATM 2 MSE A",
+            "ATOM 3 ALA A",
+            "TER 4 ALA A",
+            "ATOM 5 GLY B",
+            "ATOM 6 GLY B",
+            "TER 7 GLY B",
+            "HETATM 8 NAG A",
+            "HETATM 9 ZN B",
+            "HETATM 10 HOH A",
+            "HETATM 11 HOH B",
+            "HETATM 12 HOH C",
+        ]
+    );
+}
+
+#[test]
+fn read_back_in_canonical_order() {
+    let (original, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    let lines = write(true).join("\n");
+    let (read, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(lines.as_bytes())
+        .unwrap();
+    assert_eq!(read.atom_count(), original.atom_count());
+    for id in ["A", "B", "C"] {
+        let names = |pdb: &PDB| -> Vec<String> {
+            let mut names: Vec<String> = pdb
+                .chains()
+                .filter(|c| c.id() == id)
+                .flat_map(|c| c.residues())
+                .map(|r| r.name().unwrap().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(&read), names(&original));
+    }
+}
+
+#[test]
+fn default_keeps_order() {
+    let summary = summary(&write(false));
+    assert_eq!(summary.len(), 12);
+    assert_eq!(summary[0], "ATOM 1 GLY B");
+    assert_eq!(summary[4], "HETATM 4 ZN B");
+    assert_eq!(summary[11], "HETATM 10 HOH C");
+}
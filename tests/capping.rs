@@ -0,0 +1,106 @@
+// Test capping the termini of protein chains
+
+mod common;
+
+use common::open;
+use pdbtbx::build::{peptide, PeptideConformation, TerminalCaps};
+use pdbtbx::*;
+
+/// Find an atom by residue index and name
+fn atom<'a>(chain: &'a Chain, residue: usize, name: &str) -> &'a Atom {
+    chain
+        .residue(residue)
+        .unwrap()
+        .atoms()
+        .find(|a| a.name() == name)
+        .unwrap()
+}
+
+#[test]
+fn neutral() {
+    let mut pdb = peptide("GAPG", PeptideConformation::Extended).unwrap();
+    let chain = pdb.chain_mut(0).unwrap();
+    chain.cap_termini(TerminalCaps::Neutral).unwrap();
+    assert_eq!(
+        chain.sequence(),
+        vec!["ACE", "GLY", "ALA", "PRO", "GLY", "NME"]
+    );
+    assert_eq!(chain.residue(0).unwrap().serial_number(), 0);
+    assert_eq!(chain.residue(5).unwrap().serial_number(), 5);
+    assert!(chain.atoms().all(|a| a.name() != "OXT"));
+    assert!(chain.breaks().is_empty());
+
+    // Ideal trans peptide bonds to the caps
+    assert!((atom(chain, 0, "C").distance(atom(chain, 1, "N")) - 1.329).abs() < 0.001);
+    assert!((atom(chain, 4, "C").distance(atom(chain, 5, "N")) - 1.329).abs() < 0.001);
+    let omega = geometry::dihedral(
+        atom(chain, 0, "CH3"),
+        atom(chain, 0, "C"),
+        atom(chain, 1, "N"),
+        atom(chain, 1, "CA"),
+    );
+    assert!((omega.abs() - 180.0).abs() < 0.001);
+    let omega = geometry::dihedral(
+        atom(chain, 4, "CA"),
+        atom(chain, 4, "C"),
+        atom(chain, 5, "N"),
+        atom(chain, 5, "C"),
+    );
+    assert!((omega.abs() - 180.0).abs() < 0.001);
+
+    // The caps are bonded to the chain and nothing else, the proline ring is the only cycle
+    let atoms: Vec<&Atom> = chain.atoms().collect();
+    let bonds = chemistry::infer_bonds(&atoms);
+    assert_eq!(bonds.len(), atoms.len());
+
+    // Capping again does nothing
+    let count = chain.atom_count();
+    chain.cap_termini(TerminalCaps::Neutral).unwrap();
+    assert_eq!(chain.atom_count(), count);
+    assert_eq!(chain.residue_count(), 6);
+}
+
+#[test]
+fn charged() {
+    let mut pdb = peptide("PG", PeptideConformation::Extended).unwrap();
+    pdb.chain_mut(0)
+        .unwrap()
+        .remove_atoms_by(|a| a.name() == "OXT");
+    let chain = pdb.chain_mut(0).unwrap();
+    chain.cap_termini(TerminalCaps::Charged).unwrap();
+    assert_eq!(chain.residue_count(), 2);
+    let names: Vec<&str> = chain.residue(0).unwrap().atoms().map(Atom::name).collect();
+    assert!(names.contains(&"H2") && names.contains(&"H3") && !names.contains(&"H1"));
+    for name in ["H2", "H3"] {
+        assert!((atom(chain, 0, "N").distance(atom(chain, 0, name)) - 1.01).abs() < 0.001);
+        assert!(atom(chain, 0, "CD").distance(atom(chain, 0, name)) > 1.9);
+    }
+    let oxt = atom(chain, 1, "OXT");
+    assert!((atom(chain, 1, "C").distance(oxt) - 1.231).abs() < 0.001);
+    assert!(atom(chain, 1, "O").distance(oxt) > 2.0);
+}
+
+#[test]
+fn renumbering() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let max_serial = pdb.atoms().map(Atom::serial_number).max().unwrap();
+    let chain = pdb.chain_mut(0).unwrap();
+    chain.cap_termini(TerminalCaps::Neutral).unwrap();
+    let ids: Vec<(isize, Option<&str>)> = chain.residues().map(Residue::id).collect();
+    // The water numbered 77 moves out of the way of the NME residue
+    assert_eq!(chain.residue(0).unwrap().name(), Some("ACE"));
+    assert_eq!(ids[0], (0, None));
+    assert_eq!(chain.residue(77).unwrap().name(), Some("NME"));
+    assert_eq!(ids[77], (77, None));
+    assert_eq!(ids[78], (78, None));
+    assert_eq!(chain.residue(78).unwrap().name(), Some("HOH"));
+    let mut sorted = ids.clone();
+    sorted.dedup();
+    assert_eq!(sorted.len(), ids.len());
+    assert!(chain
+        .residue(0)
+        .unwrap()
+        .atoms()
+        .all(|a| a.serial_number() > max_serial && !a.hetero()));
+    assert!(pdb.chain(0).unwrap().breaks().is_empty());
+}
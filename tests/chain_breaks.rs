@@ -0,0 +1,28 @@
+// Test the detection of breaks in the polymers of chains
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn missing_loop() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let chain = pdb.chains().find(|c| c.id() == "A").unwrap();
+    let breaks = chain.breaks();
+    assert_eq!(breaks.len(), 1);
+    assert_eq!(breaks[0].before.serial_number(), 174);
+    assert_eq!(breaks[0].after.serial_number(), 210);
+    assert_eq!(breaks[0].missing_residues(), 35);
+    assert!(breaks[0].is_physical());
+    assert!((breaks[0].distance.unwrap() - 10.91).abs() < 0.01);
+    assert_eq!(validate_chain_breaks(&pdb).len(), 2);
+}
+
+#[test]
+fn continuous() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    assert!(pdb.chain(0).unwrap().breaks().is_empty());
+    let pdb = open("example-pdbs/nucleic.pdb");
+    assert!(pdb.chains().all(|c| c.breaks().is_empty()));
+}
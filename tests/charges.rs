@@ -0,0 +1,38 @@
+// Test reading sloppy charges and writing them in the canonical form.
+
+use pdbtbx::*;
+
+#[test]
+fn tolerant_charges() {
+    let line = |charge: &str| {
+        format!("HETATM    1 FE    FE A 101      26.266  25.413   2.842  1.00 10.38          FE{charge}\n")
+    };
+    let input: String = ["2+", "+2", "-3", "3", " -", "1-", "  "]
+        .iter()
+        .map(|c| line(c))
+        .collect();
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Medium)
+        .read_raw(input.as_bytes())
+        .unwrap();
+    let charges: Vec<_> = pdb.atoms().map(Atom::charge).collect();
+    assert_eq!(charges, [2, 2, -3, 3, -1, -1, 0]);
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|e| e.short_description() == "Atom charge not canonical")
+            .count(),
+        4
+    );
+    let written: Vec<_> = pdb.atoms().map(Atom::pdb_charge).collect();
+    assert_eq!(written, ["2+", "2+", "3-", "3+", "1-", "1-", ""]);
+
+    let errors = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .read_raw(line("X").as_bytes())
+        .unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.short_description() == "Atom charge is not correct"));
+}
@@ -0,0 +1,59 @@
+// Test the clash detection and clash score of full structures.
+
+#![cfg(feature = "rstar")]
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn protein_clash_score() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let score = clash_score(&pdb);
+    assert_eq!(score.atom_count, pdb.model(0).unwrap().atom_count());
+    assert!(score.heavy_atom_count < score.atom_count);
+    assert!(score.score() > 0.0 && score.score() < 40.0);
+    assert!(score.score_without_hydrogens() <= score.score());
+    assert_eq!(score.warnings().len(), score.clashes.len());
+    for (index, clash) in score.clashes.iter().enumerate() {
+        assert!(clash.overlap >= geometry::CLASH_MIN_OVERLAP);
+        assert!(score.clashes[index + 1..]
+            .iter()
+            .all(|other| other.overlap <= clash.overlap));
+    }
+}
+
+#[test]
+fn two_residues() {
+    let mut chain = Chain::new("A").unwrap();
+    let mut add = |serial: usize, name: &str, x: f64, residue: isize| {
+        chain.add_atom(
+            Atom::new(false, serial, name, x, 0.0, 0.0, 1.0, 0.0, &name[..1], 0).unwrap(),
+            (residue, None),
+            ("ALA", None),
+        );
+    };
+    // Bonded atoms within a residue are not clashes, close carbons of different residues are
+    add(1, "CA", 0.0, 1);
+    add(2, "CB", 1.5, 1);
+    add(3, "CB", 4.2, 2);
+    add(4, "O", 7.5, 2);
+    add(5, "N", 10.0, 3);
+    let residues: Vec<&Residue> = chain.residues().collect();
+    let clashes = geometry::clashes(&residues);
+    // The hydrogen bond acceptor pair O-N is not a clash
+    assert_eq!(clashes.len(), 1);
+    assert_eq!(clashes[0].atom_a.serial_number(), 2);
+    assert_eq!(clashes[0].atom_b.serial_number(), 3);
+    let radius = Element::C.atomic_radius().van_der_waals.unwrap();
+    assert!((clashes[0].overlap - (2.0 * radius - 2.7)).abs() < 1e-6);
+
+    let mut model = Model::new(0);
+    model.add_chain(chain);
+    let mut pdb = PDB::new();
+    pdb.add_model(model);
+    let score = clash_score(&pdb);
+    assert!((score.score() - 200.0).abs() < 1e-6);
+    assert!((score.score_without_hydrogens() - 200.0).abs() < 1e-6);
+}
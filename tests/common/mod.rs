@@ -0,0 +1,25 @@
+//! Helpers shared between the integration tests
+#![allow(dead_code)]
+
+use pdbtbx::*;
+
+/// Open the given file at the `Loose` strictness level, ignoring the warnings
+pub fn open(path: &str) -> PDB {
+    open_with_errors(path).0
+}
+
+/// Open the given file at the `Loose` strictness level, with the warnings found
+pub fn open_with_errors(path: &str) -> (PDB, Vec<PDBError>) {
+    ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .read(path)
+        .unwrap()
+}
+
+/// The path to the file with the given name in the temporary directory, to save test output to
+pub fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("pdbtbx_{name}"))
+        .to_string_lossy()
+        .into_owned()
+}
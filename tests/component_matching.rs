@@ -0,0 +1,121 @@
+// Test matching ligands against chemical component definitions.
+
+mod common;
+
+use common::open;
+use pdbtbx::chemistry::Component;
+use pdbtbx::*;
+
+/// A shortened version of the CCD entry for pyridoxal phosphate
+const PLP: &str = "data_PLP
+#
+_chem_comp.id PLP
+_chem_comp.name \"PYRIDOXAL-5'-PHOSPHATE\"
+_chem_comp.type NON-POLYMER
+#
+loop_
+_chem_comp_atom.comp_id
+_chem_comp_atom.atom_id
+_chem_comp_atom.type_symbol
+_chem_comp_atom.charge
+PLP N1  N 0
+PLP C2  C 0
+PLP C2A C 0
+PLP C3  C 0
+PLP O3  O 0
+PLP C4  C 0
+PLP C4A C 0
+PLP O4A O 0
+PLP C5  C 0
+PLP C6  C 0
+PLP C5A C 0
+PLP O4P O 0
+PLP P   P 0
+PLP O1P O 0
+PLP O2P O 0
+PLP O3P O 0
+PLP H4A H 0
+#
+loop_
+_chem_comp_bond.comp_id
+_chem_comp_bond.atom_id_1
+_chem_comp_bond.atom_id_2
+_chem_comp_bond.value_order
+PLP N1  C2  DOUB
+PLP N1  C6  SING
+PLP C2  C2A SING
+PLP C2  C3  SING
+PLP C3  O3  SING
+PLP C3  C4  DOUB
+PLP C4  C4A SING
+PLP C4  C5  SING
+PLP C4A O4A DOUB
+PLP C4A H4A SING
+PLP C5  C6  DOUB
+PLP C5  C5A SING
+PLP C5A O4P SING
+PLP O4P P   SING
+PLP P   O1P DOUB
+PLP P   O2P SING
+PLP P   O3P SING
+#
+";
+
+#[test]
+fn parse() {
+    let component = Component::from_cif(PLP).unwrap();
+    assert_eq!(component.id, "PLP");
+    assert_eq!(component.atoms.len(), 17);
+    assert_eq!(component.atoms[12].element, Some(Element::P));
+    assert_eq!(component.bonds.len(), 17);
+    assert_eq!(component.bonds[0], (0, 1, chemistry::BondOrder::Double));
+
+    let zinc = Component::from_cif(
+        "data_ZN\n_chem_comp_atom.comp_id ZN\n_chem_comp_atom.atom_id ZN\n_chem_comp_atom.type_symbol ZN\n_chem_comp_atom.charge 2\n",
+    )
+    .unwrap();
+    assert_eq!(zinc.atoms.len(), 1);
+    assert_eq!(zinc.atoms[0].charge, 2);
+    assert!(zinc.bonds.is_empty());
+
+    assert!(Component::from_cif("data_EMPTY\n_chem_comp.id EMPTY\n").is_err());
+}
+
+#[test]
+fn identity() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let component = Component::from_cif(PLP).unwrap();
+    let ligand = pdb.residues().find(|r| r.name() == Some("PLP")).unwrap();
+    let matched = ligand.match_component(&component).unwrap();
+    assert_eq!(matched.mapping.len(), 15);
+    assert_eq!(matched.renamed().count(), 0);
+    // The aldehyde oxygen is lost by binding to the protein
+    assert_eq!(matched.missing, vec!["O4A".to_string()]);
+    assert!(!matched.is_complete());
+
+    // Methionine has a sulphur, which is not in the component
+    let methionine = pdb
+        .residues()
+        .find(|r| r.name() == Some("MET") && r.atom_count() > 0)
+        .unwrap();
+    assert_eq!(methionine.match_component(&component), None);
+}
+
+#[test]
+fn renamed() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let component = Component::from_cif(PLP).unwrap();
+    let mut ligand = pdb
+        .residues()
+        .find(|r| r.name() == Some("PLP"))
+        .unwrap()
+        .clone();
+    ligand
+        .atoms_mut()
+        .find(|a| a.name() == "C2A")
+        .unwrap()
+        .set_name("CM2")
+        .unwrap();
+    let matched = ligand.match_component(&component).unwrap();
+    assert_eq!(matched.renamed().collect::<Vec<_>>(), vec![("CM2", "C2A")]);
+}
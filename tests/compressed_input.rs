@@ -0,0 +1,48 @@
+// Test reading gzipped files and streams without a recognisable extension.
+#![cfg(feature = "compression")]
+
+mod common;
+
+use common::{open, temp_path};
+use pdbtbx::*;
+
+fn compressed(name: &str) -> (PDB, String) {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let path = temp_path(&format!("{name}.pdb.gz"));
+    save_gz(&pdb, &path, StrictnessLevel::Loose, None).unwrap();
+    (pdb, path)
+}
+
+#[test]
+fn extension() {
+    let (pdb, path) = compressed("compressed_extension");
+    let read = open(&path);
+    assert_eq!(read.atom_count(), pdb.atom_count());
+}
+
+#[test]
+fn forced_format() {
+    let (pdb, path) = compressed("compressed_forced");
+    let renamed = temp_path("compressed_forced.download");
+    std::fs::rename(&path, &renamed).unwrap();
+    let (read, _) = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .set_format(Format::Pdb)
+        .set_decompress(true)
+        .read(&renamed)
+        .unwrap();
+    assert_eq!(read.atom_count(), pdb.atom_count());
+}
+
+#[test]
+fn stream() {
+    let (pdb, path) = compressed("compressed_stream");
+    let bytes = std::fs::read(path).unwrap();
+    let (read, _) = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .set_format(Format::Pdb)
+        .set_decompress(true)
+        .read_raw(bytes.as_slice())
+        .unwrap();
+    assert_eq!(read.atom_count(), pdb.atom_count());
+}
@@ -0,0 +1,93 @@
+// Test parsing the connectivity given by CONECT records
+
+mod common;
+
+use common::{open, open_with_errors};
+use pdbtbx::*;
+
+#[test]
+fn ligand_connectivity() {
+    let (pdb, errors) = open_with_errors("example-pdbs/1yyf.pdb");
+    assert!(!errors
+        .iter()
+        .any(|e| e.short_description() == "Could not find a bond partner"));
+    let phosphorus = pdb.atoms().find(|a| a.serial_number() == 9166).unwrap();
+    let mut bonded: Vec<usize> = pdb
+        .bonded_atoms(phosphorus)
+        .map(|(atom, bond)| {
+            assert_eq!(bond, Bond::Covalent);
+            atom.serial_number()
+        })
+        .collect();
+    bonded.sort_unstable();
+    assert_eq!(bonded, [9167, 9168, 9169, 9173]);
+    // Every bond is listed for both atoms, but only stored once
+    let bridge = pdb.atoms().find(|a| a.serial_number() == 9173).unwrap();
+    assert_eq!(pdb.bonded_atoms(bridge).count(), 2);
+}
+
+#[test]
+fn missing_partner() {
+    let input = "ATOM      1  N   GLY A   1      26.266  25.413   2.842  1.00 20.00           N
+ATOM      2  CA  GLY A   1      27.475  26.142   3.271  1.00 20.00           C
+CONECT    1    2
+CONECT    2    1    3
+END
+";
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(input.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.bonds().count(), 1);
+    let error = errors
+        .iter()
+        .find(|e| e.short_description() == "Could not find a bond partner")
+        .unwrap();
+    assert_eq!(error.level(), ErrorLevel::LooseWarning);
+    assert!(error.long_description().contains("Atom 3"));
+    assert!(ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Medium)
+        .read_raw(input.as_bytes())
+        .is_err());
+}
+
+#[test]
+fn save_bonds() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let pairs = |pdb: &PDB| {
+        let mut pairs: Vec<(usize, usize)> = pdb
+            .bonds()
+            .map(|(a, b, _)| {
+                let (a, b) = (a.serial_number(), b.serial_number());
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        pairs.sort_unstable();
+        pairs
+    };
+    assert_eq!(pdb.bonds().count(), 58);
+
+    let mut output = Vec::new();
+    save_pdb_raw(
+        &pdb,
+        std::io::BufWriter::new(&mut output),
+        StrictnessLevel::Medium,
+    );
+    let output = String::from_utf8(output).unwrap();
+    let conect = output.lines().filter(|l| l.starts_with("CONECT")).count();
+    assert!(conect > 0);
+    let master = output.lines().find(|l| l.starts_with("MASTER")).unwrap();
+    assert_eq!(master[60..65].trim().parse::<usize>().unwrap(), conect);
+
+    let (saved, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(output.as_bytes())
+        .unwrap();
+    assert!(!errors
+        .iter()
+        .any(|e| e.short_description() == "Could not find a bond partner"));
+    assert_eq!(pairs(&saved), pairs(&pdb));
+}
@@ -0,0 +1,25 @@
+// Test converting between file formats.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn convert_pdb_to_cif() {
+    let output = std::env::temp_dir().join("pdbtbx_convert_test.cif");
+    let output = output.to_string_lossy();
+    convert(
+        "example-pdbs/1ubq.pdb",
+        &output,
+        ReadOptions::new().set_level(StrictnessLevel::Loose),
+    )
+    .unwrap();
+    let pdb = open(&output);
+    assert_eq!(pdb.atom_count(), 1271);
+
+    let errors = convert("example-pdbs/1ubq.pdb", "1ubq.txt", &ReadOptions::new()).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.short_description() == "Incorrect extension"));
+}
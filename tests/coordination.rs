@@ -0,0 +1,34 @@
+// Test analysing the coordination spheres of metals.
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::CoordinationGeometry;
+use pdbtbx::*;
+
+#[test]
+fn calcium_sites() {
+    let pdb = open("example-pdbs/3nig.pdb");
+    let calcium: Vec<&Atom> = pdb
+        .atoms()
+        .filter(|a| a.element() == Some(&Element::Ca))
+        .collect();
+    assert!(!calcium.is_empty());
+    for metal in calcium {
+        let sphere = pdb.coordination_sphere(metal, 3.0);
+        // All coordinating atoms given in the LINK records are found
+        let linked = pdb
+            .bonds()
+            .filter(|(a, b, bond)| *bond == Bond::MetalCoordination && (a == &metal || b == &metal))
+            .count();
+        assert!(sphere.coordination_number() >= linked);
+        assert!(sphere.ligands.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(sphere
+            .ligands
+            .iter()
+            .all(|(atom, distance)| { *distance <= 3.0 && atom.element() != Some(&Element::C) }));
+        if sphere.geometry == CoordinationGeometry::Irregular {
+            assert!(sphere.angle_deviation.is_none());
+        }
+    }
+}
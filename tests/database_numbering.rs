@@ -0,0 +1,57 @@
+// Test renumbering residues to the numbering of the referenced database sequence.
+
+mod common;
+
+use common::open;
+
+#[test]
+fn renumber() {
+    let mut pdb = open("example-pdbs/1kmk.pdb");
+    let chain = pdb.chain_mut(0).unwrap();
+    let first = chain.residue(0).unwrap().serial_number();
+    let last = chain.residues().last().unwrap().serial_number();
+    // Shifting by 100 would clash with the ligands and waters (numbered from 500)
+    chain
+        .database_reference_mut()
+        .unwrap()
+        .database_position
+        .start = 101;
+    let before = chain.clone();
+    assert!(chain.renumber_to_database().is_err());
+    assert_eq!(*chain, before);
+
+    // Pretend the construct starts at position 1001 of the database sequence
+    chain
+        .database_reference_mut()
+        .unwrap()
+        .database_position
+        .start = 1001;
+    chain.renumber_to_database().unwrap();
+
+    assert_eq!(chain.residue(0).unwrap().serial_number(), first + 1000);
+    let modified = chain.residues().find(|r| r.name() == Some("CSZ")).unwrap();
+    assert_eq!(modified.serial_number(), 1364);
+    // Waters after the sequence are not part of the reference
+    assert_eq!(chain.residues().last().unwrap().serial_number(), last);
+
+    let reference = chain.database_reference().unwrap();
+    assert_eq!(reference.pdb_position.start, 1001);
+    assert_eq!(reference.pdb_position.end, 1406);
+    assert_eq!(reference.differences[0].residue.1, 1364);
+    assert_eq!(
+        reference.differences[0].database_residue,
+        Some(("CYS".to_string(), 364))
+    );
+
+    // Already in the database numbering, so nothing changes
+    let before = chain.clone();
+    chain.renumber_to_database().unwrap();
+    assert_eq!(*chain, before);
+}
+
+#[test]
+fn missing_reference() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let mut chain = pdb.chain(0).unwrap().clone();
+    assert!(chain.renumber_to_database().is_err());
+}
@@ -0,0 +1,80 @@
+// Test voxelizing structures onto density grids and saving them as CCP4/MRC maps
+
+mod common;
+
+use common::{open, temp_path};
+use pdbtbx::geometry::AtomKernel;
+use pdbtbx::*;
+
+fn word(bytes: &[u8], index: usize) -> [u8; 4] {
+    bytes[index * 4..index * 4 + 4].try_into().unwrap()
+}
+
+#[test]
+fn electron_counts() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let electrons: f64 = pdb
+        .atoms()
+        .map(|a| (a.element().unwrap().atomic_number() as f64 - a.charge() as f64) * a.occupancy())
+        .sum();
+    let grid = pdb.to_density_grid(0.5, AtomKernel::Gaussian).unwrap();
+    let total: f64 = grid.as_slice().iter().sum();
+    assert!((total - electrons).abs() / electrons < 0.01);
+
+    // The grid covers all atoms and is aligned to the spacing
+    let ((min_x, _, _), (max_x, _, _)) = pdb.bounding_box();
+    let origin = grid.origin();
+    let (nx, ny, nz) = grid.dimensions();
+    assert!(origin.0 < min_x && grid.position(nx - 1, 0, 0).0 > max_x);
+    assert!(((origin.0 / 0.5) - (origin.0 / 0.5).round()).abs() < 1e-9);
+    assert_eq!(grid.as_slice().len(), nx * ny * nz);
+    assert_eq!(grid.get(nx, 0, 0), None);
+
+    // The density is highest near atoms
+    let sulfur = pdb.atoms().find(|a| a.name() == "SD").unwrap();
+    assert!(grid.value_at(sulfur.pos()).unwrap() > 0.2);
+    assert_eq!(grid.value_at(origin).unwrap(), 0.0);
+    assert_eq!(grid.value_at((origin.0 - 10.0, origin.1, origin.2)), None);
+
+    let mask = grid.mask(0.1);
+    assert!(mask.as_slice().iter().all(|v| *v == 0.0 || *v == 1.0));
+    assert_eq!(mask.value_at(sulfur.pos()), Some(1.0));
+}
+
+#[test]
+fn atom_counts() {
+    let atom = Atom::new(false, 1, "CA", 1.2, -3.4, 5.6, 1.0, 20.0, "C", 0).unwrap();
+    let grid = geometry::density_grid(&[&atom], 1.0, AtomKernel::Count).unwrap();
+    assert_eq!(grid.as_slice().iter().sum::<f64>(), 1.0);
+    assert_eq!(grid.value_at(atom.pos()), Some(1.0));
+    assert_eq!(grid.dimensions(), (4, 4, 4));
+
+    assert!(geometry::density_grid(&[], 1.0, AtomKernel::Count).is_none());
+    assert!(geometry::density_grid(&[&atom], 0.0, AtomKernel::Count).is_none());
+}
+
+#[test]
+fn save_ccp4_map() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let grid = pdb.to_density_grid(2.0, AtomKernel::Gaussian).unwrap();
+    let path = temp_path("1ubq.map");
+    save_map(&grid, &path).unwrap();
+
+    let bytes = std::fs::read(&path).unwrap();
+    let (nx, ny, nz) = grid.dimensions();
+    assert_eq!(bytes.len(), 1024 + 4 * nx * ny * nz);
+    let int = |index| i32::from_le_bytes(word(&bytes, index));
+    let float = |index| f32::from_le_bytes(word(&bytes, index));
+    assert_eq!([int(0), int(1), int(2)], [nx as i32, ny as i32, nz as i32]);
+    assert_eq!(int(3), 2);
+    assert_eq!(int(4) as f64 * 2.0, grid.origin().0);
+    assert_eq!(float(10), nx as f32 * 2.0);
+    assert_eq!(&word(&bytes, 52), b"MAP ");
+    assert!(
+        (float(21) as f64 - grid.as_slice().iter().sum::<f64>() / (nx * ny * nz) as f64).abs()
+            < 1e-4
+    );
+    assert_eq!(float(256), grid.get(0, 0, 0).unwrap() as f32);
+
+    assert!(save_map(&grid, temp_path("missing/1ubq.map")).is_err());
+}
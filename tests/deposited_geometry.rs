@@ -0,0 +1,72 @@
+// Test parsing the geometry outliers deposited in the REMARK 500 records.
+
+mod common;
+
+use common::open;
+
+#[test]
+fn close_contacts() {
+    let geometry = open("example-pdbs/1kmk.pdb").deposited_geometry();
+    assert_eq!(geometry.close_contacts.len(), 7);
+    let first = &geometry.close_contacts[0];
+    assert_eq!(first.atoms.0.name, "NZ");
+    assert_eq!(first.atoms.0.residue.name, "LYS");
+    assert_eq!(first.atoms.0.residue.chain, "A");
+    assert_eq!(first.atoms.0.residue.id(), (226, None));
+    assert_eq!(first.atoms.1.name, "N");
+    assert_eq!(first.atoms.1.residue.name, "SEC");
+    assert_eq!(first.atoms.1.residue.serial_number, 502);
+    assert_eq!(first.symmetry_operation, None);
+    assert_eq!(first.distance, 2.12);
+    let symmetry: Vec<_> = geometry
+        .close_contacts
+        .iter()
+        .filter_map(|contact| contact.symmetry_operation.as_deref())
+        .collect();
+    assert_eq!(symmetry, ["7555"; 4]);
+    assert_eq!(geometry.close_contacts[3].distance, 1.42);
+}
+
+#[test]
+fn angles_and_torsions() {
+    let geometry = open("example-pdbs/1kmk.pdb").deposited_geometry();
+    assert!(geometry.bond_deviations.is_empty());
+    assert_eq!(geometry.angle_deviations.len(), 30);
+    let angle = &geometry.angle_deviations[0];
+    assert_eq!(angle.residue.name, "ARG");
+    assert_eq!(angle.residue.serial_number, 9);
+    assert_eq!(angle.residue.model, None);
+    assert_eq!(angle.atoms, ["NE", "CZ", "NH1"]);
+    assert_eq!(angle.deviation, 5.2);
+    assert_eq!(geometry.angle_deviations[2].deviation, -14.1);
+    assert_eq!(geometry.torsion_outliers.len(), 8);
+    let torsion = &geometry.torsion_outliers[0];
+    assert_eq!(torsion.residue.name, "HIS");
+    assert_eq!(torsion.residue.serial_number, 55);
+    assert_eq!(torsion.psi, 49.70);
+    assert_eq!(torsion.phi, -98.63);
+}
+
+#[test]
+fn bond_lengths() {
+    let geometry = open("example-pdbs/1yyf.pdb").deposited_geometry();
+    assert!(!geometry.bond_deviations.is_empty());
+    let bond = &geometry.bond_deviations[0];
+    assert_eq!(bond.atoms.0.residue, bond.atoms.1.residue);
+    assert_eq!(bond.atoms.0.residue.name, "MET");
+    assert_eq!(bond.atoms.0.residue.serial_number, 1);
+    assert_eq!(
+        (bond.atoms.0.name.as_str(), bond.atoms.1.name.as_str()),
+        ("CG", "SD")
+    );
+    assert_eq!(bond.deviation, 0.211);
+    assert_eq!(geometry.angle_deviations.len(), 50);
+    assert_eq!(geometry.torsion_outliers.len(), 264);
+}
+
+#[test]
+fn no_remarks() {
+    assert!(open("example-pdbs/1ubq.pdb")
+        .deposited_geometry()
+        .is_empty());
+}
@@ -0,0 +1,37 @@
+// Test the depth of atoms and residues below the solvent accessible surface
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn single_atom() {
+    let atom = Atom::new(false, 1, "C", 5.0, -3.0, 2.0, 1.0, 0.0, "C", 0).unwrap();
+    let depths = geometry::depth(&[&atom]);
+    let radius = Element::C.atomic_radius().van_der_waals.unwrap();
+    assert_eq!(depths.len(), 1);
+    assert!((depths[0] - radius).abs() < 1e-9);
+    assert!(geometry::depth(&[]).is_empty());
+}
+
+#[test]
+fn ubiquitin_core() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let residues: Vec<&Residue> = pdb.chain(0).unwrap().residues().collect();
+    let depths = geometry::residue_depth(&residues);
+    assert_eq!(depths.len(), residues.len());
+    let depth = |serial| {
+        depths
+            .iter()
+            .find(|(residue, _)| residue.serial_number() == serial)
+            .unwrap()
+            .1
+    };
+    // Val 26 and Leu 67 are buried in the hydrophobic core of ubiquitin
+    assert!(depth(26) > 5.0);
+    assert!(depth(67) > 5.0);
+    // Lys 48 and the C terminal Gly 76 are on the surface
+    assert!(depth(48) < 2.0);
+    assert!(depth(76) < 2.0);
+}
@@ -0,0 +1,69 @@
+// Test the center of mass and dipole moment calculations
+
+use pdbtbx::*;
+
+#[test]
+fn center_of_mass() {
+    let oxygen = Atom::new(false, 1, "O", 0.0, 0.0, 0.0, 1.0, 0.0, "O", 0).unwrap();
+    let hydrogen = Atom::new(false, 2, "H", 1.0, 0.0, 0.0, 1.0, 0.0, "H", 0).unwrap();
+    let (x, y, z) = geometry::center_of_mass(&[&oxygen, &hydrogen]).unwrap();
+    let (o, h) = (Element::O.weight().unwrap(), Element::H.weight().unwrap());
+    assert!((x - h / (o + h)).abs() < 1e-9);
+    assert!(y.abs() < 1e-9 && z.abs() < 1e-9);
+    assert_eq!(geometry::center_of_mass(&[]), None);
+}
+
+#[test]
+fn neutral_dipole() {
+    // A TIP3P water molecule, the dipole points from the oxygen to the hydrogens
+    let water = |offset: f64| {
+        [
+            ("O", "O", 0.0, 0.0, -0.834),
+            ("H1", "H", 0.7570, 0.5859, 0.417),
+            ("H2", "H", -0.7570, 0.5859, 0.417),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, (name, element, x, y, charge))| {
+            Atom::new(
+                false,
+                index,
+                *name,
+                offset + x,
+                *y,
+                0.0,
+                *charge,
+                0.0,
+                *element,
+                0,
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>()
+    };
+    // The charges are stored in the occupancy, like for PQR files
+    let dipole = |atoms: &[Atom]| {
+        let atoms: Vec<&Atom> = atoms.iter().collect();
+        geometry::dipole_moment(&atoms, Atom::occupancy)
+    };
+    let here = dipole(&water(0.0));
+    assert!(here.vector.0.abs() < 1e-9 && here.vector.2.abs() < 1e-9);
+    assert!((here.magnitude - here.vector.1).abs() < 1e-9);
+    // The dipole moment of the TIP3P model is 2.35 D
+    assert!((here.debye() - 2.35).abs() < 0.01);
+
+    // The dipole moment of a neutral selection does not depend on its position
+    let moved = dipole(&water(25.0));
+    assert!((moved.magnitude - here.magnitude).abs() < 1e-9);
+}
+
+#[test]
+fn charged_dipole() {
+    // A charged selection is taken relative to its center of mass
+    let heavy = Atom::new(false, 1, "CL", 0.0, 0.0, 0.0, 1.0, 0.0, "CL", -1).unwrap();
+    let light = Atom::new(false, 2, "NA", 0.0, 0.0, 4.0, 1.0, 0.0, "NA", 0).unwrap();
+    let center = geometry::center_of_mass(&[&heavy, &light]).unwrap();
+    let dipole = geometry::dipole_moment(&[&heavy, &light], |atom| atom.charge() as f64);
+    assert!((dipole.vector.2 - center.2).abs() < 1e-9);
+    assert!((dipole.magnitude - center.2).abs() < 1e-9);
+}
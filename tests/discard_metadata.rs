@@ -0,0 +1,31 @@
+// Test skipping the header metadata while reading files.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn discard_metadata() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    assert!(pdb.remark_count() > 0);
+
+    let (pdb, errors) = ReadOptions::default()
+        .set_level(StrictnessLevel::Loose)
+        .set_discard_metadata(true)
+        .read("example-pdbs/1ubq.pdb")
+        .unwrap();
+    assert_eq!(pdb.remark_count(), 0);
+    assert_eq!(pdb.identifier, None);
+    assert_eq!(pdb.atom_count(), 1271);
+    assert!(!errors
+        .iter()
+        .any(|e| e.long_description().contains("number of REMARKS")));
+
+    let (pdb, _errors) = ReadOptions::default()
+        .set_level(StrictnessLevel::Loose)
+        .set_discard_metadata(true)
+        .read("example-pdbs/1ubq.cif")
+        .unwrap();
+    assert_eq!(pdb.identifier, None);
+}
@@ -0,0 +1,34 @@
+// Test pairwise distance matrices.
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::*;
+use pdbtbx::*;
+
+#[test]
+fn residue_contacts() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let residues: Vec<&Residue> = pdb.residues().take(10).collect();
+    let matrix = residue_distance_matrix(&residues, &residues);
+    assert_eq!((matrix.rows(), matrix.columns()), (10, 10));
+    for i in 0..10 {
+        assert_eq!(matrix.get(i, i), Some(0.0));
+        // Consecutive residues are bonded
+        if i < 9 {
+            assert!(matrix.get(i, i + 1).unwrap() < 1.5);
+        }
+        for j in 0..10 {
+            assert_eq!(matrix.get(i, j), matrix.get(j, i));
+        }
+    }
+    #[cfg(feature = "rayon")]
+    assert_eq!(par_residue_distance_matrix(&residues, &residues), matrix);
+
+    let atoms: Vec<&Atom> = residues[0].atoms().collect();
+    let condensed = condensed_distance_matrix(&atoms);
+    let dense = distance_matrix(&atoms, &atoms);
+    assert_eq!(condensed.len(), atoms.len() * (atoms.len() - 1) / 2);
+    assert_eq!(condensed[0], dense.get(0, 1).unwrap());
+    assert_eq!(condensed[atoms.len() - 1], dense.get(1, 2).unwrap());
+}
@@ -0,0 +1,54 @@
+// Test removing parts of structures and renumbering them afterwards
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn remove_atoms_keeps_bonds_consistent() {
+    let mut pdb = open("example-pdbs/1yyf.pdb");
+    let bonds = pdb.bonds().count();
+    assert!(bonds > 0);
+    pdb.remove_atoms_by(|a| a.serial_number() == 9166);
+    // All bonds to the removed phosphorus are gone, and the other bonds can still be resolved
+    assert_eq!(pdb.bonds().count(), bonds - 4);
+    pdb.remove_residues_by(|r| r.atoms().any(Atom::hetero));
+    assert!(pdb.bonds().all(|(a, b, _)| !a.hetero() && !b.hetero()));
+
+    let mut pdb = open("example-pdbs/1yyf.pdb");
+    for chain in pdb.chains_mut() {
+        chain.remove_atoms_by(Atom::hetero);
+    }
+    pdb.remove_dangling_bonds();
+    assert!(pdb.bonds().all(|(a, b, _)| !a.hetero() && !b.hetero()));
+}
+
+#[test]
+fn remove_residue_by_serial_number() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain_mut(0).unwrap();
+    let count = chain.residue_count();
+    assert!(chain.remove_residue_by_serial_number(10));
+    assert!(!chain.remove_residue_by_serial_number(10));
+    assert_eq!(chain.residue_count(), count - 1);
+}
+
+#[test]
+fn renumber_sequential() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    pdb.chain_mut(0).unwrap().remove_residue_by_serial_number(1);
+    pdb.renumber_sequential();
+    let chain = pdb.chain(0).unwrap();
+    assert_eq!(chain.id(), "A");
+    assert_eq!(chain.residue(0).unwrap().serial_number(), 1);
+    assert!(chain
+        .residues()
+        .zip(1..)
+        .all(|(r, serial)| r.serial_number() == serial && r.insertion_code().is_none()));
+    assert_eq!(pdb.atom(0).unwrap().serial_number(), 1);
+    assert_eq!(
+        pdb.atoms().last().unwrap().serial_number(),
+        pdb.atom_count()
+    );
+}
@@ -0,0 +1,37 @@
+// Test inferring the element of atoms without an element column.
+
+use pdbtbx::*;
+
+#[test]
+fn infer_elements() {
+    let input = "ATOM      1  CA  MET A   1      26.266  25.413   2.842  1.00 10.38
+ATOM      2 HG12 VAL A   2      26.266  25.413   2.842  1.00 10.38
+ATOM      3  OXT VAL A   2      26.266  25.413   2.842  1.00 10.38                  
+HETATM    4 CA    CA A 101      26.266  25.413   2.842  1.00 10.38
+HETATM    5 FE1  SF4 A 102      26.266  25.413   2.842  1.00 10.38
+END
+";
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Medium)
+        .read_raw(input.as_bytes())
+        .unwrap();
+    let elements: Vec<_> = pdb.atoms().map(|a| a.element().copied()).collect();
+    assert_eq!(
+        elements,
+        [
+            Some(Element::C),
+            Some(Element::H),
+            Some(Element::O),
+            Some(Element::Ca),
+            Some(Element::Fe)
+        ]
+    );
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|e| e.short_description() == "Element inferred")
+            .count(),
+        5
+    );
+}
@@ -0,0 +1,76 @@
+// Test reading, writing, and detecting polymer entities.
+
+mod common;
+
+use common::{open, temp_path};
+use pdbtbx::*;
+
+#[test]
+fn pdb_compound() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    assert_eq!(pdb.entity_count(), 2);
+    let first = pdb.entity(1).unwrap();
+    assert_eq!(
+        first.description.as_deref(),
+        Some("ATP-DEPENDENT HSL PROTEASE ATP-BINDING SUBUNIT HSLU")
+    );
+    assert_eq!(first.chains, vec!["A".to_string(), "B".to_string()]);
+    assert_eq!(first.sequence.len(), 443);
+    assert_eq!(first.sequence[0], "MET");
+    assert_eq!(pdb.chain_entity("C").unwrap().id, 2);
+    assert_eq!(pdb.entity(2).unwrap().chains, vec!["D", "C"]);
+    let chains: Vec<&str> = pdb.entity_chains(2).map(Chain::id).collect();
+    assert_eq!(chains, vec!["D", "C"]);
+}
+
+#[test]
+fn mmcif_entity_poly() {
+    let pdb = open("example-pdbs/1yyf.cif");
+    assert_eq!(pdb.entity_count(), 2);
+    let first = pdb.entity(1).unwrap();
+    assert_eq!(
+        first.description.as_deref(),
+        Some("ATP-dependent hsl protease ATP-binding subunit hslU")
+    );
+    assert_eq!(first.chains, vec!["A".to_string(), "B".to_string()]);
+    assert_eq!(first.sequence.len(), 443);
+}
+
+#[test]
+fn mmcif_round_trip() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let path = temp_path("1yyf_entities.cif");
+    save(&pdb, &path, StrictnessLevel::Loose).unwrap();
+    let saved = open(&path);
+    assert_eq!(
+        pdb.entities().collect::<Vec<_>>(),
+        saved.entities().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn detect() {
+    let mut pdb = open("example-pdbs/1yyf.pdb");
+    pdb.detect_entities(0.95);
+    assert_eq!(pdb.entity_count(), 2);
+    assert_eq!(pdb.chain_entity("A"), pdb.chain_entity("B"));
+    assert_eq!(pdb.chain_entity("C"), pdb.chain_entity("D"));
+    assert_ne!(pdb.chain_entity("A"), pdb.chain_entity("C"));
+    assert!(pdb.entity(1).unwrap().sequence.is_empty());
+}
+
+#[test]
+fn copies_are_added() {
+    let mut pdb = open("example-pdbs/1yyf.pdb");
+    pdb.remove_models_by(|model| model.serial_number() > 1);
+    let mut second = pdb.model(0).unwrap().clone();
+    second.set_serial_number(2);
+    pdb.add_model(second);
+    let mapping = pdb.flatten_models();
+    let (_, original, copy) = mapping.last().unwrap();
+    assert_eq!(
+        pdb.chain_entity(original).unwrap().id,
+        pdb.chain_entity(copy).unwrap().id
+    );
+    assert_eq!(pdb.entity(1).unwrap().chains.len(), 4);
+}
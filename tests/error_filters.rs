@@ -0,0 +1,78 @@
+// Test suppressing and downgrading errors while reading.
+
+use pdbtbx::*;
+
+const INPUT: &str =
+    "ATOM      1  N   MET A   1      27.340  24.430   2.614  1.00  9.67           N X
+ATOM      2  CA  MET A   1      26.266  25.413   2.842  1.00 10.38           C
+MASTER        0    0    0    0    0    0    0    0    5    0    0    0
+END
+";
+
+#[test]
+fn downgrade_and_suppress() {
+    let errors = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(INPUT.as_bytes())
+        .unwrap_err();
+    assert_eq!(errors.len(), 2);
+
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .downgrade_errors(
+            ErrorFilter::title("Atom charge is not correct"),
+            ErrorLevel::LooseWarning,
+        )
+        .suppress_errors(ErrorFilter::record("MASTER"))
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.atom_count(), 2);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].level(), ErrorLevel::LooseWarning);
+    assert_eq!(errors[0].short_description(), "Atom charge is not correct");
+}
+
+#[test]
+fn filter_on_level() {
+    let (_, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .suppress_errors(ErrorFilter::record("ATOM").with_level(ErrorLevel::InvalidatingError))
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].short_description(), "MASTER checksum failed");
+}
+
+#[test]
+fn filter_errors_found_after_parsing() {
+    // The SEQRES records are only validated after all lines are parsed
+    let title = "SEQRES residue total invalid";
+    let read = |options: &mut ReadOptions| {
+        options
+            .set_level(StrictnessLevel::Medium)
+            .read("example-pdbs/1kmk.pdb")
+    };
+    let errors = read(&mut ReadOptions::new()).unwrap_err();
+    assert!(errors.iter().all(|e| e.short_description() == title));
+
+    let (_, errors) = read(ReadOptions::new().suppress_errors(ErrorFilter::title(title))).unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+
+    let (_, errors) = read(
+        ReadOptions::new().downgrade_errors(ErrorFilter::title(title), ErrorLevel::GeneralWarning),
+    )
+    .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].level(), ErrorLevel::GeneralWarning);
+
+    // For mmCIF files all errors are found after parsing
+    let (_, errors) = ReadOptions::new()
+        .set_level(StrictnessLevel::Medium)
+        .suppress_errors(ErrorFilter::title("Invalid Model"))
+        .read("example-pdbs/pTLS-6484.cif")
+        .unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+}
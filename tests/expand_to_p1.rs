@@ -0,0 +1,54 @@
+// Test expanding the asymmetric unit to the full unit cell.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn expand() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let atoms = pdb.atom_count();
+    let operators = pdb.symmetry.as_ref().unwrap().transformations().len();
+    assert_eq!(operators, 4); // P 21 21 21
+
+    pdb.expand_to_p1().unwrap();
+    assert_eq!(pdb.symmetry, Symmetry::new("P 1"));
+    assert_eq!(pdb.atom_count(), atoms * operators);
+    assert_eq!(pdb.chain_count(), operators);
+    let ids: Vec<&str> = pdb.chains().map(Chain::id).collect();
+    assert_eq!(ids, vec!["A", "B", "C", "D"]);
+
+    // The centre of every chain lies in the unit cell
+    let fractionalization = pdb.unit_cell.as_ref().unwrap().fractionalization_matrix();
+    for chain in pdb.chains() {
+        let count = chain.atom_count() as f64;
+        let centre = chain.atoms().fold((0.0, 0.0, 0.0), |sum, atom| {
+            let p = fractionalization.apply(atom.pos());
+            (
+                sum.0 + p.0 / count,
+                sum.1 + p.1 / count,
+                sum.2 + p.2 / count,
+            )
+        });
+        for value in [centre.0, centre.1, centre.2] {
+            assert!((0.0..1.0).contains(&value), "{value}");
+        }
+    }
+
+    // The copies have the same internal geometry
+    let first: Vec<&Atom> = pdb.chain(0).unwrap().atoms().take(10).collect();
+    let second: Vec<&Atom> = pdb.chain(1).unwrap().atoms().take(10).collect();
+    for i in 1..10 {
+        assert!((first[0].distance(first[i]) - second[0].distance(second[i])).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn missing_cell() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    pdb.unit_cell = None;
+    let before = pdb.clone();
+    assert!(pdb.expand_to_p1().is_err());
+    assert_eq!(pdb, before);
+}
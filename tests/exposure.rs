@@ -0,0 +1,46 @@
+// Test the relative solvent accessibility and buried/exposed classification of residues
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn classify() {
+    let threshold = geometry::BURIED_MAX_RELATIVE_SASA;
+    for (relative, expected) in [
+        (0.0, geometry::Exposure::Buried),
+        (0.24, geometry::Exposure::Buried),
+        (0.25, geometry::Exposure::Exposed),
+        (1.1, geometry::Exposure::Exposed),
+    ] {
+        assert_eq!(geometry::Exposure::classify(relative, threshold), expected);
+    }
+}
+
+#[test]
+fn ubiquitin() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let residues: Vec<&Residue> = pdb.chain(0).unwrap().residues().collect();
+    let classified = geometry::exposure(&residues, geometry::BURIED_MAX_RELATIVE_SASA);
+    // The waters are left out
+    assert_eq!(classified.len(), 76);
+    let find = |serial| {
+        classified
+            .iter()
+            .find(|(residue, _, _)| residue.serial_number() == serial)
+            .unwrap()
+    };
+    // The hydrophobic core of ubiquitin
+    for serial in [3, 5, 26, 30, 43, 56, 61, 67] {
+        let (_, relative, exposure) = find(serial);
+        assert!(*relative < 0.01);
+        assert_eq!(*exposure, geometry::Exposure::Buried);
+    }
+    // Leu 8 and Lys 48 on the surface and the flexible C terminal tail
+    for serial in [8, 48, 74, 76] {
+        let (_, relative, exposure) = find(serial);
+        assert!(*relative > 0.3);
+        assert_eq!(*exposure, geometry::Exposure::Exposed);
+    }
+}
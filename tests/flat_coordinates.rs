@@ -0,0 +1,41 @@
+// Test getting and setting coordinates through flat buffers.
+
+mod common;
+
+use common::open;
+
+#[test]
+fn copy_and_apply_coordinates() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let model = pdb.model_mut(0).unwrap();
+    let mut buffer = vec![0.0; model.atom_count() * 3];
+    model.copy_coordinates_into(&mut buffer).unwrap();
+    let atom = model.atom(5).unwrap();
+    assert_eq!(&buffer[15..18], &[atom.x(), atom.y(), atom.z()]);
+
+    // Translate everything and apply
+    for value in &mut buffer {
+        *value += 1.0;
+    }
+    let old = model.atom(5).unwrap().pos();
+    model.apply_coordinates(&buffer).unwrap();
+    assert_eq!(
+        model.atom(5).unwrap().pos(),
+        (old.0 + 1.0, old.1 + 1.0, old.2 + 1.0)
+    );
+}
+
+#[test]
+fn length_and_value_checks() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let model = pdb.model_mut(0).unwrap();
+    let mut short = vec![0.0; 3];
+    assert!(model.copy_coordinates_into(&mut short).is_err());
+    assert!(model.apply_coordinates(&short).is_err());
+
+    let first = model.atom(0).unwrap().pos();
+    let mut buffer = vec![0.0; model.atom_count() * 3];
+    buffer[100] = f64::NAN;
+    assert!(model.apply_coordinates(&buffer).is_err());
+    assert_eq!(model.atom(0).unwrap().pos(), first);
+}
@@ -0,0 +1,44 @@
+// Test flattening multi-model ensembles into a single model.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn flatten() {
+    let mut pdb = open("example-pdbs/models.pdb");
+    let models = pdb.model_count();
+    let atoms: usize = pdb.models().map(Model::atom_count).sum();
+    assert!(models > 1);
+
+    let mapping = pdb.flatten_models();
+    assert_eq!(pdb.model_count(), 1);
+    assert_eq!(pdb.atom_count(), atoms);
+    assert_eq!(pdb.chain_count(), models);
+    assert_eq!(mapping.len(), models);
+    assert_eq!(mapping[0].1, mapping[0].2);
+    let ids: Vec<&str> = pdb.chains().map(Chain::id).collect();
+    assert_eq!(ids, vec!["A", "B", "C", "D", "E", "F"]);
+    assert!(mapping.iter().all(|(_, original, _)| original == "A"));
+}
+
+#[test]
+fn many_chains() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let model = pdb.model(0).unwrap().clone();
+    for serial in 2..=70 {
+        let mut copy = model.clone();
+        copy.set_serial_number(serial);
+        pdb.add_model(copy);
+    }
+    let mapping = pdb.flatten_models();
+    assert_eq!(pdb.chain_count(), 70);
+    // The original chain A is skipped, so the single characters run out after 61 chains
+    assert_eq!(mapping[61].2, "9");
+    assert_eq!(mapping[62].2, "AA");
+    let mut ids: Vec<&str> = pdb.chains().map(Chain::id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), 70);
+}
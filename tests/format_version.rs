@@ -0,0 +1,67 @@
+// Test detecting and normalizing the version of the PDB format conventions.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+const OLD_DNA: &str =
+    "ATOM      1  O5*   C A   1      -4.549   5.095   4.262  1.00 28.71           O
+ATOM      2  C5*   C A   1      -4.176   6.323   3.646  1.00 27.35           C
+ATOM      3  C1*   C A   1      -2.045   7.848   1.617  1.00 24.33           C
+ATOM      4 1H5*   C A   1      -3.543   6.811   4.389  1.00 27.35           H
+ATOM      5  C1*   U A   2      -2.045   7.848   1.617  1.00 24.33           C
+ATOM      6  O2*   U A   2      -2.045   7.848   1.617  1.00 24.33           O
+END
+";
+
+#[test]
+fn detect_version() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    assert_eq!(pdb.format_version(), Some(FormatVersion::V3));
+
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(OLD_DNA.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.format_version(), Some(FormatVersion::V2));
+}
+
+#[test]
+fn normalize_on_parse() {
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .set_normalize_nomenclature(true)
+        .read_raw(OLD_DNA.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.format_version(), Some(FormatVersion::V3));
+    let names: Vec<_> = pdb.atoms().map(Atom::name).collect();
+    assert_eq!(names, ["O5'", "C5'", "C1'", "H5'", "C1'", "O2'"]);
+    let residues: Vec<_> = pdb.conformers().map(Conformer::name).collect();
+    assert_eq!(residues, ["DC", "U"]);
+}
+
+#[test]
+fn remap_between_versions() {
+    let (mut pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(OLD_DNA.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.remap_nomenclature(FormatVersion::V3), 7);
+    assert_eq!(pdb.remap_nomenclature(FormatVersion::V3), 0);
+    assert_eq!(pdb.remap_nomenclature(FormatVersion::V2), 7);
+    // Without its partner H5'' the hydrogen does not need a number
+    let names: Vec<_> = pdb.atoms().map(Atom::name).collect();
+    assert_eq!(names, ["O5*", "C5*", "C1*", "H5*", "C1*", "O2*"]);
+    let residues: Vec<_> = pdb.conformers().map(Conformer::name).collect();
+    assert_eq!(residues, ["C", "U"]);
+
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    pdb.remap_nomenclature(FormatVersion::V2);
+    let isoleucine = pdb.residues().find(|r| r.name() == Some("ILE")).unwrap();
+    assert!(isoleucine.atoms().any(|a| a.name() == "CD"));
+    assert!(isoleucine.atoms().all(|a| a.name() != "CD1"));
+}
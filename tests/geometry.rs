@@ -0,0 +1,39 @@
+// Test the geometric measurement utilities.
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::*;
+use pdbtbx::*;
+
+#[test]
+fn measurements() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let n = select(&pdb, "A/2/N").unwrap();
+    let ca = select(&pdb, "A/2/CA").unwrap();
+    let c = select(&pdb, "A/2/C").unwrap();
+    assert_eq!(distance(n, ca), pdb.distance("A/2/N", "A/2/CA").unwrap());
+    assert!((distance(n, ca) - 1.46).abs() < 0.05);
+    assert!((angle(n, ca, c) - 111.0).abs() < 5.0);
+
+    // Residue 2 (GLN) is part of a beta strand, so phi is negative and psi is positive
+    let phi = pdb.dihedral("A/1/C", "A/2/N", "A/2/CA", "A/2/C").unwrap();
+    let psi = pdb.dihedral("A/2/N", "A/2/CA", "A/2/C", "A/3/N").unwrap();
+    assert!(phi < -60.0 && phi > -180.0, "phi {phi}");
+    assert!(psi > 60.0 && psi <= 180.0, "psi {psi}");
+    assert!(
+        (pdb.angle("A/2/N", "A/2/CA", "A/2/C").unwrap() - angle(n, ca, c)).abs() < f64::EPSILON
+    );
+}
+
+#[test]
+fn invalid_selections() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let error = select(&pdb, "A/2").unwrap_err();
+    assert_eq!(error.short_description(), "Invalid selection");
+    let error = select(&pdb, "A/X2/CA").unwrap_err();
+    assert_eq!(error.short_description(), "Invalid selection");
+    let error = pdb.distance("A/2/CA", "B/2/CA").unwrap_err();
+    assert_eq!(error.short_description(), "Empty selection");
+    assert_eq!(error.context(), &Context::show("B/2/CA"));
+}
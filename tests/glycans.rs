@@ -0,0 +1,85 @@
+// Test reading LINK records and building glycan trees from them.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn links() {
+    let pdb = open("example-pdbs/3nig.pdb");
+    let count = |kind: Bond| pdb.bonds().filter(|(_, _, bond)| *bond == kind).count();
+    // 12 covalent bonds from the LINK records, the CONECT records add the bonds within ligands
+    assert_eq!(count(Bond::Covalent), 213);
+    assert_eq!(count(Bond::MetalCoordination), 96);
+    let (a, b, _) = pdb
+        .bonds()
+        .find(|(_, _, bond)| *bond == Bond::Covalent)
+        .unwrap();
+    assert_eq!((a.name(), b.name()), ("ND2", "C1"));
+    assert!((a.distance(b) - 1.43).abs() < 0.01);
+}
+
+#[test]
+fn n_linked_glycans() {
+    let pdb = open("example-pdbs/3nig.pdb");
+    let glycans = pdb.glycans();
+    assert_eq!(glycans.len(), 6);
+    let sizes: Vec<usize> = glycans.iter().map(Glycan::len).collect();
+    assert_eq!(sizes, vec![1, 3, 2, 1, 3, 2]);
+    for glycan in &glycans {
+        assert_eq!(glycan.kind, GlycosylationType::NLinked);
+        assert_eq!(glycan.site.name(), Some("ASN"));
+        assert!(!glycan.is_branched());
+        assert_eq!(glycan.sugars[0].linkage(), None);
+        assert_eq!(glycan.sugars[0].parent_atom.name(), "ND2");
+    }
+    let glycan = &glycans[1];
+    assert_eq!(glycan.site.serial_number(), 320);
+    let names: Vec<&str> = glycan
+        .sugars
+        .iter()
+        .filter_map(|s| s.residue.name())
+        .collect();
+    assert_eq!(names, vec!["NAG", "NAG", "MAN"]);
+    assert_eq!(glycan.sugars[2].parent, Some(1));
+    assert_eq!(glycan.sugars[2].linkage(), Some((1, 4)));
+    assert_eq!(glycan.children(0).collect::<Vec<_>>(), vec![1]);
+}
+
+/// A high mannose like glycan with a branch on the mannose
+const BRANCHED: &str = "\
+LINK         ND2 ASN A   1                 C1  NAG A   2     1555   1555  1.43
+LINK         O4  NAG A   2                 C1  BMA A   3     1555   1555  1.43
+LINK         O3  BMA A   3                 C1  MAN A   4     1555   1555  1.43
+LINK         O6  BMA A   3                 C1  MAN A   5     1555   1555  1.43
+ATOM      1  ND2 ASN A   1       0.000   0.000   0.000  1.00  0.00           N
+HETATM    2  C1  NAG A   2       1.400   0.000   0.000  1.00  0.00           C
+HETATM    3  O4  NAG A   2       2.800   0.000   0.000  1.00  0.00           O
+HETATM    4  C1  BMA A   3       4.200   0.000   0.000  1.00  0.00           C
+HETATM    5  O3  BMA A   3       5.600   0.000   0.000  1.00  0.00           O
+HETATM    6  O6  BMA A   3       7.000   0.000   0.000  1.00  0.00           O
+HETATM    7  C1  MAN A   4       8.400   0.000   0.000  1.00  0.00           C
+HETATM    8  C1  MAN A   5       9.800   0.000   0.000  1.00  0.00           C
+END
+";
+
+#[test]
+fn branched_glycan() {
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(BRANCHED.as_bytes())
+        .unwrap();
+    let glycans = pdb.glycans();
+    assert_eq!(glycans.len(), 1);
+    let glycan = &glycans[0];
+    assert_eq!(glycan.len(), 4);
+    assert!(glycan.is_branched());
+    assert_eq!(glycan.children(1).collect::<Vec<_>>(), vec![2, 3]);
+    let linkages: Vec<Option<(usize, usize)>> = glycan.sugars.iter().map(Sugar::linkage).collect();
+    assert_eq!(
+        linkages,
+        vec![None, Some((1, 4)), Some((1, 3)), Some((1, 6))]
+    );
+}
@@ -0,0 +1,88 @@
+// Test reading and writing the hetero group records (HET, HETNAM, HETSYN, and FORMUL)
+
+mod common;
+
+use common::{open_with_errors, temp_path};
+use pdbtbx::*;
+
+#[test]
+fn read_het_groups() {
+    let (pdb, errors) = open_with_errors("example-pdbs/7az6.pdb");
+    assert!(!errors
+        .iter()
+        .any(|e| e.short_description() == "MASTER checksum failed"));
+    let ids: Vec<_> = pdb.het_groups().map(|g| g.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        ["ACE", "ALC", "SOQ", "ZCL", "PEG", "GOL", "ACT", "CL", "HOH"]
+    );
+
+    let gol = pdb.het_group("GOL").unwrap();
+    assert_eq!(gol.name.as_deref(), Some("GLYCEROL"));
+    assert_eq!(gol.synonyms, ["GLYCERIN", "PROPANE-1,2,3-TRIOL"]);
+    assert_eq!(gol.formula.as_deref(), Some("C3 H8 O3"));
+    assert_eq!(gol.component_number, Some(5));
+    assert_eq!(gol.residues.len(), 1);
+    assert_eq!(gol.residues[0].atom_count, 6);
+
+    let peg = pdb.het_group("PEG").unwrap();
+    assert_eq!(peg.formula.as_deref(), Some("2(C4 H10 O3)"));
+    assert_eq!(peg.residues.len(), 2);
+
+    let water = pdb.het_group("HOH").unwrap();
+    assert!(water.water);
+    assert!(water.residues.is_empty());
+    assert_eq!(water.formula.as_deref(), Some("425(H2 O)"));
+
+    // Every hetero residue listed can be found back in the structure
+    for chain in pdb.chains() {
+        for residue in chain.residues() {
+            if let Some(group) = pdb.residue_het_group(chain.id(), residue.id()) {
+                assert_eq!(residue.name(), Some(group.id.as_str()));
+            }
+        }
+    }
+    assert_eq!(pdb.residue_het_group("A", (605, None)).unwrap().id, "CL");
+}
+
+#[test]
+fn continued_names() {
+    let (pdb, _) = open_with_errors("example-pdbs/3b5j.pdb");
+    assert_eq!(
+        pdb.het_group("12D").unwrap().name.as_deref(),
+        Some("2',3'-O-[(1R,6R)-2,4,6-TRINITROCYCLOHEXA-2,4-DIENE-1,1-DIYL]ADENOSINE 5'-(TRIHYDROGEN DIPHOSPHATE)")
+    );
+}
+
+#[test]
+fn master_checksum() {
+    let errors = ReadOptions::new()
+        .set_level(StrictnessLevel::Strict)
+        .set_format(Format::Pdb)
+        .read_raw(std::io::Cursor::new(
+            "HET    SO4  A 101       5\nHETATM    1  S   SO4 A 101       0.000   0.000   0.000  1.00  0.00           S\nMASTER        0    0    2    0    0    0    0    0    1    0    0    0\nEND\n"
+                .as_bytes(),
+        ))
+        .unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.short_description() == "MASTER checksum failed"
+            && e.long_description().contains("HET records (1)")));
+}
+
+#[test]
+fn het_groups_survive_saving() {
+    for file in ["example-pdbs/7az6.pdb", "example-pdbs/3b5j.pdb"] {
+        let (pdb, _) = open_with_errors(file);
+        let name = temp_path(&format!("het_groups_{}", file.rsplit('/').next().unwrap()));
+        save(&pdb, &name, StrictnessLevel::Loose).unwrap();
+        let (saved, errors) = open_with_errors(&name);
+        assert!(!errors
+            .iter()
+            .any(|e| e.short_description() == "MASTER checksum failed"));
+        assert_eq!(
+            pdb.het_groups().collect::<Vec<_>>(),
+            saved.het_groups().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+// Test the detection of hydrophobic contacts between residues
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn closest_apolar_carbons() {
+    // The backbone carbons are closer, but bonded to an oxygen so they are polar
+    let input = "\
+ATOM      1  CB  ALA A   1       0.000   0.000   0.000  1.00 10.00           C
+ATOM      2  C   ALA A   1       0.000   5.000   0.000  1.00 10.00           C
+ATOM      3  O   ALA A   1       0.000   6.200   0.000  1.00 10.00           O
+ATOM      4  CB  ALA A   2       3.800   0.000   0.000  1.00 10.00           C
+ATOM      5  C   ALA A   2       3.000   5.000   0.000  1.00 10.00           C
+ATOM      6  O   ALA A   2       3.000   6.200   0.000  1.00 10.00           O
+ATOM      7  CB  ALA A   3      20.000   0.000   0.000  1.00 10.00           C
+END
+";
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(input.as_bytes())
+        .unwrap();
+    let residues: Vec<&Residue> = pdb.residues().collect();
+
+    let contacts = interactions::hydrophobic_contacts(
+        &residues,
+        &residues,
+        interactions::HYDROPHOBIC_MAX_DISTANCE,
+    );
+    // Every pair of residues is only reported once
+    assert_eq!(contacts.len(), 1);
+    let contact = &contacts[0];
+    assert_eq!(contact.residue_a.serial_number(), 1);
+    assert_eq!(contact.residue_b.serial_number(), 2);
+    assert_eq!((contact.atom_a.name(), contact.atom_b.name()), ("CB", "CB"));
+    assert!((contact.distance - 3.8).abs() < 1e-9);
+
+    assert!(interactions::hydrophobic_contacts(&residues, &residues, 3.5).is_empty());
+    assert!(interactions::hydrophobic_contacts(&residues[..1], &residues[2..], 10.0).is_empty());
+}
+
+#[test]
+fn between_sets() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let residues: Vec<&Residue> = pdb.residues().collect();
+    let (first, second) = residues.split_at(38);
+
+    let contacts =
+        interactions::hydrophobic_contacts(first, second, interactions::HYDROPHOBIC_MAX_DISTANCE);
+    assert!(!contacts.is_empty());
+    for contact in &contacts {
+        assert!(first.iter().any(|r| std::ptr::eq(*r, contact.residue_a)));
+        assert!(second.iter().any(|r| std::ptr::eq(*r, contact.residue_b)));
+        assert_eq!(contact.atom_a.element(), Some(&Element::C));
+        assert_eq!(contact.atom_b.element(), Some(&Element::C));
+        assert!(contact.distance <= interactions::HYDROPHOBIC_MAX_DISTANCE);
+        assert!((contact.atom_a.distance(contact.atom_b) - contact.distance).abs() < 1e-9);
+    }
+    // The contacts between the sets are part of the contacts within the whole structure
+    let all = interactions::hydrophobic_contacts(
+        &residues,
+        &residues,
+        interactions::HYDROPHOBIC_MAX_DISTANCE,
+    );
+    assert!(all.len() > contacts.len());
+}
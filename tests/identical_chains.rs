@@ -0,0 +1,43 @@
+// Test grouping chains by sequence identity.
+
+mod common;
+
+use common::open;
+
+#[test]
+fn groups() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let groups = pdb.identical_chains(0.95);
+    let ids: Vec<Vec<&str>> = groups
+        .iter()
+        .map(|group| group.iter().map(|c| c.id()).collect())
+        .collect();
+    assert_eq!(ids, vec![vec!["A", "B"], vec!["D", "C"]]);
+    assert_eq!(
+        groups.iter().map(Vec::len).sum::<usize>(),
+        pdb.chain_count()
+    );
+    // Everything is identical at a threshold of zero, nothing is at a threshold above one
+    assert_eq!(pdb.identical_chains(0.0).len(), 1);
+    assert_eq!(pdb.identical_chains(1.1).len(), pdb.chain_count());
+}
+
+#[test]
+fn identity() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain(0).unwrap();
+    assert_eq!(chain.sequence().len(), 76);
+    assert_eq!(chain.sequence()[0], "MET");
+    assert_eq!(chain.sequence_identity(chain), 1.0);
+
+    let mut mutated = chain.clone();
+    mutated
+        .residue_mut(10)
+        .unwrap()
+        .conformer_mut(0)
+        .unwrap()
+        .set_name("ALA");
+    mutated.remove_residue(50);
+    let identity = chain.sequence_identity(&mutated);
+    assert_eq!(identity, 74.0 / 76.0);
+}
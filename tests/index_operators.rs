@@ -0,0 +1,40 @@
+// Test the index operators for scripting style access to the hierarchy
+
+mod common;
+
+use common::open;
+
+#[test]
+fn access() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let residue = &pdb[0]["A"][23];
+    assert_eq!(residue.name(), Some("ILE"));
+    assert_eq!(residue["CA"].name(), "CA");
+    assert_eq!(residue.conformer(0).unwrap()["CB"].name(), "CB");
+    assert!(std::ptr::eq(&pdb[0], pdb.model(0).unwrap()));
+
+    pdb[0]["A"][23]["CA"].set_b_factor(99.0).unwrap();
+    assert_eq!(pdb.atoms().filter(|a| a.b_factor() == 99.0).count(), 1);
+    assert_eq!(pdb[0]["A"][23]["CA"].b_factor(), 99.0);
+}
+
+#[test]
+#[should_panic(expected = "there is no Chain with id `B` in this Model")]
+fn missing_chain() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let _ = &pdb[0]["B"];
+}
+
+#[test]
+#[should_panic(expected = "there is no Residue with serial number 1000 in this Chain")]
+fn missing_residue() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let _ = &pdb[0]["A"][1000];
+}
+
+#[test]
+#[should_panic]
+fn missing_model() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let _ = &pdb[1];
+}
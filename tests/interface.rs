@@ -0,0 +1,35 @@
+// Test the detection of interface residues between chains.
+#![cfg(feature = "rstar")]
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::*;
+use pdbtbx::*;
+
+#[test]
+fn matches_brute_force() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let a = pdb.chains().find(|c| c.id() == "A").unwrap();
+    let b = pdb.chains().find(|c| c.id() == "B").unwrap();
+    let contacts = interface_residues(a, b, 4.0);
+    assert!(!contacts.is_empty());
+
+    let residues_a: Vec<&Residue> = a.residues().collect();
+    let residues_b: Vec<&Residue> = b.residues().collect();
+    let matrix = residue_distance_matrix(&residues_a, &residues_b);
+    let mut expected = Vec::new();
+    for (i, residue_a) in residues_a.iter().enumerate() {
+        for (j, residue_b) in residues_b.iter().enumerate() {
+            let distance = matrix.get(i, j).unwrap();
+            if distance <= 4.0 {
+                expected.push((residue_a.id(), residue_b.id(), distance));
+            }
+        }
+    }
+    let found: Vec<_> = contacts
+        .iter()
+        .map(|(a, b, distance)| (a.id(), b.id(), *distance))
+        .collect();
+    assert_eq!(found, expected);
+}
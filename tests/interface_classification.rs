@@ -0,0 +1,67 @@
+// Test classifying the interfaces between chains as biological or crystal packing.
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::*;
+
+#[test]
+fn crystallographic_dimer() {
+    // The dimer of 1KMK is generated by a crystallographic two-fold axis (REMARK 350)
+    let interfaces = classify_interfaces(&open("example-pdbs/1kmk.pdb"));
+    assert_eq!(interfaces.len(), 2);
+    let biological: Vec<&ChainInterface> = interfaces
+        .iter()
+        .filter(|i| i.kind == InterfaceKind::Biological)
+        .collect();
+    assert_eq!(biological.len(), 1);
+    assert_eq!(biological[0].chains, ("A".to_string(), "A".to_string()));
+    assert_eq!(biological[0].symmetry_operation.as_deref(), Some("6555"));
+    assert_eq!(biological[0].in_assembly, Some(true));
+    assert!(interfaces
+        .iter()
+        .all(|i| i.kind == InterfaceKind::Biological || i.buried_area < biological[0].buried_area));
+}
+
+#[test]
+fn monomer() {
+    // 3B5J is a monomer according to REMARK 350, so all contacts are crystal packing
+    let pdb = open("example-pdbs/3b5j.pdb");
+    assert_eq!(
+        pdb.biological_assemblies()[0].chains().collect::<Vec<_>>(),
+        ["A"]
+    );
+    let interfaces = classify_interfaces(&pdb);
+    assert!(!interfaces.is_empty());
+    for interface in &interfaces {
+        assert_eq!(interface.in_assembly, Some(false));
+        assert_eq!(interface.kind, InterfaceKind::Crystal);
+        assert!(interface.buried_area > 0.0);
+    }
+    // Interfaces with the mates of inverse operations are only reported once
+    let operations: Vec<&str> = interfaces
+        .iter()
+        .filter_map(|i| i.symmetry_operation.as_deref())
+        .collect();
+    assert!(operations.contains(&"1545"));
+    assert!(!operations.contains(&"1565"));
+}
+
+#[test]
+fn without_assemblies() {
+    let mut pdb = open("example-pdbs/1kmk.pdb");
+    pdb.delete_remarks_by(|(number, _)| *number == 350);
+    let interfaces = classify_interfaces(&pdb);
+    assert_eq!(interfaces.len(), 2);
+    for interface in interfaces {
+        assert_eq!(interface.in_assembly, None);
+        assert_eq!(
+            interface.kind == InterfaceKind::Biological,
+            interface.buried_area >= BIOLOGICAL_INTERFACE_AREA
+        );
+    }
+
+    // Without the crystal information only the asymmetric unit is considered
+    pdb.unit_cell = None;
+    assert!(classify_interfaces(&pdb).is_empty());
+}
@@ -0,0 +1,37 @@
+// Test iterating over the children of the hierarchy types in for loops
+
+mod common;
+
+use common::open;
+
+#[test]
+fn for_loops() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let mut atoms = 0;
+    for model in &pdb {
+        for chain in model {
+            for residue in chain {
+                for conformer in residue {
+                    for atom in conformer {
+                        assert!(!atom.name().is_empty());
+                        atoms += 1;
+                    }
+                }
+            }
+        }
+    }
+    assert_eq!(atoms, pdb.total_atom_count());
+    assert_eq!(
+        (&pdb[0]["A"]).into_iter().count(),
+        pdb[0]["A"].residue_count()
+    );
+
+    for model in &mut pdb {
+        for chain in model {
+            for residue in chain {
+                residue.set_serial_number(residue.serial_number() + 100);
+            }
+        }
+    }
+    assert_eq!(pdb.residues().next().unwrap().serial_number(), 101);
+}
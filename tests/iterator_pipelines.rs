@@ -0,0 +1,47 @@
+// Test building structures with iterator pipelines via FromIterator and Extend
+
+use pdbtbx::*;
+
+fn atom(serial: usize, name: &str, x: f64) -> Atom {
+    Atom::new(false, serial, name, x, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap()
+}
+
+#[test]
+fn collect_hierarchy() {
+    let residues: Chain = (1..=3)
+        .map(|i| {
+            let mut residue: Residue = ["N", "CA", "C"]
+                .iter()
+                .enumerate()
+                .map(|(j, name)| atom(i * 3 + j, name, i as f64))
+                .collect();
+            residue.set_serial_number(i as isize);
+            residue
+        })
+        .collect();
+    assert_eq!(residues.id(), "A");
+    assert_eq!(residues.residue_count(), 3);
+    assert_eq!(residues.atom_count(), 9);
+    assert_eq!(residues.conformers().next().unwrap().name(), "UNK");
+
+    let model: Model = std::iter::once(residues).collect();
+    assert_eq!(model.serial_number(), 0);
+    let pdb: PDB = std::iter::once(model).collect();
+    assert_eq!(pdb.total_atom_count(), 9);
+    assert_eq!(pdb[0]["A"][2]["CA"].serial_number(), 7);
+}
+
+#[test]
+fn extend_residue() {
+    let conformer = Conformer::new("GLY", None, Some(atom(1, "N", 0.0))).unwrap();
+    let mut residue: Residue = std::iter::once(conformer).collect();
+    residue.extend(vec![atom(2, "CA", 1.0), atom(3, "C", 2.0)]);
+    assert_eq!(residue.conformer_count(), 1);
+    assert_eq!(residue.name(), Some("GLY"));
+    assert_eq!(residue.atom_count(), 3);
+
+    let mut empty = Residue::new(5, None, None).unwrap();
+    empty.extend(std::iter::once(atom(4, "O", 0.0)));
+    assert_eq!(empty.name(), Some("UNK"));
+    assert_eq!(empty.atom_count(), 1);
+}
@@ -0,0 +1,78 @@
+// Test reading the Models of PDB files one at a time
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn same_as_full_read() {
+    for file in ["example-pdbs/pTLS-6484.pdb", "example-pdbs/models.pdb"] {
+        let pdb = open(file);
+        let (models, _) = ReadOptions::new()
+            .set_level(StrictnessLevel::Loose)
+            .read_lazy(file)
+            .unwrap();
+        assert_eq!(models.header().model_count(), 0);
+        assert_eq!(models.header().identifier, pdb.identifier);
+        assert_eq!(models.header().unit_cell, pdb.unit_cell);
+
+        let mut count = 0;
+        for (model, expected) in models.zip(pdb.models()) {
+            let (model, _) = model.unwrap();
+            assert_eq!(model.serial_number(), expected.serial_number());
+            assert_eq!(model.atom_count(), expected.atom_count());
+            assert!(model
+                .atoms()
+                .zip(expected.atoms())
+                .all(|(a, b)| a.pos() == b.pos() && a.name() == b.name()));
+            count += 1;
+        }
+        assert_eq!(count, pdb.model_count());
+    }
+}
+
+#[test]
+fn without_model_records() {
+    let (mut models, _) = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .read_lazy("example-pdbs/1ubq.pdb")
+        .unwrap();
+    let (model, _) = models.next().unwrap().unwrap();
+    assert_eq!(
+        model.atom_count(),
+        open("example-pdbs/1ubq.pdb").atom_count()
+    );
+    assert!(models.next().is_none());
+}
+
+#[test]
+fn error_line_numbers() {
+    let input = "CRYST1   10.000   10.000   10.000  90.00  90.00  90.00 P 1           1\n\
+MODEL        1\n\
+ATOM      1  N   ALA A   1       0.000   0.000   0.000  1.00  0.00           N\n\
+ENDMDL\n\
+MODEL        2\n\
+ATOM      1  N   ALA A   1       0.000   0.000   0.00x  1.00  0.00           N\n\
+ENDMDL\n\
+END\n";
+    let (mut models, _) = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .read_lazy_raw(input.as_bytes())
+        .unwrap();
+    assert!(models.header().unit_cell.is_some());
+    assert!(models.next().unwrap().is_ok());
+    let errors = models.next().unwrap().unwrap_err();
+    // The line numbers are relative to the full input
+    assert!(errors
+        .iter()
+        .any(|e| e.short_description() == "Invalid data in field"
+            && e.to_string().contains("6 │ ATOM")));
+    assert!(models.next().is_none());
+}
+
+#[test]
+fn mmcif_not_supported() {
+    let errors = open_lazy("example-pdbs/1ubq.cif").unwrap_err();
+    assert_eq!(errors[0].short_description(), "Not a PDB file");
+}
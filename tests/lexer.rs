@@ -0,0 +1,47 @@
+// Test the public lexer API for PDB files.
+
+use pdbtbx::pdb_lexer::*;
+use pdbtbx::*;
+
+#[test]
+fn record_statistics() {
+    let options = ReadOptions::new();
+    let input = std::fs::read_to_string("example-pdbs/1ubq.pdb").unwrap();
+    let mut atoms = 0;
+    let mut hetero = 0;
+    let mut remarks = 0;
+    for (index, line) in input.lines().enumerate() {
+        match lex_line(line, index + 1, &options) {
+            Ok((LexItem::Atom(true, ..), _)) => hetero += 1,
+            Ok((LexItem::Atom(false, ..), _)) => atoms += 1,
+            Ok((LexItem::Remark(..), _)) => remarks += 1,
+            _ => (),
+        }
+    }
+    assert_eq!(atoms + hetero, 1271);
+    assert_eq!(atoms, 1231);
+    assert!(remarks > 0);
+}
+
+#[test]
+fn single_records() {
+    let line = "ATOM      1  CA  GLY A   1      26.266  25.413   2.842  1.00 20.00           C";
+    let (item, errors) = lex_atom(1, line, false, &ReadOptions::new()).unwrap();
+    assert!(errors.is_empty());
+    match item {
+        LexItem::Atom(false, Some(1), name, .., x, _, _, _, _, _, element, 0, None) => {
+            assert_eq!(name, "CA");
+            assert_eq!(x, 26.266);
+            assert_eq!(element, "C");
+        }
+        _ => panic!("Not an atom: {item:?}"),
+    }
+    let (item, errors) = lex_model(2, "MODEL        2");
+    assert!(errors.is_empty());
+    assert_eq!(item, LexItem::Model(2));
+    let (item, errors) = lex_conect(3, "CONECT 9166 9167 9168 9169 9173");
+    assert!(errors.is_empty());
+    assert_eq!(item, LexItem::Conect(9166, vec![9167, 9168, 9169, 9173]));
+    let (item, _) = lex_conect(4, "CONECT 9167 9166                          ");
+    assert_eq!(item, LexItem::Conect(9167, vec![9166]));
+}
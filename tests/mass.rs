@@ -0,0 +1,43 @@
+// Test the masses of atoms and residues, and the center of mass of chains
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn masses() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let atom = pdb.atom(0).unwrap();
+    assert_eq!(atom.element(), Some(&Element::N));
+    assert!((atom.mass().unwrap() - 14.007).abs() < 0.001);
+
+    let residue = pdb.residue(0).unwrap();
+    let total: f64 = residue.atoms().filter_map(Atom::mass).sum();
+    assert!(total > 0.0 && (residue.mass() - total).abs() < 1e-9);
+    assert_eq!(
+        pdb.chain(0)
+            .unwrap()
+            .residues()
+            .map(Residue::mass)
+            .sum::<f64>(),
+        pdb.chain(0).unwrap().molecular_weight()
+    );
+}
+
+#[test]
+fn center_of_mass() {
+    let mut chain = Chain::new("A").unwrap();
+    let mut add = |serial: usize, name: &str, element: &str, x: f64| {
+        let atom = Atom::new(false, serial, name, x, 0.0, 0.0, 1.0, 0.0, element, 0).unwrap();
+        chain.add_atom(atom, (1, None), ("NA", None));
+    };
+    add(1, "NA", "NA", 0.0);
+    add(2, "CL", "CL", 10.0);
+    let (x, y, z) = chain.center_of_mass().unwrap();
+    let expected = 10.0 * Element::Cl.weight().unwrap()
+        / (Element::Na.weight().unwrap() + Element::Cl.weight().unwrap());
+    assert!((x - expected).abs() < 1e-9);
+    assert_eq!((y, z), (0.0, 0.0));
+    assert_eq!(Chain::new("B").unwrap().center_of_mass(), None);
+}
@@ -0,0 +1,62 @@
+// Test that the MASTER record of saved PDB files matches the records written
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+use std::io::BufWriter;
+
+/// Save the given PDB in memory and give back the lines
+fn save(pdb: &PDB, level: StrictnessLevel) -> Vec<String> {
+    let mut output = Vec::new();
+    save_pdb_raw(pdb, BufWriter::new(&mut output), level);
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Get the numbers in the MASTER record of the given lines
+fn master(lines: &[String]) -> Vec<usize> {
+    let line = lines.iter().find(|l| l.starts_with("MASTER")).unwrap();
+    line[10..]
+        .split_whitespace()
+        .map(|n| n.parse().unwrap())
+        .collect()
+}
+
+fn count(lines: &[String], record: &str) -> usize {
+    lines.iter().filter(|l| l.starts_with(record)).count()
+}
+
+#[test]
+fn ter_and_seqres_counts() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let lines = save(&pdb, StrictnessLevel::Strict);
+    let numbers = master(&lines);
+    assert!(count(&lines, "TER") > 1);
+    assert_eq!(numbers[9], count(&lines, "TER"));
+    assert!(count(&lines, "SEQRES") > 0);
+    assert_eq!(numbers[11], count(&lines, "SEQRES"));
+}
+
+#[test]
+fn writer_ter_count() {
+    let mut writer = PDBWriter::start(Vec::new());
+    let atom = Atom::new(false, 1, "CA", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap();
+    writer.start_model(1).unwrap();
+    writer
+        .write_atom(&atom, None, "ALA", "A", (1, None))
+        .unwrap();
+    writer.write_ter().unwrap();
+    writer.write_ter().unwrap();
+    writer.start_model(2).unwrap();
+    writer
+        .write_atom(&atom, None, "ALA", "A", (1, None))
+        .unwrap();
+    writer.end_model().unwrap();
+    let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+    let lines: Vec<String> = output.lines().map(str::to_string).collect();
+    assert_eq!(master(&lines)[9], 1);
+}
@@ -0,0 +1,45 @@
+// Test aborting reading after a maximal number of errors.
+
+use pdbtbx::*;
+
+#[test]
+fn abort_after_max_errors() {
+    let line = "ATOM      1  N   MET A   1      27.340  24.430   2.614  1.00  9.67           N X\n";
+    let input = line.repeat(100);
+
+    let errors = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .set_max_errors(10)
+        .read_raw(input.as_bytes())
+        .unwrap_err();
+    assert_eq!(errors.len(), 11);
+    assert_eq!(
+        errors.last().unwrap().short_description(),
+        "Too many errors"
+    );
+
+    let errors = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(input.as_bytes())
+        .unwrap_err();
+    assert!(errors.len() >= 100);
+}
+
+#[test]
+fn limit_errors_found_after_parsing() {
+    // The errors from validating the models are found after all lines are parsed
+    for file in ["example-pdbs/pTLS-6484.pdb", "example-pdbs/pTLS-6484.cif"] {
+        let errors = ReadOptions::new()
+            .set_level(StrictnessLevel::Loose)
+            .set_max_errors(10)
+            .read(file)
+            .unwrap_err();
+        assert_eq!(errors.len(), 11);
+        assert_eq!(
+            errors.last().unwrap().short_description(),
+            "Too many errors"
+        );
+    }
+}
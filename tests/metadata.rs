@@ -0,0 +1,107 @@
+// Test reading and writing the descriptive metadata of the title section
+
+mod common;
+
+use common::{open, temp_path};
+use pdbtbx::*;
+
+#[test]
+fn read_metadata() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let metadata = pdb.metadata();
+    assert_eq!(pdb.identifier.as_deref(), Some("1KMK"));
+    assert_eq!(metadata.classification.as_deref(), Some("LYASE"));
+    assert_eq!(metadata.deposition_date.as_deref(), Some("16-DEC-01"));
+    assert_eq!(
+        metadata.title.as_deref(),
+        Some("E. COLI NIFS/CSDB PROTEIN AT 2.20A WITH THE CYSTEINE PERSELENIDE INTERMEDIATE (RESIDUE CSZ).")
+    );
+    assert!(metadata
+        .compound
+        .as_deref()
+        .unwrap()
+        .contains("SELENOCYSTEINE BETA-LYASE, SCL;"));
+    assert!(metadata
+        .source
+        .as_deref()
+        .unwrap()
+        .starts_with("MOL_ID: 1; ORGANISM_SCIENTIFIC: ESCHERICHIA COLI;"));
+    assert_eq!(metadata.keywords.len(), 7);
+    assert_eq!(metadata.keywords[1], "STRUCTURAL GENOMICS");
+    assert_eq!(metadata.experimental_methods, ["X-RAY DIFFRACTION"]);
+    assert_eq!(metadata.resolution, Some(2.2));
+    assert_eq!(
+        metadata.authors,
+        [
+            "C.D.LIMA",
+            "S.K.BURLEY",
+            "NEW YORK SGX RESEARCH CENTER FOR STRUCTURAL GENOMICS (NYSGXRC)"
+        ]
+    );
+
+    let citation = metadata.citation.as_ref().unwrap();
+    assert_eq!(citation.authors, ["C.D.LIMA"]);
+    assert_eq!(citation.title.as_deref(), Some("ANALYSIS OF THE E. COLI NIFS CSDB PROTEIN AT 2.0 A REVEALS THE STRUCTURAL BASIS FOR PERSELENIDE AND PERSULFIDE INTERMEDIATE FORMATION."));
+    assert_eq!(citation.journal.as_deref(), Some("J.MOL.BIOL."));
+    assert_eq!(citation.volume.as_deref(), Some("315"));
+    assert_eq!(citation.first_page.as_deref(), Some("1199"));
+    assert_eq!(citation.year, Some(2002));
+    assert_eq!(citation.reference_number.as_deref(), Some("ISSN 0022-2836"));
+    assert_eq!(citation.pubmed_id.as_deref(), Some("11827487"));
+    assert_eq!(citation.doi.as_deref(), Some("10.1006/JMBI.2001.5308"));
+}
+
+#[test]
+fn discard_metadata() {
+    let (pdb, _) = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .set_discard_metadata(true)
+        .read("example-pdbs/1kmk.pdb")
+        .unwrap();
+    assert!(pdb.metadata().is_empty());
+}
+
+#[test]
+fn metadata_survives_saving() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let path = temp_path("metadata_1kmk.pdb");
+    save(&pdb, &path, StrictnessLevel::Loose).unwrap();
+    let saved = open(&path);
+    assert_eq!(pdb.metadata(), saved.metadata());
+    assert_eq!(
+        pdb.remarks().collect::<Vec<_>>(),
+        saved.remarks().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn edit_metadata() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let metadata = pdb.metadata_mut();
+    metadata.title = Some("A VERY LONG TITLE ".repeat(10).trim_end().to_string());
+    metadata.authors = vec!["A.B.SMITH".to_string(); 12];
+    metadata.experimental_methods = vec![
+        "X-RAY DIFFRACTION".to_string(),
+        "NEUTRON DIFFRACTION".to_string(),
+    ];
+    metadata.resolution = Some(1.234);
+    metadata.citation = Some(Citation {
+        journal: Some("TO BE PUBLISHED".to_string()),
+        ..Citation::default()
+    });
+    let path = temp_path("metadata_edited.pdb");
+    save(&pdb, &path, StrictnessLevel::Loose).unwrap();
+
+    let saved = open(&path);
+    let mut expected = pdb.metadata().clone();
+    expected.resolution = Some(1.23);
+    assert_eq!(saved.metadata(), &expected);
+    assert_eq!(
+        saved
+            .remarks()
+            .filter(|(number, _)| *number == 2)
+            .map(|(_, text)| text.as_str())
+            .collect::<Vec<_>>(),
+        ["", "RESOLUTION.    1.23 ANGSTROMS."]
+    );
+}
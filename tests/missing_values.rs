@@ -0,0 +1,39 @@
+// Test configurable defaults for missing occupancy and B factor columns.
+
+use pdbtbx::*;
+
+const INPUT: &str = "ATOM      1  CA  GLY A   1      26.266  25.413   2.842
+ATOM      2  CA  SER A   2      27.266  25.413   2.842  0.50
+ATOM      3  CA  HIS A   3      28.266  25.413   2.842        12.00
+";
+
+#[test]
+fn missing_values_fail_by_default() {
+    let errors = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(INPUT.as_bytes())
+        .unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.short_description() == "Line too short"));
+}
+
+#[test]
+fn configured_defaults() {
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_default_occupancy(0.0)
+        .set_default_b_factor(30.0)
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    let values: Vec<_> = pdb.atoms().map(|a| (a.occupancy(), a.b_factor())).collect();
+    assert_eq!(values, [(0.0, 30.0), (0.5, 30.0), (0.0, 12.0)]);
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|e| e.short_description() == "Missing value")
+            .count(),
+        4
+    );
+}
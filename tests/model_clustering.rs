@@ -0,0 +1,47 @@
+// Test clustering the models of ensembles by their RMSD
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn ensemble() {
+    let pdb = open("example-pdbs/pTLS-6484.pdb");
+    let models: Vec<&Model> = pdb.models().collect();
+    let matrix = geometry::model_rmsd_matrix(&models).unwrap();
+    for i in 0..models.len() {
+        assert!(matrix.get(i, i).unwrap() < 1e-6);
+        for j in 0..models.len() {
+            assert!((matrix.get(i, j).unwrap() - matrix.get(j, i).unwrap()).abs() < 1e-9);
+        }
+    }
+    let largest = matrix.as_slice().iter().copied().fold(0.0, f64::max);
+
+    // Everything in one cluster or every model in its own cluster
+    let clusters = pdb.cluster_models(largest).unwrap();
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters.members(0).len(), 50);
+    let clusters = pdb.cluster_models(0.0).unwrap();
+    assert_eq!(clusters.len(), 50);
+
+    let clusters = pdb.cluster_models(largest / 2.0).unwrap();
+    assert!(clusters.len() > 1 && clusters.len() < 50);
+    for (cluster, representative) in clusters.representatives.iter().enumerate() {
+        assert_eq!(clusters.assignments[*representative], cluster);
+        if cluster > 0 {
+            assert!(clusters.members(cluster).len() <= clusters.members(cluster - 1).len());
+        }
+    }
+}
+
+#[test]
+fn mismatched() {
+    let mut pdb = open("example-pdbs/pTLS-6484.pdb");
+    pdb.model_mut(1)
+        .unwrap()
+        .chain_mut(0)
+        .unwrap()
+        .remove_residue(0);
+    assert_eq!(pdb.cluster_models(1.0), None);
+}
@@ -0,0 +1,76 @@
+// Test the molecular formulas and weights of the hierarchy
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+const GLYCINES: &str = "\
+MODEL        1
+ATOM      1  N   GLY A   1       0.000   0.000   0.000  1.00 10.00           N
+ATOM      2  CA  GLY A   1       1.458   0.000   0.000  1.00 10.00           C
+ATOM      3  C   GLY A   1       2.009   1.420   0.000  1.00 10.00           C
+ATOM      4  O   GLY A   1       1.251   2.390   0.000  1.00 10.00           O
+ATOM      5  N   GLY B   1      10.000   0.000   0.000  1.00 10.00           N
+ATOM      6  CA AGLY B   1      11.458   0.000   0.000  0.50 10.00           C
+ATOM      7  CA BGLY B   1      11.458   0.500   0.000  0.50 10.00           C
+ENDMDL
+MODEL        2
+ATOM      1  N   GLY A   1       0.000   0.000   0.000  1.00 10.00           N
+ATOM      2  CA  GLY A   1       1.458   0.000   0.000  1.00 10.00           C
+ATOM      3  C   GLY A   1       2.009   1.420   0.000  1.00 10.00           C
+ATOM      4  O   GLY A   1       1.251   2.390   0.000  1.00 10.00           O
+ATOM      5  N   GLY B   1      10.000   0.000   0.000  1.00 10.00           N
+ATOM      6  CA AGLY B   1      11.458   0.000   0.000  0.50 10.00           C
+ATOM      7  CA BGLY B   1      11.458   0.500   0.000  0.50 10.00           C
+ENDMDL
+END
+";
+
+#[test]
+fn formula_and_weight() {
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(GLYCINES.as_bytes())
+        .unwrap();
+    let weight = |element: Element| element.weight().unwrap();
+    let glycine = 2.0 * weight(Element::C) + weight(Element::N) + weight(Element::O);
+
+    let residue = pdb.chain(0).unwrap().residue(0).unwrap();
+    assert_eq!(residue.formula().unwrap(), "C2 N O");
+    assert!((residue.molecular_weight() - glycine).abs() < 1e-9);
+
+    // Only the first conformer is counted, with the shared nitrogen
+    let partial = pdb.chain(1).unwrap();
+    assert_eq!(partial.residue(0).unwrap().formula().unwrap(), "C N");
+    let partial_weight = weight(Element::C) + weight(Element::N);
+    assert!((partial.molecular_weight() - partial_weight).abs() < 1e-9);
+
+    // Every model is counted for itself, while the PDB only counts the first model
+    let model = pdb.model(0).unwrap();
+    assert!((model.molecular_weight() - glycine - partial_weight).abs() < 1e-9);
+    assert!((pdb.molecular_weight() - model.molecular_weight()).abs() < 1e-9);
+    assert!(PDB::new().molecular_weight().abs() < f64::EPSILON);
+}
+
+#[test]
+fn ubiquitin() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain(0).unwrap();
+    let protein: Vec<&Atom> = chain
+        .residues()
+        .filter(|r| r.name() != Some("HOH"))
+        .flat_map(Residue::atoms)
+        .collect();
+    // The structure includes all hydrogens, ubiquitin weighs 8564.8 Da
+    assert_eq!(chemistry::formula(&protein), "C378 H629 N105 O118 S");
+    let weight = chemistry::molecular_weight(protein.iter().copied());
+    assert!((weight - 8564.8).abs() < 0.1);
+
+    // The waters only have their oxygen atom
+    let waters = chain.residues().filter(|r| r.name() == Some("HOH")).count();
+    #[allow(clippy::cast_precision_loss)]
+    let water_weight = waters as f64 * Element::O.weight().unwrap();
+    assert!((chain.molecular_weight() - weight - water_weight).abs() < 1e-6);
+}
@@ -0,0 +1,56 @@
+// Test mutating residues into other amino acids
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+/// Get the atom names of the residue with the given serial number in the first chain
+fn atom_names(pdb: &PDB, serial: isize) -> Vec<String> {
+    pdb.chain(0)
+        .unwrap()
+        .residues()
+        .find(|r| r.serial_number() == serial)
+        .unwrap()
+        .atoms()
+        .map(|a| a.name().to_string())
+        .collect()
+}
+
+#[test]
+fn mutate_residues() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain_mut(0).unwrap();
+
+    // Serine to cysteine renames OG to SG
+    assert_eq!(chain.mutate((20, None), "CYS"), Ok(Vec::new()));
+    // Glycine to alanine needs a new CB, and renames HA2
+    assert_eq!(chain.mutate((10, None), "ala"), Ok(vec!["CB".to_string()]));
+    // Lysine to proline drops the amide hydrogen
+    assert_eq!(
+        chain.mutate((48, None), "PRO"),
+        Ok(vec!["CG".to_string(), "CD".to_string()])
+    );
+    assert!(chain.mutate((48, None), "XYZ").is_err());
+    assert!(chain.mutate((100, Some("A")), "ALA").is_err());
+
+    let cysteine = atom_names(&pdb, 20);
+    assert!(cysteine.contains(&"SG".to_string()) && !cysteine.contains(&"OG".to_string()));
+    assert!(cysteine.iter().all(|n| !n.starts_with("HB") && n != "HG"));
+    let sulfur = pdb.atoms().find(|a| a.name() == "SG").unwrap();
+    assert_eq!(sulfur.element(), Some(&Element::S));
+
+    let alanine = atom_names(&pdb, 10);
+    assert!(alanine.contains(&"HA".to_string()));
+    assert!(!alanine.contains(&"HA2".to_string()) && !alanine.contains(&"HA3".to_string()));
+
+    let proline = atom_names(&pdb, 48);
+    assert_eq!(proline, ["N", "CA", "C", "O", "CB", "HA"]);
+    let residue = pdb
+        .chain(0)
+        .unwrap()
+        .residues()
+        .find(|r| r.serial_number() == 48)
+        .unwrap();
+    assert_eq!(residue.name(), Some("PRO"));
+}
@@ -0,0 +1,23 @@
+// Test exporting structures to ndarray arrays.
+#![cfg(feature = "ndarray")]
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn coordinates_and_properties() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let model = pdb.model(0).unwrap();
+    let coordinates = model.to_ndarray();
+    assert_eq!(coordinates.shape(), &[model.atom_count(), 3]);
+    let atom = model.atom(10).unwrap();
+    assert_eq!(
+        coordinates.row(10).to_vec(),
+        vec![atom.x(), atom.y(), atom.z()]
+    );
+    assert_eq!(model.b_factors_ndarray()[10], atom.b_factor());
+    assert_eq!(model.occupancies_ndarray()[10], atom.occupancy());
+    assert_eq!(model.b_factors_ndarray().len(), model.atom_count());
+}
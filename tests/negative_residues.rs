@@ -0,0 +1,32 @@
+// Test reading and writing negative residue serial numbers, as used for expression tags.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn negative_residue_serials() {
+    let input = "ATOM      1  CA  GLY A  -2      26.266  25.413   2.842  1.00 10.38           C
+ATOM      2  CA  SER A  -1      27.266  25.413   2.842  1.00 10.38           C
+ATOM      3  CA  HIS A   0      28.266  25.413   2.842  1.00 10.38           C
+ATOM      4  CA  MET A   1      29.266  25.413   2.842  1.00 10.38           C
+END
+";
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(input.as_bytes())
+        .unwrap();
+    let serials: Vec<_> = pdb.residues().map(Residue::serial_number).collect();
+    assert_eq!(serials, [-2, -1, 0, 1]);
+
+    for extension in ["pdb", "cif"] {
+        let path = std::env::temp_dir().join(format!("pdbtbx_negative_residues.{extension}"));
+        let path = path.to_string_lossy();
+        save(&pdb, &path, StrictnessLevel::Loose).unwrap();
+        let pdb = open(&path);
+        let serials: Vec<_> = pdb.residues().map(Residue::serial_number).collect();
+        assert_eq!(serials, [-2, -1, 0, 1], "{extension}");
+    }
+}
@@ -0,0 +1,43 @@
+// Test the net charge of structures, from formal charges and from protonation states.
+
+use pdbtbx::*;
+
+#[test]
+fn protonation_states() {
+    let mut chain = Chain::new("A").unwrap();
+    let mut add =
+        |serial: usize, name: &str, element: &str, charge: isize, residue: (isize, &str)| {
+            chain.add_atom(
+                Atom::new(
+                    false, serial, name, 0.0, 0.0, 0.0, 1.0, 0.0, element, charge,
+                )
+                .unwrap(),
+                (residue.0, None),
+                (residue.1, None),
+            );
+        };
+    add(1, "CA", "C", 0, (1, "LYS"));
+    add(2, "NZ", "N", 1, (1, "LYS"));
+    add(3, "CA", "C", 0, (2, "ASP"));
+    add(4, "CA", "C", 0, (3, "GLU"));
+    add(5, "CA", "C", 0, (4, "HIS"));
+    add(6, "CA", "C", 0, (5, "ARG"));
+    add(7, "ZN", "ZN", 2, (6, "ZN"));
+    add(8, "P", "P", 0, (7, "DA"));
+
+    // Only the lysine and zinc have formal charges
+    assert_eq!(chain.net_charge(), 3);
+    // Lys + Arg - Asp - Glu + Zn - phosphate
+    assert_eq!(chain.net_charge_at_neutral_ph(), 1);
+    assert_eq!(chain.residue(6).unwrap().net_charge_at_neutral_ph(), -1);
+
+    let mut model = Model::new(0);
+    model.add_chain(chain.clone());
+    let mut pdb = PDB::new();
+    pdb.add_model(model.clone());
+    // Other models are not counted
+    pdb.add_model(model);
+    assert_eq!(pdb.net_charge(), 3);
+    assert_eq!(pdb.net_charge_at_neutral_ph(), 1);
+    assert_eq!(PDB::new().net_charge(), 0);
+}
@@ -0,0 +1,45 @@
+// Test calculating the backbone torsions and sugar pucker of nucleotides.
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::PuckerConformation;
+use pdbtbx::*;
+
+#[test]
+fn b_dna() {
+    let pdb = open("example-pdbs/nucleic.pdb");
+    let torsions = pdb.chain(0).unwrap().nucleic_torsions();
+    assert_eq!(torsions.len(), 2);
+    let (first, second) = (torsions[0].1, torsions[1].1);
+    // The phosphodiester bond only connects the first to the second nucleotide
+    assert!(first.alpha.is_none() && second.alpha.is_some());
+    assert!(first.epsilon.is_some() && first.zeta.is_some());
+    assert!(second.epsilon.is_none() && second.zeta.is_none());
+    for (_, torsion) in &torsions {
+        assert!((torsion.delta.unwrap() - 143.4).abs() < 0.5);
+        assert!((torsion.chi.unwrap() + 98.0).abs() < 0.5);
+        let pucker = torsion.pucker.unwrap();
+        assert_eq!(pucker.conformation(), PuckerConformation::C2Endo);
+        assert!((pucker.amplitude - 44.8).abs() < 0.5);
+    }
+}
+
+#[test]
+fn chain_break() {
+    let mut pdb = open("example-pdbs/nucleic.pdb");
+    pdb.residues_mut()
+        .nth(1)
+        .unwrap()
+        .apply_transformation(&TransformationMatrix::translation(0.0, 0.0, 5.0));
+    let torsions = pdb.chain(0).unwrap().nucleic_torsions();
+    assert!(torsions[0].1.zeta.is_none());
+    assert!(torsions[1].1.alpha.is_none());
+    assert!(torsions[1].1.beta.is_some());
+}
+
+#[test]
+fn proteins_skipped() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    assert!(pdb.chain(0).unwrap().nucleic_torsions().is_empty());
+}
@@ -0,0 +1,50 @@
+// Test that the validation passes give their errors in the order of the structure, also when
+// running in parallel
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn errors_in_chain_order() {
+    let mut model = Model::new(1);
+    let ids = ["AA", "BB", "CC", "DD", "EE", "FF", "GG", "HH"];
+    for id in ids {
+        let mut chain = Chain::new(id).unwrap();
+        chain.add_atom(
+            Atom::new(false, 1, "CA", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap(),
+            (1, None),
+            ("GLY", None),
+        );
+        model.add_chain(chain);
+    }
+    let mut pdb = PDB::new();
+    pdb.add_model(model);
+    let errors = validate_pdb(&pdb);
+    let chains: Vec<String> = errors
+        .iter()
+        .filter(|e| e.short_description() == "Chain id too long")
+        .map(|e| e.long_description().split(' ').nth(1).unwrap().to_string())
+        .collect();
+    assert_eq!(chains, ids);
+}
+
+#[test]
+fn models_in_order() {
+    let pdb = open("example-pdbs/models.pdb");
+    let mut broken = pdb.clone();
+    for model in broken.models_mut().skip(1) {
+        model.remove_atoms_by(|atom| atom.serial_number() == 1);
+    }
+    let serials: Vec<usize> = pdb.models().skip(1).map(Model::serial_number).collect();
+    let invalid: Vec<String> = validate(&broken)
+        .iter()
+        .filter(|e| e.short_description() == "Invalid Model")
+        .map(|e| e.long_description().to_string())
+        .collect();
+    assert_eq!(invalid.len(), serials.len());
+    for (message, serial) in invalid.iter().zip(serials) {
+        assert!(message.starts_with(&format!("Model {serial} ")));
+    }
+}
@@ -0,0 +1,51 @@
+// Test parsing files given as separate lines.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+fn lines(path: &str) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn pdb_lines() {
+    let (pdb, _) = parse_lines(
+        lines("example-pdbs/1ubq.pdb"),
+        Context::show("1ubq.pdb"),
+        StrictnessLevel::Loose,
+    )
+    .unwrap();
+    let expected = open("example-pdbs/1ubq.pdb");
+    assert_eq!(pdb, expected);
+}
+
+#[test]
+fn line_endings() {
+    let input = vec![
+        "ATOM      1  CA  GLY A   1      26.266  25.413   2.842  1.00 20.00           C\r\n"
+            .to_string(),
+        "END\n".to_string(),
+    ];
+    let (pdb, errors) = ReadOptions::new()
+        .set_level(StrictnessLevel::Medium)
+        .read_lines(input)
+        .unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(pdb.atom(0).unwrap().element(), Some(&Element::C));
+}
+
+#[test]
+fn mmcif_lines() {
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Mmcif)
+        .set_level(StrictnessLevel::Loose)
+        .read_lines(lines("example-pdbs/1ubq.cif"))
+        .unwrap();
+    assert_eq!(pdb.atom_count(), 660);
+}
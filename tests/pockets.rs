@@ -0,0 +1,44 @@
+// Test the geometric pocket detection.
+
+mod common;
+
+use common::open;
+use pdbtbx::geometry::*;
+use pdbtbx::*;
+
+#[test]
+fn ligand_pocket() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let ligand = pdb.residues().find(|r| r.name() == Some("PLP")).unwrap();
+    let (x, y, z) = ligand.atoms().fold((0.0, 0.0, 0.0), |s, a| {
+        (s.0 + a.x(), s.1 + a.y(), s.2 + a.z())
+    });
+    let n = ligand.atom_count() as f64;
+    let centre = (x / n, y / n, z / n);
+
+    let protein: Vec<&Residue> = pdb
+        .residues()
+        .filter(|r| r.conformers().any(Conformer::is_amino_acid))
+        .collect();
+    let pockets = pockets(&protein);
+    assert!(!pockets.is_empty());
+    assert!(pockets
+        .windows(2)
+        .all(|pair| pair[0].volume >= pair[1].volume));
+
+    // The ligand sits in one of the detected pockets
+    let pocket = pockets
+        .iter()
+        .find(|pocket| {
+            pocket.points.iter().any(|p| {
+                ((p.0 - centre.0).powi(2) + (p.1 - centre.1).powi(2) + (p.2 - centre.2).powi(2))
+                    .sqrt()
+                    < 3.0
+            })
+        })
+        .expect("No pocket found at the ligand");
+    let site = pdb.binding_site(ligand, 4.0);
+    assert!(site
+        .iter()
+        .any(|(r, _)| pocket.residues.iter().any(|l| std::ptr::eq(*l, *r))));
+}
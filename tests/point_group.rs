@@ -0,0 +1,122 @@
+// Test point group symmetry detection of assemblies.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+/// Build an assembly of copies of the first chain of 1ubq, each transformed by the given
+/// rotations (applied in order)
+fn assembly(rotations: &[Vec<TransformationMatrix>]) -> Vec<Chain> {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let mut base = pdb.chain(0).unwrap().clone();
+    base.remove_residues_by(|r| r.atoms().all(Atom::hetero));
+    // Move the chain away from the origin so it does not lie on any symmetry axis
+    base.apply_transformation(&TransformationMatrix::translation(25.0, 0.0, 0.0));
+    rotations
+        .iter()
+        .enumerate()
+        .map(|(index, transformations)| {
+            let mut chain = base.clone();
+            chain.set_id(((b'A' + index as u8) as char).to_string());
+            for transformation in transformations {
+                chain.apply_transformation(transformation);
+            }
+            chain
+        })
+        .collect()
+}
+
+#[test]
+fn cyclic() {
+    let chains = assembly(&[
+        vec![],
+        vec![TransformationMatrix::rotation_z(120.0)],
+        vec![TransformationMatrix::rotation_z(240.0)],
+    ]);
+    let symmetry = geometry::point_group(&chains.iter().collect::<Vec<_>>()).unwrap();
+    assert_eq!(symmetry.point_group, geometry::PointGroup::Cyclic(3));
+    assert_eq!(symmetry.point_group.to_string(), "C3");
+    assert!(symmetry.axis.2.abs() > 0.999);
+    assert!(symmetry.center.0.abs() < 1e-6 && symmetry.center.1.abs() < 1e-6);
+    assert!(symmetry.rmsd < 1e-6);
+}
+
+#[test]
+fn dihedral() {
+    let chains = assembly(&[
+        vec![],
+        vec![TransformationMatrix::rotation_z(180.0)],
+        vec![TransformationMatrix::rotation_x(180.0)],
+        vec![TransformationMatrix::rotation_y(180.0)],
+    ]);
+    let symmetry = geometry::point_group(&chains.iter().collect::<Vec<_>>()).unwrap();
+    assert_eq!(symmetry.point_group, geometry::PointGroup::Dihedral(2));
+
+    let chains = assembly(&[
+        vec![],
+        vec![TransformationMatrix::rotation_z(120.0)],
+        vec![TransformationMatrix::rotation_z(240.0)],
+        vec![TransformationMatrix::rotation_x(180.0)],
+        vec![
+            TransformationMatrix::rotation_z(120.0),
+            TransformationMatrix::rotation_x(180.0),
+        ],
+        vec![
+            TransformationMatrix::rotation_z(240.0),
+            TransformationMatrix::rotation_x(180.0),
+        ],
+    ]);
+    let symmetry = geometry::point_group(&chains.iter().collect::<Vec<_>>()).unwrap();
+    assert_eq!(symmetry.point_group, geometry::PointGroup::Dihedral(3));
+    assert!(symmetry.axis.2.abs() > 0.999);
+}
+
+#[test]
+fn asymmetric() {
+    let chains = assembly(&[
+        vec![],
+        vec![TransformationMatrix::translation(0.0, 0.0, 40.0)],
+    ]);
+    assert_eq!(
+        geometry::point_group(&chains.iter().collect::<Vec<_>>()),
+        None
+    );
+    assert_eq!(geometry::point_group(&[&chains[0]]), None);
+}
+
+#[test]
+fn structure() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    // Chains A and B are neighbouring subunits of a hexameric ring, so not a closed assembly
+    assert_eq!(pdb.point_group(), None);
+    // Three of the biological assembly operations generate a trimer around the z axis
+    let operations = [
+        [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ],
+        [
+            [-0.5, -0.866_025, 0.0, 181.196],
+            [0.866_025, -0.5, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ],
+        [
+            [-0.5, 0.866_025, 0.0, 90.598],
+            [-0.866_025, -0.5, 0.0, 156.920_34],
+            [0.0, 0.0, 1.0, 0.0],
+        ],
+    ];
+    let chains: Vec<Chain> = operations
+        .iter()
+        .map(|matrix| {
+            let mut chain = pdb.chain(0).unwrap().clone();
+            chain.apply_transformation(&TransformationMatrix::from_matrix(*matrix));
+            chain
+        })
+        .collect();
+    let symmetry = geometry::point_group(&chains.iter().collect::<Vec<_>>()).unwrap();
+    assert_eq!(symmetry.point_group, geometry::PointGroup::Cyclic(3));
+    assert!(symmetry.axis.2.abs() > 0.999);
+}
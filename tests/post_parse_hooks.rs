@@ -0,0 +1,53 @@
+// Test transformations run after parsing, before validation.
+
+use pdbtbx::*;
+
+fn strip_hydrogens(pdb: &mut PDB) -> Vec<PDBError> {
+    pdb.remove_atoms_by(|atom| atom.element() == Some(&Element::H));
+    Vec::new()
+}
+
+fn count_atoms(pdb: &mut PDB) -> Vec<PDBError> {
+    vec![PDBError::new(
+        ErrorLevel::GeneralWarning,
+        "Atom count",
+        format!("{} atoms", pdb.atom_count()),
+        Context::None,
+    )]
+}
+
+fn empty(pdb: &mut PDB) -> Vec<PDBError> {
+    pdb.remove_atoms_by(|_| true);
+    Vec::new()
+}
+
+#[test]
+fn hooks_in_order() {
+    for file in ["example-pdbs/1ubq.pdb", "example-pdbs/1ubq.cif"] {
+        let (pdb, errors) = ReadOptions::new()
+            .set_level(StrictnessLevel::Loose)
+            .register_hook(strip_hydrogens)
+            .register_hook(count_atoms)
+            .read(file)
+            .unwrap();
+        assert!(pdb.atoms().all(|atom| atom.element() != Some(&Element::H)));
+        let count = errors
+            .iter()
+            .find(|e| e.short_description() == "Atom count")
+            .unwrap();
+        assert_eq!(
+            count.long_description(),
+            format!("{} atoms", pdb.atom_count())
+        );
+    }
+}
+
+#[test]
+fn hooks_before_validation() {
+    let errors = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .register_hook(empty)
+        .read("example-pdbs/1ubq.pdb")
+        .unwrap_err();
+    assert!(errors.iter().any(|e| e.short_description() == "No Atoms"));
+}
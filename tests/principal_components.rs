@@ -0,0 +1,50 @@
+// Test the principal component analysis of the coordinate variation in ensembles
+
+mod common;
+
+use common::open;
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[test]
+fn ensemble() {
+    let pdb = open("example-pdbs/pTLS-6484.pdb");
+    let pca = pdb.principal_components().unwrap();
+    let components = pca.eigenvalues.len();
+    assert!(components > 0 && components < 50);
+    assert_eq!(pca.eigenvectors.len(), components);
+    assert_eq!(pca.projections.len(), 50);
+    assert!(pca.eigenvalues.windows(2).all(|w| w[0] >= w[1]));
+
+    // The components are orthonormal
+    for (i, a) in pca.eigenvectors.iter().enumerate().take(5) {
+        assert_eq!(a.len(), pca.mean.len() * 3);
+        for (j, b) in pca.eigenvectors.iter().enumerate().take(5) {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!((dot(a, b) - expected).abs() < 1e-6);
+        }
+    }
+
+    // The variance of the projections is the eigenvalue
+    for (component, value) in pca.eigenvalues.iter().enumerate().take(5) {
+        let variance: f64 = pca
+            .projections
+            .iter()
+            .map(|p| p[component].powi(2))
+            .sum::<f64>()
+            / 49.0;
+        assert!((variance - value).abs() < 1e-6 * value.max(1.0));
+        let mean: f64 = pca.projections.iter().map(|p| p[component]).sum::<f64>();
+        assert!(mean.abs() < 1e-6);
+    }
+    let explained = pca.explained_variance();
+    assert!((explained.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn too_few_models() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    assert_eq!(pdb.principal_components(), None);
+}
@@ -0,0 +1,96 @@
+// Test the Ramachandran validation of full structures.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn ramachandran_report() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let report = ramachandran(&pdb);
+    // 76 residues, without phi for the first and psi for the last residue
+    assert_eq!(report.residues.len(), 74);
+    assert_eq!(
+        report.favored_count() + report.allowed_count() + report.outlier_count(),
+        74
+    );
+    assert!(report.favored_fraction() > 0.9);
+    assert_eq!(report.warnings().len(), report.outlier_count());
+
+    // The first residue of the helix (Ile 23 to Glu 34)
+    let (_, phi, psi, class) = report
+        .residues
+        .iter()
+        .find(|r| r.0.serial_number() == 25)
+        .unwrap();
+    assert!((-80.0..-40.0).contains(phi) && (-60.0..-20.0).contains(psi));
+    assert_eq!(*class, RamachandranClass::Favored);
+}
+
+#[test]
+fn classification() {
+    assert_eq!(
+        RamachandranClass::classify("ALA", -63.0, -43.0),
+        RamachandranClass::Favored
+    );
+    assert_eq!(
+        RamachandranClass::classify("ALA", -120.0, 130.0),
+        RamachandranClass::Favored
+    );
+    assert_eq!(
+        RamachandranClass::classify("ALA", 100.0, -20.0),
+        RamachandranClass::Allowed
+    );
+    assert_eq!(
+        RamachandranClass::classify("ALA", 60.0, -120.0),
+        RamachandranClass::Outlier
+    );
+    // Glycine can adopt the mirrored conformations
+    assert_eq!(
+        RamachandranClass::classify("GLY", 80.0, -170.0),
+        RamachandranClass::Favored
+    );
+    // Proline has a restricted phi
+    assert_eq!(
+        RamachandranClass::classify("PRO", -140.0, 150.0),
+        RamachandranClass::Outlier
+    );
+}
+
+#[test]
+fn chain_angles() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let report = ramachandran(&pdb);
+    let chain = pdb.chain(0).unwrap();
+    let angles: Vec<_> = chain
+        .ramachandran()
+        .filter_map(|(residue, phi, psi)| Some((residue, phi?, psi?)))
+        .collect();
+    assert_eq!(angles.len(), report.residues.len());
+    assert!(angles
+        .iter()
+        .zip(&report.residues)
+        .all(|(a, r)| a.0 == r.0 && a.1 == r.1 && a.2 == r.2));
+
+    // Without the CA of residue 10 its phi and psi are undefined, the psi of residue 9 only
+    // depends on the N of residue 10
+    let residues: Vec<&Residue> = chain.residues().collect();
+    assert!(residues[9]
+        .phi_psi(Some(residues[8]), Some(residues[10]))
+        .0
+        .is_some());
+    pdb.chain_mut(0)
+        .unwrap()
+        .residue_mut(9)
+        .unwrap()
+        .remove_atoms_by(|atom| atom.name() == "CA");
+    let chain = pdb.chain(0).unwrap();
+    let residues: Vec<&Residue> = chain.residues().collect();
+    assert_eq!(
+        residues[9].phi_psi(Some(residues[8]), Some(residues[10])),
+        (None, None)
+    );
+    let (phi, psi) = residues[8].phi_psi(Some(residues[7]), Some(residues[9]));
+    assert!(phi.is_some() && psi.is_some());
+}
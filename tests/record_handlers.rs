@@ -0,0 +1,63 @@
+// Test custom handlers for site specific record types.
+
+use pdbtbx::*;
+
+const INPUT: &str = "USER  MOD reduce.3.24.130724 H: found=0, std=0, add=10, rem=0, adj=1
+EXPDTL    SOLUTION NMR
+ATOM      1  CA  GLY A   1      26.266  25.413   2.842  1.00 20.00           C
+USER  bad
+END
+";
+
+fn user(linenumber: usize, line: &str, pdb: &mut PDB) -> Vec<PDBError> {
+    match line[6..].split_whitespace().nth(1) {
+        Some(program) if line[6..].starts_with("MOD") => {
+            pdb.add_remark(999, format!("USER RECORD {program}"))
+                .unwrap();
+            Vec::new()
+        }
+        _ => vec![PDBError::new(
+            ErrorLevel::LooseWarning,
+            "Invalid USER record",
+            "Could not find the program in this USER record.",
+            Context::full_line(linenumber, line),
+        )],
+    }
+}
+
+fn expdtl(_linenumber: usize, line: &str, pdb: &mut PDB) -> Vec<PDBError> {
+    pdb.identifier = Some(line[10..].trim().to_string());
+    Vec::new()
+}
+
+#[test]
+fn handlers() {
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .register_handler("USER  ", user)
+        .register_handler("EXPDTL", expdtl)
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.atom_count(), 1);
+    assert_eq!(pdb.identifier.as_deref(), Some("SOLUTION NMR"));
+    assert_eq!(
+        pdb.remarks().collect::<Vec<_>>(),
+        vec![&(999, "USER RECORD reduce.3.24.130724".to_string())]
+    );
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].short_description(), "Invalid USER record");
+    assert_eq!(errors[0].context(), &Context::full_line(4, "USER  bad"));
+}
+
+#[test]
+fn no_handlers() {
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.atom_count(), 1);
+    assert_eq!(pdb.identifier, None);
+    assert!(errors.is_empty());
+}
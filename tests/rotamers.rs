@@ -0,0 +1,57 @@
+// Test the side chain rotamer validation of full structures.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn rotamer_report() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let report = rotamers(&pdb);
+    // Ubiquitin has 76 residues of which 6 glycines and 2 alanines
+    assert!(report.residues.len() > 60 && report.residues.len() <= 68);
+    assert!(report.outlier_fraction() < 0.05);
+    assert_eq!(report.warnings().len(), report.outlier_count());
+    for (residue, chis, rotamer) in &report.residues {
+        assert!(!matches!(residue.name(), Some("GLY" | "ALA")));
+        assert!(!chis.is_empty());
+        assert_eq!(
+            rotamer.is_none(),
+            report.outliers().any(|o| o.0 == *residue)
+        );
+    }
+}
+
+#[test]
+fn outlier() {
+    let serine = |chi1: f64| {
+        let mut chain = Chain::new("A").unwrap();
+        let t = -chi1.to_radians();
+        for (serial, name, x, y, z) in [
+            (1, "N", 1.0, 0.0, 0.0),
+            (2, "CA", 0.0, 0.0, 0.0),
+            (3, "CB", 0.0, 1.5, 0.0),
+            (4, "OG", t.cos(), 2.5, t.sin()),
+        ] {
+            let atom = Atom::new(false, serial, name, x, y, z, 1.0, 0.0, &name[..1], 0).unwrap();
+            chain.add_atom(atom, (1, None), ("SER", None));
+        }
+        let mut model = Model::new(0);
+        model.add_chain(chain);
+        let mut pdb = PDB::new();
+        pdb.add_model(model);
+        pdb
+    };
+
+    let pdb = serine(-65.0);
+    let report = rotamers(&pdb);
+    assert!((report.residues[0].1[0] + 65.0).abs() < 1e-6);
+    assert_eq!(report.residues[0].2, Some("m"));
+    assert_eq!(report.outlier_count(), 0);
+
+    let pdb = serine(120.0);
+    let report = rotamers(&pdb);
+    assert_eq!(report.residues[0].2, None);
+    assert_eq!(report.warnings()[0].short_description(), "Rotamer outlier");
+}
@@ -0,0 +1,70 @@
+// Test reading and writing the secondary structure given by HELIX and SHEET records
+
+mod common;
+
+use common::open_with_errors;
+use pdbtbx::*;
+use std::io::BufWriter;
+
+#[test]
+fn records() {
+    let (pdb, errors) = open_with_errors("example-pdbs/1yyf.pdb");
+    assert_eq!(pdb.helices().count(), 56);
+    assert_eq!(
+        pdb.sheets().map(|sheet| sheet.strands.len()).sum::<usize>(),
+        40
+    );
+    assert!(!errors
+        .iter()
+        .any(|e| e.short_description() == "MASTER checksum failed"));
+
+    let helix = pdb.helices().next().unwrap();
+    assert_eq!(helix.id, "1");
+    assert_eq!(helix.class, 1);
+    assert_eq!((helix.start.id(), helix.end.id()), ((5, None), (14, None)));
+    assert_eq!(helix.length, 10);
+    let sheet = pdb.sheet("A").unwrap();
+    assert_eq!(sheet.strands.len(), 5);
+    assert_eq!(sheet.strands[1].sense, 1);
+    assert_eq!(
+        (
+            sheet.strands[0].start.name.as_str(),
+            sheet.strands[0].end.id()
+        ),
+        ("PHE", (82, None))
+    );
+}
+
+#[test]
+fn residue_annotations() {
+    let (pdb, _) = open_with_errors("example-pdbs/1yyf.pdb");
+    match pdb.secondary_structure("A", (80, None)) {
+        Some(SecondaryStructure::Strand(sheet, strand)) => {
+            assert_eq!(sheet.id, "A");
+            assert_eq!(strand.number, 1);
+        }
+        other => panic!("Residue 80 should be in a strand, found {other:?}"),
+    }
+    assert!(matches!(
+        pdb.secondary_structure("A", (155, None)),
+        Some(SecondaryStructure::Helix(_))
+    ));
+    assert!(pdb.secondary_structure("Z", (155, None)).is_none());
+}
+
+#[test]
+fn helices_and_sheets_survive_saving() {
+    let (pdb, _) = open_with_errors("example-pdbs/1yyf.pdb");
+    let mut output = Vec::new();
+    save_pdb_raw(&pdb, BufWriter::new(&mut output), StrictnessLevel::Medium);
+    let (saved, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(output.as_slice())
+        .unwrap();
+    assert!(pdb.helices().eq(saved.helices()));
+    assert!(pdb.sheets().eq(saved.sheets()));
+    assert!(!errors
+        .iter()
+        .any(|e| e.long_description().contains("HELIX") || e.long_description().contains("SHEET")));
+}
@@ -0,0 +1,38 @@
+// Test assigning chains from the segment identifiers of atoms, as written by CHARMM and NAMD.
+
+use pdbtbx::*;
+
+const INPUT: &str = "ATOM      1  N   GLY     1      26.266  25.413   2.842  1.00 20.00      PROA N
+ATOM      2  CA  GLY     1      27.475  26.142   3.271  1.00 20.00      PROA C
+ATOM      3  N   ALA     1      31.266  25.413   2.842  1.00 20.00      PROB N
+ATOM      4  CA  ALA     1      32.475  26.142   3.271  1.00 20.00      PROB C
+ATOM      5  OH2 TIP3    1      36.266  25.413   2.842  1.00 20.00      SOLV O
+ATOM      6  N   SER     2      33.266  25.413   2.842  1.00 20.00           N
+END
+";
+
+#[test]
+fn chains_from_segments() {
+    let (pdb, _errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .set_chains_from_segments(true)
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    let ids: Vec<&str> = pdb.chains().map(Chain::id).collect();
+    assert_eq!(ids, ["PROA", "PROB", "SOLV", "A"]);
+    assert_eq!(pdb.chains().next().unwrap().atom_count(), 2);
+    assert_eq!(pdb.chains().next().unwrap().residue_count(), 1);
+}
+
+#[test]
+fn chain_column_by_default() {
+    let (pdb, _errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(INPUT.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.chain_count(), 1);
+    assert_eq!(pdb.chains().next().unwrap().id(), "A");
+    assert_eq!(pdb.residue_count(), 2);
+}
@@ -0,0 +1,97 @@
+// Test composing atom selections with set operations
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn compose() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let alpha = pdb.select(Search::Single(Term::AtomName("CA".to_owned())));
+    assert_eq!(alpha.size(), pdb.total_atom_count());
+    assert_eq!(alpha.len(), 76);
+
+    let first = pdb.select(Search::Single(Term::ResidueSerialNumberRange(1, 10)));
+    let both = &alpha & &first;
+    assert_eq!(both.len(), 10);
+    assert!(pdb
+        .selected(&both)
+        .all(|h| h.atom().name() == "CA" && h.residue().serial_number() <= 10));
+    assert_eq!((&alpha | &first).len(), alpha.len() + first.len() - 10);
+    assert_eq!((&first - &alpha).len(), first.len() - 10);
+    assert_eq!((!&alpha).len(), pdb.total_atom_count() - 76);
+
+    let waters = pdb.select_by(|h| h.conformer().name() == "HOH");
+    let protein = !&waters;
+    assert_eq!(
+        pdb.selected(&protein).count(),
+        pdb.atoms().filter(|a| !a.hetero()).count()
+    );
+
+    // The ids follow the order of the atoms
+    let ids: Vec<usize> = alpha.iter().collect();
+    let atoms: Vec<&Atom> = pdb.atoms().collect();
+    assert!(ids.iter().all(|id| atoms[*id].name() == "CA"));
+}
+
+#[test]
+fn viewer_syntax() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let residues =
+        pdb.select(Term::ResidueSerialNumberRange(1, 3) | Term::ResidueSerialNumberRange(5, 5));
+    assert_eq!(residues.to_pymol(&pdb), "(chain A and resi 1-3+5)");
+    assert_eq!(residues.to_vmd(&pdb), "(chain A and resid 1 to 3 5)");
+
+    let atoms = pdb.select(
+        Term::ResidueSerialNumber(7)
+            & (Term::AtomName("CA".to_owned()) | Term::AtomName("CB".to_owned())),
+    );
+    assert_eq!(atoms.to_pymol(&pdb), "(chain A and resi 7 and name CA+CB)");
+    assert_eq!(atoms.to_vmd(&pdb), "(chain A and resid 7 and name CA CB)");
+
+    let both = &residues | &atoms;
+    assert_eq!(
+        both.to_pymol(&pdb),
+        "(chain A and resi 1-3+5) or (chain A and resi 7 and name CA+CB)"
+    );
+
+    assert_eq!(pdb.select(Search::Known(true)).to_pymol(&pdb), "all");
+    assert_eq!(pdb.select(Search::Known(false)).to_vmd(&pdb), "none");
+}
+
+#[test]
+fn find_with_operators() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let found: Vec<_> = pdb
+        .find(
+            Term::ChainId("A".to_owned())
+                & Term::ResidueSerialNumberRange(20, 40)
+                & Term::AtomName("CA".to_owned()),
+        )
+        .collect();
+    assert_eq!(found.len(), 21);
+    assert!(found.iter().all(|h| h.chain().id() == "A"
+        && (20..=40).contains(&h.residue().serial_number())
+        && h.atom().name() == "CA"));
+
+    let either = pdb
+        .find(Term::ConformerName("GLY".to_owned()) | Term::ConformerName("ALA".to_owned()))
+        .count();
+    let glycine = pdb
+        .find(Search::Single(Term::ConformerName("GLY".to_owned())))
+        .count();
+    let alanine = pdb
+        .find(Search::Single(Term::ConformerName("ALA".to_owned())))
+        .count();
+    assert_eq!(either, glycine + alanine);
+
+    let not_water = pdb.find(!Term::ConformerName("HOH".to_owned())).count();
+    assert_eq!(
+        not_water,
+        pdb.atoms().count()
+            - pdb
+                .find(Search::Single(Term::ConformerName("HOH".to_owned())))
+                .count()
+    );
+}
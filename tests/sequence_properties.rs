@@ -0,0 +1,20 @@
+// Test the physicochemical properties calculated from the sequence of a chain.
+
+mod common;
+
+use common::open;
+
+#[test]
+fn protein_properties() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let chain = pdb.chain(0).unwrap();
+    // ProtParam reports a pI of 6.56 with the Bjellqvist pKa values, the EMBOSS values give 7.5
+    let pi = chain.isoelectric_point();
+    assert!(pi > 6.5 && pi < 8.0);
+    // ProtParam reports a GRAVY of -0.489, the chain has one tyrosine and no cysteines
+    assert!((chain.gravy().unwrap() + 0.489).abs() < 0.01);
+    let coefficient = chain.extinction_coefficient();
+    assert_eq!(coefficient.reduced, 1490.0);
+    assert_eq!(coefficient.oxidized, 1490.0);
+    assert_eq!(chain.hydropathy_profile(9).len(), 76 - 8);
+}
@@ -0,0 +1,120 @@
+// Test reading and writing atom serial numbers which do not fit in their columns.
+
+use pdbtbx::*;
+
+fn atom_line(serial: &str, residue: usize) -> String {
+    format!("ATOM  {serial:>5}  CA  ALA A{residue:>4}      26.266  25.413   2.842  1.00 10.38           C\n")
+}
+
+#[test]
+fn reconstruct_serials() {
+    for overflow in [["*****", "*****"], ["186a0", "186a1"]] {
+        let input = [
+            atom_line("99998", 1),
+            atom_line("99999", 2),
+            atom_line(overflow[0], 3),
+            atom_line(overflow[1], 4),
+        ]
+        .concat();
+
+        assert!(ReadOptions::new()
+            .set_format(Format::Pdb)
+            .read_raw(input.as_bytes())
+            .is_err());
+
+        let (pdb, errors) = ReadOptions::new()
+            .set_format(Format::Pdb)
+            .set_reconstruct_serials(true)
+            .read_raw(input.as_bytes())
+            .unwrap();
+        let serials: Vec<_> = pdb.atoms().map(Atom::serial_number).collect();
+        assert_eq!(serials, [99_998, 99_999, 100_000, 100_001]);
+        assert_eq!(errors.len(), 1);
+    }
+}
+
+#[test]
+fn reconstruct_serials_in_models() {
+    let model = [
+        atom_line("99998", 1),
+        atom_line("99999", 2),
+        atom_line("*****", 3),
+        atom_line("*****", 4),
+    ]
+    .concat();
+    let input = format!("MODEL        1\n{model}ENDMDL\nMODEL        2\n{model}ENDMDL\nEND\n");
+
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .set_reconstruct_serials(true)
+        .read_raw(input.as_bytes())
+        .unwrap();
+    assert_eq!(pdb.model_count(), 2);
+    for model in pdb.models() {
+        let serials: Vec<_> = model.atoms().map(Atom::serial_number).collect();
+        assert_eq!(serials, [99_998, 99_999, 100_000, 100_001]);
+    }
+    assert!(errors
+        .iter()
+        .all(|e| e.short_description() == "Atom serial numbers reconstructed"));
+}
+
+#[test]
+fn write_overflowing_serials() {
+    for (overflow, expected) in [
+        (SerialOverflow::Stars, "*****"),
+        (SerialOverflow::Hexadecimal, "186a0"),
+        (SerialOverflow::Hybrid36, "A0000"),
+        (SerialOverflow::Wrap, "0    "),
+    ] {
+        let mut writer = PDBWriter::start_with_level(Vec::new(), StrictnessLevel::Loose);
+        writer.set_serial_overflow(overflow);
+        let atom = Atom::new(false, 100_000, "CA", 0.0, 0.0, 0.0, 1.0, 0.0, "C", 0).unwrap();
+        writer
+            .write_atom(&atom, None, "ALA", "A", (1, None))
+            .unwrap();
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(&output[6..11], expected);
+    }
+}
+
+#[test]
+fn hybrid36_serials() {
+    let input = format!(
+        "{}{}{}",
+        "ATOM  99999  CA  ALA A9999      26.266  25.413   2.842  1.00 10.38           C\n",
+        "ATOM  A0000  CA  ALA AA000      26.266  25.413   2.842  1.00 10.38           C\n",
+        "CONECT99999A0000\n",
+    );
+    let (pdb, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(input.as_bytes())
+        .unwrap();
+    let serials: Vec<_> = pdb.atoms().map(Atom::serial_number).collect();
+    assert_eq!(serials, [99_999, 100_000]);
+    let residues: Vec<_> = pdb.residues().map(Residue::serial_number).collect();
+    assert_eq!(residues, [9_999, 10_000]);
+    assert_eq!(pdb.bonds().count(), 1);
+
+    let mut options = WriteOptions::new();
+    options
+        .set_level(StrictnessLevel::Loose)
+        .set_serial_overflow(SerialOverflow::Hybrid36);
+    let mut output = Vec::new();
+    options.write_raw(&pdb, std::io::BufWriter::new(&mut output));
+    let output = String::from_utf8(output).unwrap();
+    assert!(output
+        .lines()
+        .any(|l| l.starts_with("ATOM  A0000  CA  ALA AA000")));
+    let (read, _) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(output.as_bytes())
+        .unwrap();
+    assert_eq!(
+        read.atoms().map(Atom::serial_number).collect::<Vec<_>>(),
+        serials
+    );
+}
@@ -0,0 +1,91 @@
+// Test parsing the SITE records together with their REMARK 800 descriptions.
+
+mod common;
+
+use common::open_with_errors;
+use pdbtbx::*;
+
+#[test]
+fn sites_with_descriptions() {
+    let (pdb, errors) = open_with_errors("example-pdbs/1kmk.pdb");
+    assert!(errors
+        .iter()
+        .all(|e| !e.long_description().contains("SITE records")));
+    assert_eq!(pdb.sites().count(), 2);
+    let ac1 = pdb.site("AC1").unwrap();
+    assert_eq!(ac1.residues.len(), 10);
+    assert_eq!(ac1.evidence_code.as_deref(), Some("SOFTWARE"));
+    assert_eq!(
+        ac1.description.as_deref(),
+        Some("BINDING SITE FOR RESIDUE SEC A 502")
+    );
+    assert_eq!(ac1.residues[0].name, "ALA");
+    assert_eq!(ac1.residues[0].chain, "A");
+    assert_eq!(ac1.residues[0].id(), (30, None));
+    assert!(ac1.contains("A", (732, None)));
+    assert!(!ac1.contains("B", (732, None)));
+    let ac2 = pdb.site("AC2").unwrap();
+    assert_eq!(ac2.residues.len(), 16);
+    assert_eq!(
+        ac2.description.as_deref(),
+        Some("BINDING SITE FOR RESIDUE PLP A 500")
+    );
+    assert!(pdb.site("AC3").is_none());
+
+    let (pdb, _) = open_with_errors("example-pdbs/3b5j.pdb");
+    let site = pdb.sites().next().unwrap();
+    assert_eq!(site.residues.len(), 27);
+    assert_eq!(
+        site.description.as_deref(),
+        Some("BINDING SITE FOR RESIDUE 12D A 708")
+    );
+}
+
+#[test]
+fn sites_survive_saving() {
+    let (mut pdb, _) = open_with_errors("example-pdbs/1ubq.pdb");
+    let residue = |name: &str, serial_number, insertion_code: Option<&str>| SiteResidue {
+        name: name.to_string(),
+        chain: "A".to_string(),
+        serial_number,
+        insertion_code: insertion_code.map(str::to_string),
+    };
+    pdb.add_site(Site::new(
+        "AC1",
+        vec![
+            residue("LYS", 6, None),
+            residue("LEU", 8, Some("A")),
+            residue("ILE", 44, None),
+            residue("VAL", 70, None),
+            residue("HOH", 101, None),
+        ],
+    ));
+    let mut output = Vec::new();
+    save_pdb_raw(
+        &pdb,
+        std::io::BufWriter::new(&mut output),
+        StrictnessLevel::Medium,
+    );
+    let (read, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .read_raw(output.as_slice())
+        .unwrap();
+    assert!(errors
+        .iter()
+        .all(|e| !e.long_description().contains("SITE records")));
+    assert_eq!(
+        read.sites().collect::<Vec<_>>(),
+        pdb.sites().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn discard_metadata() {
+    let (pdb, _) = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .set_discard_metadata(true)
+        .read("example-pdbs/1kmk.pdb")
+        .unwrap();
+    assert_eq!(pdb.sites().count(), 0);
+}
@@ -0,0 +1,55 @@
+// Test skipping the validation after parsing for trusted inputs.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+const INPUT: &str = "SEQRES   1 A    3  MET GLN ILE
+ATOM      1  CA  MET A   1      27.340  24.430   2.614  1.00  9.67           C
+ATOM      2  CA  GLN A   2      26.266  25.413   2.842  1.00 10.38           C
+MASTER        0    0    0    0    0    0    0    0    5    0    0    1
+END
+";
+
+fn read(skip: bool) -> (PDB, Vec<PDBError>) {
+    ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Loose)
+        .set_skip_validation(skip)
+        .read_raw(INPUT.as_bytes())
+        .unwrap()
+}
+
+#[test]
+fn skip_validation() {
+    let (pdb, errors) = read(false);
+    assert!(errors
+        .iter()
+        .any(|e| e.short_description() == "MASTER checksum failed"));
+    assert_eq!(pdb.residue_count(), 3);
+
+    let (pdb, errors) = read(true);
+    assert!(errors.is_empty());
+    assert_eq!(pdb.residue_count(), 2);
+    assert_eq!(pdb.atom_count(), 2);
+}
+
+#[test]
+fn same_structure() {
+    for file in ["example-pdbs/1ubq.pdb", "example-pdbs/1ubq.cif"] {
+        let validated = open(file);
+        let (skipped, _) = ReadOptions::new()
+            .set_level(StrictnessLevel::Loose)
+            .set_skip_validation(true)
+            .read(file)
+            .unwrap();
+        assert_eq!(validated, skipped);
+    }
+
+    let empty = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_skip_validation(true)
+        .read_raw("END\n".as_bytes());
+    assert_eq!(empty.unwrap().0.atom_count(), 0);
+}
@@ -0,0 +1,35 @@
+// Test SMILES export of ligands.
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn ligand() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let ligand = pdb.residues().find(|r| r.name() == Some("PLP")).unwrap();
+    // Pyridoxal phosphate, with the aldehyde carbon bound to the protein
+    assert_eq!(
+        ligand.to_smiles().unwrap(),
+        "N=1C(C)=C(O)C(C)=C(C1)COP(=O)(O)O"
+    );
+
+    let atoms: Vec<&Atom> = ligand.atoms().collect();
+    let bonds = chemistry::infer_bonds(&atoms);
+    assert_eq!(bonds.len(), 15);
+    assert_eq!(
+        bonds
+            .iter()
+            .filter(|(_, _, order)| *order == chemistry::BondOrder::Double)
+            .count(),
+        4
+    );
+}
+
+#[test]
+fn waters() {
+    let pdb = open("example-pdbs/1kmk.pdb");
+    let water = pdb.residues().find(|r| r.name() == Some("HOH")).unwrap();
+    assert_eq!(water.to_smiles().unwrap(), "O");
+}
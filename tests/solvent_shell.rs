@@ -0,0 +1,50 @@
+// Test the detection of the water molecules around a selection
+#![cfg(feature = "rstar")]
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn waters_near_protein() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let protein = Search::Single(Term::ResidueSerialNumberRange(1, 76));
+    let shell = pdb.solvent_shell(protein.clone(), 3.5);
+    assert!(!shell.is_empty());
+    for water in &shell {
+        assert!(water.conformers().all(Conformer::is_water));
+        let closest = pdb
+            .residues()
+            .filter(|r| r.serial_number() <= 76)
+            .flat_map(Residue::atoms)
+            .map(|a| {
+                water
+                    .atoms()
+                    .map(|w| w.distance(a))
+                    .fold(f64::MAX, f64::min)
+            })
+            .fold(f64::MAX, f64::min);
+        assert!(closest <= 3.5);
+    }
+    // Every water is close to the protein with a large enough cutoff
+    let waters = pdb
+        .residues()
+        .filter(|r| r.conformers().any(Conformer::is_water))
+        .count();
+    assert_eq!(pdb.solvent_shell(protein.clone(), 100.0).len(), waters);
+    // Waters in the selection are not part of its shell
+    assert!(pdb
+        .solvent_shell(Search::Single(Term::ConformerName("HOH".to_owned())), 100.0)
+        .is_empty());
+
+    let bridging = pdb.bridging_waters(
+        Search::Single(Term::ResidueSerialNumberRange(1, 30)),
+        Search::Single(Term::ResidueSerialNumberRange(31, 76)),
+        3.5,
+    );
+    assert!(bridging.len() <= shell.len());
+    assert!(bridging
+        .iter()
+        .all(|w| shell.iter().any(|s| std::ptr::eq(*w, *s))));
+}
@@ -0,0 +1,96 @@
+// Test the canonical ordering of the hierarchy types and their sort keys
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn full_sort_follows_keys() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    let original = pdb.clone();
+    let mut chain = pdb.chain(0).unwrap().clone();
+    chain.set_id("B");
+    pdb.model_mut(0).unwrap().add_chain(chain);
+    for chain in pdb.chains_mut() {
+        let mut residues: Vec<Residue> = chain.residues().rev().cloned().collect();
+        residues.iter_mut().for_each(|r| {
+            let atoms: Vec<Atom> = r.atoms().rev().cloned().collect();
+            let mut conformer = Conformer::new(r.conformer(0).unwrap().name(), None, None).unwrap();
+            conformer.extend(atoms);
+            *r = Residue::new(r.serial_number(), r.insertion_code(), Some(conformer)).unwrap();
+        });
+        *chain = Chain::from_iter(chain.id(), residues.into_iter()).unwrap();
+    }
+    let chains: Vec<Chain> = pdb.chains().rev().cloned().collect();
+    *pdb.model_mut(0).unwrap() = Model::from_iter(0, chains.into_iter());
+    assert_eq!(pdb.chain(0).unwrap().id(), "B");
+
+    pdb.full_sort();
+    let keys: Vec<&str> = pdb.chains().map(Chain::sort_key).collect();
+    assert_eq!(keys, ["A", "B"]);
+    for chain in pdb.chains() {
+        assert!(chain
+            .residues()
+            .zip(chain.residues().skip(1))
+            .all(|(a, b)| a.sort_key() < b.sort_key()));
+    }
+    assert!(pdb
+        .chain(0)
+        .unwrap()
+        .atoms()
+        .zip(pdb.chain(0).unwrap().atoms().skip(1))
+        .all(|(a, b)| a.sort_key() < b.sort_key()));
+    assert!(pdb
+        .chain(0)
+        .unwrap()
+        .approx_eq(original.chain(0).unwrap(), 0.0));
+}
+
+#[test]
+fn insertion_codes() {
+    let residue = |n, i| Residue::new(n, i, None).unwrap();
+    let mut residues = [
+        residue(2, None),
+        residue(1, Some("B")),
+        residue(-1, None),
+        residue(1, None),
+        residue(1, Some("A")),
+    ];
+    residues.sort();
+    let keys: Vec<(isize, Option<&str>)> = residues.iter().map(Residue::sort_key).collect();
+    assert_eq!(
+        keys,
+        [
+            (-1, None),
+            (1, None),
+            (1, Some("A")),
+            (1, Some("B")),
+            (2, None)
+        ]
+    );
+    assert_eq!(
+        residues.binary_search_by_key(&(1, Some("B")), Residue::sort_key),
+        Ok(3)
+    );
+
+    let mut chains: Vec<Chain> = ["B", "AA", "A"]
+        .iter()
+        .map(|id| Chain::new(id).unwrap())
+        .collect();
+    chains.sort();
+    assert_eq!(
+        chains.iter().map(Chain::sort_key).collect::<Vec<_>>(),
+        ["A", "AA", "B"]
+    );
+
+    let mut models = [Model::new(2), Model::new(1)];
+    models.sort();
+    assert_eq!(models[0].sort_key(), 1);
+    let mut conformers = [
+        Conformer::new("SER", Some("B"), None).unwrap(),
+        Conformer::new("SER", None, None).unwrap(),
+    ];
+    conformers.sort();
+    assert_eq!(conformers[0].sort_key(), ("SER", None));
+}
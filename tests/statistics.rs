@@ -0,0 +1,94 @@
+// Test the quality metrics of structures
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+#[test]
+fn counts_per_model() {
+    let pdb = open("example-pdbs/pTLS-6484.pdb");
+    let statistics = pdb.statistics();
+    assert_eq!(statistics.models.len(), pdb.model_count());
+    for (model, counts) in pdb.models().zip(&statistics.models) {
+        assert_eq!(counts.serial_number, model.serial_number());
+        assert_eq!(counts.atom_count, model.atom_count());
+        assert_eq!(counts.residue_count, model.residue_count());
+        assert_eq!(counts.chain_count, model.chain_count());
+    }
+}
+
+#[test]
+fn b_factors() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let b_factors = pdb.statistics().b_factors.unwrap();
+    let values: Vec<f64> = pdb.model(0).unwrap().atoms().map(Atom::b_factor).collect();
+    assert!(values
+        .iter()
+        .all(|b| (b_factors.min..=b_factors.max).contains(b)));
+    assert!(values.contains(&b_factors.min) && values.contains(&b_factors.max));
+    #[allow(clippy::cast_precision_loss)]
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    assert!((b_factors.mean - mean).abs() < 1e-9);
+    assert_eq!(PDB::new().statistics().b_factors, None);
+}
+
+#[test]
+fn anomalies() {
+    let mut pdb = open("example-pdbs/1ubq.pdb");
+    assert!(pdb.statistics().is_clean());
+
+    let residue = pdb.residue_mut(4).unwrap();
+    residue.conformer_mut(0).unwrap().remove_atom_by_name("CA");
+    residue.atom_mut(0).unwrap().set_occupancy(0.0).unwrap();
+    let position = pdb.residue(20).unwrap().atom(0).unwrap().pos();
+    pdb.residue_mut(30)
+        .unwrap()
+        .atom_mut(3)
+        .unwrap()
+        .set_pos((position.0 + 0.5, position.1, position.2))
+        .unwrap();
+
+    let statistics = pdb.statistics();
+    assert!(!statistics.is_clean());
+    assert_eq!(statistics.missing_backbone.len(), 1);
+    assert_eq!(statistics.missing_backbone[0].chain, "A");
+    assert_eq!(statistics.missing_backbone[0].residue.serial_number(), 5);
+    assert_eq!(statistics.missing_backbone[0].missing, ["CA"]);
+    assert_eq!(statistics.occupancy_anomalies.len(), 1);
+    assert_eq!(
+        statistics.occupancy_anomalies[0].kind,
+        analysis::OccupancyAnomalyKind::Zero
+    );
+    // The moved atom is also close to the atoms bonded to the atom it was placed next to
+    let moved = pdb.residue(30).unwrap().atom(3).unwrap();
+    assert!(statistics
+        .clashes
+        .iter()
+        .all(|clash| clash.atom_a == moved || clash.atom_b == moved));
+    let clash = &statistics.clashes[0];
+    assert!((clash.distance - 0.5).abs() < 1e-6);
+    assert!(clash.overlap() > 0.0);
+}
+
+#[test]
+fn over_occupied() {
+    let over_occupied = |pdb: &PDB| {
+        pdb.statistics()
+            .occupancy_anomalies
+            .iter()
+            .filter(|anomaly| anomaly.kind == analysis::OccupancyAnomalyKind::OverOccupied)
+            .count()
+    };
+    let mut pdb = open("example-pdbs/7az6.pdb");
+    assert_eq!(over_occupied(&pdb), 0);
+
+    let residue = pdb
+        .residues_mut()
+        .find(|residue| residue.conformer_count() > 1)
+        .unwrap();
+    for conformer in residue.conformers_mut() {
+        conformer.atom_mut(0).unwrap().set_occupancy(0.8).unwrap();
+    }
+    assert_eq!(over_occupied(&pdb), 1);
+}
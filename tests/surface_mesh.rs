@@ -0,0 +1,91 @@
+// Test generating molecular surface meshes and saving them as OBJ and PLY files
+
+mod common;
+
+use common::{open, temp_path};
+use std::collections::HashMap;
+
+use pdbtbx::geometry::{Mesh, SurfaceType};
+use pdbtbx::*;
+
+/// Check that every edge is shared by exactly two triangles in opposite directions
+fn closed(mesh: &Mesh) -> bool {
+    let mut edges: HashMap<(usize, usize), isize> = HashMap::new();
+    for [a, b, c] in &mesh.triangles {
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            let direction = if from < to { 1 } else { -1 };
+            *edges.entry((*from.min(to), *from.max(to))).or_default() += direction;
+        }
+    }
+    edges.values().all(|count| *count == 0)
+}
+
+#[test]
+fn single_atom() {
+    let atom = Atom::new(false, 1, "C", 1.0, 2.0, 3.0, 1.0, 0.0, "C", 0).unwrap();
+    let vdw = Element::C.atomic_radius().van_der_waals.unwrap();
+    for (surface, radius) in [
+        (SurfaceType::Accessible, vdw + geometry::PROBE_RADIUS),
+        (SurfaceType::Excluded, vdw),
+    ] {
+        let mesh = geometry::surface_mesh(&[&atom], surface, 0.25).unwrap();
+        let area = 4.0 * std::f64::consts::PI * radius * radius;
+        assert!((mesh.area() - area).abs() / area < 0.05, "{surface:?}");
+        assert!(closed(&mesh));
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+        for (vertex, normal) in mesh.vertices.iter().zip(&mesh.normals) {
+            let distance =
+                ((vertex.0 - 1.0).powi(2) + (vertex.1 - 2.0).powi(2) + (vertex.2 - 3.0).powi(2))
+                    .sqrt();
+            assert!((distance - radius).abs() < 0.1);
+            let outwards = (vertex.0 - 1.0) * normal.0
+                + (vertex.1 - 2.0) * normal.1
+                + (vertex.2 - 3.0) * normal.2;
+            assert!(outwards > 0.0);
+        }
+    }
+    assert!(geometry::surface_mesh(&[], SurfaceType::Accessible, 1.0).is_none());
+    assert!(geometry::surface_mesh(&[&atom], SurfaceType::Accessible, -1.0).is_none());
+}
+
+#[test]
+fn protein_surfaces() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let accessible = pdb.surface_mesh(SurfaceType::Accessible, 0.7).unwrap();
+    let excluded = pdb.surface_mesh(SurfaceType::Excluded, 0.7).unwrap();
+    assert!(closed(&accessible) && closed(&excluded));
+    assert!(excluded.area() < accessible.area());
+    let atoms: Vec<&Atom> = pdb.atoms().collect();
+    let sasa: f64 = geometry::sasa(&atoms).iter().sum();
+    assert!((accessible.area() - sasa).abs() / sasa < 0.2);
+}
+
+#[test]
+fn save_meshes() {
+    let atom = Atom::new(false, 1, "O", 0.0, 0.0, 0.0, 1.0, 0.0, "O", 0).unwrap();
+    let mesh = geometry::surface_mesh(&[&atom], SurfaceType::Excluded, 0.5).unwrap();
+
+    let path = temp_path("sphere.obj");
+    save_mesh(&mesh, &path).unwrap();
+    let obj = std::fs::read_to_string(&path).unwrap();
+    let count = |start: &str| obj.lines().filter(|l| l.starts_with(start)).count();
+    assert_eq!(count("v "), mesh.vertices.len());
+    assert_eq!(count("vn "), mesh.normals.len());
+    assert_eq!(count("f "), mesh.triangles.len());
+    assert!(!obj
+        .lines()
+        .any(|l| l.starts_with("f ") && l.contains(" 0//")));
+
+    let path = temp_path("sphere.ply");
+    save_mesh(&mesh, &path).unwrap();
+    let ply = std::fs::read_to_string(&path).unwrap();
+    let header = ply.lines().position(|l| l == "end_header").unwrap();
+    assert!(ply.starts_with("ply\nformat ascii 1.0\n"));
+    assert!(ply.contains(&format!("element vertex {}\n", mesh.vertices.len())));
+    assert_eq!(
+        ply.lines().count(),
+        header + 1 + mesh.vertices.len() + mesh.triangles.len()
+    );
+
+    assert!(save_mesh(&mesh, temp_path("sphere.stl")).is_err());
+}
@@ -0,0 +1,61 @@
+// Test the placement of TER records when saving PDB files
+
+mod common;
+
+use common::open;
+use pdbtbx::*;
+
+/// Save the given PDB with the given TER placement and give the lines
+fn save(pdb: &PDB, placement: TerPlacement) -> Vec<String> {
+    let mut output = Vec::new();
+    WriteOptions::new()
+        .set_level(StrictnessLevel::Medium)
+        .set_ter_placement(placement)
+        .write_raw(pdb, std::io::BufWriter::new(&mut output));
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn polymer_ends() {
+    let pdb = open("example-pdbs/1ubq.pdb");
+    let lines = save(&pdb, TerPlacement::PolymerEnds);
+    let ter: Vec<usize> = (0..lines.len())
+        .filter(|i| lines[*i].starts_with("TER"))
+        .collect();
+    assert_eq!(ter.len(), 1);
+    // Directly after the last amino acid, before the waters
+    assert!(lines[ter[0] - 1].starts_with("ATOM"));
+    assert!(lines[ter[0] + 1].starts_with("HETATM"));
+    // The serial number follows the last atom, the residue is the last amino acid
+    let fields: Vec<&str> = lines[ter[0]].split_whitespace().collect();
+    assert_eq!(fields[..3], ["TER", "1232", "GLY"]);
+
+    let lines = save(&pdb, TerPlacement::ChainEnds);
+    let ter = lines.iter().position(|l| l.starts_with("TER")).unwrap();
+    assert!(lines[ter - 1].contains("HOH"));
+}
+
+#[test]
+fn breaks() {
+    let pdb = open("example-pdbs/1yyf.pdb");
+    let count = |lines: &[String]| lines.iter().filter(|l| l.starts_with("TER")).count();
+    assert_eq!(count(&save(&pdb, TerPlacement::PolymerEnds)), 4);
+    let lines = save(&pdb, TerPlacement::PolymerEndsAndBreaks);
+    assert_eq!(count(&lines), 6);
+    let ter = lines.iter().position(|l| l.starts_with("TER")).unwrap();
+    let fields: Vec<&str> = lines[ter].split_whitespace().collect();
+    assert_eq!(fields[..3], ["TER", "1399", "GLU"]);
+    assert!(lines[ter + 1].contains("LYS"));
+
+    // The chains are kept intact when reading the file back
+    let (read, _) = ReadOptions::new()
+        .set_level(StrictnessLevel::Loose)
+        .read_lines(lines)
+        .unwrap();
+    assert_eq!(read.chain_count(), pdb.chain_count());
+    assert_eq!(read.atom_count(), pdb.atom_count());
+}
@@ -0,0 +1,21 @@
+// Test reading files with text which is not valid UTF-8.
+
+use pdbtbx::*;
+
+#[test]
+fn latin1_remark() {
+    let mut input = std::fs::read("example-pdbs/1ubq.pdb").unwrap();
+    let position = input.windows(11).position(|w| w == b"REFINEMENT.").unwrap();
+    input.splice(position + 11..position + 11, b" M\xdcLLER".iter().copied());
+    let (pdb, errors) = ReadOptions::new()
+        .set_format(Format::Pdb)
+        .set_level(StrictnessLevel::Medium)
+        .read_raw(input.as_slice())
+        .unwrap();
+    assert_eq!(pdb.atom_count(), 1271);
+    let warning = errors
+        .iter()
+        .find(|e| e.short_description() == "Invalid text encoding")
+        .unwrap();
+    assert_eq!(warning.level(), ErrorLevel::GeneralWarning);
+}
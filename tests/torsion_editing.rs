@@ -0,0 +1,68 @@
+// Test rotating groups of atoms around bonds to change dihedral angles
+
+use pdbtbx::build::{peptide, PeptideConformation};
+use pdbtbx::*;
+
+/// The difference between two angles in degrees, in the range -180 to 180
+fn difference(a: f64, b: f64) -> f64 {
+    (a - b + 540.0) % 360.0 - 180.0
+}
+
+#[test]
+fn backbone() {
+    let mut pdb = peptide("AKPA", PeptideConformation::Extended).unwrap();
+    let torsions = |pdb: &PDB| -> Vec<(f64, f64)> {
+        pdb.chain(0)
+            .unwrap()
+            .backbone_torsions()
+            .iter()
+            .map(|(_, t)| (t.phi.unwrap_or(0.0), t.psi.unwrap_or(0.0)))
+            .collect()
+    };
+    let before = torsions(&pdb);
+    let ca_c = pdb.distance("A/2/CA", "A/2/C").unwrap();
+
+    // Change psi of the second residue, everything after it moves
+    let moved = pdb
+        .rotate_dihedral("A/2/CA", "A/2/C", -90.0, Search::Known(true))
+        .unwrap();
+    assert_eq!(moved, 2 + 7 + 6);
+    let after = torsions(&pdb);
+    assert!(difference(after[1].1, before[1].1 + -90.0).abs() < 1e-6);
+    for (index, (old, new)) in before.iter().zip(&after).enumerate() {
+        assert!(difference(old.0, new.0).abs() < 1e-6);
+        if index != 1 {
+            assert!(difference(old.1, new.1).abs() < 1e-6);
+        }
+    }
+    assert!((pdb.distance("A/2/CA", "A/2/C").unwrap() - ca_c).abs() < 1e-9);
+
+    // Only atoms in the search are moved: CA, the side chain, C, and O of the second residue
+    let moved = pdb
+        .rotate_dihedral(
+            "A/2/N",
+            "A/2/CA",
+            30.0,
+            Search::Single(Term::ResidueSerialNumber(2)),
+        )
+        .unwrap();
+    assert_eq!(moved, 8);
+}
+
+#[test]
+fn invalid() {
+    let mut pdb = peptide("AKPA", PeptideConformation::Extended).unwrap();
+    let all = || Search::Known(true);
+    // The proline side chain forms a ring with its backbone
+    assert!(pdb.rotate_dihedral("A/3/N", "A/3/CA", 60.0, all()).is_err());
+    assert!(pdb.rotate_dihedral("A/1/N", "A/1/C", 60.0, all()).is_err());
+    assert!(pdb.rotate_dihedral("A/1/N", "A/9/C", 60.0, all()).is_err());
+    assert!(pdb
+        .rotate_dihedral(
+            "A/1/N",
+            "A/1/CA",
+            60.0,
+            Search::Single(Term::ResidueSerialNumber(2))
+        )
+        .is_err());
+}
@@ -0,0 +1,71 @@
+// Test the handling of text after column 80 of atom records.
+
+use pdbtbx::*;
+
+const INPUT: &str =
+    "ATOM      1  CA  GLY A   1      26.266  25.413   2.842  1.00 20.00           C   SEG1
+ATOM      2  CA  SER A   2      27.266  25.413   2.842  1.00 20.00           C1+ 
+ATOM      3  CA  HIS A   3      28.266  25.413   2.842  1.00 20.00           C12.4213
+ATOM      4  CA  LYS A   4      29.266  25.413   2.842  1.00 20.00           C1+EXTRA
+";
+
+fn read(
+    mode: Option<TrailingColumns>,
+    level: StrictnessLevel,
+) -> Result<(PDB, Vec<PDBError>), Vec<PDBError>> {
+    let mut options = ReadOptions::new();
+    options.set_format(Format::Pdb).set_level(level);
+    if let Some(mode) = mode {
+        options.set_trailing_columns(mode);
+    }
+    options.read_raw(INPUT.as_bytes())
+}
+
+#[test]
+fn ignore_by_default() {
+    let (pdb, errors) = read(None, StrictnessLevel::Medium).unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(pdb.atom_count(), 4);
+    assert!(pdb.atoms().all(|a| a.trailing_columns().is_none()));
+}
+
+#[test]
+fn warn() {
+    let errors = read(Some(TrailingColumns::Warn), StrictnessLevel::Medium).unwrap_err();
+    assert_eq!(
+        errors
+            .iter()
+            .filter(|e| e.short_description() == "Trailing columns")
+            .count(),
+        3
+    );
+    let (pdb, errors) = read(Some(TrailingColumns::Warn), StrictnessLevel::Loose).unwrap();
+    assert_eq!(pdb.atom_count(), 4);
+    assert!(errors
+        .iter()
+        .all(|e| e.short_description() == "Trailing columns"));
+    assert!(pdb.atoms().all(|a| a.trailing_columns().is_none()));
+}
+
+#[test]
+fn ignore() {
+    let (pdb, errors) = read(Some(TrailingColumns::Ignore), StrictnessLevel::Medium).unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+    assert!(pdb.atoms().all(|a| a.trailing_columns().is_none()));
+    assert_eq!(pdb.atom(1).unwrap().charge(), 1);
+    assert_eq!(pdb.atom(2).unwrap().charge(), 0);
+    // A valid charge directly followed by trailing text is kept
+    assert_eq!(pdb.atom(3).unwrap().charge(), 1);
+}
+
+#[test]
+fn capture() {
+    let (pdb, errors) = read(Some(TrailingColumns::Capture), StrictnessLevel::Medium).unwrap();
+    assert!(errors.is_empty(), "{errors:?}");
+    let trailing: Vec<_> = pdb.atoms().map(|a| a.trailing_columns()).collect();
+    assert_eq!(
+        trailing,
+        vec![Some("SEG1"), None, Some("12.4213"), Some("EXTRA")]
+    );
+    assert_eq!(pdb.atom(3).unwrap().charge(), 1);
+}